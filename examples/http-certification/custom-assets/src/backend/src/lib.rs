@@ -48,6 +48,7 @@ impl Into<HttpResponse> for HttpAssetResponse<'_> {
             headers: self.headers,
             body: self.body.to_vec(),
             upgrade: None,
+            trailers: None,
         }
     }
 }
@@ -245,8 +246,12 @@ fn certify_asset_response(
         };
 
         // certify the response
-        let certification =
-            HttpCertification::response_only(cel_expr_def, &response.clone().into(), None);
+        let certification = HttpCertification::response_only(
+            cel_expr_def,
+            Into::<HttpResponse>::into(response.clone()),
+            None,
+        )
+        .unwrap();
 
         RESPONSES.with_borrow_mut(|responses| {
             // store the response for later retrieval
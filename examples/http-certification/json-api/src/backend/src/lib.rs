@@ -126,6 +126,7 @@ fn certify_list_todos_response() {
         headers: vec![("content-type".to_string(), "application/json".to_string())],
         body,
         upgrade: None,
+        trailers: None,
     };
 
     certify_response(response, &TODOS_TREE_PATH, TODOS_PATH.to_string());
@@ -137,6 +138,7 @@ fn certify_not_found_response() {
         headers: vec![("content-type".to_string(), "text/plain".to_string())],
         body: b"Not found".to_vec(),
         upgrade: None,
+        trailers: None,
     };
 
     certify_response(response, &NOT_FOUND_TREE_PATH, NOT_FOUND_PATH.to_string());
@@ -159,7 +161,7 @@ fn certify_response(
         ));
 
         // create the certification for this response and CEL expression pair
-        HttpCertification::response_only(cel_expr_def, &response, None)
+        HttpCertification::response_only(cel_expr_def, &response, None).unwrap()
     });
 
     RESPONSES.with_borrow_mut(|responses| {
@@ -235,6 +237,7 @@ fn create_todo_item_handler(req: &HttpRequest) -> HttpResponse {
         headers: vec![("content-type".to_string(), "application/json".to_string())],
         body: json_encode(&todo_item),
         upgrade: None,
+        trailers: None,
     }
 }
 
@@ -262,12 +265,7 @@ fn not_found_handler(req: &HttpRequest) -> HttpResponse {
 }
 
 fn upgrade_to_update_call_handler() -> HttpResponse {
-    HttpResponse {
-        status_code: 200,
-        headers: vec![],
-        body: vec![],
-        upgrade: Some(true),
-    }
+    HttpResponse::upgrade_to_update_call()
 }
 
 const IC_CERTIFICATE_HEADER: &str = "IC-Certificate";
@@ -2,7 +2,7 @@ import {
   verifyRequestResponsePair,
   Request,
   Response,
-  ResponseVerificationError,
+  isResponseVerificationError,
   ResponseVerificationErrorCode,
   getMinVerificationVersion,
 } from '@dfinity/response-verification';
@@ -64,7 +64,7 @@ try {
 } catch (error) {
   console.log('Error', error);
 
-  if (error instanceof ResponseVerificationError) {
+  if (isResponseVerificationError(error)) {
     switch (error.code) {
       case ResponseVerificationErrorCode.CborDecodingFailed:
         console.log(`Error parsing cbor: ${error.message}`);
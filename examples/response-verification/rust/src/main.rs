@@ -29,6 +29,7 @@ fn main() {
         headers: http_response.headers,
         body: http_response.body,
         upgrade: None,
+        trailers: None,
     };
     println!("***Response***");
     println!("Body: {:?}", response.body);
@@ -2,7 +2,7 @@ import initResponseVerification, {
   verifyRequestResponsePair,
   Request,
   Response,
-  ResponseVerificationError,
+  isResponseVerificationError,
   ResponseVerificationErrorCode,
   getMinVerificationVersion,
 } from '@dfinity/response-verification';
@@ -67,7 +67,7 @@ window.addEventListener('load', async () => {
   } catch (error) {
     console.log('Error', error);
 
-    if (error instanceof ResponseVerificationError) {
+    if (isResponseVerificationError(error)) {
       switch (error.code) {
         case ResponseVerificationErrorCode.CborDecodingFailed:
           console.log(`Error parsing cbor: ${error.message}`);
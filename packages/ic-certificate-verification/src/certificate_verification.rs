@@ -40,20 +40,59 @@ pub fn principal_is_within_ranges(
         .any(|r| principal >= &r.0 && principal <= &r.1)
 }
 
+/// The default maximum number of delegations that a certificate may have. The Internet Computer
+/// currently only issues certificates with at most a single delegation, from the NNS subnet to the
+/// subnet that hosts the canister.
+pub const DEFAULT_MAX_DELEGATION_CHAIN_LENGTH: usize = 1;
+
 pub trait VerifyCertificate<T> {
+    /// Verifies the certificate, allowing at most [DEFAULT_MAX_DELEGATION_CHAIN_LENGTH] delegations.
     fn verify(
         &self,
         canister_id: &[u8],
         root_public_key: &[u8],
+    ) -> CertificateVerificationResult<T> {
+        self.verify_with_max_delegation_chain_length(
+            canister_id,
+            root_public_key,
+            DEFAULT_MAX_DELEGATION_CHAIN_LENGTH,
+        )
+    }
+
+    /// Verifies the certificate, allowing at most `max_delegation_chain_length` delegations.
+    ///
+    /// Longer chains are rejected with
+    /// [CertificateHasTooManyDelegations](CertificateVerificationError::CertificateHasTooManyDelegations)
+    /// before any of their signatures are verified, which bounds the work that a malicious
+    /// certificate can cause.
+    fn verify_with_max_delegation_chain_length(
+        &self,
+        canister_id: &[u8],
+        root_public_key: &[u8],
+        max_delegation_chain_length: usize,
     ) -> CertificateVerificationResult<T>;
 }
 
 impl VerifyCertificate<()> for Certificate {
-    fn verify(
+    fn verify_with_max_delegation_chain_length(
         &self,
         canister_id: &[u8],
         root_public_key: &[u8],
+        max_delegation_chain_length: usize,
     ) -> CertificateVerificationResult<()> {
+        let der_key = match &self.delegation {
+            Some(_) if max_delegation_chain_length == 0 => {
+                return Err(CertificateVerificationError::CertificateHasTooManyDelegations);
+            }
+            Some(delegation) => delegation.verify_with_max_delegation_chain_length(
+                canister_id,
+                root_public_key,
+                max_delegation_chain_length - 1,
+            )?,
+            _ => root_public_key.into(),
+        };
+        let pk = extract_der(der_key)?;
+
         let sig = self.signature.as_slice();
 
         let root_hash = self.tree.digest();
@@ -61,27 +100,23 @@ impl VerifyCertificate<()> for Certificate {
         msg.extend_from_slice(IC_STATE_ROOT_DOMAIN_SEPARATOR);
         msg.extend_from_slice(&root_hash);
 
-        let der_key = match &self.delegation {
-            Some(delegation) => delegation.verify(canister_id, root_public_key)?,
-            _ => root_public_key.into(),
-        };
-        let pk = extract_der(der_key)?;
-
         verify_signature(&pk, sig, &msg)
     }
 }
 
 impl VerifyCertificate<Vec<u8>> for Delegation {
-    fn verify(
+    fn verify_with_max_delegation_chain_length(
         &self,
         canister_id: &[u8],
         root_public_key: &[u8],
+        max_delegation_chain_length: usize,
     ) -> CertificateVerificationResult<Vec<u8>> {
         let cert: Certificate = Certificate::from_cbor(&self.certificate)?;
-        if cert.delegation.is_some() {
-            return Err(CertificateVerificationError::CertificateHasTooManyDelegations);
-        }
-        cert.verify(canister_id, root_public_key)?;
+        cert.verify_with_max_delegation_chain_length(
+            canister_id,
+            root_public_key,
+            max_delegation_chain_length,
+        )?;
 
         let canister_range_path = [
             "subnet".as_bytes(),
@@ -123,11 +158,8 @@ impl VerifyCertificate<Vec<u8>> for Delegation {
     }
 }
 
-pub fn validate_certificate_time(
-    certificate: &Certificate,
-    current_time_ns: &u128,
-    allowed_certificate_time_offset: &u128,
-) -> CertificateVerificationResult {
+/// Decodes the time of the certificate, in nanoseconds since the UNIX epoch.
+pub fn get_certificate_time(certificate: &Certificate) -> CertificateVerificationResult<u128> {
     let time_path = ["time".as_bytes()];
 
     let LookupResult::Found(mut encoded_certificate_time) =
@@ -143,6 +175,16 @@ pub fn validate_certificate_time(
             timestamp: encoded_certificate_time.to_vec(),
         }
     })? as u128;
+
+    Ok(certificate_time)
+}
+
+pub fn validate_certificate_time(
+    certificate: &Certificate,
+    current_time_ns: &u128,
+    allowed_certificate_time_offset: &u128,
+) -> CertificateVerificationResult {
+    let certificate_time = get_certificate_time(certificate)?;
     let max_certificate_time = current_time_ns + allowed_certificate_time_offset;
     let min_certificate_time = current_time_ns - allowed_certificate_time_offset;
 
@@ -227,6 +269,54 @@ mod tests {
         ))
     }
 
+    #[test]
+    fn verify_certificate_with_delegation() {
+        let canister_id = create_canister_id(CANISTER_ID);
+        let CertificateData {
+            cbor_encoded_certificate,
+            certificate: _,
+            root_key,
+        } = CertificateBuilder::new(
+            &canister_id.to_string(),
+            &AssetTree::new().get_certified_data(),
+        )
+        .unwrap()
+        .with_delegation(123, vec![(0, 9)])
+        .build()
+        .unwrap();
+
+        let certificate = Certificate::from_cbor(&cbor_encoded_certificate).unwrap();
+
+        certificate.verify(canister_id.as_ref(), &root_key).unwrap();
+    }
+
+    #[test]
+    fn verify_certificate_with_delegation_exceeding_max_chain_length_should_fail() {
+        let canister_id = create_canister_id(CANISTER_ID);
+        let CertificateData {
+            cbor_encoded_certificate,
+            certificate: _,
+            root_key,
+        } = CertificateBuilder::new(
+            &canister_id.to_string(),
+            &AssetTree::new().get_certified_data(),
+        )
+        .unwrap()
+        .with_delegation(123, vec![(0, 9)])
+        .build()
+        .unwrap();
+
+        let certificate = Certificate::from_cbor(&cbor_encoded_certificate).unwrap();
+
+        let result =
+            certificate.verify_with_max_delegation_chain_length(canister_id.as_ref(), &root_key, 0);
+
+        assert!(matches!(
+            result.err(),
+            Some(CertificateVerificationError::CertificateHasTooManyDelegations),
+        ))
+    }
+
     #[test]
     fn verify_certificate_should_fail() {
         let canister_id = create_canister_id(CANISTER_ID);
@@ -253,6 +343,32 @@ mod tests {
         ))
     }
 
+    #[test]
+    fn get_certificate_time_decodes_time() {
+        let canister_id = create_canister_id(CANISTER_ID);
+        let current_timestamp = get_current_timestamp();
+
+        let CertificateData {
+            cbor_encoded_certificate,
+            certificate: _,
+            root_key: _,
+        } = CertificateBuilder::new(
+            &canister_id.to_string(),
+            &AssetTree::new().get_certified_data(),
+        )
+        .unwrap()
+        .with_time(current_timestamp)
+        .build()
+        .unwrap();
+
+        let certificate = Certificate::from_cbor(&cbor_encoded_certificate).unwrap();
+
+        assert_eq!(
+            get_certificate_time(&certificate).unwrap(),
+            current_timestamp
+        );
+    }
+
     #[test]
     fn validate_certificate_time_with_suitable_time() {
         let canister_id = create_canister_id(CANISTER_ID);
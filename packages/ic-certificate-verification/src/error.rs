@@ -88,7 +88,7 @@ pub enum CertificateVerificationError {
     #[error("CBOR decoding failed")]
     CborDecodingFailed(#[from] CborError),
 
-    /// The certificate contained more than one delegation.
-    #[error("The certificate contained more than one delegation")]
+    /// The certificate's chain of delegations was longer than the maximum allowed length.
+    #[error("The certificate contained more delegations than allowed")]
     CertificateHasTooManyDelegations,
 }
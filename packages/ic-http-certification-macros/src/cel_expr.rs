@@ -0,0 +1,243 @@
+use ic_http_certification::{
+    cel::{
+        parse_cel_expression, validate_cel_expression, CelExpression, DefaultBodyCertification,
+        DefaultCelExpression, DefaultFullCelExpression, DefaultRequestCertification,
+        DefaultResponseOnlyCelExpression, DefaultStatusCodeCertification,
+    },
+    DefaultResponseCertification,
+};
+use ic_representation_independent_hash::hash;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+pub(crate) fn expand_cel_expr(cel_expr: &LitStr) -> syn::Result<TokenStream> {
+    let cel_expr_str = cel_expr.value();
+    let parsed_cel_expr = parse_cel_expression(&cel_expr_str)
+        .map_err(|err| syn::Error::new(cel_expr.span(), err.to_string()))?;
+    // the parser ignores unknown properties, which usually indicate a typo, such as a misspelled
+    // body certification option that would otherwise silently be left out of certification
+    validate_cel_expression(&cel_expr_str)
+        .map_err(|err| syn::Error::new(cel_expr.span(), err.to_string()))?;
+
+    let expression_str = parsed_cel_expr.to_string();
+    let hash = hash(expression_str.as_bytes());
+
+    let CelExpression::Default(default_cel_expr) = parsed_cel_expr;
+    let expression = default_cel_expr_tokens(&default_cel_expr);
+
+    Ok(quote! {
+        ::ic_http_certification::cel::PrecomputedCelExpression {
+            expression: #expression,
+            expression_str: #expression_str,
+            hash: [#(#hash),*],
+        }
+    })
+}
+
+fn default_cel_expr_tokens(cel_expr: &DefaultCelExpression) -> TokenStream {
+    match cel_expr {
+        DefaultCelExpression::Full(DefaultFullCelExpression {
+            request,
+            response,
+            body,
+            status,
+            allowed_methods,
+        }) => {
+            let request = request_certification_tokens(request);
+            let response = response_certification_tokens(response);
+            let body = body_certification_tokens(body);
+            let status = status_certification_tokens(status);
+            let allowed_methods = str_slice_tokens(allowed_methods);
+
+            quote! {
+                ::ic_http_certification::cel::DefaultFullCelExpression {
+                    request: #request,
+                    response: #response,
+                    body: #body,
+                    status: #status,
+                    allowed_methods: #allowed_methods,
+                }
+            }
+        }
+        DefaultCelExpression::ResponseOnly(DefaultResponseOnlyCelExpression {
+            response,
+            body,
+            status,
+            allowed_methods,
+        }) => {
+            let response = response_certification_tokens(response);
+            let body = body_certification_tokens(body);
+            let status = status_certification_tokens(status);
+            let allowed_methods = str_slice_tokens(allowed_methods);
+
+            quote! {
+                ::ic_http_certification::cel::DefaultResponseOnlyCelExpression {
+                    response: #response,
+                    body: #body,
+                    status: #status,
+                    allowed_methods: #allowed_methods,
+                }
+            }
+        }
+        DefaultCelExpression::Skip => {
+            quote! { ::ic_http_certification::cel::DefaultCelExpression::Skip }
+        }
+    }
+}
+
+fn request_certification_tokens(
+    request_certification: &DefaultRequestCertification,
+) -> TokenStream {
+    let headers = str_slice_tokens(&request_certification.headers);
+    let query_parameters = str_slice_tokens(&request_certification.query_parameters);
+    let multipart_form_fields = str_slice_tokens(&request_certification.multipart_form_fields);
+    let canonical_multipart_body = request_certification.canonical_multipart_body;
+
+    quote! {
+        ::ic_http_certification::cel::DefaultRequestCertification {
+            headers: #headers,
+            query_parameters: #query_parameters,
+            multipart_form_fields: #multipart_form_fields,
+            canonical_multipart_body: #canonical_multipart_body,
+        }
+    }
+}
+
+fn response_certification_tokens(
+    response_certification: &DefaultResponseCertification,
+) -> TokenStream {
+    match response_certification {
+        DefaultResponseCertification::CertifiedResponseHeaders(headers) => {
+            let headers = str_slice_tokens(headers);
+
+            quote! {
+                ::ic_http_certification::cel::DefaultResponseCertification::CertifiedResponseHeaders(#headers)
+            }
+        }
+        DefaultResponseCertification::ResponseHeaderExclusions(headers) => {
+            let headers = str_slice_tokens(headers);
+
+            quote! {
+                ::ic_http_certification::cel::DefaultResponseCertification::ResponseHeaderExclusions(#headers)
+            }
+        }
+        DefaultResponseCertification::CertifiedResponseHeadersWithExclusions {
+            headers,
+            exclusions,
+        } => {
+            let headers = str_slice_tokens(headers);
+            let exclusions = str_slice_tokens(exclusions);
+
+            quote! {
+                ::ic_http_certification::cel::DefaultResponseCertification::CertifiedResponseHeadersWithExclusions {
+                    headers: #headers,
+                    exclusions: #exclusions,
+                }
+            }
+        }
+        // the enum is non-exhaustive, so variants that are added later must be mapped above
+        // before they can be used in a precomputed expression
+        response_certification => syn::Error::new(
+            proc_macro2::Span::call_site(),
+            format!("unsupported response certification: {response_certification:?}"),
+        )
+        .to_compile_error(),
+    }
+}
+
+fn body_certification_tokens(body_certification: &DefaultBodyCertification) -> TokenStream {
+    match body_certification {
+        DefaultBodyCertification::AsServed => {
+            quote! { ::ic_http_certification::cel::DefaultBodyCertification::AsServed }
+        }
+        DefaultBodyCertification::Decoded => {
+            quote! { ::ic_http_certification::cel::DefaultBodyCertification::Decoded }
+        }
+        DefaultBodyCertification::Fragmented => {
+            quote! { ::ic_http_certification::cel::DefaultBodyCertification::Fragmented }
+        }
+    }
+}
+
+fn status_certification_tokens(
+    status_certification: &DefaultStatusCodeCertification,
+) -> TokenStream {
+    match status_certification {
+        DefaultStatusCodeCertification::Exact => {
+            quote! { ::ic_http_certification::cel::DefaultStatusCodeCertification::Exact }
+        }
+        DefaultStatusCodeCertification::Class => {
+            quote! { ::ic_http_certification::cel::DefaultStatusCodeCertification::Class }
+        }
+    }
+}
+
+fn str_slice_tokens(items: &[&str]) -> TokenStream {
+    quote! { ::std::borrow::Cow::Borrowed(&[#(#items),*]) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn expands_skip_certification() {
+        let cel_expr: LitStr = parse_quote! {
+            "default_certification ( ValidationArgs { no_certification: Empty { } } )"
+        };
+        let expression_str = "default_certification(ValidationArgs{no_certification:Empty{}})";
+        let hash = hash(expression_str.as_bytes());
+
+        let result = expand_cel_expr(&cel_expr).unwrap();
+        let expected = quote! {
+            ::ic_http_certification::cel::PrecomputedCelExpression {
+                expression: ::ic_http_certification::cel::DefaultCelExpression::Skip,
+                expression_str: #expression_str,
+                hash: [#(#hash),*],
+            }
+        };
+
+        assert_eq!(result.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn rejects_invalid_cel_expression() {
+        let cel_expr: LitStr = parse_quote! {
+            "default_certification ( ValidationArgs { } )"
+        };
+
+        let result = expand_cel_expr(&cel_expr).unwrap_err();
+
+        assert_eq!(
+            result.to_string(),
+            "The ValidationArgs parameter must specify at least one of the no_certification or certification properties"
+        );
+    }
+
+    #[test]
+    fn rejects_cel_expression_with_syntax_error() {
+        let cel_expr: LitStr = parse_quote! {
+            "default_certification ( ValidationArgs { no_certification: Empty { } )"
+        };
+
+        let result = expand_cel_expr(&cel_expr);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_cel_expression_with_unknown_property() {
+        let cel_expr: LitStr = parse_quote! {
+            r#"default_certification(ValidationArgs{certification:Certification{no_request_certification:Empty{},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:[]},decoded_bdy_certification:Empty{}}}})"#
+        };
+
+        let result = expand_cel_expr(&cel_expr).unwrap_err();
+
+        assert_eq!(
+            result.to_string(),
+            r#"Unknown property "decoded_bdy_certification" of object "ResponseCertification" at byte 201"#
+        );
+    }
+}
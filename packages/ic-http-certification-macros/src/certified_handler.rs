@@ -0,0 +1,219 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{meta::ParseNestedMeta, Expr, FnArg, ItemFn, LitStr, Path, Type};
+
+enum HandlerPath {
+    Exact(LitStr),
+    Wildcard(LitStr),
+}
+
+#[derive(Default)]
+pub(crate) struct CertifiedHandlerArgs {
+    tree: Option<Path>,
+    path: Option<HandlerPath>,
+    method: Option<LitStr>,
+    cel: Option<Expr>,
+    data_certificate: Option<Path>,
+}
+
+impl CertifiedHandlerArgs {
+    pub(crate) fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("tree") {
+            self.tree = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("path") || meta.path.is_ident("wildcard") {
+            if self.path.is_some() {
+                return Err(meta.error("only one of `path` or `wildcard` can be specified"));
+            }
+
+            let path = meta.value()?.parse()?;
+            self.path = Some(if meta.path.is_ident("path") {
+                HandlerPath::Exact(path)
+            } else {
+                HandlerPath::Wildcard(path)
+            });
+        } else if meta.path.is_ident("method") {
+            self.method = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("cel") {
+            self.cel = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("data_certificate") {
+            self.data_certificate = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("unsupported certified_handler attribute"));
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn expand_certified_handler(
+    args: CertifiedHandlerArgs,
+    handler: ItemFn,
+) -> syn::Result<TokenStream> {
+    let missing_arg = |name: &str| {
+        syn::Error::new(
+            handler.sig.ident.span(),
+            format!("missing `{name}` attribute for certified_handler"),
+        )
+    };
+
+    let tree = args.tree.ok_or_else(|| missing_arg("tree"))?;
+    let cel = args.cel.ok_or_else(|| missing_arg("cel"))?;
+    let data_certificate = args
+        .data_certificate
+        .ok_or_else(|| missing_arg("data_certificate"))?;
+    let path = match args.path.ok_or_else(|| missing_arg("path"))? {
+        HandlerPath::Exact(path) => {
+            quote! { ::ic_http_certification::HttpCertificationPath::exact(#path) }
+        }
+        HandlerPath::Wildcard(path) => {
+            quote! { ::ic_http_certification::HttpCertificationPath::wildcard(#path) }
+        }
+    };
+
+    let method_guard = args.method.map(|method| {
+        quote! {
+            if !request.method.eq_ignore_ascii_case(#method) {
+                return Err(::ic_http_certification::HttpCertificationError::UnexpectedRequestMethod {
+                    expected: #method.to_string(),
+                    found: request.method.clone(),
+                });
+            }
+        }
+    });
+
+    let mut inputs = handler.sig.inputs.iter();
+    let request_arg = match (inputs.next(), inputs.next()) {
+        (Some(FnArg::Typed(request_arg)), None) => request_arg,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &handler.sig.inputs,
+                "certified_handler functions must take a single HttpRequest argument",
+            ))
+        }
+    };
+    let request = match request_arg.ty.as_ref() {
+        Type::Reference(_) => quote! { &request },
+        _ => quote! { request.clone() },
+    };
+
+    let vis = &handler.vis;
+    let name = &handler.sig.ident;
+    let inner_name = format_ident!("__{}_handler", name);
+    let mut inner_handler = handler.clone();
+    inner_handler.sig.ident = inner_name.clone();
+    inner_handler.vis = syn::Visibility::Inherited;
+
+    Ok(quote! {
+        #vis fn #name(
+            request: ::ic_http_certification::HttpRequest,
+        ) -> ::ic_http_certification::HttpCertificationResult<::ic_http_certification::HttpResponse> {
+            #inner_handler
+
+            #method_guard
+
+            let mut response = #inner_name(#request);
+            response.headers_mut().set(
+                ::ic_http_certification::CERTIFICATE_EXPRESSION_HEADER_NAME,
+                #cel.expression_str,
+            );
+
+            let certification = #cel.certification(&request, &response)?;
+            let path = #path;
+            let entry = ::ic_http_certification::HttpCertificationTreeEntry::new(&path, certification);
+            let request_path = request.canonical_path()?;
+            let data_certificate = #data_certificate()
+                .ok_or(::ic_http_certification::HttpCertificationError::DataCertificateNotAvailable)?;
+
+            let cel_expr = ::ic_http_certification::DefaultCelExpression::from(#cel.expression.clone());
+
+            #tree.with(|tree| {
+                tree.borrow()
+                    .certify_response(&entry, &request_path, &cel_expr, Some(&request), response, None)?
+                    .serve(&data_certificate)
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::{meta, parse::Parser, parse_quote};
+
+    fn parse_args(args: TokenStream) -> syn::Result<CertifiedHandlerArgs> {
+        let mut handler_args = CertifiedHandlerArgs::default();
+        meta::parser(|meta| handler_args.parse(meta)).parse2(args)?;
+
+        Ok(handler_args)
+    }
+
+    #[test]
+    fn rejects_missing_attribute() {
+        let args = parse_args(quote! {
+            tree = HTTP_TREE,
+            path = "/",
+            data_certificate = ic_cdk::api::data_certificate,
+        })
+        .unwrap();
+        let handler: ItemFn = parse_quote! {
+            fn index(request: HttpRequest) -> HttpResponse {
+                unimplemented!()
+            }
+        };
+
+        let result = expand_certified_handler(args, handler).err().unwrap();
+
+        assert_eq!(
+            result.to_string(),
+            "missing `cel` attribute for certified_handler"
+        );
+    }
+
+    #[test]
+    fn rejects_path_and_wildcard() {
+        let result = parse_args(quote! {
+            path = "/",
+            wildcard = "/",
+        })
+        .err()
+        .unwrap();
+
+        assert_eq!(
+            result.to_string(),
+            "only one of `path` or `wildcard` can be specified"
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_attribute() {
+        let result = parse_args(quote! { status = 200 }).err().unwrap();
+
+        assert_eq!(
+            result.to_string(),
+            "unsupported certified_handler attribute"
+        );
+    }
+
+    #[test]
+    fn rejects_handler_with_multiple_arguments() {
+        let args = parse_args(quote! {
+            tree = HTTP_TREE,
+            path = "/",
+            cel = CEL_EXPR,
+            data_certificate = ic_cdk::api::data_certificate,
+        })
+        .unwrap();
+        let handler: ItemFn = parse_quote! {
+            fn index(request: HttpRequest, extra: u8) -> HttpResponse {
+                unimplemented!()
+            }
+        };
+
+        let result = expand_certified_handler(args, handler).err().unwrap();
+
+        assert_eq!(
+            result.to_string(),
+            "certified_handler functions must take a single HttpRequest argument"
+        );
+    }
+}
@@ -0,0 +1,632 @@
+use crate::cel_expr::expand_cel_expr;
+use ic_http_certification::{DefaultCelBuilder, DefaultResponseCertification};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, LitStr, Path, Token, Visibility,
+};
+
+const JSON_CONTENT_TYPE: &str = "application/json";
+const NOT_FOUND_BODY: &str = r#"{"error":"not found"}"#;
+
+enum RouteProfile {
+    ResponseOnly,
+    Update,
+    Skip,
+}
+
+struct Route {
+    method: Ident,
+    path: LitStr,
+    handler: Path,
+    profile: RouteProfile,
+}
+
+pub(crate) struct CertifiedJsonApi {
+    vis: Visibility,
+    name: Ident,
+    data_certificate: Path,
+    set_certified_data: Path,
+    canister_methods: bool,
+    routes: Vec<Route>,
+}
+
+impl Parse for CertifiedJsonApi {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        input.parse::<Token![mod]>()?;
+        let name: Ident = input.parse()?;
+        input.parse::<Token![;]>()?;
+
+        let mut data_certificate = None;
+        let mut set_certified_data = None;
+        let mut canister_methods = false;
+        let mut routes = vec![];
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+
+            if ident == "data_certificate" || ident == "set_certified_data" {
+                input.parse::<Token![=]>()?;
+                let path = Some(input.parse()?);
+                if ident == "data_certificate" {
+                    data_certificate = path;
+                } else {
+                    set_certified_data = path;
+                }
+            } else if ident == "canister_methods" {
+                canister_methods = true;
+            } else {
+                let route = parse_route(ident, input)?;
+                check_duplicate_route(&routes, &route)?;
+                routes.push(route);
+            }
+
+            input.parse::<Token![;]>()?;
+        }
+
+        let missing_setting = |setting: &str| {
+            syn::Error::new(
+                name.span(),
+                format!("missing `{setting}` setting for certified_json_api"),
+            )
+        };
+
+        Ok(Self {
+            data_certificate: data_certificate
+                .ok_or_else(|| missing_setting("data_certificate"))?,
+            set_certified_data: set_certified_data
+                .ok_or_else(|| missing_setting("set_certified_data"))?,
+            vis,
+            name,
+            canister_methods,
+            routes,
+        })
+    }
+}
+
+fn parse_route(method: Ident, input: ParseStream) -> syn::Result<Route> {
+    if !matches!(
+        method.to_string().as_str(),
+        "GET" | "HEAD" | "POST" | "PUT" | "PATCH" | "DELETE"
+    ) {
+        return Err(syn::Error::new(
+            method.span(),
+            "expected a setting or an HTTP method, such as `GET` or `POST`",
+        ));
+    }
+
+    let path: LitStr = input.parse()?;
+    if !path.value().starts_with('/') {
+        return Err(syn::Error::new(
+            path.span(),
+            "route paths must start with `/`",
+        ));
+    }
+
+    input.parse::<Token![=>]>()?;
+    let handler = input.parse()?;
+    input.parse::<Token![:]>()?;
+
+    let profile: Ident = input.parse()?;
+    let profile =
+        match profile.to_string().as_str() {
+            "response_only" => RouteProfile::ResponseOnly,
+            "update" => RouteProfile::Update,
+            "skip" => RouteProfile::Skip,
+            _ => return Err(syn::Error::new(
+                profile.span(),
+                "expected one of the `response_only`, `update` or `skip` certification profiles",
+            )),
+        };
+
+    Ok(Route {
+        method,
+        path,
+        handler,
+        profile,
+    })
+}
+
+/// Certified responses are looked up by path only, so each path can have at most one certified
+/// (`response_only` or `skip`) route, in addition to any `update` routes with other methods.
+fn check_duplicate_route(routes: &[Route], route: &Route) -> syn::Result<()> {
+    let is_certified = |route: &Route| !matches!(route.profile, RouteProfile::Update);
+
+    for existing_route in routes
+        .iter()
+        .filter(|existing_route| existing_route.path.value() == route.path.value())
+    {
+        if existing_route.method == route.method {
+            return Err(syn::Error::new(
+                route.path.span(),
+                format!("duplicate route `{} {}`", route.method, route.path.value()),
+            ));
+        }
+
+        if is_certified(existing_route) && is_certified(route) {
+            return Err(syn::Error::new(
+                route.path.span(),
+                format!(
+                    "`{}` already has a certified route, only one `response_only` or `skip` route is allowed per path",
+                    route.path.value()
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn expand_certified_json_api(api: CertifiedJsonApi) -> syn::Result<TokenStream> {
+    let CertifiedJsonApi {
+        vis,
+        name,
+        data_certificate,
+        set_certified_data,
+        canister_methods,
+        routes,
+    } = api;
+
+    let json_cel_expr = DefaultCelBuilder::response_only_certification()
+        .with_response_certification(DefaultResponseCertification::certified_response_headers(
+            vec!["Content-Type"],
+        ))
+        .build()
+        .to_string();
+    let json_cel_expr = expand_cel_expr(&LitStr::new(&json_cel_expr, Span::call_site()))?;
+    let skip_cel_expr = DefaultCelBuilder::skip_certification().to_string();
+    let skip_cel_expr = expand_cel_expr(&LitStr::new(&skip_cel_expr, Span::call_site()))?;
+
+    let mut certify_routes = vec![];
+    let mut query_routes = vec![];
+    let mut query_route_matches = vec![];
+    let mut upgrade_routes = vec![];
+    let mut update_routes = vec![];
+    for Route {
+        method,
+        path,
+        handler,
+        profile,
+    } in &routes
+    {
+        let method = method.to_string();
+        let matches_route =
+            quote! { request.method.eq_ignore_ascii_case(#method) && path == #path };
+        if !matches!(profile, RouteProfile::Update) {
+            query_route_matches.push(matches_route.clone());
+        }
+
+        match profile {
+            RouteProfile::ResponseOnly => {
+                certify_routes.push(quote! {
+                    certify_json_response(
+                        &mut tree,
+                        &mut responses,
+                        #path,
+                        ::ic_http_certification::HttpCertificationPath::exact(#path),
+                        #handler(),
+                    )?;
+                });
+                query_routes.push(quote! {
+                    if #matches_route {
+                        return certified_json_response(#path, &path);
+                    }
+                });
+            }
+            RouteProfile::Update => {
+                upgrade_routes.push(quote! {
+                    if #matches_route {
+                        return ::ic_http_certification::HttpResponse::upgrade_to_update_call();
+                    }
+                });
+                update_routes.push(quote! {
+                    if #matches_route {
+                        let response = #handler(&request);
+                        if let Err(error) = certify_responses() {
+                            return internal_error(&error);
+                        }
+
+                        return response;
+                    }
+                });
+            }
+            RouteProfile::Skip => {
+                certify_routes.push(quote! {
+                    tree.insert(&::ic_http_certification::HttpCertificationTreeEntry::new(
+                        ::ic_http_certification::HttpCertificationPath::exact(#path),
+                        ::ic_http_certification::HttpCertification::skip(),
+                    ));
+                });
+                query_routes.push(quote! {
+                    if #matches_route {
+                        let mut response = #handler(request);
+                        response.headers_mut().set(
+                            ::ic_http_certification::CERTIFICATE_EXPRESSION_HEADER_NAME,
+                            SKIP_CEL_EXPR.expression_str,
+                        );
+                        let entry = ::ic_http_certification::HttpCertificationTreeEntry::new(
+                            ::ic_http_certification::HttpCertificationPath::exact(#path),
+                            ::ic_http_certification::HttpCertification::skip(),
+                        );
+
+                        return HTTP_TREE.with_borrow(|tree| {
+                            tree.certify_response(
+                                &entry,
+                                &path,
+                                &SKIP_CEL_EXPR.expression,
+                                None,
+                                response,
+                                None,
+                            )
+                        });
+                    }
+                });
+            }
+        }
+    }
+
+    let mut route_paths = routes
+        .iter()
+        .map(|route| route.path.value())
+        .collect::<Vec<_>>();
+    route_paths.sort();
+    route_paths.dedup();
+
+    let canister_methods = canister_methods.then(|| {
+        quote! {
+            #[::ic_cdk::init]
+            fn init() {
+                if let Err(error) = #name::init() {
+                    ::ic_cdk::println!("failed to certify responses: {}", error);
+                }
+            }
+
+            #[::ic_cdk::post_upgrade]
+            fn post_upgrade() {
+                if let Err(error) = #name::post_upgrade() {
+                    ::ic_cdk::println!("failed to certify responses: {}", error);
+                }
+            }
+
+            #[::ic_cdk::query]
+            fn http_request(
+                request: ::ic_http_certification::HttpRequest,
+            ) -> ::ic_http_certification::HttpResponse {
+                #name::http_request(request)
+            }
+
+            #[::ic_cdk::update]
+            fn http_request_update(
+                request: ::ic_http_certification::HttpRequest,
+            ) -> ::ic_http_certification::HttpResponse {
+                #name::http_request_update(request)
+            }
+        }
+    });
+
+    Ok(quote! {
+        #[allow(dead_code)]
+        #vis mod #name {
+            #[allow(unused_imports)]
+            use super::*;
+            use ::std::{cell::RefCell, collections::HashMap};
+
+            /// The CEL expression of `response_only` routes and the "not found" response, which
+            /// certifies the status code, the body and the `Content-Type` header.
+            pub const JSON_CEL_EXPR: ::ic_http_certification::cel::PrecomputedCelExpression<
+                'static,
+                ::ic_http_certification::cel::DefaultResponseOnlyCelExpression<'static>,
+            > = #json_cel_expr;
+
+            /// The CEL expression of `skip` routes.
+            pub const SKIP_CEL_EXPR: ::ic_http_certification::cel::PrecomputedCelExpression<
+                'static,
+                ::ic_http_certification::cel::DefaultCelExpression<'static>,
+            > = #skip_cel_expr;
+
+            const NOT_FOUND_PATH: &str = "";
+            const ROUTE_PATHS: &[&str] = &[#(#route_paths),*];
+
+            type CertifiedJsonResponses = HashMap<
+                &'static str,
+                (
+                    ::ic_http_certification::HttpCertificationTreeEntry<'static>,
+                    ::ic_http_certification::HttpResponse,
+                ),
+            >;
+
+            thread_local! {
+                /// The certification tree of the API.
+                pub static HTTP_TREE: RefCell<::ic_http_certification::HttpCertificationTree> =
+                    RefCell::new(::ic_http_certification::HttpCertificationTree::default());
+
+                static RESPONSES: RefCell<CertifiedJsonResponses> = RefCell::new(HashMap::new());
+            }
+
+            /// Certifies all responses, to be called from the canister's `init` hook.
+            pub fn init() -> ::ic_http_certification::HttpCertificationResult {
+                certify_responses()
+            }
+
+            /// Certifies all responses, to be called from the canister's `post_upgrade` hook,
+            /// since the certification tree is not kept across upgrades.
+            pub fn post_upgrade() -> ::ic_http_certification::HttpCertificationResult {
+                certify_responses()
+            }
+
+            /// Rebuilds the certification tree from the current responses of all routes and
+            /// updates the canister's certified data.
+            ///
+            /// This is called after every `update` route, and must be called after any other
+            /// change to the data that `response_only` routes respond with.
+            ///
+            /// Returns an error, and leaves the certification tree and the canister's certified
+            /// data unchanged, if the response of a `response_only` route cannot be certified,
+            /// such as a response that upgrades the request to an update call.
+            pub fn certify_responses() -> ::ic_http_certification::HttpCertificationResult {
+                let mut tree = ::ic_http_certification::HttpCertificationTree::default();
+                let mut responses = CertifiedJsonResponses::new();
+
+                #(#certify_routes)*
+                certify_json_response(
+                    &mut tree,
+                    &mut responses,
+                    NOT_FOUND_PATH,
+                    ::ic_http_certification::HttpCertificationPath::wildcard(NOT_FOUND_PATH),
+                    ::ic_http_certification::HttpResponse::builder()
+                        .not_found()
+                        .with_body(#NOT_FOUND_BODY)
+                        .build()?,
+                )?;
+
+                #set_certified_data(&tree.root_hash());
+                HTTP_TREE.set(tree);
+                RESPONSES.set(responses);
+
+                Ok(())
+            }
+
+            fn certify_json_response(
+                tree: &mut ::ic_http_certification::HttpCertificationTree,
+                responses: &mut CertifiedJsonResponses,
+                route_path: &'static str,
+                path: ::ic_http_certification::HttpCertificationPath<'static>,
+                mut response: ::ic_http_certification::HttpResponse,
+            ) -> ::ic_http_certification::HttpCertificationResult {
+                if response.get_header_value("Content-Type").is_none() {
+                    response.headers.push(("Content-Type".to_string(), #JSON_CONTENT_TYPE.to_string()));
+                }
+                response.headers_mut().set(
+                    ::ic_http_certification::CERTIFICATE_EXPRESSION_HEADER_NAME,
+                    JSON_CEL_EXPR.expression_str,
+                );
+
+                let certification = ::ic_http_certification::HttpCertification::response_only(
+                    &JSON_CEL_EXPR.expression,
+                    &response,
+                    None,
+                )?;
+                let entry = ::ic_http_certification::HttpCertificationTreeEntry::new(path, certification);
+
+                tree.insert(&entry);
+                responses.insert(route_path, (entry, response));
+
+                Ok(())
+            }
+
+            fn certified_json_response(
+                route_path: &'static str,
+                request_path: &str,
+            ) -> ::ic_http_certification::HttpCertificationResult<
+                ::ic_http_certification::CertifiedResponse<'static>,
+            > {
+                RESPONSES.with_borrow(|responses| {
+                    let (entry, response) = responses.get(route_path).ok_or_else(|| {
+                        ::ic_http_certification::HttpCertificationError::CertificationNotFound(
+                            route_path.to_string(),
+                        )
+                    })?;
+
+                    HTTP_TREE.with_borrow(|tree| {
+                        tree.certify_response(
+                            entry,
+                            request_path,
+                            &JSON_CEL_EXPR.expression.into(),
+                            None,
+                            response.clone(),
+                            None,
+                        )
+                    })
+                })
+            }
+
+            /// Returns the [CertifiedResponse](::ic_http_certification::CertifiedResponse) for a
+            /// request to the query endpoint, before the `IC-Certificate` header is added, so
+            /// that it can be checked in tests with
+            /// [dry_run_verify](::ic_http_certification::CertifiedResponse::dry_run_verify).
+            ///
+            /// Requests to `update` routes, and requests with an unsupported method to the path of
+            /// a route, are not upgraded to update calls by this function, so they receive the
+            /// certified "not found" response, which verifiers reject for the paths of
+            /// `response_only` and `skip` routes.
+            pub fn certified_response(
+                request: &::ic_http_certification::HttpRequest,
+            ) -> ::ic_http_certification::HttpCertificationResult<
+                ::ic_http_certification::CertifiedResponse<'static>,
+            > {
+                let path = request.canonical_path()?;
+
+                #(#query_routes)*
+
+                certified_json_response(NOT_FOUND_PATH, &path)
+            }
+
+            /// Handles a request to the canister's `http_request` query endpoint.
+            pub fn http_request(
+                request: ::ic_http_certification::HttpRequest,
+            ) -> ::ic_http_certification::HttpResponse {
+                let path = match request.canonical_path() {
+                    Ok(path) => path,
+                    Err(error) => return internal_error(&error),
+                };
+
+                #(#upgrade_routes)*
+
+                // The "not found" response is not the most precise certification for the path
+                // of a route, so requests with an unsupported method are answered by the update
+                // endpoint instead, whose responses are not verified against the tree.
+                let is_query_route = false #(|| #query_route_matches)*;
+                if !is_query_route && ROUTE_PATHS.contains(&path.as_str()) {
+                    return ::ic_http_certification::HttpResponse::upgrade_to_update_call();
+                }
+
+                let data_certificate = #data_certificate().ok_or(
+                    ::ic_http_certification::HttpCertificationError::DataCertificateNotAvailable,
+                );
+
+                data_certificate
+                    .and_then(|data_certificate| {
+                        certified_response(&request)?.serve(&data_certificate)
+                    })
+                    .unwrap_or_else(|error| internal_error(&error))
+            }
+
+            /// Handles a request to the canister's `http_request_update` endpoint, for requests
+            /// to `update` routes that were upgraded to update calls.
+            pub fn http_request_update(
+                request: ::ic_http_certification::HttpRequest,
+            ) -> ::ic_http_certification::HttpResponse {
+                let path = match request.canonical_path() {
+                    Ok(path) => path,
+                    Err(error) => return internal_error(&error),
+                };
+
+                #(#update_routes)*
+
+                ::ic_http_certification::HttpResponse::builder()
+                    .not_found()
+                    .with_header("Content-Type", #JSON_CONTENT_TYPE)
+                    .with_body(#NOT_FOUND_BODY)
+                    .build()
+                    .expect("the not found response is valid")
+            }
+
+            fn internal_error(
+                error: &::ic_http_certification::HttpCertificationError,
+            ) -> ::ic_http_certification::HttpResponse {
+                ::ic_http_certification::HttpResponse {
+                    status_code: 500,
+                    headers: vec![("Content-Type".to_string(), "text/plain".to_string())],
+                    body: error.to_string().into_bytes(),
+                    upgrade: None,
+                    trailers: None,
+                }
+            }
+        }
+
+        #canister_methods
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_api(input: TokenStream) -> syn::Result<CertifiedJsonApi> {
+        syn::parse2(input)
+    }
+
+    #[test]
+    fn rejects_missing_setting() {
+        let result = parse_api(quote! {
+            mod api;
+            data_certificate = ic_cdk::api::data_certificate;
+        })
+        .err()
+        .unwrap();
+
+        assert_eq!(
+            result.to_string(),
+            "missing `set_certified_data` setting for certified_json_api"
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_profile() {
+        let result = parse_api(quote! {
+            mod api;
+            GET "/todos" => list_todos: full;
+        })
+        .err()
+        .unwrap();
+
+        assert_eq!(
+            result.to_string(),
+            "expected one of the `response_only`, `update` or `skip` certification profiles"
+        );
+    }
+
+    #[test]
+    fn rejects_relative_route_path() {
+        let result = parse_api(quote! {
+            mod api;
+            GET "todos" => list_todos: response_only;
+        })
+        .err()
+        .unwrap();
+
+        assert_eq!(result.to_string(), "route paths must start with `/`");
+    }
+
+    #[test]
+    fn rejects_duplicate_route() {
+        let result = parse_api(quote! {
+            mod api;
+            POST "/todos" => create_todo: update;
+            POST "/todos" => replace_todos: update;
+        })
+        .err()
+        .unwrap();
+
+        assert_eq!(result.to_string(), "duplicate route `POST /todos`");
+    }
+
+    #[test]
+    fn rejects_second_certified_route_for_path() {
+        let result = parse_api(quote! {
+            mod api;
+            GET "/todos" => list_todos: response_only;
+            HEAD "/todos" => todos_head: skip;
+        })
+        .err()
+        .unwrap();
+
+        assert_eq!(
+            result.to_string(),
+            "`/todos` already has a certified route, only one `response_only` or `skip` route is allowed per path"
+        );
+    }
+
+    #[test]
+    fn generates_canister_methods() {
+        let api = parse_api(quote! {
+            mod api;
+            data_certificate = ic_cdk::api::data_certificate;
+            set_certified_data = ic_cdk::api::set_certified_data;
+            canister_methods;
+            GET "/todos" => list_todos: response_only;
+        })
+        .unwrap();
+
+        let result = expand_certified_json_api(api).unwrap().to_string();
+
+        for canister_method in ["init", "post_upgrade", "query", "update"] {
+            let canister_method = Ident::new(canister_method, Span::call_site());
+            let attribute = quote! { #[::ic_cdk::#canister_method] }.to_string();
+
+            assert!(result.contains(&attribute));
+        }
+    }
+}
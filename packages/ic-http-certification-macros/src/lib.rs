@@ -0,0 +1,206 @@
+/*!
+# HTTP Certification Macros
+
+Procedural macros for the [ic-http-certification](https://docs.rs/ic-http-certification) crate.
+
+## Compile-time CEL expressions
+
+The [cel_expr!] macro parses and validates a CEL expression at compile time. Any syntax errors,
+invalid certification parameters or unknown properties, such as a misspelled
+`decoded_body_certification`, will cause a compilation error, rather than an error at runtime in
+the canister, a verification failure in the HTTP Gateway or an option that is silently left out of
+certification.
+
+The macro produces a [PrecomputedCelExpression](https://docs.rs/ic-http-certification/latest/ic_http_certification/cel/struct.PrecomputedCelExpression.html)
+that contains the structured CEL expression, its [String] representation and the hash of that
+representation, all of which can be stored in a `const` or `static`:
+
+```rust
+use ic_http_certification::cel::{DefaultFullCelExpression, PrecomputedCelExpression};
+use ic_http_certification_macros::cel_expr;
+
+const CEL_EXPR: PrecomputedCelExpression<'static, DefaultFullCelExpression<'static>> = cel_expr!(
+    r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    request_certification: RequestCertification {
+                        certified_request_headers: ["Accept"],
+                        certified_query_parameters: []
+                    },
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: ["Content-Type"]
+                        }
+                    }
+                }
+            }
+        )
+    "#
+);
+```
+
+The [String] representation is minified, so it will be identical to the result of calling
+[to_string](ToString::to_string) on the structured expression and the hash will match the one used
+by [HttpCertification](https://docs.rs/ic-http-certification/latest/ic_http_certification/struct.HttpCertification.html).
+*/
+
+#![deny(missing_docs, missing_debug_implementations, rustdoc::all, clippy::all)]
+
+mod cel_expr;
+mod certified_handler;
+mod certified_json_api;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, ItemFn, LitStr};
+
+/// Parses and validates a CEL expression at compile time.
+///
+/// The expression is expanded into a `PrecomputedCelExpression`, where the type of the
+/// `expression` field depends on the parsed expression:
+///
+/// - `DefaultFullCelExpression` for expressions that certify both the request and the response.
+/// - `DefaultResponseOnlyCelExpression` for expressions that certify only the response.
+/// - `DefaultCelExpression` for expressions that skip certification.
+///
+/// A compilation error is emitted if the expression cannot be parsed, or if it does not pass
+/// `validate_cel_expression`, which also rejects unknown properties and trailing input.
+#[proc_macro]
+pub fn cel_expr(input: TokenStream) -> TokenStream {
+    let cel_expr = parse_macro_input!(input as LitStr);
+
+    cel_expr::expand_cel_expr(&cel_expr)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generates the certification boilerplate of a canister that serves a JSON API.
+///
+/// The macro expands into a module with the given name that contains the CEL expressions, the
+/// certification tree, the `init` and `post_upgrade` hooks and the `http_request` and
+/// `http_request_update` handlers for the given routes. Each route has a method, an exact path,
+/// a handler and one of the following certification profiles:
+///
+/// - `response_only`: the handler takes no arguments and returns the `HttpResponse` for the
+///   route. Responses are certified ahead of time, so they must only depend on the canister's
+///   state, and are served from the query endpoint.
+/// - `update`: the handler takes an `&HttpRequest` and returns an `HttpResponse`. Requests are
+///   upgraded to update calls and all `response_only` routes are re-certified after the handler
+///   returns, so that they reflect any changes that it made.
+/// - `skip`: the handler takes an `&HttpRequest` and returns an `HttpResponse`, which is served
+///   from the query endpoint without certification.
+///
+/// Each path can have at most one `response_only` or `skip` route, and any number of `update`
+/// routes with other methods. Duplicate routes are rejected at compile time.
+///
+/// A `Content-Type: application/json` header is added to `response_only` responses that do not
+/// have a `Content-Type` header. Requests for paths without routes receive a certified
+/// `404 Not Found` response, while requests with an unsupported method for the path of a route
+/// are upgraded to update calls and answered with an uncertified `404 Not Found` response.
+///
+/// The following settings are supported:
+///
+/// - `data_certificate` (required): a function returning the canister's data certificate as an
+///   `Option<Vec<u8>>`, such as `ic_cdk::api::data_certificate`.
+/// - `set_certified_data` (required): a function setting the canister's certified data from a
+///   `&[u8]`, such as `ic_cdk::api::set_certified_data`.
+/// - `canister_methods` (optional): also generates the `init`, `post_upgrade`, `http_request` and
+///   `http_request_update` canister methods using the `ic_cdk` crate, which the canister must
+///   depend on.
+///
+/// The generated `init`, `post_upgrade` and `certify_responses` functions return an error, rather
+/// than panicking, if a `response_only` response cannot be certified, such as a response that
+/// upgrades the request to an update call. The generated canister methods print the error instead
+/// of trapping, so that the canister can still be installed or upgraded, and it serves
+/// `500 Internal Server Error` responses until it is fixed. `update` routes answer with the same
+/// response if the responses cannot be certified after their handler returns.
+///
+/// The generated code serves certified responses with `CertifiedResponse::serve`, so the
+/// canister must enable the `serde` feature of the `ic-http-certification` crate.
+///
+/// ```ignore
+/// certified_json_api! {
+///     mod api;
+///     data_certificate = ic_cdk::api::data_certificate;
+///     set_certified_data = ic_cdk::api::set_certified_data;
+///     canister_methods;
+///
+///     GET "/todos" => list_todos: response_only;
+///     POST "/todos" => create_todo: update;
+///     GET "/health" => health: skip;
+/// }
+///
+/// fn list_todos() -> HttpResponse {
+///     // ...
+/// }
+///
+/// fn create_todo(request: &HttpRequest) -> HttpResponse {
+///     // ...
+/// }
+///
+/// fn health(request: &HttpRequest) -> HttpResponse {
+///     // ...
+/// }
+/// ```
+#[proc_macro]
+pub fn certified_json_api(input: TokenStream) -> TokenStream {
+    let api = parse_macro_input!(input as certified_json_api::CertifiedJsonApi);
+
+    certified_json_api::expand_certified_json_api(api)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Wraps an HTTP handler so that the responses it returns are certified before they are served.
+///
+/// The annotated function must take a single `HttpRequest` argument, either by value or by
+/// reference, and return an `HttpResponse`. It is replaced by a function with the same name that
+/// takes an `HttpRequest` and returns an `HttpCertificationResult<HttpResponse>`. The generated
+/// function will:
+///
+/// 1. Reject requests that do not match the `method` attribute, if it is provided.
+/// 2. Call the original handler and set the `IC-CertificateExpression` header of its response,
+///    replacing any existing one.
+/// 3. Create an `HttpCertification` for the request and response using the `cel` attribute.
+/// 4. Look up the certification in the `tree` and generate a witness for it.
+/// 5. Attach the `IC-Certificate` header using the certificate returned by `data_certificate`.
+///
+/// The following attributes are supported:
+///
+/// - `tree` (required): a `thread_local!` static containing a `RefCell<HttpCertificationTree>`.
+/// - `path` or `wildcard` (required): the exact or wildcard path of the certification in the tree.
+/// - `cel` (required): a `PrecomputedCelExpression`, typically created with [cel_expr!].
+/// - `data_certificate` (required): a function returning the canister's data certificate as an
+///   `Option<Vec<u8>>`, such as `ic_cdk::api::data_certificate`.
+/// - `method` (optional): the request method supported by the handler.
+///
+/// The certification must have been inserted into the tree ahead of time, for example in an update
+/// call, using the same CEL expression and a response that includes the `IC-CertificateExpression`
+/// header. Otherwise the generated function will return an error.
+///
+/// The generated function serves the response with `CertifiedResponse::serve`, so the canister
+/// must enable the `serde` feature of the `ic-http-certification` crate.
+///
+/// ```ignore
+/// #[certified_handler(
+///     tree = HTTP_TREE,
+///     path = "/todos",
+///     method = "GET",
+///     cel = TODOS_CEL_EXPR,
+///     data_certificate = ic_cdk::api::data_certificate,
+/// )]
+/// fn list_todos(request: &HttpRequest) -> HttpResponse {
+///     // ...
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn certified_handler(args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut handler_args = certified_handler::CertifiedHandlerArgs::default();
+    let args_parser = syn::meta::parser(|meta| handler_args.parse(meta));
+    parse_macro_input!(args with args_parser);
+    let handler = parse_macro_input!(input as ItemFn);
+
+    certified_handler::expand_certified_handler(handler_args, handler)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
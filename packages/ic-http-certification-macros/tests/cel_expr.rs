@@ -0,0 +1,242 @@
+use ic_http_certification::{
+    cel::{
+        DefaultCelExpression, DefaultFullCelExpression, DefaultResponseOnlyCelExpression,
+        PrecomputedCelExpression,
+    },
+    DefaultCelBuilder, DefaultResponseCertification,
+};
+use ic_http_certification_macros::cel_expr;
+use ic_representation_independent_hash::hash;
+
+const SKIP_CEL_EXPR: PrecomputedCelExpression<'static, DefaultCelExpression<'static>> = cel_expr!(
+    r#"
+        default_certification (
+            ValidationArgs {
+                no_certification: Empty {}
+            }
+        )
+    "#
+);
+
+const RESPONSE_ONLY_CEL_EXPR: PrecomputedCelExpression<
+    'static,
+    DefaultResponseOnlyCelExpression<'static>,
+> = cel_expr!(
+    r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        response_header_exclusions: ResponseHeaderList {
+                            headers: ["Date", "Server", "Set-Cookie"]
+                        }
+                    }
+                }
+            }
+        )
+    "#
+);
+
+const FULL_CEL_EXPR: PrecomputedCelExpression<'static, DefaultFullCelExpression<'static>> = cel_expr!(
+    r#"default_certification(ValidationArgs{certification:Certification{request_certification:RequestCertification{certified_request_headers:["Accept","Accept-Encoding"],certified_query_parameters:["foo"]},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:["Cache-Control"]}}}})"#
+);
+
+const DECODED_BODY_CEL_EXPR: PrecomputedCelExpression<
+    'static,
+    DefaultResponseOnlyCelExpression<'static>,
+> = cel_expr!(
+    r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: ["Cache-Control"]
+                        },
+                        decoded_body_certification: Empty {}
+                    }
+                }
+            }
+        )
+    "#
+);
+
+const COMBINED_RESPONSE_HEADERS_CEL_EXPR: PrecomputedCelExpression<
+    'static,
+    DefaultResponseOnlyCelExpression<'static>,
+> = cel_expr!(
+    r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: ["glob:X-App-*"]
+                        },
+                        response_header_exclusions: ResponseHeaderList {
+                            headers: ["X-App-Debug", "Set-Cookie"]
+                        }
+                    }
+                }
+            }
+        )
+    "#
+);
+
+const STATUS_CODE_CLASS_CEL_EXPR: PrecomputedCelExpression<
+    'static,
+    DefaultResponseOnlyCelExpression<'static>,
+> = cel_expr!(
+    r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: []
+                        },
+                        status_code_class_certification: Empty {}
+                    }
+                }
+            }
+        )
+    "#
+);
+
+const ALLOWED_METHODS_CEL_EXPR: PrecomputedCelExpression<
+    'static,
+    DefaultResponseOnlyCelExpression<'static>,
+> = cel_expr!(
+    r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: []
+                        }
+                    },
+                    allowed_request_methods: ["GET", "HEAD"]
+                }
+            }
+        )
+    "#
+);
+
+#[test]
+fn skip_certification() {
+    let expected = DefaultCelBuilder::skip_certification();
+
+    assert_eq!(SKIP_CEL_EXPR.expression, DefaultCelExpression::Skip);
+    assert_eq!(SKIP_CEL_EXPR.expression_str, expected.to_string());
+    assert_eq!(SKIP_CEL_EXPR.hash, hash(expected.to_string().as_bytes()));
+}
+
+#[test]
+fn response_only_certification() {
+    let expected = DefaultCelBuilder::response_only_certification()
+        .with_response_certification(DefaultResponseCertification::response_header_exclusions(
+            vec!["Date", "Server"],
+        ))
+        .build();
+
+    assert_eq!(RESPONSE_ONLY_CEL_EXPR.expression, expected);
+    assert_eq!(RESPONSE_ONLY_CEL_EXPR.expression_str, expected.to_string());
+    assert_eq!(
+        RESPONSE_ONLY_CEL_EXPR.hash,
+        hash(expected.to_string().as_bytes())
+    );
+}
+
+#[test]
+fn full_certification() {
+    let expected = DefaultCelBuilder::full_certification()
+        .with_request_headers(vec!["Accept", "Accept-Encoding"])
+        .with_request_query_parameters(vec!["foo"])
+        .with_response_certification(DefaultResponseCertification::certified_response_headers(
+            vec!["Cache-Control"],
+        ))
+        .build();
+
+    assert_eq!(FULL_CEL_EXPR.expression, expected);
+    assert_eq!(FULL_CEL_EXPR.expression_str, expected.to_string());
+    assert_eq!(FULL_CEL_EXPR.hash, hash(expected.to_string().as_bytes()));
+}
+
+#[test]
+fn decoded_body_certification() {
+    let expected = DefaultCelBuilder::response_only_certification()
+        .with_response_certification(DefaultResponseCertification::certified_response_headers(
+            vec!["Cache-Control"],
+        ))
+        .with_decoded_body_certification()
+        .build();
+
+    assert_eq!(DECODED_BODY_CEL_EXPR.expression, expected);
+    assert_eq!(DECODED_BODY_CEL_EXPR.expression_str, expected.to_string());
+    assert_eq!(
+        DECODED_BODY_CEL_EXPR.hash,
+        hash(expected.to_string().as_bytes())
+    );
+}
+
+#[test]
+fn combined_response_header_certification() {
+    let expected = DefaultCelBuilder::response_only_certification()
+        .with_response_certification(
+            DefaultResponseCertification::certified_response_headers_with_exclusions(
+                vec!["glob:X-App-*"],
+                vec!["X-App-Debug"],
+            ),
+        )
+        .build();
+
+    assert_eq!(COMBINED_RESPONSE_HEADERS_CEL_EXPR.expression, expected);
+    assert_eq!(
+        COMBINED_RESPONSE_HEADERS_CEL_EXPR.expression_str,
+        expected.to_string()
+    );
+    assert_eq!(
+        COMBINED_RESPONSE_HEADERS_CEL_EXPR.hash,
+        hash(expected.to_string().as_bytes())
+    );
+}
+
+#[test]
+fn status_code_class_certification() {
+    let expected = DefaultCelBuilder::response_only_certification()
+        .with_status_code_class_certification()
+        .build();
+
+    assert_eq!(STATUS_CODE_CLASS_CEL_EXPR.expression, expected);
+    assert_eq!(
+        STATUS_CODE_CLASS_CEL_EXPR.expression_str,
+        expected.to_string()
+    );
+    assert_eq!(
+        STATUS_CODE_CLASS_CEL_EXPR.hash,
+        hash(expected.to_string().as_bytes())
+    );
+}
+
+#[test]
+fn allowed_methods() {
+    let expected = DefaultCelBuilder::response_only_certification()
+        .with_allowed_methods(vec!["GET", "HEAD"])
+        .build();
+
+    assert_eq!(ALLOWED_METHODS_CEL_EXPR.expression, expected);
+    assert_eq!(
+        ALLOWED_METHODS_CEL_EXPR.expression_str,
+        expected.to_string()
+    );
+    assert_eq!(
+        ALLOWED_METHODS_CEL_EXPR.hash,
+        hash(expected.to_string().as_bytes())
+    );
+}
@@ -0,0 +1,140 @@
+use ic_http_certification::{
+    cel::{DefaultResponseOnlyCelExpression, PrecomputedCelExpression},
+    HttpCertificationError, HttpCertificationPath, HttpCertificationTree,
+    HttpCertificationTreeEntry, HttpRequest, HttpResponse, CERTIFICATE_EXPRESSION_HEADER_NAME,
+    CERTIFICATE_HEADER_NAME,
+};
+use ic_http_certification_macros::{cel_expr, certified_handler};
+use std::cell::RefCell;
+
+const CEL_EXPR: PrecomputedCelExpression<'static, DefaultResponseOnlyCelExpression<'static>> = cel_expr!(
+    r#"default_certification(ValidationArgs{certification:Certification{no_request_certification:Empty{},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:[]}}}})"#
+);
+
+thread_local! {
+    static HTTP_TREE: RefCell<HttpCertificationTree> = RefCell::new(HttpCertificationTree::default());
+}
+
+fn data_certificate() -> Option<Vec<u8>> {
+    Some(vec![1, 2, 3])
+}
+
+fn hello_response() -> HttpResponse {
+    HttpResponse {
+        status_code: 200,
+        headers: vec![],
+        body: b"Hello World!".to_vec(),
+        upgrade: None,
+        trailers: None,
+    }
+}
+
+#[certified_handler(
+    tree = HTTP_TREE,
+    path = "/hello",
+    method = "GET",
+    cel = CEL_EXPR,
+    data_certificate = data_certificate,
+)]
+fn hello(_request: &HttpRequest) -> HttpResponse {
+    hello_response()
+}
+
+#[certified_handler(
+    tree = HTTP_TREE,
+    path = "/hello",
+    method = "GET",
+    cel = CEL_EXPR,
+    data_certificate = data_certificate,
+)]
+fn hello_with_expression(_request: &HttpRequest) -> HttpResponse {
+    let mut response = hello_response();
+    response.headers.push((
+        "ic-certificateexpression".to_string(),
+        "default_certification(ValidationArgs{no_certification:Empty{}})".to_string(),
+    ));
+
+    response
+}
+
+fn certify_hello() {
+    let mut response = hello_response();
+    response.headers.push((
+        CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+        CEL_EXPR.expression_str.to_string(),
+    ));
+    let request = HttpRequest {
+        method: "GET".to_string(),
+        url: "/hello".to_string(),
+        headers: vec![],
+        body: vec![],
+    };
+    let certification = CEL_EXPR.certification(&request, &response).unwrap();
+    let path = HttpCertificationPath::exact("/hello");
+    let entry = HttpCertificationTreeEntry::new(&path, certification);
+
+    HTTP_TREE.with(|tree| tree.borrow_mut().insert(&entry));
+}
+
+fn hello_request(method: &str) -> HttpRequest {
+    HttpRequest {
+        method: method.to_string(),
+        url: "/hello?greeting=hi".to_string(),
+        headers: vec![],
+        body: vec![],
+    }
+}
+
+#[test]
+fn serves_certified_response() {
+    certify_hello();
+
+    let response = hello(hello_request("GET")).unwrap();
+
+    assert_eq!(response.status_code, 200);
+    assert_eq!(response.body, b"Hello World!");
+    assert_eq!(response.headers[0].0, CERTIFICATE_EXPRESSION_HEADER_NAME);
+    assert_eq!(response.headers[0].1, CEL_EXPR.expression_str);
+    assert_eq!(response.headers[1].0, CERTIFICATE_HEADER_NAME);
+    assert!(response.headers[1]
+        .1
+        .starts_with("certificate=:AQID:, tree=:"));
+}
+
+#[test]
+fn rejects_uncertified_response() {
+    let result = hello(hello_request("GET"));
+
+    assert!(matches!(
+        result,
+        Err(HttpCertificationError::CertificationNotFound(path)) if path == "/hello"
+    ));
+}
+
+#[test]
+fn rejects_unexpected_method() {
+    certify_hello();
+
+    let result = hello(hello_request("POST"));
+
+    assert!(matches!(
+        result,
+        Err(HttpCertificationError::UnexpectedRequestMethod { expected, found })
+            if expected == "GET" && found == "POST"
+    ));
+}
+
+#[test]
+fn replaces_existing_certificate_expression_header() {
+    certify_hello();
+
+    let response = hello_with_expression(hello_request("GET")).unwrap();
+    let expression_headers = response
+        .headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case(CERTIFICATE_EXPRESSION_HEADER_NAME))
+        .collect::<Vec<_>>();
+
+    assert_eq!(expression_headers.len(), 1);
+    assert_eq!(expression_headers[0].1, CEL_EXPR.expression_str);
+}
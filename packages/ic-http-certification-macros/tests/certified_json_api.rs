@@ -0,0 +1,178 @@
+use ic_http_certification::{
+    HttpCertificationError, HttpRequest, HttpResponse, CERTIFICATE_EXPRESSION_HEADER_NAME,
+    CERTIFICATE_HEADER_NAME,
+};
+use ic_http_certification_macros::certified_json_api;
+use std::cell::RefCell;
+
+certified_json_api! {
+    mod api;
+    data_certificate = data_certificate;
+    set_certified_data = set_certified_data;
+
+    GET "/todos" => list_todos: response_only;
+    POST "/todos" => create_todo: update;
+    GET "/health" => health: skip;
+}
+
+thread_local! {
+    static TODOS: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+    static CERTIFIED_DATA: RefCell<Vec<u8>> = const { RefCell::new(vec![]) };
+}
+
+fn data_certificate() -> Option<Vec<u8>> {
+    Some(vec![1, 2, 3])
+}
+
+fn set_certified_data(data: &[u8]) {
+    CERTIFIED_DATA.set(data.to_vec());
+}
+
+fn list_todos() -> HttpResponse {
+    let todos = TODOS.with_borrow(|todos| format!(r#"["{}"]"#, todos.join(r#"",""#)));
+
+    HttpResponse::ok(todos, vec![])
+}
+
+fn create_todo(request: &HttpRequest) -> HttpResponse {
+    TODOS.with_borrow_mut(|todos| todos.push(String::from_utf8_lossy(&request.body).into()));
+
+    HttpResponse {
+        status_code: 201,
+        headers: vec![],
+        body: vec![],
+        upgrade: None,
+        trailers: None,
+    }
+}
+
+fn health(_request: &HttpRequest) -> HttpResponse {
+    HttpResponse::ok(r#"{"healthy":true}"#, vec![])
+}
+
+fn request(method: &str, url: &str) -> HttpRequest {
+    HttpRequest {
+        method: method.to_string(),
+        url: url.to_string(),
+        headers: vec![],
+        body: vec![],
+    }
+}
+
+fn certified_response(request: &HttpRequest) -> HttpResponse {
+    let certified_response = api::certified_response(request).unwrap();
+    certified_response.dry_run_verify(request, None).unwrap();
+
+    certified_response.response().clone()
+}
+
+#[test]
+fn serves_certified_responses() {
+    api::init().unwrap();
+
+    let response = api::http_request(request("GET", "/todos"));
+
+    assert_eq!(response.status_code, 200);
+    assert_eq!(response.body, br#"[""]"#);
+    assert_eq!(
+        response.get_header_value("Content-Type"),
+        Some("application/json")
+    );
+    assert_eq!(
+        response.get_header_value(CERTIFICATE_EXPRESSION_HEADER_NAME),
+        Some(api::JSON_CEL_EXPR.expression_str)
+    );
+    assert!(response
+        .get_header_value(CERTIFICATE_HEADER_NAME)
+        .unwrap()
+        .starts_with("certificate=:AQID:, tree=:"));
+    assert_eq!(
+        CERTIFIED_DATA.with_borrow(|data| data.clone()),
+        api::HTTP_TREE.with_borrow(|tree| tree.root_hash().to_vec())
+    );
+
+    certified_response(&request("GET", "/todos"));
+}
+
+#[test]
+fn recertifies_responses_after_updates() {
+    api::init().unwrap();
+
+    let response = api::http_request(request("POST", "/todos"));
+    assert!(response.is_upgrade());
+
+    let mut create_request = request("POST", "/todos");
+    create_request.body = b"buy milk".to_vec();
+    let response = api::http_request_update(create_request);
+    assert_eq!(response.status_code, 201);
+
+    let response = certified_response(&request("GET", "/todos"));
+    assert_eq!(response.body, br#"["buy milk"]"#);
+}
+
+#[test]
+fn serves_uncertified_responses() {
+    api::init().unwrap();
+
+    let response = certified_response(&request("GET", "/health"));
+
+    assert_eq!(response.body, br#"{"healthy":true}"#);
+    assert_eq!(
+        response.get_header_value(CERTIFICATE_EXPRESSION_HEADER_NAME),
+        Some(api::SKIP_CEL_EXPR.expression_str)
+    );
+}
+
+#[test]
+fn serves_certified_not_found_responses() {
+    api::init().unwrap();
+
+    for request in [request("GET", "/todos/1"), request("GET", "/")] {
+        let response = certified_response(&request);
+
+        assert_eq!(response.status_code, 404);
+        assert_eq!(response.body, br#"{"error":"not found"}"#);
+    }
+}
+
+#[test]
+fn upgrades_requests_with_unsupported_methods() {
+    api::init().unwrap();
+
+    for path in ["/todos", "/health"] {
+        let response = api::http_request(request("DELETE", path));
+        assert!(response.is_upgrade());
+
+        let response = api::http_request_update(request("DELETE", path));
+        assert_eq!(response.status_code, 404);
+    }
+}
+
+mod failing_api {
+    use super::*;
+
+    certified_json_api! {
+        mod api;
+        data_certificate = data_certificate;
+        set_certified_data = set_certified_data;
+
+        GET "/upgrade" => upgrade: response_only;
+    }
+
+    fn upgrade() -> HttpResponse {
+        HttpResponse::upgrade_to_update_call()
+    }
+
+    #[test]
+    fn returns_certification_errors() {
+        assert!(matches!(
+            api::init(),
+            Err(HttpCertificationError::UnexpectedUpgrade)
+        ));
+
+        let response = api::http_request(request("GET", "/upgrade"));
+
+        assert_eq!(response.status_code, 500);
+        assert!(response.get_header_value(CERTIFICATE_HEADER_NAME).is_none());
+    }
+}
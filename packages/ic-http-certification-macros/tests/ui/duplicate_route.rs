@@ -0,0 +1,13 @@
+use ic_http_certification_macros::certified_json_api;
+
+certified_json_api! {
+    mod api;
+    data_certificate = data_certificate;
+    set_certified_data = set_certified_data;
+
+    GET "/todos" => list_todos: response_only;
+    POST "/todos" => create_todo: update;
+    POST "/todos" => replace_todos: update;
+}
+
+fn main() {}
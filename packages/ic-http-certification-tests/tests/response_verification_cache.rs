@@ -0,0 +1,174 @@
+mod tests {
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+        HttpCertification, HttpCertificationPath, HttpCertificationTreeEntry, HttpRequest,
+        HttpResponse,
+    };
+    use ic_response_verification::{
+        types::VerifiedResponse, ResponseVerificationError, VerificationCache,
+    };
+    use ic_response_verification_test_utils::{
+        create_v2_fixture, get_current_timestamp, V2Fixture,
+    };
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+    const CACHE_SIZE: usize = 100;
+    const CACHE_TTL_NS: u128 = 60_000_000_000;
+
+    #[fixture]
+    fn response_only_cel() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                &["Cache-Control"],
+            ))
+            .build()
+    }
+
+    fn certified_response(
+        cel_expr: &DefaultResponseOnlyCelExpression,
+    ) -> (HttpRequest, HttpResponse, V2Fixture, u128) {
+        let req_path = "/";
+        let current_time = get_current_timestamp();
+        let certification_path = HttpCertificationPath::Exact("/");
+
+        let request = HttpRequest {
+            url: req_path.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+        let mut response = HttpResponse {
+            status_code: 200,
+            body: b"Hello World!".to_vec(),
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "max-age=604800".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification = HttpCertification::response_only(cel_expr, &response, None).unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture(req_path, &certification_tree_entry, &current_time);
+
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        (request, response, v2_fixture, current_time)
+    }
+
+    #[rstest]
+    fn repeated_verification_uses_cache(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+    ) {
+        let (request, response, fixture, current_time) = certified_response(&cel_expr);
+        let cache = VerificationCache::new(CACHE_SIZE, CACHE_TTL_NS);
+
+        for _ in 0..2 {
+            let result = cache
+                .verify_request_response_pair(
+                    request.clone(),
+                    response.clone(),
+                    fixture.canister_id.as_ref(),
+                    current_time,
+                    MAX_CERT_TIME_OFFSET_NS,
+                    &fixture.root_key,
+                    MIN_REQUESTED_VERIFICATION_VERSION,
+                )
+                .unwrap();
+
+            assert_eq!(result.verification_version, 2);
+            assert_eq!(
+                result.response,
+                Some(VerifiedResponse {
+                    status_code: Some(200),
+                    headers: vec![("cache-control".into(), "max-age=604800".into())],
+                    body: response.body.clone(),
+                })
+            );
+            assert_eq!(cache.len(), 1);
+        }
+    }
+
+    #[rstest]
+    fn modified_response_is_not_served_from_cache(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+    ) {
+        let (request, response, fixture, current_time) = certified_response(&cel_expr);
+        let cache = VerificationCache::new(CACHE_SIZE, CACHE_TTL_NS);
+
+        cache
+            .verify_request_response_pair(
+                request.clone(),
+                response.clone(),
+                fixture.canister_id.as_ref(),
+                current_time,
+                MAX_CERT_TIME_OFFSET_NS,
+                &fixture.root_key,
+                MIN_REQUESTED_VERIFICATION_VERSION,
+            )
+            .unwrap();
+
+        let result = cache.verify_request_response_pair(
+            request,
+            HttpResponse {
+                body: b"Hello IC!".to_vec(),
+                ..response
+            },
+            fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        );
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::InvalidResponseHashes
+        ));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[rstest]
+    fn expired_verification_is_verified_again(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+    ) {
+        let (request, response, fixture, current_time) = certified_response(&cel_expr);
+        let cache = VerificationCache::new(CACHE_SIZE, CACHE_TTL_NS);
+
+        cache
+            .verify_request_response_pair(
+                request.clone(),
+                response.clone(),
+                fixture.canister_id.as_ref(),
+                current_time,
+                MAX_CERT_TIME_OFFSET_NS,
+                &fixture.root_key,
+                MIN_REQUESTED_VERIFICATION_VERSION,
+            )
+            .unwrap();
+
+        let result = cache.verify_request_response_pair(
+            request,
+            response,
+            fixture.canister_id.as_ref(),
+            current_time + MAX_CERT_TIME_OFFSET_NS + 1,
+            MAX_CERT_TIME_OFFSET_NS,
+            &fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        );
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::CertificateVerificationFailed(_)
+        ));
+        assert!(cache.is_empty());
+    }
+}
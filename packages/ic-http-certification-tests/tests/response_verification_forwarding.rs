@@ -0,0 +1,123 @@
+mod tests {
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+        HttpCertification, HttpCertificationPath, HttpCertificationTreeEntry, HttpRequest,
+        HttpResponse,
+    };
+    use ic_response_verification::{
+        types::{ForwardedResponse, VerifiedResponse},
+        verify_request_response_pair_for_forwarding, ResponseVerificationError,
+    };
+    use ic_response_verification_test_utils::{
+        create_v2_fixture, get_current_timestamp, V2Fixture,
+    };
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+
+    #[fixture]
+    fn response_only_cel() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                &["Cache-Control"],
+            ))
+            .build()
+    }
+
+    fn certified_response(
+        cel_expr: &DefaultResponseOnlyCelExpression,
+    ) -> (HttpRequest, HttpResponse, V2Fixture, u128) {
+        let req_path = "/";
+        let current_time = get_current_timestamp();
+        let certification_path = HttpCertificationPath::Exact("/");
+
+        let request = HttpRequest {
+            url: req_path.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+        let mut response = HttpResponse {
+            status_code: 200,
+            body: b"Hello World!".to_vec(),
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "max-age=604800".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification = HttpCertification::response_only(cel_expr, &response, None).unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture(req_path, &certification_tree_entry, &current_time);
+
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        (request, response, v2_fixture, current_time)
+    }
+
+    #[rstest]
+    fn certified_response_is_separated_from_uncertified_headers(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+    ) {
+        let (request, mut response, fixture, current_time) = certified_response(&cel_expr);
+        response
+            .headers
+            .push(("Set-Cookie".into(), "session=1234".into()));
+        let body = response.body.clone();
+
+        let result = verify_request_response_pair_for_forwarding(
+            request,
+            response,
+            fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            ForwardedResponse {
+                response: Some(VerifiedResponse {
+                    status_code: Some(200),
+                    headers: vec![("cache-control".into(), "max-age=604800".into())],
+                    body,
+                }),
+                uncertified_headers: vec![("Set-Cookie".into(), "session=1234".into())],
+                verification_version: 2,
+            }
+        );
+    }
+
+    #[rstest]
+    fn modified_response_is_not_forwarded(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+    ) {
+        let (request, mut response, fixture, current_time) = certified_response(&cel_expr);
+        response.body = b"Hello IC!".to_vec();
+
+        let result = verify_request_response_pair_for_forwarding(
+            request,
+            response,
+            fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        );
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::InvalidResponseHashes
+        ));
+    }
+}
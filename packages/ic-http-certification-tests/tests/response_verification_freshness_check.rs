@@ -0,0 +1,128 @@
+mod tests {
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+        HttpCertification, HttpCertificationPath, HttpCertificationTreeEntry, HttpRequest,
+        HttpResponse,
+    };
+    use ic_response_verification::{
+        types::CertificateInfo, verify_request_response_pair_with_freshness_check,
+        ResponseVerificationError,
+    };
+    use ic_response_verification_test_utils::{
+        create_v2_fixture, create_v2_tree_fixture, get_current_timestamp, V2Fixture,
+    };
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+
+    #[fixture]
+    fn response_only_cel() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                &["Cache-Control"],
+            ))
+            .build()
+    }
+
+    fn certified_response(
+        cel_expr: &DefaultResponseOnlyCelExpression,
+        certificate_time: u128,
+    ) -> (HttpRequest, HttpResponse, V2Fixture, [u8; 32]) {
+        let req_path = "/";
+        let certification_path = HttpCertificationPath::Exact("/");
+
+        let request = HttpRequest {
+            url: req_path.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+        let mut response = HttpResponse {
+            status_code: 200,
+            body: b"Hello World!".to_vec(),
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "max-age=604800".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification = HttpCertification::response_only(cel_expr, &response, None).unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let certified_data =
+            create_v2_tree_fixture(req_path, &certification_tree_entry).certified_data;
+        let v2_fixture = create_v2_fixture(req_path, &certification_tree_entry, &certificate_time);
+
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        (request, response, v2_fixture, certified_data)
+    }
+
+    #[rstest]
+    fn freshness_check_receives_certificate_info(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+    ) {
+        let current_time = get_current_timestamp();
+        let (request, response, fixture, certified_data) =
+            certified_response(&cel_expr, current_time);
+
+        let mut certificate_info = None;
+        let result = verify_request_response_pair_with_freshness_check(
+            request,
+            response,
+            fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+            |info| {
+                certificate_info = Some(info.clone());
+                Ok(())
+            },
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(
+            certificate_info,
+            Some(CertificateInfo {
+                certificate_time_ns: current_time,
+                tree_root_hash: certified_data,
+            })
+        );
+    }
+
+    #[rstest]
+    fn outdated_certificate_fails_freshness_check(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+    ) {
+        let current_time = get_current_timestamp();
+        let last_seen_certificate_time = current_time;
+        let (request, response, fixture, _) = certified_response(&cel_expr, current_time - 1);
+
+        let result = verify_request_response_pair_with_freshness_check(
+            request,
+            response,
+            fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+            |info| match info.certificate_time_ns < last_seen_certificate_time {
+                true => Err("Certificate is older than the last seen certificate".into()),
+                false => Ok(()),
+            },
+        );
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::FreshnessCheckFailed(message) if message == "Certificate is older than the last seen certificate"
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+mod tests {
+    use ic_certification_testing::{CertificateBuilder, CertificateData};
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+        HttpCertification, HttpCertificationPath, HttpCertificationTreeEntry, HttpRequest,
+        HttpResponse,
+    };
+    use ic_response_verification::{
+        types::VerificationInfo, verify_request_response_pair_with_profile,
+        ResponseVerificationError, VerificationProfile,
+    };
+    use ic_response_verification_test_utils::{
+        create_canister_id, create_certificate_header, create_v2_fixture, get_current_timestamp,
+        AssetTree, V2Fixture,
+    };
+    use rstest::*;
+
+    const TWO_MINUTES_NS: u128 = 120_000_000_000;
+
+    #[fixture]
+    fn response_only_cel() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                &["Cache-Control"],
+            ))
+            .build()
+    }
+
+    fn certified_v2_response(
+        cel_expr: &DefaultResponseOnlyCelExpression,
+    ) -> (HttpRequest, HttpResponse, V2Fixture, u128) {
+        let req_path = "/";
+        let current_time = get_current_timestamp();
+        let certification_path = HttpCertificationPath::Exact("/");
+
+        let request = HttpRequest {
+            url: req_path.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+        let mut response = HttpResponse {
+            status_code: 200,
+            body: b"Hello World!".to_vec(),
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "max-age=604800".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification = HttpCertification::response_only(cel_expr, &response, None).unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture(req_path, &certification_tree_entry, &current_time);
+
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        (request, response, v2_fixture, current_time)
+    }
+
+    #[rstest]
+    #[case::strict(VerificationProfile::Strict)]
+    #[case::lenient(VerificationProfile::Lenient)]
+    fn v2_response_passes_verification(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+        #[case] profile: VerificationProfile,
+    ) {
+        let (request, response, fixture, current_time) = certified_v2_response(&cel_expr);
+
+        let result = verify_request_response_pair_with_profile(
+            request,
+            response,
+            fixture.canister_id.as_ref(),
+            current_time,
+            &fixture.root_key,
+            profile,
+        )
+        .unwrap();
+
+        assert_eq!(result.verification_version, 2);
+    }
+
+    #[rstest]
+    fn stale_certificate_fails_strict_verification(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+    ) {
+        let (request, response, fixture, certificate_time) = certified_v2_response(&cel_expr);
+
+        let result = verify_request_response_pair_with_profile(
+            request.clone(),
+            response.clone(),
+            fixture.canister_id.as_ref(),
+            certificate_time + TWO_MINUTES_NS,
+            &fixture.root_key,
+            VerificationProfile::Strict,
+        );
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::CertificateVerificationFailed(_)
+        ));
+
+        let result = verify_request_response_pair_with_profile(
+            request,
+            response,
+            fixture.canister_id.as_ref(),
+            certificate_time + TWO_MINUTES_NS,
+            &fixture.root_key,
+            VerificationProfile::Lenient,
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    fn duplicate_certificate_header_fails_strict_verification(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+    ) {
+        let (request, mut response, fixture, current_time) = certified_v2_response(&cel_expr);
+        response
+            .headers
+            .push(("ic-certificate".into(), fixture.certificate_header.clone()));
+
+        let result = verify_request_response_pair_with_profile(
+            request.clone(),
+            response.clone(),
+            fixture.canister_id.as_ref(),
+            current_time,
+            &fixture.root_key,
+            VerificationProfile::Strict,
+        );
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::DuplicateCertificationHeader(header_name) if header_name == "ic-certificate"
+        ));
+
+        let result = verify_request_response_pair_with_profile(
+            request,
+            response,
+            fixture.canister_id.as_ref(),
+            current_time,
+            &fixture.root_key,
+            VerificationProfile::Lenient,
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    fn v1_response_fails_strict_verification() {
+        let path = "/";
+        let body = "Hello World!";
+        let current_time = get_current_timestamp();
+        let canister_id = create_canister_id("rdmx6-jaaaa-aaaaa-aaadq-cai");
+
+        let mut asset_tree = AssetTree::new();
+        asset_tree.insert(path, body);
+        let certified_data = asset_tree.get_certified_data();
+        let tree_cbor = asset_tree.serialize_to_cbor(Some(path));
+
+        let CertificateData {
+            cbor_encoded_certificate,
+            certificate: _,
+            root_key,
+        } = CertificateBuilder::new(&canister_id.to_string(), &certified_data)
+            .unwrap()
+            .with_time(current_time)
+            .build()
+            .unwrap();
+
+        let certificate_header = create_certificate_header(&cbor_encoded_certificate, &tree_cbor);
+
+        let request = HttpRequest {
+            url: path.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+        let response = HttpResponse {
+            status_code: 200,
+            body: body.as_bytes().to_vec(),
+            headers: vec![("IC-Certificate".into(), certificate_header)],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let result = verify_request_response_pair_with_profile(
+            request.clone(),
+            response.clone(),
+            canister_id.as_ref(),
+            current_time,
+            &root_key,
+            VerificationProfile::Strict,
+        );
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::RequestedVerificationVersionMismatch {
+                min_requested_verification_version: 2,
+                requested_version: 1,
+            }
+        ));
+
+        let result = verify_request_response_pair_with_profile(
+            request,
+            response,
+            canister_id.as_ref(),
+            current_time,
+            &root_key,
+            VerificationProfile::Lenient,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            result,
+            VerificationInfo {
+                verification_version: 1,
+                ..
+            }
+        ));
+    }
+}
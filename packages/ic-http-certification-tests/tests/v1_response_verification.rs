@@ -51,6 +51,7 @@ mod tests {
             body: body.as_bytes().to_vec(),
             headers: vec![("IC-Certificate".into(), certificate_header)],
             upgrade: None,
+            trailers: None,
         };
         let expected_response = VerifiedResponse {
             status_code: None,
@@ -115,6 +116,7 @@ mod tests {
             body: body.as_bytes().to_vec(),
             headers: vec![("IC-Certificate".into(), certificate_header)],
             upgrade: None,
+            trailers: None,
         };
         let expected_response = VerifiedResponse {
             status_code: None,
@@ -178,6 +180,7 @@ mod tests {
             body: body.as_bytes().to_vec(),
             headers: vec![("IC-Certificate".into(), certificate_header)],
             upgrade: None,
+            trailers: None,
         };
         let expected_response = VerifiedResponse {
             status_code: None,
@@ -241,6 +244,7 @@ mod tests {
             body: b"Hello IC!".to_vec(),
             headers: vec![("IC-Certificate".into(), certificate_header)],
             upgrade: None,
+            trailers: None,
         };
 
         let result = verify_request_response_pair(
@@ -296,6 +300,7 @@ mod tests {
             body: body.as_bytes().to_vec(),
             headers: vec![("IC-Certificate".into(), certificate_header)],
             upgrade: None,
+            trailers: None,
         };
 
         let result = verify_request_response_pair(
@@ -355,6 +360,7 @@ mod tests {
             body: body.as_bytes().to_vec(),
             headers: vec![("IC-Certificate".into(), certificate_header)],
             upgrade: None,
+            trailers: None,
         };
 
         let result = verify_request_response_pair(
@@ -416,6 +422,7 @@ mod tests {
             body: body.as_bytes().to_vec(),
             headers: vec![("IC-Certificate".into(), certificate_header)],
             upgrade: None,
+            trailers: None,
         };
 
         let result = verify_request_response_pair(
@@ -475,6 +482,7 @@ mod tests {
             body: body.as_bytes().to_vec(),
             headers: vec![("IC-Certificate".into(), certificate_header)],
             upgrade: None,
+            trailers: None,
         };
 
         let result = verify_request_response_pair(
@@ -531,6 +539,7 @@ mod tests {
             body: body.as_bytes().to_vec(),
             headers: vec![("IC-Certificate".into(), certificate_header)],
             upgrade: None,
+            trailers: None,
         };
 
         let result = verify_request_response_pair(
@@ -585,6 +594,7 @@ mod tests {
             body: body.as_bytes().to_vec(),
             headers: vec![("IC-Certificate".into(), certificate_header)],
             upgrade: None,
+            trailers: None,
         };
 
         let result = verify_request_response_pair(
@@ -639,6 +649,7 @@ mod tests {
             body: body.as_bytes().to_vec(),
             headers: vec![("IC-Certificate".into(), certificate_header)],
             upgrade: None,
+            trailers: None,
         };
 
         let result = verify_request_response_pair(
@@ -0,0 +1,115 @@
+mod tests {
+    use ic_http_certification::{
+        HttpCertification, HttpCertificationPath, HttpCertificationTree,
+        HttpCertificationTreeEntry, HttpRequest, HttpResponse,
+    };
+    use ic_response_verification::{verify_certification_absence, ResponseVerificationError};
+    use ic_response_verification_test_utils::{
+        create_v2_certificate_fixture, get_current_timestamp, V2CertificateFixture,
+    };
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+
+    fn tree() -> HttpCertificationTree {
+        let mut tree = HttpCertificationTree::default();
+        tree.insert(&HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/index.html"),
+            HttpCertification::skip(),
+        ));
+        tree.insert(&HttpCertificationTreeEntry::new(
+            HttpCertificationPath::wildcard("/assets"),
+            HttpCertification::skip(),
+        ));
+
+        tree
+    }
+
+    fn not_found_response() -> HttpResponse {
+        HttpResponse {
+            status_code: 404,
+            headers: vec![],
+            body: b"Not Found".to_vec(),
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    fn verify_absence(
+        certified_url: &str,
+        request_url: &str,
+    ) -> Result<(), ResponseVerificationError> {
+        let tree = tree();
+        let current_time = get_current_timestamp();
+        let V2CertificateFixture {
+            root_key,
+            certificate_cbor,
+            canister_id,
+        } = create_v2_certificate_fixture(&tree.root_hash(), &current_time);
+
+        let response = tree
+            .certify_absence(certified_url, not_found_response())
+            .unwrap()
+            .serve(&certificate_cbor)
+            .unwrap();
+        let request = HttpRequest {
+            method: "GET".into(),
+            url: request_url.into(),
+            headers: vec![],
+            body: vec![],
+        };
+
+        verify_certification_absence(
+            &request,
+            &response,
+            canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &root_key,
+        )
+    }
+
+    #[rstest]
+    #[case("/")]
+    #[case("/not-found.html")]
+    #[case("/api/users/1?page=2")]
+    fn absence_of_certification_is_verified(#[case] request_url: &str) {
+        let request_path = request_url.split('?').next().unwrap();
+
+        verify_absence(request_path, request_url).unwrap();
+    }
+
+    #[rstest]
+    fn absence_proof_for_a_different_url_is_rejected() {
+        let result = verify_absence("/not-found.html", "/index.html");
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::CertificationNotAbsent
+        ));
+    }
+
+    #[rstest]
+    fn absence_proof_without_certificate_header_is_rejected() {
+        let request = HttpRequest {
+            method: "GET".into(),
+            url: "/not-found.html".into(),
+            headers: vec![],
+            body: vec![],
+        };
+
+        let result = verify_certification_absence(
+            &request,
+            &not_found_response(),
+            &[0, 0, 0, 0, 0, 0, 0, 5, 1, 1],
+            get_current_timestamp(),
+            MAX_CERT_TIME_OFFSET_NS,
+            &[],
+        );
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::MissingCertification
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+mod tests {
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+        HttpCertification, HttpCertificationError, HttpCertificationPath,
+        HttpCertificationTreeEntry, HttpRequest, HttpResponse,
+    };
+    use ic_response_verification::{verify_request_response_pair, ResponseVerificationError};
+    use ic_response_verification_test_utils::{create_v2_fixture, get_current_timestamp};
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+    const CERTIFIED_URL: &str = "/assets/app.js";
+
+    fn cel_expr() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Cache-Control"],
+            ))
+            .with_allowed_methods(vec!["GET", "HEAD"])
+            .build()
+    }
+
+    fn verify_response(method: &str) -> Result<(), ResponseVerificationError> {
+        let cel_expr = cel_expr();
+        let current_time = get_current_timestamp();
+        let certification_path = HttpCertificationPath::Exact(CERTIFIED_URL);
+        let request = HttpRequest {
+            url: CERTIFIED_URL.into(),
+            method: method.into(),
+            headers: vec![],
+            body: vec![],
+        };
+
+        let mut response = HttpResponse {
+            status_code: 200,
+            body: vec![],
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "max-age=604800".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture(CERTIFIED_URL, &certification_tree_entry, &current_time);
+
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        verify_request_response_pair(
+            request,
+            response,
+            v2_fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &v2_fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        )
+        .map(|_| ())
+    }
+
+    #[rstest]
+    #[case::get("GET")]
+    #[case::head("HEAD")]
+    #[case::lowercase_get("get")]
+    fn allowed_method_passes_verification(#[case] method: &str) {
+        assert!(verify_response(method).is_ok());
+    }
+
+    #[rstest]
+    #[case::post("POST")]
+    #[case::delete("DELETE")]
+    fn disallowed_method_fails_verification(#[case] method: &str) {
+        assert!(matches!(
+            verify_response(method),
+            Err(ResponseVerificationError::HttpCertificationError(
+                HttpCertificationError::UnexpectedRequestMethod { expected, found }
+            )) if expected == "GET, HEAD" && found == method
+        ));
+    }
+}
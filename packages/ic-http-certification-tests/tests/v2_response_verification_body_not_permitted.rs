@@ -0,0 +1,128 @@
+mod tests {
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+        HttpCertification, HttpCertificationError, HttpCertificationPath,
+        HttpCertificationTreeEntry, HttpRequest, HttpResponse,
+    };
+    use ic_response_verification::{
+        verify_request_response_pair, verify_request_response_pair_with_profile,
+        ResponseVerificationError, VerificationProfile,
+    };
+    use ic_response_verification_test_utils::{create_v2_fixture, get_current_timestamp};
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+    const CERTIFIED_URL: &str = "/api/todos/1";
+
+    fn cel_expr() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Cache-Control"],
+            ))
+            .with_status_code_class_certification()
+            .build()
+    }
+
+    fn verify_response(
+        status_code: u16,
+        body: &[u8],
+        profile: Option<VerificationProfile>,
+    ) -> Result<(), ResponseVerificationError> {
+        let cel_expr = cel_expr();
+        let current_time = get_current_timestamp();
+        let certification_path = HttpCertificationPath::Exact(CERTIFIED_URL);
+        let request = HttpRequest {
+            url: CERTIFIED_URL.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+
+        // certified with a status code that permits a body, then served with a status code of
+        // the same class that may not permit one
+        let mut response = HttpResponse {
+            status_code: 200,
+            body: body.to_vec(),
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "no-store".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture(CERTIFIED_URL, &certification_tree_entry, &current_time);
+
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+        response.status_code = status_code;
+
+        match profile {
+            Some(profile) => verify_request_response_pair_with_profile(
+                request,
+                response,
+                v2_fixture.canister_id.as_ref(),
+                current_time,
+                &v2_fixture.root_key,
+                profile,
+            ),
+            None => verify_request_response_pair(
+                request,
+                response,
+                v2_fixture.canister_id.as_ref(),
+                current_time,
+                MAX_CERT_TIME_OFFSET_NS,
+                &v2_fixture.root_key,
+                MIN_REQUESTED_VERIFICATION_VERSION,
+            ),
+        }
+        .map(|_| ())
+    }
+
+    #[rstest]
+    #[case::ok_with_body(200, b"Hello World!")]
+    #[case::accepted_with_body(202, b"Hello World!")]
+    #[case::no_content_without_body(204, b"")]
+    #[case::reset_content_without_body(205, b"")]
+    fn permitted_body_passes_verification(#[case] status_code: u16, #[case] body: &[u8]) {
+        for profile in [
+            None,
+            Some(VerificationProfile::Strict),
+            Some(VerificationProfile::Lenient),
+        ] {
+            assert!(verify_response(status_code, body, profile).is_ok());
+        }
+    }
+
+    #[rstest]
+    #[case::no_content(204)]
+    #[case::reset_content(205)]
+    fn body_with_no_body_status_code_passes_lenient_verification(#[case] status_code: u16) {
+        for profile in [None, Some(VerificationProfile::Lenient)] {
+            assert!(verify_response(status_code, b"Hello World!", profile).is_ok());
+        }
+    }
+
+    #[rstest]
+    #[case::no_content(204)]
+    #[case::reset_content(205)]
+    fn body_with_no_body_status_code_fails_strict_verification(#[case] status_code: u16) {
+        assert!(matches!(
+            verify_response(
+                status_code,
+                b"Hello World!",
+                Some(VerificationProfile::Strict)
+            ),
+            Err(ResponseVerificationError::HttpCertificationError(
+                HttpCertificationError::ResponseBodyNotPermitted { status_code: found }
+            )) if found == status_code
+        ));
+    }
+}
@@ -341,6 +341,7 @@ mod fixtures {
                 ("IC-CertificateExpression".into(), cel.to_string()),
             ],
             upgrade: None,
+            trailers: None,
         }
     }
 
@@ -348,11 +349,10 @@ mod fixtures {
     pub fn index_html_certification() -> HttpCertificationTreeEntry<'static> {
         HttpCertificationTreeEntry {
             path: Cow::Borrowed(&HttpCertificationPath::Wildcard("")),
-            certification: Cow::Owned(HttpCertification::response_only(
-                &asset_cel(),
-                &index_html_response(),
-                None,
-            )),
+            certification: Cow::Owned(
+                HttpCertification::response_only(&asset_cel(), index_html_response(), None)
+                    .unwrap(),
+            ),
         }
     }
 
@@ -370,6 +370,7 @@ mod fixtures {
                 ("IC-CertificateExpression".into(), cel.to_string()),
             ],
             upgrade: None,
+            trailers: None,
         }
     }
 
@@ -377,11 +378,9 @@ mod fixtures {
     pub fn index_js_certification() -> HttpCertificationTreeEntry<'static> {
         HttpCertificationTreeEntry {
             path: Cow::Borrowed(&HttpCertificationPath::Exact("/js/index.js")),
-            certification: Cow::Owned(HttpCertification::response_only(
-                &asset_cel(),
-                &index_js_response(),
-                None,
-            )),
+            certification: Cow::Owned(
+                HttpCertification::response_only(&asset_cel(), index_js_response(), None).unwrap(),
+            ),
         }
     }
 
@@ -399,6 +398,7 @@ mod fixtures {
                 ("IC-CertificateExpression".into(), cel.to_string()),
             ],
             upgrade: None,
+            trailers: None,
         }
     }
 
@@ -406,11 +406,10 @@ mod fixtures {
     pub fn not_found_certification() -> HttpCertificationTreeEntry<'static> {
         HttpCertificationTreeEntry {
             path: Cow::Borrowed(&HttpCertificationPath::Wildcard("/js")),
-            certification: Cow::Owned(HttpCertification::response_only(
-                &asset_cel(),
-                &not_found_response(),
-                None,
-            )),
+            certification: Cow::Owned(
+                HttpCertification::response_only(&asset_cel(), not_found_response(), None)
+                    .unwrap(),
+            ),
         }
     }
 
@@ -427,6 +426,7 @@ mod fixtures {
                 ("IC-CertificateExpression".into(), cel.to_string()),
             ],
             upgrade: None,
+            trailers: None,
         }
     }
 
@@ -434,11 +434,10 @@ mod fixtures {
     pub fn redirect_certification() -> HttpCertificationTreeEntry<'static> {
         HttpCertificationTreeEntry {
             path: Cow::Borrowed(&HttpCertificationPath::Exact("/old-path")),
-            certification: Cow::Owned(HttpCertification::response_only(
-                &redirect_cel(),
-                &redirect_response(),
-                None,
-            )),
+            certification: Cow::Owned(
+                HttpCertification::response_only(&redirect_cel(), redirect_response(), None)
+                    .unwrap(),
+            ),
         }
     }
 
@@ -455,6 +454,7 @@ mod fixtures {
                 ("IC-CertificateExpression".into(), cel.to_string()),
             ],
             upgrade: None,
+            trailers: None,
         }
     }
 
@@ -462,11 +462,14 @@ mod fixtures {
     pub fn content_encoding_identity_certification() -> HttpCertificationTreeEntry<'static> {
         HttpCertificationTreeEntry {
             path: Cow::Borrowed(&HttpCertificationPath::Exact("/multi-encoded-path")),
-            certification: Cow::Owned(HttpCertification::response_only(
-                &asset_cel(),
-                &content_encoding_identity_response(),
-                None,
-            )),
+            certification: Cow::Owned(
+                HttpCertification::response_only(
+                    &asset_cel(),
+                    content_encoding_identity_response(),
+                    None,
+                )
+                .unwrap(),
+            ),
         }
     }
 
@@ -483,6 +486,7 @@ mod fixtures {
                 ("IC-CertificateExpression".into(), cel.to_string()),
             ],
             upgrade: None,
+            trailers: None,
         }
     }
 
@@ -490,11 +494,14 @@ mod fixtures {
     pub fn content_encoding_gzip_certification() -> HttpCertificationTreeEntry<'static> {
         HttpCertificationTreeEntry {
             path: Cow::Borrowed(&HttpCertificationPath::Exact("/multi-encoded-path")),
-            certification: Cow::Owned(HttpCertification::response_only(
-                &asset_cel(),
-                &content_encoding_gzip_response(),
-                None,
-            )),
+            certification: Cow::Owned(
+                HttpCertification::response_only(
+                    &asset_cel(),
+                    content_encoding_gzip_response(),
+                    None,
+                )
+                .unwrap(),
+            ),
         }
     }
 
@@ -511,6 +518,7 @@ mod fixtures {
                 ("IC-CertificateExpression".into(), cel.to_string()),
             ],
             upgrade: None,
+            trailers: None,
         }
     }
 
@@ -518,11 +526,14 @@ mod fixtures {
     pub fn content_encoding_deflate_certification() -> HttpCertificationTreeEntry<'static> {
         HttpCertificationTreeEntry {
             path: Cow::Borrowed(&HttpCertificationPath::Exact("/multi-encoded-path")),
-            certification: Cow::Owned(HttpCertification::response_only(
-                &asset_cel(),
-                &content_encoding_deflate_response(),
-                None,
-            )),
+            certification: Cow::Owned(
+                HttpCertification::response_only(
+                    &asset_cel(),
+                    content_encoding_deflate_response(),
+                    None,
+                )
+                .unwrap(),
+            ),
         }
     }
 
@@ -545,17 +556,16 @@ mod fixtures {
     #[fixture]
     pub fn etag_caching_match_response() -> HttpResponse {
         let cel = etag_caching_match_cel();
-        let body = br#"Not Modified"#;
 
         HttpResponse {
             status_code: 304,
-            body: body.to_vec(),
+            body: vec![],
             headers: vec![
                 ("Content-Type".into(), "text/html".into()),
-                ("Content-Encoding".into(), "deflate".into()),
                 ("IC-CertificateExpression".into(), cel.to_string()),
             ],
             upgrade: None,
+            trailers: None,
         }
     }
 
@@ -568,8 +578,8 @@ mod fixtures {
             certification: Cow::Owned(
                 HttpCertification::full(
                     &etag_caching_match_cel(),
-                    &etag_caching_match_request(),
-                    &etag_caching_match_response(),
+                    etag_caching_match_request(),
+                    etag_caching_match_response(),
                     None,
                 )
                 .unwrap(),
@@ -609,6 +619,7 @@ mod fixtures {
                 ("IC-CertificateExpression".into(), cel.to_string()),
             ],
             upgrade: None,
+            trailers: None,
         }
     }
 
@@ -618,11 +629,14 @@ mod fixtures {
     ) -> HttpCertificationTreeEntry<'static> {
         HttpCertificationTreeEntry {
             path: Cow::Borrowed(path),
-            certification: Cow::Owned(HttpCertification::response_only(
-                &etag_caching_mismatch_cel(),
-                &etag_caching_mismatch_response(),
-                None,
-            )),
+            certification: Cow::Owned(
+                HttpCertification::response_only(
+                    &etag_caching_mismatch_cel(),
+                    etag_caching_mismatch_response(),
+                    None,
+                )
+                .unwrap(),
+            ),
         }
     }
 
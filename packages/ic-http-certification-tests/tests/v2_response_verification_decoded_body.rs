@@ -0,0 +1,130 @@
+mod tests {
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+        HttpCertification, HttpCertificationPath, HttpCertificationTreeEntry, HttpRequest,
+        HttpResponse,
+    };
+    use ic_response_verification::{
+        types::VerifiedResponse, verify_request_response_pair, ResponseVerificationError,
+    };
+    use ic_response_verification_test_utils::{
+        create_v2_fixture, deflate_encode, get_current_timestamp, gzip_encode,
+    };
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+    const BODY: &[u8] = b"Hello World!";
+
+    fn cel_expr(decoded_body: bool) -> DefaultResponseOnlyCelExpression<'static> {
+        let builder = DefaultCelBuilder::response_only_certification().with_response_certification(
+            DefaultResponseCertification::certified_response_headers(vec!["Cache-Control"]),
+        );
+
+        match decoded_body {
+            true => builder.with_decoded_body_certification().build(),
+            false => builder.build(),
+        }
+    }
+
+    fn verify_encoded_response(
+        cel_expr: &DefaultResponseOnlyCelExpression,
+        encoding: Option<&str>,
+        encoded_body: Vec<u8>,
+    ) -> Result<Option<VerifiedResponse>, ResponseVerificationError> {
+        let req_path = "/";
+        let current_time = get_current_timestamp();
+        let certification_path = HttpCertificationPath::Exact("/");
+
+        let request = HttpRequest {
+            url: req_path.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+        let identity_response = HttpResponse {
+            status_code: 200,
+            body: BODY.to_vec(),
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "max-age=604800".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification =
+            HttpCertification::response_only(cel_expr, &identity_response, None).unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture(req_path, &certification_tree_entry, &current_time);
+
+        let mut response = HttpResponse {
+            body: encoded_body,
+            ..identity_response
+        };
+        if let Some(encoding) = encoding {
+            response
+                .headers
+                .push(("Content-Encoding".into(), encoding.into()));
+        }
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        verify_request_response_pair(
+            request,
+            response,
+            v2_fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &v2_fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        )
+        .map(|verification_info| verification_info.response)
+    }
+
+    #[rstest]
+    #[case::identity(None, BODY.to_vec())]
+    #[case::gzip(Some("gzip"), gzip_encode(BODY))]
+    #[case::deflate(Some("deflate"), deflate_encode(BODY))]
+    #[case::brotli(Some("br"), vec![139, 5, 128, 72, 101, 108, 108, 111, 32, 87, 111, 114, 108, 100, 33, 3])]
+    fn decoded_body_certification_covers_all_encodings(
+        #[case] encoding: Option<&str>,
+        #[case] encoded_body: Vec<u8>,
+    ) {
+        let result = verify_encoded_response(&cel_expr(true), encoding, encoded_body.clone());
+
+        assert_eq!(
+            result.unwrap(),
+            Some(VerifiedResponse {
+                status_code: Some(200),
+                headers: vec![("cache-control".into(), "max-age=604800".into())],
+                body: encoded_body,
+            })
+        );
+    }
+
+    #[rstest]
+    fn decoded_body_certification_rejects_modified_body() {
+        let result =
+            verify_encoded_response(&cel_expr(true), Some("gzip"), gzip_encode(b"Hello IC!"));
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::InvalidResponseHashes
+        ));
+    }
+
+    #[rstest]
+    fn as_served_body_certification_rejects_encoded_body() {
+        let result = verify_encoded_response(&cel_expr(false), Some("gzip"), gzip_encode(BODY));
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::InvalidResponseHashes
+        ));
+    }
+}
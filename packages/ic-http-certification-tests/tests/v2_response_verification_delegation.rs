@@ -0,0 +1,183 @@
+mod tests {
+    use ic_certificate_verification::{
+        CertificateVerificationError, DEFAULT_MAX_DELEGATION_CHAIN_LENGTH,
+    };
+    use ic_certification_testing::{CertificateBuilder, CertificateData};
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+        HttpCertification, HttpCertificationPath, HttpCertificationTreeEntry, HttpRequest,
+        HttpResponse,
+    };
+    use ic_response_verification::{
+        verify_request_response_pair_with_max_delegation_chain_length,
+        verify_request_response_pair_with_profile, ResponseVerificationError, VerificationProfile,
+    };
+    use ic_response_verification_test_utils::{
+        create_v2_fixture, create_v2_header, create_v2_tree_fixture, get_current_timestamp,
+        V2TreeFixture,
+    };
+    use ic_types::CanisterId;
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+
+    fn cel_expr() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Cache-Control"],
+            ))
+            .build()
+    }
+
+    fn request() -> HttpRequest {
+        HttpRequest {
+            url: "/".into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        }
+    }
+
+    fn response(cel_expr: &DefaultResponseOnlyCelExpression) -> HttpResponse {
+        HttpResponse {
+            status_code: 200,
+            body: b"Hello World!".to_vec(),
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "no-store".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    #[rstest]
+    #[case::default_chain_length(DEFAULT_MAX_DELEGATION_CHAIN_LENGTH)]
+    #[case::longer_chain_length(2)]
+    fn delegated_certificate_within_max_chain_length_passes_verification(
+        #[case] max_delegation_chain_length: usize,
+    ) {
+        let cel_expr = cel_expr();
+        let current_time = get_current_timestamp();
+        let mut response = response(&cel_expr);
+
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+        let certification_path = HttpCertificationPath::exact("/");
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        // the fixture's certificate has a single delegation
+        let v2_fixture = create_v2_fixture("/", &certification_tree_entry, &current_time);
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        let result = verify_request_response_pair_with_max_delegation_chain_length(
+            request(),
+            response,
+            v2_fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &v2_fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+            max_delegation_chain_length,
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    fn delegated_certificate_exceeding_max_chain_length_fails_verification() {
+        let cel_expr = cel_expr();
+        let current_time = get_current_timestamp();
+        let mut response = response(&cel_expr);
+
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+        let certification_path = HttpCertificationPath::exact("/");
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture("/", &certification_tree_entry, &current_time);
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        let result = verify_request_response_pair_with_max_delegation_chain_length(
+            request(),
+            response,
+            v2_fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &v2_fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+            0,
+        );
+
+        assert!(matches!(
+            result,
+            Err(ResponseVerificationError::CertificateVerificationFailed(
+                CertificateVerificationError::CertificateHasTooManyDelegations
+            ))
+        ));
+    }
+
+    #[rstest]
+    #[case::strict(VerificationProfile::Strict)]
+    #[case::lenient(VerificationProfile::Lenient)]
+    fn nested_delegation_fails_profile_verification(#[case] profile: VerificationProfile) {
+        let cel_expr = cel_expr();
+        let current_time = get_current_timestamp();
+        let canister_id = CanisterId::from_u64(5);
+        let mut response = response(&cel_expr);
+
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+        let certification_path = HttpCertificationPath::exact("/");
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let V2TreeFixture {
+            tree_cbor,
+            certified_data,
+        } = create_v2_tree_fixture("/", &certification_tree_entry);
+        let CertificateData {
+            root_key,
+            certificate: _,
+            cbor_encoded_certificate,
+        } = CertificateBuilder::new(&canister_id.to_string(), &certified_data)
+            .unwrap()
+            .with_time(current_time)
+            .with_delegation(123, vec![(0, 10)])
+            .with_nested_delegation(456, vec![(0, 10)])
+            .build()
+            .unwrap();
+        let certificate_header = create_v2_header(
+            &certification_tree_entry,
+            &cbor_encoded_certificate,
+            &tree_cbor,
+        );
+        response
+            .headers
+            .push(("IC-Certificate".into(), certificate_header));
+
+        assert_eq!(profile.max_delegation_chain_length(), 1);
+
+        let result = verify_request_response_pair_with_profile(
+            request(),
+            response,
+            canister_id.as_ref(),
+            current_time,
+            &root_key,
+            profile,
+        );
+
+        assert!(matches!(
+            result,
+            Err(ResponseVerificationError::CertificateVerificationFailed(
+                CertificateVerificationError::CertificateHasTooManyDelegations
+            ))
+        ));
+    }
+}
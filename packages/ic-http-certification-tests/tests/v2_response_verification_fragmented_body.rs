@@ -0,0 +1,156 @@
+mod tests {
+    use ic_http_certification::{
+        fragmented_body_hash, fragments_header_value, DefaultCelBuilder,
+        DefaultResponseCertification, DefaultResponseOnlyCelExpression, HttpCertification,
+        HttpCertificationError, HttpCertificationPath, HttpCertificationTreeEntry, HttpRequest,
+        HttpResponse, CERTIFICATE_FRAGMENTS_HEADER_NAME,
+    };
+    use ic_response_verification::{
+        types::VerifiedResponse, verify_request_response_pair, ResponseVerificationError,
+    };
+    use ic_response_verification_test_utils::{create_v2_fixture, get_current_timestamp, hash};
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+    const FRAGMENTS: [&[u8]; 3] = [b"<ul>", b"<li>Hello World!</li>", b"</ul>"];
+
+    fn cel_expr() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Cache-Control"],
+            ))
+            .with_fragmented_body_certification()
+            .build()
+    }
+
+    fn verify_fragmented_response(
+        fragment_hashes: &[[u8; 32]],
+        fragments_header: Option<String>,
+        body: Vec<u8>,
+    ) -> Result<Option<VerifiedResponse>, ResponseVerificationError> {
+        let cel_expr = cel_expr();
+        let req_path = "/";
+        let current_time = get_current_timestamp();
+        let certification_path = HttpCertificationPath::Exact("/");
+
+        let request = HttpRequest {
+            url: req_path.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+        let mut response = HttpResponse {
+            status_code: 200,
+            body,
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "max-age=604800".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification = HttpCertification::response_only(
+            &cel_expr,
+            &response,
+            Some(fragmented_body_hash(fragment_hashes)),
+        )
+        .unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture(req_path, &certification_tree_entry, &current_time);
+
+        if let Some(fragments_header) = fragments_header {
+            response
+                .headers
+                .push((CERTIFICATE_FRAGMENTS_HEADER_NAME.into(), fragments_header));
+        }
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        verify_request_response_pair(
+            request,
+            response,
+            v2_fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &v2_fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        )
+        .map(|verification_info| verification_info.response)
+    }
+
+    fn fragment_hashes() -> Vec<[u8; 32]> {
+        FRAGMENTS.iter().map(|fragment| hash(fragment)).collect()
+    }
+
+    fn fragments_header() -> String {
+        fragments_header_value(&FRAGMENTS.map(|fragment| fragment.len()))
+    }
+
+    #[rstest]
+    fn fragmented_body_certification_verifies_fragments() {
+        let result = verify_fragmented_response(
+            &fragment_hashes(),
+            Some(fragments_header()),
+            FRAGMENTS.concat(),
+        );
+
+        assert_eq!(
+            result.unwrap(),
+            Some(VerifiedResponse {
+                status_code: Some(200),
+                headers: vec![("cache-control".into(), "max-age=604800".into())],
+                body: FRAGMENTS.concat(),
+            })
+        );
+    }
+
+    #[rstest]
+    fn fragmented_body_certification_rejects_modified_fragment() {
+        let body = [FRAGMENTS[0], b"<li>Hello IC!!!!</li>", FRAGMENTS[2]].concat();
+
+        let result = verify_fragmented_response(&fragment_hashes(), Some(fragments_header()), body);
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::InvalidResponseHashes
+        ));
+    }
+
+    #[rstest]
+    fn fragmented_body_certification_rejects_modified_fragment_boundaries() {
+        let result = verify_fragmented_response(
+            &fragment_hashes(),
+            Some(fragments_header_value(&[5, 20, 5])),
+            FRAGMENTS.concat(),
+        );
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::InvalidResponseHashes
+        ));
+    }
+
+    #[rstest]
+    #[case::missing_header(None)]
+    #[case::too_short(Some("4,21".to_string()))]
+    #[case::too_long(Some("4,21,6".to_string()))]
+    fn fragmented_body_certification_rejects_malformed_fragments_header(
+        #[case] fragments_header: Option<String>,
+    ) {
+        let result =
+            verify_fragmented_response(&fragment_hashes(), fragments_header, FRAGMENTS.concat());
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::HttpCertificationError(
+                HttpCertificationError::MalformedFragmentsHeader(_)
+            )
+        ));
+    }
+}
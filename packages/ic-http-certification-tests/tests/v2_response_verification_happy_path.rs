@@ -36,6 +36,7 @@ mod tests {
                 ("Cache-Control".into(), "max-age=604800".into()),
             ],
             upgrade: None,
+            trailers: None,
         };
 
         let certification = HttpCertification::skip();
@@ -99,9 +100,10 @@ mod tests {
                 ("Cache-Control".into(), "max-age=604800".into()),
             ],
             upgrade: None,
+            trailers: None,
         };
 
-        let certification = HttpCertification::response_only(&cel_expr, &response, None);
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
         let certification_tree_entry =
             HttpCertificationTreeEntry::new(&certification_path, &certification);
 
@@ -173,6 +175,7 @@ mod tests {
                 ("Cache-Control".into(), "max-age=604800".into()),
             ],
             upgrade: None,
+            trailers: None,
         };
 
         let certification = HttpCertification::full(&cel_expr, &request, &response, None).unwrap();
@@ -245,9 +248,10 @@ mod tests {
                 ("Server".into(), "Apache/2.4.1 (Unix)".into()),
             ],
             upgrade: None,
+            trailers: None,
         };
 
-        let certification = HttpCertification::response_only(&cel_expr, &response, None);
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
         let certification_tree_entry = HttpCertificationTreeEntry::new(&expr_path, &certification);
 
         let V2Fixture {
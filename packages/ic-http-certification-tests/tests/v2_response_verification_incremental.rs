@@ -0,0 +1,182 @@
+mod tests {
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+        HttpCertification, HttpCertificationPath, HttpCertificationTreeEntry, HttpRequest,
+        HttpResponse,
+    };
+    use ic_response_verification::{
+        types::VerifiedResponse, verify_request_response_headers, ResponseVerificationError,
+    };
+    use ic_response_verification_test_utils::{
+        create_v2_fixture, get_current_timestamp, V2Fixture,
+    };
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+
+    #[fixture]
+    fn response_only_cel() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                &["Cache-Control"],
+            ))
+            .build()
+    }
+
+    fn certified_response(
+        cel_expr: &DefaultResponseOnlyCelExpression,
+        body: &str,
+    ) -> (HttpRequest, HttpResponse, V2Fixture, u128) {
+        let req_path = "/";
+        let current_time = get_current_timestamp();
+        let certification_path = HttpCertificationPath::Exact("/");
+
+        let request = HttpRequest {
+            url: req_path.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+        let mut response = HttpResponse {
+            status_code: 200,
+            body: body.as_bytes().to_vec(),
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "max-age=604800".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification = HttpCertification::response_only(cel_expr, &response, None).unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture(req_path, &certification_tree_entry, &current_time);
+
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        (request, response, v2_fixture, current_time)
+    }
+
+    #[rstest]
+    fn chunked_body_passes_verification(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+    ) {
+        let body = "Hello World!";
+        let (request, response, fixture, current_time) = certified_response(&cel_expr, body);
+
+        let mut verification = verify_request_response_headers(
+            request,
+            response.status_code,
+            &response.headers,
+            fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        )
+        .unwrap();
+
+        assert_eq!(verification.verification_version, 2);
+        assert_eq!(
+            verification.response,
+            Some(VerifiedResponse {
+                status_code: Some(200),
+                headers: vec![("cache-control".into(), "max-age=604800".into())],
+                body: vec![],
+            })
+        );
+
+        for chunk in body.as_bytes().chunks(5) {
+            verification.update(chunk);
+        }
+        verification.finish().unwrap();
+    }
+
+    #[rstest]
+    fn modified_body_fails_verification(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+    ) {
+        let (request, response, fixture, current_time) =
+            certified_response(&cel_expr, "Hello World!");
+
+        let mut verification = verify_request_response_headers(
+            request,
+            response.status_code,
+            &response.headers,
+            fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        )
+        .unwrap();
+
+        verification.update(b"Hello ");
+        verification.update(b"IC!");
+
+        assert!(matches!(
+            verification.finish().unwrap_err(),
+            ResponseVerificationError::InvalidResponseHashes
+        ));
+    }
+
+    #[rstest]
+    fn modified_header_fails_verification(
+        #[from(response_only_cel)] cel_expr: DefaultResponseOnlyCelExpression<'static>,
+    ) {
+        let body = "Hello World!";
+        let (request, mut response, fixture, current_time) = certified_response(&cel_expr, body);
+        response.headers[1] = ("Cache-Control".into(), "no-store".into());
+
+        let mut verification = verify_request_response_headers(
+            request,
+            response.status_code,
+            &response.headers,
+            fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        )
+        .unwrap();
+
+        verification.update(body.as_bytes());
+
+        assert!(matches!(
+            verification.finish().unwrap_err(),
+            ResponseVerificationError::InvalidResponseHashes
+        ));
+    }
+
+    #[rstest]
+    fn missing_certificate_fails_before_body() {
+        let request = HttpRequest {
+            url: "/".into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+
+        let result = verify_request_response_headers(
+            request,
+            200,
+            &[("Cache-Control".into(), "max-age=604800".into())],
+            &[0, 0, 0, 0, 0, 0, 0, 1],
+            get_current_timestamp(),
+            MAX_CERT_TIME_OFFSET_NS,
+            &[],
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        );
+
+        assert!(matches!(
+            result.unwrap_err(),
+            ResponseVerificationError::MissingCertification
+        ));
+    }
+}
@@ -0,0 +1,95 @@
+mod tests {
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultFullCelExpression, DefaultResponseCertification,
+        HttpCertification, HttpCertificationPath, HttpCertificationTreeEntry, HttpRequest,
+        HttpResponse,
+    };
+    use ic_response_verification::{verify_request_response_pair, ResponseVerificationError};
+    use ic_response_verification_test_utils::{create_v2_fixture, get_current_timestamp};
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+    const CERTIFIED_URL: &str = "/items?tag=a&tag=b";
+
+    fn cel_expr() -> DefaultFullCelExpression<'static> {
+        DefaultCelBuilder::full_certification()
+            .with_request_query_parameters(vec!["tag"])
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Cache-Control"],
+            ))
+            .build()
+    }
+
+    fn request(url: &str) -> HttpRequest {
+        HttpRequest {
+            url: url.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        }
+    }
+
+    fn verify_request(url: &str) -> Result<(), ResponseVerificationError> {
+        let cel_expr = cel_expr();
+        let current_time = get_current_timestamp();
+        let certification_path = HttpCertificationPath::Exact("/items");
+
+        let mut response = HttpResponse {
+            status_code: 200,
+            body: br#"["a","b"]"#.to_vec(),
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "max-age=604800".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification =
+            HttpCertification::full(&cel_expr, &request(CERTIFIED_URL), &response, None).unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture(url, &certification_tree_entry, &current_time);
+
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        verify_request_response_pair(
+            request(url),
+            response,
+            v2_fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &v2_fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        )
+        .map(|_| ())
+    }
+
+    #[rstest]
+    #[case::same_parameters(CERTIFIED_URL)]
+    #[case::uncertified_parameter_between_repeated_parameters("/items?tag=a&page=2&tag=b")]
+    #[case::uncertified_parameters_around_repeated_parameters("/items?page=2&tag=a&tag=b&sort=asc")]
+    fn repeated_query_parameters_pass_verification(#[case] url: &str) {
+        assert!(verify_request(url).is_ok());
+    }
+
+    #[rstest]
+    #[case::reordered_parameters("/items?tag=b&tag=a")]
+    #[case::missing_parameter("/items?tag=a")]
+    #[case::additional_parameter("/items?tag=a&tag=b&tag=c")]
+    #[case::duplicated_parameter("/items?tag=a&tag=a&tag=b")]
+    #[case::different_name_casing("/items?TAG=a&TAG=b")]
+    #[case::percent_encoded_value("/items?tag=%61&tag=b")]
+    #[case::no_query_string("/items")]
+    fn changed_repeated_query_parameters_fail_verification(#[case] url: &str) {
+        assert!(matches!(
+            verify_request(url),
+            Err(ResponseVerificationError::InvalidResponseHashes)
+        ));
+    }
+}
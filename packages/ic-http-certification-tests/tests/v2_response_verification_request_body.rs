@@ -0,0 +1,92 @@
+mod tests {
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultFullCelExpression, DefaultResponseCertification,
+        HttpCertification, HttpCertificationPath, HttpCertificationTreeEntry, HttpRequest,
+        HttpResponse,
+    };
+    use ic_response_verification::{verify_request_response_pair, ResponseVerificationError};
+    use ic_response_verification_test_utils::{create_v2_fixture, get_current_timestamp};
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+    const CERTIFIED_URL: &str = "/api/todos";
+    const CERTIFIED_BODY: &[u8] = br#"{"title":"buy milk"}"#;
+
+    fn cel_expr() -> DefaultFullCelExpression<'static> {
+        DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Content-Type"])
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Content-Type"],
+            ))
+            .build()
+    }
+
+    fn request(body: &[u8]) -> HttpRequest {
+        HttpRequest {
+            url: CERTIFIED_URL.into(),
+            method: "POST".into(),
+            headers: vec![("Content-Type".into(), "application/json".into())],
+            body: body.to_vec(),
+        }
+    }
+
+    fn verify_request(body: &[u8]) -> Result<(), ResponseVerificationError> {
+        let cel_expr = cel_expr();
+        let current_time = get_current_timestamp();
+        let certification_path = HttpCertificationPath::Exact(CERTIFIED_URL);
+
+        let mut response = HttpResponse {
+            status_code: 201,
+            body: br#"{"id":1,"title":"buy milk"}"#.to_vec(),
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Content-Type".into(), "application/json".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification =
+            HttpCertification::full(&cel_expr, &request(CERTIFIED_BODY), &response, None).unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture(CERTIFIED_URL, &certification_tree_entry, &current_time);
+
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        verify_request_response_pair(
+            request(body),
+            response,
+            v2_fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &v2_fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        )
+        .map(|_| ())
+    }
+
+    #[rstest]
+    fn same_request_body_passes_verification() {
+        assert!(verify_request(CERTIFIED_BODY).is_ok());
+    }
+
+    #[rstest]
+    #[case::different_value(br#"{"title":"buy eggs"}"#)]
+    #[case::same_length(br#"{"title":"buy silk"}"#)]
+    #[case::different_whitespace(br#"{ "title": "buy milk" }"#)]
+    #[case::trailing_newline(b"{\"title\":\"buy milk\"}\n")]
+    #[case::truncated(br#"{"title":"buy milk""#)]
+    #[case::empty(b"")]
+    fn changed_request_body_fails_verification(#[case] body: &[u8]) {
+        assert!(matches!(
+            verify_request(body),
+            Err(ResponseVerificationError::InvalidResponseHashes)
+        ));
+    }
+}
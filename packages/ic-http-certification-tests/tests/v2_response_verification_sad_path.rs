@@ -53,6 +53,7 @@ mod tests {
                 ("Cache-Control".into(), "max-age=604800".into()),
             ],
             upgrade: None,
+            trailers: None,
         };
 
         let certification = HttpCertification::full(&cel_expr, &request, &response, None).unwrap();
@@ -111,6 +112,7 @@ mod tests {
                 ("Cache-Control".into(), "max-age=604800".into()),
             ],
             upgrade: None,
+            trailers: None,
         };
         let mut wrong_response = HttpResponse {
             status_code: 200,
@@ -121,6 +123,7 @@ mod tests {
                 ("Cache-Control".into(), "immutable".into()),
             ],
             upgrade: None,
+            trailers: None,
         };
 
         let certification = HttpCertification::full(&cel_expr, &request, &response, None).unwrap();
@@ -183,6 +186,7 @@ mod tests {
                 ("Cache-Control".into(), "max-age=604800".into()),
             ],
             upgrade: None,
+            trailers: None,
         };
 
         let certification = HttpCertification::full(&cel_expr, &request, &response, None).unwrap();
@@ -243,6 +247,7 @@ mod tests {
             body: b"Hello World!".to_vec(),
             headers: vec![("IC-CertificateExpression".to_string(), cel_expr.to_string())],
             upgrade: None,
+            trailers: None,
         };
 
         let certification = HttpCertification::skip();
@@ -343,6 +348,7 @@ mod tests {
                 ("Cache-Control".into(), "max-age=604800".into()),
             ],
             upgrade: None,
+            trailers: None,
         };
 
         response
@@ -0,0 +1,88 @@
+mod tests {
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+        HttpCertification, HttpCertificationPath, HttpCertificationTreeEntry, HttpRequest,
+        HttpResponse,
+    };
+    use ic_response_verification::{verify_request_response_pair, ResponseVerificationError};
+    use ic_response_verification_test_utils::{create_v2_fixture, get_current_timestamp};
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+    const CERTIFIED_URL: &str = "/api/todos/1";
+
+    fn cel_expr() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Cache-Control"],
+            ))
+            .with_status_code_class_certification()
+            .build()
+    }
+
+    fn verify_response(status_code: u16) -> Result<(), ResponseVerificationError> {
+        let cel_expr = cel_expr();
+        let current_time = get_current_timestamp();
+        let certification_path = HttpCertificationPath::Exact(CERTIFIED_URL);
+        let request = HttpRequest {
+            url: CERTIFIED_URL.into(),
+            method: "DELETE".into(),
+            headers: vec![],
+            body: vec![],
+        };
+
+        let mut response = HttpResponse {
+            status_code: 200,
+            body: vec![],
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "no-store".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture(CERTIFIED_URL, &certification_tree_entry, &current_time);
+
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+        response.status_code = status_code;
+
+        verify_request_response_pair(
+            request,
+            response,
+            v2_fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &v2_fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        )
+        .map(|_| ())
+    }
+
+    #[rstest]
+    #[case::ok(200)]
+    #[case::accepted(202)]
+    #[case::no_content(204)]
+    fn same_status_code_class_passes_verification(#[case] status_code: u16) {
+        assert!(verify_response(status_code).is_ok());
+    }
+
+    #[rstest]
+    #[case::not_modified(304)]
+    #[case::not_found(404)]
+    #[case::internal_server_error(500)]
+    fn different_status_code_class_fails_verification(#[case] status_code: u16) {
+        assert!(matches!(
+            verify_response(status_code),
+            Err(ResponseVerificationError::InvalidResponseHashes)
+        ));
+    }
+}
@@ -0,0 +1,101 @@
+mod tests {
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+        HeaderField, HttpCertification, HttpCertificationPath, HttpCertificationTreeEntry,
+        HttpRequest, HttpResponse,
+    };
+    use ic_response_verification::{verify_request_response_pair, ResponseVerificationError};
+    use ic_response_verification_test_utils::{create_v2_fixture, get_current_timestamp};
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+    const CERTIFIED_URL: &str = "/assets/app.js";
+
+    fn cel_expr() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Content-Type", "Digest"],
+            ))
+            .build()
+    }
+
+    fn trailers(digest: &str) -> Option<Vec<HeaderField>> {
+        Some(vec![
+            ("Digest".into(), digest.into()),
+            ("Server-Timing".into(), "total;dur=12".into()),
+        ])
+    }
+
+    fn verify_response(
+        trailers: Option<Vec<HeaderField>>,
+    ) -> Result<(), ResponseVerificationError> {
+        let cel_expr = cel_expr();
+        let current_time = get_current_timestamp();
+        let certification_path = HttpCertificationPath::Exact(CERTIFIED_URL);
+        let request = HttpRequest {
+            url: CERTIFIED_URL.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+
+        let mut response = HttpResponse {
+            status_code: 200,
+            body: b"console.log('Hello World!');".to_vec(),
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Content-Type".into(), "text/javascript".into()),
+            ],
+            upgrade: None,
+            trailers: self::trailers("sha-256=abc"),
+        };
+
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture = create_v2_fixture(CERTIFIED_URL, &certification_tree_entry, &current_time);
+
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+        response.trailers = trailers;
+
+        verify_request_response_pair(
+            request,
+            response,
+            v2_fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &v2_fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        )
+        .map(|_| ())
+    }
+
+    #[rstest]
+    #[case::same_trailers(trailers("sha-256=abc"))]
+    #[case::uncertified_trailer_changed(Some(vec![
+        ("Digest".into(), "sha-256=abc".into()),
+        ("Server-Timing".into(), "total;dur=48".into()),
+    ]))]
+    #[case::different_name_casing(Some(vec![("digest".into(), "sha-256=abc".into())]))]
+    fn certified_trailers_pass_verification(#[case] trailers: Option<Vec<HeaderField>>) {
+        assert!(verify_response(trailers).is_ok());
+    }
+
+    #[rstest]
+    #[case::different_value(trailers("sha-256=def"))]
+    #[case::missing_trailers(None)]
+    #[case::missing_certified_trailer(Some(vec![
+        ("Server-Timing".into(), "total;dur=12".into()),
+    ]))]
+    fn changed_trailers_fail_verification(#[case] trailers: Option<Vec<HeaderField>>) {
+        assert!(matches!(
+            verify_response(trailers),
+            Err(ResponseVerificationError::InvalidResponseHashes)
+        ));
+    }
+}
@@ -0,0 +1,102 @@
+mod tests {
+    use ic_http_certification::{
+        DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+        HttpCertification, HttpCertificationPath, HttpCertificationTreeEntry, HttpRequest,
+        HttpResponse,
+    };
+    use ic_response_verification::{verify_request_response_pair, ResponseVerificationError};
+    use ic_response_verification_test_utils::{create_v2_fixture, get_current_timestamp};
+    use rstest::*;
+
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+    const MIN_REQUESTED_VERIFICATION_VERSION: u8 = 2;
+
+    fn cel_expr() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Cache-Control"],
+            ))
+            .build()
+    }
+
+    fn verify_response(url: &str, certified_path: &str) -> Result<(), ResponseVerificationError> {
+        let cel_expr = cel_expr();
+        let current_time = get_current_timestamp();
+        let request = HttpRequest {
+            url: url.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+        let mut response = HttpResponse {
+            status_code: 200,
+            body: b"Hello World!".to_vec(),
+            headers: vec![
+                ("IC-CertificateExpression".into(), cel_expr.to_string()),
+                ("Cache-Control".into(), "no-store".into()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+        let certification_path = HttpCertificationPath::exact(certified_path);
+        let certification_tree_entry =
+            HttpCertificationTreeEntry::new(&certification_path, &certification);
+
+        let v2_fixture =
+            create_v2_fixture(certified_path, &certification_tree_entry, &current_time);
+
+        response.headers.push((
+            "IC-Certificate".into(),
+            v2_fixture.certificate_header.clone(),
+        ));
+
+        verify_request_response_pair(
+            request,
+            response,
+            v2_fixture.canister_id.as_ref(),
+            current_time,
+            MAX_CERT_TIME_OFFSET_NS,
+            &v2_fixture.root_key,
+            MIN_REQUESTED_VERIFICATION_VERSION,
+        )
+        .map(|_| ())
+    }
+
+    #[rstest]
+    #[case::dot_dot_segment("/assets/js/../app.js")]
+    #[case::dot_segment("/assets/./app.js")]
+    #[case::trailing_dot_dot_segment("/assets/js/..")]
+    #[case::encoded_slash("/assets%2Fapp.js")]
+    #[case::encoded_dot_dot_segment("/assets/%2E%2E/app.js")]
+    #[case::trailing_slash("/assets/")]
+    #[case::empty_segments("/assets//app.js")]
+    fn canister_and_verifier_agree_on_the_request_path(#[case] url: &str) {
+        let request = HttpRequest {
+            url: url.into(),
+            method: "GET".into(),
+            headers: vec![],
+            body: vec![],
+        };
+        let certified_path = request.canonical_path().unwrap();
+
+        assert!(verify_response(url, &certified_path).is_ok());
+    }
+
+    #[rstest]
+    #[case::dot_dot_segment("/assets/js/../app.js", "/assets/js/../app.js")]
+    #[case::encoded_slash("/assets%2Fapp.js", "/assets/app.js")]
+    #[case::encoded_dot_dot_segment("/assets/%2E%2E/app.js", "/app.js")]
+    #[case::trailing_slash("/assets/", "/assets")]
+    #[case::missing_trailing_slash("/assets", "/assets/")]
+    fn differently_normalized_path_fails_verification(
+        #[case] url: &str,
+        #[case] certified_path: &str,
+    ) {
+        assert!(matches!(
+            verify_response(url, certified_path),
+            Err(ResponseVerificationError::InvalidExpressionPath)
+        ));
+    }
+}
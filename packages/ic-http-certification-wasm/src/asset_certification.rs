@@ -0,0 +1,995 @@
+use crate::cel_builder::{response_certification_from_js, ResponseCertification};
+use flate2::{
+    write::{DeflateEncoder, GzEncoder},
+    Compression,
+};
+use ic_http_certification::{
+    cel, response_hash, HttpCertification, HttpCertificationPath, HttpCertificationTree,
+    HttpCertificationTreeEntry, HttpResponse, CERTIFICATE_EXPRESSION_HEADER_NAME,
+};
+use ic_representation_independent_hash::hash;
+use js_sys::{Array, JsString, Object, Uint8Array};
+use serde::Serialize;
+use std::io::Write;
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[wasm_bindgen(typescript_custom_section)]
+const ASSET: &'static str = r#"
+interface Asset {
+    path: string;
+    content: Uint8Array;
+    headers?: [string, string][];
+}
+
+type AssetEncoding = 'gzip' | 'deflate';
+
+interface AssetFingerprintConfig {
+    exclude?: string[];
+    manifest?: string;
+}
+
+interface AssetCertificationConfig {
+    responseCertification?: ResponseCertification;
+    encodings?: AssetEncoding[];
+    fingerprint?: AssetFingerprintConfig;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Asset")]
+    pub type JsAsset;
+
+    #[wasm_bindgen(typescript_type = "AssetCertificationConfig")]
+    pub type JsAssetCertificationConfig;
+}
+
+/// A certified asset, as returned by [certify_assets].
+#[derive(Debug, Clone)]
+#[wasm_bindgen(inspectable, getter_with_clone)]
+pub struct CertifiedAsset {
+    /// The path that the asset is certified for.
+    pub path: String,
+
+    /// The `Content-Encoding` of the certified response, if it is an encoded variant of the asset.
+    #[wasm_bindgen(js_name = contentEncoding)]
+    pub content_encoding: Option<String>,
+
+    /// The status code of the certified response.
+    #[wasm_bindgen(js_name = statusCode)]
+    pub status_code: u16,
+
+    /// The headers of the certified response, including the `IC-CertificateExpression` header.
+    /// These headers must be served exactly as they are for the response to pass verification.
+    pub headers: JsValue,
+
+    /// The body of the certified response.
+    /// For encoded variants, this is the encoded content of the asset.
+    pub body: Vec<u8>,
+
+    /// The `expr_path` field of the `IC-Certificate` header, CBOR encoded.
+    #[wasm_bindgen(js_name = exprPath)]
+    pub expr_path: Vec<u8>,
+
+    /// The `tree` field of the `IC-Certificate` header, CBOR encoded.
+    /// This is a witness proving the presence of the asset's certification in the tree.
+    pub witness: Vec<u8>,
+
+    /// The hash of the CEL expression used to certify the asset.
+    #[wasm_bindgen(js_name = celExpressionHash)]
+    pub cel_expression_hash: Vec<u8>,
+
+    /// The hash of the certified response.
+    #[wasm_bindgen(js_name = responseHash)]
+    pub response_hash: Vec<u8>,
+}
+
+/// The result of certifying a set of assets with [certify_assets].
+#[derive(Debug, Clone)]
+#[wasm_bindgen(inspectable, getter_with_clone)]
+pub struct CertifiedAssets {
+    /// The certified assets, in the same order as they were provided.
+    /// Every asset is followed by its encoded variants, if any, and the redirects from the
+    /// original paths of fingerprinted assets are at the end.
+    pub assets: Vec<CertifiedAsset>,
+
+    /// The full HTTP certification tree, CBOR encoded.
+    pub tree: Vec<u8>,
+
+    /// The root hash of the HTTP certification tree.
+    /// This should be set as the canister's certified data.
+    #[wasm_bindgen(js_name = rootHash)]
+    pub root_hash: Vec<u8>,
+}
+
+struct Asset {
+    path: String,
+    content: Vec<u8>,
+    headers: Vec<(String, String)>,
+}
+
+#[derive(Default)]
+struct AssetCertificationConfig {
+    response_certification: Option<ResponseCertification>,
+    encodings: Vec<AssetEncoding>,
+    fingerprint: Option<AssetFingerprintConfig>,
+}
+
+#[derive(Default)]
+struct AssetFingerprintConfig {
+    exclude: Vec<String>,
+    manifest: Option<String>,
+}
+
+/// The `Cache-Control` header value of fingerprinted assets, unless they already have one.
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// The number of bytes of an asset's hash that are included in its fingerprinted path.
+const FINGERPRINT_LEN: usize = 4;
+
+/// The MIME types, other than `text/*`, of assets that are compressed when encodings are
+/// configured.
+const COMPRESSIBLE_MIME_TYPES: &[&str] = &[
+    "application/javascript",
+    "application/json",
+    "application/manifest+json",
+    "application/wasm",
+    "application/xml",
+    "image/svg+xml",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AssetEncoding {
+    Gzip,
+    Deflate,
+}
+
+impl AssetEncoding {
+    fn from_js(encoding: JsValue) -> Result<Self, JsError> {
+        match encoding.as_string().as_deref() {
+            Some("gzip") => Ok(AssetEncoding::Gzip),
+            Some("deflate") => Ok(AssetEncoding::Deflate),
+            _ => Err(JsError::new(
+                "Asset encodings must be either \"gzip\" or \"deflate\"",
+            )),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            AssetEncoding::Gzip => "gzip",
+            AssetEncoding::Deflate => "deflate",
+        }
+    }
+
+    fn encode(&self, content: &[u8]) -> Result<Vec<u8>, JsError> {
+        let encoded = match self {
+            AssetEncoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+                encoder.write_all(content)?;
+                encoder.finish()?
+            }
+            AssetEncoding::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+                encoder.write_all(content)?;
+                encoder.finish()?
+            }
+        };
+
+        Ok(encoded)
+    }
+}
+
+/// Certifies a set of static assets, such as the output of a frontend build.
+///
+/// Every asset is certified for its exact path, with a `200` status code. This allows the
+/// certifications, witnesses and the certification tree to be precomputed outside of a canister
+/// and uploaded to a canister that only needs to serve them.
+///
+/// By default, all response headers are certified. This can be changed with the
+/// `responseCertification` property of the `config` parameter.
+///
+/// If the `encodings` property of the `config` parameter is set, text based assets, as determined
+/// by their `Content-Type` header, are also compressed with each of the given encodings. Every
+/// encoded variant is certified for the same path as the identity variant, together with a
+/// `Content-Encoding` header, so that the canister can choose a variant based on the request's
+/// `Accept-Encoding` header. Encoded variants that are not smaller than the identity variant are
+/// omitted.
+///
+/// If the `fingerprint` property of the `config` parameter is set, assets are certified for a
+/// path that includes a hash of their content, such as `/app.1a2b3c4d.js`, so that they can be
+/// cached indefinitely. The original path of every fingerprinted asset is certified as a `307`
+/// redirect to its fingerprinted path. Paths listed in `exclude` keep their original path, and
+/// the asset at the `manifest` path keeps its original path and has every string in its content
+/// that is equal to an original path replaced with the corresponding fingerprinted path.
+#[wasm_bindgen(js_name = certifyAssets)]
+pub fn certify_assets(
+    assets: Vec<JsAsset>,
+    config: Option<JsAssetCertificationConfig>,
+) -> Result<CertifiedAssets, JsError> {
+    let mut assets = assets
+        .into_iter()
+        .map(asset_from_js)
+        .collect::<Result<Vec<_>, _>>()?;
+    let config = match config {
+        Some(config) => config_from_js(config)?,
+        None => AssetCertificationConfig::default(),
+    };
+    let response_certification = config
+        .response_certification
+        .unwrap_or(ResponseCertification::ResponseHeaderExclusions(vec![]));
+
+    let cel_expr = cel::DefaultCelBuilder::response_only_certification()
+        .with_response_certification(response_certification.to_cel())
+        .build();
+    let cel_expr_str = cel_expr.to_string();
+    let cel_expression_hash = hash(cel_expr_str.as_bytes()).to_vec();
+
+    let fingerprints = match &config.fingerprint {
+        Some(fingerprint_config) => fingerprint_assets(&mut assets, fingerprint_config)?,
+        None => vec![],
+    };
+
+    let mut responses = Vec::new();
+    for asset in assets {
+        for (content_encoding, response) in
+            asset_responses(asset.headers, asset.content, &config.encodings)?
+        {
+            responses.push((asset.path.clone(), content_encoding, response));
+        }
+    }
+    for (original_path, fingerprinted_path) in fingerprints {
+        responses.push((original_path, None, redirect_response(fingerprinted_path)));
+    }
+
+    let mut tree = HttpCertificationTree::default();
+    let mut certified_assets = Vec::new();
+    for (path, content_encoding, mut response) in responses {
+        response.headers.push((
+            CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+            cel_expr_str.clone(),
+        ));
+
+        let certification = HttpCertification::response_only(&cel_expr, &response, None)?;
+        let entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact(path.clone()),
+            certification,
+        );
+        tree.insert(&entry);
+
+        certified_assets.push((path, content_encoding, response, entry));
+    }
+
+    let assets = certified_assets
+        .into_iter()
+        .map(|(path, content_encoding, response, entry)| {
+            Ok(CertifiedAsset {
+                content_encoding,
+                status_code: response.status_code,
+                headers: headers_to_js(&response.headers),
+                expr_path: cbor_encode(&entry.path.to_expr_path())?,
+                witness: cbor_encode(&tree.witness(&entry, &path))?,
+                cel_expression_hash: cel_expression_hash.clone(),
+                response_hash: response_hash(&response, &cel_expr.response, None)?.to_vec(),
+                body: response.body,
+                path,
+            })
+        })
+        .collect::<Result<_, JsError>>()?;
+
+    Ok(CertifiedAssets {
+        assets,
+        tree: cbor_encode(&tree.to_hash_tree())?,
+        root_hash: tree.root_hash().to_vec(),
+    })
+}
+
+fn asset_from_js(asset: JsAsset) -> Result<Asset, JsError> {
+    let path_str = JsString::from("path");
+    let content_str = JsString::from("content");
+    let headers_str = JsString::from("headers");
+
+    let mut path = None;
+    let mut content = None;
+    let mut headers = Vec::new();
+
+    let asset = Object::unchecked_from_js(JsValue::from(asset));
+    for entry in Object::entries(&asset).iter() {
+        let entry = Array::unchecked_from_js(entry);
+        let k = JsString::unchecked_from_js(entry.get(0));
+
+        if k == path_str {
+            path = entry.get(1).as_string();
+        }
+
+        if k == content_str {
+            content = entry
+                .get(1)
+                .dyn_into::<Uint8Array>()
+                .ok()
+                .map(|content| content.to_vec());
+        }
+
+        if k == headers_str && !entry.get(1).is_undefined() {
+            let headers_v = Array::unchecked_from_js(entry.get(1));
+            for header in headers_v.iter() {
+                let header = Array::unchecked_from_js(header);
+                let header_name = header.get(0).as_string();
+                let header_val = header.get(1).as_string();
+
+                match (header_name, header_val) {
+                    (Some(header_name), Some(header_val)) => {
+                        headers.push((header_name, header_val))
+                    }
+                    _ => {
+                        return Err(JsError::new(
+                            "Asset headers must be an array of [string, string] tuples",
+                        ))
+                    }
+                }
+            }
+        }
+    }
+
+    let path = path.ok_or_else(|| JsError::new("Asset must have a string `path` property"))?;
+    let content = content.ok_or_else(|| {
+        JsError::new(&format!(
+            "Asset {path} must have a Uint8Array `content` property"
+        ))
+    })?;
+
+    Ok(Asset {
+        path,
+        content,
+        headers,
+    })
+}
+
+/// Moves every asset that is not excluded by the config to its fingerprinted path, rewrites the
+/// manifest, if any, and returns the original and fingerprinted paths of the moved assets.
+fn fingerprint_assets(
+    assets: &mut [Asset],
+    config: &AssetFingerprintConfig,
+) -> Result<Vec<(String, String)>, JsError> {
+    let mut fingerprints = Vec::new();
+    for asset in assets.iter_mut() {
+        if config.exclude.contains(&asset.path) || config.manifest.as_ref() == Some(&asset.path) {
+            continue;
+        }
+
+        let fingerprinted_path = fingerprinted_path(&asset.path, &asset.content);
+        let original_path = std::mem::replace(&mut asset.path, fingerprinted_path.clone());
+        if !asset
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("Cache-Control"))
+        {
+            asset.headers.push((
+                "Cache-Control".to_string(),
+                IMMUTABLE_CACHE_CONTROL.to_string(),
+            ));
+        }
+
+        fingerprints.push((original_path, fingerprinted_path));
+    }
+
+    if let Some(manifest) = &config.manifest {
+        let manifest_asset = assets
+            .iter_mut()
+            .find(|asset| &asset.path == manifest)
+            .ok_or_else(|| {
+                JsError::new(&format!("Manifest {manifest} must be one of the assets"))
+            })?;
+
+        manifest_asset.content = rewrite_manifest(&manifest_asset.content, &fingerprints)
+            .ok_or_else(|| JsError::new(&format!("Manifest {manifest} must be valid UTF-8")))?;
+    }
+
+    Ok(fingerprints)
+}
+
+/// Inserts the first [FINGERPRINT_LEN] bytes of the content's hash, hex encoded, before the
+/// extension of the path's file name, or appends them if the file name has no extension.
+fn fingerprinted_path(path: &str, content: &[u8]) -> String {
+    let fingerprint = hash(content)[..FINGERPRINT_LEN]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    let (dir, file_name) = path.split_at(path.rfind('/').map_or(0, |i| i + 1));
+
+    match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => {
+            format!("{dir}{stem}.{fingerprint}.{extension}")
+        }
+        _ => format!("{dir}{file_name}.{fingerprint}"),
+    }
+}
+
+/// Replaces every JSON string in the manifest that is equal to an original path, with or without
+/// its leading slash, with the corresponding fingerprinted path.
+fn rewrite_manifest(manifest: &[u8], fingerprints: &[(String, String)]) -> Option<Vec<u8>> {
+    let mut manifest = String::from_utf8(manifest.to_vec()).ok()?;
+    for (original_path, fingerprinted_path) in fingerprints {
+        manifest = manifest.replace(
+            &format!("\"{original_path}\""),
+            &format!("\"{fingerprinted_path}\""),
+        );
+
+        if let (Some(original_path), Some(fingerprinted_path)) = (
+            original_path.strip_prefix('/'),
+            fingerprinted_path.strip_prefix('/'),
+        ) {
+            manifest = manifest.replace(
+                &format!("\"{original_path}\""),
+                &format!("\"{fingerprinted_path}\""),
+            );
+        }
+    }
+
+    Some(manifest.into_bytes())
+}
+
+/// Returns a `307` redirect to the given location.
+fn redirect_response(location: String) -> HttpResponse {
+    HttpResponse {
+        status_code: 307,
+        headers: vec![("Location".to_string(), location)],
+        body: vec![],
+        upgrade: None,
+        trailers: None,
+    }
+}
+
+/// Returns the identity response of an asset, followed by the encoded variants of the response
+/// for each of the given encodings, if the asset is compressible.
+fn asset_responses(
+    headers: Vec<(String, String)>,
+    content: Vec<u8>,
+    encodings: &[AssetEncoding],
+) -> Result<Vec<(Option<String>, HttpResponse)>, JsError> {
+    let mut encoded_responses = Vec::new();
+    if is_compressible(&headers) {
+        for encoding in encodings {
+            let encoded_content = encoding.encode(&content)?;
+            if encoded_content.len() >= content.len() {
+                continue;
+            }
+
+            let mut encoded_headers = headers.clone();
+            encoded_headers.push(("Content-Encoding".to_string(), encoding.name().to_string()));
+            encoded_responses.push((
+                Some(encoding.name().to_string()),
+                HttpResponse {
+                    status_code: 200,
+                    headers: encoded_headers,
+                    body: encoded_content,
+                    upgrade: None,
+                    trailers: None,
+                },
+            ));
+        }
+    }
+
+    let identity_response = HttpResponse {
+        status_code: 200,
+        headers,
+        body: content,
+        upgrade: None,
+        trailers: None,
+    };
+
+    Ok(std::iter::once((None, identity_response))
+        .chain(encoded_responses)
+        .collect())
+}
+
+/// Returns `true` if the asset's `Content-Type` header is a compressible MIME type and the asset
+/// is not already encoded.
+fn is_compressible(headers: &[(String, String)]) -> bool {
+    let mut content_type = None;
+    for (name, value) in headers {
+        if name.eq_ignore_ascii_case("Content-Encoding") {
+            return false;
+        }
+
+        if name.eq_ignore_ascii_case("Content-Type") {
+            content_type = Some(value);
+        }
+    }
+
+    content_type
+        .and_then(|content_type| content_type.split(';').next())
+        .map(|mime_type| mime_type.trim().to_ascii_lowercase())
+        .is_some_and(|mime_type| {
+            mime_type.starts_with("text/") || COMPRESSIBLE_MIME_TYPES.contains(&mime_type.as_str())
+        })
+}
+
+fn config_from_js(config: JsAssetCertificationConfig) -> Result<AssetCertificationConfig, JsError> {
+    let response_certification_str = JsString::from("responseCertification");
+    let encodings_str = JsString::from("encodings");
+    let fingerprint_str = JsString::from("fingerprint");
+
+    let mut asset_certification_config = AssetCertificationConfig::default();
+
+    let config = Object::unchecked_from_js(JsValue::from(config));
+    for entry in Object::entries(&config).iter() {
+        let entry = Array::unchecked_from_js(entry);
+        let k = JsString::unchecked_from_js(entry.get(0));
+
+        if k == response_certification_str && !entry.get(1).is_undefined() {
+            asset_certification_config.response_certification = Some(
+                response_certification_from_js(entry.get(1).unchecked_into())?,
+            );
+        }
+
+        if k == encodings_str && !entry.get(1).is_undefined() {
+            asset_certification_config.encodings = Array::unchecked_from_js(entry.get(1))
+                .iter()
+                .map(AssetEncoding::from_js)
+                .collect::<Result<_, _>>()?;
+        }
+
+        if k == fingerprint_str && !entry.get(1).is_undefined() {
+            asset_certification_config.fingerprint =
+                Some(fingerprint_config_from_js(entry.get(1))?);
+        }
+    }
+
+    Ok(asset_certification_config)
+}
+
+fn fingerprint_config_from_js(config: JsValue) -> Result<AssetFingerprintConfig, JsError> {
+    let exclude_str = JsString::from("exclude");
+    let manifest_str = JsString::from("manifest");
+
+    let mut fingerprint_config = AssetFingerprintConfig::default();
+
+    let config = Object::unchecked_from_js(config);
+    for entry in Object::entries(&config).iter() {
+        let entry = Array::unchecked_from_js(entry);
+        let k = JsString::unchecked_from_js(entry.get(0));
+
+        if k == exclude_str && !entry.get(1).is_undefined() {
+            fingerprint_config.exclude = Array::unchecked_from_js(entry.get(1))
+                .iter()
+                .map(|path| {
+                    path.as_string().ok_or_else(|| {
+                        JsError::new("Fingerprint exclusions must be an array of strings")
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+        }
+
+        if k == manifest_str && !entry.get(1).is_undefined() {
+            fingerprint_config.manifest = Some(
+                entry
+                    .get(1)
+                    .as_string()
+                    .ok_or_else(|| JsError::new("Fingerprint manifest must be a string"))?,
+            );
+        }
+    }
+
+    Ok(fingerprint_config)
+}
+
+fn headers_to_js(headers: &[(String, String)]) -> JsValue {
+    headers
+        .iter()
+        .map(|(name, value)| Array::of2(&JsValue::from(name), &JsValue::from(value)))
+        .collect::<Array>()
+        .into()
+}
+
+fn cbor_encode(value: &impl Serialize) -> Result<Vec<u8>, JsError> {
+    let mut serializer = serde_cbor::Serializer::new(Vec::new());
+    serializer
+        .self_describe()
+        .map_err(|err| JsError::new(&err.to_string()))?;
+    value
+        .serialize(&mut serializer)
+        .map_err(|err| JsError::new(&err.to_string()))?;
+
+    Ok(serializer.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use js_sys::{Reflect, JSON};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn asset(path: &str, content: &[u8], headers: &[(&str, &str)]) -> JsAsset {
+        let asset = Object::new();
+        Reflect::set(&asset, &"path".into(), &path.into()).unwrap();
+        Reflect::set(&asset, &"content".into(), &Uint8Array::from(content)).unwrap();
+        Reflect::set(
+            &asset,
+            &"headers".into(),
+            &headers_to_js(
+                &headers
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect::<Vec<_>>(),
+            ),
+        )
+        .unwrap();
+
+        asset.unchecked_into()
+    }
+
+    fn expected_tree(
+        cel_expr: &cel::DefaultResponseOnlyCelExpression,
+        assets: &[(&str, HttpResponse)],
+    ) -> HttpCertificationTree {
+        let mut tree = HttpCertificationTree::default();
+        for (path, response) in assets {
+            tree.insert(&HttpCertificationTreeEntry::new(
+                HttpCertificationPath::exact(*path),
+                HttpCertification::response_only(cel_expr, response, None).unwrap(),
+            ));
+        }
+
+        tree
+    }
+
+    #[wasm_bindgen_test]
+    fn certify_assets_with_default_config() {
+        let cel_expr = cel::DefaultCelBuilder::response_only_certification()
+            .with_response_certification(
+                cel::DefaultResponseCertification::response_header_exclusions(vec![]),
+            )
+            .build();
+        let index_response = HttpResponse {
+            status_code: 200,
+            headers: vec![
+                ("Content-Type".to_string(), "text/html".to_string()),
+                (
+                    CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+                    cel_expr.to_string(),
+                ),
+            ],
+            body: b"<html></html>".to_vec(),
+            upgrade: None,
+            trailers: None,
+        };
+        let app_response = HttpResponse {
+            status_code: 200,
+            headers: vec![(
+                CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+                cel_expr.to_string(),
+            )],
+            body: b"console.log('Hello')".to_vec(),
+            upgrade: None,
+            trailers: None,
+        };
+        let tree = expected_tree(
+            &cel_expr,
+            &[
+                ("/index.html", index_response.clone()),
+                ("/app.js", app_response),
+            ],
+        );
+
+        let result = certify_assets(
+            vec![
+                asset(
+                    "/index.html",
+                    b"<html></html>",
+                    &[("Content-Type", "text/html")],
+                ),
+                asset("/app.js", b"console.log('Hello')", &[]),
+            ],
+            None,
+        )
+        .map_err(JsValue::from)
+        .unwrap();
+
+        assert_eq!(result.root_hash, tree.root_hash().to_vec());
+        assert_eq!(
+            result.tree,
+            cbor_encode(&tree.to_hash_tree())
+                .map_err(JsValue::from)
+                .unwrap()
+        );
+        assert_eq!(result.assets.len(), 2);
+
+        let index = &result.assets[0];
+        let index_entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/index.html"),
+            HttpCertification::response_only(&cel_expr, &index_response, None).unwrap(),
+        );
+        assert_eq!(index.path, "/index.html");
+        assert_eq!(index.status_code, 200);
+        assert_eq!(
+            JSON::stringify(&index.headers).unwrap(),
+            JSON::stringify(&headers_to_js(&index_response.headers)).unwrap()
+        );
+        assert_eq!(
+            index.expr_path,
+            cbor_encode(&index_entry.path.to_expr_path())
+                .map_err(JsValue::from)
+                .unwrap()
+        );
+        assert_eq!(
+            index.witness,
+            cbor_encode(&tree.witness(&index_entry, "/index.html"))
+                .map_err(JsValue::from)
+                .unwrap()
+        );
+        assert_eq!(
+            index.cel_expression_hash,
+            hash(cel_expr.to_string().as_bytes()).to_vec()
+        );
+        assert_eq!(
+            index.response_hash,
+            response_hash(&index_response, &cel_expr.response, None)
+                .unwrap()
+                .to_vec()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn certify_assets_with_response_certification() {
+        let cel_expr = cel::DefaultCelBuilder::response_only_certification()
+            .with_response_certification(
+                cel::DefaultResponseCertification::certified_response_headers(vec!["Content-Type"]),
+            )
+            .build();
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![
+                ("Content-Type".to_string(), "text/html".to_string()),
+                ("Date".to_string(), "today".to_string()),
+                (
+                    CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+                    cel_expr.to_string(),
+                ),
+            ],
+            body: b"<html></html>".to_vec(),
+            upgrade: None,
+            trailers: None,
+        };
+        let tree = expected_tree(&cel_expr, &[("/index.html", response)]);
+        let config = JSON::parse(
+            r#"{ "responseCertification": { "certifiedResponseHeaders": ["Content-Type"] } }"#,
+        )
+        .unwrap();
+
+        let result = certify_assets(
+            vec![asset(
+                "/index.html",
+                b"<html></html>",
+                &[("Content-Type", "text/html"), ("Date", "today")],
+            )],
+            Some(config.unchecked_into()),
+        )
+        .map_err(JsValue::from)
+        .unwrap();
+
+        assert_eq!(result.root_hash, tree.root_hash().to_vec());
+    }
+
+    #[wasm_bindgen_test]
+    fn certify_assets_with_encodings() {
+        let cel_expr = cel::DefaultCelBuilder::response_only_certification()
+            .with_response_certification(
+                cel::DefaultResponseCertification::response_header_exclusions(vec![]),
+            )
+            .build();
+        let app_content = "console.log('Hello World!');\n".repeat(32).into_bytes();
+        let app_response = HttpResponse {
+            status_code: 200,
+            headers: vec![
+                ("Content-Type".to_string(), "text/javascript".to_string()),
+                (
+                    CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+                    cel_expr.to_string(),
+                ),
+            ],
+            body: app_content.clone(),
+            upgrade: None,
+            trailers: None,
+        };
+        let gzip_app_response = HttpResponse {
+            status_code: 200,
+            headers: vec![
+                ("Content-Type".to_string(), "text/javascript".to_string()),
+                ("Content-Encoding".to_string(), "gzip".to_string()),
+                (
+                    CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+                    cel_expr.to_string(),
+                ),
+            ],
+            body: AssetEncoding::Gzip
+                .encode(&app_content)
+                .map_err(JsValue::from)
+                .unwrap(),
+            upgrade: None,
+            trailers: None,
+        };
+        let logo_response = HttpResponse {
+            status_code: 200,
+            headers: vec![
+                ("Content-Type".to_string(), "image/png".to_string()),
+                (
+                    CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+                    cel_expr.to_string(),
+                ),
+            ],
+            body: vec![0; 1024],
+            upgrade: None,
+            trailers: None,
+        };
+        let tree = expected_tree(
+            &cel_expr,
+            &[
+                ("/app.js", app_response),
+                ("/app.js", gzip_app_response.clone()),
+                ("/logo.png", logo_response),
+            ],
+        );
+        let config = JSON::parse(r#"{ "encodings": ["gzip"] }"#).unwrap();
+
+        let result = certify_assets(
+            vec![
+                asset(
+                    "/app.js",
+                    &app_content,
+                    &[("Content-Type", "text/javascript")],
+                ),
+                asset("/logo.png", &[0; 1024], &[("Content-Type", "image/png")]),
+            ],
+            Some(config.unchecked_into()),
+        )
+        .map_err(JsValue::from)
+        .unwrap();
+
+        assert_eq!(result.root_hash, tree.root_hash().to_vec());
+        assert_eq!(result.assets.len(), 3);
+
+        let gzip_app = &result.assets[1];
+        assert_eq!(gzip_app.path, "/app.js");
+        assert_eq!(gzip_app.content_encoding.as_deref(), Some("gzip"));
+        assert_eq!(gzip_app.body, gzip_app_response.body);
+        assert_eq!(
+            JSON::stringify(&gzip_app.headers).unwrap(),
+            JSON::stringify(&headers_to_js(&gzip_app_response.headers)).unwrap()
+        );
+
+        let logo = &result.assets[2];
+        assert_eq!(logo.path, "/logo.png");
+        assert_eq!(logo.content_encoding, None);
+    }
+
+    #[wasm_bindgen_test]
+    fn certify_assets_with_invalid_encoding() {
+        let config = JSON::parse(r#"{ "encodings": ["compress"] }"#).unwrap();
+
+        let result = certify_assets(
+            vec![asset(
+                "/index.html",
+                b"<html></html>",
+                &[("Content-Type", "text/html")],
+            )],
+            Some(config.unchecked_into()),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn certify_assets_with_fingerprint() {
+        let app_fingerprint = hex_fingerprint(b"console.log('Hello')");
+        let app_path = format!("/assets/app.{app_fingerprint}.js");
+        let config = JSON::parse(
+            r#"{ "fingerprint": { "exclude": ["/index.html"], "manifest": "/manifest.json" } }"#,
+        )
+        .unwrap();
+
+        let result = certify_assets(
+            vec![
+                asset("/index.html", b"<html></html>", &[]),
+                asset("/assets/app.js", b"console.log('Hello')", &[]),
+                asset(
+                    "/manifest.json",
+                    br#"{ "app": "/assets/app.js", "main": "assets/app.js" }"#,
+                    &[],
+                ),
+            ],
+            Some(config.unchecked_into()),
+        )
+        .map_err(JsValue::from)
+        .unwrap();
+
+        let paths = result
+            .assets
+            .iter()
+            .map(|asset| (asset.path.as_str(), asset.status_code))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                ("/index.html", 200),
+                (app_path.as_str(), 200),
+                ("/manifest.json", 200),
+                ("/assets/app.js", 307),
+            ]
+        );
+
+        let app = &result.assets[1];
+        assert!(JSON::stringify(&app.headers)
+            .unwrap()
+            .as_string()
+            .unwrap()
+            .contains(IMMUTABLE_CACHE_CONTROL));
+
+        let manifest = &result.assets[2];
+        assert_eq!(
+            String::from_utf8(manifest.body.clone()).unwrap(),
+            format!(
+                r#"{{ "app": "{app_path}", "main": "{}" }}"#,
+                app_path.trim_start_matches('/')
+            )
+        );
+
+        let redirect = &result.assets[3];
+        assert!(redirect.body.is_empty());
+        assert!(JSON::stringify(&redirect.headers)
+            .unwrap()
+            .as_string()
+            .unwrap()
+            .contains(&format!(r#"["Location","{app_path}"]"#)));
+    }
+
+    #[wasm_bindgen_test]
+    fn certify_assets_with_missing_manifest() {
+        let config = JSON::parse(r#"{ "fingerprint": { "manifest": "/manifest.json" } }"#).unwrap();
+
+        let result = certify_assets(
+            vec![asset("/app.js", b"console.log('Hello')", &[])],
+            Some(config.unchecked_into()),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn fingerprinted_paths() {
+        let fingerprint = hex_fingerprint(b"content");
+
+        assert_eq!(
+            fingerprinted_path("/app.js", b"content"),
+            format!("/app.{fingerprint}.js")
+        );
+        assert_eq!(
+            fingerprinted_path("/assets/app.min.js", b"content"),
+            format!("/assets/app.min.{fingerprint}.js")
+        );
+        assert_eq!(
+            fingerprinted_path("/LICENSE", b"content"),
+            format!("/LICENSE.{fingerprint}")
+        );
+        assert_eq!(
+            fingerprinted_path("/.well-known/.hidden", b"content"),
+            format!("/.well-known/.hidden.{fingerprint}")
+        );
+    }
+
+    fn hex_fingerprint(content: &[u8]) -> String {
+        hash(content)[..FINGERPRINT_LEN]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn certify_assets_without_content() {
+        let asset = JSON::parse(r#"{ "path": "/index.html" }"#).unwrap();
+
+        let result = certify_assets(vec![asset.unchecked_into()], None);
+
+        assert!(result.is_err());
+    }
+}
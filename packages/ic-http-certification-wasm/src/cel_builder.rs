@@ -0,0 +1,377 @@
+use ic_http_certification::cel;
+use ic_representation_independent_hash::hash;
+use js_sys::{Array, JsString, Object, Reflect};
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[wasm_bindgen(typescript_custom_section)]
+const RESPONSE_CERTIFICATION: &'static str = r#"
+type ResponseCertification = { "certifiedResponseHeaders": string[] } | { "responseHeaderExclusions": string[] } | { "certifiedResponseHeadersWithExclusions": { headers: string[], exclusions: string[] } };
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ResponseCertification")]
+    pub type JsResponseCertification;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ResponseCertification {
+    CertifiedResponseHeaders(Vec<String>),
+    ResponseHeaderExclusions(Vec<String>),
+    CertifiedResponseHeadersWithExclusions {
+        headers: Vec<String>,
+        exclusions: Vec<String>,
+    },
+}
+
+impl Default for ResponseCertification {
+    fn default() -> Self {
+        Self::CertifiedResponseHeaders(vec![])
+    }
+}
+
+impl ResponseCertification {
+    pub(crate) fn to_cel(&self) -> cel::DefaultResponseCertification<'_> {
+        match self {
+            Self::CertifiedResponseHeaders(headers) => {
+                cel::DefaultResponseCertification::certified_response_headers(as_strs(headers))
+            }
+            Self::ResponseHeaderExclusions(headers) => {
+                cel::DefaultResponseCertification::response_header_exclusions(as_strs(headers))
+            }
+            Self::CertifiedResponseHeadersWithExclusions {
+                headers,
+                exclusions,
+            } => cel::DefaultResponseCertification::certified_response_headers_with_exclusions(
+                as_strs(headers),
+                as_strs(exclusions),
+            ),
+        }
+    }
+}
+
+fn as_strs(values: &[String]) -> Vec<&str> {
+    values.iter().map(String::as_str).collect()
+}
+
+pub(crate) fn response_certification_from_js(
+    response_certification: JsResponseCertification,
+) -> Result<ResponseCertification, JsError> {
+    let certified_response_headers_str = JsString::from("certifiedResponseHeaders");
+    let response_header_exclusions_str = JsString::from("responseHeaderExclusions");
+    let certified_response_headers_with_exclusions_str =
+        JsString::from("certifiedResponseHeadersWithExclusions");
+
+    let response_certification = Object::unchecked_from_js(JsValue::from(response_certification));
+    for entry in Object::entries(&response_certification).iter() {
+        let entry = Array::unchecked_from_js(entry);
+        let k = JsString::unchecked_from_js(entry.get(0));
+
+        if k == certified_response_headers_str {
+            return headers_from_js(entry.get(1))
+                .map(ResponseCertification::CertifiedResponseHeaders);
+        }
+
+        if k == response_header_exclusions_str {
+            return headers_from_js(entry.get(1))
+                .map(ResponseCertification::ResponseHeaderExclusions);
+        }
+
+        if k == certified_response_headers_with_exclusions_str {
+            let value = entry.get(1);
+            let property = |name: &str| {
+                Reflect::get(&value, &JsValue::from_str(name))
+                    .map_err(|_| JsError::new("Response headers must be an array of strings"))
+                    .and_then(headers_from_js)
+            };
+
+            return Ok(
+                ResponseCertification::CertifiedResponseHeadersWithExclusions {
+                    headers: property("headers")?,
+                    exclusions: property("exclusions")?,
+                },
+            );
+        }
+    }
+
+    Err(JsError::new(
+        "Response certification must have one of the `certifiedResponseHeaders`, `responseHeaderExclusions` or `certifiedResponseHeadersWithExclusions` properties",
+    ))
+}
+
+fn headers_from_js(headers: JsValue) -> Result<Vec<String>, JsError> {
+    if !Array::is_array(&headers) {
+        return Err(JsError::new("Response headers must be an array of strings"));
+    }
+
+    Array::unchecked_from_js(headers)
+        .iter()
+        .map(|header| {
+            header
+                .as_string()
+                .ok_or_else(|| JsError::new("Response headers must be an array of strings"))
+        })
+        .collect()
+}
+
+/// A CEL expression, as created by one of the [DefaultCelBuilder] methods.
+///
+/// The expression's string representation should be used as the value of the
+/// `IC-CertificateExpression` header and its hash is used when inserting the certification
+/// into the HTTP certification tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[wasm_bindgen]
+pub struct CelExpression {
+    expression: String,
+}
+
+#[wasm_bindgen]
+impl CelExpression {
+    /// Returns the string representation of the CEL expression.
+    #[wasm_bindgen(js_name = toString)]
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.expression.clone()
+    }
+
+    /// Returns the SHA-256 hash of the CEL expression's string representation.
+    pub fn hash(&self) -> Vec<u8> {
+        hash(self.expression.as_bytes()).to_vec()
+    }
+}
+
+impl From<cel::CelExpression<'_>> for CelExpression {
+    fn from(expression: cel::CelExpression<'_>) -> Self {
+        Self {
+            expression: expression.to_string(),
+        }
+    }
+}
+
+/// A CEL expression builder for creating a default certification expression.
+#[derive(Debug, Clone)]
+#[wasm_bindgen]
+pub struct DefaultCelBuilder {}
+
+#[wasm_bindgen]
+impl DefaultCelBuilder {
+    /// Create a CEL expression that skips certification entirely.
+    #[wasm_bindgen(js_name = skipCertification)]
+    pub fn skip_certification() -> CelExpression {
+        cel::DefaultCelBuilder::skip_certification().into()
+    }
+
+    /// Creates a builder for a CEL expression that will only certify a response.
+    #[wasm_bindgen(js_name = responseOnlyCertification)]
+    pub fn response_only_certification() -> DefaultResponseOnlyCelBuilder {
+        DefaultResponseOnlyCelBuilder::default()
+    }
+
+    /// Creates a builder for a CEL expression that will certify both the request and response.
+    #[wasm_bindgen(js_name = fullCertification)]
+    pub fn full_certification() -> DefaultFullCelExpressionBuilder {
+        DefaultFullCelExpressionBuilder::default()
+    }
+}
+
+/// A CEL expression builder for creating expressions that will only certify a response.
+#[derive(Debug, Clone, Default)]
+#[wasm_bindgen]
+pub struct DefaultResponseOnlyCelBuilder {
+    response_certification: ResponseCertification,
+}
+
+#[wasm_bindgen]
+impl DefaultResponseOnlyCelBuilder {
+    /// Configure the response headers that will be included in certification.
+    ///
+    /// Not calling this method will result in no response headers being certified.
+    #[wasm_bindgen(js_name = withResponseCertification)]
+    pub fn with_response_certification(
+        mut self,
+        response_certification: JsResponseCertification,
+    ) -> Result<DefaultResponseOnlyCelBuilder, JsError> {
+        self.response_certification = response_certification_from_js(response_certification)?;
+
+        Ok(self)
+    }
+
+    /// Build the CEL expression, consuming the builder.
+    pub fn build(self) -> CelExpression {
+        let expression = cel::DefaultCelBuilder::response_only_certification()
+            .with_response_certification(self.response_certification.to_cel())
+            .build();
+
+        cel::CelExpression::Default(cel::DefaultCelExpression::ResponseOnly(expression)).into()
+    }
+}
+
+/// A CEL expression builder for creating expressions that will certify both the request and response.
+#[derive(Debug, Clone, Default)]
+#[wasm_bindgen]
+pub struct DefaultFullCelExpressionBuilder {
+    request_headers: Vec<String>,
+    request_query_parameters: Vec<String>,
+    response_certification: ResponseCertification,
+}
+
+#[wasm_bindgen]
+impl DefaultFullCelExpressionBuilder {
+    /// Configure the request headers that will be included in certification.
+    ///
+    /// Not calling this method will result in no request headers being certified.
+    #[wasm_bindgen(js_name = withRequestHeaders)]
+    pub fn with_request_headers(mut self, headers: Vec<String>) -> DefaultFullCelExpressionBuilder {
+        self.request_headers = headers;
+
+        self
+    }
+
+    /// Configure the request query parameters that will be included in certification.
+    ///
+    /// Not calling this method will result in no request query parameters being certified.
+    #[wasm_bindgen(js_name = withRequestQueryParameters)]
+    pub fn with_request_query_parameters(
+        mut self,
+        query_params: Vec<String>,
+    ) -> DefaultFullCelExpressionBuilder {
+        self.request_query_parameters = query_params;
+
+        self
+    }
+
+    /// Configure the response headers that will be included in certification.
+    ///
+    /// Not calling this method will result in no response headers being certified.
+    #[wasm_bindgen(js_name = withResponseCertification)]
+    pub fn with_response_certification(
+        mut self,
+        response_certification: JsResponseCertification,
+    ) -> Result<DefaultFullCelExpressionBuilder, JsError> {
+        self.response_certification = response_certification_from_js(response_certification)?;
+
+        Ok(self)
+    }
+
+    /// Build the CEL expression, consuming the builder.
+    pub fn build(self) -> CelExpression {
+        let expression = cel::DefaultCelBuilder::full_certification()
+            .with_request_headers(as_strs(&self.request_headers))
+            .with_request_query_parameters(as_strs(&self.request_query_parameters))
+            .with_response_certification(self.response_certification.to_cel())
+            .build();
+
+        cel::CelExpression::Default(cel::DefaultCelExpression::Full(expression)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::assert_ts_decl_in_source;
+    use js_sys::JSON;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn response_certification(json: &str) -> JsResponseCertification {
+        JSON::parse(json).unwrap().unchecked_into()
+    }
+
+    #[wasm_bindgen_test]
+    fn response_certification_type() {
+        assert_ts_decl_in_source::<cel::DefaultResponseCertification>(include_str!(
+            "cel_builder.rs"
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn skip_certification() {
+        let expected = cel::DefaultCelBuilder::skip_certification();
+
+        let result = DefaultCelBuilder::skip_certification();
+
+        assert_eq!(result.to_string(), expected.to_string());
+        assert_eq!(
+            result.hash(),
+            hash(expected.to_string().as_bytes()).to_vec()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn response_only_certification() {
+        let expected = cel::DefaultCelBuilder::response_only_certification()
+            .with_response_certification(
+                cel::DefaultResponseCertification::certified_response_headers(vec![
+                    "Cache-Control",
+                    "ETag",
+                ]),
+            )
+            .build();
+
+        let result = DefaultCelBuilder::response_only_certification()
+            .with_response_certification(response_certification(
+                r#"{ "certifiedResponseHeaders": ["Cache-Control", "ETag"] }"#,
+            ))
+            .map_err(JsValue::from)
+            .unwrap()
+            .build();
+
+        assert_eq!(result.to_string(), expected.to_string());
+        assert_eq!(
+            result.hash(),
+            hash(expected.to_string().as_bytes()).to_vec()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn full_certification() {
+        let expected = cel::DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept", "Accept-Encoding"])
+            .with_request_query_parameters(vec!["page"])
+            .with_response_certification(
+                cel::DefaultResponseCertification::response_header_exclusions(vec!["Date"]),
+            )
+            .build();
+
+        let result = DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept".to_string(), "Accept-Encoding".to_string()])
+            .with_request_query_parameters(vec!["page".to_string()])
+            .with_response_certification(response_certification(
+                r#"{ "responseHeaderExclusions": ["Date"] }"#,
+            ))
+            .map_err(JsValue::from)
+            .unwrap()
+            .build();
+
+        assert_eq!(result.to_string(), expected.to_string());
+        assert_eq!(
+            result.hash(),
+            hash(expected.to_string().as_bytes()).to_vec()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn default_full_certification() {
+        let expected = cel::DefaultCelBuilder::full_certification().build();
+
+        let result = DefaultCelBuilder::full_certification().build();
+
+        assert_eq!(result.to_string(), expected.to_string());
+    }
+
+    #[wasm_bindgen_test]
+    fn invalid_response_certification() {
+        let result = DefaultCelBuilder::response_only_certification()
+            .with_response_certification(response_certification(r#"{ "headers": ["ETag"] }"#));
+
+        assert!(result.is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn invalid_response_certification_headers() {
+        let result = DefaultCelBuilder::response_only_certification().with_response_certification(
+            response_certification(r#"{ "certifiedResponseHeaders": [1, 2] }"#),
+        );
+
+        assert!(result.is_err());
+    }
+}
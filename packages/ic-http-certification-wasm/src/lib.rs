@@ -0,0 +1,17 @@
+#![deny(clippy::all)]
+
+pub mod cel_builder;
+pub use cel_builder::*;
+
+pub mod asset_certification;
+pub use asset_certification::*;
+
+#[cfg(test)]
+mod test_utils;
+
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(start)]
+pub fn main_js() {
+    console_error_panic_hook::set_once();
+}
@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A Candid-encodable representation of an HTTP request.
+ * This struct is used by canisters that implement the HTTP interface required by the HTTP Gateway Protocol.
+ *
+ * It implements `CandidType` and `Deserialize` if the `candid` feature is enabled, which it is by
+ * default, so it can be used directly in a canister's `http_request` endpoint.
+ * It also implements `Serialize` if the `serde` feature is enabled.
+ */
+export type Request = { 
+/**
+ * HTTP request method.
+ */
+method: string, 
+/**
+ * Request URL.
+ */
+url: string, 
+/**
+ * HTTP request headers.
+ */
+headers: Array<[string, string]>, 
+/**
+ * Request body as an array of bytes.
+ */
+body: Uint8Array | ArrayBuffer, };
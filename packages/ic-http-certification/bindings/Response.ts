@@ -0,0 +1,29 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A Candid-encodable representation of an HTTP response.
+ * This struct is used by canisters that implement the HTTP interface required by the HTTP Gateway Protocol.
+ *
+ * It implements `CandidType` and `Deserialize` if the `candid` feature is enabled, which it is by
+ * default, so it can be used directly in a canister's `http_request` endpoint.
+ * It also implements `Serialize` if the `serde` feature is enabled.
+ *
+ * There is no reason phrase, such as the `OK` of `200 OK`, since the HTTP Gateway Protocol does
+ * not transmit one. Reason phrases are never certified and verification only considers the
+ * numeric status code, so proxies that rewrite them do not break verification. See
+ * [serialize_http1_response_with_reason_phrase](crate::serialize_http1_response_with_reason_phrase)
+ * for serializing a response with a specific reason phrase.
+ */
+export type Response = { 
+/**
+ * HTTP response status code.
+ */
+statusCode: number, 
+/**
+ * HTTP response headers.
+ */
+headers: Array<[string, string]>, 
+/**
+ * Response body as an array of bytes.
+ */
+body: Uint8Array | ArrayBuffer, };
@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Options for configuring certification of a response.
+ *
+ * The response body and status code are always certified, but this enum allows configuring the
+ * certification of response headers. Response headers may be included using the
+ * [CertifiedResponseHeaders](DefaultResponseCertification::CertifiedResponseHeaders) variant,
+ * and response headers may be excluded using the
+ * [ResponseHeaderExclusions](DefaultResponseCertification::ResponseHeaderExclusions) variant.
+ * Both can be combined using the
+ * [CertifiedResponseHeadersWithExclusions](DefaultResponseCertification::CertifiedResponseHeadersWithExclusions)
+ * variant.
+ *
+ * New ways of certifying response headers may be added in the future, so this enum is
+ * non-exhaustive.
+ */
+export type ResponseCertification = { "certifiedResponseHeaders": string[] } | { "responseHeaderExclusions": string[] } | { "certifiedResponseHeadersWithExclusions": { 
+/**
+ * The response headers to include in certification.
+ */
+headers: string[], 
+/**
+ * The response headers to exclude from certification, even if they are included by
+ * `headers`.
+ */
+exclusions: string[], } };
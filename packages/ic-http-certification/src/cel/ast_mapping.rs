@@ -1,18 +1,16 @@
-use crate::cel::error::{CelParserError, CelParserResult};
-use crate::cel::parser::CelValue;
-use ic_http_certification::{
-    cel::{
-        CelExpression, DefaultCelExpression, DefaultFullCelExpression, DefaultRequestCertification,
-        DefaultResponseOnlyCelExpression,
-    },
-    DefaultResponseCertification,
+use super::{
+    error::{CelParserError, CelParserResult},
+    parser::CelValue,
+    CelExpression, CelVersion, DefaultBodyCertification, DefaultCelExpression,
+    DefaultFullCelExpression, DefaultRequestCertification, DefaultResponseCertification,
+    DefaultResponseOnlyCelExpression, DefaultStatusCodeCertification,
 };
 use std::collections::HashMap;
 
-fn validate_object<'a>(
-    cel: &'a CelValue<'a>,
+fn validate_object<'a, 'b>(
+    cel: &'b CelValue<'a>,
     name: &str,
-) -> CelParserResult<&'a HashMap<&'a str, CelValue<'a>>> {
+) -> CelParserResult<&'b HashMap<&'a str, CelValue<'a>>> {
     let CelValue::Object(object_name, object_value) = cel else {
         return Err(CelParserError::UnexpectedNodeType {
             node_name: name.into(),
@@ -32,10 +30,10 @@ fn validate_object<'a>(
     Ok(object_value)
 }
 
-fn validate_function<'a>(
-    cel: &'a CelValue<'a>,
-    name: &'a str,
-) -> CelParserResult<&'a Vec<CelValue<'a>>> {
+fn validate_function<'a, 'b>(
+    cel: &'b CelValue<'a>,
+    name: &str,
+) -> CelParserResult<&'b Vec<CelValue<'a>>> {
     let CelValue::Function(function_name, function_value) = cel else {
         return Err(CelParserError::UnexpectedNodeType {
             node_name: name.into(),
@@ -55,10 +53,7 @@ fn validate_function<'a>(
     Ok(function_value)
 }
 
-fn validate_string_array<'a>(
-    cel: &'a CelValue<'a>,
-    name: &'a str,
-) -> CelParserResult<Vec<&'a str>> {
+fn validate_string_array<'a>(cel: &CelValue<'a>, name: &str) -> CelParserResult<Vec<&'a str>> {
     let CelValue::Array(array) = cel else {
         return Err(CelParserError::UnexpectedNodeType {
             node_name: name.into(),
@@ -86,7 +81,7 @@ fn validate_string_array<'a>(
 }
 
 fn validate_request_certification<'a>(
-    certification: &'a HashMap<&'a str, CelValue<'a>>,
+    certification: &HashMap<&'a str, CelValue<'a>>,
 ) -> CelParserResult<Option<DefaultRequestCertification<'a>>> {
     let no_request_certification = certification.get("no_request_certification");
     let request_certification = certification.get("request_certification");
@@ -121,20 +116,47 @@ fn validate_request_certification<'a>(
             let certified_query_parameters =
                 validate_string_array(certified_query_parameters, "certified_query_parameters")?;
 
-            Ok(Some(DefaultRequestCertification::new(
-                certified_request_headers,
-                certified_query_parameters,
-            )))
+            let certified_multipart_form_fields = request_certification
+                .get("certified_multipart_form_fields")
+                .map(|certified_multipart_form_fields| {
+                    validate_string_array(
+                        certified_multipart_form_fields,
+                        "certified_multipart_form_fields",
+                    )
+                })
+                .transpose()?
+                .unwrap_or_default();
+
+            let canonical_multipart_body = request_certification
+                .get("canonical_multipart_body_certification")
+                .map(|canonical_multipart_body_certification| {
+                    validate_object(canonical_multipart_body_certification, "Empty")
+                })
+                .transpose()?
+                .is_some();
+
+            Ok(Some(
+                DefaultRequestCertification::new(
+                    certified_request_headers,
+                    certified_query_parameters,
+                )
+                .with_multipart_form_fields(certified_multipart_form_fields)
+                .with_canonical_multipart_body(canonical_multipart_body),
+            ))
         }
     };
 }
 
 fn validate_response_certification<'a>(
-    certification: &'a HashMap<&'a str, CelValue<'a>>,
-) -> CelParserResult<DefaultResponseCertification<'a>> {
+    certification: &HashMap<&'a str, CelValue<'a>>,
+) -> CelParserResult<(
+    DefaultResponseCertification<'a>,
+    DefaultBodyCertification,
+    DefaultStatusCodeCertification,
+)> {
     let Some(response_certification) = certification.get("response_certification") else {
         return Err(CelParserError::MissingObjectProperty {
-            object_name: "RequestCertification".into(),
+            object_name: "Certification".into(),
             expected_property_name: "response_certification".into(),
         });
     };
@@ -159,8 +181,36 @@ fn validate_response_certification<'a>(
     let response_header_exclusions =
         get_response_certification_headers("response_header_exclusions")?;
 
-    match (certified_response_headers, response_header_exclusions) {
-        (Some(_), Some(_)) => Err(CelParserError::ExtraneousResponseCertificationProperty),
+    let body_certification = match (
+        response_certification.get("decoded_body_certification"),
+        response_certification.get("fragmented_body_certification"),
+    ) {
+        (Some(_), Some(_)) => Err(CelParserError::ExtraneousBodyCertificationProperty),
+        (Some(decoded_body_certification), None) => {
+            validate_object(decoded_body_certification, "Empty")?;
+            Ok(DefaultBodyCertification::Decoded)
+        }
+        (None, Some(fragmented_body_certification)) => {
+            validate_object(fragmented_body_certification, "Empty")?;
+            Ok(DefaultBodyCertification::Fragmented)
+        }
+        (None, None) => Ok(DefaultBodyCertification::AsServed),
+    }?;
+
+    let status_certification = match response_certification.get("status_code_class_certification") {
+        Some(status_code_class_certification) => {
+            validate_object(status_code_class_certification, "Empty")?;
+            DefaultStatusCodeCertification::Class
+        }
+        None => DefaultStatusCodeCertification::Exact,
+    };
+
+    let response_certification = match (certified_response_headers, response_header_exclusions) {
+        (Some(headers), Some(exclusions)) => Ok(
+            DefaultResponseCertification::certified_response_headers_with_exclusions(
+                headers, exclusions,
+            ),
+        ),
         (None, None) => Err(CelParserError::MissingResponseCertificationProperty),
         (Some(headers), None) => Ok(DefaultResponseCertification::certified_response_headers(
             headers,
@@ -168,15 +218,21 @@ fn validate_response_certification<'a>(
         (None, Some(headers)) => Ok(DefaultResponseCertification::response_header_exclusions(
             headers,
         )),
-    }
+    }?;
+
+    Ok((
+        response_certification,
+        body_certification,
+        status_certification,
+    ))
 }
 
-pub(crate) fn map_cel_ast<'a>(cel: &'a CelValue<'a>) -> CelParserResult<CelExpression<'a>> {
-    let default_certification = validate_function(cel, "default_certification")?;
+pub(super) fn map_cel_ast<'a>(cel: &CelValue<'a>) -> CelParserResult<CelExpression<'a>> {
+    let default_certification = validate_function(cel, CelVersion::V2.function_name())?;
 
     let Some(validation_args) = default_certification.first() else {
         return Err(CelParserError::MissingFunctionParameter {
-            function_name: "default_certification".into(),
+            function_name: CelVersion::V2.function_name().into(),
             parameter_name: "ValidationArgs".into(),
             parameter_type: "Object".into(),
             parameter_position: 0,
@@ -197,12 +253,24 @@ pub(crate) fn map_cel_ast<'a>(cel: &'a CelValue<'a>) -> CelParserResult<CelExpre
 
             let request_certification = validate_request_certification(certification)?;
 
-            let response_certification = validate_response_certification(certification)?;
+            let (response_certification, body_certification, status_certification) =
+                validate_response_certification(certification)?;
+
+            let allowed_methods = certification
+                .get("allowed_request_methods")
+                .map(|allowed_methods| {
+                    validate_string_array(allowed_methods, "allowed_request_methods")
+                })
+                .transpose()?
+                .unwrap_or_default();
 
             let Some(request_certification) = request_certification else {
                 return Ok(CelExpression::Default(DefaultCelExpression::ResponseOnly(
                     DefaultResponseOnlyCelExpression {
                         response: response_certification,
+                        body: body_certification,
+                        status: status_certification,
+                        allowed_methods: allowed_methods.into(),
                     },
                 )));
             };
@@ -211,6 +279,9 @@ pub(crate) fn map_cel_ast<'a>(cel: &'a CelValue<'a>) -> CelParserResult<CelExpre
                 DefaultFullCelExpression {
                     request: request_certification,
                     response: response_certification,
+                    body: body_certification,
+                    status: status_certification,
+                    allowed_methods: allowed_methods.into(),
                 },
             )))
         }
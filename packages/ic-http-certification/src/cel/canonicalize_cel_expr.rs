@@ -0,0 +1,461 @@
+use super::{
+    create_default_cel_expr, DefaultCelExpression, DefaultFullCelExpression,
+    DefaultRequestCertification, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+};
+use std::borrow::Cow;
+
+/// Converts a CEL expression from a [DefaultCelExpression] struct into it's canonical [String]
+/// representation.
+///
+/// The [String] representation that is created by [create_default_cel_expr](create_default_cel_expr())
+/// keeps header, query parameter and form field names in the order and case in which they were
+/// provided, so expressions that certify the same request and response pairs may still have
+/// different representations, and therefore be hashed differently. The canonical representation
+/// additionally normalizes these names:
+///
+/// - Request headers, query parameters and response headers are compared case-insensitively, so
+///   they are converted to lowercase, sorted and deduplicated.
+/// - `multipart/form-data` fields are compared case-sensitively, so they are sorted and deduplicated.
+/// - Allowed request methods are compared case-insensitively, so they are converted to uppercase,
+///   sorted and deduplicated.
+/// - The canonical `multipart/form-data` body is omitted if form fields are certified, since it
+///   is ignored in that case.
+/// - An empty list of response header exclusions that is combined with a list of certified
+///   response headers is omitted.
+///
+/// The canonical representation is itself a valid CEL expression, so it can be parsed back with
+/// [parse_default_cel_expression](super::parse_default_cel_expression()) to certify a response
+/// with it.
+///
+/// [DefaultCelExpression::canonicalize](DefaultCelExpression::canonicalize()) is an alias of this
+/// function and can be used for ergonomics.
+pub fn canonicalize_default_cel_expr(certification: &DefaultCelExpression) -> String {
+    match certification {
+        DefaultCelExpression::Skip => create_default_cel_expr(certification),
+        DefaultCelExpression::ResponseOnly(certification) => {
+            let (headers, exclusions) = response_header_lists(&certification.response);
+            let response = canonical_name_list(headers);
+            let exclusions = canonical_name_list(exclusions);
+            let allowed_methods = canonical_method_list(&certification.allowed_methods);
+
+            create_default_cel_expr(&DefaultCelExpression::ResponseOnly(
+                DefaultResponseOnlyCelExpression {
+                    response: canonical_response_certification(
+                        &certification.response,
+                        &response,
+                        &exclusions,
+                    ),
+                    body: certification.body,
+                    status: certification.status,
+                    allowed_methods: Cow::Owned(borrow_list(&allowed_methods)),
+                },
+            ))
+        }
+        DefaultCelExpression::Full(certification) => {
+            let request_headers = canonical_name_list(&certification.request.headers);
+            let query_parameters = canonical_name_list(&certification.request.query_parameters);
+            let multipart_form_fields =
+                canonical_field_list(&certification.request.multipart_form_fields);
+            let (headers, exclusions) = response_header_lists(&certification.response);
+            let response = canonical_name_list(headers);
+            let exclusions = canonical_name_list(exclusions);
+            let allowed_methods = canonical_method_list(&certification.allowed_methods);
+
+            create_default_cel_expr(&DefaultCelExpression::Full(DefaultFullCelExpression {
+                request: DefaultRequestCertification {
+                    headers: Cow::Owned(borrow_list(&request_headers)),
+                    query_parameters: Cow::Owned(borrow_list(&query_parameters)),
+                    multipart_form_fields: Cow::Owned(borrow_list(&multipart_form_fields)),
+                    canonical_multipart_body: certification.request.canonical_multipart_body
+                        && multipart_form_fields.is_empty(),
+                },
+                response: canonical_response_certification(
+                    &certification.response,
+                    &response,
+                    &exclusions,
+                ),
+                body: certification.body,
+                status: certification.status,
+                allowed_methods: Cow::Owned(borrow_list(&allowed_methods)),
+            }))
+        }
+    }
+}
+
+/// Converts the [String] representation of a CEL expression into an indented, multi-line
+/// representation that is easier to read when debugging.
+///
+/// Objects and function calls are split over multiple lines with one property per line, while
+/// lists of names are kept on a single line. Whitespace outside of strings is discarded, so the
+/// expression does not need to be valid, or created by this crate, to be pretty-printed. The
+/// pretty-printed representation of a valid CEL expression is also valid and can be parsed with
+/// [parse_cel_expression](super::parse_cel_expression()), but since it is hashed differently
+/// from the expression that it was created from, it should not be used for certification.
+///
+/// [DefaultCelExpression::to_pretty_string](DefaultCelExpression::to_pretty_string()) is an alias
+/// of this function for expressions that are created by this crate.
+///
+/// ```
+/// use ic_http_certification::cel::pretty_print_cel_expr;
+///
+/// let cel_expr = pretty_print_cel_expr("default_certification(ValidationArgs{no_certification:Empty{}})");
+///
+/// assert_eq!(
+///     cel_expr,
+///     r#"default_certification(
+///   ValidationArgs {
+///     no_certification: Empty {}
+///   }
+/// )"#
+/// );
+/// ```
+pub fn pretty_print_cel_expr(cel_expr: &str) -> String {
+    const INDENT: &str = "  ";
+
+    let mut pretty_cel_expr = String::with_capacity(cel_expr.len() * 2);
+    let mut depth = 0usize;
+    let mut list_depth = 0usize;
+    let mut in_string = false;
+    let mut chars = cel_expr.chars().filter_map({
+        let mut in_string = false;
+        move |c| {
+            if c == '"' {
+                in_string = !in_string;
+            }
+
+            (in_string || c == '"' || !c.is_whitespace()).then_some(c)
+        }
+    });
+    let mut next_char = chars.next();
+
+    let push_line = |pretty_cel_expr: &mut String, depth: usize| {
+        pretty_cel_expr.push('\n');
+        pretty_cel_expr.push_str(&INDENT.repeat(depth));
+    };
+
+    while let Some(c) = next_char {
+        next_char = chars.next();
+
+        if in_string {
+            pretty_cel_expr.push(c);
+            in_string = c != '"';
+            continue;
+        }
+
+        match c {
+            '"' => {
+                pretty_cel_expr.push(c);
+                in_string = true;
+            }
+            '{' if next_char == Some('}') => {
+                pretty_cel_expr.push_str(" {}");
+                next_char = chars.next();
+            }
+            '(' | '{' => {
+                if c == '{' {
+                    pretty_cel_expr.push(' ');
+                }
+                pretty_cel_expr.push(c);
+                depth += 1;
+                push_line(&mut pretty_cel_expr, depth);
+            }
+            ')' | '}' => {
+                depth = depth.saturating_sub(1);
+                push_line(&mut pretty_cel_expr, depth);
+                pretty_cel_expr.push(c);
+            }
+            '[' => {
+                list_depth += 1;
+                pretty_cel_expr.push(c);
+            }
+            ']' => {
+                list_depth = list_depth.saturating_sub(1);
+                pretty_cel_expr.push(c);
+            }
+            ',' if list_depth > 0 => {
+                pretty_cel_expr.push_str(", ");
+            }
+            ',' => {
+                pretty_cel_expr.push(c);
+                push_line(&mut pretty_cel_expr, depth);
+            }
+            ':' => {
+                pretty_cel_expr.push_str(": ");
+            }
+            c => pretty_cel_expr.push(c),
+        }
+    }
+
+    pretty_cel_expr
+}
+
+fn response_header_lists<'a>(
+    response_certification: &'a DefaultResponseCertification,
+) -> (&'a [&'a str], &'a [&'a str]) {
+    match response_certification {
+        DefaultResponseCertification::CertifiedResponseHeaders(headers)
+        | DefaultResponseCertification::ResponseHeaderExclusions(headers) => (headers, &[]),
+        DefaultResponseCertification::CertifiedResponseHeadersWithExclusions {
+            headers,
+            exclusions,
+        } => (headers, exclusions),
+    }
+}
+
+fn canonical_response_certification<'a>(
+    response_certification: &DefaultResponseCertification,
+    headers: &'a [String],
+    exclusions: &'a [String],
+) -> DefaultResponseCertification<'a> {
+    match response_certification {
+        DefaultResponseCertification::ResponseHeaderExclusions(_) => {
+            DefaultResponseCertification::ResponseHeaderExclusions(Cow::Owned(borrow_list(headers)))
+        }
+        DefaultResponseCertification::CertifiedResponseHeadersWithExclusions { .. }
+            if !exclusions.is_empty() =>
+        {
+            DefaultResponseCertification::CertifiedResponseHeadersWithExclusions {
+                headers: Cow::Owned(borrow_list(headers)),
+                exclusions: Cow::Owned(borrow_list(exclusions)),
+            }
+        }
+        _ => {
+            DefaultResponseCertification::CertifiedResponseHeaders(Cow::Owned(borrow_list(headers)))
+        }
+    }
+}
+
+fn canonical_name_list(names: &[&str]) -> Vec<String> {
+    let names = names.iter().map(|name| name.to_ascii_lowercase()).collect();
+
+    sorted_and_deduplicated(names)
+}
+
+fn canonical_method_list(methods: &[&str]) -> Vec<String> {
+    let methods = methods
+        .iter()
+        .map(|method| method.to_ascii_uppercase())
+        .collect();
+
+    sorted_and_deduplicated(methods)
+}
+
+fn canonical_field_list(fields: &[&str]) -> Vec<String> {
+    let fields = fields.iter().map(|field| field.to_string()).collect();
+
+    sorted_and_deduplicated(fields)
+}
+
+fn sorted_and_deduplicated(mut names: Vec<String>) -> Vec<String> {
+    names.sort();
+    names.dedup();
+
+    names
+}
+
+fn borrow_list(names: &[String]) -> Vec<&str> {
+    names.iter().map(String::as_str).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cel::{fixtures::*, parse_cel_expression, parse_default_cel_expression},
+        DefaultCelBuilder,
+    };
+    use rstest::*;
+
+    #[rstest]
+    #[case::full(
+        DefaultCelExpression::Full(
+DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept", "accept-encoding", "ACCEPT"])
+            .with_request_query_parameters(vec!["page", "Limit"])
+            .with_request_multipart_form_fields(vec!["title", "Description", "title"])
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Content-Type", "Cache-Control"],
+            ))
+            .build()
+),
+        DefaultCelExpression::Full(
+DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["accept-encoding", "accept"])
+            .with_request_query_parameters(vec!["LIMIT", "Page"])
+            .with_request_multipart_form_fields(vec!["Description", "title"])
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["cache-control", "content-type", "Content-Type"],
+            ))
+            .build()
+),
+    )]
+    #[case::response_header_exclusions(
+        DefaultCelExpression::ResponseOnly(
+DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::response_header_exclusions(
+                vec!["Date", "glob:X-App-*"],
+            ))
+            .build()
+),
+        DefaultCelExpression::ResponseOnly(
+DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::response_header_exclusions(
+                vec!["GLOB:x-app-*", "DATE"],
+            ))
+            .build()
+),
+    )]
+    #[case::empty_exclusions(
+        DefaultCelExpression::ResponseOnly(
+DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["glob:X-App-*"],
+            ))
+            .build()
+),
+        DefaultCelExpression::ResponseOnly(
+DefaultResponseOnlyCelExpression {
+            // the builder would exclude `Set-Cookie`
+            response: DefaultResponseCertification::certified_response_headers_with_exclusions(
+                vec!["glob:X-App-*"],
+                vec![],
+            ),
+            ..DefaultCelBuilder::response_only_certification().build()
+        }
+),
+    )]
+    fn canonicalize_equivalent_expressions(
+        #[case] cel_expr: DefaultCelExpression,
+        #[case] equivalent_cel_expr: DefaultCelExpression,
+    ) {
+        assert_ne!(cel_expr.to_string(), equivalent_cel_expr.to_string());
+        assert_eq!(cel_expr.canonicalize(), equivalent_cel_expr.canonicalize());
+    }
+
+    #[rstest]
+    #[case::multipart_form_field_case(
+        DefaultCelExpression::Full(
+DefaultCelBuilder::full_certification()
+            .with_request_multipart_form_fields(vec!["title"])
+            .build()
+),
+        DefaultCelExpression::Full(
+DefaultCelBuilder::full_certification()
+            .with_request_multipart_form_fields(vec!["Title"])
+            .build()
+),
+    )]
+    #[case::exclusions(
+        DefaultCelExpression::ResponseOnly(
+DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["glob:X-App-*"],
+            ))
+            .build()
+),
+        DefaultCelExpression::ResponseOnly(
+DefaultCelBuilder::response_only_certification()
+            .with_response_certification(
+                DefaultResponseCertification::certified_response_headers_with_exclusions(
+                    vec!["glob:X-App-*"],
+                    vec!["X-App-Trace"],
+                ),
+            )
+            .build()
+),
+    )]
+    fn canonicalize_different_expressions(
+        #[case] cel_expr: DefaultCelExpression,
+        #[case] different_cel_expr: DefaultCelExpression,
+    ) {
+        assert_ne!(cel_expr.canonicalize(), different_cel_expr.canonicalize());
+    }
+
+    #[rstest]
+    fn canonicalize_ignores_canonical_multipart_body_with_form_fields() {
+        let cel_expr = DefaultCelExpression::Full(
+            DefaultCelBuilder::full_certification()
+                .with_request_multipart_form_fields(vec!["title"])
+                .with_canonical_request_multipart_body()
+                .build(),
+        );
+        let equivalent_cel_expr = DefaultCelExpression::Full(
+            DefaultCelBuilder::full_certification()
+                .with_request_multipart_form_fields(vec!["title"])
+                .build(),
+        );
+
+        assert_eq!(cel_expr.canonicalize(), equivalent_cel_expr.canonicalize());
+    }
+
+    #[rstest]
+    #[case::no_certification(no_certification_cel())]
+    #[case::no_request_response_inclusions(no_request_response_inclusions_cel())]
+    #[case::include_request_response_header_exclusions(
+        include_request_response_header_exclusions_cel()
+    )]
+    #[case::include_request_multipart_form_fields(include_request_multipart_form_fields_cel())]
+    fn canonicalize_is_idempotent(#[case] cel_expr: String) {
+        let canonical_cel_expr = parse_default_cel_expression(&cel_expr)
+            .unwrap()
+            .canonicalize();
+        let parsed_cel_expr = parse_default_cel_expression(&canonical_cel_expr).unwrap();
+
+        assert_eq!(parsed_cel_expr.canonicalize(), canonical_cel_expr);
+    }
+
+    #[rstest]
+    fn pretty_print_full_cel_expr() {
+        let cel_expr = DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept", "Accept-Encoding"])
+            .with_request_query_parameters(vec![])
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Cache-Control"],
+            ))
+            .build();
+
+        assert_eq!(
+            DefaultCelExpression::Full(cel_expr).to_pretty_string(),
+            r#"default_certification(
+  ValidationArgs {
+    certification: Certification {
+      request_certification: RequestCertification {
+        certified_request_headers: ["Accept", "Accept-Encoding"],
+        certified_query_parameters: []
+      },
+      response_certification: ResponseCertification {
+        certified_response_headers: ResponseHeaderList {
+          headers: ["Cache-Control"]
+        }
+      }
+    }
+  }
+)"#
+        );
+    }
+
+    #[rstest]
+    #[case::no_certification(no_certification_cel())]
+    #[case::no_request_response_inclusions(no_request_response_inclusions_cel())]
+    #[case::include_request_response_header_exclusions(
+        include_request_response_header_exclusions_cel()
+    )]
+    #[case::include_request_status_code_class(include_request_status_code_class_cel())]
+    fn pretty_print_can_be_parsed(#[case] cel_expr: String) {
+        let pretty_cel_expr = pretty_print_cel_expr(&cel_expr);
+
+        assert_eq!(
+            parse_cel_expression(&pretty_cel_expr).unwrap(),
+            parse_cel_expression(&cel_expr).unwrap()
+        );
+        assert_eq!(pretty_print_cel_expr(&pretty_cel_expr), pretty_cel_expr);
+    }
+
+    #[rstest]
+    fn pretty_print_keeps_whitespace_in_strings() {
+        assert_eq!(
+            pretty_print_cel_expr(r#"f( A { b : [ "x y" , "z" ] } )"#),
+            "f(\n  A {\n    b: [\"x y\", \"z\"]\n  }\n)"
+        );
+    }
+}
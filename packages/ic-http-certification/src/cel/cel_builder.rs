@@ -1,14 +1,49 @@
 use super::{
-    CelExpression, DefaultCelExpression, DefaultFullCelExpression, DefaultRequestCertification,
-    DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+    CelExpression, CelVersion, DefaultBodyCertification, DefaultCelExpression,
+    DefaultFullCelExpression, DefaultRequestCertification, DefaultResponseCertification,
+    DefaultResponseOnlyCelExpression, DefaultStatusCodeCertification,
 };
 use std::borrow::Cow;
 
+const COOKIE_HEADER_NAME: &str = "Cookie";
+const SET_COOKIE_HEADER_NAME: &str = "Set-Cookie";
+
+/// Request headers that carry credentials, which can only be certified with
+/// [with_sensitive_request_headers](DefaultFullCelExpressionBuilder::with_sensitive_request_headers).
+const SENSITIVE_REQUEST_HEADER_NAMES: &[&str] = &["Authorization", "Proxy-Authorization"];
+
+/// Response headers that are certified by the [static_asset](DefaultCelBuilder::static_asset) preset.
+const STATIC_ASSET_RESPONSE_HEADER_NAMES: &[&str] =
+    &["Content-Type", "Content-Encoding", "Cache-Control", "ETag"];
+
+/// Request methods that responses are certified for by the [static_asset](DefaultCelBuilder::static_asset) preset.
+const STATIC_ASSET_ALLOWED_METHODS: &[&str] = &["GET", "HEAD"];
+
+/// Request headers that are certified by the [json_api](DefaultCelBuilder::json_api) and
+/// [no_cache_api](DefaultCelBuilder::no_cache_api) presets.
+const API_REQUEST_HEADER_NAMES: &[&str] = &["Accept"];
+
+/// Response headers that are certified by the [json_api](DefaultCelBuilder::json_api) preset.
+const JSON_API_RESPONSE_HEADER_NAMES: &[&str] = &["Content-Type", "Cache-Control"];
+
+/// Response headers that are certified by the [no_cache_api](DefaultCelBuilder::no_cache_api) preset.
+const NO_CACHE_API_RESPONSE_HEADER_NAMES: &[&str] =
+    &["Content-Type", "Cache-Control", "Expires", "Pragma"];
+
+/// Response headers that are certified by the [redirect](DefaultCelBuilder::redirect) preset.
+const REDIRECT_RESPONSE_HEADER_NAMES: &[&str] = &["Location", "Cache-Control"];
+
 /// A CEL expression builder for creating a default certification expression.
 #[derive(Debug, Clone)]
 pub struct DefaultCelBuilder {}
 
 impl DefaultCelBuilder {
+    /// Returns the [CelVersion] of the expressions that are created by this builder, which is
+    /// the [latest](CelVersion::LATEST) version supported by this crate.
+    pub fn cel_version() -> CelVersion {
+        CelVersion::LATEST
+    }
+
     /// Create a CEL expression that skips certification entirely.
     pub fn skip_certification<'a>() -> CelExpression<'a> {
         CelExpression::Default(DefaultCelExpression::Skip)
@@ -28,13 +63,85 @@ impl DefaultCelBuilder {
     pub fn full_certification<'a>() -> DefaultFullCelExpressionBuilder<'a> {
         DefaultFullCelExpressionBuilder::default()
     }
+
+    /// Creates a builder that is preconfigured for static assets, such as HTML, JavaScript or
+    /// image files, that are served the same way regardless of the request.
+    ///
+    /// The request is not certified, and the `Content-Type`, `Content-Encoding`, `Cache-Control`
+    /// and `ETag` response headers are certified, so that a response cannot be served with a
+    /// different type, encoding or caching policy than the canister intended. Every other
+    /// response header, such as `Date`, is left uncertified so that it may differ between
+    /// responses. Responses are only certified for `GET` and `HEAD` requests, see
+    /// [with_allowed_methods](DefaultResponseOnlyCelBuilder::with_allowed_methods). The returned
+    /// builder can be configured further before building the expression.
+    pub fn static_asset<'a>() -> DefaultResponseOnlyCelBuilder<'a> {
+        Self::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                STATIC_ASSET_RESPONSE_HEADER_NAMES,
+            ))
+            .with_allowed_methods(STATIC_ASSET_ALLOWED_METHODS)
+    }
+
+    /// Creates a builder that is preconfigured for JSON APIs whose responses may be cached.
+    ///
+    /// The `Accept` request header and the `Content-Type` and `Cache-Control` response headers are
+    /// certified. No query parameters are certified, since they are specific to each API, so
+    /// endpoints whose responses depend on query parameters must add them with
+    /// [with_request_query_parameters](DefaultFullCelExpressionBuilder::with_request_query_parameters).
+    /// Credentials and cookies are not certified, see [DefaultFullCelExpressionBuilder] for how to
+    /// opt in to them.
+    pub fn json_api<'a>() -> DefaultFullCelExpressionBuilder<'a> {
+        Self::full_certification()
+            .with_request_headers(API_REQUEST_HEADER_NAMES)
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                JSON_API_RESPONSE_HEADER_NAMES,
+            ))
+    }
+
+    /// Creates a builder that is preconfigured for API endpoints whose responses must not be
+    /// cached, such as responses with user-specific data.
+    ///
+    /// Like [json_api](DefaultCelBuilder::json_api), but the `Expires` and `Pragma` response
+    /// headers are also certified, so that the caching directives of a response cannot be
+    /// removed or changed without failing verification. The canister is still responsible for
+    /// setting these headers on its responses.
+    pub fn no_cache_api<'a>() -> DefaultFullCelExpressionBuilder<'a> {
+        Self::full_certification()
+            .with_request_headers(API_REQUEST_HEADER_NAMES)
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                NO_CACHE_API_RESPONSE_HEADER_NAMES,
+            ))
+    }
+
+    /// Creates a builder that is preconfigured for redirects.
+    ///
+    /// The request is not certified, and the `Location` and `Cache-Control` response headers are
+    /// certified, so that a redirect cannot be pointed at a different location. Since the exact
+    /// status code is certified, a permanent redirect cannot be served as a temporary one, or
+    /// vice versa.
+    pub fn redirect<'a>() -> DefaultResponseOnlyCelBuilder<'a> {
+        Self::response_only_certification().with_response_certification(
+            DefaultResponseCertification::certified_response_headers(
+                REDIRECT_RESPONSE_HEADER_NAMES,
+            ),
+        )
+    }
 }
 
 /// A CEL expression builder for creating expressions that will only certify a response.
 /// To create an expression that certifies both the request and response, see [DefaultFullCelExpressionBuilder].
+///
+/// The `Set-Cookie` header is never certified through
+/// [with_response_certification](DefaultResponseOnlyCelBuilder::with_response_certification),
+/// see [with_certified_response_cookies](DefaultResponseOnlyCelBuilder::with_certified_response_cookies)
+/// for details.
 #[derive(Debug, Clone, Default)]
 pub struct DefaultResponseOnlyCelBuilder<'a> {
     response_certification: DefaultResponseCertification<'a>,
+    body_certification: DefaultBodyCertification,
+    status_certification: DefaultStatusCodeCertification,
+    allowed_methods: Cow<'a, [&'a str]>,
+    certify_response_cookies: bool,
 }
 
 impl<'a> DefaultResponseOnlyCelBuilder<'a> {
@@ -51,21 +158,114 @@ impl<'a> DefaultResponseOnlyCelBuilder<'a> {
         self
     }
 
+    /// Certify the identity body of the response, instead of the body as it is served.
+    ///
+    /// Verifiers will decode the response body according to its `Content-Encoding` header before
+    /// hashing it, so a single certification covers the `gzip`, `deflate` and `br` encodings of the
+    /// same response. See [DefaultBodyCertification::Decoded] for details.
+    pub fn with_decoded_body_certification(mut self) -> Self {
+        self.body_certification = DefaultBodyCertification::Decoded;
+
+        self
+    }
+
+    /// Certify the response body as a list of independently hashed fragments.
+    ///
+    /// The byte lengths of the fragments are listed in the
+    /// [IC-CertificateFragments](crate::CERTIFICATE_FRAGMENTS_HEADER_NAME) header of the response.
+    /// See [DefaultBodyCertification::Fragmented] for details.
+    pub fn with_fragmented_body_certification(mut self) -> Self {
+        self.body_certification = DefaultBodyCertification::Fragmented;
+
+        self
+    }
+
+    /// Certify only the class of the response status code, such as `2xx` or `4xx`, instead of
+    /// the exact status code.
+    ///
+    /// This allows a single certification to cover responses that may be served with different
+    /// status codes of the same class, such as `200` and `204`.
+    /// See [DefaultStatusCodeCertification::Class] for details.
+    pub fn with_status_code_class_certification(mut self) -> Self {
+        self.status_certification = DefaultStatusCodeCertification::Class;
+
+        self
+    }
+
+    /// Configure the request methods, such as `GET` and `HEAD`, that responses will be certified
+    /// for.
+    ///
+    /// Responses to requests with other methods will fail verification, so that a response that
+    /// is certified for a `GET` request cannot be replayed for a `DELETE` request to the same path.
+    /// Providing an empty list, or not calling this method, will result in responses being
+    /// certified for requests with any method.
+    pub fn with_allowed_methods(mut self, methods: impl Into<Cow<'a, [&'a str]>>) -> Self {
+        self.allowed_methods = methods.into();
+
+        self
+    }
+
+    /// Include the `Set-Cookie` response header in certification.
+    ///
+    /// Cookies usually carry per-user state, such as session identifiers. Certifying them
+    /// requires a separate certification for every user's response, which grows the
+    /// certification tree and prevents the response from being shared between users by caches.
+    /// Only opt in to this if clients need to trust the cookies that are set, for example in
+    /// authenticated applications.
+    ///
+    /// Without calling this method, `Set-Cookie` is removed from any list of certified response
+    /// headers and added to any list of excluded response headers. Calling this method certifies
+    /// `Set-Cookie` regardless of the headers configured with
+    /// [with_response_certification](DefaultResponseOnlyCelBuilder::with_response_certification).
+    pub fn with_certified_response_cookies(mut self) -> Self {
+        self.certify_response_cookies = true;
+
+        self
+    }
+
     /// Build the CEL expression, consuming the builder.
     pub fn build(self) -> DefaultResponseOnlyCelExpression<'a> {
         DefaultResponseOnlyCelExpression {
-            response: self.response_certification,
+            response: response_certification(
+                self.response_certification,
+                self.certify_response_cookies,
+            ),
+            body: self.body_certification,
+            status: self.status_certification,
+            allowed_methods: self.allowed_methods,
         }
     }
 }
 
 /// A CEL expression builder for creating expressions that will certify both the request and response.
 /// To create an expression that only certifies the response, see [DefaultResponseOnlyCelBuilder].
+///
+/// The request method and the exact bytes of the request body are always certified, so a
+/// response is bound to the payload of the request that it answers, such as the JSON body of a
+/// `POST` request. To certify only selected fields of a `multipart/form-data` body, or its
+/// canonical representation, see
+/// [with_request_multipart_form_fields](DefaultFullCelExpressionBuilder::with_request_multipart_form_fields)
+/// and [with_canonical_request_multipart_body](DefaultFullCelExpressionBuilder::with_canonical_request_multipart_body).
+///
+/// The `Cookie` request header and the `Set-Cookie` response header are never certified through
+/// the generic header lists, see
+/// [with_certified_request_cookies](DefaultFullCelExpressionBuilder::with_certified_request_cookies)
+/// and [with_certified_response_cookies](DefaultFullCelExpressionBuilder::with_certified_response_cookies)
+/// for details. Likewise, the `Authorization` and `Proxy-Authorization` request headers can only be
+/// certified with [with_sensitive_request_headers](DefaultFullCelExpressionBuilder::with_sensitive_request_headers).
 #[derive(Debug, Clone, Default)]
 pub struct DefaultFullCelExpressionBuilder<'a> {
     request_headers: Cow<'a, [&'a str]>,
+    sensitive_request_headers: Cow<'a, [&'a str]>,
     request_query_parameters: Cow<'a, [&'a str]>,
+    request_multipart_form_fields: Cow<'a, [&'a str]>,
+    canonical_request_multipart_body: bool,
     response_certification: DefaultResponseCertification<'a>,
+    body_certification: DefaultBodyCertification,
+    status_certification: DefaultStatusCodeCertification,
+    allowed_methods: Cow<'a, [&'a str]>,
+    certify_request_cookies: bool,
+    certify_response_cookies: bool,
 }
 
 impl<'a> DefaultFullCelExpressionBuilder<'a> {
@@ -73,16 +273,54 @@ impl<'a> DefaultFullCelExpressionBuilder<'a> {
     ///
     /// As many or as little headers can be provided as desired.
     /// Providing an empty list, or not calling this method, will result in no request query parameters being certified.
+    ///
+    /// Header names may be patterns such as `glob:X-App-*`, which certify every request header whose
+    /// name matches, see [header_name_matches](crate::header_name_matches).
+    ///
+    /// The `Cookie`, `Authorization` and `Proxy-Authorization` headers are removed from this list, see
+    /// [with_certified_request_cookies](DefaultFullCelExpressionBuilder::with_certified_request_cookies)
+    /// and [with_sensitive_request_headers](DefaultFullCelExpressionBuilder::with_sensitive_request_headers)
+    /// to certify them.
     pub fn with_request_headers(mut self, headers: impl Into<Cow<'a, [&'a str]>>) -> Self {
         self.request_headers = headers.into();
 
         self
     }
 
+    /// Configure request headers that carry credentials, such as `Authorization`, to be included
+    /// in certification.
+    ///
+    /// Certifying credentials binds the response to a single token, so every token requires a
+    /// separate certification and the response can never be shared between users by caches. The
+    /// hash of the token also becomes part of the certification tree. Only opt in to this for
+    /// token-bound APIs whose responses depend on the credentials of the request.
+    ///
+    /// The `Authorization` and `Proxy-Authorization` headers can only be certified through this
+    /// method, so that they are never certified accidentally through
+    /// [with_request_headers](DefaultFullCelExpressionBuilder::with_request_headers). The headers
+    /// are certified after the headers configured with
+    /// [with_request_headers](DefaultFullCelExpressionBuilder::with_request_headers).
+    pub fn with_sensitive_request_headers(
+        mut self,
+        headers: impl Into<Cow<'a, [&'a str]>>,
+    ) -> Self {
+        self.sensitive_request_headers = headers.into();
+
+        self
+    }
+
     /// Configure the request query parameters that will be included in certification.
     ///
     /// As many or as little query parameters can be provided as desired.
     /// Providing an empty list, or not calling this method, will result in no request query parameters being certified.
+    ///
+    /// Every occurrence of a repeated query parameter, such as `tag` in `?tag=a&tag=b`, is
+    /// certified in the order in which they appear, so reordering them changes the request hash.
+    /// See [DefaultRequestCertification::certified_query] for the exact rule.
+    ///
+    /// Query parameter names may be patterns such as `page*`, which certify every query
+    /// parameter whose name matches, and patterns that start with `!`, such as `!utm_*`, exclude
+    /// matching query parameters from certification.
     pub fn with_request_query_parameters(
         mut self,
         query_params: impl Into<Cow<'a, [&'a str]>>,
@@ -92,6 +330,40 @@ impl<'a> DefaultFullCelExpressionBuilder<'a> {
         self
     }
 
+    /// Configure the `multipart/form-data` fields of the request body that will be included in
+    /// certification, instead of the raw request body.
+    ///
+    /// This allows upload endpoints to certify metadata fields, such as a file name, without
+    /// hashing large file contents. The request body must then be a valid `multipart/form-data`
+    /// body, with a `boundary` parameter in its `Content-Type` header.
+    /// Providing an empty list, or not calling this method, will result in the raw request body
+    /// being certified.
+    pub fn with_request_multipart_form_fields(
+        mut self,
+        multipart_form_fields: impl Into<Cow<'a, [&'a str]>>,
+    ) -> Self {
+        self.request_multipart_form_fields = multipart_form_fields.into();
+
+        self
+    }
+
+    /// Certify the canonical representation of the `multipart/form-data` request body, instead of
+    /// the raw request body.
+    ///
+    /// Clients choose the boundary of a `multipart/form-data` body and may order its parts
+    /// differently, so the raw body of an upload cannot be predicted by the canister. The
+    /// canonical representation does not depend on either, see
+    /// [canonical_multipart_body](crate::canonical_multipart_body). The request body must then be
+    /// a valid `multipart/form-data` body, with a `boundary` parameter in its `Content-Type`
+    /// header. This has no effect if
+    /// [multipart form fields](DefaultFullCelExpressionBuilder::with_request_multipart_form_fields)
+    /// are certified.
+    pub fn with_canonical_request_multipart_body(mut self) -> Self {
+        self.canonical_request_multipart_body = true;
+
+        self
+    }
+
     /// Configure the response headers that will be included in certification.
     ///
     /// See [DefaultResponseCertification] for details on how to configure this.
@@ -105,22 +377,176 @@ impl<'a> DefaultFullCelExpressionBuilder<'a> {
         self
     }
 
+    /// Certify the identity body of the response, instead of the body as it is served.
+    ///
+    /// Verifiers will decode the response body according to its `Content-Encoding` header before
+    /// hashing it, so a single certification covers the `gzip`, `deflate` and `br` encodings of the
+    /// same response. See [DefaultBodyCertification::Decoded] for details.
+    pub fn with_decoded_body_certification(mut self) -> Self {
+        self.body_certification = DefaultBodyCertification::Decoded;
+
+        self
+    }
+
+    /// Certify the response body as a list of independently hashed fragments.
+    ///
+    /// The byte lengths of the fragments are listed in the
+    /// [IC-CertificateFragments](crate::CERTIFICATE_FRAGMENTS_HEADER_NAME) header of the response.
+    /// See [DefaultBodyCertification::Fragmented] for details.
+    pub fn with_fragmented_body_certification(mut self) -> Self {
+        self.body_certification = DefaultBodyCertification::Fragmented;
+
+        self
+    }
+
+    /// Certify only the class of the response status code, instead of the exact status code.
+    ///
+    /// See [DefaultResponseOnlyCelBuilder::with_status_code_class_certification] for details.
+    pub fn with_status_code_class_certification(mut self) -> Self {
+        self.status_certification = DefaultStatusCodeCertification::Class;
+
+        self
+    }
+
+    /// Configure the request methods, such as `GET` and `HEAD`, that responses will be certified
+    /// for.
+    ///
+    /// Responses to requests with other methods will fail verification, so that a response that
+    /// is certified for a `GET` request cannot be replayed for a `DELETE` request to the same path.
+    /// Providing an empty list, or not calling this method, will result in responses being
+    /// certified for requests with any method.
+    pub fn with_allowed_methods(mut self, methods: impl Into<Cow<'a, [&'a str]>>) -> Self {
+        self.allowed_methods = methods.into();
+
+        self
+    }
+
+    /// Include the `Cookie` request header in certification.
+    ///
+    /// Cookies usually carry per-user state, such as session identifiers. Certifying them
+    /// requires a separate certification for every user's request, which grows the
+    /// certification tree and prevents the response from being shared between users by caches.
+    /// Only opt in to this if the response depends on the cookies of the request, for example in
+    /// authenticated applications.
+    ///
+    /// Without calling this method, `Cookie` is removed from the headers configured with
+    /// [with_request_headers](DefaultFullCelExpressionBuilder::with_request_headers).
+    pub fn with_certified_request_cookies(mut self) -> Self {
+        self.certify_request_cookies = true;
+
+        self
+    }
+
+    /// Include the `Set-Cookie` response header in certification.
+    ///
+    /// See [DefaultResponseOnlyCelBuilder::with_certified_response_cookies] for the implications
+    /// of certifying cookies.
+    ///
+    /// Without calling this method, `Set-Cookie` is removed from any list of certified response
+    /// headers and added to any list of excluded response headers. Calling this method certifies
+    /// `Set-Cookie` regardless of the headers configured with
+    /// [with_response_certification](DefaultFullCelExpressionBuilder::with_response_certification).
+    pub fn with_certified_response_cookies(mut self) -> Self {
+        self.certify_response_cookies = true;
+
+        self
+    }
+
     /// Build the CEL expression, consuming the builder.
     pub fn build(self) -> DefaultFullCelExpression<'a> {
-        let request_certification =
-            DefaultRequestCertification::new(self.request_headers, self.request_query_parameters);
+        let mut certified_request_headers = self.sensitive_request_headers.to_vec();
+        if self.certify_request_cookies {
+            certified_request_headers.insert(0, COOKIE_HEADER_NAME);
+        }
+        let mut removed_request_headers = SENSITIVE_REQUEST_HEADER_NAMES.to_vec();
+        removed_request_headers.push(COOKIE_HEADER_NAME);
+
+        let request_certification = DefaultRequestCertification::new(
+            header_list(
+                self.request_headers,
+                &removed_request_headers,
+                &certified_request_headers,
+            ),
+            self.request_query_parameters,
+        )
+        .with_multipart_form_fields(self.request_multipart_form_fields)
+        .with_canonical_multipart_body(self.canonical_request_multipart_body);
 
         DefaultFullCelExpression {
             request: request_certification,
-            response: self.response_certification,
+            response: response_certification(
+                self.response_certification,
+                self.certify_response_cookies,
+            ),
+            body: self.body_certification,
+            status: self.status_certification,
+            allowed_methods: self.allowed_methods,
+        }
+    }
+}
+
+fn response_certification(
+    response_certification: DefaultResponseCertification<'_>,
+    certify_cookies: bool,
+) -> DefaultResponseCertification<'_> {
+    let (certified_headers, excluded_headers): (&[&str], &[&str]) = match certify_cookies {
+        true => (&[SET_COOKIE_HEADER_NAME], &[]),
+        false => (&[], &[SET_COOKIE_HEADER_NAME]),
+    };
+
+    match response_certification {
+        DefaultResponseCertification::CertifiedResponseHeaders(headers) => {
+            DefaultResponseCertification::CertifiedResponseHeaders(header_list(
+                headers,
+                &[SET_COOKIE_HEADER_NAME],
+                certified_headers,
+            ))
+        }
+        DefaultResponseCertification::CertifiedResponseHeadersWithExclusions {
+            headers,
+            exclusions,
+        } => DefaultResponseCertification::CertifiedResponseHeadersWithExclusions {
+            headers: header_list(headers, &[SET_COOKIE_HEADER_NAME], certified_headers),
+            exclusions: header_list(exclusions, &[SET_COOKIE_HEADER_NAME], excluded_headers),
+        },
+        DefaultResponseCertification::ResponseHeaderExclusions(headers) => {
+            DefaultResponseCertification::ResponseHeaderExclusions(header_list(
+                headers,
+                &[SET_COOKIE_HEADER_NAME],
+                excluded_headers,
+            ))
         }
     }
 }
 
+/// Removes `removed_headers` from `headers`, and then appends `appended_headers`.
+fn header_list<'a>(
+    headers: Cow<'a, [&'a str]>,
+    removed_headers: &[&str],
+    appended_headers: &[&'a str],
+) -> Cow<'a, [&'a str]> {
+    let is_removed_header = |header: &&str| {
+        removed_headers
+            .iter()
+            .any(|removed_header| removed_header.eq_ignore_ascii_case(header))
+    };
+    if appended_headers.is_empty() && !headers.iter().any(is_removed_header) {
+        return headers;
+    }
+
+    headers
+        .iter()
+        .copied()
+        .filter(|header| !is_removed_header(header))
+        .chain(appended_headers.iter().copied())
+        .collect::<Vec<_>>()
+        .into()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cel::fixtures::*;
+    use crate::{cel::fixtures::*, response_hash, HttpResponse};
     use rstest::*;
 
     #[rstest]
@@ -130,6 +556,30 @@ mod tests {
         assert_eq!(cel_expr, no_certification_cel);
     }
 
+    #[rstest]
+    fn include_request_multipart_form_fields(include_request_multipart_form_fields_cel: String) {
+        let cel_expr = DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Content-Type"])
+            .with_request_multipart_form_fields(vec!["title", "tags"])
+            .build()
+            .to_string();
+
+        assert_eq!(cel_expr, include_request_multipart_form_fields_cel);
+    }
+
+    #[rstest]
+    fn include_request_canonical_multipart_body(
+        include_request_canonical_multipart_body_cel: String,
+    ) {
+        let cel_expr = DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Content-Type"])
+            .with_canonical_request_multipart_body()
+            .build()
+            .to_string();
+
+        assert_eq!(cel_expr, include_request_canonical_multipart_body_cel);
+    }
+
     #[rstest]
     fn no_request_response_inclusions(no_request_response_inclusions_cel: String) {
         let cel_expr = DefaultCelBuilder::response_only_certification()
@@ -190,7 +640,10 @@ mod tests {
             .build()
             .to_string();
 
-        assert_eq!(cel_expr, no_request_empty_response_exclusions_cel);
+        assert_eq!(
+            cel_expr,
+            excluding_set_cookie(no_request_empty_response_exclusions_cel)
+        );
     }
 
     #[rstest]
@@ -282,7 +735,10 @@ mod tests {
             .build()
             .to_string();
 
-        assert_eq!(cel_expr, include_request_empty_response_exclusions_cel);
+        assert_eq!(
+            cel_expr,
+            excluding_set_cookie(include_request_empty_response_exclusions_cel)
+        );
     }
 
     #[rstest]
@@ -325,7 +781,385 @@ mod tests {
             .build()
             .to_string();
 
+        let empty_request_response_exclusions_cel =
+            excluding_set_cookie(empty_request_response_exclusions_cel);
+
         assert_eq!(implicit_cel_expr, empty_request_response_exclusions_cel);
         assert_eq!(explicit_cel_expr, empty_request_response_exclusions_cel);
     }
+
+    #[rstest]
+    fn cookies_are_not_certified_through_header_lists() {
+        let response_only_cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Cache-Control", "set-cookie"],
+            ))
+            .build();
+        let full_cel_expr = DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept", "Cookie"])
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Set-Cookie", "Cache-Control"],
+            ))
+            .build();
+
+        assert_eq!(
+            response_only_cel_expr.response,
+            DefaultResponseCertification::certified_response_headers(vec!["Cache-Control"])
+        );
+        assert_eq!(
+            full_cel_expr.request,
+            DefaultRequestCertification::new(vec!["Accept"], vec![])
+        );
+        assert_eq!(
+            full_cel_expr.response,
+            DefaultResponseCertification::certified_response_headers(vec!["Cache-Control"])
+        );
+    }
+
+    #[rstest]
+    fn cookies_are_certified_with_opt_in() {
+        let response_only_cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Cache-Control", "set-cookie"],
+            ))
+            .with_certified_response_cookies()
+            .build();
+        let full_cel_expr = DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept"])
+            .with_certified_request_cookies()
+            .with_certified_response_cookies()
+            .build();
+
+        assert_eq!(
+            response_only_cel_expr.response,
+            DefaultResponseCertification::certified_response_headers(vec![
+                "Cache-Control",
+                "Set-Cookie"
+            ])
+        );
+        assert_eq!(
+            full_cel_expr.request,
+            DefaultRequestCertification::new(vec!["Accept", "Cookie"], vec![])
+        );
+        assert_eq!(
+            full_cel_expr.response,
+            DefaultResponseCertification::certified_response_headers(vec!["Set-Cookie"])
+        );
+    }
+
+    #[rstest]
+    fn cookies_are_excluded_from_response_header_exclusions() {
+        let response_only_cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::response_header_exclusions(
+                vec![],
+            ))
+            .build();
+        let full_cel_expr = DefaultCelBuilder::full_certification()
+            .with_response_certification(
+                DefaultResponseCertification::certified_response_headers_with_exclusions(
+                    vec!["glob:X-App-*"],
+                    vec!["X-App-Debug"],
+                ),
+            )
+            .build();
+        let response = HttpResponse::ok(b"Hello World!", vec![]);
+        let response_with_cookie = HttpResponse::ok(
+            b"Hello World!",
+            vec![("Set-Cookie".to_string(), "session=1".to_string())],
+        );
+
+        assert_eq!(
+            response_only_cel_expr.response,
+            DefaultResponseCertification::response_header_exclusions(vec!["Set-Cookie"])
+        );
+        assert_eq!(
+            full_cel_expr.response,
+            DefaultResponseCertification::certified_response_headers_with_exclusions(
+                vec!["glob:X-App-*"],
+                vec!["X-App-Debug", "Set-Cookie"],
+            )
+        );
+        assert_eq!(
+            response_hash(&response, &response_only_cel_expr.response, None).unwrap(),
+            response_hash(
+                &response_with_cookie,
+                &response_only_cel_expr.response,
+                None
+            )
+            .unwrap()
+        );
+    }
+
+    #[rstest]
+    fn cookies_are_excluded_in_cel_expression() {
+        let response_only_cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::response_header_exclusions(
+                vec!["Date"],
+            ))
+            .build()
+            .to_string();
+        let full_cel_expr = DefaultCelBuilder::full_certification()
+            .with_response_certification(
+                DefaultResponseCertification::certified_response_headers_with_exclusions(
+                    vec!["glob:X-App-*"],
+                    vec!["X-App-Debug"],
+                ),
+            )
+            .build()
+            .to_string();
+
+        assert_eq!(
+            response_only_cel_expr,
+            r#"default_certification(ValidationArgs{certification:Certification{no_request_certification:Empty{},response_certification:ResponseCertification{response_header_exclusions:ResponseHeaderList{headers:["Date","Set-Cookie"]}}}})"#
+        );
+        assert_eq!(
+            full_cel_expr,
+            r#"default_certification(ValidationArgs{certification:Certification{request_certification:RequestCertification{certified_request_headers:[],certified_query_parameters:[]},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:["glob:X-App-*"]},response_header_exclusions:ResponseHeaderList{headers:["X-App-Debug","Set-Cookie"]}}}})"#
+        );
+    }
+
+    #[rstest]
+    #[case::without_opt_in(false, vec!["Date", "Set-Cookie"])]
+    #[case::with_opt_in(true, vec!["Date"])]
+    fn cookie_opt_in_with_response_header_exclusions(
+        #[case] certify_cookies: bool,
+        #[case] expected_exclusions: Vec<&str>,
+    ) {
+        let builder = DefaultCelBuilder::response_only_certification().with_response_certification(
+            DefaultResponseCertification::response_header_exclusions(vec!["Date", "Set-Cookie"]),
+        );
+        let builder = match certify_cookies {
+            true => builder.with_certified_response_cookies(),
+            false => builder,
+        };
+
+        assert_eq!(
+            builder.build().response,
+            DefaultResponseCertification::response_header_exclusions(expected_exclusions)
+        );
+    }
+
+    #[rstest]
+    #[case::without_opt_in(false, vec!["glob:X-App-*"], vec!["X-App-Debug", "Set-Cookie"])]
+    #[case::with_opt_in(true, vec!["glob:X-App-*", "Set-Cookie"], vec!["X-App-Debug"])]
+    fn cookie_opt_in_with_combined_response_header_certification(
+        #[case] certify_cookies: bool,
+        #[case] expected_headers: Vec<&str>,
+        #[case] expected_exclusions: Vec<&str>,
+    ) {
+        let builder = DefaultCelBuilder::response_only_certification().with_response_certification(
+            DefaultResponseCertification::certified_response_headers_with_exclusions(
+                vec!["glob:X-App-*", "Set-Cookie"],
+                vec!["X-App-Debug", "Set-Cookie"],
+            ),
+        );
+        let builder = match certify_cookies {
+            true => builder.with_certified_response_cookies(),
+            false => builder,
+        };
+
+        assert_eq!(
+            builder.build().response,
+            DefaultResponseCertification::certified_response_headers_with_exclusions(
+                expected_headers,
+                expected_exclusions
+            )
+        );
+    }
+
+    #[rstest]
+    fn authorization_is_not_certified_through_request_headers() {
+        let cel_expr = DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept", "authorization", "Proxy-Authorization"])
+            .build();
+
+        assert_eq!(
+            cel_expr.request,
+            DefaultRequestCertification::new(vec!["Accept"], vec![])
+        );
+    }
+
+    #[rstest]
+    fn authorization_is_certified_with_sensitive_request_headers() {
+        let cel_expr = DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept", "Authorization"])
+            .with_sensitive_request_headers(vec!["Authorization"])
+            .with_certified_request_cookies()
+            .build();
+
+        assert_eq!(
+            cel_expr.request,
+            DefaultRequestCertification::new(vec!["Accept", "Cookie", "Authorization"], vec![])
+        );
+    }
+
+    #[rstest]
+    fn decoded_body_certification() {
+        let response_only_cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_decoded_body_certification()
+            .build();
+        let full_cel_expr = DefaultCelBuilder::full_certification()
+            .with_decoded_body_certification()
+            .build();
+        let default_cel_expr = DefaultCelBuilder::response_only_certification().build();
+
+        assert_eq!(
+            response_only_cel_expr.body,
+            DefaultBodyCertification::Decoded
+        );
+        assert_eq!(full_cel_expr.body, DefaultBodyCertification::Decoded);
+        assert_eq!(default_cel_expr.body, DefaultBodyCertification::AsServed);
+    }
+
+    #[rstest]
+    fn fragmented_body_certification() {
+        let response_only_cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_fragmented_body_certification()
+            .build();
+        let full_cel_expr = DefaultCelBuilder::full_certification()
+            .with_fragmented_body_certification()
+            .build();
+
+        assert_eq!(
+            response_only_cel_expr.body,
+            DefaultBodyCertification::Fragmented
+        );
+        assert_eq!(full_cel_expr.body, DefaultBodyCertification::Fragmented);
+    }
+
+    #[rstest]
+    fn status_code_class_certification() {
+        let response_only_cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_status_code_class_certification()
+            .build();
+        let full_cel_expr = DefaultCelBuilder::full_certification()
+            .with_status_code_class_certification()
+            .build();
+        let default_cel_expr = DefaultCelBuilder::full_certification().build();
+
+        assert_eq!(
+            response_only_cel_expr.status,
+            DefaultStatusCodeCertification::Class
+        );
+        assert_eq!(full_cel_expr.status, DefaultStatusCodeCertification::Class);
+        assert_eq!(
+            default_cel_expr.status,
+            DefaultStatusCodeCertification::Exact
+        );
+    }
+
+    #[rstest]
+    fn static_asset_preset() {
+        let cel_expr = DefaultCelBuilder::static_asset().build();
+
+        assert_eq!(
+            cel_expr,
+            DefaultCelBuilder::response_only_certification()
+                .with_response_certification(
+                    DefaultResponseCertification::certified_response_headers(vec![
+                        "Content-Type",
+                        "Content-Encoding",
+                        "Cache-Control",
+                        "ETag",
+                    ])
+                )
+                .with_allowed_methods(vec!["GET", "HEAD"])
+                .build()
+        );
+    }
+
+    #[rstest]
+    fn json_api_preset() {
+        let cel_expr = DefaultCelBuilder::json_api()
+            .with_request_query_parameters(vec!["page"])
+            .build();
+
+        assert_eq!(
+            cel_expr.request,
+            DefaultRequestCertification::new(vec!["Accept"], vec!["page"])
+        );
+        assert_eq!(
+            cel_expr.response,
+            DefaultResponseCertification::certified_response_headers(vec![
+                "Content-Type",
+                "Cache-Control",
+            ])
+        );
+        assert_eq!(cel_expr.body, DefaultBodyCertification::AsServed);
+    }
+
+    #[rstest]
+    fn no_cache_api_preset() {
+        let cel_expr = DefaultCelBuilder::no_cache_api().build();
+
+        assert_eq!(
+            cel_expr.request,
+            DefaultRequestCertification::new(vec!["Accept"], vec![])
+        );
+        assert_eq!(
+            cel_expr.response,
+            DefaultResponseCertification::certified_response_headers(vec![
+                "Content-Type",
+                "Cache-Control",
+                "Expires",
+                "Pragma",
+            ])
+        );
+    }
+
+    #[rstest]
+    fn redirect_preset() {
+        let cel_expr = DefaultCelBuilder::redirect().build();
+
+        assert_eq!(
+            cel_expr.response,
+            DefaultResponseCertification::certified_response_headers(vec![
+                "Location",
+                "Cache-Control",
+            ])
+        );
+        assert_eq!(cel_expr.status, DefaultStatusCodeCertification::Exact);
+    }
+
+    #[rstest]
+    fn presets_do_not_certify_credentials() {
+        let cel_expr = DefaultCelBuilder::json_api()
+            .with_request_headers(vec!["Accept", "Cookie", "Authorization"])
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Content-Type", "Set-Cookie"],
+            ))
+            .build();
+
+        assert_eq!(cel_expr.request.headers.as_ref(), &["Accept"]);
+        assert_eq!(
+            cel_expr.response,
+            DefaultResponseCertification::certified_response_headers(vec!["Content-Type"])
+        );
+    }
+
+    #[rstest]
+    fn allowed_methods() {
+        let response_only_cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_allowed_methods(vec!["GET", "HEAD"])
+            .build();
+        let full_cel_expr = DefaultCelBuilder::full_certification()
+            .with_allowed_methods(vec!["POST"])
+            .build();
+        let default_cel_expr = DefaultCelBuilder::response_only_certification().build();
+
+        assert!(response_only_cel_expr.allows_method("GET"));
+        assert!(response_only_cel_expr.allows_method("head"));
+        assert!(!response_only_cel_expr.allows_method("DELETE"));
+        assert!(full_cel_expr.allows_method("POST"));
+        assert!(!full_cel_expr.allows_method("GET"));
+        assert!(default_cel_expr.allowed_methods.is_empty());
+        assert!(default_cel_expr.allows_method("DELETE"));
+    }
+
+    fn excluding_set_cookie(cel_expr: String) -> String {
+        cel_expr.replace(
+            "ResponseHeaderList{headers:[]}",
+            r#"ResponseHeaderList{headers:["Set-Cookie"]}"#,
+        )
+    }
 }
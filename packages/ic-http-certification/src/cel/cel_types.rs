@@ -1,13 +1,24 @@
 use super::{
-    create_cel_expr, create_default_cel_expr, create_default_full_cel_expr,
-    create_default_response_only_cel_expr,
+    canonicalize_default_cel_expr, create_cel_expr, create_default_cel_expr,
+    create_default_full_cel_expr, create_default_response_only_cel_expr,
+    diff_default_cel_expressions, pretty_print_cel_expr, CelExpressionDifference, CelVersion,
+};
+use crate::{
+    http::QUERY_PARAMETER_EXCLUSION_PREFIX, query_parameter_name_matches, HttpCertification,
+    HttpCertificationResult, HttpRequest, HttpResponse,
 };
 use std::borrow::Cow;
 
 /// A certification CEL expression defintion.
 /// Contains an enum variant for each CEL function supported for certification.
 /// Currently only one variant is supported: [CelExpression::Default].
+///
+/// If the `serde` feature is enabled, CEL expressions implement `Serialize` and `Deserialize`.
+/// Header and query parameter names are borrowed from the serialized data, so expressions must
+/// be deserialized from a buffer that outlives them, for example with `serde_json::from_str` or
+/// `serde_cbor::from_slice`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CelExpression<'a> {
     /// A certification CEL expression definition that uses the `default_certification` function.
     /// This is currently the only supported function.
@@ -15,9 +26,27 @@ pub enum CelExpression<'a> {
     /// The enum's inner value is an [Option] to allow for opting in, or out of certification.
     /// Providing [None] will opt out of certification, while providing [Some] will opt in to certification.
     /// See [DefaultCelExpression] for more details on its available parameters.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     Default(DefaultCelExpression<'a>),
 }
 
+impl CelExpression<'_> {
+    /// Returns the [CelVersion] of the CEL syntax that this expression uses.
+    pub fn version(&self) -> CelVersion {
+        match self {
+            CelExpression::Default(cel_expr) => cel_expr.version(),
+        }
+    }
+
+    /// Returns true if responses that are certified with this expression may be served for
+    /// requests with the given method.
+    pub fn allows_method(&self, method: &str) -> bool {
+        match self {
+            CelExpression::Default(cel_expr) => cel_expr.allows_method(method),
+        }
+    }
+}
+
 impl ToString for CelExpression<'_> {
     /// Converts a [CelExpression] object into it's [String] representation.
     /// Alias of [create_cel_expr](create_cel_expr()).
@@ -43,13 +72,16 @@ impl ToString for CelExpression<'_> {
 /// corresponding [HTTP response](crate::HttpResponse) from certification. This variant does not require any
 /// configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DefaultCelExpression<'a> {
     /// Includes both the [HTTP request](crate::HttpRequest) and the corresponding
     /// [HTTP response](crate::HttpResponse) in certification.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     Full(DefaultFullCelExpression<'a>),
 
     /// Includes an [HTTP response](crate::HttpResponse) in certification, but excludes the corresponding
     /// [HTTP request](crate::HttpRequest) from certification.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     ResponseOnly(DefaultResponseOnlyCelExpression<'a>),
 
     /// Skips certification entirely by excluding both the [HTTP request](crate::HttpRequest) and
@@ -57,6 +89,50 @@ pub enum DefaultCelExpression<'a> {
     Skip,
 }
 
+impl DefaultCelExpression<'_> {
+    /// Returns the [CelVersion] of the CEL syntax that this expression uses, which is
+    /// [V2](CelVersion::V2) for every default certification expression.
+    pub fn version(&self) -> CelVersion {
+        CelVersion::V2
+    }
+
+    /// Converts a [DefaultCelExpression] object into it's canonical [String] representation, which
+    /// is the same for every expression that certifies the same request and response pairs,
+    /// regardless of the order and case of header, query parameter and form field names.
+    ///
+    /// Alias of [canonicalize_default_cel_expr](canonicalize_default_cel_expr()).
+    pub fn canonicalize(&self) -> String {
+        canonicalize_default_cel_expr(self)
+    }
+
+    /// Converts a [DefaultCelExpression] object into an indented, multi-line [String]
+    /// representation for debugging.
+    ///
+    /// Alias of [pretty_print_cel_expr](pretty_print_cel_expr()) applied to
+    /// [to_string](DefaultCelExpression::to_string()).
+    pub fn to_pretty_string(&self) -> String {
+        pretty_print_cel_expr(&self.to_string())
+    }
+
+    /// Compares this expression, which is expected, with the `found` expression and explains how
+    /// they differ.
+    ///
+    /// Alias of [diff_default_cel_expressions](diff_default_cel_expressions()).
+    pub fn diff(&self, found: &DefaultCelExpression) -> Vec<CelExpressionDifference> {
+        diff_default_cel_expressions(self, found)
+    }
+
+    /// Returns true if responses that are certified with this expression may be served for
+    /// requests with the given method. Expressions that skip certification allow every method.
+    pub fn allows_method(&self, method: &str) -> bool {
+        match self {
+            DefaultCelExpression::Full(cel_expr) => cel_expr.allows_method(method),
+            DefaultCelExpression::ResponseOnly(cel_expr) => cel_expr.allows_method(method),
+            DefaultCelExpression::Skip => true,
+        }
+    }
+}
+
 impl ToString for DefaultCelExpression<'_> {
     /// Converts a [DefaultCelExpression] object into it's [String] representation.
     ///
@@ -66,13 +142,61 @@ impl ToString for DefaultCelExpression<'_> {
     }
 }
 
+impl<'a> From<DefaultFullCelExpression<'a>> for DefaultCelExpression<'a> {
+    fn from(cel_expr: DefaultFullCelExpression<'a>) -> Self {
+        DefaultCelExpression::Full(cel_expr)
+    }
+}
+
+impl<'a> From<DefaultResponseOnlyCelExpression<'a>> for DefaultCelExpression<'a> {
+    fn from(cel_expr: DefaultResponseOnlyCelExpression<'a>) -> Self {
+        DefaultCelExpression::ResponseOnly(cel_expr)
+    }
+}
+
 /// Options for configuring a CEL expression that includes only the [HTTP response](crate::HttpResponse) in
 /// certification and excludes the [HTTP request](crate::HttpRequest) from certification.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DefaultResponseOnlyCelExpression<'a> {
     /// Options for configuring response certification for this CEL expression.
     /// See [DefaultResponseCertification] for details on how to configure response certification.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub response: DefaultResponseCertification<'a>,
+
+    /// Options for configuring response body certification for this CEL expression.
+    /// See [DefaultBodyCertification] for details on how to configure response body certification.
+    pub body: DefaultBodyCertification,
+
+    /// Options for configuring response status code certification for this CEL expression.
+    /// See [DefaultStatusCodeCertification] for details on how to configure status code certification.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub status: DefaultStatusCodeCertification,
+
+    /// A list of request methods, such as `GET` and `HEAD`, that responses are certified for.
+    ///
+    /// Providing an empty list will result in responses being certified for requests with any
+    /// method. Otherwise, responses to requests with other methods are rejected, so that a
+    /// response that is certified for a `GET` request cannot be replayed for a `DELETE` request
+    /// to the same path. See [allows_method](Self::allows_method).
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    pub allowed_methods: Cow<'a, [&'a str]>,
+}
+
+impl DefaultResponseOnlyCelExpression<'_> {
+    /// Returns the [CelVersion] of the CEL syntax that this expression uses, see
+    /// [DefaultCelExpression::version].
+    pub fn version(&self) -> CelVersion {
+        CelVersion::V2
+    }
+
+    /// Returns true if responses that are certified with this expression may be served for
+    /// requests with the given method, see [allowed_methods](Self::allowed_methods).
+    ///
+    /// Methods are compared case-insensitively, in the same way as [Method](crate::Method).
+    pub fn allows_method(&self, method: &str) -> bool {
+        allows_method(&self.allowed_methods, method)
+    }
 }
 
 impl ToString for DefaultResponseOnlyCelExpression<'_> {
@@ -87,14 +211,51 @@ impl ToString for DefaultResponseOnlyCelExpression<'_> {
 /// Options for configuring a CEL expression that includes both the [HTTP response](crate::HttpResponse) and
 /// [HTTP request](crate::HttpRequest) in certification.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DefaultFullCelExpression<'a> {
     /// Options for configuring request certification for this CEL expression.
     /// See [DefaultRequestCertification] for details on how to configure request certification.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub request: DefaultRequestCertification<'a>,
 
     /// Options for configuring response certification for this CEL expression.
     /// See [DefaultResponseCertification] for details on how to configure response certification.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub response: DefaultResponseCertification<'a>,
+
+    /// Options for configuring response body certification for this CEL expression.
+    /// See [DefaultBodyCertification] for details on how to configure response body certification.
+    pub body: DefaultBodyCertification,
+
+    /// Options for configuring response status code certification for this CEL expression.
+    /// See [DefaultStatusCodeCertification] for details on how to configure status code certification.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub status: DefaultStatusCodeCertification,
+
+    /// A list of request methods, such as `GET` and `HEAD`, that responses are certified for.
+    ///
+    /// Providing an empty list will result in responses being certified for requests with any
+    /// method. Otherwise, responses to requests with other methods are rejected, so that a
+    /// response that is certified for a `GET` request cannot be replayed for a `DELETE` request
+    /// to the same path. See [allows_method](Self::allows_method).
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    pub allowed_methods: Cow<'a, [&'a str]>,
+}
+
+impl DefaultFullCelExpression<'_> {
+    /// Returns the [CelVersion] of the CEL syntax that this expression uses, see
+    /// [DefaultCelExpression::version].
+    pub fn version(&self) -> CelVersion {
+        CelVersion::V2
+    }
+
+    /// Returns true if responses that are certified with this expression may be served for
+    /// requests with the given method, see [allowed_methods](Self::allowed_methods).
+    ///
+    /// Methods are compared case-insensitively, in the same way as [Method](crate::Method).
+    pub fn allows_method(&self, method: &str) -> bool {
+        allows_method(&self.allowed_methods, method)
+    }
 }
 
 impl ToString for DefaultFullCelExpression<'_> {
@@ -111,18 +272,47 @@ impl ToString for DefaultFullCelExpression<'_> {
 /// certification of request [headers](DefaultRequestCertification::headers) and
 /// [query parameters](DefaultRequestCertification::query_parameters).
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DefaultRequestCertification<'a> {
     /// A list of request headers to include in certification.
     ///
     /// As many or as little headers can be provided as desired.
     /// Providing an empty list will result in no request headers being certified.
+    ///
+    /// Header names may be patterns such as `glob:X-App-*`, see [header_name_matches](crate::header_name_matches).
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub headers: Cow<'a, [&'a str]>,
 
     /// A list of request query parameters to include in certification.
     ///
     /// As many or as little query parameters can be provided as desired.
     /// Providing an empty list will result in no request query parameters being certified.
+    /// See [certified_query](DefaultRequestCertification::certified_query) for how repeated
+    /// query parameters are certified.
+    ///
+    /// Query parameter names may be patterns such as `page*`, or exclusions such as `!utm_*`, see
+    /// [certified_query](DefaultRequestCertification::certified_query).
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub query_parameters: Cow<'a, [&'a str]>,
+
+    /// A list of `multipart/form-data` fields to include in certification instead of the raw
+    /// request body.
+    ///
+    /// Providing an empty list will result in the raw request body being certified. Otherwise,
+    /// the request body is parsed as `multipart/form-data` and only the values of the fields
+    /// with these names are certified, so large uploads, such as files, do not need to be
+    /// hashed. See [request_hash](crate::request_hash) for details.
+    #[cfg_attr(feature = "serde", serde(default, borrow))]
+    pub multipart_form_fields: Cow<'a, [&'a str]>,
+
+    /// Whether to certify the canonical representation of a `multipart/form-data` request body
+    /// instead of the raw request body.
+    ///
+    /// The canonical representation does not depend on the boundary or on the order of the
+    /// parts, see [canonical_multipart_body](crate::canonical_multipart_body). It is ignored if
+    /// [multipart_form_fields](DefaultRequestCertification::multipart_form_fields) are certified.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub canonical_multipart_body: bool,
 }
 
 impl<'a> DefaultRequestCertification<'a> {
@@ -136,49 +326,170 @@ impl<'a> DefaultRequestCertification<'a> {
         Self {
             headers: headers.into(),
             query_parameters: query_parameters.into(),
+            multipart_form_fields: Cow::Borrowed(&[]),
+            canonical_multipart_body: false,
         }
     }
-}
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) enum DefaultResponseCertificationType<'a> {
-    CertifiedResponseHeaders(Cow<'a, [&'a str]>),
-    ResponseHeaderExclusions(Cow<'a, [&'a str]>),
+    /// Sets the `multipart/form-data` fields that are certified instead of the raw request body,
+    /// see [multipart_form_fields](DefaultRequestCertification::multipart_form_fields).
+    pub fn with_multipart_form_fields(
+        mut self,
+        multipart_form_fields: impl Into<Cow<'a, [&'a str]>>,
+    ) -> Self {
+        self.multipart_form_fields = multipart_form_fields.into();
+
+        self
+    }
+
+    /// Returns the part of a request's query string that is certified by
+    /// [request_hash](crate::request_hash).
+    ///
+    /// The query string is split on `&` into parameters, without percent-decoding them. The name
+    /// of a parameter is the text before its first `=`, or the whole parameter if it has no `=`.
+    /// Every parameter whose name matches one of the certified
+    /// [query_parameters](DefaultRequestCertification::query_parameters), compared
+    /// case-insensitively, is kept verbatim, and the kept parameters are joined with `&`.
+    ///
+    /// Certified query parameters may be patterns such as `page*`, see
+    /// [query_parameter_name_matches](crate::query_parameter_name_matches). Patterns that start
+    /// with `!`, such as `!utm_*`, exclude every parameter whose name matches the rest of the
+    /// pattern, even if it also matches a certified name or pattern. For example, `["*", "!utm_*"]`
+    /// certifies every parameter except for tracking parameters, so `?utm_source=a&page=2`
+    /// certifies `page=2`. Exclusions alone do not certify any parameters.
+    ///
+    /// Repeated parameters are therefore certified once for every occurrence, in the order in
+    /// which they appear. For example, if `tag` is certified, both `?tag=a&page=2&tag=b` and
+    /// `?tag=a&tag=b` certify `tag=a&tag=b`, while `?tag=b&tag=a` certifies `tag=b&tag=a`, and
+    /// `?TAG=a&TAG=b` certifies `TAG=a&TAG=b`, which all hash differently.
+    ///
+    /// A request with a query string is hashed differently from a request without one, even if
+    /// none of its parameters are certified.
+    pub fn certified_query(&self, query: &str) -> String {
+        query
+            .split('&')
+            .filter(|parameter| {
+                let name = parameter.split('=').next().unwrap_or_default();
+
+                let is_excluded = self.query_parameters.iter().any(|pattern| {
+                    pattern
+                        .strip_prefix(QUERY_PARAMETER_EXCLUSION_PREFIX)
+                        .is_some_and(|pattern| query_parameter_name_matches(pattern, name))
+                });
+
+                !is_excluded
+                    && self
+                        .query_parameters
+                        .iter()
+                        .any(|pattern| query_parameter_name_matches(pattern, name))
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    /// Sets whether the canonical representation of a `multipart/form-data` request body is
+    /// certified instead of the raw request body, see
+    /// [canonical_multipart_body](DefaultRequestCertification::canonical_multipart_body).
+    pub fn with_canonical_multipart_body(mut self, canonical_multipart_body: bool) -> Self {
+        self.canonical_multipart_body = canonical_multipart_body;
+
+        self
+    }
 }
 
 /// Options for configuring certification of a response.
 ///
-/// The response body and status code are always certified, but this struct allows configuring the
+/// The response body and status code are always certified, but this enum allows configuring the
 /// certification of response headers. Response headers may be included using the
 /// [CertifiedResponseHeaders](DefaultResponseCertification::CertifiedResponseHeaders) variant,
 /// and response headers may be excluded using the
 /// [ResponseHeaderExclusions](DefaultResponseCertification::ResponseHeaderExclusions) variant.
+/// Both can be combined using the
+/// [CertifiedResponseHeadersWithExclusions](DefaultResponseCertification::CertifiedResponseHeadersWithExclusions)
+/// variant.
+///
+/// New ways of certifying response headers may be added in the future, so this enum is
+/// non-exhaustive.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct DefaultResponseCertification<'a>(DefaultResponseCertificationType<'a>);
-
-impl<'a> DefaultResponseCertification<'a> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, rename = "ResponseCertification", rename_all = "camelCase")
+)]
+#[non_exhaustive]
+pub enum DefaultResponseCertification<'a> {
     /// A list of response headers to include in certification.
     ///
     /// As many or as little headers can be provided as desired.
     /// Providing an empty list will result in no response headers being certified.
-    pub fn certified_response_headers(headers: impl Into<Cow<'a, [&'a str]>>) -> Self {
-        Self(DefaultResponseCertificationType::CertifiedResponseHeaders(
-            headers.into(),
-        ))
-    }
+    ///
+    /// Header names may be patterns such as `glob:X-App-*`, see [header_name_matches](crate::header_name_matches).
+    CertifiedResponseHeaders(
+        #[cfg_attr(feature = "ts", ts(type = "string[]"))]
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        Cow<'a, [&'a str]>,
+    ),
 
     /// A list of response headers to exclude from certification.
     ///
     /// As many or as little headers can be provided as desired.
     /// Providing an empty list will result in all response headers being certified.
+    ///
+    /// Header names may be patterns such as `glob:X-App-*`, see
+    /// [excluded_header_name_matches](crate::excluded_header_name_matches).
+    ResponseHeaderExclusions(
+        #[cfg_attr(feature = "ts", ts(type = "string[]"))]
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        Cow<'a, [&'a str]>,
+    ),
+
+    /// A list of response headers to include in certification, together with a list of response
+    /// headers to exclude from certification.
+    ///
+    /// A header is certified if it matches one of the `headers` and none of the `exclusions`, so
+    /// exclusions take precedence over inclusions. This is most useful together with header name
+    /// patterns, for example to certify every `X-App-*` header except for `X-App-Request-Id`.
+    CertifiedResponseHeadersWithExclusions {
+        /// The response headers to include in certification.
+        #[cfg_attr(feature = "ts", ts(type = "string[]"))]
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        headers: Cow<'a, [&'a str]>,
+
+        /// The response headers to exclude from certification, even if they are included by
+        /// `headers`.
+        #[cfg_attr(feature = "ts", ts(type = "string[]"))]
+        #[cfg_attr(feature = "serde", serde(borrow))]
+        exclusions: Cow<'a, [&'a str]>,
+    },
+}
+
+impl<'a> DefaultResponseCertification<'a> {
+    /// Creates a [CertifiedResponseHeaders](DefaultResponseCertification::CertifiedResponseHeaders)
+    /// variant with the given `headers`. This is a convenience method for creating a
+    /// [DefaultResponseCertification] without having to directly deal with the [Cow] type.
+    pub fn certified_response_headers(headers: impl Into<Cow<'a, [&'a str]>>) -> Self {
+        Self::CertifiedResponseHeaders(headers.into())
+    }
+
+    /// Creates a [ResponseHeaderExclusions](DefaultResponseCertification::ResponseHeaderExclusions)
+    /// variant with the given `headers`. This is a convenience method for creating a
+    /// [DefaultResponseCertification] without having to directly deal with the [Cow] type.
     pub fn response_header_exclusions(headers: impl Into<Cow<'a, [&'a str]>>) -> Self {
-        Self(DefaultResponseCertificationType::ResponseHeaderExclusions(
-            headers.into(),
-        ))
+        Self::ResponseHeaderExclusions(headers.into())
     }
 
-    pub(crate) fn get_type(&self) -> &DefaultResponseCertificationType<'a> {
-        &self.0
+    /// Creates a [CertifiedResponseHeadersWithExclusions](DefaultResponseCertification::CertifiedResponseHeadersWithExclusions)
+    /// variant with the given `headers` and `exclusions`. This is a convenience method for
+    /// creating a [DefaultResponseCertification] without having to directly deal with the [Cow] type.
+    pub fn certified_response_headers_with_exclusions(
+        headers: impl Into<Cow<'a, [&'a str]>>,
+        exclusions: impl Into<Cow<'a, [&'a str]>>,
+    ) -> Self {
+        Self::CertifiedResponseHeadersWithExclusions {
+            headers: headers.into(),
+            exclusions: exclusions.into(),
+        }
     }
 }
 
@@ -187,3 +498,166 @@ impl Default for DefaultResponseCertification<'_> {
         Self::certified_response_headers(vec![])
     }
 }
+
+/// Options for configuring certification of a response body.
+///
+/// By default, the response body is certified exactly as it is served. The
+/// [Decoded](DefaultBodyCertification::Decoded) variant certifies the identity body instead, so
+/// that a single certification covers every content encoding of the same response. The
+/// [Fragmented](DefaultBodyCertification::Fragmented) variant certifies the body as a list of
+/// independently hashed fragments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DefaultBodyCertification {
+    /// The response body is certified as it is served, including any content encoding.
+    #[default]
+    AsServed,
+
+    /// The response body is certified after decoding it according to the `Content-Encoding`
+    /// header of the response. The `gzip`, `deflate` and `br` encodings are supported.
+    ///
+    /// The certification must be created from the identity body, for example by providing its
+    /// hash as the `response_body_hash` of [HttpCertification::full] or
+    /// [HttpCertification::response_only]. Since every encoding of the response must have the same
+    /// certified headers, the `Content-Encoding` and `Content-Length` headers should not be
+    /// certified.
+    Decoded,
+
+    /// The response body is certified as the concatenation of fragments, whose byte lengths are
+    /// listed in the [IC-CertificateFragments](crate::CERTIFICATE_FRAGMENTS_HEADER_NAME) header
+    /// of the response. The certified body hash is the hash of the concatenated hashes of each
+    /// fragment, see [fragmented_body_hash](crate::fragmented_body_hash).
+    ///
+    /// This allows large documents that are composed of many fragments to be re-certified after
+    /// changing a single fragment, without hashing the remaining fragments again.
+    Fragmented,
+}
+
+/// Options for configuring certification of a response status code.
+///
+/// By default, the exact status code of the response is certified. The
+/// [Class](DefaultStatusCodeCertification::Class) variant certifies only the class of the status
+/// code, such as `2xx` or `4xx`, so that a single certification covers every status code of the
+/// same class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DefaultStatusCodeCertification {
+    /// The exact status code of the response is certified.
+    #[default]
+    Exact,
+
+    /// Only the class of the response status code is certified, so a response with a `204` status
+    /// code can be served with a certification that was created for a `200` status code, and vice
+    /// versa. The class is the first digit of the status code, see
+    /// [status_code_class](crate::status_code_class).
+    ///
+    /// Since the response body is still certified, responses whose status codes share a
+    /// certification must also have the same body. Responses with a `204` or `304` status code
+    /// must not have a body, see [status_code_permits_body](crate::status_code_permits_body).
+    Class,
+}
+
+/// A CEL expression that has been parsed and validated ahead of time, together with its
+/// [String] representation and the hash of that representation.
+///
+/// This is produced by the `cel_expr!` macro of the `ic-http-certification-macros` crate, which
+/// validates the expression at compile time so that it can be stored in a `const` or `static`.
+/// The [expression](PrecomputedCelExpression::expression) is typed according to the parsed expression:
+/// [DefaultFullCelExpression] for full certification, [DefaultResponseOnlyCelExpression] for
+/// response-only certification and [DefaultCelExpression] for skipped certification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrecomputedCelExpression<'a, T> {
+    /// The structured CEL expression.
+    pub expression: T,
+
+    /// The [String] representation of the CEL expression, suitable for the `IC-CertificateExpression` header.
+    pub expression_str: &'a str,
+
+    /// The SHA-256 hash of [expression_str](PrecomputedCelExpression::expression_str).
+    pub hash: crate::Hash,
+}
+
+impl<'a> PrecomputedCelExpression<'a, DefaultFullCelExpression<'a>> {
+    /// Creates an [HttpCertification] for the given [HttpRequest] and [HttpResponse] using this CEL expression.
+    /// See [HttpCertification::full] for more details.
+    pub fn certification(
+        &self,
+        request: &HttpRequest,
+        response: &HttpResponse,
+    ) -> HttpCertificationResult<HttpCertification> {
+        HttpCertification::full(&self.expression, request, response, None)
+    }
+}
+
+impl<'a> PrecomputedCelExpression<'a, DefaultResponseOnlyCelExpression<'a>> {
+    /// Creates an [HttpCertification] for the given [HttpResponse] using this CEL expression.
+    /// The [HttpRequest] is excluded from certification. See [HttpCertification::response_only] for more details.
+    pub fn certification(
+        &self,
+        _request: &HttpRequest,
+        response: &HttpResponse,
+    ) -> HttpCertificationResult<HttpCertification> {
+        HttpCertification::response_only(&self.expression, response, None)
+    }
+}
+
+impl<'a> PrecomputedCelExpression<'a, DefaultCelExpression<'a>> {
+    /// Creates an [HttpCertification] for the given [HttpRequest] and [HttpResponse] using this CEL expression.
+    /// The [HttpRequest] and [HttpResponse] are only included in certification if the CEL expression requires it.
+    pub fn certification(
+        &self,
+        request: &HttpRequest,
+        response: &HttpResponse,
+    ) -> HttpCertificationResult<HttpCertification> {
+        match &self.expression {
+            DefaultCelExpression::Full(cel_expr) => {
+                HttpCertification::full(cel_expr, request, response, None)
+            }
+            DefaultCelExpression::ResponseOnly(cel_expr) => {
+                HttpCertification::response_only(cel_expr, response, None)
+            }
+            DefaultCelExpression::Skip => Ok(HttpCertification::skip()),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::DefaultCelBuilder;
+    use rstest::*;
+
+    #[rstest]
+    #[case(CelExpression::Default(DefaultCelExpression::Skip))]
+    #[case(CelExpression::Default(DefaultCelExpression::ResponseOnly(
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::response_header_exclusions(
+                vec!["Date"],
+            ))
+            .build()
+    )))]
+    #[case(CelExpression::Default(DefaultCelExpression::Full(
+        DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept"])
+            .with_request_query_parameters(vec!["lang"])
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Content-Type"],
+            ))
+            .build()
+    )))]
+    fn cel_expression_serde_round_trip(#[case] cel_expr: CelExpression) {
+        let serialized = serde_cbor::to_vec(&cel_expr).unwrap();
+        let deserialized: CelExpression = serde_cbor::from_slice(&serialized).unwrap();
+
+        // `CelExpression` is invariant over its lifetime, so compare representations instead
+        assert_eq!(format!("{deserialized:?}"), format!("{cel_expr:?}"));
+        assert_eq!(deserialized.to_string(), cel_expr.to_string());
+    }
+}
+
+fn allows_method(allowed_methods: &[&str], method: &str) -> bool {
+    allowed_methods.is_empty()
+        || allowed_methods
+            .iter()
+            .any(|allowed_method| allowed_method.eq_ignore_ascii_case(method))
+}
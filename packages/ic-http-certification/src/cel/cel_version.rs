@@ -0,0 +1,152 @@
+use crate::CertificateVersion;
+use std::fmt::{Display, Formatter};
+
+/// The version of the CEL syntax that is used to define how a request and response pair is
+/// certified.
+///
+/// Every version of the syntax is identified by the CEL function that its expressions call, and
+/// requires a minimum [CertificateVersion] of the verifier. Version 2 is currently the only
+/// version, which uses the `default_certification` function of the
+/// [DefaultCelExpression](super::DefaultCelExpression) types.
+///
+/// New versions may be added in the future, so this enum is non-exhaustive. Canisters and
+/// verifiers can use [negotiate](CelVersion::negotiate) to select the highest version that they
+/// both support.
+///
+/// ```
+/// use ic_http_certification::cel::CelVersion;
+///
+/// let cel_version = CelVersion::negotiate(&[CelVersion::V2]);
+/// assert_eq!(cel_version, Some(CelVersion::V2));
+/// assert_eq!(cel_version.unwrap().function_name(), "default_certification");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum CelVersion {
+    /// Version 2, which certifies requests and responses with the `default_certification`
+    /// function.
+    V2,
+}
+
+impl CelVersion {
+    /// The highest version that is supported by this crate. Expressions created with
+    /// [DefaultCelBuilder](super::DefaultCelBuilder) use this version.
+    pub const LATEST: CelVersion = CelVersion::V2;
+
+    /// Every version that is supported by this crate, in ascending order.
+    pub const SUPPORTED: &'static [CelVersion] = &[CelVersion::V2];
+
+    /// Selects the highest version that is supported by both this crate and a peer, given the
+    /// versions that the peer supports. Returns [None] if there is no such version.
+    pub fn negotiate(peer_versions: &[CelVersion]) -> Option<Self> {
+        Self::SUPPORTED
+            .iter()
+            .rev()
+            .find(|version| peer_versions.contains(version))
+            .copied()
+    }
+
+    /// Returns the version with the given number, or [None] if the version is not supported by
+    /// this crate.
+    pub fn from_u16(version: u16) -> Option<Self> {
+        Self::SUPPORTED
+            .iter()
+            .find(|supported_version| supported_version.as_u16() == version)
+            .copied()
+    }
+
+    /// Returns the version number.
+    pub const fn as_u16(&self) -> u16 {
+        match self {
+            CelVersion::V2 => 2,
+        }
+    }
+
+    /// Returns the version whose expressions call the CEL function with the given name, or [None]
+    /// if the function is not supported by this crate.
+    pub fn from_function_name(function_name: &str) -> Option<Self> {
+        Self::SUPPORTED
+            .iter()
+            .find(|version| version.function_name() == function_name)
+            .copied()
+    }
+
+    /// Returns the name of the CEL function that expressions of this version call.
+    pub const fn function_name(&self) -> &'static str {
+        match self {
+            CelVersion::V2 => "default_certification",
+        }
+    }
+
+    /// Returns the minimum [CertificateVersion] that a verifier must support to verify responses
+    /// that are certified with expressions of this version.
+    pub const fn certificate_version(&self) -> CertificateVersion {
+        match self {
+            CelVersion::V2 => CertificateVersion::V2,
+        }
+    }
+}
+
+impl From<CelVersion> for u16 {
+    fn from(cel_version: CelVersion) -> Self {
+        cel_version.as_u16()
+    }
+}
+
+impl Display for CelVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_u16())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DefaultCelBuilder, DefaultCelExpression};
+    use rstest::*;
+
+    #[rstest]
+    #[case::supported(&[CelVersion::V2], Some(CelVersion::V2))]
+    #[case::none(&[], None)]
+    fn negotiate(#[case] peer_versions: &[CelVersion], #[case] expected: Option<CelVersion>) {
+        assert_eq!(CelVersion::negotiate(peer_versions), expected);
+    }
+
+    #[rstest]
+    #[case(1, None)]
+    #[case(2, Some(CelVersion::V2))]
+    #[case(3, None)]
+    fn from_u16(#[case] version: u16, #[case] expected: Option<CelVersion>) {
+        assert_eq!(CelVersion::from_u16(version), expected);
+    }
+
+    #[rstest]
+    #[case("default_certification", Some(CelVersion::V2))]
+    #[case("default_certification_v3", None)]
+    fn from_function_name(#[case] function_name: &str, #[case] expected: Option<CelVersion>) {
+        assert_eq!(CelVersion::from_function_name(function_name), expected);
+    }
+
+    #[rstest]
+    fn latest_version_is_supported() {
+        assert_eq!(CelVersion::SUPPORTED.last(), Some(&CelVersion::LATEST));
+        assert_eq!(
+            CelVersion::LATEST.certificate_version(),
+            CertificateVersion::V2
+        );
+    }
+
+    #[rstest]
+    fn expressions_use_the_function_of_their_version() {
+        let cel_expr = DefaultCelBuilder::response_only_certification().build();
+        let skip_cel_expr = DefaultCelBuilder::skip_certification();
+
+        assert_eq!(DefaultCelBuilder::cel_version(), CelVersion::LATEST);
+        assert_eq!(cel_expr.version(), CelVersion::V2);
+        assert_eq!(skip_cel_expr.version(), CelVersion::V2);
+        assert_eq!(DefaultCelExpression::Skip.version(), CelVersion::V2);
+        assert!(cel_expr
+            .to_string()
+            .starts_with(cel_expr.version().function_name()));
+    }
+}
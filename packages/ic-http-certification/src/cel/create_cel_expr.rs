@@ -1,7 +1,7 @@
 use super::{
-    CelExpression, DefaultCelExpression, DefaultFullCelExpression, DefaultRequestCertification,
-    DefaultResponseCertification, DefaultResponseCertificationType,
-    DefaultResponseOnlyCelExpression,
+    CelExpression, DefaultBodyCertification, DefaultCelExpression, DefaultFullCelExpression,
+    DefaultRequestCertification, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+    DefaultStatusCodeCertification,
 };
 
 /// Converts a CEL expression from a [CelExpression] struct into it's [String] representation.
@@ -50,7 +50,13 @@ pub fn create_default_response_only_cel_expr(
     cel_expr.push_str("certification:Certification{");
     cel_expr.push_str("no_request_certification:Empty{},");
 
-    create_response_cel_expr(&mut cel_expr, &certification.response);
+    create_response_cel_expr(
+        &mut cel_expr,
+        &certification.response,
+        &certification.body,
+        &certification.status,
+    );
+    create_allowed_methods_cel_expr(&mut cel_expr, &certification.allowed_methods);
 
     cel_expr.push('}');
 
@@ -68,7 +74,13 @@ pub fn create_default_full_cel_expr(certification: &DefaultFullCelExpression) ->
     cel_expr.push_str("certification:Certification{");
 
     create_request_cel_expr(&mut cel_expr, &certification.request);
-    create_response_cel_expr(&mut cel_expr, &certification.response);
+    create_response_cel_expr(
+        &mut cel_expr,
+        &certification.response,
+        &certification.body,
+        &certification.status,
+    );
+    create_allowed_methods_cel_expr(&mut cel_expr, &certification.allowed_methods);
 
     cel_expr.push('}');
 
@@ -95,33 +107,90 @@ fn create_request_cel_expr(
         cel_expr.push('"');
     }
 
-    cel_expr.push_str("]},");
+    if !request_certification.multipart_form_fields.is_empty() {
+        cel_expr.push_str(r#"],certified_multipart_form_fields:[""#);
+        cel_expr.push_str(&request_certification.multipart_form_fields.join(r#"",""#));
+        cel_expr.push('"');
+    }
+    cel_expr.push(']');
+
+    if request_certification.canonical_multipart_body {
+        cel_expr.push_str(",canonical_multipart_body_certification:Empty{}");
+    }
+
+    cel_expr.push_str("},");
 }
 
 fn create_response_cel_expr(
     cel_expr: &mut String,
     response_certification: &DefaultResponseCertification,
+    body_certification: &DefaultBodyCertification,
+    status_certification: &DefaultStatusCodeCertification,
 ) {
     cel_expr.push_str("response_certification:ResponseCertification{");
 
-    let headers = match response_certification.get_type() {
-        DefaultResponseCertificationType::CertifiedResponseHeaders(headers) => {
-            cel_expr.push_str("certified_response_headers");
-            headers
+    match response_certification {
+        DefaultResponseCertification::CertifiedResponseHeaders(headers) => {
+            create_response_header_list_cel_expr(cel_expr, "certified_response_headers", headers);
+        }
+        DefaultResponseCertification::ResponseHeaderExclusions(headers) => {
+            create_response_header_list_cel_expr(cel_expr, "response_header_exclusions", headers);
+        }
+        DefaultResponseCertification::CertifiedResponseHeadersWithExclusions {
+            headers,
+            exclusions,
+        } => {
+            create_response_header_list_cel_expr(cel_expr, "certified_response_headers", headers);
+            cel_expr.push(',');
+            create_response_header_list_cel_expr(
+                cel_expr,
+                "response_header_exclusions",
+                exclusions,
+            );
+        }
+    }
+
+    match body_certification {
+        DefaultBodyCertification::AsServed => {}
+        DefaultBodyCertification::Decoded => {
+            cel_expr.push_str(",decoded_body_certification:Empty{}");
         }
-        DefaultResponseCertificationType::ResponseHeaderExclusions(headers) => {
-            cel_expr.push_str("response_header_exclusions");
-            headers
+        DefaultBodyCertification::Fragmented => {
+            cel_expr.push_str(",fragmented_body_certification:Empty{}");
         }
-    };
+    }
+
+    match status_certification {
+        DefaultStatusCodeCertification::Exact => {}
+        DefaultStatusCodeCertification::Class => {
+            cel_expr.push_str(",status_code_class_certification:Empty{}");
+        }
+    }
 
+    cel_expr.push('}');
+}
+
+fn create_allowed_methods_cel_expr(cel_expr: &mut String, allowed_methods: &[&str]) {
+    if !allowed_methods.is_empty() {
+        cel_expr.push_str(r#",allowed_request_methods:[""#);
+        cel_expr.push_str(&allowed_methods.join(r#"",""#));
+        cel_expr.push_str(r#""]"#);
+    }
+}
+
+fn create_response_header_list_cel_expr(
+    cel_expr: &mut String,
+    property_name: &str,
+    headers: &[&str],
+) {
+    cel_expr.push_str(property_name);
     cel_expr.push_str(":ResponseHeaderList{headers:[");
     if !headers.is_empty() {
         cel_expr.push('"');
         cel_expr.push_str(&headers.join(r#"",""#));
         cel_expr.push('"');
     }
-    cel_expr.push_str("]}}");
+    cel_expr.push_str("]}");
 }
 
 #[cfg(test)]
@@ -129,6 +198,7 @@ mod tests {
     use super::*;
     use crate::cel::fixtures::*;
     use rstest::*;
+    use std::borrow::Cow;
 
     #[rstest]
     #[case::no_certification(no_certification(), no_certification_cel())]
@@ -172,6 +242,31 @@ mod tests {
         empty_request_response_exclusions(),
         empty_request_response_exclusions_cel()
     )]
+    #[case::no_request_decoded_body(no_request_decoded_body(), no_request_decoded_body_cel())]
+    #[case::include_request_decoded_body(
+        include_request_decoded_body(),
+        include_request_decoded_body_cel()
+    )]
+    #[case::no_request_fragmented_body(
+        no_request_fragmented_body(),
+        no_request_fragmented_body_cel()
+    )]
+    #[case::include_request_multipart_form_fields(
+        include_request_multipart_form_fields(),
+        include_request_multipart_form_fields_cel()
+    )]
+    #[case::include_request_canonical_multipart_body(
+        include_request_canonical_multipart_body(),
+        include_request_canonical_multipart_body_cel()
+    )]
+    #[case::include_request_response_header_inclusions_with_exclusions(
+        include_request_response_header_inclusions_with_exclusions(),
+        include_request_response_header_inclusions_with_exclusions_cel()
+    )]
+    #[case::include_request_status_code_class(
+        include_request_status_code_class(),
+        include_request_status_code_class_cel()
+    )]
     fn create_cel_expr_test(#[case] certification: CelExpression, #[case] expected: String) {
         let cel_expr = create_cel_expr(&certification);
 
@@ -192,6 +287,9 @@ mod tests {
                     "Content-Type",
                     "Content-Encoding",
                 ]),
+                body: DefaultBodyCertification::AsServed,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
             },
         ))
     }
@@ -204,6 +302,9 @@ mod tests {
                     "Cookie",
                     "Set-Cookie",
                 ]),
+                body: DefaultBodyCertification::AsServed,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
             },
         ))
     }
@@ -212,6 +313,9 @@ mod tests {
         CelExpression::Default(DefaultCelExpression::ResponseOnly(
             DefaultResponseOnlyCelExpression {
                 response: DefaultResponseCertification::certified_response_headers(vec![]),
+                body: DefaultBodyCertification::AsServed,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
             },
         ))
     }
@@ -220,10 +324,36 @@ mod tests {
         CelExpression::Default(DefaultCelExpression::ResponseOnly(
             DefaultResponseOnlyCelExpression {
                 response: DefaultResponseCertification::response_header_exclusions(vec![]),
+                body: DefaultBodyCertification::AsServed,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
             },
         ))
     }
 
+    fn include_request_response_header_inclusions_with_exclusions() -> CelExpression<'static> {
+        CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
+            request: DefaultRequestCertification::new(vec!["Accept"], vec![]),
+            response: DefaultResponseCertification::certified_response_headers_with_exclusions(
+                vec!["glob:X-App-*", "Content-Type"],
+                vec!["X-App-Request-Id"],
+            ),
+            body: DefaultBodyCertification::AsServed,
+            status: DefaultStatusCodeCertification::Exact,
+            allowed_methods: Cow::Borrowed(&[]),
+        }))
+    }
+
+    fn include_request_status_code_class() -> CelExpression<'static> {
+        CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
+            request: DefaultRequestCertification::new(vec![], vec![]),
+            response: DefaultResponseCertification::certified_response_headers(vec![]),
+            body: DefaultBodyCertification::AsServed,
+            status: DefaultStatusCodeCertification::Class,
+            allowed_methods: Cow::Borrowed(&[]),
+        }))
+    }
+
     fn include_request_response_header_inclusions() -> CelExpression<'static> {
         CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
             request: DefaultRequestCertification::new(
@@ -237,6 +367,9 @@ mod tests {
                 "Content-Type",
                 "Content-Encoding",
             ]),
+            body: DefaultBodyCertification::AsServed,
+            status: DefaultStatusCodeCertification::Exact,
+            allowed_methods: Cow::Borrowed(&[]),
         }))
     }
 
@@ -251,6 +384,9 @@ mod tests {
                 "Cookie",
                 "Set-Cookie",
             ]),
+            body: DefaultBodyCertification::AsServed,
+            status: DefaultStatusCodeCertification::Exact,
+            allowed_methods: Cow::Borrowed(&[]),
         }))
     }
 
@@ -261,6 +397,9 @@ mod tests {
                 vec!["foo", "bar", "baz"],
             ),
             response: DefaultResponseCertification::certified_response_headers(vec![]),
+            body: DefaultBodyCertification::AsServed,
+            status: DefaultStatusCodeCertification::Exact,
+            allowed_methods: Cow::Borrowed(&[]),
         }))
     }
 
@@ -271,6 +410,9 @@ mod tests {
                 vec!["foo", "bar", "baz"],
             ),
             response: DefaultResponseCertification::response_header_exclusions(vec![]),
+            body: DefaultBodyCertification::AsServed,
+            status: DefaultStatusCodeCertification::Exact,
+            allowed_methods: Cow::Borrowed(&[]),
         }))
     }
 
@@ -278,6 +420,9 @@ mod tests {
         CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
             request: DefaultRequestCertification::new(vec![], vec![]),
             response: DefaultResponseCertification::certified_response_headers(vec![]),
+            body: DefaultBodyCertification::AsServed,
+            status: DefaultStatusCodeCertification::Exact,
+            allowed_methods: Cow::Borrowed(&[]),
         }))
     }
 
@@ -285,6 +430,67 @@ mod tests {
         CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
             request: DefaultRequestCertification::new(vec![], vec![]),
             response: DefaultResponseCertification::response_header_exclusions(vec![]),
+            body: DefaultBodyCertification::AsServed,
+            status: DefaultStatusCodeCertification::Exact,
+            allowed_methods: Cow::Borrowed(&[]),
+        }))
+    }
+
+    fn no_request_decoded_body() -> CelExpression<'static> {
+        CelExpression::Default(DefaultCelExpression::ResponseOnly(
+            DefaultResponseOnlyCelExpression {
+                response: DefaultResponseCertification::certified_response_headers(vec![
+                    "Cache-Control",
+                ]),
+                body: DefaultBodyCertification::Decoded,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
+            },
+        ))
+    }
+
+    fn include_request_decoded_body() -> CelExpression<'static> {
+        CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
+            request: DefaultRequestCertification::new(vec!["Accept"], vec![]),
+            response: DefaultResponseCertification::response_header_exclusions(vec!["Date"]),
+            body: DefaultBodyCertification::Decoded,
+            status: DefaultStatusCodeCertification::Exact,
+            allowed_methods: Cow::Borrowed(&[]),
+        }))
+    }
+
+    fn no_request_fragmented_body() -> CelExpression<'static> {
+        CelExpression::Default(DefaultCelExpression::ResponseOnly(
+            DefaultResponseOnlyCelExpression {
+                response: DefaultResponseCertification::certified_response_headers(vec![
+                    "IC-CertificateFragments",
+                ]),
+                body: DefaultBodyCertification::Fragmented,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
+            },
+        ))
+    }
+
+    fn include_request_multipart_form_fields() -> CelExpression<'static> {
+        CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
+            request: DefaultRequestCertification::new(vec!["Content-Type"], vec![])
+                .with_multipart_form_fields(vec!["title", "tags"]),
+            response: DefaultResponseCertification::certified_response_headers(vec![]),
+            body: DefaultBodyCertification::AsServed,
+            status: DefaultStatusCodeCertification::Exact,
+            allowed_methods: Cow::Borrowed(&[]),
+        }))
+    }
+
+    fn include_request_canonical_multipart_body() -> CelExpression<'static> {
+        CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
+            request: DefaultRequestCertification::new(vec!["Content-Type"], vec![])
+                .with_canonical_multipart_body(true),
+            response: DefaultResponseCertification::certified_response_headers(vec![]),
+            body: DefaultBodyCertification::AsServed,
+            status: DefaultStatusCodeCertification::Exact,
+            allowed_methods: Cow::Borrowed(&[]),
         }))
     }
 }
@@ -0,0 +1,688 @@
+use super::{
+    parse_default_cel_expression, CelParserResult, DefaultBodyCertification, DefaultCelExpression,
+    DefaultRequestCertification, DefaultResponseCertification, DefaultStatusCodeCertification,
+};
+use std::fmt::{Display, Formatter};
+
+/// A list of names that is certified by a [DefaultCelExpression], see
+/// [CelExpressionDifference::CertifiedNames].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CelNameList {
+    /// The certified request headers, see [DefaultRequestCertification::headers].
+    RequestHeaders,
+
+    /// The certified request query parameters, see [DefaultRequestCertification::query_parameters].
+    QueryParameters,
+
+    /// The certified `multipart/form-data` fields, see
+    /// [DefaultRequestCertification::multipart_form_fields].
+    MultipartFormFields,
+
+    /// The certified response headers, see [DefaultResponseCertification].
+    ResponseHeaders,
+
+    /// The response headers that are excluded from certification, see
+    /// [DefaultResponseCertification].
+    ResponseHeaderExclusions,
+
+    /// The request methods that responses are certified for, see
+    /// [DefaultFullCelExpression::allowed_methods](super::DefaultFullCelExpression::allowed_methods).
+    AllowedMethods,
+}
+
+impl Display for CelNameList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            CelNameList::RequestHeaders => "certified request headers",
+            CelNameList::QueryParameters => "certified query parameters",
+            CelNameList::MultipartFormFields => "certified multipart form fields",
+            CelNameList::ResponseHeaders => "certified response headers",
+            CelNameList::ResponseHeaderExclusions => "response header exclusions",
+            CelNameList::AllowedMethods => "allowed request methods",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// A difference between two CEL expressions, as returned by
+/// [diff_default_cel_expressions](diff_default_cel_expressions()).
+///
+/// Every difference describes what the `expected` expression certifies and what the `found`
+/// expression certifies instead. The [Display] implementation explains the difference in a
+/// single sentence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CelExpressionDifference {
+    /// The expressions are of different kinds, for example one certifies the request and the
+    /// other one does not. The kinds are `Full`, `ResponseOnly` and `Skip`.
+    ExpressionType {
+        /// The kind of the expected expression.
+        expected: String,
+        /// The kind of the found expression.
+        found: String,
+    },
+
+    /// The expressions certify different response headers, for example one lists the certified
+    /// response headers and the other one lists the excluded response headers. The modes are
+    /// `CertifiedResponseHeaders`, `ResponseHeaderExclusions` and
+    /// `CertifiedResponseHeadersWithExclusions`.
+    ResponseHeaderCertification {
+        /// The response header certification mode of the expected expression.
+        expected: String,
+        /// The response header certification mode of the found expression.
+        found: String,
+    },
+
+    /// A list of names differs between the expressions. Header and query parameter names and
+    /// request methods are compared case-insensitively, and `multipart/form-data` field names are
+    /// compared case-sensitively.
+    CertifiedNames {
+        /// The list that differs.
+        list: CelNameList,
+        /// The names that are listed by the expected expression, but not by the found expression.
+        missing: Vec<String>,
+        /// The names that are listed by the found expression, but not by the expected expression.
+        unexpected: Vec<String>,
+    },
+
+    /// Only one of the expressions certifies the canonical representation of a
+    /// `multipart/form-data` request body.
+    CanonicalMultipartBody {
+        /// Whether the expected expression certifies the canonical representation.
+        expected: bool,
+        /// Whether the found expression certifies the canonical representation.
+        found: bool,
+    },
+
+    /// The expressions certify the response body differently.
+    BodyCertification {
+        /// The body certification of the expected expression.
+        expected: DefaultBodyCertification,
+        /// The body certification of the found expression.
+        found: DefaultBodyCertification,
+    },
+
+    /// The expressions certify the response status code differently.
+    StatusCodeCertification {
+        /// The status code certification of the expected expression.
+        expected: DefaultStatusCodeCertification,
+        /// The status code certification of the found expression.
+        found: DefaultStatusCodeCertification,
+    },
+
+    /// The expressions certify the same request and response pairs, but their [String]
+    /// representations differ, for example in the order or case of names or in whitespace.
+    /// Since the representation is included in certification, the expressions still have
+    /// different hashes. See [DefaultCelExpression::canonicalize] to avoid this.
+    Representation {
+        /// The [String] representation of the expected expression.
+        expected: String,
+        /// The [String] representation of the found expression.
+        found: String,
+    },
+}
+
+impl Display for CelExpressionDifference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CelExpressionDifference::ExpressionType { expected, found } => {
+                write!(f, "Expected a {expected} CEL expression, found {found}")
+            }
+            CelExpressionDifference::ResponseHeaderCertification { expected, found } => {
+                write!(
+                    f,
+                    "Expected response headers to be certified with {expected}, found {found}"
+                )
+            }
+            CelExpressionDifference::CertifiedNames {
+                list,
+                missing,
+                unexpected,
+            } => {
+                write!(f, "The {list} differ")?;
+                if !missing.is_empty() {
+                    write!(f, ", missing {missing:?}")?;
+                }
+                if !unexpected.is_empty() {
+                    write!(f, ", unexpected {unexpected:?}")?;
+                }
+
+                Ok(())
+            }
+            CelExpressionDifference::CanonicalMultipartBody { expected, found } => {
+                write!(
+                    f,
+                    "Expected canonical multipart body certification to be {expected}, found {found}"
+                )
+            }
+            CelExpressionDifference::BodyCertification { expected, found } => {
+                write!(
+                    f,
+                    "Expected {expected:?} body certification, found {found:?}"
+                )
+            }
+            CelExpressionDifference::StatusCodeCertification { expected, found } => {
+                write!(
+                    f,
+                    "Expected {expected:?} status code certification, found {found:?}"
+                )
+            }
+            CelExpressionDifference::Representation { expected, found } => {
+                write!(
+                    f,
+                    "The expressions are equivalent, but their representations differ, expected {expected:?}, found {found:?}"
+                )
+            }
+        }
+    }
+}
+
+/// Compares two CEL expressions and explains how they differ.
+///
+/// This is useful to debug verification failures that are caused by a CEL expression that does
+/// not match the one that was certified, for example by comparing the value of the
+/// `IC-CertificateExpression` header of a response with the expression that the canister
+/// certified. Returns an empty list if the [String] representations of both expressions are
+/// identical, and therefore hash identically.
+///
+/// If the expressions certify the same request and response pairs, but their representations
+/// still differ, a single [Representation](CelExpressionDifference::Representation) difference
+/// is returned.
+///
+/// [DefaultCelExpression::diff](DefaultCelExpression::diff()) is an alias of this function and
+/// can be used for ergonomics.
+///
+/// ```
+/// use ic_http_certification::{
+///     cel::{diff_default_cel_expressions, CelExpressionDifference, CelNameList},
+///     DefaultCelBuilder, DefaultCelExpression,
+/// };
+///
+/// let expected = DefaultCelExpression::Full(
+///     DefaultCelBuilder::full_certification()
+///         .with_request_headers(vec!["Accept"])
+///         .build(),
+/// );
+/// let found = DefaultCelExpression::Full(
+///     DefaultCelBuilder::full_certification()
+///         .with_request_headers(vec!["Accept", "Accept-Language"])
+///         .build(),
+/// );
+///
+/// assert_eq!(
+///     diff_default_cel_expressions(&expected, &found),
+///     vec![CelExpressionDifference::CertifiedNames {
+///         list: CelNameList::RequestHeaders,
+///         missing: vec![],
+///         unexpected: vec!["Accept-Language".to_string()],
+///     }]
+/// );
+/// ```
+pub fn diff_default_cel_expressions(
+    expected: &DefaultCelExpression,
+    found: &DefaultCelExpression,
+) -> Vec<CelExpressionDifference> {
+    let mut differences = vec![];
+
+    match (expected, found) {
+        (DefaultCelExpression::Skip, DefaultCelExpression::Skip) => {}
+        (
+            DefaultCelExpression::ResponseOnly(expected),
+            DefaultCelExpression::ResponseOnly(found),
+        ) => {
+            diff_response_certification(&mut differences, &expected.response, &found.response);
+            diff_names(
+                &mut differences,
+                CelNameList::AllowedMethods,
+                &expected.allowed_methods,
+                &found.allowed_methods,
+                str::eq_ignore_ascii_case,
+            );
+            diff_value(
+                &mut differences,
+                expected.body,
+                found.body,
+                |expected, found| CelExpressionDifference::BodyCertification { expected, found },
+            );
+            diff_value(
+                &mut differences,
+                expected.status,
+                found.status,
+                |expected, found| CelExpressionDifference::StatusCodeCertification {
+                    expected,
+                    found,
+                },
+            );
+        }
+        (DefaultCelExpression::Full(expected), DefaultCelExpression::Full(found)) => {
+            diff_request_certification(&mut differences, &expected.request, &found.request);
+            diff_response_certification(&mut differences, &expected.response, &found.response);
+            diff_names(
+                &mut differences,
+                CelNameList::AllowedMethods,
+                &expected.allowed_methods,
+                &found.allowed_methods,
+                str::eq_ignore_ascii_case,
+            );
+            diff_value(
+                &mut differences,
+                expected.body,
+                found.body,
+                |expected, found| CelExpressionDifference::BodyCertification { expected, found },
+            );
+            diff_value(
+                &mut differences,
+                expected.status,
+                found.status,
+                |expected, found| CelExpressionDifference::StatusCodeCertification {
+                    expected,
+                    found,
+                },
+            );
+        }
+        (expected, found) => {
+            differences.push(CelExpressionDifference::ExpressionType {
+                expected: expression_type(expected).to_string(),
+                found: expression_type(found).to_string(),
+            });
+        }
+    }
+
+    if differences.is_empty() {
+        diff_representation(&mut differences, expected.to_string(), found.to_string());
+    }
+
+    differences
+}
+
+/// Parses two CEL expressions from their [String] representations, for example from the value
+/// of an `IC-CertificateExpression` header, and explains how they differ, see
+/// [diff_default_cel_expressions](diff_default_cel_expressions()).
+///
+/// Unlike [diff_default_cel_expressions](diff_default_cel_expressions()), this also reports a
+/// [Representation](CelExpressionDifference::Representation) difference if the expressions only
+/// differ in whitespace. Returns an error if either expression cannot be parsed.
+pub fn diff_cel_expressions(
+    expected: &str,
+    found: &str,
+) -> CelParserResult<Vec<CelExpressionDifference>> {
+    let mut differences = diff_default_cel_expressions(
+        &parse_default_cel_expression(expected)?,
+        &parse_default_cel_expression(found)?,
+    );
+
+    if differences.is_empty() {
+        diff_representation(&mut differences, expected.to_string(), found.to_string());
+    }
+
+    Ok(differences)
+}
+
+fn diff_request_certification(
+    differences: &mut Vec<CelExpressionDifference>,
+    expected: &DefaultRequestCertification,
+    found: &DefaultRequestCertification,
+) {
+    diff_names(
+        differences,
+        CelNameList::RequestHeaders,
+        &expected.headers,
+        &found.headers,
+        str::eq_ignore_ascii_case,
+    );
+    diff_names(
+        differences,
+        CelNameList::QueryParameters,
+        &expected.query_parameters,
+        &found.query_parameters,
+        str::eq_ignore_ascii_case,
+    );
+    diff_names(
+        differences,
+        CelNameList::MultipartFormFields,
+        &expected.multipart_form_fields,
+        &found.multipart_form_fields,
+        |expected, found| expected == found,
+    );
+    // the canonical multipart body is ignored if form fields are certified
+    if expected.multipart_form_fields.is_empty() && found.multipart_form_fields.is_empty() {
+        diff_value(
+            differences,
+            expected.canonical_multipart_body,
+            found.canonical_multipart_body,
+            |expected, found| CelExpressionDifference::CanonicalMultipartBody { expected, found },
+        );
+    }
+}
+
+fn diff_response_certification(
+    differences: &mut Vec<CelExpressionDifference>,
+    expected: &DefaultResponseCertification,
+    found: &DefaultResponseCertification,
+) {
+    let (expected_headers, expected_exclusions) = response_header_lists(expected);
+    let (found_headers, found_exclusions) = response_header_lists(found);
+
+    if expected_headers.is_some() != found_headers.is_some()
+        || expected_exclusions.is_some() != found_exclusions.is_some()
+    {
+        differences.push(CelExpressionDifference::ResponseHeaderCertification {
+            expected: response_certification_mode(expected_headers, expected_exclusions)
+                .to_string(),
+            found: response_certification_mode(found_headers, found_exclusions).to_string(),
+        });
+    }
+
+    if let (Some(expected_headers), Some(found_headers)) = (expected_headers, found_headers) {
+        diff_names(
+            differences,
+            CelNameList::ResponseHeaders,
+            expected_headers,
+            found_headers,
+            str::eq_ignore_ascii_case,
+        );
+    }
+    if let (Some(expected_exclusions), Some(found_exclusions)) =
+        (expected_exclusions, found_exclusions)
+    {
+        diff_names(
+            differences,
+            CelNameList::ResponseHeaderExclusions,
+            expected_exclusions,
+            found_exclusions,
+            str::eq_ignore_ascii_case,
+        );
+    }
+}
+
+/// Returns the certified and excluded response headers, treating an empty list of exclusions
+/// that is combined with certified response headers as if there were no exclusions.
+fn response_header_lists<'a>(
+    response_certification: &'a DefaultResponseCertification,
+) -> (Option<&'a [&'a str]>, Option<&'a [&'a str]>) {
+    match response_certification {
+        DefaultResponseCertification::CertifiedResponseHeaders(headers) => (Some(headers), None),
+        DefaultResponseCertification::ResponseHeaderExclusions(headers) => (None, Some(headers)),
+        DefaultResponseCertification::CertifiedResponseHeadersWithExclusions {
+            headers,
+            exclusions,
+        } => (
+            Some(headers),
+            Some(exclusions.as_ref()).filter(|exclusions| !exclusions.is_empty()),
+        ),
+    }
+}
+
+fn response_certification_mode(
+    headers: Option<&[&str]>,
+    exclusions: Option<&[&str]>,
+) -> &'static str {
+    match (headers, exclusions) {
+        (Some(_), Some(_)) => "CertifiedResponseHeadersWithExclusions",
+        (None, Some(_)) => "ResponseHeaderExclusions",
+        _ => "CertifiedResponseHeaders",
+    }
+}
+
+fn diff_names(
+    differences: &mut Vec<CelExpressionDifference>,
+    list: CelNameList,
+    expected: &[&str],
+    found: &[&str],
+    eq: impl Fn(&str, &str) -> bool,
+) {
+    let difference = |names: &[&str], other_names: &[&str]| {
+        let mut difference: Vec<String> = vec![];
+        for name in names {
+            let is_listed = other_names.iter().any(|other_name| eq(name, other_name));
+            let is_reported = difference
+                .iter()
+                .any(|reported_name| eq(name, reported_name));
+
+            if !is_listed && !is_reported {
+                difference.push(name.to_string());
+            }
+        }
+
+        difference
+    };
+
+    let missing = difference(expected, found);
+    let unexpected = difference(found, expected);
+
+    if !missing.is_empty() || !unexpected.is_empty() {
+        differences.push(CelExpressionDifference::CertifiedNames {
+            list,
+            missing,
+            unexpected,
+        });
+    }
+}
+
+fn diff_value<T: PartialEq>(
+    differences: &mut Vec<CelExpressionDifference>,
+    expected: T,
+    found: T,
+    difference: impl FnOnce(T, T) -> CelExpressionDifference,
+) {
+    if expected != found {
+        differences.push(difference(expected, found));
+    }
+}
+
+fn diff_representation(
+    differences: &mut Vec<CelExpressionDifference>,
+    expected: String,
+    found: String,
+) {
+    diff_value(differences, expected, found, |expected, found| {
+        CelExpressionDifference::Representation { expected, found }
+    });
+}
+
+fn expression_type(cel_expr: &DefaultCelExpression) -> &'static str {
+    match cel_expr {
+        DefaultCelExpression::Full(_) => "Full",
+        DefaultCelExpression::ResponseOnly(_) => "ResponseOnly",
+        DefaultCelExpression::Skip => "Skip",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cel::fixtures::*, DefaultCelBuilder};
+    use rstest::*;
+
+    fn full_cel_expr() -> DefaultCelExpression<'static> {
+        DefaultCelExpression::Full(
+            DefaultCelBuilder::full_certification()
+                .with_request_headers(vec!["Accept", "Accept-Encoding"])
+                .with_request_query_parameters(vec!["page"])
+                .with_response_certification(
+                    DefaultResponseCertification::certified_response_headers(vec![
+                        "Content-Type",
+                        "Cache-Control",
+                    ]),
+                )
+                .build(),
+        )
+    }
+
+    #[rstest]
+    fn identical_expressions() {
+        assert_eq!(
+            diff_default_cel_expressions(&full_cel_expr(), &full_cel_expr()),
+            vec![]
+        );
+    }
+
+    #[rstest]
+    #[case::expression_type(
+        DefaultCelExpression::Skip,
+        vec![CelExpressionDifference::ExpressionType {
+            expected: "Full".to_string(),
+            found: "Skip".to_string(),
+        }],
+    )]
+    #[case::certified_names(
+        DefaultCelExpression::Full(
+            DefaultCelBuilder::full_certification()
+                .with_request_headers(vec!["accept", "Accept-Language"])
+                .with_request_query_parameters(vec!["page", "limit"])
+                .with_response_certification(
+                    DefaultResponseCertification::certified_response_headers(vec![
+                        "Content-Type",
+                    ]),
+                )
+                .build(),
+        ),
+        vec![
+            CelExpressionDifference::CertifiedNames {
+                list: CelNameList::RequestHeaders,
+                missing: vec!["Accept-Encoding".to_string()],
+                unexpected: vec!["Accept-Language".to_string()],
+            },
+            CelExpressionDifference::CertifiedNames {
+                list: CelNameList::QueryParameters,
+                missing: vec![],
+                unexpected: vec!["limit".to_string()],
+            },
+            CelExpressionDifference::CertifiedNames {
+                list: CelNameList::ResponseHeaders,
+                missing: vec!["Cache-Control".to_string()],
+                unexpected: vec![],
+            },
+        ],
+    )]
+    #[case::response_header_certification(
+        DefaultCelExpression::Full(
+            DefaultCelBuilder::full_certification()
+                .with_request_headers(vec!["Accept", "Accept-Encoding"])
+                .with_request_query_parameters(vec!["page"])
+                .with_response_certification(
+                    DefaultResponseCertification::response_header_exclusions(vec!["Date"]),
+                )
+                .build(),
+        ),
+        vec![CelExpressionDifference::ResponseHeaderCertification {
+            expected: "CertifiedResponseHeaders".to_string(),
+            found: "ResponseHeaderExclusions".to_string(),
+        }],
+    )]
+    #[case::body_and_status_code_certification(
+        DefaultCelExpression::Full(
+            DefaultCelBuilder::full_certification()
+                .with_request_headers(vec!["Accept", "Accept-Encoding"])
+                .with_request_query_parameters(vec!["page"])
+                .with_response_certification(
+                    DefaultResponseCertification::certified_response_headers(vec![
+                        "Content-Type",
+                        "Cache-Control",
+                    ]),
+                )
+                .with_decoded_body_certification()
+                .with_status_code_class_certification()
+                .build(),
+        ),
+        vec![
+            CelExpressionDifference::BodyCertification {
+                expected: DefaultBodyCertification::AsServed,
+                found: DefaultBodyCertification::Decoded,
+            },
+            CelExpressionDifference::StatusCodeCertification {
+                expected: DefaultStatusCodeCertification::Exact,
+                found: DefaultStatusCodeCertification::Class,
+            },
+        ],
+    )]
+    #[case::representation(
+        DefaultCelExpression::Full(
+            DefaultCelBuilder::full_certification()
+                .with_request_headers(vec!["accept-encoding", "Accept"])
+                .with_request_query_parameters(vec!["PAGE"])
+                .with_response_certification(
+                    DefaultResponseCertification::certified_response_headers(vec![
+                        "Cache-Control",
+                        "Content-Type",
+                    ]),
+                )
+                .build(),
+        ),
+        vec![CelExpressionDifference::Representation {
+            expected: full_cel_expr().to_string(),
+            found: r#"default_certification(ValidationArgs{certification:Certification{request_certification:RequestCertification{certified_request_headers:["accept-encoding","Accept"],certified_query_parameters:["PAGE"]},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:["Cache-Control","Content-Type"]}}}})"#.to_string(),
+        }],
+    )]
+    fn different_expressions(
+        #[case] found: DefaultCelExpression,
+        #[case] expected_differences: Vec<CelExpressionDifference>,
+    ) {
+        assert_eq!(full_cel_expr().diff(&found), expected_differences);
+    }
+
+    #[rstest]
+    fn multipart_form_fields_are_compared_case_sensitively() {
+        let expected = DefaultCelExpression::Full(
+            DefaultCelBuilder::full_certification()
+                .with_request_multipart_form_fields(vec!["title"])
+                .build(),
+        );
+        let found = DefaultCelExpression::Full(
+            DefaultCelBuilder::full_certification()
+                .with_request_multipart_form_fields(vec!["Title"])
+                .with_canonical_request_multipart_body()
+                .build(),
+        );
+
+        assert_eq!(
+            expected.diff(&found),
+            vec![CelExpressionDifference::CertifiedNames {
+                list: CelNameList::MultipartFormFields,
+                missing: vec!["title".to_string()],
+                unexpected: vec!["Title".to_string()],
+            }]
+        );
+    }
+
+    #[rstest]
+    fn diff_cel_expression_strings(no_request_response_inclusions_cel: String) {
+        let pretty_cel_expr =
+            crate::cel::pretty_print_cel_expr(&no_request_response_inclusions_cel);
+
+        assert_eq!(
+            diff_cel_expressions(
+                &no_request_response_inclusions_cel,
+                &no_request_response_inclusions_cel
+            )
+            .unwrap(),
+            vec![]
+        );
+        assert_eq!(
+            diff_cel_expressions(&no_request_response_inclusions_cel, &pretty_cel_expr).unwrap(),
+            vec![CelExpressionDifference::Representation {
+                expected: no_request_response_inclusions_cel.clone(),
+                found: pretty_cel_expr,
+            }]
+        );
+        assert!(diff_cel_expressions(
+            &no_request_response_inclusions_cel,
+            "default_certification("
+        )
+        .is_err());
+    }
+
+    #[rstest]
+    fn explains_differences() {
+        let difference = CelExpressionDifference::CertifiedNames {
+            list: CelNameList::RequestHeaders,
+            missing: vec!["Accept-Encoding".to_string()],
+            unexpected: vec!["Accept-Language".to_string()],
+        };
+
+        assert_eq!(
+            difference.to_string(),
+            r#"The certified request headers differ, missing ["Accept-Encoding"], unexpected ["Accept-Language"]"#
+        );
+    }
+}
@@ -1,4 +1,5 @@
-pub(crate) type CelParserResult<T = ()> = Result<T, CelParserError>;
+/// CEL expression parsing result type.
+pub type CelParserResult<T = ()> = Result<T, CelParserError>;
 
 /// CEL expression parsing error.
 #[derive(thiserror::Error, Debug)]
@@ -74,14 +75,14 @@ pub enum CelParserError {
     #[error(r#"The request_certification object must specify at least one of the no_request_certification or request_certification properties"#)]
     MissingRequestCertificationProperty,
 
-    /// The CEL parser encountered an extraneous property on the response certification's CEL object.
-    #[error(r#"The response_certification object must only specify one of the certified_response_headers or response_header_exclusions properties, not both"#)]
-    ExtraneousResponseCertificationProperty,
-
     /// The CEL parser expected to find a property on the response certification's CEL object, but none was found.
     #[error(r#"The response_certification object must specify at least one of the certified_response_headers or response_header_exclusions properties"#)]
     MissingResponseCertificationProperty,
 
+    /// The CEL parser encountered an extraneous property on the response certification's CEL object.
+    #[error(r#"The response_certification object must only specify one of the decoded_body_certification or fragmented_body_certification properties, not both"#)]
+    ExtraneousBodyCertificationProperty,
+
     /// The CEL parser encountered an extraneous property on the certification's CEL object.
     #[error(r#"The ValidationArgs parameter must only specify one of the no_certification or certification properties, not both"#)]
     ExtraneousValidationArgsProperty,
@@ -90,7 +91,102 @@ pub enum CelParserError {
     #[error(r#"The ValidationArgs parameter must specify at least one of the no_certification or certification properties"#)]
     MissingValidationArgsProperty,
 
-    /// The CEL parser encountered a syntax error while parsing the CEL expression. Using the "debug" feature flag can help to debug these syntax errors.
+    /// The CEL expression was parsed successfully, but it is a different kind of
+    /// [DefaultCelExpression](super::DefaultCelExpression) than the one that was requested, for
+    /// example a response-only expression was parsed into a
+    /// [DefaultFullCelExpression](super::DefaultFullCelExpression).
+    #[error(r#"Expected a {expected_type:?} CEL expression, found {found_type:?}"#)]
+    UnexpectedCelExpressionType {
+        /// The requested kind of CEL expression.
+        expected_type: String,
+        /// The kind of CEL expression that was parsed.
+        found_type: String,
+    },
+
+    /// The CEL parser encountered a syntax error while parsing the CEL expression.
     #[error(r#"Cel Syntax Expception: {0}"#)]
     CelSyntaxException(String),
 }
+
+/// CEL expression validation error, returned by
+/// [validate_cel_expression](super::validate_cel_expression()).
+///
+/// Every variant includes the byte offset in the CEL expression at which the error was found,
+/// see [offset](CelValidationError::offset).
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum CelValidationError {
+    /// The CEL expression is not syntactically valid, for example because a list or an object is
+    /// not closed.
+    #[error(r#"Syntax error at byte {offset}: {message}"#)]
+    SyntaxError {
+        /// A description of the syntax error.
+        message: String,
+        /// The byte offset of the syntax error.
+        offset: usize,
+    },
+
+    /// The CEL expression uses a function other than `default_certification`, such as the
+    /// function of a certification version that is not supported.
+    #[error(r#"Unsupported CEL function {function_name:?} at byte {offset}, only default_certification is currently supported"#)]
+    UnsupportedFunction {
+        /// The name of the unsupported function.
+        function_name: String,
+        /// The byte offset of the function name.
+        offset: usize,
+    },
+
+    /// An object has a property that is not defined for objects of its type.
+    #[error(r#"Unknown property {property_name:?} of object {object_name:?} at byte {offset}"#)]
+    UnknownProperty {
+        /// The name of the object with the unknown property.
+        object_name: String,
+        /// The name of the unknown property.
+        property_name: String,
+        /// The byte offset of the property name.
+        offset: usize,
+    },
+
+    /// A value has a different type, or an object has a different name, than expected.
+    #[error(r#"Expected {expected:?} at byte {offset}, found {found:?}"#)]
+    UnexpectedValue {
+        /// The expected type or object name.
+        expected: String,
+        /// The type or object name that was found.
+        found: String,
+        /// The byte offset of the value.
+        offset: usize,
+    },
+
+    /// A list of header, query parameter or form field names is not a list of strings.
+    #[error(r#"Malformed list {list_name:?} at byte {offset}, expected a list of strings"#)]
+    MalformedList {
+        /// The name of the property with the malformed list.
+        list_name: String,
+        /// The byte offset of the malformed list, or of its first element that is not a string.
+        offset: usize,
+    },
+
+    /// The CEL expression is well-formed, but does not describe a valid certification, for
+    /// example because it both certifies and excludes response headers.
+    #[error(r#"Invalid CEL expression at byte {offset}: {message}"#)]
+    InvalidCertification {
+        /// A description of the error, see [CelParserError].
+        message: String,
+        /// The byte offset of the object that the error applies to.
+        offset: usize,
+    },
+}
+
+impl CelValidationError {
+    /// Returns the byte offset in the CEL expression at which the error was found.
+    pub fn offset(&self) -> usize {
+        match self {
+            CelValidationError::SyntaxError { offset, .. }
+            | CelValidationError::UnsupportedFunction { offset, .. }
+            | CelValidationError::UnknownProperty { offset, .. }
+            | CelValidationError::UnexpectedValue { offset, .. }
+            | CelValidationError::MalformedList { offset, .. }
+            | CelValidationError::InvalidCertification { offset, .. } => *offset,
+        }
+    }
+}
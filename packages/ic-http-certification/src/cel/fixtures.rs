@@ -261,6 +261,159 @@ pub(super) fn empty_request_response_exclusions_cel() -> String {
     )
 }
 
-fn remove_whitespace(s: &str) -> String {
+#[fixture]
+pub(super) fn no_request_decoded_body_cel() -> String {
+    remove_whitespace(
+        r#"default_certification(
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: ["Cache-Control"]
+                        },
+                        decoded_body_certification: Empty {}
+                    }
+                }
+            }
+        )"#,
+    )
+}
+
+#[fixture]
+pub(super) fn include_request_decoded_body_cel() -> String {
+    remove_whitespace(
+        r#"default_certification(
+            ValidationArgs {
+                certification: Certification {
+                    request_certification: RequestCertification {
+                        certified_request_headers: ["Accept"],
+                        certified_query_parameters: []
+                    },
+                    response_certification: ResponseCertification {
+                        response_header_exclusions: ResponseHeaderList {
+                            headers: ["Date"]
+                        },
+                        decoded_body_certification: Empty {}
+                    }
+                }
+            }
+        )"#,
+    )
+}
+
+#[fixture]
+pub(super) fn include_request_multipart_form_fields_cel() -> String {
+    remove_whitespace(
+        r#"default_certification(
+            ValidationArgs {
+                certification: Certification {
+                    request_certification: RequestCertification {
+                        certified_request_headers: ["Content-Type"],
+                        certified_query_parameters: [],
+                        certified_multipart_form_fields: ["title", "tags"]
+                    },
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: []
+                        }
+                    }
+                }
+            }
+        )"#,
+    )
+}
+
+#[fixture]
+pub(super) fn include_request_canonical_multipart_body_cel() -> String {
+    remove_whitespace(
+        r#"default_certification(
+            ValidationArgs {
+                certification: Certification {
+                    request_certification: RequestCertification {
+                        certified_request_headers: ["Content-Type"],
+                        certified_query_parameters: [],
+                        canonical_multipart_body_certification: Empty {}
+                    },
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: []
+                        }
+                    }
+                }
+            }
+        )"#,
+    )
+}
+
+#[fixture]
+pub(super) fn no_request_fragmented_body_cel() -> String {
+    remove_whitespace(
+        r#"default_certification(
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: ["IC-CertificateFragments"]
+                        },
+                        fragmented_body_certification: Empty {}
+                    }
+                }
+            }
+        )"#,
+    )
+}
+
+#[fixture]
+pub(super) fn include_request_response_header_inclusions_with_exclusions_cel() -> String {
+    remove_whitespace(
+        r#"default_certification(
+            ValidationArgs {
+                certification: Certification {
+                    request_certification: RequestCertification {
+                        certified_request_headers: ["Accept"],
+                        certified_query_parameters: []
+                    },
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: [
+                                "glob:X-App-*",
+                                "Content-Type"
+                            ]
+                        },
+                        response_header_exclusions: ResponseHeaderList {
+                            headers: ["X-App-Request-Id"]
+                        }
+                    }
+                }
+            }
+        )"#,
+    )
+}
+
+#[fixture]
+pub(super) fn include_request_status_code_class_cel() -> String {
+    remove_whitespace(
+        r#"default_certification(
+            ValidationArgs {
+                certification: Certification {
+                    request_certification: RequestCertification {
+                        certified_request_headers: [],
+                        certified_query_parameters: []
+                    },
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: []
+                        },
+                        status_code_class_certification: Empty {}
+                    }
+                }
+            }
+        )"#,
+    )
+}
+
+pub(super) fn remove_whitespace(s: &str) -> String {
     s.chars().filter(|c| !c.is_whitespace()).collect()
 }
@@ -1,5 +1,6 @@
 //! The CEL module contains functions and builders for creating CEL expression
-//! definitions and converting them into their `String` representation.
+//! definitions, converting them into their `String` representation and parsing
+//! them back from their `String` representation.
 
 mod cel_builder;
 pub use cel_builder::*;
@@ -7,8 +8,29 @@ pub use cel_builder::*;
 mod cel_types;
 pub use cel_types::*;
 
+mod cel_version;
+pub use cel_version::*;
+
 mod create_cel_expr;
 pub use create_cel_expr::*;
 
+mod canonicalize_cel_expr;
+pub use canonicalize_cel_expr::*;
+
+mod diff_cel_expr;
+pub use diff_cel_expr::*;
+
+mod parse_cel_expr;
+pub use parse_cel_expr::*;
+
+mod validate_cel_expr;
+pub use validate_cel_expr::*;
+
+mod error;
+pub use error::*;
+
+mod ast_mapping;
+mod parser;
+
 #[cfg(test)]
 mod fixtures;
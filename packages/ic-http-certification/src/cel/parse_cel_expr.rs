@@ -0,0 +1,595 @@
+use super::{
+    ast_mapping::map_cel_ast, parser::parse_cel_value, CelExpression, CelParserError,
+    CelParserResult, DefaultCelExpression, DefaultFullCelExpression,
+    DefaultResponseOnlyCelExpression,
+};
+
+/// Parses a CEL expression from its [String] representation, such as the value of the
+/// `IC-CertificateExpression` header, into a [CelExpression] struct.
+///
+/// This is the inverse of [create_cel_expr](super::create_cel_expr()). Both minified and
+/// formatted CEL expressions are accepted.
+pub fn parse_cel_expression(cel_expr: &str) -> CelParserResult<CelExpression> {
+    let cel_ast = parse_cel_value(cel_expr)?;
+
+    map_cel_ast(&cel_ast)
+}
+
+/// Parses a CEL expression from its [String] representation into a [DefaultCelExpression].
+///
+/// This is the same as [parse_cel_expression], for callers that only work with the
+/// `default_certification` function, which is currently the only supported function.
+///
+/// ```
+/// use ic_http_certification::{
+///     cel::{parse_default_cel_expression, DefaultCelExpression},
+///     DefaultCelBuilder, DefaultResponseCertification,
+/// };
+///
+/// let cel_expr = DefaultCelBuilder::response_only_certification()
+///     .with_response_certification(DefaultResponseCertification::certified_response_headers(
+///         vec!["Cache-Control"],
+///     ))
+///     .build();
+/// let cel_expr_str = cel_expr.to_string();
+///
+/// assert_eq!(
+///     parse_default_cel_expression(&cel_expr_str).unwrap(),
+///     DefaultCelExpression::ResponseOnly(cel_expr),
+/// );
+/// ```
+pub fn parse_default_cel_expression(cel_expr: &str) -> CelParserResult<DefaultCelExpression<'_>> {
+    match parse_cel_expression(cel_expr)? {
+        CelExpression::Default(default_cel_expr) => Ok(default_cel_expr),
+    }
+}
+
+impl<'a> TryFrom<&'a str> for CelExpression<'a> {
+    type Error = CelParserError;
+
+    /// Parses a CEL expression from its [String] representation.
+    ///
+    /// Alias of [parse_cel_expression].
+    fn try_from(cel_expr: &'a str) -> CelParserResult<Self> {
+        parse_cel_expression(cel_expr)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for DefaultCelExpression<'a> {
+    type Error = CelParserError;
+
+    /// Parses a CEL expression from its [String] representation.
+    ///
+    /// Alias of [parse_default_cel_expression].
+    fn try_from(cel_expr: &'a str) -> CelParserResult<Self> {
+        parse_default_cel_expression(cel_expr)
+    }
+}
+
+impl<'a> TryFrom<&'a str> for DefaultResponseOnlyCelExpression<'a> {
+    type Error = CelParserError;
+
+    /// Parses a response-only CEL expression from its [String] representation.
+    ///
+    /// Returns [CelParserError::UnexpectedCelExpressionType] if the expression is valid, but does
+    /// not certify only the response.
+    fn try_from(cel_expr: &'a str) -> CelParserResult<Self> {
+        match parse_default_cel_expression(cel_expr)? {
+            DefaultCelExpression::ResponseOnly(response_only_cel_expr) => {
+                Ok(response_only_cel_expr)
+            }
+            default_cel_expr => Err(unexpected_cel_expression_type(
+                "ResponseOnly",
+                &default_cel_expr,
+            )),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for DefaultFullCelExpression<'a> {
+    type Error = CelParserError;
+
+    /// Parses a full CEL expression, that certifies both the request and the response, from its
+    /// [String] representation.
+    ///
+    /// Returns [CelParserError::UnexpectedCelExpressionType] if the expression is valid, but does
+    /// not certify the request.
+    fn try_from(cel_expr: &'a str) -> CelParserResult<Self> {
+        match parse_default_cel_expression(cel_expr)? {
+            DefaultCelExpression::Full(full_cel_expr) => Ok(full_cel_expr),
+            default_cel_expr => Err(unexpected_cel_expression_type("Full", &default_cel_expr)),
+        }
+    }
+}
+
+fn unexpected_cel_expression_type(
+    expected_type: &str,
+    found_cel_expr: &DefaultCelExpression,
+) -> CelParserError {
+    let found_type = match found_cel_expr {
+        DefaultCelExpression::Full(_) => "Full",
+        DefaultCelExpression::ResponseOnly(_) => "ResponseOnly",
+        DefaultCelExpression::Skip => "Skip",
+    };
+
+    CelParserError::UnexpectedCelExpressionType {
+        expected_type: expected_type.to_string(),
+        found_type: found_type.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cel::{
+        fixtures::remove_whitespace, DefaultBodyCertification, DefaultCelBuilder,
+        DefaultRequestCertification, DefaultResponseCertification, DefaultStatusCodeCertification,
+    };
+    use std::borrow::Cow;
+
+    #[test]
+    fn parses_no_certification_expression() {
+        let cel_expression = r#"
+        default_certification (
+            ValidationArgs {
+                no_certification: Empty { }
+            }
+        )
+    "#
+        .to_string();
+        let expected_result = CelExpression::Default(DefaultCelExpression::Skip);
+
+        let result = parse_cel_expression(&cel_expression).unwrap();
+
+        let minified_cel_expression = remove_whitespace(&cel_expression);
+        let minified_result = parse_cel_expression(&minified_cel_expression).unwrap();
+
+        assert_eq!(&result, &expected_result);
+        assert_eq!(&minified_result, &expected_result);
+    }
+
+    #[test]
+    fn parses_no_request_certification_expression() {
+        let cel_expression = r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        response_header_exclusions: ResponseHeaderList {
+                            headers: ["Server","Date","X-Cache-Status"]
+                        }
+                    }
+                }
+            }
+        )
+    "#
+        .to_string();
+        let expected_result = CelExpression::Default(DefaultCelExpression::ResponseOnly(
+            DefaultResponseOnlyCelExpression {
+                response: DefaultResponseCertification::response_header_exclusions(vec![
+                    "Server",
+                    "Date",
+                    "X-Cache-Status",
+                ]),
+                body: DefaultBodyCertification::AsServed,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
+            },
+        ));
+
+        let result = parse_cel_expression(&cel_expression).unwrap();
+
+        let minified_cel_expression = remove_whitespace(&cel_expression);
+        let minified_result = parse_cel_expression(&minified_cel_expression).unwrap();
+
+        assert_eq!(&result, &expected_result);
+        assert_eq!(&minified_result, &expected_result);
+    }
+
+    #[test]
+    fn parses_full_certification_expression() {
+        let cel_expression = r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    request_certification: RequestCertification {
+                        certified_request_headers: ["host"],
+                        certified_query_parameters: ["filter"]
+                    },
+                    response_certification: ResponseCertification {
+                        response_header_exclusions: ResponseHeaderList {
+                            headers: ["Content-Type","X-Frame-Options","Content-Security-Policy","Strict-Transport-Security","Referrer-Policy","Permissions-Policy"]
+                        }
+                    }
+                }
+            }
+        )
+    "#.to_string();
+        let expected_result =
+            CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
+                request: DefaultRequestCertification::new(vec!["host"], vec!["filter"]),
+                response: DefaultResponseCertification::response_header_exclusions(vec![
+                    "Content-Type",
+                    "X-Frame-Options",
+                    "Content-Security-Policy",
+                    "Strict-Transport-Security",
+                    "Referrer-Policy",
+                    "Permissions-Policy",
+                ]),
+                body: DefaultBodyCertification::AsServed,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
+            }));
+
+        let result = parse_cel_expression(&cel_expression).unwrap();
+
+        let minified_cel_expression = remove_whitespace(&cel_expression);
+        let minified_result = parse_cel_expression(&minified_cel_expression).unwrap();
+
+        assert_eq!(&result, &expected_result);
+        assert_eq!(&minified_result, &expected_result);
+    }
+
+    #[test]
+    fn parses_decoded_body_certification_expression() {
+        let cel_expression = r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: ["Cache-Control"]
+                        },
+                        decoded_body_certification: Empty {}
+                    }
+                }
+            }
+        )
+    "#
+        .to_string();
+        let expected_result = CelExpression::Default(DefaultCelExpression::ResponseOnly(
+            DefaultResponseOnlyCelExpression {
+                response: DefaultResponseCertification::certified_response_headers(vec![
+                    "Cache-Control",
+                ]),
+                body: DefaultBodyCertification::Decoded,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
+            },
+        ));
+
+        let result = parse_cel_expression(&cel_expression).unwrap();
+
+        let minified_cel_expression = remove_whitespace(&cel_expression);
+        let minified_result = parse_cel_expression(&minified_cel_expression).unwrap();
+
+        assert_eq!(&result, &expected_result);
+        assert_eq!(&minified_result, &expected_result);
+        assert_eq!(result.to_string(), minified_cel_expression);
+    }
+
+    #[test]
+    fn parses_fragmented_body_certification_expression() {
+        let cel_expression = r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        response_header_exclusions: ResponseHeaderList {
+                            headers: []
+                        },
+                        fragmented_body_certification: Empty {}
+                    }
+                }
+            }
+        )
+    "#
+        .to_string();
+        let expected_result = CelExpression::Default(DefaultCelExpression::ResponseOnly(
+            DefaultResponseOnlyCelExpression {
+                response: DefaultResponseCertification::response_header_exclusions(vec![]),
+                body: DefaultBodyCertification::Fragmented,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
+            },
+        ));
+
+        let result = parse_cel_expression(&cel_expression).unwrap();
+
+        assert_eq!(&result, &expected_result);
+        assert_eq!(result.to_string(), remove_whitespace(&cel_expression));
+    }
+
+    #[test]
+    fn parses_status_code_class_certification_expression() {
+        let cel_expression = r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: ["Cache-Control"]
+                        },
+                        decoded_body_certification: Empty {},
+                        status_code_class_certification: Empty {}
+                    }
+                }
+            }
+        )
+    "#
+        .to_string();
+        let expected_result = CelExpression::Default(DefaultCelExpression::ResponseOnly(
+            DefaultResponseOnlyCelExpression {
+                response: DefaultResponseCertification::certified_response_headers(vec![
+                    "Cache-Control",
+                ]),
+                body: DefaultBodyCertification::Decoded,
+                status: DefaultStatusCodeCertification::Class,
+                allowed_methods: Cow::Borrowed(&[]),
+            },
+        ));
+
+        let result = parse_cel_expression(&cel_expression).unwrap();
+
+        assert_eq!(&result, &expected_result);
+        assert_eq!(result.to_string(), remove_whitespace(&cel_expression));
+    }
+
+    #[test]
+    fn parses_allowed_methods_certification_expression() {
+        let cel_expression = r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    request_certification: RequestCertification {
+                        certified_request_headers: ["Accept"],
+                        certified_query_parameters: []
+                    },
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: ["Content-Type"]
+                        }
+                    },
+                    allowed_request_methods: ["GET", "HEAD"]
+                }
+            }
+        )
+    "#
+        .to_string();
+        let expected_result =
+            CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
+                request: DefaultRequestCertification::new(vec!["Accept"], vec![]),
+                response: DefaultResponseCertification::certified_response_headers(vec![
+                    "Content-Type",
+                ]),
+                body: DefaultBodyCertification::AsServed,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Owned(vec!["GET", "HEAD"]),
+            }));
+
+        let result = parse_cel_expression(&cel_expression).unwrap();
+
+        assert_eq!(&result, &expected_result);
+        assert_eq!(result.to_string(), remove_whitespace(&cel_expression));
+    }
+
+    #[test]
+    fn parses_multipart_form_fields_certification_expression() {
+        let cel_expression = r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    request_certification: RequestCertification {
+                        certified_request_headers: ["Content-Type"],
+                        certified_query_parameters: [],
+                        certified_multipart_form_fields: ["title"]
+                    },
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: []
+                        }
+                    }
+                }
+            }
+        )
+    "#
+        .to_string();
+        let expected_result =
+            CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
+                request: DefaultRequestCertification::new(vec!["Content-Type"], vec![])
+                    .with_multipart_form_fields(vec!["title"]),
+                response: DefaultResponseCertification::certified_response_headers(vec![]),
+                body: DefaultBodyCertification::AsServed,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
+            }));
+
+        let result = parse_cel_expression(&cel_expression).unwrap();
+
+        let minified_cel_expression = remove_whitespace(&cel_expression);
+
+        assert_eq!(&result, &expected_result);
+        assert_eq!(result.to_string(), minified_cel_expression);
+    }
+
+    #[test]
+    fn parses_header_name_patterns() {
+        let cel_expr = DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["glob:X-Client-*"])
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["glob:X-App-*", "glob:*-Id"],
+            ))
+            .build();
+        let cel_expr_str = cel_expr.to_string();
+
+        assert_eq!(
+            DefaultFullCelExpression::try_from(cel_expr_str.as_str()).unwrap(),
+            cel_expr
+        );
+    }
+
+    #[test]
+    fn parses_canonical_multipart_body_certification_expression() {
+        let cel_expression = r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    request_certification: RequestCertification {
+                        certified_request_headers: [],
+                        certified_query_parameters: [],
+                        canonical_multipart_body_certification: Empty {}
+                    },
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: []
+                        }
+                    }
+                }
+            }
+        )
+    "#
+        .to_string();
+        let expected_result =
+            CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
+                request: DefaultRequestCertification::new(vec![], vec![])
+                    .with_canonical_multipart_body(true),
+                response: DefaultResponseCertification::certified_response_headers(vec![]),
+                body: DefaultBodyCertification::AsServed,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
+            }));
+
+        let result = parse_cel_expression(&cel_expression).unwrap();
+
+        assert_eq!(&result, &expected_result);
+        assert_eq!(result.to_string(), remove_whitespace(&cel_expression));
+    }
+
+    #[test]
+    fn parses_combined_response_header_certification_expression() {
+        let cel_expression = r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        certified_response_headers: ResponseHeaderList {
+                            headers: ["glob:X-App-*","Content-Type"]
+                        },
+                        response_header_exclusions: ResponseHeaderList {
+                            headers: ["X-App-Request-Id"]
+                        }
+                    }
+                }
+            }
+        )
+    "#;
+        let expected_result = CelExpression::Default(DefaultCelExpression::ResponseOnly(
+            DefaultResponseOnlyCelExpression {
+                response: DefaultResponseCertification::certified_response_headers_with_exclusions(
+                    vec!["glob:X-App-*", "Content-Type"],
+                    vec!["X-App-Request-Id"],
+                ),
+                body: DefaultBodyCertification::AsServed,
+                status: DefaultStatusCodeCertification::Exact,
+                allowed_methods: Cow::Borrowed(&[]),
+            },
+        ));
+
+        let result = parse_cel_expression(cel_expression).unwrap();
+
+        assert_eq!(&result, &expected_result);
+        assert_eq!(result.to_string(), remove_whitespace(cel_expression));
+    }
+
+    #[test]
+    fn rejects_decoded_and_fragmented_body_certification_expression() {
+        let cel_expression = r#"
+        default_certification (
+            ValidationArgs {
+                certification: Certification {
+                    no_request_certification: Empty {},
+                    response_certification: ResponseCertification {
+                        response_header_exclusions: ResponseHeaderList {
+                            headers: []
+                        },
+                        decoded_body_certification: Empty {},
+                        fragmented_body_certification: Empty {}
+                    }
+                }
+            }
+        )
+    "#;
+
+        assert!(matches!(
+            parse_cel_expression(cel_expression),
+            Err(CelParserError::ExtraneousBodyCertificationProperty)
+        ));
+    }
+
+    #[test]
+    fn parses_typed_cel_expressions() {
+        let response_only_cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Cache-Control", "ETag"],
+            ))
+            .with_decoded_body_certification()
+            .build();
+        let full_cel_expr = DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept"])
+            .with_request_query_parameters(vec!["page"])
+            .with_response_certification(DefaultResponseCertification::response_header_exclusions(
+                vec!["Date"],
+            ))
+            .build();
+        let response_only_cel_expr_str = response_only_cel_expr.to_string();
+        let full_cel_expr_str = full_cel_expr.to_string();
+
+        assert_eq!(
+            DefaultResponseOnlyCelExpression::try_from(response_only_cel_expr_str.as_str())
+                .unwrap(),
+            response_only_cel_expr
+        );
+        assert_eq!(
+            DefaultFullCelExpression::try_from(full_cel_expr_str.as_str()).unwrap(),
+            full_cel_expr
+        );
+        assert_eq!(
+            DefaultCelExpression::try_from(full_cel_expr_str.as_str()).unwrap(),
+            DefaultCelExpression::Full(full_cel_expr.clone())
+        );
+        assert_eq!(
+            CelExpression::try_from(response_only_cel_expr_str.as_str()).unwrap(),
+            CelExpression::Default(DefaultCelExpression::ResponseOnly(response_only_cel_expr))
+        );
+    }
+
+    #[test]
+    fn rejects_typed_cel_expressions_of_another_kind() {
+        let skip_cel_expr_str = DefaultCelBuilder::skip_certification().to_string();
+        let full_cel_expr_str = DefaultCelBuilder::full_certification().build().to_string();
+
+        assert!(matches!(
+            DefaultResponseOnlyCelExpression::try_from(full_cel_expr_str.as_str()),
+            Err(CelParserError::UnexpectedCelExpressionType {
+                expected_type,
+                found_type,
+            }) if expected_type == "ResponseOnly" && found_type == "Full"
+        ));
+        assert!(matches!(
+            DefaultFullCelExpression::try_from(skip_cel_expr_str.as_str()),
+            Err(CelParserError::UnexpectedCelExpressionType {
+                expected_type,
+                found_type,
+            }) if expected_type == "Full" && found_type == "Skip"
+        ));
+        assert!(matches!(
+            DefaultFullCelExpression::try_from("default_certification()"),
+            Err(CelParserError::MissingFunctionParameter { .. })
+        ));
+    }
+}
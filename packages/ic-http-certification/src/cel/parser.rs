@@ -1,10 +1,10 @@
-use crate::cel::error::{CelParserError, CelParserResult};
+use super::error::{CelParserError, CelParserResult};
 use nom::branch::alt;
 use nom::bytes::complete::{escaped, take_while};
 use nom::character::complete::{char, multispace0, one_of};
 use nom::character::is_alphanumeric;
 use nom::combinator::{cut, map};
-use nom::error::{context, ContextError, ParseError};
+use nom::error::{context, ContextError, ParseError, VerboseError, VerboseErrorKind};
 use nom::multi::separated_list0;
 use nom::sequence::{preceded, separated_pair, terminated, tuple};
 use nom::{IResult, Parser};
@@ -12,7 +12,7 @@ use std::collections::HashMap;
 use std::fmt;
 
 #[derive(Debug, Eq, PartialEq)]
-pub(crate) enum CelValue<'a> {
+pub(super) enum CelValue<'a> {
     String(&'a str),
     Array(Vec<CelValue<'a>>),
     Object(&'a str, HashMap<&'a str, CelValue<'a>>),
@@ -65,7 +65,7 @@ fn ident<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
 fn parse_str<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     i: &'a str,
 ) -> IResult<&'a str, &'a str, E> {
-    let acceptable_special_chars = "-";
+    let acceptable_special_chars = "-*:";
 
     context(
         "parse_str",
@@ -161,21 +161,59 @@ fn cel_value<'a, E: ParseError<&'a str> + ContextError<&'a str>>(
     )(i)
 }
 
-pub(crate) fn parse_cel_expression(i: &str) -> CelParserResult<CelValue> {
-    #[cfg(feature = "debug")]
-    let result = cel_value::<nom::error::VerboseError<&str>>(i);
-
-    #[cfg(not(feature = "debug"))]
-    let result = cel_value::<nom::error::Error<&str>>(i);
-
-    match result {
-        #[cfg(feature = "debug")]
-        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
-            let stacktrace = nom::error::convert_error(i, e);
-
-            Err(CelParserError::CelSyntaxException(stacktrace))
-        }
+pub(super) fn parse_cel_value(i: &str) -> CelParserResult<CelValue> {
+    match cel_value::<nom::error::Error<&str>>(i) {
         Err(e) => Err(CelParserError::CelSyntaxException(e.to_string())),
         Ok((_remaining, result)) => Ok(result),
     }
 }
+
+/// Parses a CEL value that must span the whole input, apart from surrounding whitespace.
+///
+/// Unlike [parse_cel_value], a syntax error is reported with the byte offset at which it was
+/// found and a description of what was expected there.
+pub(super) fn parse_complete_cel_value(i: &str) -> Result<CelValue<'_>, (usize, String)> {
+    let offset = |remaining: &str| i.len() - remaining.len();
+
+    match cel_value::<VerboseError<&str>>(i) {
+        Ok((remaining, result)) => match remaining.trim_start() {
+            "" => Ok(result),
+            trailing_input => Err((
+                offset(trailing_input),
+                format!("unexpected {}", describe_input(trailing_input)),
+            )),
+        },
+        Err(nom::Err::Incomplete(_)) => Err((i.len(), "unexpected end of input".into())),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            let Some((remaining, kind)) = e.errors.first() else {
+                return Err((0, "invalid CEL expression".into()));
+            };
+
+            let expected = match kind {
+                VerboseErrorKind::Char(c) => format!("expected '{c}'"),
+                VerboseErrorKind::Context(context) => format!("invalid {context}"),
+                VerboseErrorKind::Nom(kind) => format!("invalid {}", kind.description()),
+            };
+            let context = e.errors.iter().find_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(
+                    context @ ("string" | "array" | "key_value" | "object" | "function"),
+                ) => Some(*context),
+                _ => None,
+            });
+
+            let mut message = format!("{expected}, found {}", describe_input(remaining));
+            if let Some(context) = context {
+                message.push_str(&format!(" in {context}"));
+            }
+
+            Err((offset(remaining), message))
+        }
+    }
+}
+
+fn describe_input(i: &str) -> String {
+    match i.chars().next() {
+        Some(c) => format!("'{c}'"),
+        None => "end of input".into(),
+    }
+}
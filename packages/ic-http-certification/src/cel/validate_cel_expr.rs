@@ -0,0 +1,445 @@
+use super::{
+    ast_mapping::map_cel_ast,
+    parser::{parse_complete_cel_value, CelValue},
+    CelParserError, CelValidationError, CelVersion,
+};
+use std::collections::HashMap;
+
+const DEFAULT_CERTIFICATION_FUNCTION_NAME: &str = CelVersion::V2.function_name();
+
+#[derive(Debug, Clone, Copy)]
+enum PropertyType {
+    Object(&'static str),
+    StringList,
+}
+
+/// The properties of each object that can appear in a `default_certification` CEL expression.
+const OBJECT_PROPERTIES: &[(&str, &[(&str, PropertyType)])] = &[
+    (
+        "ValidationArgs",
+        &[
+            ("no_certification", PropertyType::Object("Empty")),
+            ("certification", PropertyType::Object("Certification")),
+        ],
+    ),
+    (
+        "Certification",
+        &[
+            ("no_request_certification", PropertyType::Object("Empty")),
+            (
+                "request_certification",
+                PropertyType::Object("RequestCertification"),
+            ),
+            (
+                "response_certification",
+                PropertyType::Object("ResponseCertification"),
+            ),
+            ("allowed_request_methods", PropertyType::StringList),
+        ],
+    ),
+    (
+        "RequestCertification",
+        &[
+            ("certified_request_headers", PropertyType::StringList),
+            ("certified_query_parameters", PropertyType::StringList),
+            ("certified_multipart_form_fields", PropertyType::StringList),
+            (
+                "canonical_multipart_body_certification",
+                PropertyType::Object("Empty"),
+            ),
+        ],
+    ),
+    (
+        "ResponseCertification",
+        &[
+            (
+                "certified_response_headers",
+                PropertyType::Object("ResponseHeaderList"),
+            ),
+            (
+                "response_header_exclusions",
+                PropertyType::Object("ResponseHeaderList"),
+            ),
+            ("decoded_body_certification", PropertyType::Object("Empty")),
+            (
+                "fragmented_body_certification",
+                PropertyType::Object("Empty"),
+            ),
+            (
+                "status_code_class_certification",
+                PropertyType::Object("Empty"),
+            ),
+        ],
+    ),
+    (
+        "ResponseHeaderList",
+        &[("headers", PropertyType::StringList)],
+    ),
+    ("Empty", &[]),
+];
+
+/// Validates a CEL expression, such as the value of the `IC-CertificateExpression` header, and
+/// reports the first error that it contains together with its byte offset.
+///
+/// This is stricter than [parse_cel_expression](super::parse_cel_expression()): in addition to
+/// the errors that prevent an expression from being parsed, it reports unknown properties and
+/// trailing input, which the parser ignores but which usually indicate a typo. An expression
+/// that passes validation can always be parsed.
+///
+/// ```
+/// use ic_http_certification::cel::{validate_cel_expression, CelValidationError};
+///
+/// let cel_expr = r#"default_certification(ValidationArgs{no_certfication:Empty{}})"#;
+///
+/// assert_eq!(
+///     validate_cel_expression(cel_expr),
+///     Err(CelValidationError::UnknownProperty {
+///         object_name: "ValidationArgs".to_string(),
+///         property_name: "no_certfication".to_string(),
+///         offset: 37,
+///     })
+/// );
+/// ```
+pub fn validate_cel_expression(cel_expr: &str) -> Result<(), CelValidationError> {
+    let cel_value = parse_complete_cel_value(cel_expr)
+        .map_err(|(offset, message)| CelValidationError::SyntaxError { message, offset })?;
+
+    let mut validator = CelValidator {
+        cel_expr,
+        object_offsets: HashMap::new(),
+    };
+    validator.validate_root(&cel_value)?;
+
+    map_cel_ast(&cel_value)
+        .map(|_| ())
+        .map_err(|err| validator.invalid_certification(err))
+}
+
+struct CelValidator<'a> {
+    cel_expr: &'a str,
+    object_offsets: HashMap<&'a str, usize>,
+}
+
+impl<'a> CelValidator<'a> {
+    fn validate_root(&mut self, cel_value: &CelValue<'a>) -> Result<(), CelValidationError> {
+        let CelValue::Function(function_name, parameters) = cel_value else {
+            return Err(CelValidationError::UnexpectedValue {
+                expected: DEFAULT_CERTIFICATION_FUNCTION_NAME.into(),
+                found: describe_value(cel_value),
+                offset: self.value_offset(cel_value).unwrap_or(0),
+            });
+        };
+
+        let function_offset = self.offset(function_name);
+        if *function_name != DEFAULT_CERTIFICATION_FUNCTION_NAME {
+            return Err(CelValidationError::UnsupportedFunction {
+                function_name: (*function_name).into(),
+                offset: function_offset,
+            });
+        }
+        self.object_offsets.insert(function_name, function_offset);
+
+        if let Some(extra_parameter) = parameters.get(1) {
+            return Err(CelValidationError::UnexpectedValue {
+                expected: "')'".into(),
+                found: describe_value(extra_parameter),
+                offset: self
+                    .value_offset(extra_parameter)
+                    .unwrap_or(function_offset),
+            });
+        }
+
+        match parameters.first() {
+            Some(validation_args) => self.validate_value(
+                validation_args,
+                PropertyType::Object("ValidationArgs"),
+                "ValidationArgs",
+                function_offset,
+            ),
+            None => Ok(()),
+        }
+    }
+
+    fn validate_value(
+        &mut self,
+        cel_value: &CelValue<'a>,
+        expected_type: PropertyType,
+        property_name: &str,
+        property_offset: usize,
+    ) -> Result<(), CelValidationError> {
+        match (expected_type, cel_value) {
+            (PropertyType::Object(expected_name), CelValue::Object(object_name, properties)) => {
+                let object_offset = self.offset(object_name);
+                if *object_name != expected_name {
+                    return Err(CelValidationError::UnexpectedValue {
+                        expected: expected_name.into(),
+                        found: (*object_name).into(),
+                        offset: object_offset,
+                    });
+                }
+                self.object_offsets
+                    .entry(object_name)
+                    .or_insert(object_offset);
+
+                self.validate_properties(object_name, properties)
+            }
+            (PropertyType::Object(expected_name), _) => Err(CelValidationError::UnexpectedValue {
+                expected: expected_name.into(),
+                found: describe_value(cel_value),
+                offset: self.value_offset(cel_value).unwrap_or(property_offset),
+            }),
+            (PropertyType::StringList, CelValue::Array(elements)) => {
+                match elements
+                    .iter()
+                    .find(|element| !matches!(element, CelValue::String(_)))
+                {
+                    Some(element) => Err(CelValidationError::MalformedList {
+                        list_name: property_name.into(),
+                        offset: self.value_offset(element).unwrap_or(property_offset),
+                    }),
+                    None => Ok(()),
+                }
+            }
+            (PropertyType::StringList, _) => Err(CelValidationError::MalformedList {
+                list_name: property_name.into(),
+                offset: self.value_offset(cel_value).unwrap_or(property_offset),
+            }),
+        }
+    }
+
+    fn validate_properties(
+        &mut self,
+        object_name: &str,
+        properties: &HashMap<&'a str, CelValue<'a>>,
+    ) -> Result<(), CelValidationError> {
+        let known_properties = OBJECT_PROPERTIES
+            .iter()
+            .find(|(name, _)| *name == object_name)
+            .map(|(_, known_properties)| *known_properties)
+            .unwrap_or_default();
+
+        // validate properties in the order in which they appear, so that the first error in the
+        // expression is reported
+        let mut properties: Vec<_> = properties.iter().collect();
+        properties.sort_by_key(|(property_name, _)| self.offset(property_name));
+
+        for (property_name, property_value) in properties {
+            let property_offset = self.offset(property_name);
+            let Some((_, property_type)) = known_properties
+                .iter()
+                .find(|(known_property_name, _)| known_property_name == property_name)
+            else {
+                return Err(CelValidationError::UnknownProperty {
+                    object_name: object_name.into(),
+                    property_name: (*property_name).into(),
+                    offset: property_offset,
+                });
+            };
+
+            self.validate_value(
+                property_value,
+                *property_type,
+                property_name,
+                property_offset,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn invalid_certification(&self, err: CelParserError) -> CelValidationError {
+        let object_name = match &err {
+            CelParserError::MissingObjectProperty { object_name, .. } => object_name.as_str(),
+            CelParserError::ExtraneousValidationArgsProperty
+            | CelParserError::MissingValidationArgsProperty => "ValidationArgs",
+            CelParserError::ExtraneousRequestCertificationProperty
+            | CelParserError::MissingRequestCertificationProperty => "Certification",
+            CelParserError::MissingResponseCertificationProperty
+            | CelParserError::ExtraneousBodyCertificationProperty => "ResponseCertification",
+            _ => DEFAULT_CERTIFICATION_FUNCTION_NAME,
+        };
+
+        CelValidationError::InvalidCertification {
+            message: err.to_string(),
+            offset: self.object_offsets.get(object_name).copied().unwrap_or(0),
+        }
+    }
+
+    /// Returns the byte offset of a slice of the CEL expression.
+    fn offset(&self, slice: &str) -> usize {
+        slice.as_ptr() as usize - self.cel_expr.as_ptr() as usize
+    }
+
+    fn value_offset(&self, cel_value: &CelValue) -> Option<usize> {
+        match cel_value {
+            // the offset of the opening quote
+            CelValue::String(value) => Some(self.offset(value) - 1),
+            CelValue::Object(name, _) | CelValue::Function(name, _) => Some(self.offset(name)),
+            CelValue::Array(_) => None,
+        }
+    }
+}
+
+fn describe_value(cel_value: &CelValue) -> String {
+    match cel_value {
+        CelValue::String(_) => "String".into(),
+        CelValue::Array(_) => "Array".into(),
+        CelValue::Object(name, _) | CelValue::Function(name, _) => (*name).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DefaultCelBuilder, DefaultResponseCertification};
+    use rstest::*;
+
+    #[rstest]
+    #[case::skip(DefaultCelBuilder::skip_certification().to_string())]
+    #[case::response_only(
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::response_header_exclusions(
+                vec!["Date"],
+            ))
+            .with_decoded_body_certification()
+            .with_status_code_class_certification()
+            .build()
+            .to_string()
+    )]
+    #[case::full(
+        DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept"])
+            .with_request_query_parameters(vec!["page"])
+            .with_request_multipart_form_fields(vec!["title"])
+            .with_fragmented_body_certification()
+            .build()
+            .to_string()
+    )]
+    #[case::canonical_multipart_body(
+        DefaultCelBuilder::full_certification()
+            .with_canonical_request_multipart_body()
+            .build()
+            .to_string()
+    )]
+    #[case::formatted(r#"
+        default_certification (
+            ValidationArgs {
+                no_certification: Empty { }
+            }
+        )
+    "#.to_string())]
+    fn validate_valid_cel_expression(#[case] cel_expr: String) {
+        assert_eq!(validate_cel_expression(&cel_expr), Ok(()));
+    }
+
+    #[rstest]
+    #[case::unclosed_list(
+        r#"default_certification(ValidationArgs{certification:Certification{no_request_certification:Empty{},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:["ETag","Date"}}}})"#,
+        211,
+        "expected ']', found '}' in array"
+    )]
+    #[case::trailing_comma(
+        r#"default_certification(ValidationArgs{certification:Certification{no_request_certification:Empty{},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:["ETag",]}}}})"#,
+        204,
+        "expected ']', found ',' in array"
+    )]
+    #[case::trailing_input(
+        r#"default_certification(ValidationArgs{no_certification:Empty{}}))"#,
+        63,
+        "unexpected ')'"
+    )]
+    fn validate_cel_expression_with_syntax_error(
+        #[case] cel_expr: &str,
+        #[case] expected_offset: usize,
+        #[case] expected_message: &str,
+    ) {
+        assert_eq!(
+            validate_cel_expression(cel_expr),
+            Err(CelValidationError::SyntaxError {
+                message: expected_message.into(),
+                offset: expected_offset,
+            })
+        );
+    }
+
+    #[rstest]
+    fn validate_cel_expression_with_unsupported_function() {
+        assert_eq!(
+            validate_cel_expression("  default_certification_v3(ValidationArgs{})"),
+            Err(CelValidationError::UnsupportedFunction {
+                function_name: "default_certification_v3".into(),
+                offset: 2,
+            })
+        );
+    }
+
+    #[rstest]
+    fn validate_cel_expression_with_unknown_property() {
+        let cel_expr = r#"default_certification(ValidationArgs{certification:Certification{no_request_certification:Empty{},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:[]},certified_response_cookies:Empty{}}}})"#;
+
+        assert_eq!(
+            validate_cel_expression(cel_expr),
+            Err(CelValidationError::UnknownProperty {
+                object_name: "ResponseCertification".into(),
+                property_name: "certified_response_cookies".into(),
+                offset: cel_expr.find("certified_response_cookies").unwrap(),
+            })
+        );
+    }
+
+    #[rstest]
+    #[case::element_is_not_a_string(
+        r#"default_certification(ValidationArgs{certification:Certification{request_certification:RequestCertification{certified_request_headers:["Accept",Empty{}],certified_query_parameters:[]},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:[]}}}})"#,
+        "certified_request_headers",
+        "Empty{}"
+    )]
+    #[case::list_is_not_a_list(
+        r#"default_certification(ValidationArgs{certification:Certification{no_request_certification:Empty{},response_certification:ResponseCertification{response_header_exclusions:ResponseHeaderList{headers:"Date"}}}})"#,
+        "headers",
+        r#""Date""#
+    )]
+    fn validate_cel_expression_with_malformed_list(
+        #[case] cel_expr: &str,
+        #[case] expected_list_name: &str,
+        #[case] malformed_value: &str,
+    ) {
+        assert_eq!(
+            validate_cel_expression(cel_expr),
+            Err(CelValidationError::MalformedList {
+                list_name: expected_list_name.into(),
+                offset: cel_expr.find(malformed_value).unwrap(),
+            })
+        );
+    }
+
+    #[rstest]
+    fn validate_cel_expression_with_unexpected_object() {
+        let cel_expr = r#"default_certification(ValidationArgs{certification:Certification{no_request_certification:Nothing{},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:[]}}}})"#;
+
+        assert_eq!(
+            validate_cel_expression(cel_expr),
+            Err(CelValidationError::UnexpectedValue {
+                expected: "Empty".into(),
+                found: "Nothing".into(),
+                offset: cel_expr.find("Nothing").unwrap(),
+            })
+        );
+    }
+
+    #[rstest]
+    fn validate_cel_expression_with_invalid_certification() {
+        let cel_expr = r#"default_certification(ValidationArgs{certification:Certification{no_request_certification:Empty{},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:[]},decoded_body_certification:Empty{},fragmented_body_certification:Empty{}}}})"#;
+
+        let err = validate_cel_expression(cel_expr).unwrap_err();
+
+        assert_eq!(
+            err.offset(),
+            cel_expr.find("ResponseCertification{").unwrap()
+        );
+        assert!(matches!(
+            err,
+            CelValidationError::InvalidCertification { message, .. }
+                if message == CelParserError::ExtraneousBodyCertificationProperty.to_string()
+        ));
+    }
+}
@@ -5,13 +5,212 @@
 pub type HttpCertificationResult<T = ()> = Result<T, HttpCertificationError>;
 
 /// HTTP certification error type.
+///
+/// New errors may be added in the future, so this enum is non-exhaustive.
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum HttpCertificationError {
     /// The URL was malformed and could not be parsed correctly.
     #[error(r#"Failed to parse url: "{0}""#)]
     MalformedUrl(String),
 
+    /// A required field was not set when building an [HttpRequest](crate::HttpRequest).
+    #[error(r#"Missing required request field: "{0}""#)]
+    MissingRequestField(String),
+
+    /// A required field was not set when building an [HttpResponse](crate::HttpResponse).
+    #[error(r#"Missing required response field: "{0}""#)]
+    MissingResponseField(String),
+
+    /// The `Host` header was malformed and could not be parsed correctly.
+    #[error(r#"Failed to parse host: "{0}""#)]
+    MalformedHost(String),
+
     /// Error converting UTF-8 string.
     #[error(r#"Error converting UTF8 string bytes: "{0}""#)]
     Utf8ConversionError(#[from] std::string::FromUtf8Error),
+
+    /// The certification for the given path could not be found in the tree.
+    #[error(r#"Certification not found in tree for path: "{0}""#)]
+    CertificationNotFound(String),
+
+    /// A certification with the same path, CEL expression and request hash, but a different
+    /// response hash, was found in the tree.
+    #[error(r#"Conflicting certification exists in tree for path: "{0}""#)]
+    CertificationConflict(String),
+
+    /// A certification for the given path was found in the tree, so its absence cannot be proven.
+    #[error(r#"Certification exists in tree for path: "{0}""#)]
+    CertificationExists(String),
+
+    /// The certification for the given path was found in the tree, but the response hash,
+    /// recomputed according to its CEL expression, does not match it.
+    #[error(r#"Response does not match the certification in tree for path: "{0}""#)]
+    CertificationMismatch(String),
+
+    /// The CEL expression certifies the request, but no request was provided to recompute its
+    /// hash.
+    #[error(r#"A request is required to recompute the certification of a full CEL expression"#)]
+    MissingCertifiedRequest,
+
+    /// The request method is not supported by the handler that received the request.
+    #[error(r#"Unexpected request method: "{found}", expected: "{expected}""#)]
+    UnexpectedRequestMethod {
+        /// The request method supported by the handler.
+        expected: String,
+        /// The request method that was received.
+        found: String,
+    },
+
+    /// The data certificate is not available, for example because the response is being created in an update call.
+    #[error(r#"The data certificate is not available"#)]
+    DataCertificateNotAvailable,
+
+    /// Error encoding a value as CBOR.
+    #[error(r#"Error encoding CBOR: "{0}""#)]
+    CborEncodingError(String),
+
+    /// Error decoding a value from CBOR.
+    #[error(r#"Error decoding CBOR: "{0}""#)]
+    CborDecodingError(String),
+
+    /// The response status code is outside of the valid range of 100 to 599.
+    #[error(r#"Invalid response status code: {0}"#)]
+    InvalidStatusCode(u16),
+
+    /// The header name is empty or contains characters that are not permitted in header names.
+    #[error(r#"Invalid header name: "{0}""#)]
+    InvalidHeaderName(String),
+
+    /// A header that must appear at most once is present multiple times.
+    #[error(r#"Duplicate header: "{0}""#)]
+    DuplicateHeader(String),
+
+    /// The header value exceeds the maximum supported size.
+    #[error(r#"The value of header "{header_name}" is {size} bytes, exceeding the maximum of {max_size} bytes"#)]
+    HeaderValueTooLarge {
+        /// The name of the header.
+        header_name: String,
+        /// The size of the header value in bytes.
+        size: usize,
+        /// The maximum supported size of a header value in bytes.
+        max_size: usize,
+    },
+
+    /// The `Content-Length` header of a response does not match the length of its body.
+    #[error(
+        r#"The Content-Length header "{content_length}" does not match the body length of {body_length} bytes"#
+    )]
+    ContentLengthMismatch {
+        /// The value of the `Content-Length` header.
+        content_length: String,
+        /// The length of the response body in bytes.
+        body_length: usize,
+    },
+
+    /// The path of a tree entry is longer than the maximum configured for the tree.
+    #[error(r#"The url is {url_length} bytes, exceeding the maximum of {max_url_length} bytes"#)]
+    UrlTooLong {
+        /// The length of the url in bytes.
+        url_length: usize,
+        /// The maximum length of a url in bytes.
+        max_url_length: usize,
+    },
+
+    /// The path of a tree entry has more segments than the maximum configured for the tree.
+    #[error(
+        r#"The path has {path_depth} segments, exceeding the maximum of {max_path_depth} segments"#
+    )]
+    PathTooDeep {
+        /// The number of segments of the path.
+        path_depth: usize,
+        /// The maximum number of segments of a path.
+        max_path_depth: usize,
+    },
+
+    /// The header listing the fragments of a fragmented response body is missing or does not
+    /// match the body.
+    #[error(r#"Malformed IC-CertificateFragments header: {0}"#)]
+    MalformedFragmentsHeader(String),
+
+    /// The request body is not a valid `multipart/form-data` body, but its form fields are
+    /// certified.
+    #[error(r#"Malformed multipart/form-data body: {0}"#)]
+    MalformedMultipartBody(String),
+
+    /// The raw HTTP/1.1 message is malformed and could not be parsed.
+    #[error(r#"Malformed HTTP/1.1 message: {0}"#)]
+    MalformedHttp1Message(String),
+
+    /// The request body exceeds the maximum supported size.
+    #[error(
+        r#"The request body is at least {size} bytes, exceeding the maximum of {max_size} bytes"#
+    )]
+    RequestBodyTooLarge {
+        /// The size of the request body in bytes, or of the part of the body that was read
+        /// before the maximum was exceeded.
+        size: usize,
+        /// The maximum supported size of a request body in bytes.
+        max_size: usize,
+    },
+
+    /// The response body exceeds the maximum size configured in the
+    /// [HashLimits](crate::HashLimits) that it is hashed with.
+    #[error(r#"The response body is {size} bytes, exceeding the maximum of {max_size} bytes"#)]
+    ResponseBodyTooLarge {
+        /// The size of the response body in bytes.
+        size: usize,
+        /// The maximum supported size of a response body in bytes.
+        max_size: usize,
+    },
+
+    /// The request or response has more headers than the maximum configured in the
+    /// [HashLimits](crate::HashLimits) that it is hashed with.
+    #[error(
+        r#"There are {header_count} headers, exceeding the maximum of {max_header_count} headers"#
+    )]
+    TooManyHeaders {
+        /// The number of headers.
+        header_count: usize,
+        /// The maximum number of headers.
+        max_header_count: usize,
+    },
+
+    /// The response body is streamed, but no response body hash was provided for the hash
+    /// function that the response is hashed with.
+    #[error(r#"A response body hash is required to hash a streamed response body"#)]
+    MissingStreamingBodyHash,
+
+    /// The response upgrades the request to an update call, which is not permitted for certified
+    /// responses or for responses to update calls.
+    #[error(r#"The response upgrades the request to an update call, which is not permitted here"#)]
+    UnexpectedUpgrade,
+
+    /// The response has a body, but its status code does not permit one.
+    #[error(r#"Responses with status code {status_code} must not have a body"#)]
+    ResponseBodyNotPermitted {
+        /// The status code of the response.
+        status_code: u16,
+    },
+
+    /// Legacy v1 certification was requested from a
+    /// [DualCertificationTree](crate::DualCertificationTree) after it was
+    /// [dropped](crate::DualCertificationTree::drop_v1).
+    #[error(r#"Legacy v1 certification has been dropped"#)]
+    V1CertificationDropped,
+
+    /// A [dry-run verification](crate::CertifiedResponse::dry_run_verify) found that HTTP
+    /// gateways would reject the response.
+    #[error(r#"Dry-run verification failed: {0}"#)]
+    DryRunVerificationFailed(String),
+
+    /// Error compressing a response body with a content encoding.
+    #[cfg(feature = "compression")]
+    #[error(r#"Error compressing the response body: {0}"#)]
+    ContentEncodingError(String),
+
+    /// Error converting to or from a type of the `http` crate.
+    #[cfg(feature = "http-crate")]
+    #[error(r#"Error converting to or from an `http` crate type: {0}"#)]
+    HttpCrateConversionError(String),
 }
@@ -0,0 +1,172 @@
+use super::Hash;
+use crate::{HttpCertificationError, HttpCertificationResult, HttpResponse};
+use ic_representation_independent_hash::hash;
+
+/// The name of the header that lists the byte lengths of the fragments of a response body that
+/// is certified with [fragmented body certification](crate::DefaultBodyCertification::Fragmented).
+pub const CERTIFICATE_FRAGMENTS_HEADER_NAME: &str = "IC-CertificateFragments";
+
+/// Calculates the hash of a fragmented response body from the hashes of its fragments, in order.
+///
+/// Canisters can keep the hash of each fragment and only re-hash the fragments that change.
+pub fn fragmented_body_hash(fragment_hashes: &[Hash]) -> Hash {
+    hash(fragment_hashes.concat().as_slice())
+}
+
+/// Creates the value of the [CERTIFICATE_FRAGMENTS_HEADER_NAME] header from the byte lengths of
+/// the fragments of a response body, in order.
+pub fn fragments_header_value(fragment_lengths: &[usize]) -> String {
+    fragment_lengths
+        .iter()
+        .map(|fragment_length| fragment_length.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Splits the body of an [HttpResponse] into the fragments listed in its
+/// [CERTIFICATE_FRAGMENTS_HEADER_NAME] header.
+///
+/// Returns an error if the header is missing, malformed, or if the fragment lengths do not add up
+/// to the length of the body.
+pub fn response_fragments(response: &HttpResponse) -> HttpCertificationResult<Vec<&[u8]>> {
+    split_fragments(&response.body, find_fragments_header(&response.headers)?)
+}
+
+/// Returns the value of the [CERTIFICATE_FRAGMENTS_HEADER_NAME] header, or an error if it is
+/// missing.
+pub(crate) fn find_fragments_header(
+    headers: &[(impl AsRef<str>, impl AsRef<str>)],
+) -> HttpCertificationResult<&str> {
+    headers
+        .iter()
+        .find(|(header_name, _)| {
+            header_name
+                .as_ref()
+                .eq_ignore_ascii_case(CERTIFICATE_FRAGMENTS_HEADER_NAME)
+        })
+        .map(|(_, header_value)| header_value.as_ref())
+        .ok_or_else(|| {
+            HttpCertificationError::MalformedFragmentsHeader("the header is missing".into())
+        })
+}
+
+/// Splits a response body into the fragments listed in the value of a
+/// [CERTIFICATE_FRAGMENTS_HEADER_NAME] header.
+///
+/// Returns an error if the header value is malformed, or if the fragment lengths do not add up to
+/// the length of the body.
+pub fn split_fragments<'a>(
+    body: &'a [u8],
+    header_value: &str,
+) -> HttpCertificationResult<Vec<&'a [u8]>> {
+    let mut fragments = Vec::new();
+    let mut remaining_body = body;
+
+    if !header_value.trim().is_empty() {
+        for fragment_length in header_value.split(',') {
+            let fragment_length = fragment_length.trim().parse::<usize>().map_err(|_| {
+                HttpCertificationError::MalformedFragmentsHeader(format!(
+                    "invalid fragment length {fragment_length:?}"
+                ))
+            })?;
+            if fragment_length > remaining_body.len() {
+                return Err(HttpCertificationError::MalformedFragmentsHeader(
+                    "the fragments are longer than the body".into(),
+                ));
+            }
+
+            let (fragment, rest) = remaining_body.split_at(fragment_length);
+            fragments.push(fragment);
+            remaining_body = rest;
+        }
+    }
+
+    if !remaining_body.is_empty() {
+        return Err(HttpCertificationError::MalformedFragmentsHeader(
+            "the fragments are shorter than the body".into(),
+        ));
+    }
+
+    Ok(fragments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    fn fragmented_body_hash_of_fragments() {
+        let fragments: [&[u8]; 3] = [b"<ul>", b"<li>Hello</li>", b"</ul>"];
+        let fragment_hashes: Vec<Hash> = fragments.iter().map(|fragment| hash(fragment)).collect();
+
+        let expected_hash = hash(
+            [hash(b"<ul>"), hash(b"<li>Hello</li>"), hash(b"</ul>")]
+                .concat()
+                .as_slice(),
+        );
+
+        assert_eq!(fragmented_body_hash(&fragment_hashes), expected_hash);
+        assert_ne!(
+            fragmented_body_hash(&fragment_hashes),
+            hash(fragments.concat().as_slice())
+        );
+    }
+
+    #[rstest]
+    fn response_fragments_round_trip() {
+        let fragments: [&[u8]; 3] = [b"<ul>", b"<li>Hello</li>", b"</ul>"];
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![(
+                "ic-certificatefragments".into(),
+                fragments_header_value(&[4, 14, 5]),
+            )],
+            body: fragments.concat(),
+            upgrade: None,
+            trailers: None,
+        };
+
+        assert_eq!(response_fragments(&response).unwrap(), fragments);
+    }
+
+    #[rstest]
+    #[case("", b"")]
+    #[case("0", b"")]
+    #[case(" 2, 3 ", b"Hello")]
+    fn split_fragments_valid(#[case] header_value: &str, #[case] body: &[u8]) {
+        let fragments = split_fragments(body, header_value).unwrap();
+
+        assert_eq!(fragments.concat(), body);
+    }
+
+    #[rstest]
+    #[case("", b"Hello")]
+    #[case("2", b"Hello")]
+    #[case("2,4", b"Hello")]
+    #[case("2,-3", b"Hello")]
+    #[case("2,,3", b"Hello")]
+    #[case("2;3", b"Hello")]
+    fn split_fragments_invalid(#[case] header_value: &str, #[case] body: &[u8]) {
+        assert!(matches!(
+            split_fragments(body, header_value),
+            Err(HttpCertificationError::MalformedFragmentsHeader(_))
+        ));
+    }
+
+    #[rstest]
+    fn response_fragments_without_header() {
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![],
+            body: b"Hello".to_vec(),
+            upgrade: None,
+            trailers: None,
+        };
+
+        assert!(matches!(
+            response_fragments(&response),
+            Err(HttpCertificationError::MalformedFragmentsHeader(_))
+        ));
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{HttpCertificationError, HttpCertificationResult, HttpRequestRef, HttpResponseRef};
+
+/// Limits on the size of the requests and responses that are hashed with
+/// [request_hash_with_limits](crate::request_hash_with_limits) and
+/// [response_hash_with_limits](crate::response_hash_with_limits).
+///
+/// Gateways and other off-chain verifiers hash requests and responses that they do not control,
+/// so they can use these limits to reject oversized inputs before hashing them. By default, no
+/// limits are enforced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HashLimits {
+    max_body_size: Option<usize>,
+    max_header_count: Option<usize>,
+    max_header_value_size: Option<usize>,
+}
+
+impl HashLimits {
+    /// Creates new [HashLimits] that do not enforce any limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum size, in bytes, of a request or response body. For a
+    /// [streamed](crate::Body::Streaming) response body, this applies to its first chunk.
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = Some(max_body_size);
+
+        self
+    }
+
+    /// Sets the maximum number of headers of a request or response, including headers that are
+    /// not certified.
+    pub fn with_max_header_count(mut self, max_header_count: usize) -> Self {
+        self.max_header_count = Some(max_header_count);
+
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of a single header value.
+    pub fn with_max_header_value_size(mut self, max_header_value_size: usize) -> Self {
+        self.max_header_value_size = Some(max_header_value_size);
+
+        self
+    }
+
+    /// Checks that the given request is within these limits.
+    pub fn check_request(&self, request: &HttpRequestRef) -> HttpCertificationResult {
+        if let Some(max_body_size) = self.max_body_size {
+            if request.body.len() > max_body_size {
+                return Err(HttpCertificationError::RequestBodyTooLarge {
+                    size: request.body.len(),
+                    max_size: max_body_size,
+                });
+            }
+        }
+
+        self.check_headers(&request.headers)
+    }
+
+    /// Checks that the given response is within these limits.
+    pub fn check_response(&self, response: &HttpResponseRef) -> HttpCertificationResult {
+        if let Some(max_body_size) = self.max_body_size {
+            let body_size = response.body.first_chunk().len();
+            if body_size > max_body_size {
+                return Err(HttpCertificationError::ResponseBodyTooLarge {
+                    size: body_size,
+                    max_size: max_body_size,
+                });
+            }
+        }
+
+        self.check_headers(&response.headers)
+    }
+
+    fn check_headers(
+        &self,
+        headers: &[(impl AsRef<str>, impl AsRef<str>)],
+    ) -> HttpCertificationResult {
+        if let Some(max_header_count) = self.max_header_count {
+            if headers.len() > max_header_count {
+                return Err(HttpCertificationError::TooManyHeaders {
+                    header_count: headers.len(),
+                    max_header_count,
+                });
+            }
+        }
+
+        if let Some(max_header_value_size) = self.max_header_value_size {
+            for (header_name, header_value) in headers {
+                let size = header_value.as_ref().len();
+                if size > max_header_value_size {
+                    return Err(HttpCertificationError::HeaderValueTooLarge {
+                        header_name: header_name.as_ref().to_string(),
+                        size,
+                        max_size: max_header_value_size,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Body, HttpRequest, HttpResponse, StreamingBody};
+    use rstest::*;
+
+    #[fixture]
+    fn request() -> HttpRequest {
+        HttpRequest {
+            method: "POST".into(),
+            url: "/".into(),
+            headers: vec![
+                ("Accept".into(), "text/plain".into()),
+                ("Accept-Language".into(), "en".into()),
+            ],
+            body: b"Hello World!".to_vec(),
+        }
+    }
+
+    #[fixture]
+    fn response() -> HttpResponse {
+        HttpResponse {
+            status_code: 200,
+            headers: vec![
+                ("Content-Type".into(), "text/plain".into()),
+                ("Cache-Control".into(), "no-cache".into()),
+            ],
+            body: b"Hello World!".to_vec(),
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    #[fixture]
+    fn limits() -> HashLimits {
+        HashLimits::new()
+            .with_max_body_size(12)
+            .with_max_header_count(2)
+            .with_max_header_value_size(10)
+    }
+
+    #[rstest]
+    fn check_within_limits(limits: HashLimits, request: HttpRequest, response: HttpResponse) {
+        assert!(limits.check_request(&(&request).into()).is_ok());
+        assert!(limits.check_response(&(&response).into()).is_ok());
+    }
+
+    #[rstest]
+    fn check_without_limits(mut request: HttpRequest, mut response: HttpResponse) {
+        request.body = vec![0; 1_000_000];
+        response.headers = vec![("X-Large".into(), "a".repeat(1_000_000)); 100];
+
+        assert!(HashLimits::new().check_request(&(&request).into()).is_ok());
+        assert!(HashLimits::new()
+            .check_response(&(&response).into())
+            .is_ok());
+    }
+
+    #[rstest]
+    fn check_body_too_large(
+        limits: HashLimits,
+        mut request: HttpRequest,
+        mut response: HttpResponse,
+    ) {
+        request.body.push(b'!');
+        response.body.push(b'!');
+
+        assert!(matches!(
+            limits.check_request(&(&request).into()),
+            Err(HttpCertificationError::RequestBodyTooLarge {
+                size: 13,
+                max_size: 12
+            })
+        ));
+        assert!(matches!(
+            limits.check_response(&(&response).into()),
+            Err(HttpCertificationError::ResponseBodyTooLarge {
+                size: 13,
+                max_size: 12
+            })
+        ));
+    }
+
+    #[rstest]
+    fn check_streamed_body(limits: HashLimits, response: HttpResponse) {
+        let mut response = HttpResponseRef::from(&response);
+        response.body = Body::Streaming(StreamingBody {
+            first_chunk: b"Hello".as_slice().into(),
+            token: b"next-chunk".as_slice().into(),
+            body_hash: [0; 32],
+        });
+
+        assert!(limits.check_response(&response).is_ok());
+    }
+
+    #[rstest]
+    fn check_too_many_headers(
+        limits: HashLimits,
+        mut request: HttpRequest,
+        mut response: HttpResponse,
+    ) {
+        request
+            .headers
+            .push(("Accept-Encoding".into(), "gzip".into()));
+        response.headers.push(("ETag".into(), "\"abc\"".into()));
+
+        assert!(matches!(
+            limits.check_request(&(&request).into()),
+            Err(HttpCertificationError::TooManyHeaders {
+                header_count: 3,
+                max_header_count: 2
+            })
+        ));
+        assert!(matches!(
+            limits.check_response(&(&response).into()),
+            Err(HttpCertificationError::TooManyHeaders {
+                header_count: 3,
+                max_header_count: 2
+            })
+        ));
+    }
+
+    #[rstest]
+    fn check_header_value_too_large(limits: HashLimits, mut response: HttpResponse) {
+        response.headers[1].1 = "no-cache, no-store".into();
+
+        assert!(matches!(
+            limits.check_response(&(&response).into()),
+            Err(HttpCertificationError::HeaderValueTooLarge {
+                header_name,
+                size: 18,
+                max_size: 10
+            }) if header_name == "Cache-Control"
+        ));
+    }
+}
@@ -2,11 +2,22 @@
 //! [Representation Independent Hashes](https://internetcomputer.org/docs/current/references/ic-interface-spec/#hash-of-map)
 //! of [crate::HttpRequest] and [crate::HttpRequest] objects.
 
+mod fragment_hash;
+pub use fragment_hash::*;
+
+mod hash_limits;
+pub use hash_limits::*;
+
+mod multipart;
+pub use multipart::canonical_multipart_body;
+
 mod request_hash;
 pub use request_hash::*;
 
 mod response_hash;
 pub use response_hash::*;
 
+pub use ic_representation_independent_hash::{DigestAlgorithm, Sha256Algorithm};
+
 /// Sha256 Digest: 32 bytes
 pub type Hash = [u8; 32];
@@ -0,0 +1,304 @@
+use crate::{HeaderFieldRef, HttpCertificationError, HttpCertificationResult, HttpRequestRef};
+use serde_cbor::Value;
+
+const CONTENT_TYPE_HEADER_NAME: &str = "Content-Type";
+const CONTENT_DISPOSITION_HEADER_NAME: &str = "Content-Disposition";
+const MULTIPART_FORM_DATA_MEDIA_TYPE: &str = "multipart/form-data";
+
+/// A part of a `multipart/form-data` body.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct MultipartPart<'b> {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+    content: &'b [u8],
+}
+
+/// Parses a `multipart/form-data` request body, whose boundary is taken from the `Content-Type`
+/// header, and returns the name and value of every field whose name is in `field_names`, in the
+/// order that they appear in the body.
+///
+/// Names are compared case-sensitively, since form field names are case-sensitive. Fields that
+/// appear more than once are returned once for every occurrence.
+pub(crate) fn multipart_form_fields<'b>(
+    headers: &[HeaderFieldRef],
+    body: &'b [u8],
+    field_names: &[&str],
+) -> HttpCertificationResult<Vec<(String, &'b [u8])>> {
+    let fields = multipart_parts(headers, body)?
+        .into_iter()
+        .filter(|part| field_names.contains(&part.name.as_str()))
+        .map(|part| (part.name, part.content))
+        .collect();
+
+    Ok(fields)
+}
+
+/// Returns a canonical representation of the `multipart/form-data` body of a request, which does
+/// not depend on the boundary or on the order of the parts.
+///
+/// The boundary is taken from the `Content-Type` header of the request. Every part is reduced to
+/// its field name, its file name and content type, if any, and its content. Other part headers,
+/// the preamble and the epilogue are ignored. The parts are sorted and encoded as a CBOR array,
+/// in which every part is an array of the field name, the file name or `null`, the lowercased
+/// content type or `null`, and the content as a byte string.
+///
+/// This is the body that is hashed by [request_hash](crate::request_hash) if the
+/// [canonical multipart body](crate::cel::DefaultRequestCertification::canonical_multipart_body)
+/// is certified, so that a canister can certify an upload without knowing the boundary that the
+/// client will choose. Returns an error if the body cannot be parsed.
+pub fn canonical_multipart_body<'a>(
+    request: impl Into<HttpRequestRef<'a>>,
+) -> HttpCertificationResult<Vec<u8>> {
+    let request = request.into();
+    let mut parts = multipart_parts(&request.headers, &request.body)?;
+    parts.sort();
+
+    let parts = parts
+        .into_iter()
+        .map(|part| {
+            Value::Array(vec![
+                Value::Text(part.name),
+                part.filename.map_or(Value::Null, Value::Text),
+                part.content_type.map_or(Value::Null, Value::Text),
+                Value::Bytes(part.content.to_vec()),
+            ])
+        })
+        .collect();
+
+    serde_cbor::to_vec(&Value::Array(parts))
+        .map_err(|err| HttpCertificationError::CborEncodingError(err.to_string()))
+}
+
+/// Parses a `multipart/form-data` body, whose boundary is taken from the `Content-Type` header,
+/// and returns its parts in the order that they appear in the body.
+fn multipart_parts<'b>(
+    headers: &[HeaderFieldRef],
+    body: &'b [u8],
+) -> HttpCertificationResult<Vec<MultipartPart<'b>>> {
+    let content_type = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(CONTENT_TYPE_HEADER_NAME))
+        .map(|(_, value)| value.as_ref())
+        .ok_or_else(|| malformed_multipart_body("the Content-Type header is missing"))?;
+    let boundary = multipart_boundary(content_type)?;
+    let delimiter = [b"--", boundary.as_bytes()].concat();
+    let part_delimiter = [b"\r\n", delimiter.as_slice()].concat();
+
+    let Some(start) = find(body, &delimiter) else {
+        return Err(malformed_multipart_body("the first boundary is missing"));
+    };
+    let mut remaining = &body[start + delimiter.len()..];
+    let mut parts = vec![];
+
+    loop {
+        if remaining.starts_with(b"--") {
+            return Ok(parts);
+        }
+        remaining = remaining
+            .strip_prefix(b"\r\n")
+            .ok_or_else(|| malformed_multipart_body("a boundary is not followed by CRLF"))?;
+
+        let part_end = find(remaining, &part_delimiter)
+            .ok_or_else(|| malformed_multipart_body("the closing boundary is missing"))?;
+        let part = &remaining[..part_end];
+        remaining = &remaining[part_end + part_delimiter.len()..];
+
+        let headers_end = find(part, b"\r\n\r\n")
+            .ok_or_else(|| malformed_multipart_body("a part has no header section"))?;
+        parts.push(multipart_part(
+            &part[..headers_end],
+            &part[headers_end + 4..],
+        )?);
+    }
+}
+
+fn multipart_boundary(content_type: &str) -> HttpCertificationResult<String> {
+    let mut parameters = content_type.split(';').map(str::trim);
+    let media_type = parameters.next().unwrap_or_default();
+    if !media_type.eq_ignore_ascii_case(MULTIPART_FORM_DATA_MEDIA_TYPE) {
+        return Err(malformed_multipart_body(&format!(
+            "the Content-Type header is not {MULTIPART_FORM_DATA_MEDIA_TYPE}"
+        )));
+    }
+
+    parameters
+        .find_map(|parameter| parameter_value(parameter, "boundary"))
+        .filter(|boundary| !boundary.is_empty())
+        .ok_or_else(|| malformed_multipart_body("the Content-Type header has no boundary"))
+}
+
+fn multipart_part<'b>(
+    part_headers: &[u8],
+    content: &'b [u8],
+) -> HttpCertificationResult<MultipartPart<'b>> {
+    let part_headers = std::str::from_utf8(part_headers)
+        .map_err(|_| malformed_multipart_body("the headers of a part are not valid UTF-8"))?;
+    let part_header = |header_name: &str| {
+        part_headers
+            .split("\r\n")
+            .filter_map(|header| header.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case(header_name))
+            .map(|(_, value)| value)
+    };
+
+    let content_disposition = part_header(CONTENT_DISPOSITION_HEADER_NAME)
+        .map(|value| value.split(';').map(str::trim).collect::<Vec<_>>())
+        .filter(|parameters| parameters[0].eq_ignore_ascii_case("form-data"))
+        .ok_or_else(|| malformed_multipart_body("a part has no form-data field name"))?;
+    let name = content_disposition
+        .iter()
+        .find_map(|parameter| parameter_value(parameter, "name"))
+        .ok_or_else(|| malformed_multipart_body("a part has no form-data field name"))?;
+    let filename = content_disposition
+        .iter()
+        .find_map(|parameter| parameter_value(parameter, "filename"));
+    let content_type = part_header(CONTENT_TYPE_HEADER_NAME).map(|value| {
+        value
+            .split(';')
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join(";")
+            .to_ascii_lowercase()
+    });
+
+    Ok(MultipartPart {
+        name,
+        filename,
+        content_type,
+        content,
+    })
+}
+
+/// Returns the value of a `name=value` or `name="value"` parameter, if it has the given name.
+fn parameter_value(parameter: &str, name: &str) -> Option<String> {
+    let (parameter_name, value) = parameter.split_once('=')?;
+    if !parameter_name.trim().eq_ignore_ascii_case(name) {
+        return None;
+    }
+
+    let value = value.trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value);
+
+    Some(value.to_string())
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn malformed_multipart_body(reason: &str) -> HttpCertificationError {
+    HttpCertificationError::MalformedMultipartBody(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::borrow::Cow;
+
+    const BODY: &[u8] = b"preamble\r\n--XyZ\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nHoliday\r\n--XyZ\r\nContent-Disposition: form-data; name=\"file\"; filename=\"photo.jpg\"\r\nContent-Type: image/jpeg\r\n\r\n\xff\xd8\xff\r\n--XyZ\r\ncontent-disposition: form-data; name=tags\r\n\r\nbeach\r\n--XyZ--\r\n";
+
+    fn headers(content_type: &str) -> Vec<HeaderFieldRef<'_>> {
+        vec![(Cow::Borrowed("content-type"), Cow::Borrowed(content_type))]
+    }
+
+    #[test]
+    fn selects_form_fields() {
+        let fields = multipart_form_fields(
+            &headers(r#"multipart/form-data; boundary="XyZ""#),
+            BODY,
+            &["tags", "title"],
+        )
+        .unwrap();
+
+        assert_eq!(
+            fields,
+            vec![
+                ("title".to_string(), b"Holiday".as_slice()),
+                ("tags".to_string(), b"beach".as_slice()),
+            ]
+        );
+    }
+
+    #[test]
+    fn selects_binary_form_fields() {
+        let fields = multipart_form_fields(
+            &headers("multipart/form-data; boundary=XyZ"),
+            BODY,
+            &["file"],
+        )
+        .unwrap();
+
+        assert_eq!(
+            fields,
+            vec![("file".to_string(), b"\xff\xd8\xff".as_slice())]
+        );
+    }
+
+    #[test]
+    fn canonical_multipart_body_sorts_parts() {
+        let request = HttpRequestRef {
+            method: Cow::Borrowed("POST"),
+            url: Cow::Borrowed("/upload"),
+            headers: headers("multipart/form-data; boundary=XyZ"),
+            body: Cow::Borrowed(BODY),
+        };
+
+        let result = canonical_multipart_body(request).unwrap();
+
+        assert_eq!(
+            serde_cbor::from_slice::<Value>(&result).unwrap(),
+            Value::Array(vec![
+                Value::Array(vec![
+                    Value::Text("file".to_string()),
+                    Value::Text("photo.jpg".to_string()),
+                    Value::Text("image/jpeg".to_string()),
+                    Value::Bytes(b"\xff\xd8\xff".to_vec()),
+                ]),
+                Value::Array(vec![
+                    Value::Text("tags".to_string()),
+                    Value::Null,
+                    Value::Null,
+                    Value::Bytes(b"beach".to_vec()),
+                ]),
+                Value::Array(vec![
+                    Value::Text("title".to_string()),
+                    Value::Null,
+                    Value::Null,
+                    Value::Bytes(b"Holiday".to_vec()),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_other_content_types() {
+        assert!(matches!(
+            multipart_form_fields(&headers("application/json"), BODY, &["title"]),
+            Err(HttpCertificationError::MalformedMultipartBody(_))
+        ));
+        assert!(matches!(
+            multipart_form_fields(&headers("multipart/form-data"), BODY, &["title"]),
+            Err(HttpCertificationError::MalformedMultipartBody(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_truncated_body() {
+        let truncated_body = &BODY[..BODY.len() - 10];
+
+        assert!(matches!(
+            multipart_form_fields(
+                &headers("multipart/form-data; boundary=XyZ"),
+                truncated_body,
+                &["title"]
+            ),
+            Err(HttpCertificationError::MalformedMultipartBody(_))
+        ));
+    }
+}
@@ -1,51 +1,120 @@
-use super::Hash;
-use crate::{cel::DefaultRequestCertification, HttpCertificationResult, HttpRequest};
-use ic_representation_independent_hash::{hash, representation_independent_hash, Value};
+use super::{
+    multipart::{canonical_multipart_body, multipart_form_fields},
+    Hash, HashLimits,
+};
+use crate::{
+    cel::DefaultRequestCertification, header_name_matches, normalize_pseudo_headers,
+    HttpCertificationResult, HttpRequestRef, Method,
+};
+use ic_representation_independent_hash::{
+    representation_independent_hash_with, DigestAlgorithm, Sha256Algorithm, Value,
+};
 
 /// Calculates the
 /// [Representation Independent Hash](https://internetcomputer.org/docs/current/references/ic-interface-spec/#hash-of-map)
-/// of an [HttpRequest] according to a CEL expression defined by [DefaultRequestCertification].
+/// of an [HttpRequest](crate::HttpRequest) according to a CEL expression defined by
+/// [DefaultRequestCertification].
+///
+/// The request method is uppercased before it is hashed, see [Method]. HTTP/2 pseudo-headers are
+/// normalized before the certified request headers are selected, see [normalize_pseudo_headers].
+///
+/// If [multipart form fields](DefaultRequestCertification::multipart_form_fields) are certified,
+/// the body is parsed as `multipart/form-data` and the raw body hash is replaced by the
+/// representation independent hash of the selected fields, mapping each field name to the bytes
+/// of its value. Otherwise, if the
+/// [canonical multipart body](DefaultRequestCertification::canonical_multipart_body) is
+/// certified, the raw body hash is replaced by the hash of the [canonical_multipart_body]. Returns
+/// an error if the body cannot be parsed.
+///
+/// The request may be an [HttpRequest](crate::HttpRequest), a reference to one, or an
+/// [HttpRequestRef] that borrows its body and headers.
 pub fn request_hash<'a>(
-    request: &'a HttpRequest,
-    request_certification: &'a DefaultRequestCertification,
+    request: impl Into<HttpRequestRef<'a>>,
+    request_certification: &DefaultRequestCertification,
 ) -> HttpCertificationResult<Hash> {
-    let mut filtered_headers = get_filtered_headers(&request.headers, request_certification);
+    request_hash_with::<Sha256Algorithm>(request, request_certification)
+}
+
+/// Calculates the [request_hash] of an [HttpRequest](crate::HttpRequest), after checking that the
+/// request is within the given [HashLimits].
+///
+/// Returns an error, without hashing the request, if it exceeds the limits, see
+/// [HashLimits::check_request].
+pub fn request_hash_with_limits<'a>(
+    request: impl Into<HttpRequestRef<'a>>,
+    request_certification: &DefaultRequestCertification,
+    limits: &HashLimits,
+) -> HttpCertificationResult<Hash> {
+    let request = request.into();
+    limits.check_request(&request)?;
+
+    request_hash(request, request_certification)
+}
+
+/// Calculates the [request_hash] of an [HttpRequest](crate::HttpRequest) with the hash function `D`.
+pub fn request_hash_with<'a, D: DigestAlgorithm>(
+    request: impl Into<HttpRequestRef<'a>>,
+    request_certification: &DefaultRequestCertification,
+) -> HttpCertificationResult<D::Output> {
+    let request = request.into();
+    let mut filtered_headers = get_filtered_headers(
+        &normalize_pseudo_headers(&request.headers),
+        request_certification,
+    );
 
     filtered_headers.push((
         ":ic-cert-method".into(),
-        Value::String(request.method.to_string()),
+        Value::String(Method::from(request.method.as_ref()).into()),
     ));
 
     let filtered_query = request
-        .get_query()?
-        .map(|query| get_filtered_query(&query, request_certification));
+        .canonical_query()?
+        .map(|query| request_certification.certified_query(&query));
     if let Some(query_hash) = filtered_query {
         filtered_headers.push((":ic-cert-query".into(), Value::String(query_hash)))
     }
 
+    let body_hash = match (
+        request_certification.multipart_form_fields.is_empty(),
+        request_certification.canonical_multipart_body,
+    ) {
+        (true, false) => D::digest(&request.body),
+        (true, true) => D::digest(&canonical_multipart_body(request)?),
+        (false, _) => {
+            let form_fields: Vec<(String, Value)> = multipart_form_fields(
+                &request.headers,
+                &request.body,
+                &request_certification.multipart_form_fields,
+            )?
+            .into_iter()
+            .map(|(name, value)| (name, Value::Bytes(value.to_vec())))
+            .collect();
+
+            representation_independent_hash_with::<D>(&form_fields)
+        }
+    };
+
     let concatenated_hashes = [
-        representation_independent_hash(&filtered_headers),
-        hash(&request.body),
+        representation_independent_hash_with::<D>(&filtered_headers).as_ref(),
+        body_hash.as_ref(),
     ]
     .concat();
 
-    Ok(hash(concatenated_hashes.as_slice()))
+    Ok(D::digest(concatenated_hashes.as_slice()))
 }
 
 fn get_filtered_headers(
-    headers: &[(String, String)],
+    headers: &[(impl AsRef<str>, impl AsRef<str>)],
     request_certification: &DefaultRequestCertification,
 ) -> Vec<(String, Value)> {
     headers
         .iter()
         .filter_map(|(header_name, header_value)| {
-            let is_header_included =
-                request_certification
-                    .headers
-                    .iter()
-                    .any(|header_to_include| {
-                        header_to_include.eq_ignore_ascii_case(&header_name.to_string())
-                    });
+            let (header_name, header_value) = (header_name.as_ref(), header_value.as_ref());
+            let is_header_included = request_certification
+                .headers
+                .iter()
+                .any(|header_to_include| header_name_matches(header_to_include, header_name));
 
             if !is_header_included {
                 return None;
@@ -59,33 +128,10 @@ fn get_filtered_headers(
         .collect()
 }
 
-fn get_filtered_query(query: &str, request_certification: &DefaultRequestCertification) -> String {
-    let filtered_query_string = query
-        .split('&')
-        .filter(|query_fragment| {
-            let mut split_fragment: Vec<&str> = query_fragment.split('=').take(1).collect();
-            let query_param_name = split_fragment.pop();
-
-            query_param_name
-                .map(|query_param_name| {
-                    request_certification
-                        .query_parameters
-                        .iter()
-                        .any(|query_param_to_include| {
-                            query_param_to_include.eq_ignore_ascii_case(query_param_name)
-                        })
-                })
-                .unwrap_or(false)
-        })
-        .collect::<Vec<&str>>()
-        .join("&");
-
-    filtered_query_string
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{HttpCertificationError, HttpRequest};
 
     #[test]
     fn request_hash_without_query() {
@@ -130,6 +176,81 @@ mod tests {
         assert_ne!(result, reordered_result);
     }
 
+    #[test]
+    fn certified_query_keeps_repeated_parameters_in_order() {
+        let request_certification = DefaultRequestCertification::new(vec![], vec!["tag"]);
+
+        assert_eq!(
+            request_certification.certified_query("tag=a&page=2&tag=b"),
+            "tag=a&tag=b"
+        );
+        assert_eq!(
+            request_certification.certified_query("tag=b&tag=a"),
+            "tag=b&tag=a"
+        );
+        assert_eq!(
+            request_certification.certified_query("TAG=a&tag&tags=c&tag="),
+            "TAG=a&tag&tag="
+        );
+        assert_eq!(request_certification.certified_query("page=2"), "");
+    }
+
+    #[test]
+    fn request_hash_of_repeated_query_parameters() {
+        let request_certification = DefaultRequestCertification::new(vec![], vec!["tag"]);
+        let hash_url = |url| request_hash(create_request(url), &request_certification).unwrap();
+
+        let result = hash_url("/items?tag=a&tag=b");
+
+        assert_eq!(result, hash_url("/items?tag=a&page=2&tag=b"));
+        assert_ne!(result, hash_url("/items?tag=b&tag=a"));
+        assert_ne!(result, hash_url("/items?tag=a"));
+        assert_ne!(result, hash_url("/items?TAG=a&TAG=b"));
+        assert_ne!(hash_url("/items?page=2"), hash_url("/items"));
+    }
+
+    #[test]
+    fn certified_query_with_patterns() {
+        let request_certification = DefaultRequestCertification::new(vec![], vec!["page*"]);
+
+        assert_eq!(
+            request_certification.certified_query("page=2&limit=10&PAGE_SIZE=20&utm_source=a"),
+            "page=2&PAGE_SIZE=20"
+        );
+        assert_eq!(request_certification.certified_query("limit=10"), "");
+    }
+
+    #[test]
+    fn certified_query_with_exclusions() {
+        let request_certification =
+            DefaultRequestCertification::new(vec![], vec!["*", "!utm_*", "!fbclid"]);
+        let exclusions_only = DefaultRequestCertification::new(vec![], vec!["!utm_*"]);
+
+        assert_eq!(
+            request_certification
+                .certified_query("utm_source=a&page=2&FBCLID=b&UTM_MEDIUM=c&tag=x"),
+            "page=2&tag=x"
+        );
+        assert_eq!(exclusions_only.certified_query("utm_source=a&page=2"), "");
+    }
+
+    #[test]
+    fn request_hash_of_query_parameter_patterns() {
+        let request_certification =
+            DefaultRequestCertification::new(vec![], vec!["page*", "!page_token"]);
+        let hash_url = |url| request_hash(create_request(url), &request_certification).unwrap();
+
+        let result = hash_url("/items?page=2&page_size=20");
+
+        assert_eq!(result, hash_url("/items?page=2&utm_source=a&page_size=20"));
+        assert_eq!(
+            result,
+            hash_url("/items?page=2&page_token=abc&page_size=20")
+        );
+        assert_ne!(result, hash_url("/items?page_size=20&page=2"));
+        assert_ne!(result, hash_url("/items?page=3&page_size=20"));
+    }
+
     #[test]
     fn request_hash_query_with_fragment_does_not_change() {
         let request_certification =
@@ -147,6 +268,219 @@ mod tests {
         assert_eq!(result, result_with_fragment);
     }
 
+    #[test]
+    fn request_hash_of_absolute_and_origin_form_urls_match() {
+        let request_certification =
+            DefaultRequestCertification::new(vec!["host"], vec!["q", "name"]);
+        let request = create_request("https://ic0.app/index.html?q=hello+world&name=foo");
+        let origin_form_request = create_request("/index.html?q=hello+world&name=foo");
+
+        let result = request_hash(&request, &request_certification).unwrap();
+        let origin_form_result =
+            request_hash(&origin_form_request, &request_certification).unwrap();
+
+        assert_eq!(result, origin_form_result);
+    }
+
+    #[test]
+    fn request_hash_of_lowercase_and_uppercase_methods_match() {
+        let request_certification = DefaultRequestCertification::new(vec!["host"], vec![]);
+        let request = create_request("/index.html");
+        let mut lowercase_request = create_request("/index.html");
+        lowercase_request.method = "post".into();
+
+        let result = request_hash(&request, &request_certification).unwrap();
+        let lowercase_result = request_hash(&lowercase_request, &request_certification).unwrap();
+
+        assert_eq!(result, lowercase_result);
+    }
+
+    #[test]
+    fn request_hash_of_different_bodies_differ() {
+        let request_certification = DefaultRequestCertification::new(vec![], vec![]);
+        let request = create_request("https://ic0.app");
+        let mut changed_request = request.clone();
+        changed_request.body[0] = 1;
+
+        assert_ne!(
+            request_hash(&changed_request, &request_certification).unwrap(),
+            request_hash(&request, &request_certification).unwrap()
+        );
+    }
+
+    #[test]
+    fn request_hash_of_multipart_form_fields_ignores_other_fields() {
+        let request_certification = DefaultRequestCertification::new(vec![], vec![])
+            .with_multipart_form_fields(vec!["title"]);
+        let multipart_request = |file: &str| {
+            HttpRequest {
+            url: "/upload".into(),
+            method: "POST".into(),
+            headers: vec![(
+                "Content-Type".into(),
+                "multipart/form-data; boundary=XyZ".into(),
+            )],
+            body: format!("--XyZ\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nHoliday\r\n--XyZ\r\nContent-Disposition: form-data; name=\"file\"\r\n\r\n{file}\r\n--XyZ--\r\n").into_bytes(),
+        }
+        };
+
+        let result = request_hash(multipart_request("abc"), &request_certification).unwrap();
+        let other_file_result =
+            request_hash(multipart_request("def"), &request_certification).unwrap();
+        let raw_body_result = request_hash(
+            multipart_request("abc"),
+            &DefaultRequestCertification::new(vec![], vec![]),
+        )
+        .unwrap();
+
+        assert_eq!(result, other_file_result);
+        assert_ne!(result, raw_body_result);
+    }
+
+    #[test]
+    fn request_hash_of_canonical_multipart_body_ignores_boundary_and_part_order() {
+        let request_certification =
+            DefaultRequestCertification::new(vec![], vec![]).with_canonical_multipart_body(true);
+        let multipart_request = |boundary: &str, parts: [(&str, &str); 2]| {
+            let body: String = parts
+                .iter()
+                .map(|(name, value)| {
+                    format!("--{boundary}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n")
+                })
+                .chain([format!("--{boundary}--\r\n")])
+                .collect();
+
+            HttpRequest {
+                url: "/upload".into(),
+                method: "POST".into(),
+                headers: vec![(
+                    "Content-Type".into(),
+                    format!("multipart/form-data; boundary={boundary}"),
+                )],
+                body: body.into_bytes(),
+            }
+        };
+
+        let result = request_hash(
+            multipart_request("XyZ", [("title", "Holiday"), ("file", "abc")]),
+            &request_certification,
+        )
+        .unwrap();
+        let reordered_result = request_hash(
+            multipart_request("AbC", [("file", "abc"), ("title", "Holiday")]),
+            &request_certification,
+        )
+        .unwrap();
+        let other_file_result = request_hash(
+            multipart_request("XyZ", [("title", "Holiday"), ("file", "def")]),
+            &request_certification,
+        )
+        .unwrap();
+
+        assert_eq!(result, reordered_result);
+        assert_ne!(result, other_file_result);
+    }
+
+    #[test]
+    fn request_hash_of_multipart_form_fields_requires_multipart_body() {
+        let request_certification = DefaultRequestCertification::new(vec![], vec![])
+            .with_multipart_form_fields(vec!["title"]);
+
+        let result = request_hash(create_request("/upload"), &request_certification);
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::MalformedMultipartBody(_))
+        ));
+    }
+
+    #[test]
+    fn request_hash_normalizes_pseudo_headers() {
+        let request_certification = DefaultRequestCertification::new(vec!["host"], vec![]);
+        let request = create_request("https://ic0.app");
+        let mut http2_request = request.clone();
+        http2_request.headers = vec![
+            (":method".into(), "POST".into()),
+            (":path".into(), "/".into()),
+            ("Accept-Language".into(), "en".into()),
+            ("Accept-Language".into(), "en-US".into()),
+            (":authority".into(), "https://ic0.app".into()),
+        ];
+
+        assert_eq!(
+            request_hash(&http2_request, &request_certification).unwrap(),
+            request_hash(&request, &request_certification).unwrap()
+        );
+    }
+
+    #[test]
+    fn request_hash_with_limits_checks_limits() {
+        let request_certification = DefaultRequestCertification::new(vec!["host"], vec![]);
+        let request = create_request("https://ic0.app");
+
+        let result = request_hash_with_limits(
+            &request,
+            &request_certification,
+            &HashLimits::new().with_max_header_count(3),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            request_hash(&request, &request_certification).unwrap()
+        );
+
+        assert!(matches!(
+            request_hash_with_limits(
+                &request,
+                &request_certification,
+                &HashLimits::new().with_max_body_size(6),
+            ),
+            Err(HttpCertificationError::RequestBodyTooLarge {
+                size: 7,
+                max_size: 6
+            })
+        ));
+    }
+
+    #[test]
+    fn request_hash_with_header_name_pattern() {
+        let mut request = create_request("https://ic0.app");
+        request.headers.extend([
+            ("Cookie".into(), "session=abc".into()),
+            ("Accept".into(), "*/*".into()),
+        ]);
+
+        let result = request_hash(
+            &request,
+            &DefaultRequestCertification::new(vec!["glob:Accept-*"], vec![]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            request_hash(
+                &request,
+                &DefaultRequestCertification::new(vec!["Accept-Language"], vec![]),
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            request_hash(
+                &request,
+                &DefaultRequestCertification::new(vec!["glob:*"], vec![])
+            )
+            .unwrap(),
+            request_hash(
+                &request,
+                &DefaultRequestCertification::new(
+                    vec!["Accept", "Accept-Language", "Host"],
+                    vec![]
+                ),
+            )
+            .unwrap()
+        );
+    }
+
     fn create_request(uri: &str) -> HttpRequest {
         HttpRequest {
             url: uri.into(),
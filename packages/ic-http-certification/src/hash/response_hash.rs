@@ -1,9 +1,18 @@
-use super::Hash;
-use crate::{cel::DefaultResponseCertificationType, DefaultResponseCertification, HttpResponse};
-use ic_representation_independent_hash::{hash, representation_independent_hash, Value};
+use super::{Hash, HashLimits};
+use crate::{
+    cel::DefaultStatusCodeCertification, excluded_header_name_matches, header_name_matches,
+    DefaultResponseCertification, HttpCertificationError, HttpCertificationResult, HttpResponseRef,
+};
+use ic_representation_independent_hash::{
+    representation_independent_hash_with, DigestAlgorithm, Sha256Algorithm, Value,
+};
+
+/// The name of the header that carries the certificate, witness and expression path of a response.
+pub const CERTIFICATE_HEADER_NAME: &str = "IC-Certificate";
+
+/// The name of the header that carries the CEL expression used to certify a response.
+pub const CERTIFICATE_EXPRESSION_HEADER_NAME: &str = "IC-CertificateExpression";
 
-const CERTIFICATE_HEADER_NAME: &str = "IC-Certificate";
-const CERTIFICATE_EXPRESSION_HEADER_NAME: &str = "IC-CertificateExpression";
 const RESPONSE_STATUS_PSEUDO_HEADER_NAME: &str = ":ic-cert-status";
 
 /// Representation of response headers filtered by [filter_response_headers].
@@ -17,29 +26,19 @@ pub struct ResponseHeaders {
     pub certificate_expression: Option<String>,
 }
 
-/// Filters the headers of an [HttpResponse] according to a CEL expression defined by
-/// [DefaultResponseCertification].
-pub fn filter_response_headers(
-    response: &HttpResponse,
+/// Filters the headers of an [HttpResponse](crate::HttpResponse) according to a CEL expression
+/// defined by [DefaultResponseCertification].
+pub fn filter_response_headers<'a>(
+    response: impl Into<HttpResponseRef<'a>>,
     response_certification: &DefaultResponseCertification<'_>,
 ) -> ResponseHeaders {
-    let headers_filter: Box<dyn Fn(_) -> _> = match response_certification.get_type() {
-        DefaultResponseCertificationType::CertifiedResponseHeaders(headers_to_include) => {
-            Box::new(move |header_name: &String| {
-                headers_to_include.iter().any(|header_to_include| {
-                    header_to_include.eq_ignore_ascii_case(&header_name.to_string())
-                })
-            })
-        }
-        DefaultResponseCertificationType::ResponseHeaderExclusions(headers_to_exclude) => {
-            Box::new(move |header_name: &String| {
-                !headers_to_exclude.iter().any(|header_to_exclude| {
-                    header_to_exclude.eq_ignore_ascii_case(&header_name.to_string())
-                })
-            })
-        }
-    };
+    filter_headers(&response.into(), response_certification)
+}
 
+fn filter_headers(
+    response: &HttpResponseRef<'_>,
+    response_certification: &DefaultResponseCertification<'_>,
+) -> ResponseHeaders {
     let mut response_headers = ResponseHeaders {
         headers: vec![],
         certificate: None,
@@ -50,6 +49,7 @@ pub fn filter_response_headers(
         .headers
         .iter()
         .filter_map(|(header_name, header_value)| {
+            let (header_name, header_value) = (header_name.as_ref(), header_value.as_ref());
             let is_certificate_header = header_name
                 .to_string()
                 .eq_ignore_ascii_case(CERTIFICATE_HEADER_NAME);
@@ -66,7 +66,7 @@ pub fn filter_response_headers(
                 return None;
             }
 
-            if headers_filter(header_name) {
+            if is_certified_header(response_certification, header_name) {
                 return Some((
                     header_name.to_string().to_ascii_lowercase(),
                     String::from(header_value),
@@ -80,10 +80,62 @@ pub fn filter_response_headers(
     response_headers
 }
 
+fn is_certified_header(
+    response_certification: &DefaultResponseCertification<'_>,
+    header_name: &str,
+) -> bool {
+    let is_included = |patterns: &[&str]| {
+        patterns
+            .iter()
+            .any(|pattern| header_name_matches(pattern, header_name))
+    };
+    let is_excluded = |patterns: &[&str]| {
+        patterns
+            .iter()
+            .any(|pattern| excluded_header_name_matches(pattern, header_name))
+    };
+
+    match response_certification {
+        DefaultResponseCertification::CertifiedResponseHeaders(headers_to_include) => {
+            is_included(headers_to_include)
+        }
+        DefaultResponseCertification::ResponseHeaderExclusions(headers_to_exclude) => {
+            !is_excluded(headers_to_exclude)
+        }
+        DefaultResponseCertification::CertifiedResponseHeadersWithExclusions {
+            headers: headers_to_include,
+            exclusions: headers_to_exclude,
+        } => is_included(headers_to_include) && !is_excluded(headers_to_exclude),
+    }
+}
+
 /// Calculates the
 /// [Representation Independent Hash](https://internetcomputer.org/docs/current/references/ic-interface-spec/#hash-of-map)
 /// of [ResponseHeaders] that have been filtered with [filter_response_headers].
 pub fn response_headers_hash(status_code: &u64, response_headers: &ResponseHeaders) -> Hash {
+    response_headers_hash_with::<Sha256Algorithm>(status_code, response_headers)
+}
+
+/// Calculates the [response_headers_hash] of [ResponseHeaders] with the hash function `D`.
+pub fn response_headers_hash_with<D: DigestAlgorithm>(
+    status_code: &u64,
+    response_headers: &ResponseHeaders,
+) -> D::Output {
+    response_headers_hash_with_status_certification::<D>(
+        status_code,
+        response_headers,
+        &DefaultStatusCodeCertification::Exact,
+    )
+}
+
+/// Calculates the [response_headers_hash] of [ResponseHeaders] with the hash function `D`,
+/// certifying either the exact status code or only its [class](status_code_class), according to
+/// [DefaultStatusCodeCertification].
+pub fn response_headers_hash_with_status_certification<D: DigestAlgorithm>(
+    status_code: &u64,
+    response_headers: &ResponseHeaders,
+    status_certification: &DefaultStatusCodeCertification,
+) -> D::Output {
     let mut headers_to_verify: Vec<(String, Value)> = response_headers
         .headers
         .iter()
@@ -102,40 +154,203 @@ pub fn response_headers_hash(status_code: &u64, response_headers: &ResponseHeade
         ));
     }
 
-    headers_to_verify.push((
-        RESPONSE_STATUS_PSEUDO_HEADER_NAME.into(),
-        Value::Number(*status_code),
-    ));
+    let status = match status_certification {
+        DefaultStatusCodeCertification::Exact => Value::Number(*status_code),
+        DefaultStatusCodeCertification::Class => Value::String(status_code_class(*status_code)),
+    };
+    headers_to_verify.push((RESPONSE_STATUS_PSEUDO_HEADER_NAME.into(), status));
+
+    representation_independent_hash_with::<D>(&headers_to_verify)
+}
+
+/// Calculates the
+/// [Representation Independent Hash](https://internetcomputer.org/docs/current/references/ic-interface-spec/#hash-of-map)
+/// of the trailers of an [HttpResponse](crate::HttpResponse) that are certified according to a
+/// CEL expression defined by [DefaultResponseCertification].
+///
+/// Trailers are certified with the same rules as headers, so a trailer is certified if a header
+/// with the same name would be. Returns [None] if none of the trailers are certified, in which
+/// case the trailers do not contribute to the [response_hash].
+pub fn response_trailers_hash(
+    trailers: &[(impl AsRef<str>, impl AsRef<str>)],
+    response_certification: &DefaultResponseCertification<'_>,
+) -> Option<Hash> {
+    response_trailers_hash_with::<Sha256Algorithm>(trailers, response_certification)
+}
+
+/// Calculates the [response_trailers_hash] of the trailers of an
+/// [HttpResponse](crate::HttpResponse) with the hash function `D`.
+pub fn response_trailers_hash_with<D: DigestAlgorithm>(
+    trailers: &[(impl AsRef<str>, impl AsRef<str>)],
+    response_certification: &DefaultResponseCertification<'_>,
+) -> Option<D::Output> {
+    let trailers_to_verify: Vec<(String, Value)> = trailers
+        .iter()
+        .filter(|(trailer_name, _)| {
+            is_certified_header(response_certification, trailer_name.as_ref())
+        })
+        .map(|(trailer_name, trailer_value)| {
+            (
+                trailer_name.as_ref().to_ascii_lowercase(),
+                Value::String(trailer_value.as_ref().to_string()),
+            )
+        })
+        .collect();
+
+    if trailers_to_verify.is_empty() {
+        return None;
+    }
 
-    representation_independent_hash(&headers_to_verify)
+    Some(representation_independent_hash_with::<D>(
+        &trailers_to_verify,
+    ))
 }
 
 /// Calculates the
 /// [Representation Independent Hash](https://internetcomputer.org/docs/current/references/ic-interface-spec/#hash-of-map)
-/// of an [HttpResponse] according to a CEL expression defined by [DefaultResponseCertification].
+/// of an [HttpResponse](crate::HttpResponse) according to a CEL expression defined by
+/// [DefaultResponseCertification].
+///
+/// The response may be an [HttpResponse](crate::HttpResponse), a reference to one, or an
+/// [HttpResponseRef] that borrows its body and headers.
 ///
 /// An optional response body hash may be provided if this is known beforehand. If this override is not
 /// provided then the response body hash will be calculated by this function.
-pub fn response_hash(
-    response: &HttpResponse,
+///
+/// If the response body is [streamed](crate::Body::Streaming) and no override is provided, the
+/// precomputed hash of the streamed body is used.
+///
+/// Responses with a status code that does not permit a body, see [status_code_permits_body], are
+/// hashed with an empty body. An error is returned if such a response has a body, or if the
+/// provided response body hash is not the hash of an empty body.
+///
+/// The certified [trailers](crate::HttpResponse::trailers) of the response, if any, are hashed
+/// with [response_trailers_hash] and appended after the body hash. Responses without certified
+/// trailers are hashed from their status code, headers and body alone.
+pub fn response_hash<'a>(
+    response: impl Into<HttpResponseRef<'a>>,
+    response_certification: &DefaultResponseCertification,
+    response_body_hash: Option<Hash>,
+) -> HttpCertificationResult<Hash> {
+    let response = response.into();
+    let response_body_hash = response_body_hash.or_else(|| response.body.body_hash());
+
+    response_hash_with::<Sha256Algorithm>(response, response_certification, response_body_hash)
+}
+
+/// Calculates the [response_hash] of an [HttpResponse](crate::HttpResponse), certifying either the
+/// exact status code of the response or only its [class](status_code_class), according to
+/// [DefaultStatusCodeCertification].
+pub fn response_hash_with_status_certification<'a>(
+    response: impl Into<HttpResponseRef<'a>>,
+    response_certification: &DefaultResponseCertification,
+    status_certification: &DefaultStatusCodeCertification,
+    response_body_hash: Option<Hash>,
+) -> HttpCertificationResult<Hash> {
+    let response = response.into();
+    let response_body_hash = response_body_hash.or_else(|| response.body.body_hash());
+
+    hash_response::<Sha256Algorithm>(
+        response,
+        response_certification,
+        status_certification,
+        response_body_hash,
+    )
+}
+
+/// Calculates the [response_hash] of an [HttpResponse](crate::HttpResponse), after checking that
+/// the response is within the given [HashLimits].
+///
+/// Returns an error, without hashing the response, if it exceeds the limits, see
+/// [HashLimits::check_response].
+pub fn response_hash_with_limits<'a>(
+    response: impl Into<HttpResponseRef<'a>>,
     response_certification: &DefaultResponseCertification,
     response_body_hash: Option<Hash>,
-) -> Hash {
-    let response_body_hash = response_body_hash.unwrap_or(hash(&response.body));
+    limits: &HashLimits,
+) -> HttpCertificationResult<Hash> {
+    let response = response.into();
+    limits.check_response(&response)?;
+
+    response_hash(response, response_certification, response_body_hash)
+}
 
-    let filtered_headers = filter_response_headers(response, response_certification);
-    let concatenated_hashes = [
-        response_headers_hash(&response.status_code.into(), &filtered_headers),
+/// Calculates the [response_hash] of an [HttpResponse](crate::HttpResponse) with the hash
+/// function `D`.
+///
+/// The precomputed hash of a [streamed](crate::Body::Streaming) body is a SHA-256 hash, so a
+/// response body hash must be provided for a streamed body.
+pub fn response_hash_with<'a, D: DigestAlgorithm>(
+    response: impl Into<HttpResponseRef<'a>>,
+    response_certification: &DefaultResponseCertification,
+    response_body_hash: Option<D::Output>,
+) -> HttpCertificationResult<D::Output> {
+    hash_response::<D>(
+        response.into(),
+        response_certification,
+        &DefaultStatusCodeCertification::Exact,
         response_body_hash,
+    )
+}
+
+fn hash_response<D: DigestAlgorithm>(
+    response: HttpResponseRef<'_>,
+    response_certification: &DefaultResponseCertification,
+    status_certification: &DefaultStatusCodeCertification,
+    response_body_hash: Option<D::Output>,
+) -> HttpCertificationResult<D::Output> {
+    let response_body_hash = match (response_body_hash, response.body.bytes()) {
+        (Some(response_body_hash), _) => response_body_hash,
+        (None, Some(body)) => D::digest(body),
+        (None, None) => return Err(HttpCertificationError::MissingStreamingBodyHash),
+    };
+
+    if !status_code_permits_body(response.status_code) && response_body_hash != D::digest(&[]) {
+        return Err(HttpCertificationError::ResponseBodyNotPermitted {
+            status_code: response.status_code,
+        });
+    }
+
+    let filtered_headers = filter_headers(&response, response_certification);
+    let mut concatenated_hashes = [
+        response_headers_hash_with_status_certification::<D>(
+            &response.status_code.into(),
+            &filtered_headers,
+            status_certification,
+        )
+        .as_ref(),
+        response_body_hash.as_ref(),
     ]
     .concat();
 
-    hash(concatenated_hashes.as_slice())
+    let trailers = response.trailers.as_deref().unwrap_or_default();
+    if let Some(trailers_hash) = response_trailers_hash_with::<D>(trailers, response_certification)
+    {
+        concatenated_hashes.extend_from_slice(trailers_hash.as_ref());
+    }
+
+    Ok(D::digest(concatenated_hashes.as_slice()))
+}
+
+/// Returns whether a response with the given status code may have a body.
+///
+/// Informational (1xx), 204 (No Content), 205 (Reset Content) and 304 (Not Modified) responses
+/// must not have a body, see [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-status-codes).
+pub fn status_code_permits_body(status_code: u16) -> bool {
+    !matches!(status_code, 100..=199 | 204 | 205 | 304)
+}
+
+/// Returns the class of a status code, such as `2xx` for a `204` status code, as it is certified
+/// with [DefaultStatusCodeCertification::Class].
+pub fn status_code_class(status_code: u64) -> String {
+    format!("{}xx", status_code / 100)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Body, HttpResponse, StreamingBody};
+    use ic_representation_independent_hash::{hash, representation_independent_hash};
 
     const HELLO_WORLD_BODY: &[u8] = &[72, 101, 108, 108, 111, 32, 87, 111, 114, 108, 100, 33];
     const CERTIFICATE: &str = "certificate=:SGVsbG8gQ2VydGlmaWNhdGUh:,tree=:SGVsbG8gVHJlZSE=:";
@@ -213,7 +428,7 @@ mod tests {
             hex::decode("3393250e3cedc30408dcb7e8963898c3d7549b8a0b76496b82fdfeae99c2ac78")
                 .unwrap();
 
-        let result = response_hash(&response, &response_certification, None);
+        let result = response_hash(&response, &response_certification, None).unwrap();
 
         assert_eq!(result, expected_hash.as_slice());
     }
@@ -234,14 +449,16 @@ mod tests {
             ],
             body: HELLO_WORLD_BODY.into(),
             upgrade: None,
+            trailers: None,
         };
 
-        let result = response_hash(&response, &response_certification, None);
+        let result = response_hash(&response, &response_certification, None).unwrap();
         let result_without_excluded_headers = response_hash(
             &response_without_excluded_headers,
             &response_certification,
             None,
-        );
+        )
+        .unwrap();
 
         assert_eq!(result, result_without_excluded_headers);
     }
@@ -258,7 +475,7 @@ mod tests {
             hex::decode("a2ffb50ef8971650c2fb46c0a2788b7d5ac5a027d635175e8e06b419ce6c4cda")
                 .unwrap();
 
-        let result = response_hash(&response, &response_certification, None);
+        let result = response_hash(&response, &response_certification, None).unwrap();
 
         assert_eq!(result, expected_hash.as_slice());
     }
@@ -283,14 +500,16 @@ mod tests {
             ],
             body: HELLO_WORLD_BODY.into(),
             upgrade: None,
+            trailers: None,
         };
 
-        let result = response_hash(&response, &response_certification, None);
+        let result = response_hash(&response, &response_certification, None).unwrap();
         let result_without_excluded_headers = response_hash(
             &response_without_excluded_headers,
             &response_certification,
             None,
-        );
+        )
+        .unwrap();
 
         assert_eq!(result, result_without_excluded_headers);
     }
@@ -330,6 +549,7 @@ mod tests {
             ],
             body: HELLO_WORLD_BODY.into(),
             upgrade: None,
+            trailers: None,
         };
 
         let filtered_headers = filter_response_headers(&response, &response_certification);
@@ -383,6 +603,7 @@ mod tests {
             ],
             body: HELLO_WORLD_BODY.into(),
             upgrade: None,
+            trailers: None,
         };
 
         let filtered_headers = filter_response_headers(&response, &response_certification);
@@ -415,11 +636,391 @@ mod tests {
             hex::decode("1afc744a377cb8785d1078f53f9bbc9160d86b7a05f490e42c89366326eaef20")
                 .unwrap();
 
-        let result = response_hash(&response, &response_certification, Some(response_body_hash));
+        let result =
+            response_hash(&response, &response_certification, Some(response_body_hash)).unwrap();
 
         assert_eq!(result, expected_hash.as_slice());
     }
 
+    #[test]
+    fn status_code_permits_body_for_no_body_status_codes() {
+        for status_code in [100, 101, 103, 199, 204, 205, 304] {
+            assert!(!status_code_permits_body(status_code));
+        }
+
+        for status_code in [200, 201, 206, 301, 400, 404, 500] {
+            assert!(status_code_permits_body(status_code));
+        }
+    }
+
+    #[test]
+    fn response_hash_without_body_for_no_body_status_codes() {
+        let response_certification =
+            DefaultResponseCertification::certified_response_headers(vec!["Cache-Control"]);
+
+        for status_code in [101, 204, 304] {
+            let response = HttpResponse {
+                status_code,
+                body: vec![],
+                ..create_response(CERTIFIED_HEADERS_CEL_EXPRESSION)
+            };
+
+            let result = response_hash(&response, &response_certification, None).unwrap();
+            let result_with_empty_body_hash =
+                response_hash(&response, &response_certification, Some(hash(&[]))).unwrap();
+
+            assert_eq!(result, result_with_empty_body_hash);
+        }
+    }
+
+    #[test]
+    fn response_hash_with_body_for_no_body_status_codes() {
+        let response_certification =
+            DefaultResponseCertification::certified_response_headers(vec!["Cache-Control"]);
+
+        for status_code in [101, 204, 304] {
+            let response = HttpResponse {
+                status_code,
+                ..create_response(CERTIFIED_HEADERS_CEL_EXPRESSION)
+            };
+
+            let result = response_hash(&response, &response_certification, None);
+
+            assert!(matches!(
+                result,
+                Err(HttpCertificationError::ResponseBodyNotPermitted { status_code: code })
+                    if code == status_code
+            ));
+        }
+    }
+
+    #[test]
+    fn response_hash_with_body_hash_override_for_no_body_status_codes() {
+        let response_certification =
+            DefaultResponseCertification::certified_response_headers(vec!["Cache-Control"]);
+        let response = HttpResponse {
+            status_code: 304,
+            body: vec![],
+            ..create_response(CERTIFIED_HEADERS_CEL_EXPRESSION)
+        };
+
+        let result = response_hash(
+            &response,
+            &response_certification,
+            Some(hash(HELLO_WORLD_BODY)),
+        );
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::ResponseBodyNotPermitted { status_code: 304 })
+        ));
+    }
+
+    #[test]
+    fn response_hash_with_limits_checks_limits() {
+        let response_certification =
+            DefaultResponseCertification::certified_response_headers(vec!["Cache-Control"]);
+        let response = create_response(CERTIFIED_HEADERS_CEL_EXPRESSION);
+
+        let result = response_hash_with_limits(
+            &response,
+            &response_certification,
+            None,
+            &HashLimits::new().with_max_body_size(HELLO_WORLD_BODY.len()),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            response_hash(&response, &response_certification, None).unwrap()
+        );
+
+        assert!(matches!(
+            response_hash_with_limits(
+                &response,
+                &response_certification,
+                None,
+                &HashLimits::new().with_max_header_count(1),
+            ),
+            Err(HttpCertificationError::TooManyHeaders { .. })
+        ));
+    }
+
+    #[test]
+    fn response_hash_with_sha256_matches_response_hash() {
+        let response_certification =
+            DefaultResponseCertification::certified_response_headers(vec!["Cache-Control"]);
+        let response = create_response(CERTIFIED_HEADERS_CEL_EXPRESSION);
+
+        let result =
+            response_hash_with::<Sha256Algorithm>(&response, &response_certification, None)
+                .unwrap();
+
+        assert_eq!(
+            result,
+            response_hash(&response, &response_certification, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn response_hash_with_streaming_body() {
+        let response_certification =
+            DefaultResponseCertification::certified_response_headers(vec!["Cache-Control"]);
+        let response = create_response(CERTIFIED_HEADERS_CEL_EXPRESSION);
+        let streaming_response = HttpResponseRef {
+            body: Body::Streaming(StreamingBody {
+                first_chunk: HELLO_WORLD_BODY[..5].into(),
+                token: b"chunk-1".as_slice().into(),
+                body_hash: hash(HELLO_WORLD_BODY),
+            }),
+            ..HttpResponseRef::from(&response)
+        };
+
+        let result = response_hash(streaming_response.clone(), &response_certification, None);
+
+        assert_eq!(
+            result.unwrap(),
+            response_hash(&response, &response_certification, None).unwrap()
+        );
+        assert!(matches!(
+            response_hash_with::<Sha256Algorithm>(
+                streaming_response,
+                &response_certification,
+                None
+            ),
+            Err(HttpCertificationError::MissingStreamingBodyHash)
+        ));
+    }
+
+    #[test]
+    fn response_hash_without_certified_trailers() {
+        let response_certification =
+            DefaultResponseCertification::certified_response_headers(vec!["Cache-Control"]);
+        let response = create_response(CERTIFIED_HEADERS_CEL_EXPRESSION);
+        let response_with_trailers = HttpResponse {
+            trailers: Some(vec![("Server-Timing".into(), "total;dur=12".into())]),
+            ..response.clone()
+        };
+
+        assert_eq!(
+            response_trailers_hash(
+                response_with_trailers.trailers.as_deref().unwrap(),
+                &response_certification
+            ),
+            None
+        );
+        assert_eq!(
+            response_hash(&response_with_trailers, &response_certification, None).unwrap(),
+            response_hash(&response, &response_certification, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn response_hash_with_certified_trailers() {
+        let response_certification =
+            DefaultResponseCertification::certified_response_headers(vec!["Digest"]);
+        let response = create_response(CERTIFIED_HEADERS_CEL_EXPRESSION);
+        let trailers = vec![
+            ("Digest".to_string(), "sha-256=abc".to_string()),
+            ("Server-Timing".to_string(), "total;dur=12".to_string()),
+        ];
+        let response_with_trailers = HttpResponse {
+            trailers: Some(trailers.clone()),
+            ..response.clone()
+        };
+        let filtered_headers = filter_response_headers(&response, &response_certification);
+        let expected_hash = hash(
+            [
+                response_headers_hash(&200, &filtered_headers).as_slice(),
+                hash(HELLO_WORLD_BODY).as_slice(),
+                representation_independent_hash(&[(
+                    "digest".to_string(),
+                    Value::String("sha-256=abc".to_string()),
+                )])
+                .as_slice(),
+            ]
+            .concat()
+            .as_slice(),
+        );
+
+        let result = response_hash(&response_with_trailers, &response_certification, None).unwrap();
+
+        assert_eq!(result, expected_hash);
+        assert_ne!(
+            result,
+            response_hash(&response, &response_certification, None).unwrap()
+        );
+        assert_ne!(
+            result,
+            response_hash(
+                &HttpResponse {
+                    trailers: Some(vec![("Digest".into(), "sha-256=def".into())]),
+                    ..response
+                },
+                &response_certification,
+                None
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn response_hash_with_header_name_patterns() {
+        let mut response = create_response(CERTIFIED_HEADERS_CEL_EXPRESSION);
+        response
+            .headers
+            .push(("Set-Cookie".into(), "session=abc".into()));
+
+        assert_eq!(
+            response_hash(
+                &response,
+                &DefaultResponseCertification::certified_response_headers(vec![
+                    "glob:*-Encoding",
+                    "glob:Cache-*"
+                ]),
+                None
+            )
+            .unwrap(),
+            response_hash(
+                &response,
+                &DefaultResponseCertification::certified_response_headers(vec![
+                    "Accept-Encoding",
+                    "Cache-Control"
+                ]),
+                None
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            response_hash(
+                &response,
+                &DefaultResponseCertification::response_header_exclusions(vec!["glob:Content-*"]),
+                None
+            )
+            .unwrap(),
+            response_hash(
+                &response,
+                &DefaultResponseCertification::response_header_exclusions(vec![
+                    "Content-Security-Policy"
+                ]),
+                None
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            response_hash(
+                &response,
+                &DefaultResponseCertification::response_header_exclusions(vec!["glob:*"]),
+                None
+            )
+            .unwrap(),
+            response_hash(
+                &response,
+                &DefaultResponseCertification::certified_response_headers(vec![]),
+                None
+            )
+            .unwrap()
+        );
+        assert_ne!(
+            response_hash(
+                &response,
+                &DefaultResponseCertification::certified_response_headers(vec!["*-Encoding"]),
+                None
+            )
+            .unwrap(),
+            response_hash(
+                &response,
+                &DefaultResponseCertification::certified_response_headers(vec!["glob:*-Encoding"]),
+                None
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn response_hash_with_combined_header_certification() {
+        let mut response = create_response(CERTIFIED_HEADERS_CEL_EXPRESSION);
+        response
+            .headers
+            .push(("Set-Cookie".into(), "session=abc".into()));
+
+        let expected_hash = response_hash(
+            &response,
+            &DefaultResponseCertification::certified_response_headers(vec![
+                "Accept-Encoding",
+                "Content-Security-Policy",
+            ]),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            response_hash(
+                &response,
+                &DefaultResponseCertification::certified_response_headers_with_exclusions(
+                    vec!["glob:*"],
+                    vec!["glob:Cache-*"]
+                ),
+                None
+            )
+            .unwrap(),
+            expected_hash
+        );
+        assert_eq!(
+            response_hash(
+                &response,
+                &DefaultResponseCertification::certified_response_headers_with_exclusions(
+                    vec![
+                        "Accept-Encoding",
+                        "Cache-Control",
+                        "Content-Security-Policy"
+                    ],
+                    vec!["cache-control"]
+                ),
+                None
+            )
+            .unwrap(),
+            expected_hash
+        );
+    }
+
+    #[test]
+    fn status_code_class_of_status_code() {
+        assert_eq!(status_code_class(200), "2xx");
+        assert_eq!(status_code_class(204), "2xx");
+        assert_eq!(status_code_class(304), "3xx");
+        assert_eq!(status_code_class(404), "4xx");
+        assert_eq!(status_code_class(500), "5xx");
+    }
+
+    #[test]
+    fn response_headers_hash_with_status_code_class() {
+        let response = create_response(CERTIFIED_HEADERS_CEL_EXPRESSION);
+        let response_certification =
+            DefaultResponseCertification::certified_response_headers(vec!["Cache-Control"]);
+        let response_headers = filter_response_headers(&response, &response_certification);
+        let expected_hash = representation_independent_hash(&[
+            ("cache-control".into(), Value::String("no-cache".into())),
+            ("cache-control".into(), Value::String("no-store".into())),
+            (
+                CERTIFICATE_EXPRESSION_HEADER_NAME.to_ascii_lowercase(),
+                Value::String(remove_whitespace(CERTIFIED_HEADERS_CEL_EXPRESSION)),
+            ),
+            (
+                RESPONSE_STATUS_PSEUDO_HEADER_NAME.into(),
+                Value::String("2xx".into()),
+            ),
+        ]);
+
+        let result = response_headers_hash_with_status_certification::<Sha256Algorithm>(
+            &204,
+            &response_headers,
+            &DefaultStatusCodeCertification::Class,
+        );
+
+        assert_eq!(result, expected_hash);
+        assert_ne!(result, response_headers_hash(&204, &response_headers));
+    }
+
     fn create_response(cel_expression: &str) -> HttpResponse {
         HttpResponse {
             status_code: 200,
@@ -439,6 +1040,7 @@ mod tests {
             ],
             body: HELLO_WORLD_BODY.into(),
             upgrade: None,
+            trailers: None,
         }
     }
 
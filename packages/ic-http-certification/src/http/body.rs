@@ -0,0 +1,170 @@
+use crate::Hash;
+use std::{borrow::Cow, sync::Arc};
+
+/// The body of an [HttpResponseRef](crate::HttpResponseRef).
+///
+/// A body is either fully available as [bytes](Body::Bytes), or [streamed](Body::Streaming) to
+/// the client in chunks. A streamed body may be larger than the message size limit of a canister,
+/// so only its first chunk is held in memory, and it is certified with a precomputed hash of the
+/// full body instead of hashing its bytes.
+///
+/// A body that is fully available may also be [shared](Body::Shared), so that a large body can
+/// be held once in canister memory and referenced from every response that serves it, without
+/// borrowing it. Cloning a shared body does not copy its bytes.
+///
+/// ```
+/// use ic_http_certification::{response_hash, Body, DefaultResponseCertification, HttpResponseRef, StreamingBody};
+/// use ic_representation_independent_hash::hash;
+///
+/// let full_body: &[u8] = b"<h1>Hello World!</h1>";
+/// let streamed_response = HttpResponseRef {
+///     status_code: 200,
+///     headers: vec![],
+///     body: Body::Streaming(StreamingBody {
+///         first_chunk: full_body[..10].into(),
+///         token: b"next-chunk".as_slice().into(),
+///         body_hash: hash(full_body),
+///     }),
+///     upgrade: None,
+///     trailers: None,
+/// };
+/// let response = HttpResponseRef {
+///     body: Body::from(full_body),
+///     ..streamed_response.clone()
+/// };
+/// let certification = DefaultResponseCertification::certified_response_headers(vec![]);
+///
+/// assert_eq!(
+///     response_hash(streamed_response, &certification, None).unwrap(),
+///     response_hash(response, &certification, None).unwrap(),
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Body<'a> {
+    /// A body whose bytes are all available.
+    Bytes(Cow<'a, [u8]>),
+    /// A body whose bytes are all available and shared with other owners.
+    Shared(Arc<[u8]>),
+    /// A body that is streamed to the client in chunks, of which only the first is available.
+    Streaming(StreamingBody<'a>),
+}
+
+/// A [streamed](Body::Streaming) response body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StreamingBody<'a> {
+    /// The first chunk of the body, that is served with the response.
+    pub first_chunk: Cow<'a, [u8]>,
+    /// An opaque token that the canister's streaming callback uses to serve the next chunk.
+    pub token: Cow<'a, [u8]>,
+    /// The precomputed hash of the full body, that is certified instead of the hash of its
+    /// bytes. If the body is certified with
+    /// [fragmented](crate::DefaultBodyCertification::Fragmented) body certification, this must
+    /// be the [fragmented body hash](crate::fragmented_body_hash).
+    pub body_hash: Hash,
+}
+
+impl Body<'_> {
+    /// Returns the bytes of the body if they are all available, or [None] for a streamed body.
+    pub fn bytes(&self) -> Option<&[u8]> {
+        match self {
+            Body::Bytes(bytes) => Some(bytes),
+            Body::Shared(bytes) => Some(bytes),
+            Body::Streaming(_) => None,
+        }
+    }
+
+    /// Returns the bytes that are served with the response, which is the full body, or the
+    /// first chunk of a streamed body.
+    pub fn first_chunk(&self) -> &[u8] {
+        match self {
+            Body::Bytes(bytes) => bytes,
+            Body::Shared(bytes) => bytes,
+            Body::Streaming(streaming_body) => &streaming_body.first_chunk,
+        }
+    }
+
+    /// Returns the precomputed hash of a streamed body, or [None] if the body is fully available.
+    pub fn body_hash(&self) -> Option<Hash> {
+        match self {
+            Body::Bytes(_) | Body::Shared(_) => None,
+            Body::Streaming(streaming_body) => Some(streaming_body.body_hash),
+        }
+    }
+
+    /// Converts this body into the bytes that are served with the response, copying any
+    /// borrowed data.
+    pub fn into_owned(self) -> Vec<u8> {
+        match self {
+            Body::Bytes(bytes) => bytes.into_owned(),
+            Body::Shared(bytes) => bytes.to_vec(),
+            Body::Streaming(streaming_body) => streaming_body.first_chunk.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<&'a [u8]> for Body<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Body::Bytes(Cow::Borrowed(bytes))
+    }
+}
+
+impl From<Vec<u8>> for Body<'_> {
+    fn from(bytes: Vec<u8>) -> Self {
+        Body::Bytes(Cow::Owned(bytes))
+    }
+}
+
+impl From<Arc<[u8]>> for Body<'_> {
+    fn from(bytes: Arc<[u8]>) -> Self {
+        Body::Shared(bytes)
+    }
+}
+
+impl<'a> From<StreamingBody<'a>> for Body<'a> {
+    fn from(streaming_body: StreamingBody<'a>) -> Self {
+        Body::Streaming(streaming_body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{response_hash, DefaultResponseCertification, HttpResponseRef};
+
+    #[test]
+    fn shared_body_is_hashed_like_bytes() {
+        let bytes: &[u8] = b"<h1>Hello World!</h1>";
+        let shared_bytes: Arc<[u8]> = Arc::from(bytes);
+        let response = HttpResponseRef {
+            status_code: 200,
+            headers: vec![],
+            body: Body::from(bytes),
+            upgrade: None,
+            trailers: None,
+        };
+        let shared_response = HttpResponseRef {
+            body: Body::from(shared_bytes.clone()),
+            ..response.clone()
+        };
+        let certification = DefaultResponseCertification::certified_response_headers(vec![]);
+
+        assert_eq!(
+            response_hash(shared_response.clone(), &certification, None).unwrap(),
+            response_hash(response, &certification, None).unwrap()
+        );
+        assert_eq!(shared_response.into_owned().body, bytes);
+    }
+
+    #[test]
+    fn cloning_shared_body_does_not_copy() {
+        let shared_bytes: Arc<[u8]> = Arc::from(b"Hello World!".as_slice());
+        let body = Body::from(shared_bytes.clone());
+
+        match body.clone() {
+            Body::Shared(cloned_bytes) => assert!(Arc::ptr_eq(&cloned_bytes, &shared_bytes)),
+            _ => panic!("expected a shared body"),
+        }
+        assert_eq!(body.bytes(), Some(shared_bytes.as_ref()));
+        assert_eq!(body.body_hash(), None);
+    }
+}
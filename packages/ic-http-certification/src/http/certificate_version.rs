@@ -0,0 +1,143 @@
+use crate::{HttpResponse, CERTIFICATE_EXPRESSION_HEADER_NAME};
+use std::fmt::{Display, Formatter};
+
+/// The version of response verification that a canister should certify its response for.
+///
+/// HTTP Gateways send the highest version that they support in the `certificate_version` field of
+/// the canister's `http_request` endpoint. Gateways that do not send the field only support
+/// version 1. Use [negotiate](CertificateVersion::negotiate) to select the version to respond
+/// with, and
+/// [set_certificate_expression_header](CertificateVersion::set_certificate_expression_header) to
+/// add the `IC-CertificateExpression` header that the selected version requires.
+///
+/// ```
+/// use ic_http_certification::{CertificateVersion, DefaultCelBuilder, HttpRequest, HttpResponse};
+///
+/// let request = HttpRequest::builder()
+///     .with_method("GET")
+///     .with_url("/index.html")
+///     .build()
+///     .unwrap();
+/// let certificate_version = request.negotiate_certificate_version(Some(2));
+/// assert_eq!(certificate_version, CertificateVersion::V2);
+///
+/// let cel_expr = DefaultCelBuilder::response_only_certification().build();
+/// let mut response = HttpResponse::builder().ok().build().unwrap();
+/// certificate_version.set_certificate_expression_header(&mut response, &cel_expr);
+///
+/// assert_eq!(
+///     response.get_header_value("IC-CertificateExpression"),
+///     Some(cel_expr.to_string().as_str())
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CertificateVersion {
+    /// Version 1, which only certifies response bodies for exact paths, with the asset tree of
+    /// the canister. It does not use the `IC-CertificateExpression` header, and cannot be
+    /// produced with an [HttpCertificationTree](crate::HttpCertificationTree).
+    V1,
+    /// Version 2, which certifies requests and responses according to the
+    /// `IC-CertificateExpression` header, with an
+    /// [HttpCertificationTree](crate::HttpCertificationTree).
+    V2,
+}
+
+impl CertificateVersion {
+    /// Selects the version to respond with, given the `certificate_version` of the request.
+    ///
+    /// Requests without a `certificate_version`, or that request version 1, are answered with
+    /// [V1](CertificateVersion::V1). Requests for version 2 or higher are answered with
+    /// [V2](CertificateVersion::V2), the highest version that is supported by this crate.
+    pub fn negotiate(certificate_version: Option<u16>) -> Self {
+        match certificate_version {
+            Some(certificate_version) if certificate_version >= 2 => CertificateVersion::V2,
+            _ => CertificateVersion::V1,
+        }
+    }
+
+    /// Returns the version number, as used in the `certificate_version` field of the request
+    /// and the `version` field of the `IC-Certificate` header.
+    pub fn as_u16(&self) -> u16 {
+        match self {
+            CertificateVersion::V1 => 1,
+            CertificateVersion::V2 => 2,
+        }
+    }
+
+    /// Sets the `IC-CertificateExpression` header of the response to the given CEL expression for
+    /// [V2](CertificateVersion::V2), replacing any existing header, or removes the header for
+    /// [V1](CertificateVersion::V1), which does not use it.
+    ///
+    /// This must be done before the response is certified, since the header is part of the
+    /// certified response.
+    pub fn set_certificate_expression_header(
+        &self,
+        response: &mut HttpResponse,
+        cel_expr: &impl ToString,
+    ) {
+        match self {
+            CertificateVersion::V1 => {
+                response
+                    .headers_mut()
+                    .remove(CERTIFICATE_EXPRESSION_HEADER_NAME);
+            }
+            CertificateVersion::V2 => {
+                response
+                    .headers_mut()
+                    .set(CERTIFICATE_EXPRESSION_HEADER_NAME, cel_expr.to_string());
+            }
+        }
+    }
+}
+
+impl From<CertificateVersion> for u16 {
+    fn from(certificate_version: CertificateVersion) -> Self {
+        certificate_version.as_u16()
+    }
+}
+
+impl Display for CertificateVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_u16())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DefaultCelBuilder;
+    use rstest::*;
+
+    #[rstest]
+    #[case(None, CertificateVersion::V1)]
+    #[case(Some(0), CertificateVersion::V1)]
+    #[case(Some(1), CertificateVersion::V1)]
+    #[case(Some(2), CertificateVersion::V2)]
+    #[case(Some(3), CertificateVersion::V2)]
+    fn negotiate(#[case] certificate_version: Option<u16>, #[case] expected: CertificateVersion) {
+        assert_eq!(CertificateVersion::negotiate(certificate_version), expected);
+    }
+
+    #[rstest]
+    fn set_certificate_expression_header() {
+        let cel_expr = DefaultCelBuilder::skip_certification();
+        let mut response = HttpResponse::builder()
+            .ok()
+            .with_header("ic-certificateexpression", "outdated")
+            .with_automatic_content_length(false)
+            .build()
+            .unwrap();
+
+        CertificateVersion::V2.set_certificate_expression_header(&mut response, &cel_expr);
+        assert_eq!(
+            response.headers,
+            vec![(
+                CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+                cel_expr.to_string()
+            )]
+        );
+
+        CertificateVersion::V1.set_certificate_expression_header(&mut response, &cel_expr);
+        assert!(response.headers.is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    status_code_permits_body, HttpCertificationError, HttpCertificationResult, HttpResponse,
+};
+use flate2::{
+    write::{DeflateEncoder, GzEncoder},
+    Compression,
+};
+use std::io::Write;
+
+const BROTLI_BUFFER_SIZE: usize = 4096;
+const BROTLI_QUALITY: u32 = 11;
+const BROTLI_WINDOW_SIZE: u32 = 22;
+
+/// A content encoding that response bodies can be compressed with, see
+/// [HttpResponse::compress].
+///
+/// These are the content encodings that HTTP Gateways can decode, so they are also supported by
+/// [decoded body certification](crate::cel::DefaultResponseOnlyCelBuilder::with_decoded_body_certification).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentEncoding {
+    /// The `gzip` content encoding.
+    Gzip,
+    /// The `deflate` content encoding.
+    Deflate,
+    /// The `br` (Brotli) content encoding.
+    Brotli,
+}
+
+impl ContentEncoding {
+    /// Returns the value of the `Content-Encoding` header for this content encoding.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Deflate => "deflate",
+            ContentEncoding::Brotli => "br",
+        }
+    }
+
+    /// Compresses the given body with this content encoding.
+    pub fn encode(&self, body: &[u8]) -> HttpCertificationResult<Vec<u8>> {
+        let encoded_body = match self {
+            ContentEncoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+                encoder.write_all(body).and_then(|_| encoder.finish())
+            }
+            ContentEncoding::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+                encoder.write_all(body).and_then(|_| encoder.finish())
+            }
+            ContentEncoding::Brotli => {
+                let mut encoder = brotli::CompressorWriter::new(
+                    Vec::new(),
+                    BROTLI_BUFFER_SIZE,
+                    BROTLI_QUALITY,
+                    BROTLI_WINDOW_SIZE,
+                );
+                encoder.write_all(body).map(|_| encoder.into_inner())
+            }
+        };
+
+        encoded_body.map_err(|err| HttpCertificationError::ContentEncodingError(err.to_string()))
+    }
+}
+
+impl HttpResponse {
+    /// Compresses the body of the response with the given [ContentEncoding] and sets the
+    /// `Content-Encoding` header accordingly. A `Content-Length` header, if present, is updated
+    /// to the length of the compressed body.
+    ///
+    /// The returned response is ready to be certified, for example with
+    /// [HttpCertification::response_only](crate::HttpCertification::response_only), and served
+    /// as it is. Since the body is compressed before it is certified, the certified hash always
+    /// corresponds to the encoded bytes that are served. To certify the uncompressed body instead,
+    /// so that a single certification covers every encoding, use
+    /// [decoded body certification](crate::cel::DefaultResponseOnlyCelBuilder::with_decoded_body_certification).
+    ///
+    /// Returns an error if the response already has a `Content-Encoding` header, or if its status
+    /// code does not permit a body, since compressing an empty body still produces a non-empty
+    /// body.
+    ///
+    /// ```
+    /// use ic_http_certification::{ContentEncoding, HttpResponse};
+    ///
+    /// let response = HttpResponse::ok(
+    ///     b"Hello World!",
+    ///     vec![("Content-Type".into(), "text/plain".into())],
+    /// )
+    /// .compress(ContentEncoding::Gzip)
+    /// .unwrap();
+    ///
+    /// assert_eq!(response.get_header_value("Content-Encoding"), Some("gzip"));
+    /// ```
+    pub fn compress(mut self, encoding: ContentEncoding) -> HttpCertificationResult<HttpResponse> {
+        if let Some((header_name, _)) = self
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("Content-Encoding"))
+        {
+            return Err(HttpCertificationError::DuplicateHeader(header_name.clone()));
+        }
+
+        if !status_code_permits_body(self.status_code) {
+            return Err(HttpCertificationError::ResponseBodyNotPermitted {
+                status_code: self.status_code,
+            });
+        }
+
+        self.body = encoding.encode(&self.body)?;
+        for (_, value) in self
+            .headers
+            .iter_mut()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("Content-Length"))
+        {
+            *value = self.body.len().to_string();
+        }
+        self.headers
+            .push(("Content-Encoding".into(), encoding.as_str().into()));
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DefaultCelBuilder, DefaultResponseCertification, HttpCertification};
+    use flate2::read::{DeflateDecoder, GzDecoder};
+    use rstest::*;
+    use std::io::Read;
+
+    const BODY: &[u8] = b"Hello World! Hello World! Hello World!";
+
+    fn decode(encoding: ContentEncoding, body: &[u8]) -> Vec<u8> {
+        let mut decoded_body = vec![];
+        match encoding {
+            ContentEncoding::Gzip => GzDecoder::new(body).read_to_end(&mut decoded_body),
+            ContentEncoding::Deflate => DeflateDecoder::new(body).read_to_end(&mut decoded_body),
+            ContentEncoding::Brotli => {
+                brotli::Decompressor::new(body, BROTLI_BUFFER_SIZE).read_to_end(&mut decoded_body)
+            }
+        }
+        .unwrap();
+
+        decoded_body
+    }
+
+    #[rstest]
+    #[case(ContentEncoding::Gzip, "gzip")]
+    #[case(ContentEncoding::Deflate, "deflate")]
+    #[case(ContentEncoding::Brotli, "br")]
+    fn compress_response(#[case] encoding: ContentEncoding, #[case] expected_header_value: &str) {
+        let response = HttpResponse::builder()
+            .ok()
+            .with_header("Content-Type", "text/plain")
+            .with_body(BODY)
+            .build()
+            .unwrap()
+            .compress(encoding)
+            .unwrap();
+
+        assert_eq!(decode(encoding, &response.body), BODY);
+        assert_eq!(
+            response.headers,
+            vec![
+                ("Content-Type".into(), "text/plain".into()),
+                ("Content-Length".into(), response.body.len().to_string()),
+                ("Content-Encoding".into(), expected_header_value.into()),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(ContentEncoding::Gzip)]
+    #[case(ContentEncoding::Deflate)]
+    #[case(ContentEncoding::Brotli)]
+    fn compressed_response_certifies_encoded_body(#[case] encoding: ContentEncoding) {
+        let cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Content-Encoding"],
+            ))
+            .build();
+        let response = HttpResponse::ok(BODY, vec![]).compress(encoding).unwrap();
+
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+
+        assert_eq!(
+            certification,
+            HttpCertification::response_only(
+                &cel_expr,
+                &HttpResponse {
+                    status_code: 200,
+                    headers: vec![("Content-Encoding".into(), encoding.as_str().into())],
+                    body: encoding.encode(BODY).unwrap(),
+                    upgrade: None,
+                    trailers: None,
+                },
+                None
+            )
+            .unwrap()
+        );
+    }
+
+    #[rstest]
+    fn compress_encoded_response() {
+        let response = HttpResponse::ok(BODY, vec![("content-encoding".into(), "gzip".into())]);
+
+        assert!(matches!(
+            response.compress(ContentEncoding::Brotli),
+            Err(HttpCertificationError::DuplicateHeader(header_name)) if header_name == "content-encoding"
+        ));
+    }
+
+    #[rstest]
+    fn compress_response_without_body() {
+        let response = HttpResponse::builder()
+            .with_status_code(304u16)
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            response.compress(ContentEncoding::Gzip),
+            Err(HttpCertificationError::ResponseBodyNotPermitted { status_code: 304 })
+        ));
+    }
+}
@@ -1,2 +1,438 @@
 /// An HTTP header field, represented as a tuple of (name, value).
 pub type HeaderField = (String, String);
+
+/// Returns the value of the first header with the given name, compared case-insensitively.
+pub(super) fn get_header_value<'a>(headers: &'a [HeaderField], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+        .map(|(_, header_value)| header_value.as_str())
+}
+
+/// Returns the headers with lowercased names, sorted by name.
+///
+/// The sort is stable, so headers with the same name keep their relative order, which is
+/// significant in HTTP since their values may be combined into a single comma-separated list.
+/// This is used to compare and hash headers regardless of their order and name casing, see
+/// [HttpRequest::eq_semantic](crate::HttpRequest::eq_semantic) and
+/// [HttpResponse::eq_semantic](crate::HttpResponse::eq_semantic).
+pub(super) fn normalize_headers(headers: &[HeaderField]) -> Vec<(String, &str)> {
+    let mut normalized_headers: Vec<(String, &str)> = headers
+        .iter()
+        .map(|(header_name, header_value)| {
+            (header_name.to_ascii_lowercase(), header_value.as_str())
+        })
+        .collect();
+    normalized_headers.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    normalized_headers
+}
+
+/// Normalizes the HTTP/2 pseudo-headers of a request, such as `:authority` and `:path`, into
+/// their HTTP/1.1 equivalents.
+///
+/// Gateways that forward HTTP/2 traffic may pass pseudo-headers on to the canister, while the
+/// same request sent over HTTP/1.1 has a `Host` header instead. To hash both the same way,
+/// [request_hash](crate::request_hash) applies this normalization before it selects the certified
+/// request headers:
+/// - `:authority` is renamed to `host`, unless the request also has a `Host` header, in which
+///   case it is removed.
+/// - All other pseudo-headers, whose names start with `:`, are removed, since the method, path
+///   and query are already certified from the request itself.
+///
+/// The order of the remaining headers is preserved.
+///
+/// ```
+/// use ic_http_certification::normalize_pseudo_headers;
+///
+/// let headers = vec![
+///     (":method".to_string(), "GET".to_string()),
+///     (":authority".to_string(), "example.com".to_string()),
+///     (":path".to_string(), "/index.html".to_string()),
+///     ("accept".to_string(), "text/html".to_string()),
+/// ];
+///
+/// assert_eq!(
+///     normalize_pseudo_headers(&headers),
+///     vec![("host", "example.com"), ("accept", "text/html")]
+/// );
+/// ```
+pub fn normalize_pseudo_headers(
+    headers: &[(impl AsRef<str>, impl AsRef<str>)],
+) -> Vec<(&str, &str)> {
+    let has_host_header = headers
+        .iter()
+        .any(|(header_name, _)| header_name.as_ref().eq_ignore_ascii_case("host"));
+
+    headers
+        .iter()
+        .filter_map(|(header_name, header_value)| {
+            let (header_name, header_value) = (header_name.as_ref(), header_value.as_ref());
+
+            match header_name.strip_prefix(':') {
+                Some(pseudo_header_name)
+                    if pseudo_header_name.eq_ignore_ascii_case("authority") && !has_host_header =>
+                {
+                    Some(("host", header_value))
+                }
+                Some(_) => None,
+                None => Some((header_name, header_value)),
+            }
+        })
+        .collect()
+}
+
+/// The prefix that marks a header name in a
+/// [DefaultRequestCertification](crate::DefaultRequestCertification) or
+/// [DefaultResponseCertification](crate::DefaultResponseCertification) header list as a pattern.
+/// The prefix is compared case-insensitively, like header names. Header names cannot contain a
+/// `:`, so the prefix cannot be confused with a header name.
+pub const HEADER_NAME_PATTERN_PREFIX: &str = "glob:";
+
+/// Headers that carry credentials or session state. Patterns in lists of certified headers never
+/// match these headers, so they are only certified when they are named explicitly.
+const PATTERN_EXCLUDED_HEADER_NAMES: &[&str] = &[
+    "Authorization",
+    "Cookie",
+    "Proxy-Authorization",
+    "Set-Cookie",
+];
+
+/// Returns true if the given header name matches a header name or pattern from a list of
+/// certified headers of a [DefaultRequestCertification](crate::DefaultRequestCertification) or
+/// [DefaultResponseCertification](crate::DefaultResponseCertification).
+///
+/// Header names are compared case-insensitively. Names that start with
+/// [HEADER_NAME_PATTERN_PREFIX] are patterns, where a `*` in the rest of the name matches any
+/// sequence of characters, including an empty one, so that a family of headers such as
+/// `glob:X-App-*` can be certified without listing every name. A `*` in any other name is
+/// compared literally. Since the certified headers are hashed as a map, the hash does not depend
+/// on the order in which the headers matching a pattern appear.
+///
+/// Patterns never match the `Authorization`, `Cookie`, `Proxy-Authorization` and `Set-Cookie`
+/// headers, which must be named explicitly to be certified. Use
+/// [excluded_header_name_matches] for lists of excluded headers.
+///
+/// ```
+/// use ic_http_certification::header_name_matches;
+///
+/// assert!(header_name_matches("glob:X-App-*", "x-app-version"));
+/// assert!(header_name_matches("Content-Type", "content-type"));
+/// assert!(!header_name_matches("glob:X-App-*", "X-Request-Id"));
+/// assert!(!header_name_matches("X-App-*", "X-App-Version"));
+/// assert!(!header_name_matches("glob:*", "Authorization"));
+/// ```
+pub fn header_name_matches(pattern: &str, header_name: &str) -> bool {
+    let is_excluded_header = PATTERN_EXCLUDED_HEADER_NAMES
+        .iter()
+        .any(|excluded_header_name| excluded_header_name.eq_ignore_ascii_case(header_name));
+
+    match strip_pattern_prefix(pattern) {
+        Some(pattern) => {
+            !is_excluded_header && glob_matches(pattern.as_bytes(), header_name.as_bytes())
+        }
+        None => pattern.eq_ignore_ascii_case(header_name),
+    }
+}
+
+/// Returns true if the given header name matches a header name or pattern from a list of
+/// excluded headers of a [DefaultResponseCertification](crate::DefaultResponseCertification).
+///
+/// Names and patterns are matched in the same way as by [header_name_matches], except that
+/// patterns also match the `Authorization`, `Cookie`, `Proxy-Authorization` and `Set-Cookie`
+/// headers, so that excluding `glob:*` never certifies them.
+///
+/// ```
+/// use ic_http_certification::excluded_header_name_matches;
+///
+/// assert!(excluded_header_name_matches("glob:*", "Set-Cookie"));
+/// assert!(excluded_header_name_matches("glob:X-App-*", "x-app-version"));
+/// assert!(!excluded_header_name_matches("*", "Set-Cookie"));
+/// ```
+pub fn excluded_header_name_matches(pattern: &str, header_name: &str) -> bool {
+    match strip_pattern_prefix(pattern) {
+        Some(pattern) => glob_matches(pattern.as_bytes(), header_name.as_bytes()),
+        None => pattern.eq_ignore_ascii_case(header_name),
+    }
+}
+
+fn strip_pattern_prefix(pattern: &str) -> Option<&str> {
+    let prefix_len = HEADER_NAME_PATTERN_PREFIX.len();
+
+    pattern
+        .get(..prefix_len)
+        .filter(|prefix| prefix.eq_ignore_ascii_case(HEADER_NAME_PATTERN_PREFIX))
+        .map(|_| &pattern[prefix_len..])
+}
+
+pub(super) fn glob_matches(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut pattern_index, mut name_index) = (0, 0);
+    // the position of the last `*` in the pattern and the position in the name that it has
+    // matched up to, to backtrack to if the rest of the pattern does not match
+    let mut last_wildcard = None;
+
+    while name_index < name.len() {
+        match pattern.get(pattern_index) {
+            Some(b'*') => {
+                last_wildcard = Some((pattern_index, name_index));
+                pattern_index += 1;
+            }
+            Some(c) if c.eq_ignore_ascii_case(&name[name_index]) => {
+                pattern_index += 1;
+                name_index += 1;
+            }
+            _ => match last_wildcard {
+                Some((wildcard_index, wildcard_name_index)) => {
+                    last_wildcard = Some((wildcard_index, wildcard_name_index + 1));
+                    pattern_index = wildcard_index + 1;
+                    name_index = wildcard_name_index + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[pattern_index..].iter().all(|c| *c == b'*')
+}
+
+/// Mutable access to the headers of an [HttpRequest](crate::HttpRequest) or
+/// [HttpResponse](crate::HttpResponse), created with
+/// [HttpRequest::headers_mut](crate::HttpRequest::headers_mut) or
+/// [HttpResponse::headers_mut](crate::HttpResponse::headers_mut).
+///
+/// Header names are compared case-insensitively, so that, for example, setting `content-type`
+/// replaces an existing `Content-Type` header instead of adding a second one. Headers that differ
+/// only in casing are a common source of hash mismatches, since certified headers are matched
+/// case-insensitively while the headers that are served are not de-duplicated.
+#[derive(Debug)]
+pub struct HeadersMut<'a> {
+    headers: &'a mut Vec<HeaderField>,
+}
+
+impl<'a> HeadersMut<'a> {
+    pub(super) fn new(headers: &'a mut Vec<HeaderField>) -> Self {
+        Self { headers }
+    }
+
+    /// Returns the value of the first header with the given name, if any.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        get_header_value(self.headers, name)
+    }
+
+    /// Sets the value of the header with the given name.
+    ///
+    /// The first existing header with this name is replaced in place, using the casing of `name`,
+    /// and any other headers with this name are removed. If there is no such header, it is added
+    /// after the existing headers.
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        let value = value.into();
+
+        match self.position(&name) {
+            Some(index) => {
+                self.headers[index] = (name.clone(), value);
+                let mut current_index = 0;
+                self.headers.retain(|(header_name, _)| {
+                    let is_duplicate =
+                        current_index > index && header_name.eq_ignore_ascii_case(&name);
+                    current_index += 1;
+                    !is_duplicate
+                });
+            }
+            None => self.headers.push((name, value)),
+        }
+    }
+
+    /// Adds a header after the existing headers, even if a header with the same name exists,
+    /// as required by headers such as `Set-Cookie`.
+    pub fn append(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.headers.push((name.into(), value.into()));
+    }
+
+    /// Removes all headers with the given name and returns their values, in order.
+    pub fn remove(&mut self, name: &str) -> Vec<String> {
+        let mut removed = vec![];
+        self.headers.retain_mut(|(header_name, header_value)| {
+            let is_match = header_name.eq_ignore_ascii_case(name);
+            if is_match {
+                removed.push(std::mem::take(header_value));
+            }
+            !is_match
+        });
+
+        removed
+    }
+
+    /// Removes all but the first header of each name, keeping the first header's casing and
+    /// value.
+    pub fn dedup(&mut self) {
+        let mut seen_names: Vec<String> = vec![];
+        self.headers.retain(|(header_name, _)| {
+            let lowercase_name = header_name.to_ascii_lowercase();
+            if seen_names.contains(&lowercase_name) {
+                return false;
+            }
+
+            seen_names.push(lowercase_name);
+            true
+        });
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.headers
+            .iter()
+            .position(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    fn headers(headers: &[(&str, &str)]) -> Vec<HeaderField> {
+        headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn get_header_value_is_case_insensitive() {
+        let headers = headers(&[
+            ("Content-Type", "text/html"),
+            ("content-type", "text/plain"),
+        ]);
+
+        assert_eq!(
+            get_header_value(&headers, "CONTENT-TYPE"),
+            Some("text/html")
+        );
+        assert_eq!(get_header_value(&headers, "Content-Length"), None);
+    }
+
+    #[rstest]
+    #[case::authority(
+        vec![(":authority", "example.com"), ("accept", "*/*")],
+        vec![("host", "example.com"), ("accept", "*/*")]
+    )]
+    #[case::authority_and_host(
+        vec![(":authority", "example.com"), ("Host", "example.com")],
+        vec![("Host", "example.com")]
+    )]
+    #[case::request_pseudo_headers(
+        vec![
+            (":method", "GET"),
+            (":scheme", "https"),
+            (":path", "/index.html"),
+            (":protocol", "websocket"),
+            ("accept", "*/*"),
+        ],
+        vec![("accept", "*/*")]
+    )]
+    #[case::without_pseudo_headers(
+        vec![("Host", "example.com"), ("Accept", "*/*")],
+        vec![("Host", "example.com"), ("Accept", "*/*")]
+    )]
+    fn normalize_pseudo_headers_of_request(
+        #[case] headers: Vec<(&str, &str)>,
+        #[case] expected_headers: Vec<(&str, &str)>,
+    ) {
+        assert_eq!(normalize_pseudo_headers(&headers), expected_headers);
+    }
+
+    #[rstest]
+    #[case::exact_name("Content-Type", "content-type", true)]
+    #[case::different_name("Content-Type", "Content-Length", false)]
+    #[case::prefix("glob:X-App-*", "X-APP-VERSION", true)]
+    #[case::empty_wildcard("glob:X-App-*", "X-App-", true)]
+    #[case::prefix_mismatch("glob:X-App-*", "X-Request-Id", false)]
+    #[case::suffix("glob:*-Id", "X-Request-Id", true)]
+    #[case::infix("glob:X-*-Id", "X-Request-Trace-Id", true)]
+    #[case::infix_mismatch("glob:X-*-Id", "X-Request-Ids", false)]
+    #[case::repeated_wildcards("glob:*a*b*", "xaybzb", true)]
+    #[case::backtracking("glob:*ab", "aab", true)]
+    #[case::wildcard("glob:*", "ETag", true)]
+    #[case::wildcard_without_prefix("*", "ETag", false)]
+    #[case::literal_wildcard("X-App-*", "x-app-*", true)]
+    #[case::literal_wildcard_mismatch("X-App-*", "X-App-Version", false)]
+    #[case::prefix_without_pattern("glob:ETag", "etag", true)]
+    #[case::uppercase_prefix("GLOB:X-App-*", "X-App-Version", true)]
+    #[case::wildcard_cookie("glob:*", "Cookie", false)]
+    #[case::pattern_set_cookie("glob:Set-*", "set-cookie", false)]
+    #[case::pattern_authorization("glob:*Authorization", "Proxy-Authorization", false)]
+    #[case::exact_authorization("Authorization", "authorization", true)]
+    fn header_name_matches_patterns(
+        #[case] pattern: &str,
+        #[case] header_name: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(header_name_matches(pattern, header_name), expected);
+    }
+
+    #[rstest]
+    #[case::exact_name("Date", "date", true)]
+    #[case::prefix("glob:X-App-*", "x-app-debug", true)]
+    #[case::wildcard_without_prefix("*", "ETag", false)]
+    #[case::wildcard_set_cookie("glob:*", "Set-Cookie", true)]
+    #[case::wildcard_cookie("glob:*", "cookie", true)]
+    #[case::pattern_authorization("glob:*Authorization", "Proxy-Authorization", true)]
+    #[case::exact_set_cookie("Set-Cookie", "set-cookie", true)]
+    fn excluded_header_name_matches_patterns(
+        #[case] pattern: &str,
+        #[case] header_name: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(excluded_header_name_matches(pattern, header_name), expected);
+    }
+
+    #[test]
+    fn set_replaces_all_headers_with_the_same_name() {
+        let mut headers = headers(&[
+            ("Content-Type", "text/html"),
+            ("Cache-Control", "no-cache"),
+            ("CONTENT-TYPE", "text/plain"),
+        ]);
+
+        HeadersMut::new(&mut headers).set("content-type", "application/json");
+        HeadersMut::new(&mut headers).set("ETag", "\"1\"");
+
+        assert_eq!(
+            headers,
+            self::headers(&[
+                ("content-type", "application/json"),
+                ("Cache-Control", "no-cache"),
+                ("ETag", "\"1\""),
+            ])
+        );
+    }
+
+    #[test]
+    fn append_and_remove() {
+        let mut headers = headers(&[("Set-Cookie", "a=1")]);
+        let mut headers_mut = HeadersMut::new(&mut headers);
+
+        headers_mut.append("set-cookie", "b=2");
+        headers_mut.append("Cache-Control", "no-cache");
+
+        assert_eq!(headers_mut.remove("SET-COOKIE"), vec!["a=1", "b=2"]);
+        assert!(headers_mut.remove("Set-Cookie").is_empty());
+        assert_eq!(headers, self::headers(&[("Cache-Control", "no-cache")]));
+    }
+
+    #[test]
+    fn dedup_keeps_first_header_of_each_name() {
+        let mut headers = headers(&[
+            ("Content-Type", "text/html"),
+            ("Cache-Control", "no-cache"),
+            ("content-type", "text/plain"),
+        ]);
+
+        HeadersMut::new(&mut headers).dedup();
+
+        assert_eq!(
+            headers,
+            self::headers(&[("Content-Type", "text/html"), ("Cache-Control", "no-cache")])
+        );
+    }
+}
@@ -0,0 +1,743 @@
+use super::http_response::is_header_name_char;
+use crate::{
+    status_code_permits_body, HeaderField, HttpCertificationError, HttpCertificationResult,
+    HttpRequest, HttpResponse,
+};
+
+/// The default maximum size, in bytes, of the body of a request parsed with
+/// [parse_http1_request].
+pub const DEFAULT_MAX_HTTP1_REQUEST_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+/// Parses the raw bytes of a single, complete HTTP/1.1 request into an [HttpRequest], such as a
+/// request that is received by a reverse proxy in front of the HTTP Gateway.
+///
+/// The request is parsed according to [RFC 9112](https://www.rfc-editor.org/rfc/rfc9112):
+/// - Lines may be terminated with either CRLF or LF.
+/// - Obsolete line folding in header values is replaced with a single space.
+/// - Bytes outside of the ASCII range in the request target are percent-encoded, and malformed
+///   percent-encodings are rejected, so that the resulting URL can be parsed by
+///   [get_path](HttpRequest::get_path) and [get_query](HttpRequest::get_query).
+/// - The body is read according to the `Content-Length` header, or decoded if the
+///   `Transfer-Encoding` header ends with `chunked`. Requests with both headers, with
+///   conflicting `Content-Length` headers or with any bytes after the end of the request are
+///   rejected, since they would be interpreted differently by different parsers.
+///
+/// Requests with a body larger than `max_body_size` are rejected with
+/// [RequestBodyTooLarge](HttpCertificationError::RequestBodyTooLarge).
+pub fn parse_http1_request(
+    bytes: &[u8],
+    max_body_size: usize,
+) -> HttpCertificationResult<HttpRequest> {
+    let mut rest = bytes;
+    let mut request_line = None;
+    let mut headers: Vec<HeaderField> = Vec::new();
+    loop {
+        let (line, next) = split_line(rest)?;
+        rest = next;
+
+        match request_line {
+            // empty lines before the request line are ignored
+            None if line.is_empty() => continue,
+            None => request_line = Some(line),
+            Some(_) if line.is_empty() => break,
+            Some(_) if line.starts_with(b" ") || line.starts_with(b"\t") => {
+                let (_, header_value) = headers.last_mut().ok_or_else(|| {
+                    malformed("the first header line must not start with whitespace")
+                })?;
+                let folded_value = trim_whitespace(line);
+                if !folded_value.is_empty() {
+                    header_value.push(' ');
+                    header_value.push_str(&to_string(folded_value, "header value")?);
+                }
+            }
+            Some(_) => headers.push(parse_header(line)?),
+        }
+    }
+
+    let (method, url) = parse_request_line(request_line.unwrap_or_default())?;
+    let body = parse_body(&headers, rest, max_body_size)?;
+
+    Ok(HttpRequest {
+        method,
+        url,
+        headers,
+        body,
+    })
+}
+
+fn parse_request_line(line: &[u8]) -> HttpCertificationResult<(String, String)> {
+    let parts = line.split(|byte| *byte == b' ').collect::<Vec<_>>();
+    let [method, target, version] = parts.as_slice() else {
+        return Err(malformed(
+            "the request line must consist of a method, a request target and a version",
+        ));
+    };
+
+    if method.is_empty() || !method.iter().copied().all(is_header_name_char) {
+        return Err(malformed("the request method is not a valid token"));
+    }
+
+    if !matches!(*version, b"HTTP/1.1" | b"HTTP/1.0") {
+        return Err(malformed(
+            "the request version must be HTTP/1.1 or HTTP/1.0",
+        ));
+    }
+
+    Ok((
+        to_string(method, "request method")?,
+        normalize_request_target(target)?,
+    ))
+}
+
+/// Percent-encodes bytes outside of the ASCII range and checks that existing
+/// percent-encodings are well formed.
+fn normalize_request_target(target: &[u8]) -> HttpCertificationResult<String> {
+    if target.is_empty() {
+        return Err(malformed("the request target must not be empty"));
+    }
+
+    let mut normalized = String::with_capacity(target.len());
+    for (index, byte) in target.iter().enumerate() {
+        match byte {
+            b'%' => {
+                let is_valid_encoding = target
+                    .get(index + 1..index + 3)
+                    .is_some_and(|digits| digits.iter().all(u8::is_ascii_hexdigit));
+                if !is_valid_encoding {
+                    return Err(malformed(
+                        "the request target contains an invalid percent-encoding",
+                    ));
+                }
+
+                normalized.push('%');
+            }
+            0x21..=0x7e => normalized.push(char::from(*byte)),
+            0x80..=0xff => normalized.push_str(&format!("%{byte:02X}")),
+            _ => {
+                return Err(malformed(
+                    "the request target contains whitespace or control characters",
+                ))
+            }
+        }
+    }
+
+    Ok(normalized)
+}
+
+fn parse_header(line: &[u8]) -> HttpCertificationResult<HeaderField> {
+    let colon_index = line
+        .iter()
+        .position(|byte| *byte == b':')
+        .ok_or_else(|| malformed("a header line must contain a colon"))?;
+    let (header_name, header_value) = (&line[..colon_index], &line[colon_index + 1..]);
+
+    if header_name.is_empty() || !header_name.iter().copied().all(is_header_name_char) {
+        return Err(HttpCertificationError::InvalidHeaderName(
+            String::from_utf8_lossy(header_name).into_owned(),
+        ));
+    }
+
+    Ok((
+        to_string(header_name, "header name")?,
+        to_string(trim_whitespace(header_value), "header value")?,
+    ))
+}
+
+fn parse_body(
+    headers: &[HeaderField],
+    bytes: &[u8],
+    max_body_size: usize,
+) -> HttpCertificationResult<Vec<u8>> {
+    let transfer_encoding = headers
+        .iter()
+        .rev()
+        .find(|(name, _)| name.eq_ignore_ascii_case("Transfer-Encoding"))
+        .and_then(|(_, value)| value.rsplit(',').next())
+        .map(str::trim);
+    let content_length = parse_content_length(headers)?;
+
+    let body = match (transfer_encoding, content_length) {
+        (Some(_), Some(_)) => {
+            return Err(malformed(
+                "the request has both a Transfer-Encoding and a Content-Length header",
+            ))
+        }
+        (Some(coding), None) if coding.eq_ignore_ascii_case("chunked") => {
+            return decode_chunked_body(bytes, max_body_size)
+        }
+        (Some(_), None) => {
+            return Err(malformed(
+                "the final transfer coding of the request must be chunked",
+            ))
+        }
+        (None, Some(content_length)) => {
+            if content_length > max_body_size {
+                return Err(HttpCertificationError::RequestBodyTooLarge {
+                    size: content_length,
+                    max_size: max_body_size,
+                });
+            }
+            if bytes.len() < content_length {
+                return Err(malformed(
+                    "the request body is shorter than its Content-Length",
+                ));
+            }
+
+            bytes.split_at(content_length)
+        }
+        (None, None) => (&[][..], bytes),
+    };
+
+    match body {
+        (body, []) => Ok(body.to_vec()),
+        _ => Err(malformed("unexpected data after the end of the request")),
+    }
+}
+
+fn parse_content_length(headers: &[HeaderField]) -> HttpCertificationResult<Option<usize>> {
+    let mut content_length = None;
+    for value in headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("Content-Length"))
+        .flat_map(|(_, value)| value.split(','))
+        .map(str::trim)
+    {
+        let length = parse_digits(value.as_bytes(), 10)
+            .ok_or_else(|| malformed("the Content-Length header is not a valid length"))?;
+
+        if content_length.is_some_and(|content_length| content_length != length) {
+            return Err(malformed(
+                "the request has conflicting Content-Length headers",
+            ));
+        }
+        content_length = Some(length);
+    }
+
+    Ok(content_length)
+}
+
+fn decode_chunked_body(bytes: &[u8], max_body_size: usize) -> HttpCertificationResult<Vec<u8>> {
+    let mut body = Vec::new();
+    let mut rest = bytes;
+    loop {
+        let (line, next) = split_line(rest)?;
+        rest = next;
+
+        // chunk extensions are ignored
+        let chunk_size = line.split(|byte| *byte == b';').next().unwrap_or_default();
+        let chunk_size = parse_digits(trim_whitespace(chunk_size), 16)
+            .ok_or_else(|| malformed("a chunk size is not a valid hexadecimal number"))?;
+        if chunk_size == 0 {
+            break;
+        }
+
+        let size = body.len().saturating_add(chunk_size);
+        if size > max_body_size {
+            return Err(HttpCertificationError::RequestBodyTooLarge {
+                size,
+                max_size: max_body_size,
+            });
+        }
+        if rest.len() < chunk_size {
+            return Err(malformed("a chunk is shorter than its size"));
+        }
+
+        let (chunk, next) = rest.split_at(chunk_size);
+        body.extend_from_slice(chunk);
+
+        let (line, next) = split_line(next)?;
+        if !line.is_empty() {
+            return Err(malformed("a chunk is longer than its size"));
+        }
+        rest = next;
+    }
+
+    // trailer fields are not merged into the request headers
+    loop {
+        let (line, next) = split_line(rest)?;
+        rest = next;
+
+        if line.is_empty() {
+            break;
+        }
+    }
+
+    if !rest.is_empty() {
+        return Err(malformed("unexpected data after the end of the request"));
+    }
+
+    Ok(body)
+}
+
+/// Response headers that describe the framing of the message or the connection, rather than
+/// the response itself. They are replaced by [serialize_http1_response], since the framing of
+/// the serialized response is determined by its body.
+const FRAMING_RESPONSE_HEADER_NAMES: &[&str] = &[
+    "Connection",
+    "Content-Length",
+    "Keep-Alive",
+    "Proxy-Connection",
+    "Trailer",
+    "Transfer-Encoding",
+    "Upgrade",
+];
+
+/// Serializes an [HttpResponse] into the raw bytes of an HTTP/1.1 response, so that a proxy can
+/// forward a verified response directly to its client.
+///
+/// The response should only contain the headers that were verified, such as the headers of a
+/// verified response returned by the `ic-response-verification` crate, since all headers are
+/// serialized as they are, except for headers that describe the framing of the message, such as
+/// `Content-Length`, `Transfer-Encoding` and `Connection`. These are replaced by a
+/// `Content-Length` header that matches the body, unless the status code does not permit a body.
+/// The status line uses the standard [reason phrase](reason_phrase) of the status code, see
+/// [serialize_http1_response_with_reason_phrase] to use a different one.
+///
+/// Responses with an invalid status code, invalid header names, header values containing line
+/// breaks or a body that is not permitted by their status code are rejected, since they cannot
+/// be serialized without changing their meaning.
+pub fn serialize_http1_response(response: &HttpResponse) -> HttpCertificationResult<Vec<u8>> {
+    serialize_http1_response_with_reason_phrase(response, reason_phrase(response.status_code))
+}
+
+/// Serializes an [HttpResponse] in the same way as [serialize_http1_response], but with the given
+/// reason phrase in the status line.
+///
+/// Reason phrases are not part of an [HttpResponse], since the HTTP Gateway Protocol does not
+/// transmit them. They are never certified and verifiers only consider the numeric status code,
+/// so a proxy that forwards a verified response can use any reason phrase, or an empty one,
+/// without affecting verification. Clients should likewise ignore the reason phrase, as
+/// recommended by [RFC 9112](https://www.rfc-editor.org/rfc/rfc9112#name-status-line).
+///
+/// Reason phrases containing line breaks or other control characters, apart from horizontal
+/// tabs, are rejected.
+pub fn serialize_http1_response_with_reason_phrase(
+    response: &HttpResponse,
+    reason_phrase: &str,
+) -> HttpCertificationResult<Vec<u8>> {
+    if reason_phrase
+        .bytes()
+        .any(|byte| byte != b'\t' && byte.is_ascii_control())
+    {
+        return Err(malformed("the reason phrase contains a control character"));
+    }
+
+    if !(100..=599).contains(&response.status_code) {
+        return Err(HttpCertificationError::InvalidStatusCode(
+            response.status_code,
+        ));
+    }
+
+    let permits_body = status_code_permits_body(response.status_code);
+    if !permits_body && !response.body.is_empty() {
+        return Err(HttpCertificationError::ResponseBodyNotPermitted {
+            status_code: response.status_code,
+        });
+    }
+
+    let mut bytes = format!("HTTP/1.1 {} {}\r\n", response.status_code, reason_phrase).into_bytes();
+
+    for (header_name, header_value) in &response.headers {
+        if header_name.is_empty() || !header_name.bytes().all(is_header_name_char) {
+            return Err(HttpCertificationError::InvalidHeaderName(
+                header_name.clone(),
+            ));
+        }
+
+        if header_value
+            .bytes()
+            .any(|byte| matches!(byte, b'\r' | b'\n' | b'\0'))
+        {
+            return Err(malformed(&format!(
+                "the value of header {header_name} contains a line break"
+            )));
+        }
+
+        let is_framing_header = FRAMING_RESPONSE_HEADER_NAMES
+            .iter()
+            .any(|framing_header| framing_header.eq_ignore_ascii_case(header_name));
+        if !is_framing_header {
+            bytes.extend_from_slice(format!("{header_name}: {header_value}\r\n").as_bytes());
+        }
+    }
+
+    if permits_body {
+        bytes.extend_from_slice(format!("Content-Length: {}\r\n", response.body.len()).as_bytes());
+    }
+
+    bytes.extend_from_slice(b"\r\n");
+    bytes.extend_from_slice(&response.body);
+
+    Ok(bytes)
+}
+
+/// Returns the standard reason phrase of a status code, as defined by
+/// [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-status-codes), or an empty string
+/// for status codes without a standard reason phrase.
+pub fn reason_phrase(status_code: u16) -> &'static str {
+    match status_code {
+        100 => "Continue",
+        101 => "Switching Protocols",
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        203 => "Non-Authoritative Information",
+        204 => "No Content",
+        205 => "Reset Content",
+        206 => "Partial Content",
+        300 => "Multiple Choices",
+        301 => "Moved Permanently",
+        302 => "Found",
+        303 => "See Other",
+        304 => "Not Modified",
+        305 => "Use Proxy",
+        307 => "Temporary Redirect",
+        308 => "Permanent Redirect",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        402 => "Payment Required",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        406 => "Not Acceptable",
+        407 => "Proxy Authentication Required",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        410 => "Gone",
+        411 => "Length Required",
+        412 => "Precondition Failed",
+        413 => "Content Too Large",
+        414 => "URI Too Long",
+        415 => "Unsupported Media Type",
+        416 => "Range Not Satisfiable",
+        417 => "Expectation Failed",
+        421 => "Misdirected Request",
+        422 => "Unprocessable Content",
+        426 => "Upgrade Required",
+        428 => "Precondition Required",
+        429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        505 => "HTTP Version Not Supported",
+        _ => "",
+    }
+}
+
+/// Splits the bytes after the next LF, removing the LF and a preceding CR from the line.
+fn split_line(bytes: &[u8]) -> HttpCertificationResult<(&[u8], &[u8])> {
+    let line_end = bytes
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .ok_or_else(|| malformed("the request is incomplete"))?;
+    let line = &bytes[..line_end];
+
+    Ok((
+        line.strip_suffix(b"\r").unwrap_or(line),
+        &bytes[line_end + 1..],
+    ))
+}
+
+fn parse_digits(digits: &[u8], radix: u32) -> Option<usize> {
+    let digits = std::str::from_utf8(digits).ok()?;
+    if digits.is_empty() || !digits.chars().all(|digit| digit.is_digit(radix)) {
+        return None;
+    }
+
+    usize::from_str_radix(digits, radix).ok()
+}
+
+fn trim_whitespace(bytes: &[u8]) -> &[u8] {
+    let is_whitespace = |byte: &u8| *byte == b' ' || *byte == b'\t';
+    let start = bytes
+        .iter()
+        .position(|byte| !is_whitespace(byte))
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|byte| !is_whitespace(byte))
+        .map_or(start, |end| end + 1);
+
+    &bytes[start..end]
+}
+
+fn to_string(bytes: &[u8], name: &str) -> HttpCertificationResult<String> {
+    String::from_utf8(bytes.to_vec())
+        .map_err(|_| malformed(&format!("the {name} is not valid UTF-8")))
+}
+
+fn malformed(reason: &str) -> HttpCertificationError {
+    HttpCertificationError::MalformedHttp1Message(reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const MAX_BODY_SIZE: usize = 16;
+
+    #[rstest]
+    #[case::crlf(b"GET /index.html?lang=en HTTP/1.1\r\nHost: ic0.app\r\nAccept: */*\r\n\r\n")]
+    #[case::lf(b"GET /index.html?lang=en HTTP/1.1\nHost: ic0.app\nAccept: */*\n\n")]
+    #[case::leading_empty_line(
+        b"\r\nGET /index.html?lang=en HTTP/1.1\r\nHost: ic0.app\r\nAccept: */*\r\n\r\n"
+    )]
+    #[case::optional_whitespace(
+        b"GET /index.html?lang=en HTTP/1.1\r\nHost:ic0.app\r\nAccept: \t*/* \r\n\r\n"
+    )]
+    fn parse_request_without_body(#[case] bytes: &[u8]) {
+        let request = parse_http1_request(bytes, MAX_BODY_SIZE).unwrap();
+
+        assert_eq!(
+            request,
+            HttpRequest {
+                method: "GET".into(),
+                url: "/index.html?lang=en".into(),
+                headers: vec![
+                    ("Host".into(), "ic0.app".into()),
+                    ("Accept".into(), "*/*".into()),
+                ],
+                body: vec![],
+            }
+        );
+    }
+
+    #[rstest]
+    #[case::content_length(b"POST / HTTP/1.1\r\nContent-Length: 12\r\n\r\nHello World!")]
+    #[case::repeated_content_length(
+        b"POST / HTTP/1.1\r\nContent-Length: 12, 12\r\nContent-Length: 12\r\n\r\nHello World!"
+    )]
+    #[case::chunked(
+        b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n6;ext=1\r\nHello \r\n6\r\nWorld!\r\n0\r\nTrailer: 1\r\n\r\n"
+    )]
+    #[case::gzip_chunked(
+        b"POST / HTTP/1.1\r\nTransfer-Encoding: gzip, chunked\r\n\r\nc\r\nHello World!\r\n0\r\n\r\n"
+    )]
+    fn parse_request_with_body(#[case] bytes: &[u8]) {
+        let request = parse_http1_request(bytes, MAX_BODY_SIZE).unwrap();
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.body, b"Hello World!");
+    }
+
+    #[rstest]
+    fn parse_request_with_folded_header() {
+        let request = parse_http1_request(
+            b"GET / HTTP/1.1\r\nX-Folded: first\r\n  second\r\n\tthird\r\n\r\n",
+            MAX_BODY_SIZE,
+        )
+        .unwrap();
+
+        assert_eq!(
+            request.headers,
+            vec![("X-Folded".into(), "first second third".into())]
+        );
+    }
+
+    #[rstest]
+    fn parse_request_with_non_ascii_target() {
+        let request = parse_http1_request(
+            "GET /caf\u{e9}%20menu.html HTTP/1.1\r\n\r\n".as_bytes(),
+            MAX_BODY_SIZE,
+        )
+        .unwrap();
+
+        assert_eq!(request.url, "/caf%C3%A9%20menu.html");
+        assert_eq!(request.get_path().unwrap(), "/caf\u{e9} menu.html");
+    }
+
+    #[rstest]
+    #[case::incomplete_head(b"GET / HTTP/1.1\r\nHost: ic0.app\r\n")]
+    #[case::missing_version(b"GET /\r\n\r\n")]
+    #[case::unsupported_version(b"GET / HTTP/2\r\n\r\n")]
+    #[case::invalid_method(b"G(T / HTTP/1.1\r\n\r\n")]
+    #[case::invalid_percent_encoding(b"GET /%zz HTTP/1.1\r\n\r\n")]
+    #[case::truncated_percent_encoding(b"GET /%2 HTTP/1.1\r\n\r\n")]
+    #[case::leading_folded_header(b"GET / HTTP/1.1\r\n folded\r\n\r\n")]
+    #[case::missing_colon(b"GET / HTTP/1.1\r\nHost\r\n\r\n")]
+    #[case::content_length_and_chunked(
+        b"POST / HTTP/1.1\r\nContent-Length: 1\r\nTransfer-Encoding: chunked\r\n\r\n1\r\na\r\n0\r\n\r\n"
+    )]
+    #[case::conflicting_content_length(
+        b"POST / HTTP/1.1\r\nContent-Length: 1\r\nContent-Length: 2\r\n\r\nab"
+    )]
+    #[case::invalid_content_length(b"POST / HTTP/1.1\r\nContent-Length: +1\r\n\r\na")]
+    #[case::short_body(b"POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\na")]
+    #[case::trailing_data(b"POST / HTTP/1.1\r\nContent-Length: 1\r\n\r\nab")]
+    #[case::body_without_length(b"POST / HTTP/1.1\r\n\r\na")]
+    #[case::final_coding_not_chunked(
+        b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked, gzip\r\n\r\n"
+    )]
+    #[case::invalid_chunk_size(b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\nx\r\n\r\n")]
+    #[case::long_chunk(
+        b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n1\r\nab\r\n0\r\n\r\n"
+    )]
+    #[case::missing_last_chunk(b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n1\r\na\r\n")]
+    fn parse_malformed_request(#[case] bytes: &[u8]) {
+        let result = parse_http1_request(bytes, MAX_BODY_SIZE);
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::MalformedHttp1Message(_))
+        ));
+    }
+
+    #[rstest]
+    #[case::whitespace_before_colon(b"GET / HTTP/1.1\r\nHost : ic0.app\r\n\r\n")]
+    #[case::empty_name(b"GET / HTTP/1.1\r\n: ic0.app\r\n\r\n")]
+    fn parse_request_with_invalid_header_name(#[case] bytes: &[u8]) {
+        let result = parse_http1_request(bytes, MAX_BODY_SIZE);
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::InvalidHeaderName(_))
+        ));
+    }
+
+    #[rstest]
+    #[case::content_length(b"POST / HTTP/1.1\r\nContent-Length: 17\r\n\r\nHello World!Hello")]
+    #[case::chunked(
+        b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\nc\r\nHello World!\r\n5\r\nHello\r\n0\r\n\r\n"
+    )]
+    fn parse_request_with_oversized_body(#[case] bytes: &[u8]) {
+        let result = parse_http1_request(bytes, MAX_BODY_SIZE);
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::RequestBodyTooLarge {
+                size: 17,
+                max_size: MAX_BODY_SIZE
+            })
+        ));
+    }
+
+    #[rstest]
+    fn serialize_response() {
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![
+                ("Content-Type".into(), "text/plain".into()),
+                ("Cache-Control".into(), "no-cache".into()),
+                ("Content-Length".into(), "5".into()),
+                ("transfer-encoding".into(), "chunked".into()),
+                ("Connection".into(), "keep-alive".into()),
+            ],
+            body: b"Hello World!".to_vec(),
+            upgrade: None,
+            trailers: None,
+        };
+
+        let bytes = serialize_http1_response(&response).unwrap();
+
+        assert_eq!(
+            bytes,
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nCache-Control: no-cache\r\nContent-Length: 12\r\n\r\nHello World!"
+        );
+    }
+
+    #[rstest]
+    #[case::not_modified(304, "HTTP/1.1 304 Not Modified\r\nETag: \"abc\"\r\n\r\n")]
+    #[case::no_content(204, "HTTP/1.1 204 No Content\r\nETag: \"abc\"\r\n\r\n")]
+    #[case::non_standard(599, "HTTP/1.1 599 \r\nETag: \"abc\"\r\nContent-Length: 0\r\n\r\n")]
+    fn serialize_response_without_body(#[case] status_code: u16, #[case] expected: &str) {
+        let response = HttpResponse {
+            status_code,
+            headers: vec![
+                ("ETag".into(), "\"abc\"".into()),
+                ("Content-Length".into(), "12".into()),
+            ],
+            body: vec![],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let bytes = serialize_http1_response(&response).unwrap();
+
+        assert_eq!(bytes, expected.as_bytes());
+    }
+
+    #[rstest]
+    #[case::custom("Everything Is Fine", "HTTP/1.1 200 Everything Is Fine\r\n")]
+    #[case::empty("", "HTTP/1.1 200 \r\n")]
+    #[case::tab_and_obs_text("D\u{e9}j\u{e0}\tvu", "HTTP/1.1 200 D\u{e9}j\u{e0}\tvu\r\n")]
+    fn serialize_response_with_reason_phrase(#[case] reason_phrase: &str, #[case] expected: &str) {
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![],
+            body: vec![],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let bytes = serialize_http1_response_with_reason_phrase(&response, reason_phrase).unwrap();
+
+        assert_eq!(
+            bytes,
+            [expected.as_bytes(), b"Content-Length: 0\r\n\r\n"].concat()
+        );
+    }
+
+    #[rstest]
+    #[case("OK\r\nSet-Cookie: session=1234")]
+    #[case("OK\n")]
+    #[case("OK\0")]
+    fn serialize_response_with_invalid_reason_phrase(#[case] reason_phrase: &str) {
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![],
+            body: vec![],
+            upgrade: None,
+            trailers: None,
+        };
+
+        assert!(matches!(
+            serialize_http1_response_with_reason_phrase(&response, reason_phrase),
+            Err(HttpCertificationError::MalformedHttp1Message(_))
+        ));
+    }
+
+    #[rstest]
+    fn serialize_invalid_response() {
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![],
+            body: vec![],
+            upgrade: None,
+            trailers: None,
+        };
+
+        assert!(matches!(
+            serialize_http1_response(&HttpResponse {
+                status_code: 99,
+                ..response.clone()
+            }),
+            Err(HttpCertificationError::InvalidStatusCode(99))
+        ));
+        assert!(matches!(
+            serialize_http1_response(&HttpResponse {
+                headers: vec![("Bad Name".into(), "value".into())],
+                ..response.clone()
+            }),
+            Err(HttpCertificationError::InvalidHeaderName(_))
+        ));
+        assert!(matches!(
+            serialize_http1_response(&HttpResponse {
+                headers: vec![("Set-Cookie".into(), "a=b\r\nX-Injected: 1".into())],
+                ..response.clone()
+            }),
+            Err(HttpCertificationError::MalformedHttp1Message(_))
+        ));
+        assert!(matches!(
+            serialize_http1_response(&HttpResponse {
+                status_code: 304,
+                body: b"Hello World!".to_vec(),
+                ..response
+            }),
+            Err(HttpCertificationError::ResponseBodyNotPermitted { status_code: 304 })
+        ));
+    }
+}
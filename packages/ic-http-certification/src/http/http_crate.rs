@@ -0,0 +1,163 @@
+use crate::{HeaderField, HttpCertificationError, HttpRequest, HttpResponse};
+use http::header::HeaderMap;
+
+/// Converts an [http::Request] into an [HttpRequest].
+///
+/// The URL is taken from the request URI as-is, and header values that are not valid UTF-8 are
+/// converted lossily, since the HTTP Gateway Protocol only supports UTF-8 header values.
+impl From<http::Request<Vec<u8>>> for HttpRequest {
+    fn from(request: http::Request<Vec<u8>>) -> Self {
+        let (parts, body) = request.into_parts();
+
+        HttpRequest {
+            method: parts.method.to_string(),
+            url: parts.uri.to_string(),
+            headers: from_header_map(&parts.headers),
+            body,
+        }
+    }
+}
+
+/// Converts an [HttpRequest] into an [http::Request].
+///
+/// Returns an error if the method, URL or headers of the [HttpRequest] are not valid according to
+/// the `http` crate.
+impl TryFrom<HttpRequest> for http::Request<Vec<u8>> {
+    type Error = HttpCertificationError;
+
+    fn try_from(request: HttpRequest) -> Result<Self, Self::Error> {
+        let mut builder = http::Request::builder()
+            .method(request.method.as_str())
+            .uri(request.url.as_str());
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+
+        builder
+            .body(request.body)
+            .map_err(|err| HttpCertificationError::HttpCrateConversionError(err.to_string()))
+    }
+}
+
+/// Converts an [http::Response] into an [HttpResponse].
+///
+/// Header values that are not valid UTF-8 are converted lossily, since the HTTP Gateway Protocol
+/// only supports UTF-8 header values. The [upgrade](HttpResponse::upgrade) field is not set.
+impl From<http::Response<Vec<u8>>> for HttpResponse {
+    fn from(response: http::Response<Vec<u8>>) -> Self {
+        let (parts, body) = response.into_parts();
+
+        HttpResponse {
+            status_code: parts.status.as_u16(),
+            headers: from_header_map(&parts.headers),
+            body,
+            upgrade: None,
+            trailers: None,
+        }
+    }
+}
+
+/// Converts an [HttpResponse] into an [http::Response].
+///
+/// Returns an error if the status code or headers of the [HttpResponse] are not valid according
+/// to the `http` crate. The [upgrade](HttpResponse::upgrade) field has no equivalent in the `http`
+/// crate and is discarded.
+impl TryFrom<HttpResponse> for http::Response<Vec<u8>> {
+    type Error = HttpCertificationError;
+
+    fn try_from(response: HttpResponse) -> Result<Self, Self::Error> {
+        let mut builder = http::Response::builder().status(response.status_code);
+        for (name, value) in &response.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+
+        builder
+            .body(response.body)
+            .map_err(|err| HttpCertificationError::HttpCrateConversionError(err.to_string()))
+    }
+}
+
+fn from_header_map(headers: &HeaderMap) -> Vec<HeaderField> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_round_trip() {
+        let request = HttpRequest {
+            method: "POST".to_string(),
+            url: "/index.html?lang=en".to_string(),
+            headers: vec![
+                ("accept".to_string(), "text/html".to_string()),
+                ("x-custom".to_string(), "1".to_string()),
+                ("x-custom".to_string(), "2".to_string()),
+            ],
+            body: b"Hello World!".to_vec(),
+        };
+
+        let http_request = http::Request::try_from(request.clone()).unwrap();
+
+        assert_eq!(http_request.method(), http::Method::POST);
+        assert_eq!(http_request.uri(), "/index.html?lang=en");
+        assert_eq!(http_request.headers().get_all("x-custom").iter().count(), 2);
+        assert_eq!(HttpRequest::from(http_request), request);
+    }
+
+    #[test]
+    fn response_round_trip() {
+        let response = HttpResponse {
+            status_code: 404,
+            headers: vec![("content-type".to_string(), "text/plain".to_string())],
+            body: b"Not Found".to_vec(),
+            upgrade: None,
+            trailers: None,
+        };
+
+        let http_response = http::Response::try_from(response.clone()).unwrap();
+
+        assert_eq!(http_response.status(), http::StatusCode::NOT_FOUND);
+        assert_eq!(HttpResponse::from(http_response), response);
+    }
+
+    #[test]
+    fn invalid_request_fails() {
+        let request = HttpRequest {
+            method: "GET".to_string(),
+            url: "/".to_string(),
+            headers: vec![("invalid header".to_string(), "value".to_string())],
+            body: vec![],
+        };
+
+        assert!(matches!(
+            http::Request::try_from(request),
+            Err(HttpCertificationError::HttpCrateConversionError(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_response_fails() {
+        let response = HttpResponse {
+            status_code: 1000,
+            headers: vec![],
+            body: vec![],
+            upgrade: None,
+            trailers: None,
+        };
+
+        assert!(matches!(
+            http::Response::try_from(response),
+            Err(HttpCertificationError::HttpCrateConversionError(_))
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+use super::{http_request::parse_url, http_response::validate_response};
+use crate::{Body, HeaderField, HttpCertificationResult, HttpRequest, HttpResponse, RequestUrl};
+use std::borrow::Cow;
+
+/// A header of an [HttpRequestRef] or [HttpResponseRef], whose name and value may be borrowed.
+pub type HeaderFieldRef<'a> = (Cow<'a, str>, Cow<'a, str>);
+
+/// A borrowed representation of an [HttpRequest].
+///
+/// The method, URL, header names and values, and body may be borrowed, so requests can be
+/// hashed with [request_hash](crate::request_hash) and certified with
+/// [HttpCertification::full](crate::HttpCertification::full) without copying their bodies, for
+/// example by a gateway that verifies many responses. An [HttpRequest] can be borrowed as an
+/// [HttpRequestRef] with [From], so it can be passed anywhere an [HttpRequestRef] is accepted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpRequestRef<'a> {
+    /// HTTP request method.
+    pub method: Cow<'a, str>,
+    /// Request URL.
+    pub url: Cow<'a, str>,
+    /// HTTP request headers.
+    pub headers: Vec<HeaderFieldRef<'a>>,
+    /// Request body as an array of bytes.
+    pub body: Cow<'a, [u8]>,
+}
+
+impl HttpRequestRef<'_> {
+    /// Returns the [RequestUrl] of the request, in the same form as [HttpRequest::get_url].
+    pub fn get_url(&self) -> HttpCertificationResult<RequestUrl> {
+        RequestUrl::parse(&self.url)
+    }
+
+    /// Returns the query parameters of the request URL, if any, in the same form as
+    /// [HttpRequest::canonical_query].
+    pub fn canonical_query(&self) -> HttpCertificationResult<Option<String>> {
+        parse_url(&self.url).map(|uri| uri.query().map(|query| query.to_owned()))
+    }
+
+    /// Converts this request into an owned [HttpRequest], copying any borrowed data.
+    pub fn into_owned(self) -> HttpRequest {
+        HttpRequest {
+            method: self.method.into_owned(),
+            url: self.url.into_owned(),
+            headers: into_owned_headers(self.headers),
+            body: self.body.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<&'a HttpRequest> for HttpRequestRef<'a> {
+    fn from(request: &'a HttpRequest) -> Self {
+        HttpRequestRef {
+            method: Cow::Borrowed(&request.method),
+            url: Cow::Borrowed(&request.url),
+            headers: borrow_headers(&request.headers),
+            body: Cow::Borrowed(&request.body),
+        }
+    }
+}
+
+impl From<HttpRequest> for HttpRequestRef<'_> {
+    fn from(request: HttpRequest) -> Self {
+        HttpRequestRef {
+            method: Cow::Owned(request.method),
+            url: Cow::Owned(request.url),
+            headers: own_headers(request.headers),
+            body: Cow::Owned(request.body),
+        }
+    }
+}
+
+/// A borrowed representation of an [HttpResponse].
+///
+/// The header names and values, and body may be borrowed, so responses can be hashed with
+/// [response_hash](crate::response_hash) and certified with
+/// [HttpCertification](crate::HttpCertification) without copying their bodies. An
+/// [HttpResponse] can be borrowed as an [HttpResponseRef] with [From], so it can be passed
+/// anywhere an [HttpResponseRef] is accepted.
+///
+/// The body may also be [streamed](Body::Streaming), so that responses that are larger than the
+/// message size limit can be certified with a precomputed body hash, without materializing the
+/// full body, or [shared](Body::Shared), so that an `HttpResponseRef<'static>` can reference a
+/// large body that is also held elsewhere, such as by an asset store, without copying it.
+/// [HttpResponse] itself always holds its body as bytes, so that its Candid interface is
+/// unchanged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpResponseRef<'a> {
+    /// HTTP response status code.
+    pub status_code: u16,
+    /// HTTP response headers.
+    pub headers: Vec<HeaderFieldRef<'a>>,
+    /// Response body, either as an array of bytes, shared bytes or a streamed body.
+    pub body: Body<'a>,
+    /// Whether the request should be upgraded to an update call.
+    pub upgrade: Option<bool>,
+    /// HTTP response trailers, if any.
+    pub trailers: Option<Vec<HeaderFieldRef<'a>>>,
+}
+
+impl HttpResponseRef<'_> {
+    /// Checks that the response is well-formed, in the same way as [HttpResponse::validate].
+    pub fn validate(&self) -> HttpCertificationResult {
+        validate_response(self.status_code, &self.headers)
+    }
+
+    /// Returns true if this response upgrades the request to an update call, in the same way as
+    /// [HttpResponse::is_upgrade].
+    pub fn is_upgrade(&self) -> bool {
+        self.upgrade == Some(true)
+    }
+
+    /// Converts this response into an owned [HttpResponse], copying any borrowed data.
+    ///
+    /// The body of a response with a [streamed](Body::Streaming) body is its first chunk.
+    pub fn into_owned(self) -> HttpResponse {
+        HttpResponse {
+            status_code: self.status_code,
+            headers: into_owned_headers(self.headers),
+            body: self.body.into_owned(),
+            upgrade: self.upgrade,
+            trailers: self.trailers.map(into_owned_headers),
+        }
+    }
+}
+
+impl<'a> From<&'a HttpResponse> for HttpResponseRef<'a> {
+    fn from(response: &'a HttpResponse) -> Self {
+        HttpResponseRef {
+            status_code: response.status_code,
+            headers: borrow_headers(&response.headers),
+            body: Body::from(response.body.as_slice()),
+            upgrade: response.upgrade,
+            trailers: response.trailers.as_deref().map(borrow_headers),
+        }
+    }
+}
+
+impl From<HttpResponse> for HttpResponseRef<'_> {
+    fn from(response: HttpResponse) -> Self {
+        HttpResponseRef {
+            status_code: response.status_code,
+            headers: own_headers(response.headers),
+            body: Body::from(response.body),
+            upgrade: response.upgrade,
+            trailers: response.trailers.map(own_headers),
+        }
+    }
+}
+
+fn borrow_headers(headers: &[HeaderField]) -> Vec<HeaderFieldRef<'_>> {
+    headers
+        .iter()
+        .map(|(name, value)| (Cow::Borrowed(name.as_str()), Cow::Borrowed(value.as_str())))
+        .collect()
+}
+
+fn own_headers<'a>(headers: Vec<HeaderField>) -> Vec<HeaderFieldRef<'a>> {
+    headers
+        .into_iter()
+        .map(|(name, value)| (Cow::Owned(name), Cow::Owned(value)))
+        .collect()
+}
+
+fn into_owned_headers(headers: Vec<HeaderFieldRef<'_>>) -> Vec<HeaderField> {
+    headers
+        .into_iter()
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        request_hash, response_hash, DefaultCelBuilder, DefaultResponseCertification,
+        HttpCertification, HttpCertificationError,
+    };
+
+    fn request() -> HttpRequest {
+        HttpRequest {
+            method: "GET".to_string(),
+            url: "/index.html?lang=en".to_string(),
+            headers: vec![("Accept".to_string(), "text/html".to_string())],
+            body: b"Hello World!".to_vec(),
+        }
+    }
+
+    fn response() -> HttpResponse {
+        HttpResponse {
+            status_code: 200,
+            headers: vec![("Content-Type".to_string(), "text/html".to_string())],
+            body: b"<h1>Hello World!</h1>".to_vec(),
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    #[test]
+    fn borrowed_and_owned_hashes_match() {
+        let cel_expr = DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept"])
+            .with_request_query_parameters(vec!["lang"])
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Content-Type"],
+            ))
+            .build();
+        let request = request();
+        let response = response();
+        let body = response.body.clone();
+
+        let borrowed_request = HttpRequestRef {
+            method: Cow::Borrowed("GET"),
+            url: Cow::Borrowed("/index.html?lang=en"),
+            headers: vec![(Cow::Borrowed("Accept"), Cow::Borrowed("text/html"))],
+            body: Cow::Borrowed(b"Hello World!"),
+        };
+        let borrowed_response = HttpResponseRef {
+            status_code: 200,
+            headers: vec![(Cow::Borrowed("Content-Type"), Cow::Borrowed("text/html"))],
+            body: Body::from(body.as_slice()),
+            upgrade: None,
+            trailers: None,
+        };
+
+        assert_eq!(
+            request_hash(borrowed_request.clone(), &cel_expr.request).unwrap(),
+            request_hash(&request, &cel_expr.request).unwrap()
+        );
+        assert_eq!(
+            response_hash(borrowed_response.clone(), &cel_expr.response, None).unwrap(),
+            response_hash(&response, &cel_expr.response, None).unwrap()
+        );
+        assert_eq!(
+            HttpCertification::full(&cel_expr, borrowed_request, borrowed_response, None).unwrap(),
+            HttpCertification::full(&cel_expr, &request, &response, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn borrowing_does_not_copy_bodies() {
+        let request = request();
+        let response = response();
+
+        let borrowed_request = HttpRequestRef::from(&request);
+        let borrowed_response = HttpResponseRef::from(&response);
+
+        assert!(std::ptr::eq(
+            borrowed_request.body.as_ptr(),
+            request.body.as_ptr()
+        ));
+        assert!(std::ptr::eq(
+            borrowed_response.body.first_chunk().as_ptr(),
+            response.body.as_ptr()
+        ));
+    }
+
+    #[test]
+    fn into_owned_round_trip() {
+        assert_eq!(HttpRequestRef::from(&request()).into_owned(), request());
+        assert_eq!(HttpRequestRef::from(request()).into_owned(), request());
+        assert_eq!(HttpResponseRef::from(&response()).into_owned(), response());
+        assert_eq!(HttpResponseRef::from(response()).into_owned(), response());
+    }
+
+    #[test]
+    fn validate_matches_owned_response() {
+        let mut response = response();
+        response
+            .headers
+            .push(("Content-Type".to_string(), "text/plain".to_string()));
+
+        assert!(matches!(
+            HttpResponseRef::from(&response).validate(),
+            Err(HttpCertificationError::DuplicateHeader(_))
+        ));
+        assert!(matches!(
+            response.validate(),
+            Err(HttpCertificationError::DuplicateHeader(_))
+        ));
+    }
+}
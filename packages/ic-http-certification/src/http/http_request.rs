@@ -1,10 +1,26 @@
-use crate::{HeaderField, HttpCertificationError, HttpCertificationResult};
-use candid::{CandidType, Deserialize};
+use super::header_field::{get_header_value, normalize_headers};
+use crate::{
+    CertificateVersion, HeaderField, HeadersMut, HttpCertificationError, HttpCertificationResult,
+    Method, RequestUrl,
+};
 use http::Uri;
+use std::hash::{Hash, Hasher};
 
 /// A Candid-encodable representation of an HTTP request.
 /// This struct is used by canisters that implement the HTTP interface required by the HTTP Gateway Protocol.
-#[derive(Clone, Debug, CandidType, Deserialize, PartialEq, Eq)]
+///
+/// It implements `CandidType` and `Deserialize` if the `candid` feature is enabled, which it is by
+/// default, so it can be used directly in a canister's `http_request` endpoint.
+/// It also implements `Serialize` if the `serde` feature is enabled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "candid", derive(candid::CandidType))]
+#[cfg_attr(any(feature = "candid", feature = "serde"), derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, rename = "Request", rename_all = "camelCase")
+)]
 pub struct HttpRequest {
     /// HTTP request method.
     pub method: String,
@@ -13,33 +29,309 @@ pub struct HttpRequest {
     /// HTTP request headers.
     pub headers: Vec<HeaderField>,
     /// Request body as an array of bytes.
+    #[cfg_attr(feature = "ts", ts(type = "Uint8Array | ArrayBuffer"))]
     pub body: Vec<u8>,
 }
 
 impl HttpRequest {
+    /// Creates a new [HttpRequestBuilder] for constructing an [HttpRequest].
+    pub fn builder() -> HttpRequestBuilder {
+        HttpRequestBuilder::default()
+    }
+
+    /// Returns the value of the first header with the given name, compared case-insensitively.
+    pub fn get_header_value(&self, name: &str) -> Option<&str> {
+        get_header_value(&self.headers, name)
+    }
+
+    /// Returns true if this request is semantically equal to `other`.
+    ///
+    /// Unlike `==`, header order and header name casing are ignored, so that requests that only
+    /// differ in how a client or proxy serialized their headers are considered equal. Headers
+    /// with the same name must still appear in the same relative order, and the method, url and
+    /// body must be equal byte for byte. This does not affect certification, which hashes
+    /// requests as described by [request_hash](crate::request_hash).
+    pub fn eq_semantic(&self, other: &HttpRequest) -> bool {
+        self.method == other.method
+            && self.url == other.url
+            && self.body == other.body
+            && normalize_headers(&self.headers) == normalize_headers(&other.headers)
+    }
+
+    /// Feeds this request into the given [Hasher], consistently with
+    /// [eq_semantic](HttpRequest::eq_semantic), so that it can be used to compute cache keys.
+    pub fn hash_semantic<H: Hasher>(&self, state: &mut H) {
+        self.method.hash(state);
+        self.url.hash(state);
+        normalize_headers(&self.headers).hash(state);
+        self.body.hash(state);
+    }
+
+    /// Returns [HeadersMut] for modifying the headers of the request, matching header names
+    /// case-insensitively.
+    pub fn headers_mut(&mut self) -> HeadersMut<'_> {
+        HeadersMut::new(&mut self.headers)
+    }
+
+    /// Returns the request [Method], uppercased so that, for example, `get` and `GET` are the
+    /// same method.
+    pub fn get_method(&self) -> Method {
+        Method::from(self.method.as_str())
+    }
+
+    /// Returns the [CertificateVersion] that the response to this request should be certified
+    /// for, given the `certificate_version` field of the canister's `http_request` endpoint.
+    ///
+    /// The `certificate_version` field is not part of [HttpRequest], so it must be passed
+    /// separately. See [CertificateVersion::negotiate].
+    pub fn negotiate_certificate_version(
+        &self,
+        certificate_version: Option<u16>,
+    ) -> CertificateVersion {
+        CertificateVersion::negotiate(certificate_version)
+    }
+
+    /// Returns the [RequestUrl] of the request, split into its normalized path and query.
+    ///
+    /// Unlike [get_path](HttpRequest::get_path), the path of a [RequestUrl] has `.` and `..`
+    /// segments removed and keeps encoded slashes, in the same way as the
+    /// [canonical_path](HttpRequest::canonical_path).
+    pub fn get_url(&self) -> HttpCertificationResult<RequestUrl> {
+        RequestUrl::parse(&self.url)
+    }
+
     /// Returns the path of the request URL, without domain, query parameters or fragments.
+    ///
+    /// The request URL may be in origin form, such as `/index.html`, or in absolute form, such as
+    /// `https://example.com/index.html`. The authority of absolute form URLs is ignored, so both
+    /// forms of the same URL have the same path.
     pub fn get_path(&self) -> HttpCertificationResult<String> {
-        let uri = self
-            .url
-            .parse::<Uri>()
-            .map_err(|_| HttpCertificationError::MalformedUrl(self.url.clone()))?;
+        let uri = self.parse_url()?;
 
         let decoded_path = urlencoding::decode(uri.path()).map(|path| path.into_owned())?;
         Ok(decoded_path)
     }
 
     /// Returns the query parameters of the request URL, if any, as a string.
+    ///
+    /// As with [get_path](HttpRequest::get_path), the authority of absolute form URLs is ignored.
     pub fn get_query(&self) -> HttpCertificationResult<Option<String>> {
-        self.url
-            .parse::<Uri>()
+        self.parse_url()
             .map(|uri| uri.query().map(|uri| uri.to_owned()))
-            .map_err(|_| HttpCertificationError::MalformedUrl(self.url.clone()))
+    }
+
+    /// Returns the path of the request URL, normalized in the same way as when the
+    /// `expr_path` of a certification is derived from the request URL.
+    ///
+    /// This is the [canonical_path](RequestUrl::canonical_path) of the request's
+    /// [RequestUrl], so `.` and `..` segments are removed, the path is decoded except for encoded
+    /// slashes, and empty segments are removed. For example `/assets/../js//app.js` becomes
+    /// `/js/app.js`. A trailing slash is preserved.
+    ///
+    /// The response verifier derives the request path in the same way, so routing and certifying
+    /// requests based on this path guarantees that a canister serves the same certification that
+    /// a verifier will look up for the request.
+    pub fn canonical_path(&self) -> HttpCertificationResult<String> {
+        Ok(self.get_url()?.canonical_path())
+    }
+
+    /// Returns the query parameters of the request URL, if any, in the same form that they are
+    /// included in the [request hash](crate::request_hash), before certified query parameters
+    /// are selected.
+    ///
+    /// The query is not decoded and the fragment of the URL is not included.
+    pub fn canonical_query(&self) -> HttpCertificationResult<Option<String>> {
+        self.get_query()
+    }
+
+    /// Returns the query parameters of the request URL as percent-decoded name and value pairs,
+    /// in the order that they appear in the URL.
+    ///
+    /// Parameters are split in the same way as when certified query parameters are selected for
+    /// the [request hash](crate::request_hash), so a parameter without a value, such as `flag` in
+    /// `?flag&lang=en`, has an empty value and empty parameters are skipped. Only percent-encoding
+    /// is decoded, a `+` is not decoded as a space.
+    pub fn get_query_params(&self) -> HttpCertificationResult<Vec<(String, String)>> {
+        let Some(query) = self.get_query()? else {
+            return Ok(vec![]);
+        };
+
+        query
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .map(|param| {
+                let (name, value) = param.split_once('=').unwrap_or((param, ""));
+
+                Ok((
+                    urlencoding::decode(name)?.into_owned(),
+                    urlencoding::decode(value)?.into_owned(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Returns the percent-decoded value of the first query parameter with the given name, if
+    /// any.
+    ///
+    /// Names are compared case-insensitively before decoding, in the same way as certified query
+    /// parameters are selected for the [request hash](crate::request_hash), so serving a request
+    /// based on this value relies on the same parameter that is certified.
+    pub fn get_query_param(&self, name: &str) -> HttpCertificationResult<Option<String>> {
+        let Some(query) = self.get_query()? else {
+            return Ok(None);
+        };
+
+        query
+            .split('&')
+            .filter_map(|param| param.split_once('=').or(Some((param, ""))))
+            .find(|(param_name, _)| param_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| Ok(urlencoding::decode(value)?.into_owned()))
+            .transpose()
+    }
+
+    /// Returns the host of the request in lowercase, without port or user information.
+    ///
+    /// The host is taken from the request URL if it is in absolute form, such as
+    /// `https://example.com/index.html`, and otherwise from the `Host` header.
+    /// Returns `None` if neither of them is present.
+    pub fn get_host(&self) -> HttpCertificationResult<Option<String>> {
+        if let Some(host) = self.parse_url()?.host() {
+            return Ok(Some(host.to_ascii_lowercase()));
+        }
+
+        self.get_header_value("Host")
+            .map(|value| {
+                value
+                    .parse::<Uri>()
+                    .ok()
+                    .and_then(|uri| uri.host().map(|host| host.to_ascii_lowercase()))
+                    .ok_or_else(|| HttpCertificationError::MalformedHost(value.to_string()))
+            })
+            .transpose()
+    }
+
+    fn parse_url(&self) -> HttpCertificationResult<Uri> {
+        parse_url(&self.url)
+    }
+}
+
+pub(super) fn parse_url(url: &str) -> HttpCertificationResult<Uri> {
+    url.parse::<Uri>()
+        .map_err(|_| HttpCertificationError::MalformedUrl(url.to_string()))
+}
+
+/// A builder for [HttpRequest], created with [HttpRequest::builder].
+///
+/// The method and URL are required, while the headers and body default to being empty.
+///
+/// ```
+/// use ic_http_certification::HttpRequest;
+///
+/// let request = HttpRequest::builder()
+///     .with_method("POST")
+///     .with_url("/api/users")
+///     .with_header("Content-Type", "application/json")
+///     .with_body(br#"{"name":"Alice"}"#.to_vec())
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(request.method, "POST");
+/// assert_eq!(request.get_path().unwrap(), "/api/users");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HttpRequestBuilder {
+    method: Option<String>,
+    url: Option<String>,
+    headers: Vec<HeaderField>,
+    body: Vec<u8>,
+}
+
+impl HttpRequestBuilder {
+    /// Sets the HTTP request method, such as `GET`, `POST` or [Method::Post].
+    pub fn with_method(mut self, method: impl Into<String>) -> Self {
+        self.method = Some(method.into());
+
+        self
+    }
+
+    /// Sets the request URL, in origin form, such as `/index.html`, or in absolute form, such as
+    /// `https://example.com/index.html`.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+
+        self
+    }
+
+    /// Sets the HTTP request headers, replacing any headers that were previously added.
+    pub fn with_headers(mut self, headers: Vec<HeaderField>) -> Self {
+        self.headers = headers;
+
+        self
+    }
+
+    /// Adds an HTTP request header.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+
+        self
+    }
+
+    /// Sets the request body.
+    pub fn with_body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+
+        self
+    }
+
+    /// Builds the [HttpRequest].
+    ///
+    /// Returns an error if the method or URL were not set, if the method is empty, or if the URL
+    /// cannot be parsed.
+    pub fn build(self) -> HttpCertificationResult<HttpRequest> {
+        let method = self
+            .method
+            .filter(|method| !method.is_empty())
+            .ok_or_else(|| HttpCertificationError::MissingRequestField("method".into()))?;
+        let url = self
+            .url
+            .ok_or_else(|| HttpCertificationError::MissingRequestField("url".into()))?;
+
+        let request = HttpRequest {
+            method,
+            url,
+            headers: self.headers,
+            body: self.body,
+        };
+        request.parse_url()?;
+
+        Ok(request)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::HttpCertificationPath;
+    use rstest::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn request_serde_round_trip() {
+        let request = HttpRequest::builder()
+            .with_method("POST")
+            .with_url("/api/items?lang=en")
+            .with_header("Content-Type", "application/json")
+            .with_body(br#"{"name":"item"}"#.to_vec())
+            .build()
+            .unwrap();
+
+        let serialized = serde_cbor::to_vec(&request).unwrap();
+
+        assert_eq!(
+            serde_cbor::from_slice::<HttpRequest>(&serialized).unwrap(),
+            request
+        );
+    }
 
     #[test]
     fn request_get_uri() {
@@ -110,4 +402,246 @@ mod tests {
             assert_eq!(query.unwrap_or_default(), *expected_query);
         }
     }
+
+    #[rstest]
+    #[case("https://canister.com/path?foo=bar#baz", "/path", Some("foo=bar"))]
+    #[case("http://canister.com:8080/path", "/path", None)]
+    #[case("HTTPS://user@Canister.com/a%20file.txt", "/a file.txt", None)]
+    #[case("https://canister.com?foo=bar", "/", Some("foo=bar"))]
+    #[case("/path?foo=bar", "/path", Some("foo=bar"))]
+    fn request_get_path_and_query_of_absolute_and_origin_form(
+        #[case] url: &str,
+        #[case] expected_path: &str,
+        #[case] expected_query: Option<&str>,
+    ) {
+        let req = create_request(url, vec![]);
+
+        assert_eq!(req.get_path().unwrap(), expected_path);
+        assert_eq!(req.get_query().unwrap().as_deref(), expected_query);
+    }
+
+    #[rstest]
+    #[rstest]
+    #[case("/path", vec![])]
+    #[case("/path?", vec![])]
+    #[case("/path?lang=en&q=hello%20world", vec![("lang", "en"), ("q", "hello world")])]
+    #[case("/path?flag&&lang=", vec![("flag", ""), ("lang", "")])]
+    #[case("/path?b=2&a=1&b=3", vec![("b", "2"), ("a", "1"), ("b", "3")])]
+    #[case("/path?%6Eame=a%3Db#fragment", vec![("name", "a=b")])]
+    #[case("/path?q=a+b", vec![("q", "a+b")])]
+    fn request_get_query_params(#[case] url: &str, #[case] expected: Vec<(&str, &str)>) {
+        let req = create_request(url, vec![]);
+        let expected: Vec<(String, String)> = expected
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        assert_eq!(req.get_query_params().unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("/path", "lang", None)]
+    #[case("/path?lang=en", "lang", Some("en"))]
+    #[case("/path?LANG=en", "lang", Some("en"))]
+    #[case("/path?lang=en&lang=de", "lang", Some("en"))]
+    #[case("/path?flag", "flag", Some(""))]
+    #[case("/path?q=hello%20world", "q", Some("hello world"))]
+    #[case("/path?language=en", "lang", None)]
+    fn request_get_query_param(
+        #[case] url: &str,
+        #[case] name: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let req = create_request(url, vec![]);
+
+        assert_eq!(req.get_query_param(name).unwrap().as_deref(), expected);
+    }
+
+    #[rstest]
+    fn request_get_query_param_with_invalid_utf8() {
+        let req = create_request("/path?q=%FF", vec![]);
+
+        assert!(matches!(
+            req.get_query_param("q"),
+            Err(HttpCertificationError::Utf8ConversionError(_))
+        ));
+        assert!(req.get_query_params().is_err());
+    }
+
+    #[rstest]
+    #[case("https://Canister.com/path", vec![], Some("canister.com"))]
+    #[case("https://user@canister.com:8080/path", vec![], Some("canister.com"))]
+    #[case("https://canister.com/path", vec![("Host", "other.com")], Some("canister.com"))]
+    #[case("/path", vec![("host", "Canister.com:443")], Some("canister.com"))]
+    #[case("/path", vec![("Host", "https://canister.com")], Some("canister.com"))]
+    #[case("/path", vec![], None)]
+    fn request_get_host(
+        #[case] url: &str,
+        #[case] headers: Vec<(&str, &str)>,
+        #[case] expected_host: Option<&str>,
+    ) {
+        let req = create_request(url, headers);
+
+        assert_eq!(req.get_host().unwrap().as_deref(), expected_host);
+    }
+
+    #[rstest]
+    fn request_get_host_with_malformed_host_header() {
+        let req = create_request("/path", vec![("Host", "not a host")]);
+
+        assert!(matches!(
+            req.get_host(),
+            Err(HttpCertificationError::MalformedHost(host)) if host == "not a host"
+        ));
+    }
+
+    #[rstest]
+    #[case("/", "/")]
+    #[case("https://canister.com", "/")]
+    #[case("//", "/")]
+    #[case("/assets//app.js", "/assets/app.js")]
+    #[case("/assets/", "/assets/")]
+    #[case("/assets//", "/assets/")]
+    #[case("https://canister.com/%61ssets/app.js?foo=bar", "/assets/app.js")]
+    #[case("/assets/../index.html", "/index.html")]
+    #[case("/assets/./js/../app.js", "/assets/app.js")]
+    #[case("/assets/%2E%2E/index.html", "/assets/../index.html")]
+    #[case("/assets%2Fjs/app.js", "/assets%2Fjs/app.js")]
+    #[case("/assets/js/..", "/assets/")]
+    fn request_canonical_path(#[case] url: &str, #[case] expected_path: &str) {
+        let req = create_request(url, vec![]);
+
+        assert_eq!(req.canonical_path().unwrap(), expected_path);
+    }
+
+    #[rstest]
+    fn request_canonical_path_matches_expr_path() {
+        let req = create_request("/assets//js/app.js", vec![]);
+
+        assert_eq!(
+            HttpCertificationPath::exact(req.canonical_path().unwrap()).to_expr_path(),
+            HttpCertificationPath::exact(req.get_url().unwrap().path()).to_expr_path()
+        );
+    }
+
+    #[rstest]
+    #[case("/path", None)]
+    #[case(
+        "/path?q=hello%20world&name=foo#section",
+        Some("q=hello%20world&name=foo")
+    )]
+    fn request_canonical_query(#[case] url: &str, #[case] expected_query: Option<&str>) {
+        let req = create_request(url, vec![]);
+
+        assert_eq!(req.canonical_query().unwrap().as_deref(), expected_query);
+    }
+
+    fn create_request(url: &str, headers: Vec<(&str, &str)>) -> HttpRequest {
+        HttpRequest {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            headers: headers
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: vec![],
+        }
+    }
+
+    #[rstest]
+    fn request_builder() {
+        let request = HttpRequest::builder()
+            .with_method("POST")
+            .with_url("https://canister.com/api?page=1")
+            .with_headers(vec![("Accept".into(), "application/json".into())])
+            .with_header("Content-Type", "text/plain")
+            .with_body(b"Hello World!".to_vec())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            request,
+            HttpRequest {
+                method: "POST".into(),
+                url: "https://canister.com/api?page=1".into(),
+                headers: vec![
+                    ("Accept".into(), "application/json".into()),
+                    ("Content-Type".into(), "text/plain".into()),
+                ],
+                body: b"Hello World!".to_vec(),
+            }
+        );
+    }
+
+    #[rstest]
+    fn request_builder_defaults() {
+        let request = HttpRequest::builder()
+            .with_method("GET")
+            .with_url("/")
+            .build()
+            .unwrap();
+
+        assert_eq!(request, create_request("/", vec![]));
+    }
+
+    #[rstest]
+    #[case::missing_method(HttpRequest::builder().with_url("/"), "method")]
+    #[case::empty_method(HttpRequest::builder().with_method("").with_url("/"), "method")]
+    #[case::missing_url(HttpRequest::builder().with_method("GET"), "url")]
+    fn request_builder_missing_field(
+        #[case] builder: HttpRequestBuilder,
+        #[case] expected_field: &str,
+    ) {
+        assert!(matches!(
+            builder.build(),
+            Err(HttpCertificationError::MissingRequestField(field)) if field == expected_field
+        ));
+    }
+
+    #[rstest]
+    fn request_builder_malformed_url() {
+        let result = HttpRequest::builder()
+            .with_method("GET")
+            .with_url("/a file.txt")
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::MalformedUrl(url)) if url == "/a file.txt"
+        ));
+    }
+
+    fn semantic_hash(request: &HttpRequest) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        request.hash_semantic(&mut hasher);
+
+        hasher.finish()
+    }
+
+    #[rstest]
+    #[case::same_headers(vec![("Accept", "*/*"), ("Host", "ic0.app")])]
+    #[case::reordered_headers(vec![("Host", "ic0.app"), ("Accept", "*/*")])]
+    #[case::different_name_casing(vec![("accept", "*/*"), ("HOST", "ic0.app")])]
+    fn request_eq_semantic(#[case] headers: Vec<(&str, &str)>) {
+        let request = create_request("/", vec![("Accept", "*/*"), ("Host", "ic0.app")]);
+        let other = create_request("/", headers);
+
+        assert!(request.eq_semantic(&other));
+        assert_eq!(semantic_hash(&request), semantic_hash(&other));
+    }
+
+    #[rstest]
+    #[case::different_url(create_request("/index.html", vec![("Accept", "*/*")]))]
+    #[case::different_value_casing(create_request("/", vec![("Accept", "TEXT/HTML")]))]
+    #[case::missing_header(create_request("/", vec![]))]
+    #[case::reordered_repeated_headers(create_request(
+        "/",
+        vec![("Accept", "text/plain"), ("Accept", "text/html")]
+    ))]
+    fn request_not_eq_semantic(#[case] other: HttpRequest) {
+        let request = create_request("/", vec![("Accept", "text/html"), ("Accept", "text/plain")]);
+
+        assert!(!request.eq_semantic(&other));
+        assert_ne!(semantic_hash(&request), semantic_hash(&other));
+    }
 }
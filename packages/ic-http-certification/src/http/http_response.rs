@@ -1,16 +1,796 @@
-use crate::HeaderField;
-use candid::{CandidType, Deserialize};
+use super::header_field::{get_header_value, normalize_headers};
+use crate::{
+    status_code_permits_body, HeaderField, HeadersMut, HttpCertificationError,
+    HttpCertificationResult, CERTIFICATE_EXPRESSION_HEADER_NAME, CERTIFICATE_HEADER_NAME,
+};
+use std::{
+    borrow::Cow,
+    hash::{Hash, Hasher},
+};
+
+/// The maximum supported size, in bytes, of a single response header value.
+pub const MAX_HEADER_VALUE_SIZE: usize = 8 * 1024;
+
+/// Response headers that must appear at most once, since repeating them makes the response
+/// ambiguous to clients and to the HTTP Gateway.
+const SINGLETON_RESPONSE_HEADER_NAMES: &[&str] = &[
+    CERTIFICATE_HEADER_NAME,
+    CERTIFICATE_EXPRESSION_HEADER_NAME,
+    "Content-Encoding",
+    "Content-Length",
+    "Content-Type",
+    "Location",
+];
 
 /// A Candid-encodable representation of an HTTP response.
 /// This struct is used by canisters that implement the HTTP interface required by the HTTP Gateway Protocol.
-#[derive(Clone, Debug, CandidType, Deserialize, PartialEq, Eq)]
+///
+/// It implements `CandidType` and `Deserialize` if the `candid` feature is enabled, which it is by
+/// default, so it can be used directly in a canister's `http_request` endpoint.
+/// It also implements `Serialize` if the `serde` feature is enabled.
+///
+/// There is no reason phrase, such as the `OK` of `200 OK`, since the HTTP Gateway Protocol does
+/// not transmit one. Reason phrases are never certified and verification only considers the
+/// numeric status code, so proxies that rewrite them do not break verification. See
+/// [serialize_http1_response_with_reason_phrase](crate::serialize_http1_response_with_reason_phrase)
+/// for serializing a response with a specific reason phrase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "candid", derive(candid::CandidType))]
+#[cfg_attr(any(feature = "candid", feature = "serde"), derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, rename = "Response", rename_all = "camelCase")
+)]
 pub struct HttpResponse {
     /// HTTP response status code.
     pub status_code: u16,
     /// HTTP response headers.
     pub headers: Vec<HeaderField>,
     /// Response body as an array of bytes.
+    #[cfg_attr(feature = "ts", ts(type = "Uint8Array | ArrayBuffer"))]
     pub body: Vec<u8>,
     /// Whether the request should be upgraded to an update call.
+    #[cfg_attr(feature = "ts", ts(skip))]
     pub upgrade: Option<bool>,
+    /// HTTP response trailers, sent after the body, such as a digest of a streamed body.
+    ///
+    /// Trailers are certified with the same rules as headers, see
+    /// [response_hash](crate::response_hash).
+    #[cfg_attr(feature = "ts", ts(skip))]
+    pub trailers: Option<Vec<HeaderField>>,
+}
+
+impl HttpResponse {
+    /// Creates a new [HttpResponseBuilder] for constructing an [HttpResponse].
+    pub fn builder() -> HttpResponseBuilder {
+        HttpResponseBuilder::default()
+    }
+
+    /// Creates a `200 OK` response with the given body and headers.
+    ///
+    /// Like the other shortcut constructors, the response is not validated, see
+    /// [validate](HttpResponse::validate). Use [builder](HttpResponse::builder) to construct a
+    /// response that is validated as it is built.
+    ///
+    /// ```
+    /// use ic_http_certification::HttpResponse;
+    ///
+    /// let response = HttpResponse::ok(
+    ///     b"Hello World!",
+    ///     vec![("Content-Type".into(), "text/plain".into())],
+    /// );
+    ///
+    /// assert_eq!(response.status_code, 200);
+    /// assert_eq!(response.get_header_value("content-type"), Some("text/plain"));
+    /// ```
+    pub fn ok(body: impl Into<Vec<u8>>, headers: Vec<HeaderField>) -> HttpResponse {
+        HttpResponse {
+            status_code: 200,
+            headers,
+            body: body.into(),
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    /// Creates a `404 Not Found` response without headers or body.
+    pub fn not_found() -> HttpResponse {
+        HttpResponse {
+            status_code: 404,
+            headers: vec![],
+            body: vec![],
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    /// Creates a `307 Temporary Redirect` response with a `Location` header with the given
+    /// location.
+    pub fn temporary_redirect(location: impl Into<String>) -> HttpResponse {
+        HttpResponse::redirect(307, location.into())
+    }
+
+    /// Creates a `308 Permanent Redirect` response with a `Location` header with the given
+    /// location.
+    pub fn permanent_redirect(location: impl Into<String>) -> HttpResponse {
+        HttpResponse::redirect(308, location.into())
+    }
+
+    fn redirect(status_code: u16, location: String) -> HttpResponse {
+        HttpResponse {
+            status_code,
+            headers: vec![("Location".into(), location)],
+            body: vec![],
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    /// Creates a response that asks the HTTP Gateway to upgrade the request to an update call,
+    /// so that it is retried on the canister's `http_request_update` endpoint.
+    ///
+    /// The response is discarded by the HTTP Gateway, so it must not be certified, see
+    /// [is_upgrade](HttpResponse::is_upgrade). The response to the update call is represented by
+    /// an [HttpUpdateResponse](crate::HttpUpdateResponse).
+    pub fn upgrade_to_update_call() -> HttpResponse {
+        HttpResponse {
+            status_code: 200,
+            headers: vec![],
+            body: vec![],
+            upgrade: Some(true),
+            trailers: None,
+        }
+    }
+
+    /// Returns true if this response upgrades the request to an update call.
+    ///
+    /// Such responses are rejected by the [HttpCertification](crate::HttpCertification)
+    /// constructors, since the HTTP Gateway discards them without verifying them.
+    pub fn is_upgrade(&self) -> bool {
+        self.upgrade == Some(true)
+    }
+
+    /// Returns the value of the first header with the given name, compared case-insensitively.
+    pub fn get_header_value(&self, name: &str) -> Option<&str> {
+        get_header_value(&self.headers, name)
+    }
+
+    /// Returns true if this response is semantically equal to `other`.
+    ///
+    /// Unlike `==`, header order and header name casing are ignored. Headers with the same name
+    /// must still appear in the same relative order, and the status code, body and upgrade flag
+    /// must be equal. Trailers are compared in the same way as headers. This does not affect certification, which hashes responses as described by
+    /// [response_hash](crate::response_hash).
+    pub fn eq_semantic(&self, other: &HttpResponse) -> bool {
+        self.status_code == other.status_code
+            && self.body == other.body
+            && self.upgrade == other.upgrade
+            && normalize_headers(&self.headers) == normalize_headers(&other.headers)
+            && self.trailers.as_deref().map(normalize_headers)
+                == other.trailers.as_deref().map(normalize_headers)
+    }
+
+    /// Feeds this response into the given [Hasher], consistently with
+    /// [eq_semantic](HttpResponse::eq_semantic).
+    pub fn hash_semantic<H: Hasher>(&self, state: &mut H) {
+        self.status_code.hash(state);
+        normalize_headers(&self.headers).hash(state);
+        self.body.hash(state);
+        self.upgrade.hash(state);
+        self.trailers.as_deref().map(normalize_headers).hash(state);
+    }
+
+    /// Returns [HeadersMut] for modifying the headers of the response, matching header names
+    /// case-insensitively.
+    pub fn headers_mut(&mut self) -> HeadersMut<'_> {
+        HeadersMut::new(&mut self.headers)
+    }
+
+    /// Checks that the response is well formed, so that it can be certified and later verified.
+    ///
+    /// This is called by the [HttpCertification](crate::HttpCertification) constructors, so that
+    /// malformed responses are rejected with a precise error instead of producing a hash that can
+    /// never verify. A response is rejected if:
+    /// - its status code is outside of the range 100 to 599,
+    /// - a header name is empty or contains characters that are not permitted in header names,
+    /// - a header value is larger than [MAX_HEADER_VALUE_SIZE],
+    /// - a header that must appear at most once, such as `Content-Type` or
+    ///   `IC-CertificateExpression`, is repeated.
+    pub fn validate(&self) -> HttpCertificationResult {
+        validate_response(self.status_code, &self.headers)
+    }
+}
+
+pub(super) fn validate_response(
+    status_code: u16,
+    headers: &[(impl AsRef<str>, impl AsRef<str>)],
+) -> HttpCertificationResult {
+    if !(100..=599).contains(&status_code) {
+        return Err(HttpCertificationError::InvalidStatusCode(status_code));
+    }
+
+    for (index, (header_name, header_value)) in headers.iter().enumerate() {
+        let (header_name, header_value) = (header_name.as_ref(), header_value.as_ref());
+
+        if header_name.is_empty() || !header_name.bytes().all(is_header_name_char) {
+            return Err(HttpCertificationError::InvalidHeaderName(
+                header_name.to_string(),
+            ));
+        }
+
+        if header_value.len() > MAX_HEADER_VALUE_SIZE {
+            return Err(HttpCertificationError::HeaderValueTooLarge {
+                header_name: header_name.to_string(),
+                size: header_value.len(),
+                max_size: MAX_HEADER_VALUE_SIZE,
+            });
+        }
+
+        let is_singleton_header = SINGLETON_RESPONSE_HEADER_NAMES
+            .iter()
+            .any(|singleton| singleton.eq_ignore_ascii_case(header_name));
+        let is_duplicate_header = headers[..index]
+            .iter()
+            .any(|(previous_name, _)| previous_name.as_ref().eq_ignore_ascii_case(header_name));
+        if is_singleton_header && is_duplicate_header {
+            return Err(HttpCertificationError::DuplicateHeader(
+                header_name.to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// See the `token` rule of [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-tokens).
+pub(super) fn is_header_name_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte)
+}
+
+/// A builder for [HttpResponse], created with [HttpResponse::builder].
+///
+/// The status code is required and can be set with
+/// [with_status_code](HttpResponseBuilder::with_status_code), or with shortcuts such as
+/// [ok](HttpResponseBuilder::ok) and [not_found](HttpResponseBuilder::not_found). The headers and
+/// body default to being empty.
+///
+/// A `Content-Length` header matching the body is added when the response is built, or checked
+/// against the body if it was set explicitly, since HTTP Gateways reject responses whose
+/// `Content-Length` does not match their body. This can be disabled with
+/// [with_automatic_content_length](HttpResponseBuilder::with_automatic_content_length).
+///
+/// ```
+/// use ic_http_certification::HttpResponse;
+///
+/// let response = HttpResponse::builder()
+///     .ok()
+///     .with_header("Content-Type", "text/plain")
+///     .with_body(b"Hello World!".to_vec())
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(response.status_code, 200);
+/// assert_eq!(response.get_header_value("Content-Length"), Some("12"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct HttpResponseBuilder {
+    status_code: Option<u16>,
+    headers: Vec<HeaderField>,
+    body: Vec<u8>,
+    upgrade: Option<bool>,
+    trailers: Option<Vec<HeaderField>>,
+    automatic_content_length: bool,
+}
+
+impl Default for HttpResponseBuilder {
+    fn default() -> Self {
+        Self {
+            status_code: None,
+            headers: vec![],
+            body: vec![],
+            upgrade: None,
+            trailers: None,
+            automatic_content_length: true,
+        }
+    }
+}
+
+impl HttpResponseBuilder {
+    /// Sets the HTTP response status code, either as a [u16] or as an [http::StatusCode].
+    pub fn with_status_code(mut self, status_code: impl Into<u16>) -> Self {
+        self.status_code = Some(status_code.into());
+
+        self
+    }
+
+    /// Sets the status code to `200 OK`.
+    pub fn ok(self) -> Self {
+        self.with_status_code(200u16)
+    }
+
+    /// Sets the status code to `404 Not Found`.
+    pub fn not_found(self) -> Self {
+        self.with_status_code(404u16)
+    }
+
+    /// Sets the status code to `307 Temporary Redirect` and adds a `Location` header with the
+    /// given location.
+    pub fn temporary_redirect(self, location: impl Into<String>) -> Self {
+        self.with_status_code(307u16)
+            .with_header("Location", location)
+    }
+
+    /// Sets the status code to `308 Permanent Redirect` and adds a `Location` header with the
+    /// given location.
+    pub fn permanent_redirect(self, location: impl Into<String>) -> Self {
+        self.with_status_code(308u16)
+            .with_header("Location", location)
+    }
+
+    /// Sets the HTTP response headers, replacing any headers that were previously added.
+    pub fn with_headers(mut self, headers: Vec<HeaderField>) -> Self {
+        self.headers = headers;
+
+        self
+    }
+
+    /// Adds an HTTP response header.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+
+        self
+    }
+
+    /// Sets the response body.
+    pub fn with_body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+
+        self
+    }
+
+    /// Sets whether the request should be upgraded to an update call.
+    pub fn with_upgrade(mut self, upgrade: bool) -> Self {
+        self.upgrade = Some(upgrade);
+
+        self
+    }
+
+    /// Sets the HTTP response trailers, replacing any trailers that were previously added.
+    pub fn with_trailers(mut self, trailers: Vec<HeaderField>) -> Self {
+        self.trailers = Some(trailers);
+
+        self
+    }
+
+    /// Adds an HTTP response trailer.
+    pub fn with_trailer(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.trailers
+            .get_or_insert_with(Vec::new)
+            .push((name.into(), value.into()));
+
+        self
+    }
+
+    /// Sets whether the `Content-Length` header is added, or checked against the body if it was
+    /// set explicitly, when the response is built. This is enabled by default.
+    ///
+    /// It can be disabled for responses whose `Content-Length` intentionally differs from their
+    /// body, such as responses to `HEAD` requests.
+    pub fn with_automatic_content_length(mut self, automatic_content_length: bool) -> Self {
+        self.automatic_content_length = automatic_content_length;
+
+        self
+    }
+
+    /// Builds the [HttpResponse].
+    ///
+    /// Returns an error if the status code was not set, if the response is malformed, see
+    /// [HttpResponse::validate], or if the `Content-Length` header does not match the body, see
+    /// [with_automatic_content_length](HttpResponseBuilder::with_automatic_content_length).
+    pub fn build(self) -> HttpCertificationResult<HttpResponse> {
+        let status_code = self
+            .status_code
+            .ok_or_else(|| HttpCertificationError::MissingResponseField("status_code".into()))?;
+
+        let mut response = HttpResponse {
+            status_code,
+            headers: self.headers,
+            body: self.body,
+            upgrade: self.upgrade,
+            trailers: self.trailers,
+        };
+        response.validate()?;
+
+        if self.automatic_content_length && status_code_permits_body(status_code) {
+            set_content_length(&mut response)?;
+        }
+
+        Ok(response)
+    }
+}
+
+fn set_content_length(response: &mut HttpResponse) -> HttpCertificationResult {
+    let body_length = response.body.len();
+
+    match response.get_header_value("Content-Length") {
+        Some(content_length) if content_length.trim().parse() == Ok(body_length) => Ok(()),
+        Some(content_length) => Err(HttpCertificationError::ContentLengthMismatch {
+            content_length: content_length.to_string(),
+            body_length,
+        }),
+        None => {
+            response
+                .headers
+                .push(("Content-Length".into(), body_length.to_string()));
+
+            Ok(())
+        }
+    }
+}
+
+impl<'a> From<HttpResponse> for Cow<'a, HttpResponse> {
+    fn from(response: HttpResponse) -> Cow<'a, HttpResponse> {
+        Cow::Owned(response)
+    }
+}
+
+impl<'a> From<&'a HttpResponse> for Cow<'a, HttpResponse> {
+    fn from(response: &'a HttpResponse) -> Cow<'a, HttpResponse> {
+        Cow::Borrowed(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn response() -> HttpResponse {
+        HttpResponse {
+            status_code: 200,
+            headers: vec![
+                ("Content-Type".into(), "text/plain".into()),
+                ("Cache-Control".into(), "no-cache".into()),
+                ("Cache-Control".into(), "no-store".into()),
+            ],
+            body: b"Hello World!".to_vec(),
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    #[rstest]
+    fn validate_valid_response(response: HttpResponse) {
+        assert!(response.validate().is_ok());
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(99)]
+    #[case(600)]
+    #[case(999)]
+    fn validate_invalid_status_code(mut response: HttpResponse, #[case] status_code: u16) {
+        response.status_code = status_code;
+
+        assert!(matches!(
+            response.validate(),
+            Err(HttpCertificationError::InvalidStatusCode(code)) if code == status_code
+        ));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("Content Type")]
+    #[case("Content:Type")]
+    #[case("Contént-Type")]
+    fn validate_invalid_header_name(mut response: HttpResponse, #[case] header_name: &str) {
+        response
+            .headers
+            .push((header_name.into(), "text/plain".into()));
+
+        assert!(matches!(
+            response.validate(),
+            Err(HttpCertificationError::InvalidHeaderName(name)) if name == header_name
+        ));
+    }
+
+    #[rstest]
+    fn validate_oversized_header_value(mut response: HttpResponse) {
+        response
+            .headers
+            .push(("X-Large".into(), "a".repeat(MAX_HEADER_VALUE_SIZE + 1)));
+
+        assert!(matches!(
+            response.validate(),
+            Err(HttpCertificationError::HeaderValueTooLarge {
+                header_name,
+                size,
+                max_size: MAX_HEADER_VALUE_SIZE,
+            }) if header_name == "X-Large" && size == MAX_HEADER_VALUE_SIZE + 1
+        ));
+    }
+
+    #[rstest]
+    #[case("content-type")]
+    #[case("IC-CertificateExpression")]
+    fn validate_duplicate_singleton_header(mut response: HttpResponse, #[case] header_name: &str) {
+        response.headers.push((header_name.into(), "a".into()));
+        response.headers.push((header_name.into(), "b".into()));
+
+        assert!(matches!(
+            response.validate(),
+            Err(HttpCertificationError::DuplicateHeader(name)) if name == header_name
+        ));
+    }
+
+    #[rstest]
+    fn response_builder() {
+        let response = HttpResponse::builder()
+            .with_status_code(201u16)
+            .with_headers(vec![("Content-Type".into(), "text/plain".into())])
+            .with_header("Cache-Control", "no-cache")
+            .with_body(b"Hello World!".to_vec())
+            .with_upgrade(false)
+            .with_trailer("Server-Timing", "total;dur=12")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            response,
+            HttpResponse {
+                status_code: 201,
+                headers: vec![
+                    ("Content-Type".into(), "text/plain".into()),
+                    ("Cache-Control".into(), "no-cache".into()),
+                    ("Content-Length".into(), "12".into()),
+                ],
+                body: b"Hello World!".to_vec(),
+                upgrade: Some(false),
+                trailers: Some(vec![("Server-Timing".into(), "total;dur=12".into())]),
+            }
+        );
+    }
+
+    #[rstest]
+    #[case::ok(HttpResponse::builder().ok(), 200, vec![])]
+    #[case::not_found(HttpResponse::builder().not_found(), 404, vec![])]
+    #[case::temporary_redirect(
+        HttpResponse::builder().temporary_redirect("/new"),
+        307,
+        vec![("Location".into(), "/new".into())]
+    )]
+    #[case::permanent_redirect(
+        HttpResponse::builder().permanent_redirect("/new"),
+        308,
+        vec![("Location".into(), "/new".into())]
+    )]
+    #[case::typed_status_code(
+        HttpResponse::builder().with_status_code(http::StatusCode::IM_A_TEAPOT),
+        418,
+        vec![]
+    )]
+    fn response_builder_status_codes(
+        #[case] builder: HttpResponseBuilder,
+        #[case] expected_status_code: u16,
+        #[case] expected_headers: Vec<HeaderField>,
+    ) {
+        assert_eq!(
+            builder
+                .with_automatic_content_length(false)
+                .build()
+                .unwrap(),
+            HttpResponse {
+                status_code: expected_status_code,
+                headers: expected_headers,
+                body: vec![],
+                upgrade: None,
+                trailers: None,
+            }
+        );
+    }
+
+    #[rstest]
+    #[case::ok(
+        HttpResponse::ok(b"Hello World!", vec![("Content-Type".into(), "text/plain".into())]),
+        HttpResponse::builder()
+            .ok()
+            .with_header("Content-Type", "text/plain")
+            .with_body(b"Hello World!")
+    )]
+    #[case::not_found(HttpResponse::not_found(), HttpResponse::builder().not_found())]
+    #[case::temporary_redirect(
+        HttpResponse::temporary_redirect("/new"),
+        HttpResponse::builder().temporary_redirect("/new")
+    )]
+    #[case::permanent_redirect(
+        HttpResponse::permanent_redirect("/new"),
+        HttpResponse::builder().permanent_redirect("/new")
+    )]
+    fn response_constructors(
+        #[case] response: HttpResponse,
+        #[case] expected_builder: HttpResponseBuilder,
+    ) {
+        assert!(response.validate().is_ok());
+        assert!(!response.is_upgrade());
+        assert_eq!(
+            response,
+            expected_builder
+                .with_automatic_content_length(false)
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case::empty_body(200, b"", "0")]
+    #[case::body(200, b"Hello World!", "12")]
+    #[case::error(500, b"Internal Server Error", "21")]
+    fn response_builder_content_length(
+        #[case] status_code: u16,
+        #[case] body: &[u8],
+        #[case] expected_content_length: &str,
+    ) {
+        let response = HttpResponse::builder()
+            .with_status_code(status_code)
+            .with_body(body)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            response.headers,
+            vec![("Content-Length".into(), expected_content_length.into())]
+        );
+    }
+
+    #[rstest]
+    #[case::no_content(204)]
+    #[case::not_modified(304)]
+    fn response_builder_content_length_without_body(#[case] status_code: u16) {
+        let response = HttpResponse::builder()
+            .with_status_code(status_code)
+            .build()
+            .unwrap();
+
+        assert_eq!(response.get_header_value("Content-Length"), None);
+    }
+
+    #[rstest]
+    #[case("Content-Length", "12")]
+    #[case("content-length", " 12 ")]
+    fn response_builder_matching_content_length(
+        #[case] header_name: &str,
+        #[case] header_value: &str,
+    ) {
+        let response = HttpResponse::builder()
+            .ok()
+            .with_header(header_name, header_value)
+            .with_body(b"Hello World!")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            response.headers,
+            vec![(header_name.into(), header_value.into())]
+        );
+    }
+
+    #[rstest]
+    #[case("11")]
+    #[case("13")]
+    #[case("-12")]
+    #[case("twelve")]
+    fn response_builder_mismatched_content_length(#[case] content_length: &str) {
+        let builder = HttpResponse::builder()
+            .ok()
+            .with_header("Content-Length", content_length)
+            .with_body(b"Hello World!");
+
+        assert!(matches!(
+            builder.clone().build(),
+            Err(HttpCertificationError::ContentLengthMismatch {
+                content_length: value,
+                body_length: 12,
+            }) if value == content_length
+        ));
+        assert_eq!(
+            builder
+                .with_automatic_content_length(false)
+                .build()
+                .unwrap()
+                .get_header_value("Content-Length"),
+            Some(content_length)
+        );
+    }
+
+    #[rstest]
+    fn response_builder_without_automatic_content_length() {
+        let response = HttpResponse::builder()
+            .ok()
+            .with_body(b"Hello World!")
+            .with_automatic_content_length(false)
+            .build()
+            .unwrap();
+
+        assert_eq!(response.get_header_value("Content-Length"), None);
+    }
+
+    #[rstest]
+    fn response_builder_missing_status_code() {
+        assert!(matches!(
+            HttpResponse::builder().build(),
+            Err(HttpCertificationError::MissingResponseField(field)) if field == "status_code"
+        ));
+    }
+
+    #[rstest]
+    fn response_builder_invalid_response() {
+        assert!(matches!(
+            HttpResponse::builder().with_status_code(600u16).build(),
+            Err(HttpCertificationError::InvalidStatusCode(600))
+        ));
+        assert!(matches!(
+            HttpResponse::builder()
+                .permanent_redirect("/new")
+                .with_header("location", "/other")
+                .build(),
+            Err(HttpCertificationError::DuplicateHeader(header_name)) if header_name == "location"
+        ));
+    }
+
+    fn semantic_hash(response: &HttpResponse) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        response.hash_semantic(&mut hasher);
+
+        hasher.finish()
+    }
+
+    #[rstest]
+    fn response_eq_semantic(response: HttpResponse) {
+        let other = HttpResponse {
+            headers: vec![
+                ("cache-control".into(), "no-cache".into()),
+                ("CONTENT-TYPE".into(), "text/plain".into()),
+                ("Cache-Control".into(), "no-store".into()),
+            ],
+            ..response.clone()
+        };
+
+        assert_ne!(response, other);
+        assert!(response.eq_semantic(&other));
+        assert_eq!(semantic_hash(&response), semantic_hash(&other));
+    }
+
+    #[rstest]
+    #[case::status_code(HttpResponse { status_code: 201, ..response() })]
+    #[case::body(HttpResponse { body: b"Hello World?".to_vec(), ..response() })]
+    #[case::upgrade(HttpResponse { upgrade: Some(false), ..response() })]
+    #[case::trailers(HttpResponse {
+        trailers: Some(vec![("Server-Timing".into(), "total;dur=12".into())]),
+        ..response()
+    })]
+    #[case::header_value(HttpResponse {
+        headers: vec![
+            ("Content-Type".into(), "text/html".into()),
+            ("Cache-Control".into(), "no-cache".into()),
+            ("Cache-Control".into(), "no-store".into()),
+        ],
+        ..response()
+    })]
+    #[case::reordered_repeated_headers(HttpResponse {
+        headers: vec![
+            ("Content-Type".into(), "text/plain".into()),
+            ("Cache-Control".into(), "no-store".into()),
+            ("Cache-Control".into(), "no-cache".into()),
+        ],
+        ..response()
+    })]
+    fn response_not_eq_semantic(#[case] other: HttpResponse) {
+        let response = response();
+
+        assert!(!response.eq_semantic(&other));
+        assert_ne!(semantic_hash(&response), semantic_hash(&other));
+    }
 }
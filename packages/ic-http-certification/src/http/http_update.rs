@@ -0,0 +1,161 @@
+use crate::{HeaderField, HttpCertificationError, HttpRequest, HttpResponse};
+
+/// A Candid-encodable representation of an HTTP request that is received by a canister's
+/// `http_request_update` endpoint.
+///
+/// The HTTP Gateway makes this update call after the canister's `http_request` query endpoint
+/// responded with a response that [upgrades](HttpResponse::upgrade_to_update_call) the request.
+/// It has the same fields as an [HttpRequest], and can be converted to and from one with [From].
+///
+/// It implements `CandidType` and `Deserialize` if the `candid` feature is enabled, which it is by
+/// default. It also implements `Serialize` if the `serde` feature is enabled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "candid", derive(candid::CandidType))]
+#[cfg_attr(any(feature = "candid", feature = "serde"), derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HttpUpdateRequest {
+    /// HTTP request method.
+    pub method: String,
+    /// Request URL.
+    pub url: String,
+    /// HTTP request headers.
+    pub headers: Vec<HeaderField>,
+    /// Request body as an array of bytes.
+    pub body: Vec<u8>,
+}
+
+impl From<HttpRequest> for HttpUpdateRequest {
+    fn from(request: HttpRequest) -> Self {
+        Self {
+            method: request.method,
+            url: request.url,
+            headers: request.headers,
+            body: request.body,
+        }
+    }
+}
+
+impl From<HttpUpdateRequest> for HttpRequest {
+    fn from(request: HttpUpdateRequest) -> Self {
+        Self {
+            method: request.method,
+            url: request.url,
+            headers: request.headers,
+            body: request.body,
+        }
+    }
+}
+
+/// A Candid-encodable representation of an HTTP response that is returned by a canister's
+/// `http_request_update` endpoint.
+///
+/// Responses to update calls are certified by consensus, so they do not need to be certified with
+/// an [HttpCertification](crate::HttpCertification), and they cannot upgrade the request again.
+/// Unlike [HttpResponse], it therefore has no `upgrade` field. It can be converted into an
+/// [HttpResponse] with [From], and an [HttpResponse] that does not upgrade the request can be
+/// converted into an [HttpUpdateResponse] with [TryFrom].
+///
+/// It implements `CandidType` and `Deserialize` if the `candid` feature is enabled, which it is by
+/// default. It also implements `Serialize` if the `serde` feature is enabled.
+///
+/// ```
+/// use ic_http_certification::{HttpResponse, HttpUpdateResponse};
+///
+/// let response = HttpResponse::builder().ok().build().unwrap();
+/// let update_response = HttpUpdateResponse::try_from(response).unwrap();
+/// assert_eq!(update_response.status_code, 200);
+///
+/// let upgrade_response = HttpResponse::upgrade_to_update_call();
+/// assert!(HttpUpdateResponse::try_from(upgrade_response).is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "candid", derive(candid::CandidType))]
+#[cfg_attr(any(feature = "candid", feature = "serde"), derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HttpUpdateResponse {
+    /// HTTP response status code.
+    pub status_code: u16,
+    /// HTTP response headers.
+    pub headers: Vec<HeaderField>,
+    /// Response body as an array of bytes.
+    pub body: Vec<u8>,
+}
+
+impl From<HttpUpdateResponse> for HttpResponse {
+    fn from(response: HttpUpdateResponse) -> Self {
+        Self {
+            status_code: response.status_code,
+            headers: response.headers,
+            body: response.body,
+            upgrade: None,
+            trailers: None,
+        }
+    }
+}
+
+impl TryFrom<HttpResponse> for HttpUpdateResponse {
+    type Error = HttpCertificationError;
+
+    fn try_from(response: HttpResponse) -> Result<Self, Self::Error> {
+        if response.is_upgrade() {
+            return Err(HttpCertificationError::UnexpectedUpgrade);
+        }
+
+        Ok(Self {
+            status_code: response.status_code,
+            headers: response.headers,
+            body: response.body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_request_round_trip() {
+        let request = HttpRequest::builder()
+            .with_method("POST")
+            .with_url("/todos")
+            .with_header("Content-Type", "application/json")
+            .with_body(b"{}".to_vec())
+            .build()
+            .unwrap();
+
+        let update_request = HttpUpdateRequest::from(request.clone());
+
+        assert_eq!(update_request.url, "/todos");
+        assert_eq!(HttpRequest::from(update_request), request);
+    }
+
+    #[test]
+    fn update_response_round_trip() {
+        let response = HttpResponse::builder()
+            .with_status_code(201u16)
+            .with_header("Content-Type", "application/json")
+            .with_body(b"{}".to_vec())
+            .with_upgrade(false)
+            .build()
+            .unwrap();
+
+        let update_response = HttpUpdateResponse::try_from(response.clone()).unwrap();
+
+        assert_eq!(
+            HttpResponse::from(update_response),
+            HttpResponse {
+                upgrade: None,
+                trailers: None,
+                ..response
+            }
+        );
+    }
+
+    #[test]
+    fn update_response_rejects_upgrade() {
+        assert!(matches!(
+            HttpUpdateResponse::try_from(HttpResponse::upgrade_to_update_call()),
+            Err(HttpCertificationError::UnexpectedUpgrade)
+        ));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{CertificateVersion, HeaderField, HttpRequest, HttpResponse};
+
+/// The Candid record that the HTTP Gateway sends to a canister's `http_request` query endpoint,
+/// as defined by the HTTP Gateway Protocol.
+///
+/// Unlike [HttpRequest], it includes the `certificate_version` field, so it can be used directly
+/// as the argument of an `ic-cdk` `#[query]` function, without defining the record by hand. It
+/// can be converted to and from an [HttpRequest] with [From].
+#[derive(Clone, Debug, PartialEq, Eq, candid::CandidType, serde::Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GatewayHttpRequest {
+    /// HTTP request method.
+    pub method: String,
+    /// Request URL.
+    pub url: String,
+    /// HTTP request headers.
+    pub headers: Vec<HeaderField>,
+    /// Request body as an array of bytes.
+    pub body: Vec<u8>,
+    /// The highest version of response verification that the HTTP Gateway supports, or [None]
+    /// if it only supports version 1.
+    pub certificate_version: Option<u16>,
+}
+
+impl GatewayHttpRequest {
+    /// Returns the [CertificateVersion] that the response to this request should be certified
+    /// for. See [CertificateVersion::negotiate].
+    pub fn negotiate_certificate_version(&self) -> CertificateVersion {
+        CertificateVersion::negotiate(self.certificate_version)
+    }
+}
+
+/// Converts a [GatewayHttpRequest] into an [HttpRequest].
+///
+/// The `certificate_version` field has no equivalent in an [HttpRequest] and is discarded, so it
+/// should be [negotiated](GatewayHttpRequest::negotiate_certificate_version) first.
+impl From<GatewayHttpRequest> for HttpRequest {
+    fn from(request: GatewayHttpRequest) -> Self {
+        HttpRequest {
+            method: request.method,
+            url: request.url,
+            headers: request.headers,
+            body: request.body,
+        }
+    }
+}
+
+/// Converts an [HttpRequest] into a [GatewayHttpRequest] without a `certificate_version`.
+impl From<HttpRequest> for GatewayHttpRequest {
+    fn from(request: HttpRequest) -> Self {
+        GatewayHttpRequest {
+            method: request.method,
+            url: request.url,
+            headers: request.headers,
+            body: request.body,
+            certificate_version: None,
+        }
+    }
+}
+
+/// The Candid record that a canister's `http_request` query endpoint returns to the HTTP
+/// Gateway, as defined by the HTTP Gateway Protocol.
+///
+/// Streaming strategies are not supported, so the optional `streaming_strategy` field is never
+/// returned. It can be converted to and from an [HttpResponse] with [From].
+#[derive(Clone, Debug, PartialEq, Eq, candid::CandidType, serde::Deserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GatewayHttpResponse {
+    /// HTTP response status code.
+    pub status_code: u16,
+    /// HTTP response headers.
+    pub headers: Vec<HeaderField>,
+    /// Response body as an array of bytes.
+    pub body: Vec<u8>,
+    /// Whether the HTTP Gateway should upgrade the request to an update call, see
+    /// [HttpResponse::upgrade_to_update_call].
+    pub upgrade: Option<bool>,
+}
+
+/// Converts a [GatewayHttpResponse] into an [HttpResponse] without trailers.
+impl From<GatewayHttpResponse> for HttpResponse {
+    fn from(response: GatewayHttpResponse) -> Self {
+        HttpResponse {
+            status_code: response.status_code,
+            headers: response.headers,
+            body: response.body,
+            upgrade: response.upgrade,
+            trailers: None,
+        }
+    }
+}
+
+/// Converts an [HttpResponse] into a [GatewayHttpResponse].
+///
+/// The HTTP Gateway Protocol has no equivalent of the [trailers](HttpResponse::trailers) of an
+/// [HttpResponse], so they are discarded.
+impl From<HttpResponse> for GatewayHttpResponse {
+    fn from(response: HttpResponse) -> Self {
+        GatewayHttpResponse {
+            status_code: response.status_code,
+            headers: response.headers,
+            body: response.body,
+            upgrade: response.upgrade,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::{decode_one, encode_one};
+    use rstest::*;
+
+    #[rstest]
+    #[case("GET")]
+    #[case("POST")]
+    #[case("DELETE")]
+    #[case("PATCH")]
+    fn request_round_trip(#[case] method: &str) {
+        let request = HttpRequest {
+            method: method.to_string(),
+            url: "/items?lang=en".to_string(),
+            headers: vec![
+                ("accept".to_string(), "application/json".to_string()),
+                ("x-custom".to_string(), "1".to_string()),
+                ("x-custom".to_string(), "2".to_string()),
+            ],
+            body: b"Hello World!".to_vec(),
+        };
+
+        let gateway_request = GatewayHttpRequest::from(request.clone());
+
+        assert_eq!(gateway_request.method, method);
+        assert_eq!(gateway_request.certificate_version, None);
+        assert_eq!(HttpRequest::from(gateway_request), request);
+    }
+
+    #[rstest]
+    fn request_negotiates_certificate_version() {
+        let gateway_request = GatewayHttpRequest {
+            method: "GET".to_string(),
+            url: "/".to_string(),
+            headers: vec![],
+            body: vec![],
+            certificate_version: Some(2),
+        };
+
+        assert_eq!(
+            gateway_request.negotiate_certificate_version(),
+            CertificateVersion::V2
+        );
+    }
+
+    #[rstest]
+    #[case(None)]
+    #[case(Some(false))]
+    #[case(Some(true))]
+    fn response_round_trip(#[case] upgrade: Option<bool>) {
+        let response = HttpResponse {
+            status_code: 404,
+            headers: vec![("content-type".to_string(), "text/plain".to_string())],
+            body: b"Not Found".to_vec(),
+            upgrade,
+            trailers: None,
+        };
+
+        let gateway_response = GatewayHttpResponse::from(response.clone());
+
+        assert_eq!(gateway_response.upgrade, upgrade);
+        assert_eq!(HttpResponse::from(gateway_response), response);
+    }
+
+    #[rstest]
+    fn candid_request_decodes_as_http_request() {
+        let gateway_request = GatewayHttpRequest {
+            method: "PUT".to_string(),
+            url: "/items/1".to_string(),
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: br#"{"name":"item"}"#.to_vec(),
+            certificate_version: Some(2),
+        };
+
+        let encoded = encode_one(&gateway_request).unwrap();
+
+        assert_eq!(
+            decode_one::<GatewayHttpRequest>(&encoded).unwrap(),
+            gateway_request
+        );
+        assert_eq!(
+            decode_one::<HttpRequest>(&encoded).unwrap(),
+            HttpRequest::from(gateway_request)
+        );
+    }
+
+    #[rstest]
+    fn candid_response_keeps_upgrade() {
+        let response = HttpResponse::upgrade_to_update_call();
+
+        let encoded = encode_one(GatewayHttpResponse::from(response.clone())).unwrap();
+
+        assert_eq!(
+            HttpResponse::from(decode_one::<GatewayHttpResponse>(&encoded).unwrap()),
+            response
+        );
+    }
+}
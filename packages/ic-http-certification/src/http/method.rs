@@ -0,0 +1,129 @@
+use std::fmt::{Display, Formatter};
+
+/// An HTTP request method.
+///
+/// Method names are case-sensitive, but clients and gateways do not always agree on their
+/// casing. To avoid hash mismatches between a canister and a verifier, methods are uppercased
+/// when they are converted from a string, and the method of an
+/// [HttpRequest](crate::HttpRequest) is uppercased before it is hashed by
+/// [request_hash](crate::request_hash). So, for example, `get` and `GET` are the same method.
+///
+/// ```
+/// use ic_http_certification::{HttpRequest, Method};
+///
+/// let request = HttpRequest::builder()
+///     .with_method(Method::Post)
+///     .with_url("/api/items")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(request.method, "POST");
+/// assert_eq!(request.get_method(), Method::Post);
+/// assert_eq!(Method::from("purge"), Method::Other("PURGE".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// The `GET` method.
+    Get,
+    /// The `HEAD` method.
+    Head,
+    /// The `POST` method.
+    Post,
+    /// The `PUT` method.
+    Put,
+    /// The `DELETE` method.
+    Delete,
+    /// The `CONNECT` method.
+    Connect,
+    /// The `OPTIONS` method.
+    Options,
+    /// The `TRACE` method.
+    Trace,
+    /// The `PATCH` method.
+    Patch,
+    /// Any other method, such as `PURGE`, in uppercase.
+    Other(String),
+}
+
+impl Method {
+    /// Returns the uppercase name of the method.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Method::Get => "GET",
+            Method::Head => "HEAD",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+            Method::Connect => "CONNECT",
+            Method::Options => "OPTIONS",
+            Method::Trace => "TRACE",
+            Method::Patch => "PATCH",
+            Method::Other(method) => method,
+        }
+    }
+}
+
+impl From<&str> for Method {
+    fn from(method: &str) -> Self {
+        match method.to_ascii_uppercase().as_str() {
+            "GET" => Method::Get,
+            "HEAD" => Method::Head,
+            "POST" => Method::Post,
+            "PUT" => Method::Put,
+            "DELETE" => Method::Delete,
+            "CONNECT" => Method::Connect,
+            "OPTIONS" => Method::Options,
+            "TRACE" => Method::Trace,
+            "PATCH" => Method::Patch,
+            method => Method::Other(method.to_string()),
+        }
+    }
+}
+
+impl From<String> for Method {
+    fn from(method: String) -> Self {
+        Method::from(method.as_str())
+    }
+}
+
+impl From<Method> for String {
+    fn from(method: Method) -> Self {
+        match method {
+            Method::Other(method) => method,
+            method => method.as_str().to_string(),
+        }
+    }
+}
+
+impl Display for Method {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("GET", Method::Get)]
+    #[case("get", Method::Get)]
+    #[case("Post", Method::Post)]
+    #[case("patch", Method::Patch)]
+    #[case("purge", Method::Other("PURGE".to_string()))]
+    fn method_from_str(#[case] method: &str, #[case] expected: Method) {
+        let method = Method::from(method);
+
+        assert_eq!(method, expected);
+        assert_eq!(method.as_str(), method.as_str().to_ascii_uppercase());
+    }
+
+    #[rstest]
+    fn method_round_trip() {
+        for method in ["GET", "HEAD", "POST", "PUT", "DELETE", "OPTIONS", "PURGE"] {
+            assert_eq!(String::from(Method::from(method)), method);
+            assert_eq!(Method::from(method).to_string(), method);
+        }
+    }
+}
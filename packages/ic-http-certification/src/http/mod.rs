@@ -2,10 +2,34 @@
 //! These types are Candid-encodable and are used by canisters that implement the
 //! HTTP interface required by the HTTP Gateway Protocol.
 
+mod body;
+mod certificate_version;
+#[cfg(feature = "compression")]
+mod content_encoding;
 mod header_field;
+mod http1;
+#[cfg(feature = "http-crate")]
+mod http_crate;
+mod http_ref;
 mod http_request;
 mod http_response;
+mod http_update;
+#[cfg(feature = "ic-cdk")]
+mod ic_cdk_crate;
+mod method;
+mod request_url;
 
+pub use body::*;
+pub use certificate_version::*;
+#[cfg(feature = "compression")]
+pub use content_encoding::*;
 pub use header_field::*;
+pub use http1::*;
+pub use http_ref::*;
 pub use http_request::*;
 pub use http_response::*;
+pub use http_update::*;
+#[cfg(feature = "ic-cdk")]
+pub use ic_cdk_crate::*;
+pub use method::*;
+pub use request_url::*;
@@ -0,0 +1,218 @@
+use super::{header_field::glob_matches, http_request::parse_url};
+use crate::{HttpCertificationPath, HttpCertificationResult};
+
+/// The prefix of query parameter patterns that exclude the names that they match from
+/// certification, such as `!utm_*`.
+pub(crate) const QUERY_PARAMETER_EXCLUSION_PREFIX: char = '!';
+
+const ENCODED_SLASH: &str = "%2F";
+
+/// The URL of an [HttpRequest](crate::HttpRequest), split into its path and query.
+///
+/// The path and query are normalized as follows:
+///
+/// - The URL may be in origin form, such as `/index.html`, or in absolute form, such as
+///   `https://example.com/index.html`. The authority of absolute form URLs is ignored.
+/// - `.` and `..` segments are removed from the [path](RequestUrl::path), as described in
+///   [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-5.2.4), so for example
+///   `/assets/../index.html` becomes `/index.html`. Dot segments are removed before the path is
+///   percent-decoded, so encoded dots such as `%2E%2E` never traverse the path.
+/// - The path is then percent-decoded, except for encoded slashes (`%2F`), which are kept as they
+///   are so that they never become path separators.
+/// - The [query](RequestUrl::query) is not decoded, since it is included in the
+///   [request hash](crate::request_hash) as it was sent.
+/// - The fragment is not included.
+///
+/// This is the normalization that the response verifier applies to the request URL before it
+/// checks the `expr_path` of a certification, see
+/// [HttpRequest::canonical_path](crate::HttpRequest::canonical_path). Canisters that route
+/// requests and certify responses by the same path serve the certification that the verifier
+/// looks up.
+///
+/// ```
+/// use ic_http_certification::RequestUrl;
+///
+/// let url = RequestUrl::parse("https://example.com/assets/./../hello%20world.txt?lang=en#top")
+///     .unwrap();
+///
+/// assert_eq!(url.path(), "/hello world.txt");
+/// assert_eq!(url.query(), Some("lang=en"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestUrl {
+    path: String,
+    query: Option<String>,
+}
+
+impl RequestUrl {
+    /// Parses and normalizes a request URL.
+    ///
+    /// Returns an error if the URL is malformed or if its path is not valid UTF-8 once decoded.
+    pub fn parse(url: &str) -> HttpCertificationResult<Self> {
+        let uri = parse_url(url)?;
+        let path = remove_dot_segments(uri.path())
+            .split('/')
+            .map(decode_path_segment)
+            .collect::<HttpCertificationResult<Vec<_>>>()?
+            .join("/");
+
+        Ok(Self {
+            path,
+            query: uri.query().map(str::to_owned),
+        })
+    }
+
+    /// Returns the normalized and decoded path of the URL.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the query of the URL, if any, without the leading `?`.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Returns the [path](RequestUrl::path) with empty segments removed, in the same way as when
+    /// the `expr_path` of a certification is derived from the request URL.
+    ///
+    /// For example, `/assets//app.js` becomes `/assets/app.js`. A trailing slash is preserved.
+    pub fn canonical_path(&self) -> String {
+        let expr_path = HttpCertificationPath::exact(self.path()).to_expr_path();
+        let segments = &expr_path[1..expr_path.len() - 1];
+
+        format!("/{}", segments.join("/"))
+    }
+
+    /// Splits the URL into its [path](RequestUrl::path) and [query](RequestUrl::query).
+    pub fn into_parts(self) -> (String, Option<String>) {
+        (self.path, self.query)
+    }
+}
+
+/// Returns true if the given query parameter name matches a query parameter name or pattern from
+/// the [query_parameters](crate::DefaultRequestCertification::query_parameters) of a
+/// [DefaultRequestCertification](crate::DefaultRequestCertification).
+///
+/// Query parameter names are compared case-insensitively. A `*` in `pattern` matches any
+/// sequence of characters, including an empty one, so that a family of parameters such as
+/// `page*` can be certified without listing every name. Patterns that start with `!` exclude the
+/// names that they match instead, see
+/// [certified_query](crate::DefaultRequestCertification::certified_query), so this function
+/// never matches them.
+///
+/// ```
+/// use ic_http_certification::query_parameter_name_matches;
+///
+/// assert!(query_parameter_name_matches("page*", "page_size"));
+/// assert!(query_parameter_name_matches("Filter", "filter"));
+/// assert!(!query_parameter_name_matches("page*", "limit"));
+/// assert!(!query_parameter_name_matches("!utm_*", "utm_source"));
+/// ```
+pub fn query_parameter_name_matches(pattern: &str, name: &str) -> bool {
+    if pattern.starts_with(QUERY_PARAMETER_EXCLUSION_PREFIX) {
+        return false;
+    }
+
+    match pattern.contains('*') {
+        true => glob_matches(pattern.as_bytes(), name.as_bytes()),
+        false => pattern.eq_ignore_ascii_case(name),
+    }
+}
+
+// Decodes a path segment, except for encoded slashes, so that they are not confused with the
+// separators between segments.
+fn decode_path_segment(segment: &str) -> HttpCertificationResult<String> {
+    let mut decoded_segment = String::with_capacity(segment.len());
+    let mut remaining_segment = segment;
+    while let Some(index) = remaining_segment.to_ascii_uppercase().find(ENCODED_SLASH) {
+        decoded_segment.push_str(&urlencoding::decode(&remaining_segment[..index])?);
+        decoded_segment.push_str(&remaining_segment[index..index + ENCODED_SLASH.len()]);
+        remaining_segment = &remaining_segment[index + ENCODED_SLASH.len()..];
+    }
+    decoded_segment.push_str(&urlencoding::decode(remaining_segment)?);
+
+    Ok(decoded_segment)
+}
+
+// See [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-5.2.4).
+fn remove_dot_segments(path: &str) -> String {
+    let (prefix, relative_path) = match path.strip_prefix('/') {
+        Some(relative_path) => ("/", relative_path),
+        None => ("", path),
+    };
+
+    let segments = relative_path.split('/').collect::<Vec<_>>();
+    let last_index = segments.len() - 1;
+    let mut output: Vec<&str> = vec![];
+    for (index, segment) in segments.into_iter().enumerate() {
+        match segment {
+            "." => {}
+            ".." => {
+                output.pop();
+            }
+            segment => output.push(segment),
+        }
+
+        // a trailing dot segment refers to a directory, so the trailing slash is kept
+        if index == last_index && (segment == "." || segment == "..") {
+            output.push("");
+        }
+    }
+
+    format!("{prefix}{}", output.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case("/", "/")]
+    #[case("/index.html", "/index.html")]
+    #[case("/assets/", "/assets/")]
+    #[case("/assets//app.js", "/assets//app.js")]
+    #[case("/a/./b", "/a/b")]
+    #[case("/a/b/../c", "/a/c")]
+    #[case("/a/b/..", "/a/")]
+    #[case("/a/b/.", "/a/b/")]
+    #[case("/..", "/")]
+    #[case("/../../a", "/a")]
+    #[case("/a//../b", "/a/b")]
+    #[case("/a/..b/c.", "/a/..b/c.")]
+    #[case("/a/%2E%2E/b", "/a/../b")]
+    #[case("/a/%2e/b", "/a/./b")]
+    #[case("/a%2Fb/../c", "/c")]
+    #[case("/a/b%2F..%2fc", "/a/b%2F..%2fc")]
+    #[case("/hello%20world", "/hello world")]
+    #[case("https://example.com/a/../b", "/b")]
+    fn parse_path(#[case] url: &str, #[case] expected_path: &str) {
+        assert_eq!(RequestUrl::parse(url).unwrap().path(), expected_path);
+    }
+
+    #[rstest]
+    #[case("/", None)]
+    #[case("/?", Some(""))]
+    #[case("/?a=1&b=%20", Some("a=1&b=%20"))]
+    #[case("/?a=1#fragment", Some("a=1"))]
+    #[case("https://example.com/?a=1", Some("a=1"))]
+    fn parse_query(#[case] url: &str, #[case] expected_query: Option<&str>) {
+        assert_eq!(RequestUrl::parse(url).unwrap().query(), expected_query);
+    }
+
+    #[rstest]
+    #[case("/assets//app.js", "/assets/app.js")]
+    #[case("/assets/./../app.js/", "/app.js/")]
+    #[case("/", "/")]
+    fn canonical_path(#[case] url: &str, #[case] expected_path: &str) {
+        assert_eq!(
+            RequestUrl::parse(url).unwrap().canonical_path(),
+            expected_path
+        );
+    }
+
+    #[rstest]
+    fn parse_malformed_url() {
+        assert!(RequestUrl::parse("/a file.txt").is_err());
+    }
+}
@@ -40,6 +40,93 @@ let certification = CelExpression::Default(DefaultCelExpression::Skip);
 let cel_expr = create_cel_expr(&certification);
 ```
 
+The [String] representation keeps header, query parameter and form field names in the order and case in which they were provided. Since the representation is included in certification, semantically identical expressions that differ only in these details are hashed differently. To avoid this, use [DefaultCelExpression.canonicalize](cel::DefaultCelExpression::canonicalize()), which sorts, deduplicates and, where names are compared case-insensitively, lowercases these names. The canonical representation can be parsed back into a [DefaultCelExpression](cel::DefaultCelExpression) to certify responses with it:
+
+```rust
+use ic_http_certification::{DefaultCelBuilder, DefaultCelExpression};
+
+let cel_expr = DefaultCelExpression::Full(
+    DefaultCelBuilder::full_certification()
+        .with_request_headers(vec!["Accept-Encoding", "Accept"])
+        .build(),
+);
+let equivalent_cel_expr = DefaultCelExpression::Full(
+    DefaultCelBuilder::full_certification()
+        .with_request_headers(vec!["accept", "accept-encoding"])
+        .build(),
+);
+
+assert_ne!(cel_expr.to_string(), equivalent_cel_expr.to_string());
+assert_eq!(cel_expr.canonicalize(), equivalent_cel_expr.canonicalize());
+```
+
+To print a CEL expression for debugging, use [DefaultCelExpression.to_pretty_string](cel::DefaultCelExpression::to_pretty_string()), or [pretty_print_cel_expr](cel::pretty_print_cel_expr()) for any [String] representation. The pretty-printed representation splits objects over multiple lines, but it is hashed differently, so it should not be used for certification.
+
+### Parsing CEL expressions from their `String` representation
+
+To turn a [String] representation, such as the value of an `IC-CertificateExpression` header, back into a [CelExpression](cel::CelExpression), use [parse_cel_expression](cel::parse_cel_expression()). Typed expressions can be parsed with [TryFrom], which returns an error if the expression is valid but of a different kind, so that verifiers and test suites can compare a served expression with the expected one:
+
+```rust
+use ic_http_certification::{DefaultCelBuilder, DefaultFullCelExpression, DefaultResponseCertification};
+
+let cel_expr = DefaultCelBuilder::full_certification()
+    .with_request_headers(vec!["Accept"])
+    .with_response_certification(DefaultResponseCertification::certified_response_headers(vec![
+        "Content-Type",
+    ]))
+    .build();
+let cel_expr_str = cel_expr.to_string();
+
+let parsed_cel_expr = DefaultFullCelExpression::try_from(cel_expr_str.as_str()).unwrap();
+
+assert_eq!(parsed_cel_expr, cel_expr);
+```
+
+To find out why an expression is invalid, for example one that causes verification to fail at the HTTP Gateway, use [validate_cel_expression](cel::validate_cel_expression()). It reports syntax errors, unsupported functions, unknown properties and malformed lists, together with the byte offset at which they were found:
+
+```rust
+use ic_http_certification::cel::validate_cel_expression;
+
+let cel_expr = r#"default_certification(ValidationArgs{no_certification:Empty{}}"#;
+let err = validate_cel_expression(cel_expr).unwrap_err();
+
+assert_eq!(err.offset(), cel_expr.len());
+```
+
+If an expression is valid, but does not match the expression that was certified, for example because verification fails with a CEL expression hash mismatch, use [diff_cel_expressions](cel::diff_cel_expressions()) or [DefaultCelExpression.diff](cel::DefaultCelExpression::diff()) to find out which certified headers, query parameters or other options differ:
+
+```rust
+use ic_http_certification::cel::{diff_cel_expressions, CelExpressionDifference, CelNameList};
+
+let expected = r#"default_certification(ValidationArgs{certification:Certification{no_request_certification:Empty{},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:["Content-Type","ETag"]}}}})"#;
+let found = r#"default_certification(ValidationArgs{certification:Certification{no_request_certification:Empty{},response_certification:ResponseCertification{certified_response_headers:ResponseHeaderList{headers:["Content-Type"]}}}})"#;
+let differences = diff_cel_expressions(expected, found).unwrap();
+
+assert_eq!(
+    differences,
+    vec![CelExpressionDifference::CertifiedNames {
+        list: CelNameList::ResponseHeaders,
+        missing: vec!["ETag".to_string()],
+        unexpected: vec![],
+    }]
+);
+assert_eq!(
+    differences[0].to_string(),
+    r#"The certified response headers differ, missing ["ETag"]"#
+);
+```
+
+Every CEL expression uses a version of the CEL syntax, see [CelVersion](cel::CelVersion). The [DefaultCelBuilder] creates expressions of the [latest](cel::CelVersion::LATEST) version, which is currently version 2 with the `default_certification` function. Canisters and verifiers that may support different versions can select the highest version that they both support with [negotiate](cel::CelVersion::negotiate):
+
+```rust
+use ic_http_certification::{cel::CelVersion, DefaultCelBuilder};
+
+let cel_version = CelVersion::negotiate(&[CelVersion::V2]).unwrap();
+let cel_expr = DefaultCelBuilder::response_only_certification().build();
+
+assert_eq!(cel_expr.version(), cel_version);
+```
+
 ### Using the CEL builder
 
 The CEL builder interface is provided to ease the creation of CEL expressions through an ergonomic interface. It is also possible to [create CEL expressions directly](#directly-creating-a-cel-expression). To define a CEL expression, start with [DefaultCelBuilder]. This struct provides a set of associated functions that can be used to define how a request and response pair should be certified.
@@ -55,6 +142,7 @@ When certifying responses:
 - To certify response headers, use [with_response_certification](cel::DefaultFullCelExpressionBuilder::with_response_certification()). This associated function takes the [DefaultResponseCertification](DefaultResponseCertification) enum as an argument.
   - To specify header inclusions, use the [certified_response_headers](DefaultResponseCertification::certified_response_headers) associated function of the [DefaultResponseCertification](DefaultResponseCertification) enum.
   - To certify all response headers (with some exclusions) use the [response_header_exclusions](DefaultResponseCertification::response_header_exclusions) associated function of the [DefaultResponseCertification](DefaultResponseCertification) enum. Both associated functions take a [str] slice as an argument.
+  - To certify a list of response headers, except for some exclusions, use the [certified_response_headers_with_exclusions](DefaultResponseCertification::certified_response_headers_with_exclusions) associated function of the [DefaultResponseCertification](DefaultResponseCertification) enum. It takes two [str] slices as arguments, and exclusions take precedence over inclusions.
 
 #### Fully certified request / response pair
 
@@ -161,6 +249,168 @@ let cel_expr = DefaultCelBuilder::full_certification()
 
 To skip response certification completely, certification overall must be skipped completely. It wouldn't be useful to certify a request without certifying a response.
 
+#### Certifying families of headers
+
+Header names in any of the header lists may be patterns, so that a family of custom headers can be certified without listing every name. Patterns must be opted into explicitly with the `glob:` prefix, after which `*` matches any sequence of characters, while a `*` in a header name without the prefix is compared literally. Patterns are matched case-insensitively against the headers of each request and response when they are hashed, see [header_name_matches](header_name_matches()). Patterns in lists of certified headers never match the `Cookie`, `Set-Cookie`, `Authorization` and `Proxy-Authorization` headers, which must always be named explicitly to be certified. Patterns in lists of excluded headers do match them, so excluding `glob:*` never certifies them, see [excluded_header_name_matches](excluded_header_name_matches()):
+
+```rust
+use ic_http_certification::{DefaultCelBuilder, DefaultResponseCertification};
+
+let cel_expr = DefaultCelBuilder::full_certification()
+    .with_request_headers(vec!["Accept", "glob:X-Client-*"])
+    .with_response_certification(DefaultResponseCertification::certified_response_headers(vec![
+        "Content-Type",
+        "glob:X-App-*",
+    ]))
+    .build();
+```
+
+Patterns are most useful together with exclusions. A header is certified if it matches one of the certified headers and none of the excluded headers, so the following expression certifies every `X-App-*` header except for `X-App-Request-Id`:
+
+```rust
+use ic_http_certification::{DefaultCelBuilder, DefaultResponseCertification};
+
+let cel_expr = DefaultCelBuilder::response_only_certification()
+    .with_response_certification(
+        DefaultResponseCertification::certified_response_headers_with_exclusions(
+            vec!["glob:X-App-*"],
+            vec!["X-App-Request-Id"],
+        ),
+    )
+    .build();
+```
+
+#### Certifying families of query parameters
+
+Like header names, certified query parameter names may be patterns that contain `*`, such as `page*`, which certify every query parameter whose name matches the pattern, see [query_parameter_name_matches](query_parameter_name_matches()). Patterns that start with `!` exclude every query parameter whose name matches the rest of the pattern instead, and take precedence over the other names and patterns. This allows paginated or tracking-heavy URLs to be certified without listing every query parameter:
+
+```rust
+use ic_http_certification::DefaultCelBuilder;
+
+let cel_expr = DefaultCelBuilder::full_certification()
+    .with_request_query_parameters(vec!["*", "!utm_*"])
+    .build();
+
+assert_eq!(
+    cel_expr.request.certified_query("utm_source=newsletter&page=2&page_size=20"),
+    "page=2&page_size=20"
+);
+```
+
+Matching query parameters are certified in the order in which they appear in the URL, see [certified_query](cel::DefaultRequestCertification::certified_query).
+
+#### Certifying cookies
+
+Cookies usually carry per-user state, so certifying them requires a separate certification for every user and prevents responses from being shared between users by caches. For this reason, the `Cookie` request header and the `Set-Cookie` response header are removed from the header lists that are passed to [with_request_headers](cel::DefaultFullCelExpressionBuilder::with_request_headers) and [certified_response_headers](DefaultResponseCertification::certified_response_headers), and `Set-Cookie` is added to the list of excluded response headers that is passed to [response_header_exclusions](DefaultResponseCertification::response_header_exclusions). To certify cookies, opt in explicitly with [with_certified_request_cookies](cel::DefaultFullCelExpressionBuilder::with_certified_request_cookies) and [with_certified_response_cookies](cel::DefaultFullCelExpressionBuilder::with_certified_response_cookies):
+
+```rust
+use ic_http_certification::{DefaultCelBuilder, DefaultResponseCertification};
+
+let cel_expr = DefaultCelBuilder::full_certification()
+    .with_request_headers(vec!["Accept"])
+    .with_certified_request_cookies()
+    .with_response_certification(DefaultResponseCertification::certified_response_headers(vec![
+        "Cache-Control",
+    ]))
+    .with_certified_response_cookies()
+    .build();
+```
+
+Similarly, the `Authorization` and `Proxy-Authorization` request headers are removed from the header list that is passed to `with_request_headers`. Token-bound APIs can certify them with [with_sensitive_request_headers](cel::DefaultFullCelExpressionBuilder::with_sensitive_request_headers):
+
+```rust
+use ic_http_certification::DefaultCelBuilder;
+
+let cel_expr = DefaultCelBuilder::full_certification()
+    .with_request_headers(vec!["Accept"])
+    .with_sensitive_request_headers(vec!["Authorization"])
+    .build();
+```
+
+#### Certifying multipart form fields
+
+Upload endpoints often receive `multipart/form-data` bodies with large files. To certify only selected form fields, such as a title, instead of the raw request body, use [with_request_multipart_form_fields](cel::DefaultFullCelExpressionBuilder::with_request_multipart_form_fields). The request body is then parsed as `multipart/form-data`, using the `boundary` of its `Content-Type` header, and only the values of these fields are hashed, both when the certification is created and when it is verified. Fields that are not listed, such as the uploaded file, are not certified.
+
+```rust
+use ic_http_certification::DefaultCelBuilder;
+
+let cel_expr = DefaultCelBuilder::full_certification()
+    .with_request_headers(vec!["Content-Type"])
+    .with_request_multipart_form_fields(vec!["title", "description"])
+    .build();
+```
+
+To certify every part of the body instead, use [with_canonical_request_multipart_body](cel::DefaultFullCelExpressionBuilder::with_canonical_request_multipart_body). The hashed body is then the [canonical_multipart_body] of the request, which does not depend on the boundary chosen by the client or on the order of the parts, so the certification of an upload can be created before the request is received.
+
+```rust
+use ic_http_certification::DefaultCelBuilder;
+
+let cel_expr = DefaultCelBuilder::full_certification()
+    .with_request_headers(vec!["Content-Type"])
+    .with_canonical_request_multipart_body()
+    .build();
+```
+
+#### Certifying decoded bodies
+
+By default, the response body is certified exactly as it is served. To serve the same response with different content encodings, such as `gzip` and `br`, use [with_decoded_body_certification](cel::DefaultResponseOnlyCelBuilder::with_decoded_body_certification). Verifiers will then decode the body according to its `Content-Encoding` header before hashing it, so a single certification of the identity body covers every encoding. The `Content-Encoding` header must not be certified in this case, since it differs between encodings.
+
+```rust
+use ic_http_certification::{DefaultCelBuilder, DefaultResponseCertification};
+
+let cel_expr = DefaultCelBuilder::response_only_certification()
+    .with_response_certification(DefaultResponseCertification::certified_response_headers(vec![
+        "Cache-Control",
+    ]))
+    .with_decoded_body_certification()
+    .build();
+```
+
+#### Certifying status code classes
+
+By default, the exact status code of the response is certified. If a resource may be served with different status codes of the same class, such as `200` and `204`, use [with_status_code_class_certification](cel::DefaultResponseOnlyCelBuilder::with_status_code_class_certification) to certify only the class of the status code, such as `2xx`. A single certification then covers every status code of that class, as long as the rest of the response, including its body, is the same.
+
+```rust
+use ic_http_certification::DefaultCelBuilder;
+
+let cel_expr = DefaultCelBuilder::response_only_certification()
+    .with_status_code_class_certification()
+    .build();
+```
+
+#### Using presets
+
+For common scenarios, [DefaultCelBuilder] provides presets that return a preconfigured builder, so that new expressions do not need to be built from scratch:
+
+- [static_asset](DefaultCelBuilder::static_asset()) certifies the `Content-Type`, `Content-Encoding`, `Cache-Control` and `ETag` response headers of static assets, without certifying the request, and only allows `GET` and `HEAD` requests.
+- [json_api](DefaultCelBuilder::json_api()) certifies the `Accept` request header and the `Content-Type` and `Cache-Control` response headers of JSON APIs.
+- [no_cache_api](DefaultCelBuilder::no_cache_api()) additionally certifies the `Expires` and `Pragma` response headers, for responses that must not be cached.
+- [redirect](DefaultCelBuilder::redirect()) certifies the `Location` and `Cache-Control` response headers of redirects, without certifying the request.
+
+The presets never certify cookies or credentials, and they can be configured further before building the expression. For example, query parameters are specific to each API, so they must be added to the [json_api](DefaultCelBuilder::json_api()) preset:
+
+```rust
+use ic_http_certification::DefaultCelBuilder;
+
+let cel_expr = DefaultCelBuilder::json_api()
+    .with_request_query_parameters(vec!["page", "limit"])
+    .build();
+```
+
+#### Certifying allowed request methods
+
+By default, a certified response may be served for a request with any method. To restrict the request methods that a response may be served for, use the `with_allowed_methods` method of [DefaultCelBuilder]:
+
+```rust
+use ic_http_certification::DefaultCelBuilder;
+
+let cel_expr = DefaultCelBuilder::full_certification()
+    .with_allowed_methods(vec!["GET", "HEAD"])
+    .build();
+```
+
+The allowed methods are compared case-insensitively and are included in the CEL expression, so the verifier rejects responses to requests with any other method. Creating a [full](HttpCertification::full()) certification for a request with a method that is not allowed fails with an [UnexpectedRequestMethod](HttpCertificationError::UnexpectedRequestMethod) error.
+
 #### Skipping certification
 
 To skip certification entirely, use [DefaultCelBuilder::skip_certification](DefaultCelBuilder::skip_certification()), for example:
@@ -220,6 +470,7 @@ let response = HttpResponse {
     ],
     body: vec![1, 2, 3, 4, 5, 6],
     upgrade: None,
+    trailers: None,
 };
 
 let certification = HttpCertification::full(&cel_expr, &request, &response, None);
@@ -249,9 +500,35 @@ let response = HttpResponse {
     ],
     body: vec![1, 2, 3, 4, 5, 6],
     upgrade: None,
+    trailers: None,
 };
 
-let certification = HttpCertification::response_only(&cel_expr, &response, None);
+let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+```
+
+### Certifying response trailers
+
+Responses may carry [trailers](HttpResponse::trailers), such as a digest of a streamed body, that are sent after the body. Trailers are certified with the same rules as headers: a trailer is certified if a response header with the same name would be. The certified trailers are hashed together with the certified headers and body, so they must be passed to the verifier along with the rest of the response. Responses without certified trailers are hashed from their headers and body alone.
+
+```rust
+use ic_http_certification::{HttpCertification, HttpResponse, DefaultCelBuilder, DefaultResponseCertification};
+
+let cel_expr = DefaultCelBuilder::response_only_certification()
+    .with_response_certification(DefaultResponseCertification::certified_response_headers(vec![
+        "Content-Type",
+        "Digest",
+    ]))
+    .build();
+
+let response = HttpResponse::builder()
+    .ok()
+    .with_header("Content-Type", "text/plain")
+    .with_body(b"Hello World!".to_vec())
+    .with_trailer("Digest", "sha-256=f4OxZX/x/FO5LcGBSKHWXfwtSx+j1ncoSt3SABJtkGk=")
+    .build()
+    .unwrap();
+
+let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
 ```
 
 ### Skipping certification
@@ -329,6 +606,7 @@ let response = HttpResponse {
     ],
     body: vec![1, 2, 3, 4, 5, 6],
     upgrade: None,
+    trailers: None,
 };
 
 let request_url = "/example.json";
@@ -349,6 +627,79 @@ let witness = http_certification_tree.witness(&entry, request_url);
 http_certification_tree.delete(&entry);
 ```
 
+### Serving certified responses
+
+To make sure that a response is never served without its certificate, use [HttpCertificationTree::certify_response] to wrap the response in a [CertifiedResponse]. This will fail if the entry is not present in the tree, or if the certification of the entry does not match the response. The certification is recomputed for the response according to the CEL expression that it was created with, so the CEL expression must be provided as well, together with the request if the CEL expression is a [full](DefaultCelExpression::Full) expression. The [CertifiedResponse] can then be turned into the final response with [serve](CertifiedResponse::serve), which attaches the `IC-Certificate` header using the canister's data certificate. Serving a response CBOR encodes its witness, so it requires the `serde` feature.
+
+For example:
+
+```rust
+use ic_http_certification::{HttpCertification, HttpResponse, DefaultCelBuilder, DefaultCelExpression, HttpCertificationTree, HttpCertificationTreeEntry, HttpCertificationPath};
+
+let cel_expr = DefaultCelBuilder::response_only_certification().build();
+
+let response = HttpResponse {
+    status_code: 200,
+    headers: vec![],
+    body: vec![1, 2, 3, 4, 5, 6],
+    upgrade: None,
+    trailers: None,
+};
+
+let request_url = "/example.json";
+let path = HttpCertificationPath::exact(request_url);
+let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+
+let mut http_certification_tree = HttpCertificationTree::default();
+let entry = HttpCertificationTreeEntry::new(&path, &certification);
+http_certification_tree.insert(&entry);
+
+// in a canister, this would be the result of `ic_cdk::api::data_certificate()`
+let data_certificate = vec![1, 2, 3];
+
+let certified_response = http_certification_tree
+    .certify_response(
+        &entry,
+        request_url,
+        &DefaultCelExpression::ResponseOnly(cel_expr),
+        None,
+        &response,
+        None,
+    )
+    .unwrap();
+# #[cfg(feature = "serde")]
+let response = certified_response.serve(&data_certificate).unwrap();
+```
+
+### Serving "not found" responses
+
+A canister that receives a request for a URL that it never certified can prove that no certification exists for it, neither for the exact path nor for any wildcard path matching it. Use `HttpCertificationTree::certify_absence` to wrap the "not found" response with an absence proof. This will fail if a certification for the URL is present in the tree. Clients can check the proof with `is_certification_absent`, or with `verify_certification_absence` from the `ic-response-verification` crate, but note that the response itself is not certified. As with certified responses, serving the absence proof requires the `serde` feature.
+
+For example:
+
+```rust
+use ic_http_certification::{HttpResponse, HttpCertificationTree};
+
+let http_certification_tree = HttpCertificationTree::default();
+
+let response = HttpResponse {
+    status_code: 404,
+    headers: vec![],
+    body: b"Not Found".to_vec(),
+    upgrade: None,
+    trailers: None,
+};
+
+// in a canister, this would be the result of `ic_cdk::api::data_certificate()`
+let data_certificate = vec![1, 2, 3];
+
+let certified_response = http_certification_tree
+    .certify_absence("/not-found.html", &response)
+    .unwrap();
+# #[cfg(feature = "serde")]
+let response = certified_response.serve(&data_certificate).unwrap();
+```
+
 ## Directly creating a CEL expression
 
 To define a CEL expression, start with the [CelExpression](cel::CelExpression) enum. This enum provides a set of variants that can be used to define different types of CEL expressions supported by ICP HTTP gateways. Currently only one variant is supported, known as the "default" certification expression, but more may be added in the future as the HTTP certification protocol evolves over time.
@@ -371,7 +722,7 @@ To define a fully certified request and response pair, including request headers
 
 ```rust
 use std::borrow::Cow;
-use ic_http_certification::cel::{CelExpression, DefaultCelExpression, DefaultFullCelExpression, DefaultRequestCertification, DefaultResponseCertification};
+use ic_http_certification::cel::{CelExpression, DefaultCelExpression, DefaultFullCelExpression, DefaultRequestCertification, DefaultResponseCertification, DefaultBodyCertification, DefaultStatusCodeCertification};
 
 let cel_expr = CelExpression::Default(DefaultCelExpression::Full(
   DefaultFullCelExpression {
@@ -383,6 +734,9 @@ let cel_expr = CelExpression::Default(DefaultCelExpression::Full(
       "ETag",
       "Cache-Control",
     ]),
+    body: DefaultBodyCertification::AsServed,
+    status: DefaultStatusCodeCertification::Exact,
+    allowed_methods: Cow::Borrowed(&[]),
   }));
 ```
 
@@ -415,7 +769,7 @@ For example, to certify only the request body and method:
 
 ```rust
 use std::borrow::Cow;
-use ic_http_certification::cel::{CelExpression, DefaultCelExpression, DefaultFullCelExpression, DefaultRequestCertification, DefaultResponseCertification};
+use ic_http_certification::cel::{CelExpression, DefaultCelExpression, DefaultFullCelExpression, DefaultRequestCertification, DefaultResponseCertification, DefaultBodyCertification, DefaultStatusCodeCertification};
 
 let cel_expr = CelExpression::Default(DefaultCelExpression::Full(
   DefaultFullCelExpression {
@@ -427,6 +781,9 @@ let cel_expr = CelExpression::Default(DefaultCelExpression::Full(
       "ETag",
       "Cache-Control",
     ]),
+    body: DefaultBodyCertification::AsServed,
+    status: DefaultStatusCodeCertification::Exact,
+    allowed_methods: Cow::Borrowed(&[]),
   }));
 ```
 
@@ -459,7 +816,7 @@ For example:
 
 ```rust
 use std::borrow::Cow;
-use ic_http_certification::cel::{CelExpression, DefaultCelExpression, DefaultResponseOnlyCelExpression, DefaultResponseCertification};
+use ic_http_certification::cel::{CelExpression, DefaultCelExpression, DefaultResponseOnlyCelExpression, DefaultResponseCertification, DefaultBodyCertification, DefaultStatusCodeCertification};
 
 let cel_expr = CelExpression::Default(DefaultCelExpression::ResponseOnly(
   DefaultResponseOnlyCelExpression {
@@ -467,6 +824,9 @@ let cel_expr = CelExpression::Default(DefaultCelExpression::ResponseOnly(
       "ETag",
       "Cache-Control",
     ]),
+    body: DefaultBodyCertification::AsServed,
+    status: DefaultStatusCodeCertification::Exact,
+    allowed_methods: Cow::Borrowed(&[]),
   }));
 ```
 
@@ -496,7 +856,7 @@ For example:
 
 ```rust
 use std::borrow::Cow;
-use ic_http_certification::cel::{CelExpression, DefaultCelExpression, DefaultFullCelExpression, DefaultRequestCertification, DefaultResponseCertification};
+use ic_http_certification::cel::{CelExpression, DefaultCelExpression, DefaultFullCelExpression, DefaultRequestCertification, DefaultResponseCertification, DefaultBodyCertification, DefaultStatusCodeCertification};
 
 
 let cel_expr = CelExpression::Default(DefaultCelExpression::Full(
@@ -506,6 +866,9 @@ let cel_expr = CelExpression::Default(DefaultCelExpression::Full(
       vec!["foo", "bar", "baz"],
     ),
     response: DefaultResponseCertification::certified_response_headers(vec![]),
+    body: DefaultBodyCertification::AsServed,
+    status: DefaultStatusCodeCertification::Exact,
+    allowed_methods: Cow::Borrowed(&[]),
   }));
 ```
 
@@ -531,7 +894,7 @@ If the [response_header_exclusions](DefaultResponseCertification::response_heade
 
 ```rust
 use std::borrow::Cow;
-use ic_http_certification::cel::{CelExpression, DefaultCelExpression, DefaultFullCelExpression, DefaultRequestCertification, DefaultResponseCertification};
+use ic_http_certification::cel::{CelExpression, DefaultCelExpression, DefaultFullCelExpression, DefaultRequestCertification, DefaultResponseCertification, DefaultBodyCertification, DefaultStatusCodeCertification};
 
 let cel_expr = CelExpression::Default(DefaultCelExpression::Full(
   DefaultFullCelExpression {
@@ -540,6 +903,9 @@ let cel_expr = CelExpression::Default(DefaultCelExpression::Full(
       vec!["foo", "bar", "baz"],
     ),
     response: DefaultResponseCertification::response_header_exclusions(vec![]),
+    body: DefaultBodyCertification::AsServed,
+    status: DefaultStatusCodeCertification::Exact,
+    allowed_methods: Cow::Borrowed(&[]),
   }));
 ```
 
@@ -582,6 +948,52 @@ default_certification (
   }
 )
 ```
+
+## Candid
+
+The `candid` feature, which is enabled by default, derives `CandidType` and `Deserialize` for the [HttpRequest] and [HttpResponse] types, so that they can be used directly as the arguments and results of a canister's `http_request` and `http_request_update` endpoints, without mirror structs. Off-chain tools that do not need Candid can disable default features to avoid the dependency.
+
+## Serde
+
+The `serde` feature, which is disabled by default, implements `Serialize` and `Deserialize` for [HttpRequest], [HttpResponse], the CEL expression types, such as [DefaultCelExpression], and [HttpCertificationPath]. This allows test fixtures, replay tooling and off-chain pipelines to persist them, for example as JSON or CBOR. CEL expressions borrow their header and query parameter names from the serialized data, so they must be deserialized from a buffer that outlives them. It is also required to serve a [CertifiedResponse], since its witness is CBOR encoded for the `IC-Certificate` header.
+
+## TypeScript types
+
+Enabling the `ts` feature derives [ts-rs](https://crates.io/crates/ts-rs) TypeScript definitions for the [HttpRequest], [HttpResponse] and [DefaultResponseCertification] types, as they are represented by the JavaScript bindings. Running `cargo test --features ts` exports these definitions to the `bindings` directory of this crate.
+
+The `@dfinity/response-verification` and `@dfinity/http-certification` packages test their TypeScript definitions against these types, so that the definitions published to NPM cannot drift from the Rust types.
+
+## Property testing
+
+Enabling the `testing` feature exposes the `strategies` module, containing [proptest](https://crates.io/crates/proptest) strategies for random requests, responses, CEL expressions and certification paths. These are the same strategies that this crate uses for its own property tests, so integrations can be property tested against the same distributions. [HttpRequest], [HttpResponse], the CEL expression types and [HttpCertificationPath] also implement proptest's `Arbitrary` trait, so they can be generated with `any::<HttpRequest>()`.
+
+## Response compression
+
+Enabling the `compression` feature adds [HttpResponse::compress], which compresses a response body with `gzip`, `deflate` or `br` and sets the `Content-Encoding` header, so that the response can be certified and served as is. Since the body is compressed before it is certified, the certified hash always corresponds to the encoded bytes that are served.
+
+```rust,ignore
+use ic_http_certification::{ContentEncoding, DefaultCelBuilder, HttpCertification, HttpResponse};
+
+let cel_expr = DefaultCelBuilder::response_only_certification().build();
+let response = HttpResponse::builder()
+    .ok()
+    .with_header("Content-Type", "text/plain")
+    .with_body(b"Hello World!")
+    .build()
+    .unwrap()
+    .compress(ContentEncoding::Brotli)
+    .unwrap();
+
+let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+```
+
+## `http` crate interoperability
+
+Enabling the `http-crate` feature adds conversions between the [HttpRequest] and [HttpResponse] types and the `Request` and `Response` types of the [http](https://crates.io/crates/http) crate, so that off-chain tools and gateways built on the `http` ecosystem can hash and verify requests and responses without converting them by hand. Converting to an `http` type is fallible, since the `http` crate rejects invalid methods, URLs, status codes and header names.
+
+## `ic-cdk` interoperability
+
+Enabling the `ic-cdk` feature adds the `GatewayHttpRequest` and `GatewayHttpResponse` types, the Candid records that the HTTP Gateway exchanges with a canister's `http_request` query endpoint, so that canisters built with the [ic-cdk](https://crates.io/crates/ic-cdk) crate can use them as the argument and return type of the endpoint without defining the records by hand. Unlike [HttpRequest], `GatewayHttpRequest` includes the `certificate_version` requested by the HTTP Gateway. Both types can be converted to and from [HttpRequest] and [HttpResponse] with [From], for any request method and keeping the `upgrade` field of the response. Response trailers have no equivalent in the HTTP Gateway Protocol and are discarded.
 */
 
 #![deny(missing_docs, missing_debug_implementations, rustdoc::all, clippy::all)]
@@ -599,6 +1011,8 @@ pub mod http;
 pub use crate::http::*;
 pub mod tree;
 pub use tree::*;
+#[cfg(any(test, feature = "testing"))]
+pub mod strategies;
 
 // https://github.com/la10736/rstest/tree/master/rstest_reuse#cavelets
 #[cfg(test)]
@@ -0,0 +1,352 @@
+//! The strategies module contains [proptest] strategies for generating random requests,
+//! responses, CEL expressions and certification paths.
+//!
+//! These are the same strategies that this crate uses for its own property tests, so that
+//! downstream crates can property test their integrations against the same distributions.
+//! Every generated value can be certified, i.e. generated responses pass
+//! [validation](HttpResponse::validate) and never have a body when their status code does not
+//! permit one.
+//!
+//! The generated types also implement [Arbitrary], using these strategies, so they can be
+//! generated with [any], for example `any::<HttpRequest>()`.
+
+use crate::{
+    status_code_permits_body, CelExpression, DefaultCelBuilder, DefaultCelExpression,
+    DefaultFullCelExpression, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+    HeaderField, HttpCertificationPath, HttpRequest, HttpResponse,
+};
+use proptest::{prelude::*, sample::subsequence};
+
+/// The header names used by the generated requests, responses and CEL expressions.
+///
+/// None of these headers are restricted to appearing once in a response, so they can be repeated
+/// freely.
+pub const HEADER_NAMES: &[&str] = &[
+    "Accept",
+    "Accept-Encoding",
+    "Accept-Language",
+    "Cache-Control",
+    "ETag",
+    "Expires",
+    "If-None-Match",
+    "Last-Modified",
+    "Strict-Transport-Security",
+    "Vary",
+    "X-Content-Type-Options",
+    "X-Frame-Options",
+];
+
+/// The query parameter names used by the generated requests and CEL expressions.
+pub const QUERY_PARAMETER_NAMES: &[&str] = &["page", "limit", "sort", "q", "lang"];
+
+/// The request methods used by the generated requests.
+pub const REQUEST_METHODS: &[&str] = &["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE"];
+
+/// Generates a subset of [HEADER_NAMES], without duplicates.
+pub fn header_names() -> impl Strategy<Value = Vec<&'static str>> {
+    subsequence(HEADER_NAMES, 0..=HEADER_NAMES.len())
+}
+
+/// Generates a subset of [QUERY_PARAMETER_NAMES], without duplicates.
+pub fn query_parameter_names() -> impl Strategy<Value = Vec<&'static str>> {
+    subsequence(QUERY_PARAMETER_NAMES, 0..=QUERY_PARAMETER_NAMES.len())
+}
+
+/// Generates a list of headers with names from [HEADER_NAMES] and printable ASCII values.
+/// Header names may be repeated.
+pub fn headers() -> impl Strategy<Value = Vec<HeaderField>> {
+    prop::collection::vec((prop::sample::select(HEADER_NAMES), "[ -~]{0,32}"), 0..8).prop_map(
+        |headers| {
+            headers
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect()
+        },
+    )
+}
+
+/// Generates an absolute URL path, such as `/assets/app.js` or `/docs/`.
+pub fn url_path() -> impl Strategy<Value = String> {
+    (
+        prop::collection::vec("[a-zA-Z0-9._~-]{1,12}", 0..5),
+        any::<bool>(),
+    )
+        .prop_map(|(segments, trailing_slash)| {
+            let path = format!("/{}", segments.join("/"));
+
+            match trailing_slash && !segments.is_empty() {
+                true => format!("{path}/"),
+                false => path,
+            }
+        })
+}
+
+/// Generates a URL, consisting of a [path](url_path) and optional query parameters with names
+/// from [QUERY_PARAMETER_NAMES].
+pub fn url() -> impl Strategy<Value = String> {
+    (
+        url_path(),
+        prop::collection::vec(
+            (
+                prop::sample::select(QUERY_PARAMETER_NAMES),
+                "[a-zA-Z0-9]{0,8}",
+            ),
+            0..4,
+        ),
+    )
+        .prop_map(|(path, query_parameters)| {
+            if query_parameters.is_empty() {
+                return path;
+            }
+
+            let query = query_parameters
+                .into_iter()
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect::<Vec<_>>()
+                .join("&");
+
+            format!("{path}?{query}")
+        })
+}
+
+/// Generates an [HttpRequest] with a method from [REQUEST_METHODS], a random [url], [headers]
+/// and body.
+pub fn http_request() -> impl Strategy<Value = HttpRequest> {
+    (
+        prop::sample::select(REQUEST_METHODS),
+        url(),
+        headers(),
+        prop::collection::vec(any::<u8>(), 0..64),
+    )
+        .prop_map(|(method, url, headers, body)| HttpRequest {
+            method: method.to_string(),
+            url,
+            headers,
+            body,
+        })
+}
+
+/// Generates an [HttpResponse] with a status code between 100 and 599, random [headers] and
+/// body. Responses with a status code that does not permit a body have an empty body.
+pub fn http_response() -> impl Strategy<Value = HttpResponse> {
+    (
+        100u16..600,
+        headers(),
+        prop::collection::vec(any::<u8>(), 0..64),
+    )
+        .prop_map(|(status_code, headers, body)| HttpResponse {
+            status_code,
+            headers,
+            body: match status_code_permits_body(status_code) {
+                true => body,
+                false => vec![],
+            },
+            upgrade: None,
+            trailers: None,
+        })
+}
+
+/// Generates a [DefaultResponseCertification] that either includes or excludes a subset of
+/// [HEADER_NAMES].
+pub fn default_response_certification(
+) -> impl Strategy<Value = DefaultResponseCertification<'static>> {
+    prop_oneof![
+        header_names().prop_map(DefaultResponseCertification::certified_response_headers),
+        header_names().prop_map(DefaultResponseCertification::response_header_exclusions),
+    ]
+}
+
+/// Generates a [DefaultResponseOnlyCelExpression] with a random
+/// [response certification](default_response_certification).
+pub fn default_response_only_cel_expression(
+) -> impl Strategy<Value = DefaultResponseOnlyCelExpression<'static>> {
+    (default_response_certification(), any::<bool>()).prop_map(
+        |(response_certification, decoded_body)| {
+            let builder = DefaultCelBuilder::response_only_certification()
+                .with_response_certification(response_certification);
+
+            match decoded_body {
+                true => builder.with_decoded_body_certification().build(),
+                false => builder.build(),
+            }
+        },
+    )
+}
+
+/// Generates a [DefaultFullCelExpression] with random request headers, query parameters and
+/// [response certification](default_response_certification).
+pub fn default_full_cel_expression() -> impl Strategy<Value = DefaultFullCelExpression<'static>> {
+    (
+        header_names(),
+        query_parameter_names(),
+        default_response_certification(),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(request_headers, query_parameters, response_certification, decoded_body)| {
+                let builder = DefaultCelBuilder::full_certification()
+                    .with_request_headers(request_headers)
+                    .with_request_query_parameters(query_parameters)
+                    .with_response_certification(response_certification);
+
+                match decoded_body {
+                    true => builder.with_decoded_body_certification().build(),
+                    false => builder.build(),
+                }
+            },
+        )
+}
+
+/// Generates a [CelExpression] that either skips certification, or certifies a
+/// [response only](default_response_only_cel_expression) or a
+/// [full request and response pair](default_full_cel_expression).
+pub fn cel_expression() -> impl Strategy<Value = CelExpression<'static>> {
+    prop_oneof![
+        Just(DefaultCelExpression::Skip),
+        default_response_only_cel_expression().prop_map(DefaultCelExpression::ResponseOnly),
+        default_full_cel_expression().prop_map(DefaultCelExpression::Full),
+    ]
+    .prop_map(CelExpression::Default)
+}
+
+/// Generates an [exact](HttpCertificationPath::exact) or
+/// [wildcard](HttpCertificationPath::wildcard) [HttpCertificationPath] for a random
+/// [url_path].
+pub fn http_certification_path() -> impl Strategy<Value = HttpCertificationPath<'static>> {
+    prop_oneof![
+        url_path().prop_map(HttpCertificationPath::exact),
+        url_path().prop_map(HttpCertificationPath::wildcard),
+    ]
+}
+
+impl Arbitrary for HttpRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        http_request().boxed()
+    }
+}
+
+impl Arbitrary for HttpResponse {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        http_response().boxed()
+    }
+}
+
+impl Arbitrary for DefaultResponseCertification<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        default_response_certification().boxed()
+    }
+}
+
+impl Arbitrary for DefaultResponseOnlyCelExpression<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        default_response_only_cel_expression().boxed()
+    }
+}
+
+impl Arbitrary for DefaultFullCelExpression<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        default_full_cel_expression().boxed()
+    }
+}
+
+impl Arbitrary for CelExpression<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        cel_expression().boxed()
+    }
+}
+
+impl Arbitrary for HttpCertificationPath<'static> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        http_certification_path().boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cel::{create_cel_expr, parse_cel_expression},
+        HttpCertification, HttpCertificationTree, HttpCertificationTreeEntry,
+    };
+
+    proptest! {
+        #[test]
+        fn generated_cel_expressions_round_trip(cel_expr in cel_expression()) {
+            let cel_expr_str = create_cel_expr(&cel_expr);
+
+            let parsed_cel_expr = parse_cel_expression(&cel_expr_str).unwrap();
+
+            prop_assert_eq!(create_cel_expr(&parsed_cel_expr), cel_expr_str);
+        }
+
+        #[test]
+        fn generated_responses_can_be_certified(
+            cel_expr in default_full_cel_expression(),
+            request in http_request(),
+            response in http_response(),
+        ) {
+            prop_assert!(response.validate().is_ok());
+            prop_assert!(HttpCertification::full(&cel_expr, &request, &response, None).is_ok());
+        }
+
+        #[test]
+        fn generated_entries_can_be_witnessed(
+            cel_expr in default_response_only_cel_expression(),
+            response in http_response(),
+            path in http_certification_path(),
+            request_url in url_path(),
+        ) {
+            let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+            let entry = HttpCertificationTreeEntry::new(path, certification);
+            let cel_expr = DefaultCelExpression::ResponseOnly(cel_expr);
+            let mut tree = HttpCertificationTree::default();
+
+            tree.insert(&entry);
+            prop_assert_eq!(tree.witness(&entry, &request_url).digest(), tree.root_hash());
+            prop_assert!(tree
+                .certify_response(&entry, &request_url, &cel_expr, None, &response, None)
+                .is_ok());
+
+            tree.delete(&entry);
+            prop_assert!(tree
+                .certify_response(&entry, &request_url, &cel_expr, None, &response, None)
+                .is_err());
+        }
+
+        #[test]
+        fn arbitrary_values_can_be_certified(
+            cel_expr in any::<DefaultFullCelExpression>(),
+            request in any::<HttpRequest>(),
+            response in any::<HttpResponse>(),
+            path in any::<HttpCertificationPath>(),
+        ) {
+            let certification = HttpCertification::full(&cel_expr, &request, &response, None).unwrap();
+            let entry = HttpCertificationTreeEntry::new(path, certification);
+            let mut tree = HttpCertificationTree::default();
+
+            tree.insert(&entry);
+            prop_assert_eq!(tree.witness(&entry, "/").digest(), tree.root_hash());
+        }
+    }
+}
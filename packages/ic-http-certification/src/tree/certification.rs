@@ -1,6 +1,9 @@
 use crate::{
-    request_hash, response_hash, DefaultCelBuilder, DefaultFullCelExpression,
-    DefaultResponseOnlyCelExpression, HttpCertificationResult, HttpRequest, HttpResponse,
+    cel::DefaultBodyCertification, find_fragments_header, fragmented_body_hash, request_hash,
+    response_hash_with_status_certification, split_fragments, DefaultCelBuilder,
+    DefaultCelExpression, DefaultFullCelExpression, DefaultResponseOnlyCelExpression,
+    HttpCertificationError, HttpCertificationResult, HttpRequest, HttpRequestRef, HttpResponse,
+    HttpResponseRef,
 };
 use ic_certification::Hash;
 use ic_representation_independent_hash::hash;
@@ -50,31 +53,88 @@ impl HttpCertification {
 
     /// Creates a certification that includes an [HTTP response](crate::HttpResponse), but excludes the
     /// corresponding [HTTP request](crate::HttpRequest).
-    pub fn response_only(
+    ///
+    /// Returns an error if the response is malformed, see [HttpResponse::validate](crate::HttpResponse::validate), or if it has a
+    /// body, but its status code does not permit one.
+    ///
+    /// Responses that [upgrade](crate::HttpResponse::is_upgrade) the request to an update call are
+    /// rejected, since the HTTP Gateway discards them without verifying them.
+    ///
+    /// If the CEL expression uses [fragmented](DefaultBodyCertification::Fragmented) body
+    /// certification and no response body hash is provided, the body is split into the fragments
+    /// listed in its [IC-CertificateFragments](crate::CERTIFICATE_FRAGMENTS_HEADER_NAME) header.
+    ///
+    /// The response may be borrowed as an [HttpResponseRef], so that its body is not copied.
+    pub fn response_only<'a>(
         cel_expr: &DefaultResponseOnlyCelExpression,
-        response: &HttpResponse,
+        response: impl Into<HttpResponseRef<'a>>,
         response_body_hash: Option<Hash>,
-    ) -> HttpCertification {
+    ) -> HttpCertificationResult<HttpCertification> {
+        let response = response.into();
+        response.validate()?;
+        if response.is_upgrade() {
+            return Err(HttpCertificationError::UnexpectedUpgrade);
+        }
+
         let cel_expr_hash = hash(cel_expr.to_string().as_bytes());
-        let response_hash = response_hash(response, &cel_expr.response, response_body_hash);
+        let response_body_hash = body_hash(&response, &cel_expr.body, response_body_hash)?;
+        let response_hash = response_hash_with_status_certification(
+            response,
+            &cel_expr.response,
+            &cel_expr.status,
+            response_body_hash,
+        )?;
 
-        Self(HttpCertificationType::ResponseOnly {
+        Ok(Self(HttpCertificationType::ResponseOnly {
             cel_expr_hash,
             response_hash,
-        })
+        }))
     }
 
     /// Creates a certification that includes both an [HTTP response](crate::HttpResponse) and the corresponding
     /// [HTTP request](crate::HttpRequest).
-    pub fn full(
+    ///
+    /// Returns an error if the response is malformed, see [HttpResponse::validate](crate::HttpResponse::validate), if the
+    /// request or response cannot be hashed, or if the request method is not one of the
+    /// [allowed methods](DefaultFullCelExpression::allowed_methods) of the CEL expression.
+    ///
+    /// Responses that [upgrade](crate::HttpResponse::is_upgrade) the request to an update call are
+    /// rejected, since the HTTP Gateway discards them without verifying them.
+    ///
+    /// Fragmented response bodies are handled in the same way as
+    /// [response_only](HttpCertification::response_only).
+    ///
+    /// The request and response may be borrowed as an [HttpRequestRef] and [HttpResponseRef], so
+    /// that their bodies are not copied.
+    pub fn full<'a>(
         cel_expr: &DefaultFullCelExpression,
-        request: &HttpRequest,
-        response: &HttpResponse,
+        request: impl Into<HttpRequestRef<'a>>,
+        response: impl Into<HttpResponseRef<'a>>,
         response_body_hash: Option<Hash>,
     ) -> HttpCertificationResult<HttpCertification> {
+        let response = response.into();
+        response.validate()?;
+        if response.is_upgrade() {
+            return Err(HttpCertificationError::UnexpectedUpgrade);
+        }
+
+        let request = request.into();
+        if !cel_expr.allows_method(&request.method) {
+            return Err(HttpCertificationError::UnexpectedRequestMethod {
+                expected: cel_expr.allowed_methods.join(", "),
+                found: request.method.into_owned(),
+            });
+        }
+
         let cel_expr_hash = hash(cel_expr.to_string().as_bytes());
         let request_hash = request_hash(request, &cel_expr.request)?;
-        let response_hash = response_hash(response, &cel_expr.response, response_body_hash);
+        let response_body_hash = body_hash(&response, &cel_expr.body, response_body_hash)?;
+        let response_hash = response_hash_with_status_certification(
+            response,
+            &cel_expr.response,
+            &cel_expr.status,
+            response_body_hash,
+        )?;
 
         Ok(Self(HttpCertificationType::Full {
             cel_expr_hash,
@@ -83,6 +143,97 @@ impl HttpCertification {
         }))
     }
 
+    /// Creates a response only or full certification from precomputed hashes, depending on
+    /// whether a request hash is provided.
+    pub(super) fn from_hashes(
+        cel_expr_hash: Hash,
+        request_hash: Option<Hash>,
+        response_hash: Hash,
+    ) -> HttpCertification {
+        match request_hash {
+            Some(request_hash) => Self(HttpCertificationType::Full {
+                cel_expr_hash,
+                request_hash,
+                response_hash,
+            }),
+            None => Self(HttpCertificationType::ResponseOnly {
+                cel_expr_hash,
+                response_hash,
+            }),
+        }
+    }
+
+    /// Recreates a certification from the segments of its tree path, as created by
+    /// [to_tree_path](HttpCertification::to_tree_path).
+    pub(super) fn from_tree_path(segments: &[Vec<u8>]) -> Option<HttpCertification> {
+        match segments {
+            [cel_expr_hash] => Some(Self(HttpCertificationType::Skip {
+                cel_expr_hash: cel_expr_hash.as_slice().try_into().ok()?,
+            })),
+            [cel_expr_hash, request_hash, response_hash] => {
+                let request_hash = match request_hash.is_empty() {
+                    true => None,
+                    false => Some(request_hash.as_slice().try_into().ok()?),
+                };
+
+                Some(Self::from_hashes(
+                    cel_expr_hash.as_slice().try_into().ok()?,
+                    request_hash,
+                    response_hash.as_slice().try_into().ok()?,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if this certification matches the certification of `response`, recomputed
+    /// according to `cel_expr` in the same way as [response_only](HttpCertification::response_only)
+    /// and [full](HttpCertification::full).
+    ///
+    /// `request` is only required if `cel_expr` is a [full](DefaultCelExpression::Full)
+    /// expression, and `response_body_hash` must be the same hash that was provided when the
+    /// certification was created.
+    pub(super) fn matches_response(
+        &self,
+        cel_expr: &DefaultCelExpression,
+        request: Option<&HttpRequest>,
+        response: &HttpResponse,
+        response_body_hash: Option<Hash>,
+    ) -> HttpCertificationResult<bool> {
+        let certification = match cel_expr {
+            DefaultCelExpression::Skip => Self::skip(),
+            DefaultCelExpression::ResponseOnly(cel_expr) => {
+                Self::response_only(cel_expr, response, response_body_hash)?
+            }
+            DefaultCelExpression::Full(cel_expr) => {
+                let request = request.ok_or(HttpCertificationError::MissingCertifiedRequest)?;
+
+                Self::full(cel_expr, request, response, response_body_hash)?
+            }
+        };
+
+        Ok(certification == *self)
+    }
+
+    /// Returns true if this certification excludes both the request and the response.
+    pub(super) fn is_skip(&self) -> bool {
+        matches!(self.0, HttpCertificationType::Skip { .. })
+    }
+
+    /// Returns the hash of the CEL expression of this certification.
+    pub(super) fn cel_expr_hash(&self) -> Hash {
+        match self.0 {
+            HttpCertificationType::Skip { cel_expr_hash }
+            | HttpCertificationType::ResponseOnly { cel_expr_hash, .. }
+            | HttpCertificationType::Full { cel_expr_hash, .. } => cel_expr_hash,
+        }
+    }
+
+    /// Returns true if this certification includes the request.
+    pub(super) fn is_full(&self) -> bool {
+        matches!(self.0, HttpCertificationType::Full { .. })
+    }
+
     pub(crate) fn to_tree_path(self) -> Vec<Vec<u8>> {
         match self.0 {
             HttpCertificationType::Skip { cel_expr_hash } => vec![cel_expr_hash.to_vec()],
@@ -107,6 +258,31 @@ impl HttpCertification {
     }
 }
 
+/// Calculates the hash of a [fragmented](DefaultBodyCertification::Fragmented) response body if no
+/// response body hash was provided, and the body is not [streamed](crate::Body::Streaming) with a
+/// precomputed hash.
+fn body_hash(
+    response: &HttpResponseRef,
+    body_certification: &DefaultBodyCertification,
+    response_body_hash: Option<Hash>,
+) -> HttpCertificationResult<Option<Hash>> {
+    let response_body_hash = response_body_hash.or_else(|| response.body.body_hash());
+
+    match (body_certification, response_body_hash) {
+        (DefaultBodyCertification::Fragmented, None) => {
+            let header_value = find_fragments_header(&response.headers)?;
+            let fragment_hashes: Vec<Hash> =
+                split_fragments(response.body.first_chunk(), header_value)?
+                    .into_iter()
+                    .map(hash)
+                    .collect();
+
+            Ok(Some(fragmented_body_hash(&fragment_hashes)))
+        }
+        _ => Ok(response_body_hash),
+    }
+}
+
 impl<'a> From<HttpCertification> for Cow<'a, HttpCertification> {
     fn from(cert: HttpCertification) -> Cow<'a, HttpCertification> {
         Cow::Owned(cert)
@@ -122,7 +298,11 @@ impl<'a> From<&'a HttpCertification> for Cow<'a, HttpCertification> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::DefaultResponseCertification;
+    use crate::{
+        fragments_header_value, response_hash, Body, DefaultResponseCertification,
+        HttpCertificationError, HttpRequest, HttpResponse, StreamingBody,
+        CERTIFICATE_FRAGMENTS_HEADER_NAME,
+    };
     use rstest::*;
 
     #[rstest]
@@ -153,10 +333,11 @@ mod tests {
             body: vec![],
             headers: vec![],
             upgrade: None,
+            trailers: None,
         };
-        let expected_response_hash = response_hash(response, &cel_expr.response, None);
+        let expected_response_hash = response_hash(response, &cel_expr.response, None).unwrap();
 
-        let result = HttpCertification::response_only(&cel_expr, response, None);
+        let result = HttpCertification::response_only(&cel_expr, response, None).unwrap();
 
         assert!(matches!(
             result.0,
@@ -176,6 +357,30 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn status_code_class_certification() {
+        let class_cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_status_code_class_certification()
+            .build();
+        let exact_cel_expr = DefaultCelBuilder::response_only_certification().build();
+        let certify = |cel_expr, status_code| {
+            let response = &HttpResponse {
+                status_code,
+                body: vec![],
+                headers: vec![],
+                upgrade: None,
+                trailers: None,
+            };
+
+            HttpCertification::response_only(cel_expr, response, None).unwrap()
+        };
+
+        assert_eq!(certify(&class_cel_expr, 200), certify(&class_cel_expr, 204));
+        assert_ne!(certify(&class_cel_expr, 200), certify(&class_cel_expr, 404));
+        assert_ne!(certify(&exact_cel_expr, 200), certify(&exact_cel_expr, 204));
+        assert_ne!(certify(&class_cel_expr, 200), certify(&exact_cel_expr, 200));
+    }
+
     #[rstest]
     fn full_certification() {
         let cel_expr = DefaultCelBuilder::full_certification()
@@ -200,8 +405,9 @@ mod tests {
             body: vec![],
             headers: vec![],
             upgrade: None,
+            trailers: None,
         };
-        let expected_response_hash = response_hash(response, &cel_expr.response, None);
+        let expected_response_hash = response_hash(response, &cel_expr.response, None).unwrap();
 
         let result = HttpCertification::full(&cel_expr, request, response, None).unwrap();
 
@@ -224,4 +430,169 @@ mod tests {
             ]
         );
     }
+
+    #[rstest]
+    fn certifications_reject_invalid_response() {
+        let request = &HttpRequest {
+            body: vec![],
+            headers: vec![],
+            method: "GET".to_string(),
+            url: "/index.html".to_string(),
+        };
+        let response = &HttpResponse {
+            status_code: 200,
+            body: vec![],
+            headers: vec![
+                ("Content-Type".to_string(), "text/html".to_string()),
+                ("Content-Type".to_string(), "text/plain".to_string()),
+            ],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let response_only_result = HttpCertification::response_only(
+            &DefaultCelBuilder::response_only_certification().build(),
+            response,
+            None,
+        );
+        let full_result = HttpCertification::full(
+            &DefaultCelBuilder::full_certification().build(),
+            request,
+            response,
+            None,
+        );
+
+        assert!(matches!(
+            response_only_result,
+            Err(HttpCertificationError::DuplicateHeader(header_name)) if header_name == "Content-Type"
+        ));
+        assert!(matches!(
+            full_result,
+            Err(HttpCertificationError::DuplicateHeader(header_name)) if header_name == "Content-Type"
+        ));
+    }
+
+    #[rstest]
+    #[case::allowed("GET", true)]
+    #[case::allowed_lowercase("head", true)]
+    #[case::not_allowed("POST", false)]
+    fn full_certification_with_allowed_methods(#[case] method: &str, #[case] allowed: bool) {
+        let cel_expr = DefaultCelBuilder::full_certification()
+            .with_allowed_methods(vec!["GET", "HEAD"])
+            .build();
+        let request = &HttpRequest {
+            body: vec![],
+            headers: vec![],
+            method: method.to_string(),
+            url: "/index.html".to_string(),
+        };
+        let response = &HttpResponse {
+            status_code: 200,
+            body: vec![],
+            headers: vec![],
+            upgrade: None,
+            trailers: None,
+        };
+
+        let result = HttpCertification::full(&cel_expr, request, response, None);
+
+        match allowed {
+            true => assert!(result.is_ok()),
+            false => assert!(matches!(
+                result,
+                Err(HttpCertificationError::UnexpectedRequestMethod { expected, found })
+                    if expected == "GET, HEAD" && found == method
+            )),
+        }
+    }
+
+    #[rstest]
+    fn upgrade_responses_are_not_certified() {
+        let request = HttpRequest::builder()
+            .with_method("POST")
+            .with_url("/todos")
+            .build()
+            .unwrap();
+        let response = HttpResponse::upgrade_to_update_call();
+
+        assert!(matches!(
+            HttpCertification::response_only(
+                &DefaultCelBuilder::response_only_certification().build(),
+                &response,
+                None
+            ),
+            Err(HttpCertificationError::UnexpectedUpgrade)
+        ));
+        assert!(matches!(
+            HttpCertification::full(
+                &DefaultCelBuilder::full_certification().build(),
+                &request,
+                &response,
+                None
+            ),
+            Err(HttpCertificationError::UnexpectedUpgrade)
+        ));
+    }
+
+    #[rstest]
+    fn response_only_fragmented_body_certification() {
+        let cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_fragmented_body_certification()
+            .build();
+        let fragments: [&[u8]; 2] = [b"Hello", b" World!"];
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![(
+                CERTIFICATE_FRAGMENTS_HEADER_NAME.into(),
+                fragments_header_value(&[5, 7]),
+            )],
+            body: fragments.concat(),
+            upgrade: None,
+            trailers: None,
+        };
+        let body_hash = fragmented_body_hash(&[hash(fragments[0]), hash(fragments[1])]);
+
+        let result = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+
+        assert_eq!(
+            result,
+            HttpCertification::response_only(&cel_expr, &response, Some(body_hash)).unwrap()
+        );
+
+        let response = HttpResponse {
+            headers: vec![],
+            ..response
+        };
+        assert!(matches!(
+            HttpCertification::response_only(&cel_expr, &response, None),
+            Err(HttpCertificationError::MalformedFragmentsHeader(_))
+        ));
+    }
+
+    #[rstest]
+    fn response_only_streaming_body_certification() {
+        let cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_fragmented_body_certification()
+            .build();
+        let fragments: [&[u8]; 2] = [b"Hello", b" World!"];
+        let body_hash = fragmented_body_hash(&[hash(fragments[0]), hash(fragments[1])]);
+        let response = HttpResponseRef {
+            status_code: 200,
+            headers: vec![],
+            body: Body::Streaming(StreamingBody {
+                first_chunk: Cow::Borrowed(fragments[0]),
+                token: Cow::Borrowed(b"chunk-1"),
+                body_hash,
+            }),
+            upgrade: None,
+            trailers: None,
+        };
+
+        let result = HttpCertification::response_only(&cel_expr, response.clone(), None).unwrap();
+
+        assert_eq!(
+            result,
+            HttpCertification::response_only(&cel_expr, response, Some(body_hash)).unwrap()
+        );
+    }
 }
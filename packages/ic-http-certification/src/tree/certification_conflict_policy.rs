@@ -0,0 +1,24 @@
+/// How [try_insert](crate::HttpCertificationTree::try_insert) handles an entry that conflicts
+/// with entries that are already in an [HttpCertificationTree](crate::HttpCertificationTree).
+///
+/// Two entries conflict if they have the same path, CEL expression and request hash, but
+/// different response hashes, see
+/// [conflicting_entries](crate::HttpCertificationTree::conflicting_entries). For example, this
+/// happens when a response is recertified after its content changed, but the previous
+/// certification was not deleted. Both certifications are then valid for the same requests, so
+/// which response a client accepts depends on which one the canister happens to serve.
+///
+/// By default, conflicts are [allowed](CertificationConflictPolicy::Allow), in the same way as
+/// with [insert](crate::HttpCertificationTree::insert).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CertificationConflictPolicy {
+    /// Conflicting entries are kept in the tree alongside the inserted entry.
+    #[default]
+    Allow,
+    /// The entry is not inserted, and a
+    /// [CertificationConflict](crate::HttpCertificationError::CertificationConflict) error is
+    /// returned.
+    Reject,
+    /// Conflicting entries are deleted from the tree, and the entry is inserted in their place.
+    Replace,
+}
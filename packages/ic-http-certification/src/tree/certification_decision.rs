@@ -0,0 +1,97 @@
+use super::certification_tree_path::{HttpCertificationPath, HttpCertificationPathType};
+use std::fmt::{Display, Formatter};
+
+/// The name of the response header that identifies the encoding of the served variant.
+#[cfg(feature = "serde")]
+pub(super) const CONTENT_ENCODING_HEADER_NAME: &str = "Content-Encoding";
+
+/// How the certification of a [CertifiedResponse](crate::CertifiedResponse) was found in the
+/// [HttpCertificationTree](crate::HttpCertificationTree) for the request URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertificationMatch {
+    /// The certification is stored under an [exact](HttpCertificationPath::exact) path.
+    Exact(String),
+    /// The certification is stored under a [wildcard](HttpCertificationPath::wildcard) path,
+    /// that is a prefix of the request URL path.
+    Wildcard(String),
+    /// No certification is present for the request URL, and an
+    /// [absence proof](crate::HttpCertificationTree::certify_absence) is served instead.
+    Absent,
+}
+
+impl From<&HttpCertificationPath<'_>> for CertificationMatch {
+    fn from(path: &HttpCertificationPath<'_>) -> Self {
+        match path.get_type() {
+            HttpCertificationPathType::Exact(path) => CertificationMatch::Exact(path.to_string()),
+            HttpCertificationPathType::Wildcard(path) => {
+                CertificationMatch::Wildcard(path.to_string())
+            }
+        }
+    }
+}
+
+impl Display for CertificationMatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CertificationMatch::Exact(path) => write!(f, "exact {path}"),
+            CertificationMatch::Wildcard(path) => write!(f, "wildcard {path}"),
+            CertificationMatch::Absent => write!(f, "absent"),
+        }
+    }
+}
+
+/// A record of how a [CertifiedResponse](crate::CertifiedResponse) was certified, created with
+/// [CertifiedResponse::decision](crate::CertifiedResponse::decision).
+///
+/// Canisters can log this record when serving a response, to find out why a request URL was
+/// served a particular response, for example because a wildcard certification matched instead
+/// of an expected exact certification, or because a different encoding was served. The
+/// [Display] implementation formats the record as a single line of `key=value` pairs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificationDecision {
+    /// The URL of the request that the response is served for.
+    pub request_url: String,
+    /// How the certification of the response was found in the tree.
+    pub certification_match: CertificationMatch,
+    /// The status code of the served response.
+    pub status_code: u16,
+    /// The value of the `Content-Encoding` header of the served response, if any, which
+    /// identifies the encoded variant that was served.
+    pub content_encoding: Option<String>,
+    /// The size, in bytes, of the CBOR encoded witness that is included in the `IC-Certificate`
+    /// header.
+    pub witness_size: usize,
+}
+
+impl CertificationDecision {
+    #[cfg(feature = "serde")]
+    pub(super) fn new(
+        request_url: &str,
+        certification_match: CertificationMatch,
+        status_code: u16,
+        content_encoding: Option<&str>,
+        witness_size: usize,
+    ) -> Self {
+        Self {
+            request_url: request_url.to_string(),
+            certification_match,
+            status_code,
+            content_encoding: content_encoding.map(str::to_string),
+            witness_size,
+        }
+    }
+}
+
+impl Display for CertificationDecision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "url={} match=\"{}\" status={} encoding={} witness_size={}",
+            self.request_url,
+            self.certification_match,
+            self.status_code,
+            self.content_encoding.as_deref().unwrap_or("identity"),
+            self.witness_size
+        )
+    }
+}
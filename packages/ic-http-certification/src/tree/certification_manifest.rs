@@ -0,0 +1,370 @@
+use super::{certification_tree_path::HttpCertificationPathType, certified_response::cbor_encode};
+use crate::{
+    DefaultCelBuilder, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+    HttpCertification, HttpCertificationError, HttpCertificationPath, HttpCertificationResult,
+    HttpCertificationTree, HttpCertificationTreeEntry, HttpResponse, Method,
+    CERTIFICATE_EXPRESSION_HEADER_NAME,
+};
+use serde::{Deserialize, Serialize};
+
+/// The well-known path at which a [CertificationManifest] is served.
+pub const CERTIFICATION_MANIFEST_PATH: &str = "/.well-known/ic-certification-manifest";
+
+/// The content type of a served [CertificationManifest].
+pub const CERTIFICATION_MANIFEST_CONTENT_TYPE: &str = "application/cbor";
+
+/// The kind of an [HttpCertification] listed in a [CertificationManifest].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CertificationManifestType {
+    /// The request and response are [excluded](HttpCertification::skip) from certification.
+    Skip,
+    /// Only the response is [certified](HttpCertification::response_only).
+    ResponseOnly,
+    /// Both the request and the response are [certified](HttpCertification::full).
+    Full,
+}
+
+/// A certified URL pattern listed in a [CertificationManifest].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct CertificationManifestEntry {
+    /// The URL path of the certification, such as `/index.html`.
+    pub path: String,
+    /// Whether the path is a [wildcard](HttpCertificationPath::wildcard) that matches every URL
+    /// path it is a prefix of, instead of an [exact](HttpCertificationPath::exact) path.
+    pub wildcard: bool,
+    /// The kind of certification.
+    pub certification_type: CertificationManifestType,
+    /// The hex encoded hash of the CEL expression of the certification, which is the hash of the
+    /// `IC-CertificateExpression` header served with matching responses.
+    pub cel_expr_hash: String,
+    /// The request methods that the canister serves for this path, in uppercase.
+    ///
+    /// Request methods are part of the request hash and cannot be recovered from the tree, so
+    /// this is empty unless the methods are declared with
+    /// [with_methods](CertificationManifest::with_methods).
+    pub methods: Vec<String>,
+}
+
+/// A machine-readable listing of the URL patterns, request methods and CEL expression hashes
+/// that are certified in an [HttpCertificationTree].
+///
+/// Canisters can [certify](CertificationManifest::certify) the manifest and serve it at
+/// [CERTIFICATION_MANIFEST_PATH], so that its contents are backed by the same certificate as
+/// every other certified response. HTTP gateways and monitoring tools can then fetch and
+/// [decode](CertificationManifest::from_cbor) it to pre-validate routing, or compare it against
+/// an expected manifest to detect configuration drift.
+///
+/// Entries are deduplicated and sorted, so that the manifest only changes when a URL pattern or
+/// CEL expression is added or removed, not when the certified content of a response changes.
+///
+/// ```
+/// use ic_http_certification::{
+///     CertificationManifest, DefaultCelExpression, HttpCertification, HttpCertificationPath,
+///     HttpCertificationTree, HttpCertificationTreeEntry, Method, CERTIFICATION_MANIFEST_PATH,
+/// };
+///
+/// let mut tree = HttpCertificationTree::default();
+/// let path = HttpCertificationPath::wildcard("/assets");
+/// tree.insert(&HttpCertificationTreeEntry::new(&path, HttpCertification::skip()));
+///
+/// let manifest = CertificationManifest::from_tree(&tree).with_methods(&path, [Method::Get]);
+/// let (entry, response) = manifest.certify(&mut tree).unwrap();
+///
+/// let certified_response = tree
+///     .certify_response(
+///         &entry,
+///         CERTIFICATION_MANIFEST_PATH,
+///         &DefaultCelExpression::ResponseOnly(CertificationManifest::cel_expr()),
+///         None,
+///         response,
+///         None,
+///     )
+///     .unwrap();
+/// let served_manifest =
+///     CertificationManifest::from_cbor(&certified_response.response().body).unwrap();
+/// assert_eq!(served_manifest, manifest);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificationManifest {
+    /// The certified URL patterns, sorted by path.
+    pub entries: Vec<CertificationManifestEntry>,
+}
+
+impl CertificationManifest {
+    /// Creates a manifest that lists every certification in the tree, except for the
+    /// certification of the manifest itself at [CERTIFICATION_MANIFEST_PATH].
+    pub fn from_tree(tree: &HttpCertificationTree) -> Self {
+        let mut entries: Vec<_> = tree
+            .entries_under("/")
+            .iter()
+            .filter(|entry| !is_manifest_entry(entry))
+            .map(CertificationManifestEntry::from)
+            .collect();
+        entries.sort();
+        entries.dedup();
+
+        Self { entries }
+    }
+
+    /// Declares the request methods that the canister serves for the given path.
+    ///
+    /// The methods are set on every entry with this path, replacing any previously declared
+    /// methods. Paths that are not in the manifest are ignored.
+    pub fn with_methods(
+        mut self,
+        path: &HttpCertificationPath,
+        methods: impl IntoIterator<Item = Method>,
+    ) -> Self {
+        let mut methods: Vec<_> = methods
+            .into_iter()
+            .map(|method| method.as_str().to_string())
+            .collect();
+        methods.sort();
+        methods.dedup();
+
+        let wildcard = matches!(path.get_type(), HttpCertificationPathType::Wildcard(_));
+        self.entries
+            .iter_mut()
+            .filter(|entry| entry.path == path.get_path() && entry.wildcard == wildcard)
+            .for_each(|entry| entry.methods = methods.clone());
+
+        self
+    }
+
+    /// Encodes the manifest as self-describing CBOR, as it is served by
+    /// [certify](CertificationManifest::certify).
+    pub fn to_cbor(&self) -> HttpCertificationResult<Vec<u8>> {
+        cbor_encode(self)
+    }
+
+    /// Decodes a manifest from CBOR, such as the body of a served manifest response.
+    pub fn from_cbor(bytes: &[u8]) -> HttpCertificationResult<Self> {
+        serde_cbor::from_slice(bytes)
+            .map_err(|err| HttpCertificationError::CborDecodingError(err.to_string()))
+    }
+
+    /// Returns the CEL expression that the response serving the manifest is certified with, as
+    /// required by [certify_response](HttpCertificationTree::certify_response).
+    pub fn cel_expr() -> DefaultResponseOnlyCelExpression<'static> {
+        DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::response_header_exclusions(
+                vec![],
+            ))
+            .build()
+    }
+
+    /// Creates the response that serves the manifest and inserts its
+    /// [response only](HttpCertification::response_only) certification into the tree at
+    /// [CERTIFICATION_MANIFEST_PATH], replacing the certification of any previous manifest.
+    ///
+    /// Returns the tree entry and the response that must be served for
+    /// [CERTIFICATION_MANIFEST_PATH], see
+    /// [certify_response](HttpCertificationTree::certify_response). The manifest should be
+    /// certified again whenever certifications are added to or removed from the tree.
+    pub fn certify(
+        &self,
+        tree: &mut HttpCertificationTree,
+    ) -> HttpCertificationResult<(HttpCertificationTreeEntry<'static>, HttpResponse)> {
+        let cel_expr = Self::cel_expr();
+        let response = HttpResponse::builder()
+            .ok()
+            .with_header("Content-Type", CERTIFICATION_MANIFEST_CONTENT_TYPE)
+            .with_header(CERTIFICATE_EXPRESSION_HEADER_NAME, cel_expr.to_string())
+            .with_body(self.to_cbor()?)
+            .build()?;
+
+        let certification = HttpCertification::response_only(&cel_expr, &response, None)?;
+        let entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact(CERTIFICATION_MANIFEST_PATH),
+            certification,
+        );
+
+        for previous_entry in tree.entries_under(CERTIFICATION_MANIFEST_PATH) {
+            if is_manifest_entry(&previous_entry) {
+                tree.delete(&previous_entry);
+            }
+        }
+        tree.insert(&entry);
+
+        Ok((entry, response))
+    }
+}
+
+impl From<&HttpCertificationTreeEntry<'_>> for CertificationManifestEntry {
+    fn from(entry: &HttpCertificationTreeEntry<'_>) -> Self {
+        let certification_type =
+            match (entry.certification.is_skip(), entry.certification.is_full()) {
+                (true, _) => CertificationManifestType::Skip,
+                (false, true) => CertificationManifestType::Full,
+                (false, false) => CertificationManifestType::ResponseOnly,
+            };
+
+        Self {
+            path: entry.path.get_path().to_string(),
+            wildcard: matches!(
+                entry.path.get_type(),
+                HttpCertificationPathType::Wildcard(_)
+            ),
+            certification_type,
+            cel_expr_hash: hex::encode(entry.certification.cel_expr_hash()),
+            methods: vec![],
+        }
+    }
+}
+
+fn is_manifest_entry(entry: &HttpCertificationTreeEntry) -> bool {
+    entry.path.as_ref() == &HttpCertificationPath::exact(CERTIFICATION_MANIFEST_PATH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DefaultCelBuilder, DefaultCelExpression, HttpRequest};
+    use ic_representation_independent_hash::hash;
+    use rstest::*;
+
+    fn response(body: &str) -> HttpResponse {
+        HttpResponse::builder()
+            .ok()
+            .with_body(body.as_bytes().to_vec())
+            .build()
+            .unwrap()
+    }
+
+    #[fixture]
+    fn tree() -> HttpCertificationTree {
+        let response_only_cel_expr = DefaultCelBuilder::response_only_certification().build();
+        let full_cel_expr = DefaultCelBuilder::full_certification().build();
+        let request = HttpRequest::builder()
+            .with_method("POST")
+            .with_url("/api/todos")
+            .build()
+            .unwrap();
+
+        let mut tree = HttpCertificationTree::default();
+        for body in ["a", "b"] {
+            tree.insert(&HttpCertificationTreeEntry::new(
+                HttpCertificationPath::exact("/index.html"),
+                HttpCertification::response_only(&response_only_cel_expr, response(body), None)
+                    .unwrap(),
+            ));
+        }
+        tree.insert(&HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/api/todos"),
+            HttpCertification::full(&full_cel_expr, &request, response("[]"), None).unwrap(),
+        ));
+        tree.insert(&HttpCertificationTreeEntry::new(
+            HttpCertificationPath::wildcard(""),
+            HttpCertification::skip(),
+        ));
+
+        tree
+    }
+
+    #[rstest]
+    fn from_tree(tree: HttpCertificationTree) {
+        let response_only_cel_expr = DefaultCelBuilder::response_only_certification().build();
+        let full_cel_expr = DefaultCelBuilder::full_certification().build();
+        let skip_cel_expr = DefaultCelBuilder::skip_certification();
+
+        let manifest = CertificationManifest::from_tree(&tree);
+
+        assert_eq!(
+            manifest.entries,
+            vec![
+                CertificationManifestEntry {
+                    path: "".to_string(),
+                    wildcard: true,
+                    certification_type: CertificationManifestType::Skip,
+                    cel_expr_hash: hex::encode(hash(skip_cel_expr.to_string().as_bytes())),
+                    methods: vec![],
+                },
+                CertificationManifestEntry {
+                    path: "/api/todos".to_string(),
+                    wildcard: false,
+                    certification_type: CertificationManifestType::Full,
+                    cel_expr_hash: hex::encode(hash(full_cel_expr.to_string().as_bytes())),
+                    methods: vec![],
+                },
+                CertificationManifestEntry {
+                    path: "/index.html".to_string(),
+                    wildcard: false,
+                    certification_type: CertificationManifestType::ResponseOnly,
+                    cel_expr_hash: hex::encode(hash(response_only_cel_expr.to_string().as_bytes())),
+                    methods: vec![],
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    fn with_methods(tree: HttpCertificationTree) {
+        let manifest = CertificationManifest::from_tree(&tree).with_methods(
+            &HttpCertificationPath::exact("/api/todos"),
+            [Method::Post, Method::Get, Method::from("post")],
+        );
+
+        let methods: Vec<_> = manifest
+            .entries
+            .iter()
+            .map(|entry| entry.methods.clone())
+            .collect();
+        assert_eq!(
+            methods,
+            vec![vec![], vec!["GET".to_string(), "POST".to_string()], vec![]]
+        );
+    }
+
+    #[rstest]
+    fn cbor_round_trip(tree: HttpCertificationTree) {
+        let manifest = CertificationManifest::from_tree(&tree)
+            .with_methods(&HttpCertificationPath::wildcard(""), [Method::Get]);
+
+        let cbor = manifest.to_cbor().unwrap();
+
+        assert_eq!(CertificationManifest::from_cbor(&cbor).unwrap(), manifest);
+        assert!(matches!(
+            CertificationManifest::from_cbor(b"not cbor"),
+            Err(HttpCertificationError::CborDecodingError(_))
+        ));
+    }
+
+    #[rstest]
+    fn certify_replaces_previous_manifest(mut tree: HttpCertificationTree) {
+        let manifest = CertificationManifest::from_tree(&tree);
+        let (first_entry, _) = manifest.certify(&mut tree).unwrap();
+
+        tree.delete(&HttpCertificationTreeEntry::new(
+            HttpCertificationPath::wildcard(""),
+            HttpCertification::skip(),
+        ));
+        let manifest = CertificationManifest::from_tree(&tree);
+        let (entry, response) = manifest.certify(&mut tree).unwrap();
+
+        assert_eq!(manifest.entries.len(), 2);
+        assert_ne!(entry, first_entry);
+        assert_eq!(
+            tree.entries_under(CERTIFICATION_MANIFEST_PATH),
+            vec![entry.clone()]
+        );
+        assert_eq!(
+            response.get_header_value("Content-Type"),
+            Some(CERTIFICATION_MANIFEST_CONTENT_TYPE)
+        );
+        assert_eq!(
+            CertificationManifest::from_cbor(&response.body).unwrap(),
+            manifest
+        );
+        assert!(tree
+            .certify_response(
+                &entry,
+                CERTIFICATION_MANIFEST_PATH,
+                &DefaultCelExpression::ResponseOnly(CertificationManifest::cel_expr()),
+                None,
+                &response,
+                None
+            )
+            .is_ok());
+    }
+}
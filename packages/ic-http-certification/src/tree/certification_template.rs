@@ -0,0 +1,221 @@
+use super::certification::HttpCertification;
+use crate::{
+    cel::DefaultStatusCodeCertification, filter_response_headers, request_hash,
+    response_headers_hash_with_status_certification, status_code_permits_body,
+    DefaultFullCelExpression, DefaultResponseCertification, DefaultResponseOnlyCelExpression,
+    HttpCertificationError, HttpCertificationResult, HttpRequest, HttpResponse,
+};
+use ic_certification::Hash;
+use ic_representation_independent_hash::{hash, Sha256Algorithm};
+
+/// A certification of a response "shell", its status code and headers, whose body hash is
+/// supplied later with [certify](HttpCertificationTemplate::certify).
+///
+/// This is useful for template-driven responses whose bodies are assembled from precomputed
+/// fragments at serve time. The CEL expression, request and response headers are hashed once,
+/// when the template is created, so each assembled body only requires combining its hash with
+/// the precomputed ones.
+///
+/// The body of the response shell is ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpCertificationTemplate {
+    cel_expr_hash: Hash,
+    request_hash: Option<Hash>,
+    response_headers_hash: Hash,
+    status_code: u16,
+}
+
+impl HttpCertificationTemplate {
+    /// Creates a template for a [response only](HttpCertification::response_only) certification.
+    ///
+    /// Returns an error if the response shell is malformed, see [HttpResponse::validate].
+    pub fn response_only(
+        cel_expr: &DefaultResponseOnlyCelExpression,
+        response: &HttpResponse,
+    ) -> HttpCertificationResult<Self> {
+        Self::new(
+            cel_expr.to_string(),
+            None,
+            response,
+            &cel_expr.response,
+            &cel_expr.status,
+        )
+    }
+
+    /// Creates a template for a [full](HttpCertification::full) certification.
+    ///
+    /// Returns an error if the response shell is malformed, see [HttpResponse::validate], or if
+    /// the request cannot be hashed.
+    pub fn full(
+        cel_expr: &DefaultFullCelExpression,
+        request: &HttpRequest,
+        response: &HttpResponse,
+    ) -> HttpCertificationResult<Self> {
+        let request_hash = request_hash(request, &cel_expr.request)?;
+
+        Self::new(
+            cel_expr.to_string(),
+            Some(request_hash),
+            response,
+            &cel_expr.response,
+            &cel_expr.status,
+        )
+    }
+
+    /// Creates an [HttpCertification] for the response shell with a body whose hash is
+    /// `response_body_hash`.
+    ///
+    /// The result is identical to certifying the assembled response with
+    /// [response_only](HttpCertification::response_only) or [full](HttpCertification::full).
+    /// Returns an error if the response's status code does not permit a body, see
+    /// [status_code_permits_body], and `response_body_hash` is not the hash of an empty body.
+    pub fn certify(&self, response_body_hash: Hash) -> HttpCertificationResult<HttpCertification> {
+        if !status_code_permits_body(self.status_code) && response_body_hash != hash(&[]) {
+            return Err(HttpCertificationError::ResponseBodyNotPermitted {
+                status_code: self.status_code,
+            });
+        }
+
+        let response_hash = hash(&[self.response_headers_hash, response_body_hash].concat());
+
+        Ok(HttpCertification::from_hashes(
+            self.cel_expr_hash,
+            self.request_hash,
+            response_hash,
+        ))
+    }
+
+    fn new(
+        cel_expr: String,
+        request_hash: Option<Hash>,
+        response: &HttpResponse,
+        response_certification: &DefaultResponseCertification,
+        status_certification: &DefaultStatusCodeCertification,
+    ) -> HttpCertificationResult<Self> {
+        response.validate()?;
+
+        let filtered_headers = filter_response_headers(response, response_certification);
+
+        Ok(Self {
+            cel_expr_hash: hash(cel_expr.as_bytes()),
+            request_hash,
+            response_headers_hash: response_headers_hash_with_status_certification::<Sha256Algorithm>(
+                &response.status_code.into(),
+                &filtered_headers,
+                status_certification,
+            ),
+            status_code: response.status_code,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DefaultCelBuilder;
+    use rstest::*;
+
+    const BODY: &[u8] = b"<html><body>Hello World!</body></html>";
+
+    #[fixture]
+    fn response_shell() -> HttpResponse {
+        HttpResponse {
+            status_code: 200,
+            headers: vec![
+                ("Content-Type".into(), "text/html".into()),
+                ("Cache-Control".into(), "no-cache".into()),
+            ],
+            body: vec![],
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    #[rstest]
+    fn response_only_template_matches_response_only_certification(response_shell: HttpResponse) {
+        let cel_expr = DefaultCelBuilder::response_only_certification()
+            .with_response_certification(DefaultResponseCertification::certified_response_headers(
+                vec!["Content-Type"],
+            ))
+            .build();
+        let response = HttpResponse {
+            body: BODY.to_vec(),
+            ..response_shell.clone()
+        };
+
+        let template =
+            HttpCertificationTemplate::response_only(&cel_expr, &response_shell).unwrap();
+
+        assert_eq!(
+            template.certify(hash(BODY)).unwrap(),
+            HttpCertification::response_only(&cel_expr, &response, None).unwrap()
+        );
+    }
+
+    #[rstest]
+    fn full_template_matches_full_certification(response_shell: HttpResponse) {
+        let cel_expr = DefaultCelBuilder::full_certification()
+            .with_request_query_parameters(vec!["lang"])
+            .with_response_certification(DefaultResponseCertification::response_header_exclusions(
+                vec!["Cache-Control"],
+            ))
+            .build();
+        let request = HttpRequest {
+            method: "GET".into(),
+            url: "/index.html?lang=en".into(),
+            headers: vec![],
+            body: vec![],
+        };
+        let response = HttpResponse {
+            body: BODY.to_vec(),
+            ..response_shell.clone()
+        };
+
+        let template =
+            HttpCertificationTemplate::full(&cel_expr, &request, &response_shell).unwrap();
+
+        assert_eq!(
+            template.certify(hash(BODY)).unwrap(),
+            HttpCertification::full(&cel_expr, &request, &response, None).unwrap()
+        );
+        assert_ne!(
+            template.certify(hash(b"Hello IC!")).unwrap(),
+            template.certify(hash(BODY)).unwrap()
+        );
+    }
+
+    #[rstest]
+    fn template_with_no_body_status_code(response_shell: HttpResponse) {
+        let cel_expr = DefaultCelBuilder::response_only_certification().build();
+        let response_shell = HttpResponse {
+            status_code: 304,
+            ..response_shell
+        };
+
+        let template =
+            HttpCertificationTemplate::response_only(&cel_expr, &response_shell).unwrap();
+
+        assert_eq!(
+            template.certify(hash(&[])).unwrap(),
+            HttpCertification::response_only(&cel_expr, &response_shell, None).unwrap()
+        );
+        assert!(matches!(
+            template.certify(hash(BODY)),
+            Err(HttpCertificationError::ResponseBodyNotPermitted { status_code: 304 })
+        ));
+    }
+
+    #[rstest]
+    fn template_with_malformed_response(response_shell: HttpResponse) {
+        let cel_expr = DefaultCelBuilder::response_only_certification().build();
+        let response_shell = HttpResponse {
+            status_code: 42,
+            ..response_shell
+        };
+
+        assert!(matches!(
+            HttpCertificationTemplate::response_only(&cel_expr, &response_shell),
+            Err(HttpCertificationError::InvalidStatusCode(42))
+        ));
+    }
+}
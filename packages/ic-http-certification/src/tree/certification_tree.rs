@@ -1,22 +1,38 @@
+#[cfg(feature = "serde")]
+use super::certified_response::{
+    cbor_encode, certificate_header_size, ESTIMATED_DATA_CERTIFICATE_SIZE,
+};
 use super::{
+    certification_conflict_policy::CertificationConflictPolicy,
+    certification_decision::CertificationMatch,
     certification_tree_entry::HttpCertificationTreeEntry,
-    certification_tree_path::{CertificationTreePathSegment, PATH_PREFIX_BYTES},
+    certification_tree_limits::HttpCertificationTreeLimits,
+    certification_tree_path::{CertificationTreePathSegment, InnerTreePath, PATH_PREFIX_BYTES},
+    certified_response::CertifiedResponse,
 };
 use crate::{
     tree::{HttpCertificationPathType, WILDCARD_PATH_TERMINATOR_BYTES},
-    HttpCertificationPath,
+    DefaultCelExpression, HttpCertificationError, HttpCertificationPath, HttpCertificationResult,
+    HttpRequest, HttpResponse,
 };
 use ic_certification::{
-    empty, labeled, labeled_hash, merge_hash_trees, AsHashTree, HashTree, NestedTree,
+    empty, labeled, labeled_hash, merge_hash_trees, AsHashTree, Hash, HashTree, HashTreeNode,
+    NestedTree, SubtreeLookupResult,
 };
 use ic_representation_independent_hash::Sha256Digest;
+use std::borrow::Cow;
 
 type CertificationTree = NestedTree<CertificationTreePathSegment, Vec<u8>>;
 
+/// The number of hex characters of each digest that are shown by [HttpCertificationTree::to_dot].
+const DOT_DIGEST_PREFIX_LEN: usize = 8;
+
 /// A certification tree for generic HTTP requests.
 #[derive(Debug)]
 pub struct HttpCertificationTree {
     tree: CertificationTree,
+    limits: HttpCertificationTreeLimits,
+    conflict_policy: CertificationConflictPolicy,
 }
 
 impl Default for HttpCertificationTree {
@@ -29,7 +45,27 @@ impl HttpCertificationTree {
     /// Creates a new empty [HttpCertificationTree] from a given [CertificationTree].
     /// The [default](HttpCertificationTree::default) implementation should be used in most cases.
     pub fn new(tree: CertificationTree) -> Self {
-        Self { tree }
+        Self {
+            tree,
+            limits: HttpCertificationTreeLimits::default(),
+            conflict_policy: CertificationConflictPolicy::default(),
+        }
+    }
+
+    /// Sets the [HttpCertificationTreeLimits] that are enforced by
+    /// [try_insert](HttpCertificationTree::try_insert).
+    pub fn with_limits(mut self, limits: HttpCertificationTreeLimits) -> Self {
+        self.limits = limits;
+
+        self
+    }
+
+    /// Sets the [CertificationConflictPolicy] that is applied by
+    /// [try_insert](HttpCertificationTree::try_insert).
+    pub fn with_conflict_policy(mut self, conflict_policy: CertificationConflictPolicy) -> Self {
+        self.conflict_policy = conflict_policy;
+
+        self
     }
 
     /// Returns the root hash of the tree.
@@ -38,6 +74,31 @@ impl HttpCertificationTree {
         labeled_hash(PATH_PREFIX_BYTES, &self.tree.root_hash())
     }
 
+    /// Returns the full [HashTree], without any pruning.
+    /// The [digest](HashTree::digest) of this tree is equal to the [root hash](HttpCertificationTree::root_hash).
+    ///
+    /// This is useful for precomputing the tree outside of a canister, such as in a build pipeline.
+    /// To prove the presence of a single [HttpCertificationTreeEntry], a [witness](HttpCertificationTree::witness)
+    /// should be used instead.
+    pub fn to_hash_tree(&self) -> HashTree {
+        labeled(PATH_PREFIX_BYTES, self.tree.as_hash_tree())
+    }
+
+    /// Renders the full tree in the [DOT](https://graphviz.org/doc/info/lang.html) format, so that
+    /// it can be visualized with Graphviz.
+    ///
+    /// Every node shows the first few characters of its digest. Labels are shown as text when they
+    /// are printable ASCII, such as path segments, and as truncated hex otherwise, such as CEL
+    /// expression, request and response hashes.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph HttpCertificationTree {\n");
+        dot.push_str("    node [shape=box, fontname=monospace];\n");
+        write_dot_node(&mut dot, &HashTreeNode::from(self.to_hash_tree()), &mut 0);
+        dot.push_str("}\n");
+
+        dot
+    }
+
     /// Inserts a given [HttpCertificationTreeEntry] into the tree.
     /// After performing this operation, the canister's certified variable will need to be updated
     /// with the new [root hash](HttpCertificationTree::root_hash) of the tree.
@@ -46,6 +107,78 @@ impl HttpCertificationTree {
         self.tree.insert(&tree_path, vec![]);
     }
 
+    /// Inserts a given [HttpCertificationTreeEntry] into the tree, if it is within the tree's
+    /// [limits](HttpCertificationTree::with_limits), applying the tree's
+    /// [conflict policy](HttpCertificationTree::with_conflict_policy).
+    ///
+    /// Returns an error, without modifying the tree, if the entry's path is
+    /// [too long](HttpCertificationError::UrlTooLong) or
+    /// [too deep](HttpCertificationError::PathTooDeep), or if it
+    /// [conflicts](HttpCertificationError::CertificationConflict) with entries in the tree and
+    /// conflicts are [rejected](CertificationConflictPolicy::Reject). Conflicting entries are
+    /// deleted if they are [replaced](CertificationConflictPolicy::Replace). Otherwise, this is the
+    /// same as [insert](HttpCertificationTree::insert), which does not check the limits or
+    /// conflicts.
+    pub fn try_insert(&mut self, entry: &HttpCertificationTreeEntry) -> HttpCertificationResult {
+        self.limits.check(entry)?;
+
+        match self.conflict_policy {
+            CertificationConflictPolicy::Allow => {}
+            CertificationConflictPolicy::Reject => {
+                if !self.conflicting_entries(entry).is_empty() {
+                    return Err(HttpCertificationError::CertificationConflict(
+                        entry.path.get_path().to_string(),
+                    ));
+                }
+            }
+            CertificationConflictPolicy::Replace => {
+                for conflicting_entry in self.conflicting_entries(entry) {
+                    self.delete(&conflicting_entry);
+                }
+            }
+        }
+        self.insert(entry);
+
+        Ok(())
+    }
+
+    /// Returns all [HttpCertificationTreeEntry]s in the tree that conflict with the given entry,
+    /// because they have the same path, CEL expression and request hash, but a different
+    /// response hash.
+    ///
+    /// A [skip](crate::HttpCertification::skip) certification has no response hash, so it never
+    /// conflicts with other entries.
+    pub fn conflicting_entries(
+        &self,
+        entry: &HttpCertificationTreeEntry,
+    ) -> Vec<HttpCertificationTreeEntry<'static>> {
+        if entry.certification.is_skip() {
+            return vec![];
+        }
+
+        let mut tree_path = entry.to_tree_path();
+        let Some(response_hash) = tree_path.pop() else {
+            return vec![];
+        };
+        let Some(NestedTree::Nested(response_hashes)) = self.subtree(&tree_path) else {
+            return vec![];
+        };
+
+        response_hashes
+            .iter()
+            .filter(|(segment, child)| {
+                **segment != response_hash && matches!(child, NestedTree::Leaf(_))
+            })
+            .filter_map(|(segment, _)| {
+                tree_path.push(segment.clone());
+                let conflicting_entry = HttpCertificationTreeEntry::from_tree_path(&tree_path);
+                tree_path.pop();
+
+                conflicting_entry
+            })
+            .collect()
+    }
+
     /// Deletes a given [HttpCertificationTreeEntry] from the tree.
     /// After performing this operation, the canister's certified variable will need to be updated
     /// with the new [root hash](HttpCertificationTree::root_hash) of the tree.
@@ -54,43 +187,746 @@ impl HttpCertificationTree {
         self.tree.delete(&tree_path);
     }
 
+    /// Returns all [HttpCertificationTreeEntry]s whose path is equal to, or nested under, the given
+    /// path prefix.
+    ///
+    /// The prefix is matched on whole path segments, so `/api/v1` matches `/api/v1` and
+    /// `/api/v1/users`, but not `/api/v10`. Both exact and wildcard entries are returned. This can
+    /// be used to inspect a subtree, or to [delete](HttpCertificationTree::delete) all entries
+    /// under a path in bulk.
+    pub fn entries_under(&self, prefix: &str) -> Vec<HttpCertificationTreeEntry<'static>> {
+        let mut tree_path: InnerTreePath = prefix
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| segment.as_bytes().to_vec())
+            .collect();
+
+        let Some(subtree) = self.subtree(&tree_path) else {
+            return vec![];
+        };
+
+        let mut entries = vec![];
+        collect_entries(subtree, &mut tree_path, &mut entries);
+
+        entries
+    }
+
+    /// Returns the subtree at the given path, if there is one.
+    fn subtree(&self, tree_path: &[CertificationTreePathSegment]) -> Option<&CertificationTree> {
+        tree_path
+            .iter()
+            .try_fold(&self.tree, |subtree, segment| match subtree {
+                NestedTree::Nested(tree) => tree.get(segment),
+                NestedTree::Leaf(_) => None,
+            })
+    }
+
     /// Returns a pruned [HashTree] that will prove the presence of a given [HttpCertificationTreeEntry]
     /// in the full [HttpCertificationTree], without needing to return the full tree.
     ///
     /// `request_url` is required so that the witness can be generated with respect to the request URL.
     pub fn witness(&self, entry: &HttpCertificationTreeEntry, request_url: &str) -> HashTree {
-        let witness = match entry.path.get_type() {
-            HttpCertificationPathType::Exact(_) => self.tree.witness(&entry.to_tree_path()),
-
-            HttpCertificationPathType::Wildcard(_) => {
-                let requested_tree_path = HttpCertificationPath::exact(request_url).to_tree_path();
-
-                // For wildcards we need to prove that there is not a more specific wildcard in the tree that
-                // matches the request URL. So we step through the path and generate a witness for each subpath,
-                // with and without trailing slashes.
-                (0..requested_tree_path.len())
-                    .flat_map(|index| {
-                        let sub_path = requested_tree_path[0..index].to_vec();
-
-                        let without_trailing_slash = [
-                            sub_path.clone(),
-                            vec![WILDCARD_PATH_TERMINATOR_BYTES.to_vec()],
-                        ]
-                        .concat();
-                        let with_trailing_slash = [
-                            sub_path,
-                            vec![b"".to_vec(), WILDCARD_PATH_TERMINATOR_BYTES.to_vec()],
-                        ]
-                        .concat();
-
-                        [without_trailing_slash, with_trailing_slash]
-                    })
-                    .fold(empty(), |acc, path| {
-                        merge_hash_trees(acc, self.tree.witness(&path))
-                    })
+        match entry.path.get_type() {
+            HttpCertificationPathType::Exact(_) => self.exact_witness(&entry.to_tree_path()),
+            HttpCertificationPathType::Wildcard(_) => self.wildcard_witness(request_url),
+        }
+    }
+
+    /// Returns the [witness](HttpCertificationTree::witness) for a given
+    /// [HttpCertificationTreeEntry], or an error if the entry is not present in the tree.
+    ///
+    /// The tree path of the entry is only computed once, for both checking its presence and
+    /// generating the witness.
+    pub(super) fn checked_witness(
+        &self,
+        entry: &HttpCertificationTreeEntry,
+        request_url: &str,
+    ) -> HttpCertificationResult<HashTree> {
+        let tree_path = entry.to_tree_path();
+        if !self.tree.contains_leaf(&tree_path) {
+            return Err(HttpCertificationError::CertificationNotFound(
+                entry.path.get_path().to_string(),
+            ));
+        }
+
+        Ok(match entry.path.get_type() {
+            HttpCertificationPathType::Exact(_) => self.exact_witness(&tree_path),
+            HttpCertificationPathType::Wildcard(_) => self.wildcard_witness(request_url),
+        })
+    }
+
+    fn exact_witness(&self, tree_path: &[CertificationTreePathSegment]) -> HashTree {
+        labeled(PATH_PREFIX_BYTES, self.tree.witness(tree_path))
+    }
+
+    // For wildcards we need to prove that there is not a more specific wildcard in the tree that
+    // matches the request URL.
+    fn wildcard_witness(&self, request_url: &str) -> HashTree {
+        let witness = wildcard_tree_paths(request_url)
+            .into_iter()
+            .fold(empty(), |acc, path| {
+                merge_hash_trees(acc, self.tree.witness(&path))
+            });
+
+        labeled(PATH_PREFIX_BYTES, witness)
+    }
+
+    /// Returns a pruned [HashTree] that will prove the absence of any certification that is valid
+    /// for the given request URL, neither for its exact path nor for any wildcard path matching it.
+    ///
+    /// This allows canisters to serve verifiable "not found" responses for arbitrary URLs that
+    /// were never certified, see [certify_absence](HttpCertificationTree::certify_absence).
+    /// Returns an error if a certification for the request URL is present in the tree.
+    pub fn absence_witness(&self, request_url: &str) -> HttpCertificationResult<HashTree> {
+        let exact_tree_path = HttpCertificationPath::exact(request_url).to_tree_path();
+        let witness = wildcard_tree_paths(request_url)
+            .into_iter()
+            .fold(self.tree.witness(&exact_tree_path), |acc, path| {
+                merge_hash_trees(acc, self.tree.witness(&path))
+            });
+        let witness = labeled(PATH_PREFIX_BYTES, witness);
+
+        if !is_certification_absent(&witness, request_url) {
+            return Err(HttpCertificationError::CertificationExists(
+                request_url.to_string(),
+            ));
+        }
+
+        Ok(witness)
+    }
+
+    /// Estimates the size, in bytes, of the `IC-Certificate` header that will be added when a
+    /// response for the given [HttpCertificationTreeEntry] is [served](CertifiedResponse::serve),
+    /// including the header name.
+    ///
+    /// The sizes of the witness and expression path are exact, but the size of the data
+    /// certificate is only known at serve time, so [ESTIMATED_DATA_CERTIFICATE_SIZE] is assumed.
+    /// This allows canisters to detect entries whose deep paths or large witnesses would exceed
+    /// the header size limits of HTTP gateways and proxies, before serving them.
+    ///
+    /// `request_url` is required so that the witness can be generated with respect to the request URL.
+    ///
+    /// Requires the `serde` feature, which is needed to CBOR encode the witness.
+    #[cfg(feature = "serde")]
+    pub fn estimate_header_size(
+        &self,
+        entry: &HttpCertificationTreeEntry,
+        request_url: &str,
+    ) -> HttpCertificationResult<usize> {
+        let witness = cbor_encode(&self.witness(entry, request_url))?;
+        let expr_path = cbor_encode(&entry.path.to_expr_path())?;
+
+        Ok(certificate_header_size(
+            ESTIMATED_DATA_CERTIFICATE_SIZE,
+            witness.len(),
+            expr_path.len(),
+        ))
+    }
+
+    /// Returns true if the given [HttpCertificationTreeEntry] is present in the tree.
+    #[cfg(test)]
+    pub(super) fn contains(&self, entry: &HttpCertificationTreeEntry) -> bool {
+        self.tree.contains_leaf(&entry.to_tree_path())
+    }
+
+    /// Wraps an [HttpResponse], such as a "not found" response, in a [CertifiedResponse] that
+    /// proves that no certification for the given request URL is present in the tree.
+    ///
+    /// The `IC-Certificate` header of the served response includes the
+    /// [absence witness](HttpCertificationTree::absence_witness), but no expression path, and
+    /// the response itself is not certified. Clients can check the proof with
+    /// [is_certification_absent] once they have verified the certificate.
+    pub fn certify_absence<'a>(
+        &self,
+        request_url: &str,
+        response: impl Into<Cow<'a, HttpResponse>>,
+    ) -> HttpCertificationResult<CertifiedResponse<'a>> {
+        Ok(CertifiedResponse::new(
+            response.into(),
+            self.absence_witness(request_url)?,
+            vec![],
+            request_url,
+            CertificationMatch::Absent,
+        ))
+    }
+
+    /// Wraps an [HttpResponse] in a [CertifiedResponse] that can be served to the HTTP Gateway.
+    ///
+    /// This checks that the given [HttpCertificationTreeEntry] is present in the tree, and that
+    /// its certification matches the response, and generates the
+    /// [witness](HttpCertificationTree::witness) and expression path that will be included in the
+    /// `IC-Certificate` header when the response is [served](CertifiedResponse::serve).
+    ///
+    /// `request_url` is required so that the witness can be generated with respect to the request URL.
+    ///
+    /// The certification is recomputed for the response according to `cel_expr`, which must be
+    /// the CEL expression that the certification was created with. `request` is only required if
+    /// `cel_expr` is a [full](crate::DefaultCelExpression::Full) expression, and
+    /// `response_body_hash` must be the same hash that was provided when the certification was
+    /// created. Returns a [CertificationMismatch](HttpCertificationError::CertificationMismatch)
+    /// error if the recomputed certification does not match the entry.
+    pub fn certify_response<'a>(
+        &self,
+        entry: &HttpCertificationTreeEntry,
+        request_url: &str,
+        cel_expr: &DefaultCelExpression,
+        request: Option<&HttpRequest>,
+        response: impl Into<Cow<'a, HttpResponse>>,
+        response_body_hash: Option<Hash>,
+    ) -> HttpCertificationResult<CertifiedResponse<'a>> {
+        let witness = self.checked_witness(entry, request_url)?;
+        let response = response.into();
+        entry.validate_response(cel_expr, request, &response, response_body_hash)?;
+
+        Ok(CertifiedResponse::new(
+            response,
+            witness,
+            entry.path.to_expr_path(),
+            request_url,
+            CertificationMatch::from(entry.path.as_ref()),
+        ))
+    }
+}
+
+/// Collects the entries of all leaves in `tree`, whose path within the full tree is `tree_path`.
+fn collect_entries(
+    tree: &CertificationTree,
+    tree_path: &mut InnerTreePath,
+    entries: &mut Vec<HttpCertificationTreeEntry<'static>>,
+) {
+    match tree {
+        NestedTree::Leaf(_) => {
+            entries.extend(HttpCertificationTreeEntry::from_tree_path(tree_path))
+        }
+        NestedTree::Nested(tree) => {
+            for (segment, child) in tree.iter() {
+                tree_path.push(segment.clone());
+                collect_entries(child, tree_path, entries);
+                tree_path.pop();
+            }
+        }
+    }
+}
+
+/// Checks whether a [witness](HttpCertificationTree::absence_witness) proves that no
+/// certification is valid for the given request URL, neither for its exact path nor for any
+/// wildcard path matching it.
+///
+/// Returns `false` if a certification is present, or if the witness does not include enough of
+/// the tree to prove its absence. The witness must be checked against a verified certificate
+/// before relying on the result.
+pub fn is_certification_absent(witness: &HashTree, request_url: &str) -> bool {
+    let exact_tree_path = HttpCertificationPath::exact(request_url).to_tree_path();
+
+    std::iter::once(exact_tree_path)
+        .chain(wildcard_tree_paths(request_url))
+        .all(|tree_path| {
+            let expr_path = [vec![PATH_PREFIX_BYTES.to_vec()], tree_path].concat();
+
+            match witness.lookup_subtree(&expr_path) {
+                SubtreeLookupResult::Absent => true,
+                SubtreeLookupResult::Unknown => false,
+                // deleted certifications can leave behind empty subtrees
+                SubtreeLookupResult::Found(subtree) => !has_leaf(subtree.as_ref()),
             }
+        })
+}
+
+/// Returns true if the node contains a leaf, or a pruned node that may contain one.
+fn has_leaf(node: &HashTreeNode) -> bool {
+    match node {
+        HashTreeNode::Empty() => false,
+        HashTreeNode::Fork(nodes) => has_leaf(&nodes.0) || has_leaf(&nodes.1),
+        HashTreeNode::Labeled(_, node) => has_leaf(node),
+        HashTreeNode::Leaf(_) | HashTreeNode::Pruned(_) => true,
+    }
+}
+
+/// Returns the tree paths of every wildcard that could match the request URL. Each subpath is
+/// included with and without a trailing slash.
+pub(super) fn wildcard_tree_paths(request_url: &str) -> Vec<Vec<CertificationTreePathSegment>> {
+    let requested_tree_path = HttpCertificationPath::exact(request_url).to_tree_path();
+
+    (0..requested_tree_path.len())
+        .flat_map(|index| {
+            let sub_path = requested_tree_path[0..index].to_vec();
+
+            let without_trailing_slash = [
+                sub_path.clone(),
+                vec![WILDCARD_PATH_TERMINATOR_BYTES.to_vec()],
+            ]
+            .concat();
+            let with_trailing_slash = [
+                sub_path,
+                vec![b"".to_vec(), WILDCARD_PATH_TERMINATOR_BYTES.to_vec()],
+            ]
+            .concat();
+
+            [without_trailing_slash, with_trailing_slash]
+        })
+        .collect()
+}
+
+fn write_dot_node(dot: &mut String, node: &HashTreeNode, next_id: &mut usize) -> usize {
+    let id = *next_id;
+    *next_id += 1;
+
+    let digest = dot_truncated_hex(&node.digest());
+    let (label, children) = match node {
+        HashTreeNode::Empty() => ("empty".to_string(), vec![]),
+        HashTreeNode::Fork(nodes) => ("fork".to_string(), vec![&nodes.0, &nodes.1]),
+        HashTreeNode::Labeled(label, node) => (dot_label(label.as_bytes()), vec![node.as_ref()]),
+        HashTreeNode::Leaf(value) => (format!("leaf {}", dot_label(value)), vec![]),
+        HashTreeNode::Pruned(_) => ("pruned".to_string(), vec![]),
+    };
+    dot.push_str(&format!("    n{id} [label=\"{label}\\n{digest}\"];\n"));
+
+    for child in children {
+        let child_id = write_dot_node(dot, child, next_id);
+        dot.push_str(&format!("    n{id} -> n{child_id};\n"));
+    }
+
+    id
+}
+
+fn dot_label(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) if text.chars().all(|c| c.is_ascii_graphic()) => {
+            format!(
+                "\\\"{}\\\"",
+                text.replace('\\', "\\\\").replace('"', "\\\"")
+            )
+        }
+        _ => format!("0x{}", dot_truncated_hex(bytes)),
+    }
+}
+
+fn dot_truncated_hex(bytes: &[u8]) -> String {
+    let hex = hex::encode(bytes);
+
+    match hex.len() > DOT_DIGEST_PREFIX_LEN {
+        true => format!("{}...", &hex[..DOT_DIGEST_PREFIX_LEN]),
+        false => hex,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DefaultCelBuilder, HttpCertification, HttpCertificationTreeEntry};
+    use rstest::*;
+
+    #[rstest]
+    fn to_hash_tree_digest_matches_root_hash() {
+        let cel_expr = DefaultCelBuilder::response_only_certification().build();
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![],
+            body: vec![1, 2, 3],
+            upgrade: None,
+            trailers: None,
         };
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+        let mut tree = HttpCertificationTree::default();
+        tree.insert(&HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/index.html"),
+            certification,
+        ));
+        tree.insert(&HttpCertificationTreeEntry::new(
+            HttpCertificationPath::wildcard("/assets"),
+            certification,
+        ));
 
-        labeled(PATH_PREFIX_BYTES, witness)
+        assert_eq!(tree.to_hash_tree().digest(), tree.root_hash());
+    }
+
+    #[rstest]
+    fn try_insert_enforces_limits() {
+        let mut tree = HttpCertificationTree::default().with_limits(
+            HttpCertificationTreeLimits::new()
+                .with_max_url_length(32)
+                .with_max_path_depth(2),
+        );
+        let root_hash = tree.root_hash();
+
+        let too_long = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/".repeat(33)),
+            HttpCertification::skip(),
+        );
+        assert!(matches!(
+            tree.try_insert(&too_long),
+            Err(HttpCertificationError::UrlTooLong { .. })
+        ));
+
+        let too_deep = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/assets/js/app.js"),
+            HttpCertification::skip(),
+        );
+        assert!(matches!(
+            tree.try_insert(&too_deep),
+            Err(HttpCertificationError::PathTooDeep { .. })
+        ));
+        assert_eq!(tree.root_hash(), root_hash);
+
+        let entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/assets/app.js"),
+            HttpCertification::skip(),
+        );
+        tree.try_insert(&entry).unwrap();
+        assert_ne!(tree.root_hash(), root_hash);
+
+        // insert does not check the limits
+        let root_hash = tree.root_hash();
+        tree.insert(&too_deep);
+        assert_ne!(tree.root_hash(), root_hash);
+    }
+
+    fn response_only_entry(path: &str, body: &[u8]) -> HttpCertificationTreeEntry<'static> {
+        let cel_expr = DefaultCelBuilder::response_only_certification().build();
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![],
+            body: body.to_vec(),
+            upgrade: None,
+            trailers: None,
+        };
+
+        HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact(path.to_string()),
+            HttpCertification::response_only(&cel_expr, &response, None).unwrap(),
+        )
+    }
+
+    #[rstest]
+    fn conflicting_entries_have_different_response_hashes() {
+        let mut tree = HttpCertificationTree::default();
+        let old_entry = response_only_entry("/index.html", b"old");
+        let new_entry = response_only_entry("/index.html", b"new");
+        let other_entry = response_only_entry("/other.html", b"old");
+        let skip_entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/index.html"),
+            HttpCertification::skip(),
+        );
+        tree.insert(&old_entry);
+        tree.insert(&other_entry);
+        tree.insert(&skip_entry);
+
+        assert_eq!(
+            tree.conflicting_entries(&new_entry),
+            vec![old_entry.clone()]
+        );
+        assert!(tree.conflicting_entries(&old_entry).is_empty());
+        assert!(tree.conflicting_entries(&skip_entry).is_empty());
+    }
+
+    #[rstest]
+    fn try_insert_allows_conflicts_by_default() {
+        let mut tree = HttpCertificationTree::default();
+        let old_entry = response_only_entry("/index.html", b"old");
+        let new_entry = response_only_entry("/index.html", b"new");
+        tree.try_insert(&old_entry).unwrap();
+        tree.try_insert(&new_entry).unwrap();
+
+        assert!(tree.contains(&old_entry));
+        assert!(tree.contains(&new_entry));
+    }
+
+    #[rstest]
+    fn try_insert_rejects_conflicts() {
+        let mut tree = HttpCertificationTree::default()
+            .with_conflict_policy(CertificationConflictPolicy::Reject);
+        let old_entry = response_only_entry("/index.html", b"old");
+        let new_entry = response_only_entry("/index.html", b"new");
+        tree.try_insert(&old_entry).unwrap();
+        let root_hash = tree.root_hash();
+
+        assert!(matches!(
+            tree.try_insert(&new_entry),
+            Err(HttpCertificationError::CertificationConflict(path)) if path == "/index.html"
+        ));
+        assert_eq!(tree.root_hash(), root_hash);
+
+        // reinserting the same entry is not a conflict
+        tree.try_insert(&old_entry).unwrap();
+        assert_eq!(tree.root_hash(), root_hash);
+    }
+
+    #[rstest]
+    fn try_insert_replaces_conflicts() {
+        let mut tree = HttpCertificationTree::default()
+            .with_conflict_policy(CertificationConflictPolicy::Replace);
+        let old_entry = response_only_entry("/index.html", b"old");
+        let new_entry = response_only_entry("/index.html", b"new");
+        tree.try_insert(&old_entry).unwrap();
+        tree.try_insert(&new_entry).unwrap();
+
+        assert!(!tree.contains(&old_entry));
+        assert!(tree.contains(&new_entry));
+        assert!(tree.conflicting_entries(&new_entry).is_empty());
+
+        tree.try_insert(&old_entry).unwrap();
+        assert!(tree.contains(&old_entry));
+        assert!(!tree.contains(&new_entry));
+    }
+
+    #[rstest]
+    fn entries_under_returns_nested_entries() {
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![],
+            body: vec![1, 2, 3],
+            upgrade: None,
+            trailers: None,
+        };
+        let request = crate::HttpRequest {
+            method: "GET".to_string(),
+            url: "/api/v1/users".to_string(),
+            headers: vec![],
+            body: vec![],
+        };
+        let response_only = HttpCertification::response_only(
+            &DefaultCelBuilder::response_only_certification().build(),
+            &response,
+            None,
+        )
+        .unwrap();
+        let full = HttpCertification::full(
+            &DefaultCelBuilder::full_certification().build(),
+            &request,
+            &response,
+            None,
+        )
+        .unwrap();
+
+        let v1_exact = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/api/v1"),
+            HttpCertification::skip(),
+        );
+        let v1_wildcard = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::wildcard("/api/v1/"),
+            response_only,
+        );
+        let v1_users =
+            HttpCertificationTreeEntry::new(HttpCertificationPath::exact("/api/v1/users"), full);
+        let v10 = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/api/v10"),
+            response_only,
+        );
+        let root = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact(""),
+            HttpCertification::skip(),
+        );
+
+        let mut tree = HttpCertificationTree::default();
+        for entry in [&v1_exact, &v1_wildcard, &v1_users, &v10, &root] {
+            tree.insert(entry);
+        }
+
+        let entries = tree.entries_under("/api/v1/");
+        assert_eq!(entries.len(), 3);
+        for entry in [&v1_exact, &v1_wildcard, &v1_users] {
+            assert!(entries.contains(entry));
+        }
+
+        assert_eq!(tree.entries_under("").len(), 5);
+        assert!(tree.entries_under("").contains(&root));
+        assert_eq!(tree.entries_under("/api/v1/users/"), vec![v1_users.clone()]);
+        assert!(tree.entries_under("/api/v2").is_empty());
+
+        for entry in tree.entries_under("/api/v1") {
+            tree.delete(&entry);
+        }
+        assert!(tree.entries_under("/api/v1").is_empty());
+        assert_eq!(tree.entries_under("/api").len(), 1);
+    }
+
+    #[rstest]
+    fn to_dot_empty_tree() {
+        let tree = HttpCertificationTree::default();
+        let root_digest = hex::encode(tree.root_hash());
+
+        assert_eq!(
+            tree.to_dot(),
+            format!(
+                r#"digraph HttpCertificationTree {{
+    node [shape=box, fontname=monospace];
+    n0 [label="\"http_expr\"\n{}..."];
+    n1 [label="empty\n{}..."];
+    n0 -> n1;
+}}
+"#,
+                &root_digest[..8],
+                &hex::encode(HashTreeNode::from(empty()).digest())[..8],
+            )
+        );
+    }
+
+    #[rstest]
+    fn to_dot_contains_entry_labels() {
+        let cel_expr = DefaultCelBuilder::response_only_certification().build();
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![],
+            body: vec![1, 2, 3],
+            upgrade: None,
+            trailers: None,
+        };
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+        let mut tree = HttpCertificationTree::default();
+        tree.insert(&HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/index.html"),
+            certification,
+        ));
+
+        let dot = tree.to_dot();
+        let node_count = dot.matches(" [label=").count();
+        let edge_count = dot.matches(" -> ").count();
+
+        assert!(dot.starts_with("digraph HttpCertificationTree {"));
+        assert!(dot.contains(r#"[label="\"http_expr\"\n"#));
+        assert!(dot.contains(r#"[label="\"index.html\"\n"#));
+        assert!(dot.contains(r#"[label="\"<$>\"\n"#));
+        assert!(dot.contains(r#"[label="leaf \"\"\n"#));
+        assert_eq!(edge_count, node_count - 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    #[case::exact(HttpCertificationPath::exact("/assets/js/app.js"), "/assets/js/app.js")]
+    #[case::wildcard(HttpCertificationPath::wildcard("/assets"), "/assets/js/app.js")]
+    fn estimate_header_size_matches_served_header(
+        #[case] path: HttpCertificationPath,
+        #[case] request_url: &str,
+    ) {
+        let cel_expr = DefaultCelBuilder::response_only_certification().build();
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![],
+            body: vec![1, 2, 3],
+            upgrade: None,
+            trailers: None,
+        };
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+        let entry = HttpCertificationTreeEntry::new(path, certification);
+        let mut tree = HttpCertificationTree::default();
+        tree.insert(&entry);
+
+        let estimated_size = tree.estimate_header_size(&entry, request_url).unwrap();
+
+        let served_response = tree
+            .certify_response(
+                &entry,
+                request_url,
+                &DefaultCelExpression::ResponseOnly(cel_expr),
+                None,
+                &response,
+                None,
+            )
+            .unwrap()
+            .serve(&[0; ESTIMATED_DATA_CERTIFICATE_SIZE])
+            .unwrap();
+        let (header_name, header_value) = served_response.headers.last().unwrap();
+        assert_eq!(estimated_size, header_name.len() + header_value.len());
+    }
+
+    fn absence_tree() -> HttpCertificationTree {
+        let mut tree = HttpCertificationTree::default();
+        tree.insert(&HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/index.html"),
+            HttpCertification::skip(),
+        ));
+        tree.insert(&HttpCertificationTreeEntry::new(
+            HttpCertificationPath::wildcard("/assets/"),
+            HttpCertification::skip(),
+        ));
+        tree.insert(&HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/api/users"),
+            HttpCertification::skip(),
+        ));
+
+        tree
+    }
+
+    #[rstest]
+    #[case("/")]
+    #[case("/not-found.html")]
+    #[case("/index.html/")]
+    #[case("/api")]
+    #[case("/api/users/1")]
+    #[case("/asset")]
+    fn absence_witness_proves_absence(#[case] request_url: &str) {
+        let tree = absence_tree();
+
+        let witness = tree.absence_witness(request_url).unwrap();
+
+        assert_eq!(witness.digest(), tree.root_hash());
+        assert!(is_certification_absent(&witness, request_url));
+    }
+
+    #[rstest]
+    #[case("/index.html")]
+    #[case("/assets")]
+    #[case("/assets/")]
+    #[case("/assets/app.js")]
+    #[case("/api/users")]
+    fn absence_witness_for_certified_url(#[case] request_url: &str) {
+        let tree = absence_tree();
+
+        assert!(matches!(
+            tree.absence_witness(request_url),
+            Err(HttpCertificationError::CertificationExists(url)) if url == request_url
+        ));
+    }
+
+    #[rstest]
+    fn absence_witness_after_delete() {
+        let mut tree = absence_tree();
+        let entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/index.html"),
+            HttpCertification::skip(),
+        );
+
+        tree.delete(&entry);
+
+        let witness = tree.absence_witness("/index.html").unwrap();
+        assert!(is_certification_absent(&witness, "/index.html"));
+    }
+
+    #[rstest]
+    fn is_certification_absent_for_other_witnesses() {
+        let tree = absence_tree();
+        let entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/index.html"),
+            HttpCertification::skip(),
+        );
+
+        // a witness for a certified url cannot prove its absence
+        let witness = tree.witness(&entry, "/index.html");
+        assert!(!is_certification_absent(&witness, "/index.html"));
+
+        // a witness for a different url does not include enough of the tree
+        let witness = tree.absence_witness("/not-found.html").unwrap();
+        assert!(!is_certification_absent(&witness, "/api/users/1"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn certify_absence_omits_expr_path() {
+        let tree = absence_tree();
+        let response = HttpResponse {
+            status_code: 404,
+            headers: vec![],
+            body: b"Not Found".to_vec(),
+            upgrade: None,
+            trailers: None,
+        };
+
+        let certified_response = tree.certify_absence("/not-found.html", &response).unwrap();
+        assert!(certified_response.expr_path().is_empty());
+
+        let served_response = certified_response.serve(&[1, 2, 3]).unwrap();
+        let (_, header_value) = served_response.headers.last().unwrap();
+        assert!(header_value.starts_with("certificate=:AQID:, tree=:"));
+        assert!(!header_value.contains("expr_path"));
     }
 }
@@ -1,5 +1,12 @@
-use super::certification_tree_path::{HttpCertificationPath, InnerTreePath};
-use crate::HttpCertification;
+use super::certification_tree_path::{
+    CertificationTreePathSegment, HttpCertificationPath, InnerTreePath,
+    EXACT_PATH_TERMINATOR_BYTES, WILDCARD_PATH_TERMINATOR_BYTES,
+};
+use crate::{
+    DefaultCelExpression, HttpCertification, HttpCertificationError, HttpCertificationResult,
+    HttpRequest, HttpResponse,
+};
+use ic_certification::Hash;
 use std::borrow::Cow;
 
 /// An entry in an [HttpCertificationTree](crate::HttpCertificationTree).
@@ -15,6 +22,12 @@ use std::borrow::Cow;
 /// [HttpCertification](crate::HttpCertification) definition itself.
 ///
 /// Use the [new](HttpCertificationTreeEntry::new) associated function to create a new `HttpCertificationTreeEntry`.
+///
+/// Both properties may be borrowed, so an entry for a request can be created from a path and
+/// certification that are kept in the canister's state without copying them. Entries that are
+/// kept in the canister's state themselves can be borrowed with
+/// [as_borrowed](HttpCertificationTreeEntry::as_borrowed), and borrowed entries can be converted
+/// into entries that can be stored with [into_owned](HttpCertificationTreeEntry::into_owned).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HttpCertificationTreeEntry<'a> {
     /// The path of an [HttpCertification](crate::HttpCertification) definition within the tree.
@@ -40,6 +53,37 @@ impl<'a> HttpCertificationTreeEntry<'a> {
         }
     }
 
+    /// Returns an entry that borrows the path and certification of `self`, without copying them.
+    pub fn as_borrowed(&self) -> HttpCertificationTreeEntry<'_> {
+        HttpCertificationTreeEntry {
+            path: Cow::Borrowed(self.path.as_ref()),
+            certification: Cow::Borrowed(self.certification.as_ref()),
+        }
+    }
+
+    /// Converts this entry into an entry that owns its path and certification, copying them if
+    /// they are borrowed.
+    pub fn into_owned(self) -> HttpCertificationTreeEntry<'static> {
+        HttpCertificationTreeEntry {
+            path: Cow::Owned(self.path.into_owned().into_owned()),
+            certification: Cow::Owned(self.certification.into_owned()),
+        }
+    }
+
+    /// Recreates an entry from its full tree path, as created by
+    /// [to_tree_path](HttpCertificationTreeEntry::to_tree_path).
+    pub(super) fn from_tree_path(tree_path: &[CertificationTreePathSegment]) -> Option<Self> {
+        let terminator_index = tree_path.iter().position(|segment| {
+            segment == EXACT_PATH_TERMINATOR_BYTES || segment == WILDCARD_PATH_TERMINATOR_BYTES
+        })?;
+        let (path, certification) = tree_path.split_at(terminator_index + 1);
+
+        Some(Self::new(
+            HttpCertificationPath::from_tree_path(path)?,
+            HttpCertification::from_tree_path(certification)?,
+        ))
+    }
+
     pub(super) fn to_tree_path(&self) -> InnerTreePath {
         let mut tree_path = vec![];
         tree_path.append(&mut self.path.to_tree_path());
@@ -47,14 +91,36 @@ impl<'a> HttpCertificationTreeEntry<'a> {
 
         tree_path
     }
+
+    /// Checks that the certification of this entry matches `response`, see
+    /// [HttpCertificationTree::certify_response](crate::HttpCertificationTree::certify_response).
+    pub(super) fn validate_response(
+        &self,
+        cel_expr: &DefaultCelExpression,
+        request: Option<&HttpRequest>,
+        response: &HttpResponse,
+        response_body_hash: Option<Hash>,
+    ) -> HttpCertificationResult {
+        match self.certification.matches_response(
+            cel_expr,
+            request,
+            response,
+            response_body_hash,
+        )? {
+            true => Ok(()),
+            false => Err(HttpCertificationError::CertificationMismatch(
+                self.path.get_path().to_string(),
+            )),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        request_hash, response_hash, DefaultCelBuilder, DefaultResponseCertification, HttpRequest,
-        HttpResponse,
+        request_hash, response_hash, tree::HttpCertificationPathType, DefaultCelBuilder,
+        DefaultResponseCertification,
     };
     use ic_representation_independent_hash::hash;
     use rstest::*;
@@ -114,10 +180,11 @@ mod tests {
             body: vec![],
             headers: vec![],
             upgrade: None,
+            trailers: None,
         };
-        let expected_response_hash = response_hash(&response, &cel_expr.response, None);
+        let expected_response_hash = response_hash(&response, &cel_expr.response, None).unwrap();
 
-        let certification = HttpCertification::response_only(&cel_expr, &response, None);
+        let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
         let entry = HttpCertificationTreeEntry::new(&path, certification);
 
         let result = entry.to_tree_path();
@@ -161,8 +228,9 @@ mod tests {
             body: vec![],
             headers: vec![],
             upgrade: None,
+            trailers: None,
         };
-        let expected_response_hash = response_hash(&response, &cel_expr.response, None);
+        let expected_response_hash = response_hash(&response, &cel_expr.response, None).unwrap();
 
         let certification = HttpCertification::full(&cel_expr, &request, &response, None).unwrap();
         let entry = HttpCertificationTreeEntry::new(&path, certification);
@@ -186,4 +254,32 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[apply(certification_paths)]
+    fn borrowed_and_owned_entries(
+        #[case] path: HttpCertificationPath<'static>,
+        #[case] _expected: Vec<&str>,
+    ) {
+        let owned_entry = HttpCertificationTreeEntry::new(path.clone(), HttpCertification::skip());
+        let borrowed_entry = owned_entry.as_borrowed();
+
+        assert!(matches!(borrowed_entry.path, Cow::Borrowed(_)));
+        assert!(matches!(borrowed_entry.certification, Cow::Borrowed(_)));
+        assert_eq!(borrowed_entry, owned_entry.as_borrowed());
+        assert_eq!(borrowed_entry.to_tree_path(), owned_entry.to_tree_path());
+
+        let request_path = String::from(path.get_path());
+        let borrowed_path = match path.get_type() {
+            HttpCertificationPathType::Exact(_) => HttpCertificationPath::exact(&request_path),
+            HttpCertificationPathType::Wildcard(_) => {
+                HttpCertificationPath::wildcard(&request_path)
+            }
+        };
+        let entry = HttpCertificationTreeEntry::new(&borrowed_path, HttpCertification::skip());
+        let owned_entry_from_borrowed = entry.into_owned();
+        drop(request_path);
+
+        assert!(matches!(owned_entry_from_borrowed.path, Cow::Owned(_)));
+        assert_eq!(owned_entry_from_borrowed, owned_entry);
+    }
 }
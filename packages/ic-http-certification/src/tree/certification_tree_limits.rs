@@ -0,0 +1,125 @@
+use super::certification_tree_entry::HttpCertificationTreeEntry;
+use crate::{HttpCertificationError, HttpCertificationResult};
+
+/// Limits on the entries that can be inserted into an
+/// [HttpCertificationTree](crate::HttpCertificationTree) with
+/// [try_insert](crate::HttpCertificationTree::try_insert).
+///
+/// Canisters that certify user-influenced URLs can use these limits to prevent requests from
+/// growing the tree without bounds. By default, no limits are enforced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HttpCertificationTreeLimits {
+    max_url_length: Option<usize>,
+    max_path_depth: Option<usize>,
+}
+
+impl HttpCertificationTreeLimits {
+    /// Creates new [HttpCertificationTreeLimits] that do not enforce any limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum length, in bytes, of the path of a certified entry.
+    pub fn with_max_url_length(mut self, max_url_length: usize) -> Self {
+        self.max_url_length = Some(max_url_length);
+
+        self
+    }
+
+    /// Sets the maximum number of segments of the path of a certified entry, as they appear in
+    /// its expression path. For example, `/assets/js/app.js` has a depth of `3`.
+    pub fn with_max_path_depth(mut self, max_path_depth: usize) -> Self {
+        self.max_path_depth = Some(max_path_depth);
+
+        self
+    }
+
+    /// Checks that the given [HttpCertificationTreeEntry] is within these limits.
+    pub fn check(&self, entry: &HttpCertificationTreeEntry) -> HttpCertificationResult {
+        let url = entry.path.get_path();
+        if let Some(max_url_length) = self.max_url_length {
+            if url.len() > max_url_length {
+                return Err(HttpCertificationError::UrlTooLong {
+                    url_length: url.len(),
+                    max_url_length,
+                });
+            }
+        }
+
+        if let Some(max_path_depth) = self.max_path_depth {
+            // the expression path includes a prefix and a terminator
+            let path_depth = entry.path.to_expr_path().len() - 2;
+            if path_depth > max_path_depth {
+                return Err(HttpCertificationError::PathTooDeep {
+                    path_depth,
+                    max_path_depth,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HttpCertification, HttpCertificationPath};
+    use rstest::*;
+
+    fn entry(path: &str) -> HttpCertificationTreeEntry<'_> {
+        HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact(path),
+            HttpCertification::skip(),
+        )
+    }
+
+    #[rstest]
+    #[case("/")]
+    #[case("/assets/js/app.js")]
+    #[case("/assets/js/")]
+    fn check_within_limits(#[case] path: &str) {
+        let limits = HttpCertificationTreeLimits::new()
+            .with_max_url_length(17)
+            .with_max_path_depth(3);
+
+        assert!(limits.check(&entry(path)).is_ok());
+    }
+
+    #[rstest]
+    fn check_without_limits() {
+        let path = "/a".repeat(1_000);
+
+        assert!(HttpCertificationTreeLimits::new()
+            .check(&entry(&path))
+            .is_ok());
+    }
+
+    #[rstest]
+    fn check_url_too_long() {
+        let limits = HttpCertificationTreeLimits::new().with_max_url_length(16);
+
+        assert!(matches!(
+            limits.check(&entry("/assets/js/app.js")),
+            Err(HttpCertificationError::UrlTooLong {
+                url_length: 17,
+                max_url_length: 16
+            })
+        ));
+    }
+
+    #[rstest]
+    #[case("/assets/js/app.js", 3)]
+    #[case("/assets/js/lib/", 4)]
+    fn check_path_too_deep(#[case] path: &str, #[case] expected_depth: usize) {
+        let limits = HttpCertificationTreeLimits::new().with_max_path_depth(2);
+
+        assert!(matches!(
+            limits.check(&entry(path)),
+            Err(HttpCertificationError::PathTooDeep {
+                path_depth,
+                max_path_depth: 2
+            }) if path_depth == expected_depth
+        ));
+    }
+}
@@ -13,6 +13,7 @@ pub(super) const WILDCARD_PATH_TERMINATOR: &str = "<*>";
 pub(super) const WILDCARD_PATH_TERMINATOR_BYTES: &[u8] = WILDCARD_PATH_TERMINATOR.as_bytes();
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(super) enum HttpCertificationPathType<'a> {
     Exact(Cow<'a, str>),
     Wildcard(Cow<'a, str>),
@@ -30,6 +31,7 @@ pub(super) enum HttpCertificationPathType<'a> {
 /// - The [Wildcard](HttpCertificationPath::Wildcard) variant is used for paths that match a URL path prefix.
 /// For example, `HttpCertificationPath::Wildcard('/foo')` will match the URL paths `/foo/bar` and `/foo/baz`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HttpCertificationPath<'a>(HttpCertificationPathType<'a>);
 
 impl<'a> HttpCertificationPath<'a> {
@@ -47,6 +49,29 @@ impl<'a> HttpCertificationPath<'a> {
         Self(HttpCertificationPathType::Wildcard(path.into()))
     }
 
+    /// Returns a path that borrows the path of `self`, without copying it.
+    pub fn as_borrowed(&self) -> HttpCertificationPath<'_> {
+        match &self.0 {
+            HttpCertificationPathType::Exact(path) => HttpCertificationPath::exact(path.as_ref()),
+            HttpCertificationPathType::Wildcard(path) => {
+                HttpCertificationPath::wildcard(path.as_ref())
+            }
+        }
+    }
+
+    /// Converts this path into a path that owns its path, copying it if it is borrowed, so that
+    /// it can be stored independently of the data it was borrowed from.
+    pub fn into_owned(self) -> HttpCertificationPath<'static> {
+        match self.0 {
+            HttpCertificationPathType::Exact(path) => {
+                HttpCertificationPath::exact(path.into_owned())
+            }
+            HttpCertificationPathType::Wildcard(path) => {
+                HttpCertificationPath::wildcard(path.into_owned())
+            }
+        }
+    }
+
     pub(super) fn to_tree_path(&self) -> InnerTreePath {
         match &self.0 {
             HttpCertificationPathType::Exact(path) => {
@@ -58,10 +83,37 @@ impl<'a> HttpCertificationPath<'a> {
         }
     }
 
+    /// Recreates a path from the segments of its tree path, as created by
+    /// [to_tree_path](HttpCertificationPath::to_tree_path), including the terminator.
+    pub(super) fn from_tree_path(segments: &[CertificationTreePathSegment]) -> Option<Self> {
+        let (terminator, segments) = segments.split_last()?;
+        let path = match segments.is_empty() {
+            true => String::new(),
+            false => segments
+                .iter()
+                .map(|segment| std::str::from_utf8(segment).map(|segment| format!("/{segment}")))
+                .collect::<Result<String, _>>()
+                .ok()?,
+        };
+
+        match terminator.as_slice() {
+            EXACT_PATH_TERMINATOR_BYTES => Some(Self::exact(path)),
+            WILDCARD_PATH_TERMINATOR_BYTES => Some(Self::wildcard(path)),
+            _ => None,
+        }
+    }
+
     pub(super) fn get_type(&self) -> &HttpCertificationPathType<'a> {
         &self.0
     }
 
+    pub(super) fn get_path(&self) -> &str {
+        match &self.0 {
+            HttpCertificationPathType::Exact(path) => path.as_ref(),
+            HttpCertificationPathType::Wildcard(path) => path.as_ref(),
+        }
+    }
+
     /// Converts this path into a format suitable for use in the `expr_path` field of the `IC-Certificate` header.
     pub fn to_expr_path(&self) -> Vec<String> {
         match &self.0 {
@@ -208,4 +260,17 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    #[case(HttpCertificationPath::exact("/index.html"))]
+    #[case(HttpCertificationPath::wildcard("/assets"))]
+    fn path_serde_round_trip(#[case] path: HttpCertificationPath<'static>) {
+        let serialized = serde_cbor::to_vec(&path).unwrap();
+
+        assert_eq!(
+            serde_cbor::from_slice::<HttpCertificationPath>(&serialized).unwrap(),
+            path
+        );
+    }
 }
@@ -0,0 +1,149 @@
+use ic_representation_independent_hash::Sha256Digest;
+
+/// Tracks changes to the root hash of a certification tree, so that a canister's certified data
+/// is only updated once per update call or timer tick, and only if the root hash has changed.
+///
+/// Write-heavy canisters often modify a tree many times in a single update call. Instead of
+/// calling the `set_certified_data` system API after every modification,
+/// [record](CertifiedDataGuard::record) the new root hash after each modification and
+/// [flush](CertifiedDataGuard::flush) once at the end of the update call or timer tick:
+///
+/// ```
+/// use ic_http_certification::{
+///     CertifiedDataGuard, HttpCertification, HttpCertificationPath, HttpCertificationTree,
+///     HttpCertificationTreeEntry,
+/// };
+///
+/// let mut tree = HttpCertificationTree::default();
+/// let mut guard = CertifiedDataGuard::new();
+///
+/// for path in ["/a", "/b", "/c"] {
+///     let path = HttpCertificationPath::exact(path);
+///     tree.insert(&HttpCertificationTreeEntry::new(&path, HttpCertification::skip()));
+///     guard.record(tree.root_hash());
+/// }
+///
+/// let mut calls = 0;
+/// // `set_certified_data` would be called here, instead of counting calls
+/// guard.flush(|_root_hash| calls += 1);
+/// guard.flush(|_root_hash| calls += 1);
+///
+/// assert_eq!(calls, 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CertifiedDataGuard {
+    certified_root_hash: Option<Sha256Digest>,
+    pending_root_hash: Option<Sha256Digest>,
+    change_count: u64,
+    flush_count: u64,
+}
+
+impl CertifiedDataGuard {
+    /// Creates a new [CertifiedDataGuard] that has not certified any root hash yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the current root hash of the tree, after it may have been modified.
+    ///
+    /// The root hash is only certified by the next [flush](CertifiedDataGuard::flush) if it
+    /// differs from the last certified root hash.
+    pub fn record(&mut self, root_hash: Sha256Digest) {
+        let previous_root_hash = self.pending_root_hash.or(self.certified_root_hash);
+        if previous_root_hash == Some(root_hash) {
+            return;
+        }
+
+        self.change_count += 1;
+        self.pending_root_hash = match self.certified_root_hash == Some(root_hash) {
+            true => None,
+            false => Some(root_hash),
+        };
+    }
+
+    /// Returns true if a recorded root hash has not been certified yet.
+    pub fn is_pending(&self) -> bool {
+        self.pending_root_hash.is_some()
+    }
+
+    /// Calls `set_certified_data` with the last recorded root hash, if it has changed since the
+    /// last flush, and returns whether it was called.
+    ///
+    /// This should be called once at the end of each update call or timer tick that may have
+    /// modified the tree.
+    pub fn flush(&mut self, set_certified_data: impl FnOnce(&[u8])) -> bool {
+        let Some(root_hash) = self.pending_root_hash.take() else {
+            return false;
+        };
+
+        set_certified_data(&root_hash);
+        self.certified_root_hash = Some(root_hash);
+        self.flush_count += 1;
+
+        true
+    }
+
+    /// Returns the number of times that a [recorded](CertifiedDataGuard::record) root hash
+    /// differed from the previously recorded one.
+    pub fn change_count(&self) -> u64 {
+        self.change_count
+    }
+
+    /// Returns the number of times that [flush](CertifiedDataGuard::flush) updated the certified
+    /// data.
+    pub fn flush_count(&self) -> u64 {
+        self.flush_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const ROOT_HASH_A: Sha256Digest = [1; 32];
+    const ROOT_HASH_B: Sha256Digest = [2; 32];
+
+    #[rstest]
+    fn flush_without_changes_does_nothing() {
+        let mut guard = CertifiedDataGuard::new();
+
+        assert!(!guard.is_pending());
+        assert!(!guard.flush(|_| panic!("certified data should not be set")));
+        assert_eq!(guard.flush_count(), 0);
+    }
+
+    #[rstest]
+    fn flush_certifies_last_recorded_root_hash_once() {
+        let mut guard = CertifiedDataGuard::new();
+        guard.record(ROOT_HASH_A);
+        guard.record(ROOT_HASH_B);
+
+        let mut certified_data = vec![];
+        assert!(guard.is_pending());
+        assert!(guard.flush(|root_hash| certified_data = root_hash.to_vec()));
+        assert!(!guard.flush(|_| panic!("certified data should not be set")));
+
+        assert_eq!(certified_data, ROOT_HASH_B);
+        assert_eq!(guard.change_count(), 2);
+        assert_eq!(guard.flush_count(), 1);
+    }
+
+    #[rstest]
+    fn reverting_to_certified_root_hash_is_not_pending() {
+        let mut guard = CertifiedDataGuard::new();
+        guard.record(ROOT_HASH_A);
+        guard.flush(|_| {});
+
+        guard.record(ROOT_HASH_A);
+        assert!(!guard.is_pending());
+
+        guard.record(ROOT_HASH_B);
+        guard.record(ROOT_HASH_A);
+        assert!(!guard.is_pending());
+        assert!(!guard.flush(|_| panic!("certified data should not be set")));
+
+        assert_eq!(guard.change_count(), 3);
+        assert_eq!(guard.flush_count(), 1);
+    }
+}
@@ -0,0 +1,771 @@
+#[cfg(feature = "serde")]
+use super::certification_decision::{CertificationDecision, CONTENT_ENCODING_HEADER_NAME};
+use super::{
+    certification_decision::CertificationMatch,
+    certification_tree::{is_certification_absent, wildcard_tree_paths},
+    certification_tree_path::PATH_PREFIX_BYTES,
+};
+#[cfg(feature = "serde")]
+use crate::CERTIFICATE_HEADER_NAME;
+use crate::{
+    cel::parse_cel_expression, CelExpression, DefaultCelExpression, HttpCertification,
+    HttpCertificationError, HttpCertificationPath, HttpCertificationResult, HttpRequest,
+    HttpResponse, CERTIFICATE_EXPRESSION_HEADER_NAME,
+};
+#[cfg(feature = "serde")]
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use ic_certification::{Hash, HashTree, SubtreeLookupResult};
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// A conservative estimate, in bytes, of the size of a canister's data certificate, including a
+/// subnet delegation. Used by
+/// [HttpCertificationTree::estimate_header_size](crate::HttpCertificationTree::estimate_header_size).
+pub const ESTIMATED_DATA_CERTIFICATE_SIZE: usize = 1_500;
+
+/// An [HttpResponse] that has been checked against an
+/// [HttpCertificationTree](crate::HttpCertificationTree) and is ready to be served.
+///
+/// A `CertifiedResponse` can only be created by
+/// [HttpCertificationTree::certify_response](crate::HttpCertificationTree::certify_response),
+/// which guarantees that the corresponding
+/// [HttpCertificationTreeEntry](crate::HttpCertificationTreeEntry) is present in the tree, or by
+/// [HttpCertificationTree::certify_absence](crate::HttpCertificationTree::certify_absence),
+/// which guarantees that no certification for the request URL is present in the tree.
+/// The only way to get the [HttpResponse] back out is through [serve](CertifiedResponse::serve),
+/// which attaches the `IC-Certificate` header, so a certified route that returns the
+/// result of [serve](CertifiedResponse::serve) cannot accidentally return an uncertified response.
+///
+/// Serving the response requires the `serde` feature, which is needed to CBOR encode the
+/// witness and the expression path for the `IC-Certificate` header.
+#[derive(Debug, Clone)]
+pub struct CertifiedResponse<'a> {
+    response: Cow<'a, HttpResponse>,
+    witness: HashTree,
+    expr_path: Vec<String>,
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    request_url: String,
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    certification_match: CertificationMatch,
+}
+
+impl<'a> CertifiedResponse<'a> {
+    pub(super) fn new(
+        response: Cow<'a, HttpResponse>,
+        witness: HashTree,
+        expr_path: Vec<String>,
+        request_url: &str,
+        certification_match: CertificationMatch,
+    ) -> Self {
+        Self {
+            response,
+            witness,
+            expr_path,
+            request_url: request_url.to_string(),
+            certification_match,
+        }
+    }
+
+    /// Returns the [HttpResponse] that will be served.
+    pub fn response(&self) -> &HttpResponse {
+        &self.response
+    }
+
+    /// Returns the pruned [HashTree] proving the presence of the response's certification in the
+    /// tree, or the absence of any certification for an
+    /// [absence proof](crate::HttpCertificationTree::certify_absence).
+    pub fn witness(&self) -> &HashTree {
+        &self.witness
+    }
+
+    /// Returns the path of the response's certification in the tree,
+    /// as used in the `expr_path` field of the `IC-Certificate` header.
+    /// The path is empty for an [absence proof](crate::HttpCertificationTree::certify_absence).
+    pub fn expr_path(&self) -> &[String] {
+        &self.expr_path
+    }
+
+    /// Returns a [CertificationDecision] that records how the response was certified, such as
+    /// whether an exact or a wildcard certification matched the request URL, which encoding is
+    /// served and the size of the witness, so it can be logged before the response is
+    /// [served](CertifiedResponse::serve).
+    ///
+    /// Requires the `serde` feature, which is needed to CBOR encode the witness.
+    #[cfg(feature = "serde")]
+    pub fn decision(&self) -> HttpCertificationResult<CertificationDecision> {
+        let witness_size = cbor_encode(&self.witness)?.len();
+
+        Ok(CertificationDecision::new(
+            &self.request_url,
+            self.certification_match.clone(),
+            self.response.status_code,
+            self.response.get_header_value(CONTENT_ENCODING_HEADER_NAME),
+            witness_size,
+        ))
+    }
+
+    /// Runs the checks of the HTTP Gateway Protocol's verification algorithm that do not depend
+    /// on the data certificate against the response for the given request, so that
+    /// misconfigurations are caught in canister tests, rather than by HTTP gateways.
+    ///
+    /// This checks that the [expression path](CertifiedResponse::expr_path) is the most precise
+    /// path in the [witness](CertifiedResponse::witness) that matches the request path, that the
+    /// `IC-CertificateExpression` header is present and is a valid CEL expression in its
+    /// canonical form, and that the request and response hashes, recomputed according to that
+    /// expression, are present in the witness. For an
+    /// [absence proof](crate::HttpCertificationTree::certify_absence), it checks that the witness
+    /// proves that no certification matches the request path.
+    ///
+    /// `response_body_hash` must be the same hash that was provided when the response was
+    /// certified, such as the hash of the identity body for
+    /// [decoded](crate::cel::DefaultBodyCertification::Decoded) body certification.
+    ///
+    /// The data certificate is not checked, nor whether the witness matches the canister's
+    /// certified data, so a successful dry run does not guarantee that verification succeeds.
+    pub fn dry_run_verify(
+        &self,
+        request: &HttpRequest,
+        response_body_hash: Option<Hash>,
+    ) -> HttpCertificationResult {
+        let request_path = request.canonical_path()?;
+
+        // absence proofs do not have an expression path
+        if self.expr_path.is_empty() {
+            return match is_certification_absent(&self.witness, &request_path) {
+                true => Ok(()),
+                false => Err(dry_run_error(
+                    "the witness does not prove the absence of a certification for the request path",
+                )),
+            };
+        }
+
+        let expr_path = self
+            .expr_path
+            .iter()
+            .map(|segment| segment.as_bytes().to_vec())
+            .collect::<Vec<_>>();
+        validate_expr_path(&expr_path, &request_path, &self.witness)?;
+
+        let cel_expr = self
+            .response
+            .get_header_value(CERTIFICATE_EXPRESSION_HEADER_NAME)
+            .ok_or_else(|| dry_run_error("the IC-CertificateExpression header is missing"))?;
+        let parsed_cel_expr = parse_cel_expression(cel_expr).map_err(|err| {
+            dry_run_error(&format!(
+                "the IC-CertificateExpression header is not a valid CEL expression: {err}"
+            ))
+        })?;
+        if parsed_cel_expr.to_string() != cel_expr {
+            return Err(dry_run_error(
+                "the IC-CertificateExpression header is not in its canonical form, so its hash does not match the certification",
+            ));
+        }
+
+        let certification = match &parsed_cel_expr {
+            CelExpression::Default(DefaultCelExpression::Skip) => HttpCertification::skip(),
+            CelExpression::Default(DefaultCelExpression::ResponseOnly(cel_expr)) => {
+                HttpCertification::response_only(cel_expr, self.response(), response_body_hash)?
+            }
+            CelExpression::Default(DefaultCelExpression::Full(cel_expr)) => {
+                HttpCertification::full(cel_expr, request, self.response(), response_body_hash)?
+            }
+        };
+
+        let tree_path = [expr_path, certification.to_tree_path()].concat();
+        match self.witness.lookup_subtree(&tree_path) {
+            SubtreeLookupResult::Found(_) => Ok(()),
+            _ => Err(dry_run_error(
+                "the recomputed expression, request or response hash is not present in the witness",
+            )),
+        }
+    }
+
+    /// Consumes the `CertifiedResponse` and returns the final [HttpResponse] with the `IC-Certificate`
+    /// header attached.
+    ///
+    /// `data_certificate` is the canister's current data certificate, as returned by the
+    /// `data_certificate` system API. Note that this is only available in query calls.
+    ///
+    /// Requires the `serde` feature, which is needed to CBOR encode the witness.
+    #[cfg(feature = "serde")]
+    pub fn serve(self, data_certificate: &[u8]) -> HttpCertificationResult<HttpResponse> {
+        let witness = cbor_encode(&self.witness)?;
+
+        // absence proofs do not have an expression path
+        let certificate_header = match self.expr_path.is_empty() {
+            true => format!(
+                "certificate=:{}:, tree=:{}:, version=2",
+                BASE64.encode(data_certificate),
+                BASE64.encode(witness)
+            ),
+            false => format!(
+                "certificate=:{}:, tree=:{}:, expr_path=:{}:, version=2",
+                BASE64.encode(data_certificate),
+                BASE64.encode(witness),
+                BASE64.encode(cbor_encode(&self.expr_path)?)
+            ),
+        };
+
+        let mut response = self.response.into_owned();
+        response
+            .headers
+            .push((CERTIFICATE_HEADER_NAME.to_string(), certificate_header));
+
+        Ok(response)
+    }
+}
+
+/// Checks that the expression path is the most precise path that matches the request path, in
+/// the same way as HTTP gateways. Paths that are more precise than the expression path must be
+/// absent from the witness.
+fn validate_expr_path(
+    expr_path: &[Vec<u8>],
+    request_path: &str,
+    witness: &HashTree,
+) -> HttpCertificationResult {
+    let exact_tree_path = HttpCertificationPath::exact(request_path).to_tree_path();
+    let candidate_tree_paths =
+        std::iter::once(exact_tree_path).chain(wildcard_tree_paths(request_path).into_iter().rev());
+
+    for tree_path in candidate_tree_paths {
+        let candidate_expr_path = [vec![PATH_PREFIX_BYTES.to_vec()], tree_path].concat();
+        if candidate_expr_path == expr_path {
+            return match witness.lookup_subtree(expr_path) {
+                SubtreeLookupResult::Found(_) => Ok(()),
+                _ => Err(dry_run_error(
+                    "the expression path is not present in the witness",
+                )),
+            };
+        }
+
+        if !matches!(
+            witness.lookup_subtree(&candidate_expr_path),
+            SubtreeLookupResult::Absent
+        ) {
+            return Err(dry_run_error(
+                "the witness does not prove that there is no more precise path for the request path",
+            ));
+        }
+    }
+
+    Err(dry_run_error(
+        "the expression path does not match the request path",
+    ))
+}
+
+fn dry_run_error(reason: &str) -> HttpCertificationError {
+    HttpCertificationError::DryRunVerificationFailed(reason.to_string())
+}
+
+/// Returns the size, in bytes, of the name and value of an `IC-Certificate` header, as created
+/// by [CertifiedResponse::serve], for CBOR encoded witness and expression path of the given sizes.
+#[cfg(feature = "serde")]
+pub(super) fn certificate_header_size(
+    data_certificate_size: usize,
+    witness_size: usize,
+    expr_path_size: usize,
+) -> usize {
+    let base64_size = |size: usize| size.div_ceil(3) * 4;
+
+    CERTIFICATE_HEADER_NAME.len()
+        + "certificate=::, tree=::, expr_path=::, version=2".len()
+        + base64_size(data_certificate_size)
+        + base64_size(witness_size)
+        + base64_size(expr_path_size)
+}
+
+pub(super) fn cbor_encode(value: &impl Serialize) -> HttpCertificationResult<Vec<u8>> {
+    let mut serializer = serde_cbor::Serializer::new(Vec::new());
+    serializer
+        .self_describe()
+        .map_err(|err| HttpCertificationError::CborEncodingError(err.to_string()))?;
+    value
+        .serialize(&mut serializer)
+        .map_err(|err| HttpCertificationError::CborEncodingError(err.to_string()))?;
+
+    Ok(serializer.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DefaultCelBuilder, DefaultFullCelExpression, HttpCertificationTree,
+        HttpCertificationTreeEntry,
+    };
+    use rstest::*;
+
+    #[fixture]
+    fn response() -> HttpResponse {
+        HttpResponse {
+            status_code: 200,
+            headers: vec![],
+            body: vec![1, 2, 3, 4, 5, 6],
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    #[fixture]
+    fn cel_expr() -> DefaultCelExpression<'static> {
+        DefaultCelExpression::ResponseOnly(DefaultCelBuilder::response_only_certification().build())
+    }
+
+    #[fixture]
+    fn certification(response: HttpResponse) -> HttpCertification {
+        let cel_expr = DefaultCelBuilder::response_only_certification().build();
+
+        HttpCertification::response_only(&cel_expr, &response, None).unwrap()
+    }
+
+    #[rstest]
+    fn certify_response_for_entry_in_tree(
+        response: HttpResponse,
+        certification: HttpCertification,
+    ) {
+        let path = HttpCertificationPath::exact("/index.html");
+        let entry = HttpCertificationTreeEntry::new(&path, certification);
+        let mut tree = HttpCertificationTree::default();
+        tree.insert(&entry);
+
+        let certified_response = tree
+            .certify_response(&entry, "/index.html", &cel_expr(), None, &response, None)
+            .unwrap();
+
+        assert_eq!(certified_response.response(), &response);
+        assert_eq!(certified_response.expr_path(), path.to_expr_path());
+        assert_eq!(
+            certified_response.witness().digest(),
+            tree.witness(&entry, "/index.html").digest()
+        );
+    }
+
+    #[rstest]
+    fn certify_response_for_entry_not_in_tree(
+        response: HttpResponse,
+        certification: HttpCertification,
+    ) {
+        let path = HttpCertificationPath::exact("/index.html");
+        let entry = HttpCertificationTreeEntry::new(&path, certification);
+        let tree = HttpCertificationTree::default();
+
+        let result =
+            tree.certify_response(&entry, "/index.html", &cel_expr(), None, &response, None);
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::CertificationNotFound(path)) if path == "/index.html"
+        ));
+    }
+
+    #[rstest]
+    fn certify_response_for_changed_response(
+        response: HttpResponse,
+        certification: HttpCertification,
+    ) {
+        let path = HttpCertificationPath::exact("/index.html");
+        let entry = HttpCertificationTreeEntry::new(&path, certification);
+        let mut tree = HttpCertificationTree::default();
+        tree.insert(&entry);
+        let changed_response = HttpResponse {
+            body: b"Goodbye World!".to_vec(),
+            ..response
+        };
+
+        let result = tree.certify_response(
+            &entry,
+            "/index.html",
+            &cel_expr(),
+            None,
+            &changed_response,
+            None,
+        );
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::CertificationMismatch(path)) if path == "/index.html"
+        ));
+    }
+
+    #[rstest]
+    fn certify_response_for_different_cel_expression(
+        response: HttpResponse,
+        certification: HttpCertification,
+    ) {
+        let path = HttpCertificationPath::exact("/index.html");
+        let entry = HttpCertificationTreeEntry::new(&path, certification);
+        let mut tree = HttpCertificationTree::default();
+        tree.insert(&entry);
+
+        let result = tree.certify_response(
+            &entry,
+            "/index.html",
+            &DefaultCelExpression::Skip,
+            None,
+            &response,
+            None,
+        );
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::CertificationMismatch(_))
+        ));
+    }
+
+    #[rstest]
+    fn certify_response_for_full_certification_without_request() {
+        let mut tree = HttpCertificationTree::default();
+        let entry = insert_full_certification(
+            &mut tree,
+            HttpCertificationPath::exact("/index.html"),
+            &get_request("/index.html"),
+        );
+
+        let result = tree.certify_response(
+            &entry,
+            "/index.html",
+            &DefaultCelExpression::Full(full_cel_expr()),
+            None,
+            full_response(),
+            None,
+        );
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::MissingCertifiedRequest)
+        ));
+    }
+
+    #[rstest]
+    fn certify_response_for_full_certification_with_different_request() {
+        let mut tree = HttpCertificationTree::default();
+        let entry = insert_full_certification(
+            &mut tree,
+            HttpCertificationPath::exact("/index.html"),
+            &get_request("/index.html"),
+        );
+        let request = HttpRequest {
+            headers: vec![("Accept".to_string(), "application/json".to_string())],
+            ..get_request("/index.html")
+        };
+
+        let result = tree.certify_response(
+            &entry,
+            "/index.html",
+            &DefaultCelExpression::Full(full_cel_expr()),
+            Some(&request),
+            full_response(),
+            None,
+        );
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::CertificationMismatch(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn serve_adds_certificate_header(response: HttpResponse, certification: HttpCertification) {
+        let path = HttpCertificationPath::wildcard("/");
+        let entry = HttpCertificationTreeEntry::new(&path, certification);
+        let mut tree = HttpCertificationTree::default();
+        tree.insert(&entry);
+        let data_certificate = vec![7, 8, 9];
+
+        let served_response = tree
+            .certify_response(&entry, "/not-found", &cel_expr(), None, &response, None)
+            .unwrap()
+            .serve(&data_certificate)
+            .unwrap();
+
+        let expected_witness = cbor_encode(&tree.witness(&entry, "/not-found")).unwrap();
+        let expected_expr_path = cbor_encode(&path.to_expr_path()).unwrap();
+        let mut expected_headers = response.headers.clone();
+        expected_headers.push((
+            CERTIFICATE_HEADER_NAME.to_string(),
+            format!(
+                "certificate=:{}:, tree=:{}:, expr_path=:{}:, version=2",
+                BASE64.encode(&data_certificate),
+                BASE64.encode(expected_witness),
+                BASE64.encode(expected_expr_path)
+            ),
+        ));
+
+        assert_eq!(served_response.status_code, response.status_code);
+        assert_eq!(served_response.body, response.body);
+        assert_eq!(served_response.headers, expected_headers);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn decision_records_wildcard_match(response: HttpResponse) {
+        let mut response = response;
+        response
+            .headers
+            .push(("Content-Encoding".to_string(), "gzip".to_string()));
+        let path = HttpCertificationPath::wildcard("/assets");
+        let entry = HttpCertificationTreeEntry::new(&path, certification(response.clone()));
+        let mut tree = HttpCertificationTree::default();
+        tree.insert(&entry);
+
+        let decision = tree
+            .certify_response(&entry, "/assets/app.js", &cel_expr(), None, &response, None)
+            .unwrap()
+            .decision()
+            .unwrap();
+
+        assert_eq!(
+            decision,
+            CertificationDecision {
+                request_url: "/assets/app.js".to_string(),
+                certification_match: CertificationMatch::Wildcard("/assets".to_string()),
+                status_code: 200,
+                content_encoding: Some("gzip".to_string()),
+                witness_size: cbor_encode(&tree.witness(&entry, "/assets/app.js"))
+                    .unwrap()
+                    .len(),
+            }
+        );
+        assert_eq!(
+            decision.to_string(),
+            format!(
+                "url=/assets/app.js match=\"wildcard /assets\" status=200 encoding=gzip witness_size={}",
+                decision.witness_size
+            )
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn decision_records_absence(response: HttpResponse) {
+        let tree = HttpCertificationTree::default();
+
+        let decision = tree
+            .certify_absence("/not-found", &response)
+            .unwrap()
+            .decision()
+            .unwrap();
+
+        assert_eq!(decision.certification_match, CertificationMatch::Absent);
+        assert_eq!(decision.content_encoding, None);
+        assert!(decision
+            .to_string()
+            .starts_with("url=/not-found match=\"absent\" status=200 encoding=identity"));
+    }
+
+    fn insert_full_certification(
+        tree: &mut HttpCertificationTree,
+        path: HttpCertificationPath<'static>,
+        request: &HttpRequest,
+    ) -> HttpCertificationTreeEntry<'static> {
+        let certification =
+            HttpCertification::full(&full_cel_expr(), request, full_response(), None).unwrap();
+        let entry = HttpCertificationTreeEntry::new(path, certification);
+        tree.insert(&entry);
+
+        entry
+    }
+
+    fn full_cel_expr() -> DefaultFullCelExpression<'static> {
+        DefaultCelBuilder::full_certification()
+            .with_request_headers(vec!["Accept"])
+            .build()
+    }
+
+    fn full_response() -> HttpResponse {
+        HttpResponse {
+            status_code: 200,
+            headers: vec![(
+                CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+                full_cel_expr().to_string(),
+            )],
+            body: b"Hello World!".to_vec(),
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    fn get_request(url: &str) -> HttpRequest {
+        HttpRequest {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            headers: vec![("Accept".to_string(), "text/html".to_string())],
+            body: vec![],
+        }
+    }
+
+    #[rstest]
+    #[case(HttpCertificationPath::exact("/index.html"), "/index.html")]
+    #[case(HttpCertificationPath::wildcard("/"), "/assets/app.js")]
+    #[case(HttpCertificationPath::wildcard("/assets/"), "/assets/app.js?v=1")]
+    fn dry_run_verify_succeeds(#[case] path: HttpCertificationPath<'static>, #[case] url: &str) {
+        let mut tree = HttpCertificationTree::default();
+        let request = get_request(url);
+        let entry = insert_full_certification(&mut tree, path, &request);
+        let response = full_response();
+
+        let certified_response = tree
+            .certify_response(
+                &entry,
+                url,
+                &DefaultCelExpression::Full(full_cel_expr()),
+                Some(&request),
+                &response,
+                None,
+            )
+            .unwrap();
+
+        assert!(certified_response.dry_run_verify(&request, None).is_ok());
+    }
+
+    #[rstest]
+    #[case("/assets/js/../app.js", "/assets/app.js")]
+    #[case("/assets/./app.js", "/assets/app.js")]
+    #[case("/assets%2Fapp.js", "/assets%2Fapp.js")]
+    #[case("/assets/", "/assets/")]
+    #[case("/assets/js/..", "/assets/")]
+    fn dry_run_verify_normalizes_request_url(#[case] url: &str, #[case] certified_path: &str) {
+        let mut tree = HttpCertificationTree::default();
+        let request = get_request(url);
+        let request_path = request.canonical_path().unwrap();
+        let entry = insert_full_certification(
+            &mut tree,
+            HttpCertificationPath::exact(request_path.clone()),
+            &request,
+        );
+
+        let certified_response = tree
+            .certify_response(
+                &entry,
+                &request_path,
+                &DefaultCelExpression::Full(full_cel_expr()),
+                Some(&request),
+                full_response(),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(request_path, certified_path);
+        assert!(certified_response.dry_run_verify(&request, None).is_ok());
+    }
+
+    #[rstest]
+    #[case("/assets/%2E%2E/app.js")]
+    #[case("/assets%2F..%2Fapp.js")]
+    #[case("/app.js/")]
+    fn dry_run_verify_fails_for_differently_normalized_url(#[case] url: &str) {
+        let mut tree = HttpCertificationTree::default();
+        let request = get_request(url);
+        let entry =
+            insert_full_certification(&mut tree, HttpCertificationPath::exact("/app.js"), &request);
+
+        let result = tree
+            .certify_response(
+                &entry,
+                "/app.js",
+                &DefaultCelExpression::Full(full_cel_expr()),
+                Some(&request),
+                full_response(),
+                None,
+            )
+            .unwrap()
+            .dry_run_verify(&request, None);
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::DryRunVerificationFailed(_))
+        ));
+    }
+
+    #[rstest]
+    fn dry_run_verify_fails_for_changed_request() {
+        let mut tree = HttpCertificationTree::default();
+        let request = get_request("/index.html");
+        let entry = insert_full_certification(
+            &mut tree,
+            HttpCertificationPath::exact("/index.html"),
+            &request,
+        );
+        let changed_request = HttpRequest {
+            headers: vec![("Accept".to_string(), "application/json".to_string())],
+            ..get_request("/index.html")
+        };
+
+        let result = tree
+            .certify_response(
+                &entry,
+                "/index.html",
+                &DefaultCelExpression::Full(full_cel_expr()),
+                Some(&request),
+                full_response(),
+                None,
+            )
+            .unwrap()
+            .dry_run_verify(&changed_request, None);
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::DryRunVerificationFailed(_))
+        ));
+    }
+
+    #[rstest]
+    fn dry_run_verify_fails_for_missing_expression_header(certification: HttpCertification) {
+        let path = HttpCertificationPath::exact("/index.html");
+        let entry = HttpCertificationTreeEntry::new(&path, certification);
+        let mut tree = HttpCertificationTree::default();
+        tree.insert(&entry);
+
+        let result = tree
+            .certify_response(&entry, "/index.html", &cel_expr(), None, response(), None)
+            .unwrap()
+            .dry_run_verify(&get_request("/index.html"), None);
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::DryRunVerificationFailed(reason)) if reason.contains("IC-CertificateExpression")
+        ));
+    }
+
+    #[rstest]
+    fn dry_run_verify_fails_for_less_precise_path() {
+        let mut tree = HttpCertificationTree::default();
+        let request = get_request("/assets/app.js");
+        let entry =
+            insert_full_certification(&mut tree, HttpCertificationPath::wildcard("/"), &request);
+        tree.insert(&HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/assets/app.js"),
+            HttpCertification::skip(),
+        ));
+        let response = full_response();
+
+        let result = tree
+            .certify_response(
+                &entry,
+                "/assets/app.js",
+                &DefaultCelExpression::Full(full_cel_expr()),
+                Some(&request),
+                &response,
+                None,
+            )
+            .unwrap()
+            .dry_run_verify(&request, None);
+
+        assert!(matches!(
+            result,
+            Err(HttpCertificationError::DryRunVerificationFailed(reason)) if reason.contains("more precise path")
+        ));
+    }
+
+    #[rstest]
+    fn dry_run_verify_absence(response: HttpResponse) {
+        let tree = HttpCertificationTree::default();
+
+        let certified_response = tree.certify_absence("/not-found", &response).unwrap();
+
+        assert!(certified_response
+            .dry_run_verify(&get_request("/not-found"), None)
+            .is_ok());
+    }
+}
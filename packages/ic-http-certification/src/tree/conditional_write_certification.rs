@@ -0,0 +1,248 @@
+use crate::{
+    DefaultCelBuilder, DefaultFullCelExpression, DefaultResponseCertification, HttpCertification,
+    HttpCertificationPath, HttpCertificationResult, HttpCertificationTree,
+    HttpCertificationTreeEntry, HttpRequest, HttpResponse, CERTIFICATE_EXPRESSION_HEADER_NAME,
+};
+
+/// The name of the request header that makes a write conditional on the current entity tag of a
+/// resource.
+pub const IF_MATCH_HEADER_NAME: &str = "If-Match";
+
+/// The name of the response header that carries the current entity tag of a resource.
+pub const ETAG_HEADER_NAME: &str = "ETag";
+
+/// Certifies the responses of endpoints that accept conditional writes, such as `PUT` or `PATCH`
+/// requests with an `If-Match` header, for optimistic concurrency control.
+///
+/// Responses are certified with [full certification](HttpCertification::full), with the
+/// [IF_MATCH_HEADER_NAME] request header certified, so a client can verify that a response,
+/// including a `412 Precondition Failed` response, was produced for the entity tag that it sent.
+/// The request hash always includes the request body, so each certification is only valid for
+/// the exact request that it was created for. Certifications are therefore created while handling
+/// the request in an update call, and inserted into the tree with an
+/// [exact](HttpCertificationPath::exact) path for the request path.
+#[derive(Debug, Clone)]
+pub struct ConditionalWriteCertification {
+    response_certification: DefaultResponseCertification<'static>,
+}
+
+impl Default for ConditionalWriteCertification {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConditionalWriteCertification {
+    /// Creates a new [ConditionalWriteCertification] that certifies all response headers.
+    pub fn new() -> Self {
+        Self {
+            response_certification: DefaultResponseCertification::response_header_exclusions(
+                vec![],
+            ),
+        }
+    }
+
+    /// Sets the [DefaultResponseCertification] that is used for every response.
+    pub fn with_response_certification(
+        mut self,
+        response_certification: DefaultResponseCertification<'static>,
+    ) -> Self {
+        self.response_certification = response_certification;
+
+        self
+    }
+
+    /// Returns the CEL expression that is used to certify every response.
+    pub fn cel_expr(&self) -> DefaultFullCelExpression<'_> {
+        DefaultCelBuilder::full_certification()
+            .with_request_headers(vec![IF_MATCH_HEADER_NAME])
+            .with_response_certification(self.response_certification())
+            .build()
+    }
+
+    fn response_certification(&self) -> DefaultResponseCertification<'_> {
+        match &self.response_certification {
+            DefaultResponseCertification::CertifiedResponseHeaders(headers) => {
+                DefaultResponseCertification::certified_response_headers(headers.to_vec())
+            }
+            DefaultResponseCertification::ResponseHeaderExclusions(headers) => {
+                DefaultResponseCertification::response_header_exclusions(headers.to_vec())
+            }
+            DefaultResponseCertification::CertifiedResponseHeadersWithExclusions {
+                headers,
+                exclusions,
+            } => DefaultResponseCertification::certified_response_headers_with_exclusions(
+                headers.to_vec(),
+                exclusions.to_vec(),
+            ),
+        }
+    }
+
+    /// Returns whether the `If-Match` precondition of the request holds for a resource whose
+    /// current entity tag is `current_etag`, such as `"v1"`, including the quotes.
+    ///
+    /// Requests without an `If-Match` header are unconditional and always match. Otherwise, the
+    /// header matches if it is `*`, or if it lists `current_etag`. Entity tags are compared with
+    /// the strong comparison required for `If-Match`, see
+    /// [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-if-match), so weak entity tags
+    /// never match.
+    pub fn precondition_matches(&self, request: &HttpRequest, current_etag: &str) -> bool {
+        let Some(if_match) = request.get_header_value(IF_MATCH_HEADER_NAME) else {
+            return true;
+        };
+
+        if if_match.trim() == "*" {
+            return true;
+        }
+
+        !current_etag.starts_with("W/")
+            && if_match
+                .split(',')
+                .map(str::trim)
+                .any(|etag| etag == current_etag)
+    }
+
+    /// Creates a `412 Precondition Failed` response for a resource whose current entity tag is
+    /// `current_etag`, so the client can retry with the current entity tag.
+    pub fn precondition_failed_response(&self, current_etag: &str) -> HttpResponse {
+        HttpResponse {
+            status_code: 412,
+            headers: vec![(ETAG_HEADER_NAME.to_string(), current_etag.to_string())],
+            body: vec![],
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    /// Certifies the response to a conditional write request and inserts its certification into
+    /// the tree, with an [exact](HttpCertificationPath::exact) path for the request path.
+    ///
+    /// The `IC-CertificateExpression` header is added to the response if it is not present yet.
+    /// Returns the tree entry, which can be used to [serve](HttpCertificationTree::certify_response)
+    /// the response and to [delete](HttpCertificationTree::delete) the certification once it is no
+    /// longer needed, and the response that must be served.
+    pub fn certify_response(
+        &self,
+        tree: &mut HttpCertificationTree,
+        request: &HttpRequest,
+        mut response: HttpResponse,
+    ) -> HttpCertificationResult<(HttpCertificationTreeEntry<'static>, HttpResponse)> {
+        let cel_expr = self.cel_expr();
+        if response
+            .get_header_value(CERTIFICATE_EXPRESSION_HEADER_NAME)
+            .is_none()
+        {
+            response.headers.push((
+                CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+                cel_expr.to_string(),
+            ));
+        }
+
+        let certification = HttpCertification::full(&cel_expr, request, &response, None)?;
+        let entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact(request.canonical_path()?),
+            certification,
+        );
+        tree.insert(&entry);
+
+        Ok((entry, response))
+    }
+
+    /// Certifies a [precondition failed](ConditionalWriteCertification::precondition_failed_response)
+    /// response to a conditional write request, in the same way as
+    /// [certify_response](ConditionalWriteCertification::certify_response).
+    pub fn certify_precondition_failed(
+        &self,
+        tree: &mut HttpCertificationTree,
+        request: &HttpRequest,
+        current_etag: &str,
+    ) -> HttpCertificationResult<(HttpCertificationTreeEntry<'static>, HttpResponse)> {
+        self.certify_response(
+            tree,
+            request,
+            self.precondition_failed_response(current_etag),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{request_hash, DefaultCelExpression};
+    use rstest::*;
+
+    fn put_request(if_match: Option<&str>) -> HttpRequest {
+        HttpRequest {
+            method: "PUT".to_string(),
+            url: "/api/items/1".to_string(),
+            headers: if_match
+                .map(|if_match| vec![(IF_MATCH_HEADER_NAME.to_string(), if_match.to_string())])
+                .unwrap_or_default(),
+            body: br#"{"name":"item"}"#.to_vec(),
+        }
+    }
+
+    #[rstest]
+    #[case(None, r#""v1""#, true)]
+    #[case(Some("*"), r#""v1""#, true)]
+    #[case(Some(r#""v1""#), r#""v1""#, true)]
+    #[case(Some(r#""v0", "v1""#), r#""v1""#, true)]
+    #[case(Some(r#""v0""#), r#""v1""#, false)]
+    #[case(Some(r#"W/"v1""#), r#""v1""#, false)]
+    #[case(Some(r#"W/"v1""#), r#"W/"v1""#, false)]
+    fn precondition_matches(
+        #[case] if_match: Option<&str>,
+        #[case] current_etag: &str,
+        #[case] expected: bool,
+    ) {
+        let certification = ConditionalWriteCertification::new();
+
+        assert_eq!(
+            certification.precondition_matches(&put_request(if_match), current_etag),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn certify_precondition_failed() {
+        let certification = ConditionalWriteCertification::new();
+        let mut tree = HttpCertificationTree::default();
+        let request = put_request(Some(r#""v0""#));
+
+        let (entry, response) = certification
+            .certify_precondition_failed(&mut tree, &request, r#""v1""#)
+            .unwrap();
+
+        assert_eq!(response.status_code, 412);
+        assert_eq!(response.get_header_value(ETAG_HEADER_NAME), Some(r#""v1""#));
+        assert_eq!(
+            response.get_header_value(CERTIFICATE_EXPRESSION_HEADER_NAME),
+            Some(certification.cel_expr().to_string().as_str())
+        );
+        assert_eq!(
+            *entry.certification,
+            HttpCertification::full(&certification.cel_expr(), &request, &response, None).unwrap()
+        );
+        assert!(tree
+            .certify_response(
+                &entry,
+                &request.url,
+                &DefaultCelExpression::Full(certification.cel_expr()),
+                Some(&request),
+                &response,
+                None
+            )
+            .is_ok());
+    }
+
+    #[rstest]
+    fn if_match_header_is_certified() {
+        let certification = ConditionalWriteCertification::new();
+        let cel_expr = certification.cel_expr();
+
+        assert_ne!(
+            request_hash(put_request(Some(r#""v0""#)), &cel_expr.request).unwrap(),
+            request_hash(put_request(Some(r#""v1""#)), &cel_expr.request).unwrap()
+        );
+    }
+}
@@ -0,0 +1,408 @@
+#[cfg(feature = "serde")]
+use super::certified_response::cbor_encode;
+use super::{
+    certification_decision::CertificationMatch, certification_tree::HttpCertificationTree,
+    certified_response::CertifiedResponse,
+};
+use crate::{
+    DefaultCelExpression, HttpCertificationError, HttpCertificationResult,
+    HttpCertificationTreeEntry, HttpRequest, HttpResponse,
+};
+#[cfg(feature = "serde")]
+use crate::{HeaderField, CERTIFICATE_HEADER_NAME};
+#[cfg(feature = "serde")]
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use ic_certification::{
+    fork, fork_hash, labeled, labeled_hash, pruned, AsHashTree, Hash, HashTree, RbTree,
+};
+use ic_representation_independent_hash::{hash, Sha256Digest};
+use std::borrow::Cow;
+
+/// The label of the subtree that holds legacy v1 asset hashes.
+const V1_ASSETS_LABEL: &[u8] = b"http_assets";
+
+type V1AssetTree = RbTree<String, Sha256Digest>;
+
+/// A certification tree that maintains both legacy v1 asset hashes and an
+/// [HttpCertificationTree] for the same content, so that canisters can migrate from response
+/// verification v1 to v2 without a flag-day.
+///
+/// While v1 certification is enabled, the [root hash](DualCertificationTree::root_hash) covers
+/// the `http_assets` subtree used by v1 and the `http_expr` subtree used by v2, and clients of
+/// either version can verify responses served by the canister. Once all clients support v2,
+/// v1 certification can be [dropped](DualCertificationTree::drop_v1), after which the root hash
+/// is identical to that of the inner [HttpCertificationTree].
+#[derive(Debug)]
+pub struct DualCertificationTree {
+    v1_assets: Option<V1AssetTree>,
+    v2_tree: HttpCertificationTree,
+}
+
+impl Default for DualCertificationTree {
+    fn default() -> Self {
+        Self::new(HttpCertificationTree::default())
+    }
+}
+
+impl DualCertificationTree {
+    /// Creates a new [DualCertificationTree] with v1 certification enabled, wrapping the given
+    /// [HttpCertificationTree].
+    /// The [default](DualCertificationTree::default) implementation should be used in most cases.
+    pub fn new(v2_tree: HttpCertificationTree) -> Self {
+        Self {
+            v1_assets: Some(V1AssetTree::new()),
+            v2_tree,
+        }
+    }
+
+    /// Returns true if legacy v1 certification has not been
+    /// [dropped](DualCertificationTree::drop_v1).
+    pub fn is_v1_enabled(&self) -> bool {
+        self.v1_assets.is_some()
+    }
+
+    /// Drops legacy v1 certification, removing all v1 asset hashes from the tree.
+    ///
+    /// After calling this, the [root hash](DualCertificationTree::root_hash) is identical to
+    /// that of the inner [HttpCertificationTree], and v1 clients will no longer be able to
+    /// verify responses. This cannot be undone.
+    pub fn drop_v1(&mut self) {
+        self.v1_assets = None;
+    }
+
+    /// Returns the inner [HttpCertificationTree].
+    ///
+    /// Once v1 certification has been [dropped](DualCertificationTree::drop_v1), this can be
+    /// used to complete the migration and continue with only an [HttpCertificationTree].
+    pub fn into_v2_tree(self) -> HttpCertificationTree {
+        self.v2_tree
+    }
+
+    /// Returns the root hash of the tree.
+    /// This hash can be used as the canister's certified variable.
+    pub fn root_hash(&self) -> Sha256Digest {
+        match &self.v1_assets {
+            Some(v1_assets) => fork_hash(
+                &labeled_hash(V1_ASSETS_LABEL, &v1_assets.root_hash()),
+                &self.v2_tree.root_hash(),
+            ),
+            None => self.v2_tree.root_hash(),
+        }
+    }
+
+    /// Inserts a [HttpCertificationTreeEntry] for the identity variant of an asset into the v2
+    /// tree and, while v1 certification is enabled, the SHA-256 hash of `body` under
+    /// `asset_path` into the v1 asset hashes.
+    ///
+    /// `body` must be the identity, unencoded body that will be served for `asset_path`. The v1
+    /// asset hashes hold a single hash per asset path, so encoded variants of the same asset
+    /// must be inserted with [insert_encoded](DualCertificationTree::insert_encoded) instead.
+    /// v1 clients decode `gzip` and `deflate` encoded bodies before checking their hash, so they
+    /// can verify the encoded variants against the hash of the identity body.
+    pub fn insert(&mut self, entry: &HttpCertificationTreeEntry, asset_path: &str, body: &[u8]) {
+        if let Some(v1_assets) = &mut self.v1_assets {
+            v1_assets.insert(asset_path.to_string(), hash(body));
+        }
+
+        self.v2_tree.insert(entry);
+    }
+
+    /// Inserts a [HttpCertificationTreeEntry] for an encoded variant of an asset, such as a
+    /// `gzip` compressed body, into the v2 tree only, leaving the v1 asset hash of the identity
+    /// variant in place.
+    pub fn insert_encoded(&mut self, entry: &HttpCertificationTreeEntry) {
+        self.v2_tree.insert(entry);
+    }
+
+    /// Deletes a [HttpCertificationTreeEntry] for the identity variant of an asset from the v2
+    /// tree and, while v1 certification is enabled, the hash for `asset_path` from the v1 asset
+    /// hashes.
+    ///
+    /// Encoded variants of the asset must be deleted separately with
+    /// [delete_encoded](DualCertificationTree::delete_encoded).
+    pub fn delete(&mut self, entry: &HttpCertificationTreeEntry, asset_path: &str) {
+        if let Some(v1_assets) = &mut self.v1_assets {
+            v1_assets.delete(asset_path.as_bytes());
+        }
+
+        self.v2_tree.delete(entry);
+    }
+
+    /// Deletes a [HttpCertificationTreeEntry] for an encoded variant of an asset from the v2 tree
+    /// only, leaving the v1 asset hash of the identity variant in place.
+    pub fn delete_encoded(&mut self, entry: &HttpCertificationTreeEntry) {
+        self.v2_tree.delete(entry);
+    }
+
+    /// Returns a pruned [HashTree] that will prove the presence of a given
+    /// [HttpCertificationTreeEntry] in the full tree, for v2 clients.
+    ///
+    /// `request_url` is required so that the witness can be generated with respect to the request URL.
+    pub fn witness(&self, entry: &HttpCertificationTreeEntry, request_url: &str) -> HashTree {
+        self.with_pruned_v1_assets(self.v2_tree.witness(entry, request_url))
+    }
+
+    fn with_pruned_v1_assets(&self, witness: HashTree) -> HashTree {
+        match &self.v1_assets {
+            Some(v1_assets) => fork(
+                pruned(labeled_hash(V1_ASSETS_LABEL, &v1_assets.root_hash())),
+                witness,
+            ),
+            None => witness,
+        }
+    }
+
+    /// Returns a pruned [HashTree] that will prove the presence of the hash for `asset_path` in
+    /// the full tree, for v1 clients.
+    ///
+    /// Returns an error if v1 certification has been [dropped](DualCertificationTree::drop_v1).
+    pub fn v1_witness(&self, asset_path: &str) -> HttpCertificationResult<HashTree> {
+        let v1_assets = self
+            .v1_assets
+            .as_ref()
+            .ok_or(HttpCertificationError::V1CertificationDropped)?;
+
+        Ok(fork(
+            labeled(V1_ASSETS_LABEL, v1_assets.witness(asset_path.as_bytes())),
+            pruned(self.v2_tree.root_hash()),
+        ))
+    }
+
+    /// Creates the legacy v1 `IC-Certificate` header for `asset_path`, to be added to the
+    /// response served to v1 clients.
+    ///
+    /// `data_certificate` is the canister's current data certificate, as returned by the
+    /// `data_certificate` system API. Note that this is only available in query calls.
+    ///
+    /// Returns an error if v1 certification has been [dropped](DualCertificationTree::drop_v1).
+    ///
+    /// Requires the `serde` feature, which is needed to CBOR encode the witness.
+    #[cfg(feature = "serde")]
+    pub fn v1_certificate_header(
+        &self,
+        asset_path: &str,
+        data_certificate: &[u8],
+    ) -> HttpCertificationResult<HeaderField> {
+        let witness = cbor_encode(&self.v1_witness(asset_path)?)?;
+
+        Ok((
+            CERTIFICATE_HEADER_NAME.to_string(),
+            format!(
+                "certificate=:{}:, tree=:{}:",
+                BASE64.encode(data_certificate),
+                BASE64.encode(witness)
+            ),
+        ))
+    }
+
+    /// Wraps an [HttpResponse] in a [CertifiedResponse] that can be served to v2 clients.
+    ///
+    /// This checks that the given [HttpCertificationTreeEntry] is present in the tree, and that
+    /// its certification matches the response, and generates the
+    /// [witness](DualCertificationTree::witness) and expression path that will be included in the
+    /// `IC-Certificate` header when the response is [served](CertifiedResponse::serve).
+    ///
+    /// `request_url` is required so that the witness can be generated with respect to the request URL.
+    ///
+    /// The certification is checked in the same way as by
+    /// [HttpCertificationTree::certify_response].
+    pub fn certify_response<'a>(
+        &self,
+        entry: &HttpCertificationTreeEntry,
+        request_url: &str,
+        cel_expr: &DefaultCelExpression,
+        request: Option<&HttpRequest>,
+        response: impl Into<Cow<'a, HttpResponse>>,
+        response_body_hash: Option<Hash>,
+    ) -> HttpCertificationResult<CertifiedResponse<'a>> {
+        let witness = self.v2_tree.checked_witness(entry, request_url)?;
+        let response = response.into();
+        entry.validate_response(cel_expr, request, &response, response_body_hash)?;
+
+        Ok(CertifiedResponse::new(
+            response,
+            self.with_pruned_v1_assets(witness),
+            entry.path.to_expr_path(),
+            request_url,
+            CertificationMatch::from(entry.path.as_ref()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DefaultCelBuilder, HttpCertification, HttpCertificationPath};
+    use ic_certification::LookupResult;
+    use rstest::*;
+
+    const BODY: &[u8] = b"Hello World!";
+
+    #[fixture]
+    fn entry() -> HttpCertificationTreeEntry<'static> {
+        HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/"),
+            HttpCertification::skip(),
+        )
+    }
+
+    #[rstest]
+    fn root_hash_matches_witnesses(entry: HttpCertificationTreeEntry<'static>) {
+        let mut tree = DualCertificationTree::default();
+        tree.insert(&entry, "/", BODY);
+
+        let v2_witness = tree.witness(&entry, "/");
+        let v1_witness = tree.v1_witness("/").unwrap();
+
+        assert_eq!(v2_witness.digest(), tree.root_hash());
+        assert_eq!(v1_witness.digest(), tree.root_hash());
+        assert_ne!(tree.root_hash(), tree.v2_tree.root_hash());
+    }
+
+    #[rstest]
+    fn v1_witness_contains_body_hash(entry: HttpCertificationTreeEntry<'static>) {
+        let mut tree = DualCertificationTree::default();
+        tree.insert(&entry, "/index.html", BODY);
+
+        let witness = tree.v1_witness("/index.html").unwrap();
+
+        assert_eq!(
+            witness.lookup_path([V1_ASSETS_LABEL, b"/index.html"]),
+            LookupResult::Found(&hash(BODY))
+        );
+    }
+
+    #[rstest]
+    fn delete_removes_both_versions(entry: HttpCertificationTreeEntry<'static>) {
+        let mut tree = DualCertificationTree::default();
+        tree.insert(&entry, "/", BODY);
+        tree.delete(&entry, "/");
+
+        assert_eq!(
+            tree.v1_witness("/")
+                .unwrap()
+                .lookup_path([V1_ASSETS_LABEL, b"/"]),
+            LookupResult::Absent
+        );
+        assert!(matches!(
+            tree.certify_response(
+                &entry,
+                "/",
+                &DefaultCelExpression::Skip,
+                None,
+                HttpResponse::builder()
+                    .ok()
+                    .with_body(BODY)
+                    .build()
+                    .unwrap(),
+                None
+            ),
+            Err(HttpCertificationError::CertificationNotFound(_))
+        ));
+    }
+
+    #[rstest]
+    fn certify_response_checks_certification() {
+        let cel_expr = DefaultCelBuilder::response_only_certification().build();
+        let response = HttpResponse::builder()
+            .ok()
+            .with_body(BODY)
+            .build()
+            .unwrap();
+        let entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/"),
+            HttpCertification::response_only(&cel_expr, &response, None).unwrap(),
+        );
+        let cel_expr = DefaultCelExpression::ResponseOnly(cel_expr);
+        let mut tree = DualCertificationTree::default();
+        tree.insert(&entry, "/", BODY);
+
+        assert!(tree
+            .certify_response(&entry, "/", &cel_expr, None, &response, None)
+            .is_ok());
+        assert!(matches!(
+            tree.certify_response(
+                &entry,
+                "/",
+                &cel_expr,
+                None,
+                HttpResponse::builder()
+                    .ok()
+                    .with_body(b"Goodbye World!")
+                    .build()
+                    .unwrap(),
+                None
+            ),
+            Err(HttpCertificationError::CertificationMismatch(path)) if path == "/"
+        ));
+    }
+
+    #[rstest]
+    fn encoded_variants_keep_identity_v1_hash(entry: HttpCertificationTreeEntry<'static>) {
+        let encoded_entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact("/"),
+            HttpCertification::response_only(
+                &DefaultCelBuilder::response_only_certification().build(),
+                HttpResponse::builder()
+                    .ok()
+                    .with_header("Content-Encoding", "gzip")
+                    .with_body(b"encoded")
+                    .build()
+                    .unwrap(),
+                None,
+            )
+            .unwrap(),
+        );
+        let mut tree = DualCertificationTree::default();
+        tree.insert(&entry, "/", BODY);
+        tree.insert_encoded(&encoded_entry);
+
+        let v1_hash = |tree: &DualCertificationTree| match tree
+            .v1_witness("/")
+            .unwrap()
+            .lookup_path([V1_ASSETS_LABEL, b"/"])
+        {
+            LookupResult::Found(hash) => Some(hash.to_vec()),
+            _ => None,
+        };
+        assert_eq!(v1_hash(&tree), Some(hash(BODY).to_vec()));
+        assert_eq!(tree.witness(&encoded_entry, "/").digest(), tree.root_hash());
+
+        tree.delete_encoded(&encoded_entry);
+
+        assert_eq!(v1_hash(&tree), Some(hash(BODY).to_vec()));
+        assert_eq!(tree.witness(&entry, "/").digest(), tree.root_hash());
+        assert!(!tree.v2_tree.contains(&encoded_entry));
+    }
+
+    #[rstest]
+    fn drop_v1_leaves_only_v2(entry: HttpCertificationTreeEntry<'static>) {
+        let mut tree = DualCertificationTree::default();
+        tree.insert(&entry, "/", BODY);
+        tree.drop_v1();
+
+        assert!(!tree.is_v1_enabled());
+        assert_eq!(tree.root_hash(), tree.v2_tree.root_hash());
+        assert_eq!(tree.witness(&entry, "/").digest(), tree.root_hash());
+        assert!(matches!(
+            tree.v1_witness("/"),
+            Err(HttpCertificationError::V1CertificationDropped)
+        ));
+
+        let root_hash = tree.root_hash();
+        assert_eq!(tree.into_v2_tree().root_hash(), root_hash);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn v1_certificate_header_has_no_version(entry: HttpCertificationTreeEntry<'static>) {
+        let mut tree = DualCertificationTree::default();
+        tree.insert(&entry, "/", BODY);
+
+        let (name, value) = tree.v1_certificate_header("/", &[1, 2, 3]).unwrap();
+
+        assert_eq!(name, CERTIFICATE_HEADER_NAME);
+        assert!(value.starts_with("certificate=:AQID:, tree=:"));
+        assert!(!value.contains("version"));
+    }
+}
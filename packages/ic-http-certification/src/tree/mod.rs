@@ -5,11 +5,31 @@
 //! Certifications are prepared using the [HttpCertification] enum.
 
 mod certification;
+mod certification_conflict_policy;
+mod certification_decision;
+mod certification_manifest;
+mod certification_template;
 mod certification_tree;
 mod certification_tree_entry;
+mod certification_tree_limits;
 mod certification_tree_path;
+mod certified_data_guard;
+mod certified_response;
+mod conditional_write_certification;
+mod dual_certification_tree;
+mod paginated_certification;
 
 pub use certification::*;
+pub use certification_conflict_policy::*;
+pub use certification_decision::*;
+pub use certification_manifest::*;
+pub use certification_template::*;
 pub use certification_tree::*;
 pub use certification_tree_entry::*;
+pub use certification_tree_limits::*;
 pub use certification_tree_path::*;
+pub use certified_data_guard::*;
+pub use certified_response::*;
+pub use conditional_write_certification::*;
+pub use dual_certification_tree::*;
+pub use paginated_certification::*;
@@ -0,0 +1,454 @@
+use crate::{
+    CertifiedResponse, DefaultCelBuilder, DefaultCelExpression, DefaultFullCelExpression,
+    DefaultResponseCertification, HttpCertification, HttpCertificationError, HttpCertificationPath,
+    HttpCertificationResult, HttpCertificationTree, HttpCertificationTreeEntry, HttpRequest,
+    HttpResponse, CERTIFICATE_EXPRESSION_HEADER_NAME,
+};
+use std::{borrow::Cow, collections::BTreeMap, ops::Range};
+
+/// The default name of the query parameter that selects a page.
+pub const DEFAULT_PAGE_QUERY_PARAMETER: &str = "page";
+
+/// Certifies the pages of a paginated list endpoint, such as `/api/items?page=2`.
+///
+/// Every page is certified with [full certification](HttpCertification::full) for a `GET`
+/// request to the endpoint's path, with the page query parameter certified, so that all pages
+/// share the same [exact](HttpCertificationPath::exact) path in the tree and other query
+/// parameters are ignored. Pages are numbered from `0`.
+///
+/// When the underlying list changes, [affected_pages](PaginatedCertification::affected_pages)
+/// determines which pages need to be certified again, so that
+/// [certify_pages](PaginatedCertification::certify_pages) only re-certifies those pages.
+#[derive(Debug, Clone)]
+pub struct PaginatedCertification {
+    path: String,
+    page_size: usize,
+    page_query_parameter: String,
+    response_certification: DefaultResponseCertification<'static>,
+    certified_pages: BTreeMap<usize, HttpCertificationTreeEntry<'static>>,
+}
+
+impl PaginatedCertification {
+    /// Creates a new [PaginatedCertification] for the list endpoint at `path`, with `page_size`
+    /// items per page.
+    ///
+    /// By default, pages are selected with the [DEFAULT_PAGE_QUERY_PARAMETER] and all response
+    /// headers are certified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `page_size` is `0`.
+    pub fn new(path: impl Into<String>, page_size: usize) -> Self {
+        assert!(page_size > 0, "page_size must be greater than 0");
+
+        Self {
+            path: path.into(),
+            page_size,
+            page_query_parameter: DEFAULT_PAGE_QUERY_PARAMETER.to_string(),
+            response_certification: DefaultResponseCertification::response_header_exclusions(
+                vec![],
+            ),
+            certified_pages: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the name of the query parameter that selects a page.
+    pub fn with_page_query_parameter(mut self, page_query_parameter: impl Into<String>) -> Self {
+        self.page_query_parameter = page_query_parameter.into();
+
+        self
+    }
+
+    /// Sets the [DefaultResponseCertification] that is used for every page.
+    pub fn with_response_certification(
+        mut self,
+        response_certification: DefaultResponseCertification<'static>,
+    ) -> Self {
+        self.response_certification = response_certification;
+
+        self
+    }
+
+    /// Returns the CEL expression that is used to certify every page.
+    pub fn cel_expr(&self) -> DefaultFullCelExpression<'_> {
+        DefaultCelBuilder::full_certification()
+            .with_request_query_parameters(vec![self.page_query_parameter.as_str()])
+            .with_response_certification(self.response_certification())
+            .build()
+    }
+
+    fn response_certification(&self) -> DefaultResponseCertification<'_> {
+        match &self.response_certification {
+            DefaultResponseCertification::CertifiedResponseHeaders(headers) => {
+                DefaultResponseCertification::certified_response_headers(headers.to_vec())
+            }
+            DefaultResponseCertification::ResponseHeaderExclusions(headers) => {
+                DefaultResponseCertification::response_header_exclusions(headers.to_vec())
+            }
+            DefaultResponseCertification::CertifiedResponseHeadersWithExclusions {
+                headers,
+                exclusions,
+            } => DefaultResponseCertification::certified_response_headers_with_exclusions(
+                headers.to_vec(),
+                exclusions.to_vec(),
+            ),
+        }
+    }
+
+    /// Returns the number of pages of a list with `item_count` items.
+    /// An empty list has a single, empty page.
+    pub fn page_count(&self, item_count: usize) -> usize {
+        item_count.div_ceil(self.page_size).max(1)
+    }
+
+    /// Returns the indices of the items on the given page.
+    pub fn page_items(&self, page: usize) -> Range<usize> {
+        let start = page.saturating_mul(self.page_size);
+
+        start..start.saturating_add(self.page_size)
+    }
+
+    /// Returns the page that the item with the given index is on.
+    pub fn page_of_item(&self, item_index: usize) -> usize {
+        item_index / self.page_size
+    }
+
+    /// Returns the pages that must be certified again after the items in `changed_items` have
+    /// changed and the number of items in the list changed from `old_item_count` to
+    /// `new_item_count`.
+    ///
+    /// If the number of items is unchanged, only the pages containing the changed items are
+    /// affected. Otherwise, items after the changed items have moved to other pages, so all pages
+    /// from the first changed item onwards are affected, including pages that no longer exist.
+    pub fn affected_pages(
+        &self,
+        changed_items: Range<usize>,
+        old_item_count: usize,
+        new_item_count: usize,
+    ) -> Range<usize> {
+        let first_page = self.page_of_item(changed_items.start);
+
+        if old_item_count != new_item_count {
+            let page_count = self
+                .page_count(old_item_count)
+                .max(self.page_count(new_item_count));
+
+            return first_page.min(page_count)..page_count;
+        }
+
+        match changed_items.is_empty() {
+            true => first_page..first_page,
+            false => first_page..self.page_of_item(changed_items.end - 1) + 1,
+        }
+    }
+
+    /// Returns the request that a page is certified for.
+    pub fn page_request(&self, page: usize) -> HttpRequest {
+        HttpRequest {
+            method: "GET".to_string(),
+            url: format!("{}?{}={page}", self.path, self.page_query_parameter),
+            headers: vec![],
+            body: vec![],
+        }
+    }
+
+    /// Returns the page that is selected by the request, if the request has a valid page query
+    /// parameter.
+    pub fn page_of_request(&self, request: &HttpRequest) -> HttpCertificationResult<Option<usize>> {
+        let page = request.canonical_query()?.and_then(|query| {
+            query.split('&').find_map(|query_fragment| {
+                let (name, value) = query_fragment.split_once('=')?;
+
+                match name.eq_ignore_ascii_case(&self.page_query_parameter) {
+                    true => value.parse().ok(),
+                    false => None,
+                }
+            })
+        });
+
+        Ok(page)
+    }
+
+    /// Returns the tree entry of a page, if the page is certified.
+    pub fn page_entry(&self, page: usize) -> Option<&HttpCertificationTreeEntry<'static>> {
+        self.certified_pages.get(&page)
+    }
+
+    /// Certifies the response for a page and inserts its certification into the tree, replacing
+    /// the previous certification of the page, if any.
+    ///
+    /// The `IC-CertificateExpression` header is added to the response if it is not present yet.
+    /// The returned response is the response that must be served for the page.
+    pub fn certify_page(
+        &mut self,
+        tree: &mut HttpCertificationTree,
+        page: usize,
+        mut response: HttpResponse,
+    ) -> HttpCertificationResult<HttpResponse> {
+        let cel_expr = self.cel_expr();
+        let has_cel_expr_header = response
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case(CERTIFICATE_EXPRESSION_HEADER_NAME));
+        if !has_cel_expr_header {
+            response.headers.push((
+                CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+                cel_expr.to_string(),
+            ));
+        }
+
+        let certification =
+            HttpCertification::full(&cel_expr, self.page_request(page), &response, None)?;
+        let entry = HttpCertificationTreeEntry::new(
+            HttpCertificationPath::exact(self.path.clone()),
+            certification,
+        );
+
+        self.remove_page(tree, page);
+        tree.insert(&entry);
+        self.certified_pages.insert(page, entry);
+
+        Ok(response)
+    }
+
+    /// Removes the certification of a page from the tree, if the page is certified.
+    pub fn remove_page(&mut self, tree: &mut HttpCertificationTree, page: usize) {
+        if let Some(entry) = self.certified_pages.remove(&page) {
+            tree.delete(&entry);
+        }
+    }
+
+    /// Certifies the given pages of a list with `item_count` items, using `page_response` to
+    /// create the response for each page, and removes the certifications of all pages that no
+    /// longer exist.
+    ///
+    /// Returns the certified responses, together with their page, that must be served for the
+    /// certified pages.
+    pub fn certify_pages(
+        &mut self,
+        tree: &mut HttpCertificationTree,
+        item_count: usize,
+        pages: impl IntoIterator<Item = usize>,
+        mut page_response: impl FnMut(usize) -> HttpResponse,
+    ) -> HttpCertificationResult<Vec<(usize, HttpResponse)>> {
+        let page_count = self.page_count(item_count);
+
+        let removed_pages = self
+            .certified_pages
+            .range(page_count..)
+            .map(|(page, _)| *page)
+            .collect::<Vec<_>>();
+        for page in removed_pages {
+            self.remove_page(tree, page);
+        }
+
+        pages
+            .into_iter()
+            .filter(|page| *page < page_count)
+            .map(|page| {
+                self.certify_page(tree, page, page_response(page))
+                    .map(|response| (page, response))
+            })
+            .collect()
+    }
+
+    /// Wraps the response for the page selected by the request in a [CertifiedResponse] that can
+    /// be served to the HTTP Gateway.
+    ///
+    /// Returns an error if the request does not select a page, if the selected page is not
+    /// certified or if the response does not match the certification of the page.
+    pub fn certify_response<'b>(
+        &self,
+        tree: &HttpCertificationTree,
+        request: &HttpRequest,
+        response: impl Into<Cow<'b, HttpResponse>>,
+    ) -> HttpCertificationResult<CertifiedResponse<'b>> {
+        let (page, entry) = self
+            .page_of_request(request)?
+            .and_then(|page| self.page_entry(page).map(|entry| (page, entry)))
+            .ok_or_else(|| HttpCertificationError::CertificationNotFound(request.url.clone()))?;
+
+        tree.certify_response(
+            entry,
+            &self.path,
+            &DefaultCelExpression::Full(self.cel_expr()),
+            Some(&self.page_request(page)),
+            response,
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const PAGE_SIZE: usize = 10;
+
+    #[fixture]
+    fn pagination() -> PaginatedCertification {
+        PaginatedCertification::new("/api/items", PAGE_SIZE)
+    }
+
+    fn page_response(page: usize) -> HttpResponse {
+        HttpResponse {
+            status_code: 200,
+            headers: vec![("Content-Type".into(), "application/json".into())],
+            body: format!(r#"{{"page":{page}}}"#).into_bytes(),
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(1, 1)]
+    #[case(10, 1)]
+    #[case(11, 2)]
+    #[case(30, 3)]
+    fn page_count(pagination: PaginatedCertification, #[case] items: usize, #[case] pages: usize) {
+        assert_eq!(pagination.page_count(items), pages);
+    }
+
+    #[rstest]
+    fn page_items(pagination: PaginatedCertification) {
+        assert_eq!(pagination.page_items(0), 0..10);
+        assert_eq!(pagination.page_items(2), 20..30);
+        assert_eq!(pagination.page_of_item(9), 0);
+        assert_eq!(pagination.page_of_item(10), 1);
+    }
+
+    #[rstest]
+    #[case::update(12..13, 30, 30, 1..2)]
+    #[case::update_across_pages(8..22, 30, 30, 0..3)]
+    #[case::no_changes(12..12, 30, 30, 1..1)]
+    #[case::insert(12..13, 30, 31, 1..4)]
+    #[case::remove(12..13, 31, 30, 1..4)]
+    #[case::append(30..31, 30, 31, 3..4)]
+    fn affected_pages(
+        pagination: PaginatedCertification,
+        #[case] changed_items: Range<usize>,
+        #[case] old_item_count: usize,
+        #[case] new_item_count: usize,
+        #[case] expected: Range<usize>,
+    ) {
+        assert_eq!(
+            pagination.affected_pages(changed_items, old_item_count, new_item_count),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("/api/items?page=2", Some(2))]
+    #[case("/api/items?sort=asc&PAGE=3", Some(3))]
+    #[case("/api/items?page=two", None)]
+    #[case("/api/items", None)]
+    fn page_of_request(
+        pagination: PaginatedCertification,
+        #[case] url: &str,
+        #[case] expected: Option<usize>,
+    ) {
+        let request = HttpRequest {
+            url: url.to_string(),
+            ..pagination.page_request(0)
+        };
+
+        assert_eq!(pagination.page_of_request(&request).unwrap(), expected);
+    }
+
+    #[rstest]
+    fn certify_page(mut pagination: PaginatedCertification) {
+        let mut tree = HttpCertificationTree::default();
+
+        let response = pagination
+            .certify_page(&mut tree, 1, page_response(1))
+            .unwrap();
+        let expected_certification = HttpCertification::full(
+            &pagination.cel_expr(),
+            pagination.page_request(1),
+            &response,
+            None,
+        )
+        .unwrap();
+
+        assert!(response.headers.contains(&(
+            CERTIFICATE_EXPRESSION_HEADER_NAME.to_string(),
+            pagination.cel_expr().to_string()
+        )));
+        assert_eq!(
+            pagination.page_entry(1).unwrap().certification.as_ref(),
+            &expected_certification
+        );
+
+        let request = HttpRequest {
+            url: "/api/items?page=1&sort=asc".to_string(),
+            ..pagination.page_request(1)
+        };
+        assert!(pagination
+            .certify_response(&tree, &request, &response)
+            .is_ok());
+        assert!(pagination
+            .certify_response(&tree, &pagination.page_request(0), &response)
+            .is_err());
+    }
+
+    #[rstest]
+    fn certify_pages_replaces_changed_pages(mut pagination: PaginatedCertification) {
+        let mut tree = HttpCertificationTree::default();
+        pagination
+            .certify_pages(&mut tree, 30, 0..3, page_response)
+            .unwrap();
+        let root_hash = tree.root_hash();
+        let first_page = pagination.page_entry(0).cloned().unwrap();
+
+        let affected_pages = pagination.affected_pages(12..13, 30, 30);
+        let responses = pagination
+            .certify_pages(&mut tree, 30, affected_pages, |page| HttpResponse {
+                body: b"updated".to_vec(),
+                ..page_response(page)
+            })
+            .unwrap();
+
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].0, 1);
+        assert_ne!(tree.root_hash(), root_hash);
+        assert_eq!(pagination.page_entry(0), Some(&first_page));
+    }
+
+    #[rstest]
+    fn certify_pages_removes_pages_that_no_longer_exist(mut pagination: PaginatedCertification) {
+        let mut tree = HttpCertificationTree::default();
+        pagination
+            .certify_pages(&mut tree, 30, 0..3, page_response)
+            .unwrap();
+        let last_page = pagination.page_entry(2).cloned().unwrap();
+
+        let affected_pages = pagination.affected_pages(15..20, 30, 25);
+        let responses = pagination
+            .certify_pages(&mut tree, 25, affected_pages, page_response)
+            .unwrap();
+
+        assert_eq!(
+            responses.iter().map(|(page, _)| *page).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(pagination.page_entry(2).is_some());
+
+        pagination
+            .certify_pages(&mut tree, 20, 2..3, page_response)
+            .unwrap();
+
+        assert!(pagination.page_entry(2).is_none());
+        assert!(tree
+            .certify_response(
+                &last_page,
+                "/api/items",
+                &DefaultCelExpression::Full(pagination.cel_expr()),
+                Some(&pagination.page_request(2)),
+                page_response(2),
+                None
+            )
+            .is_err());
+    }
+}
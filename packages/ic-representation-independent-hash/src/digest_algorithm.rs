@@ -0,0 +1,72 @@
+use crate::Sha256Digest;
+use sha2::Digest;
+use std::fmt::Debug;
+
+/// A hash function that is used for certification.
+///
+/// The certification and verification code is generic over this trait, so that a future revision
+/// of the HTTP Gateway Protocol that specifies a different hash function only requires a new
+/// implementation of this trait. [Sha256Algorithm] is the hash function used by the current
+/// revision of the protocol.
+pub trait DigestAlgorithm {
+    /// The output of the hash function.
+    type Output: AsRef<[u8]> + Copy + Ord + Debug;
+
+    /// The state of an incremental hash computation.
+    type Hasher;
+
+    /// Creates a new [Hasher](DigestAlgorithm::Hasher).
+    fn new_hasher() -> Self::Hasher;
+
+    /// Updates the [Hasher](DigestAlgorithm::Hasher) with the given slice.
+    fn update(hasher: &mut Self::Hasher, data: &[u8]);
+
+    /// Consumes the [Hasher](DigestAlgorithm::Hasher) and returns the hash of all data that it
+    /// was updated with.
+    fn finalize(hasher: Self::Hasher) -> Self::Output;
+
+    /// Calculates the hash of the given slice.
+    fn digest(data: &[u8]) -> Self::Output {
+        let mut hasher = Self::new_hasher();
+        Self::update(&mut hasher, data);
+        Self::finalize(hasher)
+    }
+}
+
+/// The SHA-256 hash function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sha256Algorithm;
+
+impl DigestAlgorithm for Sha256Algorithm {
+    type Output = Sha256Digest;
+    type Hasher = sha2::Sha256;
+
+    fn new_hasher() -> Self::Hasher {
+        sha2::Sha256::new()
+    }
+
+    fn update(hasher: &mut Self::Hasher, data: &[u8]) {
+        hasher.update(data);
+    }
+
+    fn finalize(hasher: Self::Hasher) -> Self::Output {
+        hasher.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_incremental_digest_matches_digest() {
+        let mut hasher = Sha256Algorithm::new_hasher();
+        Sha256Algorithm::update(&mut hasher, b"Hello ");
+        Sha256Algorithm::update(&mut hasher, b"World!");
+
+        assert_eq!(
+            Sha256Algorithm::finalize(hasher),
+            Sha256Algorithm::digest(b"Hello World!")
+        );
+    }
+}
@@ -5,16 +5,15 @@
 /// Type alias for a SHA-256 hash.
 pub type Sha256Digest = [u8; 32];
 
+mod digest_algorithm;
+pub use digest_algorithm::*;
+
 mod representation_independent_hash;
 pub use representation_independent_hash::*;
 
-use sha2::{Digest, Sha256};
-
 /// Calculates the SHA-256 hash of the given slice.
 pub fn hash(data: &[u8]) -> Sha256Digest {
-    let mut hasher = Sha256::new();
-    hasher.update(data);
-    hasher.finalize().into()
+    Sha256Algorithm::digest(data)
 }
 
 #[cfg(test)]
@@ -1,5 +1,4 @@
-use crate::{hash, Sha256Digest};
-use sha2::{Digest, Sha256};
+use crate::{DigestAlgorithm, Sha256Algorithm, Sha256Digest};
 
 /// Represents a value to be hashed. Only UTF-8 strings, bytes and unsigned numbers are currently supported.
 #[derive(Debug, Clone)]
@@ -17,30 +16,37 @@ pub enum Value {
 ///
 /// [`Representation Independent Hash`]: https://internetcomputer.org/docs/current/references/ic-interface-spec/#hash-of-map
 pub fn representation_independent_hash(map: &[(String, Value)]) -> Sha256Digest {
+    representation_independent_hash_with::<Sha256Algorithm>(map)
+}
+
+/// Calculates the [representation_independent_hash] of the given map with the hash function `D`.
+pub fn representation_independent_hash_with<D: DigestAlgorithm>(
+    map: &[(String, Value)],
+) -> D::Output {
     let mut hashes: Vec<_> = map
         .iter()
-        .map(|(key, value)| (hash(key.as_bytes()), hash_value(value)))
+        .map(|(key, value)| (D::digest(key.as_bytes()), hash_value::<D>(value)))
         .collect();
 
     hashes.sort_unstable();
 
-    let mut hasher = Sha256::new();
+    let mut hasher = D::new_hasher();
     for (key_hash, value_hash) in hashes.iter() {
-        hasher.update(&key_hash[..]);
-        hasher.update(&value_hash[..]);
+        D::update(&mut hasher, key_hash.as_ref());
+        D::update(&mut hasher, value_hash.as_ref());
     }
 
-    hasher.finalize().into()
+    D::finalize(hasher)
 }
 
-fn hash_value(value: &Value) -> Sha256Digest {
+fn hash_value<D: DigestAlgorithm>(value: &Value) -> D::Output {
     match value {
-        Value::String(value) => hash(value.as_bytes()),
-        Value::Bytes(value) => hash(value),
+        Value::String(value) => D::digest(value.as_bytes()),
+        Value::Bytes(value) => D::digest(value),
         Value::Number(value) => {
-            let mut hasher = Sha256::new();
-            leb128::write::unsigned(&mut hasher, value.to_owned()).unwrap();
-            hasher.finalize().into()
+            let mut encoded_value = Vec::new();
+            leb128::write::unsigned(&mut encoded_value, value.to_owned()).unwrap();
+            D::digest(&encoded_value)
         }
     }
 }
@@ -0,0 +1,210 @@
+use crate::{create_v2_fixture, gzip_encode, V2Fixture};
+use ic_http_certification::{
+    DefaultCelBuilder, DefaultResponseCertification, HttpCertification, HttpCertificationPath,
+    HttpCertificationTreeEntry, HttpRequest, HttpResponse,
+};
+
+/// The current time, in nanoseconds, used by the [differential fixtures](differential_fixtures),
+/// so that the fixtures are identical on every platform.
+pub const DIFFERENTIAL_CURRENT_TIME_NS: u128 = 1_700_000_000_000_000_000;
+
+/// The maximum certificate time offset, in nanoseconds, used by the
+/// [differential fixtures](differential_fixtures).
+pub const DIFFERENTIAL_MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+
+/// A request and response pair that is verified by both the Rust and the JS bindings, whose
+/// results are expected to be identical.
+#[derive(Debug, Clone)]
+pub struct DifferentialFixture {
+    pub name: &'static str,
+    pub request: HttpRequest,
+    pub response: HttpResponse,
+    pub canister_id: Vec<u8>,
+    pub root_key: Vec<u8>,
+    pub current_time_ns: u128,
+    pub max_cert_time_offset_ns: u128,
+    pub min_requested_verification_version: u8,
+}
+
+/// Returns fixtures covering successful verification, verification failures and the different
+/// certification modes.
+pub fn differential_fixtures() -> Vec<DifferentialFixture> {
+    let mut fixtures = vec![
+        response_only_fixture("response_only", |_| {}),
+        response_only_fixture("uncertified_header", |response| {
+            response
+                .headers
+                .push(("Set-Cookie".into(), "session=1234".into()));
+        }),
+        response_only_fixture("modified_body", |response| {
+            response.body = b"Hello IC!".to_vec();
+        }),
+        response_only_fixture("modified_certified_header", |response| {
+            response.headers[1].1 = "no-store".into();
+        }),
+        response_only_fixture("missing_certificate", |response| {
+            response.headers.pop();
+        }),
+        full_fixture(),
+        skip_fixture(),
+        decoded_body_fixture(),
+    ];
+
+    let mut stale_fixture = response_only_fixture("stale_certificate", |_| {});
+    stale_fixture.current_time_ns += 2 * DIFFERENTIAL_MAX_CERT_TIME_OFFSET_NS;
+    fixtures.push(stale_fixture);
+
+    let mut version_mismatch_fixture = skip_fixture();
+    version_mismatch_fixture.name = "requested_verification_version_mismatch";
+    version_mismatch_fixture.min_requested_verification_version = 3;
+    fixtures.push(version_mismatch_fixture);
+
+    fixtures
+}
+
+fn response_only_fixture(
+    name: &'static str,
+    modify_response: impl FnOnce(&mut HttpResponse),
+) -> DifferentialFixture {
+    let cel_expr = DefaultCelBuilder::response_only_certification()
+        .with_response_certification(DefaultResponseCertification::certified_response_headers(
+            vec!["Cache-Control"],
+        ))
+        .build();
+    let request = get_request("/");
+    let response = HttpResponse {
+        status_code: 200,
+        headers: vec![
+            ("IC-CertificateExpression".into(), cel_expr.to_string()),
+            ("Cache-Control".into(), "max-age=604800".into()),
+        ],
+        body: b"Hello World!".to_vec(),
+        upgrade: None,
+        trailers: None,
+    };
+    let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+
+    let mut fixture = certified_fixture(name, request, response, certification);
+    modify_response(&mut fixture.response);
+
+    fixture
+}
+
+fn full_fixture() -> DifferentialFixture {
+    let cel_expr = DefaultCelBuilder::full_certification()
+        .with_request_headers(vec!["Accept"])
+        .with_request_query_parameters(vec!["lang"])
+        .with_response_certification(DefaultResponseCertification::response_header_exclusions(
+            vec!["Date"],
+        ))
+        .build();
+    let request = HttpRequest {
+        method: "GET".into(),
+        url: "/index.html?lang=en".into(),
+        headers: vec![("Accept".into(), "text/html".into())],
+        body: vec![],
+    };
+    let response = HttpResponse {
+        status_code: 200,
+        headers: vec![
+            ("IC-CertificateExpression".into(), cel_expr.to_string()),
+            ("Content-Type".into(), "text/html".into()),
+        ],
+        body: b"<h1>Hello World!</h1>".to_vec(),
+        upgrade: None,
+        trailers: None,
+    };
+    let certification = HttpCertification::full(&cel_expr, &request, &response, None).unwrap();
+
+    let mut fixture = certified_fixture("full", request, response, certification);
+    fixture
+        .response
+        .headers
+        .push(("Date".into(), "Tue, 14 Nov 2023 22:13:20 GMT".into()));
+
+    fixture
+}
+
+fn skip_fixture() -> DifferentialFixture {
+    let request = get_request("/");
+    let response = HttpResponse {
+        status_code: 200,
+        headers: vec![(
+            "IC-CertificateExpression".into(),
+            DefaultCelBuilder::skip_certification().to_string(),
+        )],
+        body: b"Hello World!".to_vec(),
+        upgrade: None,
+        trailers: None,
+    };
+
+    certified_fixture("skip", request, response, HttpCertification::skip())
+}
+
+fn decoded_body_fixture() -> DifferentialFixture {
+    let cel_expr = DefaultCelBuilder::response_only_certification()
+        .with_response_certification(DefaultResponseCertification::certified_response_headers(
+            vec![],
+        ))
+        .with_decoded_body_certification()
+        .build();
+    let request = get_request("/");
+    let response = HttpResponse {
+        status_code: 200,
+        headers: vec![("IC-CertificateExpression".into(), cel_expr.to_string())],
+        body: b"Hello World!".to_vec(),
+        upgrade: None,
+        trailers: None,
+    };
+    let certification = HttpCertification::response_only(&cel_expr, &response, None).unwrap();
+
+    let mut fixture = certified_fixture("decoded_body", request, response, certification);
+    fixture.response.body = gzip_encode(&fixture.response.body);
+    fixture
+        .response
+        .headers
+        .push(("Content-Encoding".into(), "gzip".into()));
+
+    fixture
+}
+
+fn get_request(url: &str) -> HttpRequest {
+    HttpRequest {
+        method: "GET".into(),
+        url: url.into(),
+        headers: vec![],
+        body: vec![],
+    }
+}
+
+fn certified_fixture(
+    name: &'static str,
+    request: HttpRequest,
+    mut response: HttpResponse,
+    certification: HttpCertification,
+) -> DifferentialFixture {
+    let req_path = request.canonical_path().unwrap();
+    let path = HttpCertificationPath::exact(req_path.clone());
+    let entry = HttpCertificationTreeEntry::new(&path, certification);
+
+    let V2Fixture {
+        root_key,
+        certificate_header,
+        canister_id,
+    } = create_v2_fixture(&req_path, &entry, &DIFFERENTIAL_CURRENT_TIME_NS);
+
+    response
+        .headers
+        .push(("IC-Certificate".into(), certificate_header));
+
+    DifferentialFixture {
+        name,
+        request,
+        response,
+        canister_id: canister_id.as_ref().to_vec(),
+        root_key,
+        current_time_ns: DIFFERENTIAL_CURRENT_TIME_NS,
+        max_cert_time_offset_ns: DIFFERENTIAL_MAX_CERT_TIME_OFFSET_NS,
+        min_requested_verification_version: 2,
+    }
+}
@@ -6,6 +6,9 @@ pub use asset_tree::*;
 mod certificate;
 pub use certificate::*;
 
+mod differential;
+pub use differential::*;
+
 mod encoding;
 pub use encoding::*;
 
@@ -0,0 +1,159 @@
+use crate::request::request_from_js;
+use crate::response::response_from_js;
+use crate::verification_options::{get, verification_options_from_js, JsVerificationOptions};
+use ic_http_certification::{HttpRequest, HttpResponse};
+use ic_response_verification::{
+    types::VerificationInfo, verify_request_response_pair, ResponseVerificationJsError,
+    ResponseVerificationResult,
+};
+use js_sys::Array;
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[cfg(feature = "wasm-threads")]
+use rayon::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const REQUEST_RESPONSE_PAIR: &'static str = r#"
+interface RequestResponsePair {
+    request: Request;
+    response: Response;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "RequestResponsePair[]")]
+    pub type JsRequestResponsePairs;
+
+    #[wasm_bindgen(typescript_type = "Array<VerificationInfo | ResponseVerificationError>")]
+    pub type JsBatchVerificationResults;
+}
+
+/// Verifies a batch of request and response pairs with the same `VerificationOptions`.
+///
+/// Returns an array that contains either the `VerificationInfo` or the
+/// `ResponseVerificationError` of each pair, in the same order as the pairs, so a single invalid
+/// response does not fail the whole batch. `isResponseVerificationError` can be used to tell them
+/// apart.
+///
+/// When the package is built with the `wasm-threads` feature, and the thread pool has been started
+/// with `initThreadPool`, the pairs are verified in parallel.
+#[wasm_bindgen(js_name = verifyRequestResponsePairs)]
+pub fn verify_request_response_pairs(
+    pairs: JsRequestResponsePairs,
+    options: JsVerificationOptions,
+) -> Result<JsBatchVerificationResults, JsValue> {
+    let options = verification_options_from_js(&options)?;
+    let pairs = Array::unchecked_from_js(JsValue::from(pairs))
+        .iter()
+        .map(|pair| {
+            (
+                request_from_js(get(&pair, "request")),
+                response_from_js(get(&pair, "response")),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let results = verify_pairs(pairs, |(request, response)| {
+        verify_request_response_pair(
+            request,
+            response,
+            &options.canister_id,
+            options.current_time_ns,
+            options.max_cert_time_offset_ns,
+            &options.root_key,
+            options.min_verification_version,
+        )
+    });
+
+    Ok(results
+        .into_iter()
+        .map(|result| match result {
+            Ok(verification_info) => JsValue::from(verification_info),
+            Err(e) => JsValue::from(ResponseVerificationJsError::from(e)),
+        })
+        .collect::<Array>()
+        .unchecked_into::<JsBatchVerificationResults>())
+}
+
+#[cfg(feature = "wasm-threads")]
+fn verify_pairs<F>(
+    pairs: Vec<(HttpRequest, HttpResponse)>,
+    verify: F,
+) -> Vec<ResponseVerificationResult<VerificationInfo>>
+where
+    F: Fn((HttpRequest, HttpResponse)) -> ResponseVerificationResult<VerificationInfo>
+        + Send
+        + Sync,
+{
+    pairs.into_par_iter().map(verify).collect()
+}
+
+#[cfg(not(feature = "wasm-threads"))]
+fn verify_pairs<F>(
+    pairs: Vec<(HttpRequest, HttpResponse)>,
+    verify: F,
+) -> Vec<ResponseVerificationResult<VerificationInfo>>
+where
+    F: Fn((HttpRequest, HttpResponse)) -> ResponseVerificationResult<VerificationInfo>
+        + Send
+        + Sync,
+{
+    pairs.into_iter().map(verify).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_response_verification::is_response_verification_error;
+    use js_sys::{Reflect, Uint8Array, JSON};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn options() -> JsVerificationOptions {
+        let options = JSON::parse("{}").unwrap();
+        Reflect::set(
+            &options,
+            &"canisterId".into(),
+            &Uint8Array::from(&[0, 0, 0, 0, 0, 0, 0, 1][..]),
+        )
+        .unwrap();
+
+        options.unchecked_into()
+    }
+
+    #[wasm_bindgen_test]
+    fn verify_request_response_pairs_with_uncertified_responses() {
+        let pairs = JSON::parse(
+            r#"[
+                {
+                    "request": { "method": "GET", "url": "/", "headers": [], "body": [] },
+                    "response": { "statusCode": 200, "headers": [], "body": [] }
+                },
+                {
+                    "request": { "method": "GET", "url": "/index.html", "headers": [], "body": [] },
+                    "response": { "statusCode": 404, "headers": [], "body": [] }
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let results = verify_request_response_pairs(pairs.unchecked_into(), options()).unwrap();
+        let results = Array::unchecked_from_js(JsValue::from(results));
+
+        assert_eq!(results.length(), 2);
+        assert!(results
+            .iter()
+            .all(|result| is_response_verification_error(&result)));
+    }
+
+    #[wasm_bindgen_test]
+    fn verify_request_response_pairs_without_pairs() {
+        let pairs = Array::new();
+
+        let results =
+            verify_request_response_pairs(JsValue::from(pairs).unchecked_into(), options())
+                .unwrap();
+
+        assert_eq!(Array::unchecked_from_js(JsValue::from(results)).length(), 0);
+    }
+}
@@ -0,0 +1,53 @@
+use js_sys::{ArrayBuffer, Uint8Array};
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[wasm_bindgen(typescript_custom_section)]
+const BYTES: &'static str = r#"
+type Bytes = Uint8Array | ArrayBuffer;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Bytes")]
+    pub type JsBytes;
+}
+
+/// Copies either an `ArrayBuffer` or a `Uint8Array` into a [Vec].
+///
+/// Accepting an `ArrayBuffer` directly allows buffers that have been transferred to a Web Worker
+/// with `postMessage` to be passed to verification without wrapping them in a view first.
+pub fn bytes_from_js(bytes: JsValue) -> Vec<u8> {
+    if bytes.is_instance_of::<ArrayBuffer>() {
+        return Uint8Array::new(&bytes).to_vec();
+    }
+
+    Uint8Array::unchecked_from_js(bytes).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn bytes_from_uint8_array() {
+        let bytes = Uint8Array::from(&[0, 1, 2, 3][..]);
+
+        assert_eq!(bytes_from_js(bytes.into()), vec![0, 1, 2, 3]);
+    }
+
+    #[wasm_bindgen_test]
+    fn bytes_from_array_buffer() {
+        let bytes = Uint8Array::from(&[0, 1, 2, 3][..]).buffer();
+
+        assert_eq!(bytes_from_js(bytes.into()), vec![0, 1, 2, 3]);
+    }
+
+    #[wasm_bindgen_test]
+    fn bytes_from_uint8_array_view() {
+        let buffer = Uint8Array::from(&[0, 1, 2, 3, 4, 5][..]).buffer();
+        let bytes = Uint8Array::new_with_byte_offset_and_length(&buffer, 2, 3);
+
+        assert_eq!(bytes_from_js(bytes.into()), vec![2, 3, 4]);
+    }
+}
@@ -0,0 +1,103 @@
+//! Runs the shared differential fixtures through both the Rust API and the JS bindings, and
+//! asserts that both produce identical verification results, so that the two surfaces cannot
+//! drift apart.
+
+use crate::{verify_request_response_pair, JsRequest, JsResponse};
+use ic_http_certification::{HeaderField, HttpRequest, HttpResponse};
+use ic_response_verification::{
+    types::VerificationInfo, verify_request_response_pair as verify_request_response_pair_impl,
+    ResponseVerificationJsError,
+};
+use ic_response_verification_test_utils::{differential_fixtures, DifferentialFixture};
+use js_sys::{Array, Object, Reflect, Uint8Array, JSON};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn rust_and_js_verification_results_are_identical() {
+    for fixture in differential_fixtures() {
+        let expected = stringify(verify_with_rust(&fixture));
+        let result = stringify(verify_with_js(&fixture));
+
+        assert_eq!(
+            result, expected,
+            "verification results differ for fixture `{}`",
+            fixture.name
+        );
+    }
+}
+
+fn verify_with_rust(fixture: &DifferentialFixture) -> JsValue {
+    verify_request_response_pair_impl(
+        fixture.request.clone(),
+        fixture.response.clone(),
+        &fixture.canister_id,
+        fixture.current_time_ns,
+        fixture.max_cert_time_offset_ns,
+        &fixture.root_key,
+        fixture.min_requested_verification_version,
+    )
+    .map(|verification_info| JsValue::from(VerificationInfo::from(verification_info)))
+    .unwrap_or_else(|error| JsValue::from(ResponseVerificationJsError::from(error)))
+}
+
+fn verify_with_js(fixture: &DifferentialFixture) -> JsValue {
+    verify_request_response_pair(
+        request_to_js(&fixture.request),
+        response_to_js(&fixture.response),
+        Uint8Array::from(fixture.canister_id.as_slice()).unchecked_into(),
+        fixture.current_time_ns as u64,
+        fixture.max_cert_time_offset_ns as u64,
+        Uint8Array::from(fixture.root_key.as_slice()).unchecked_into(),
+        fixture.min_requested_verification_version,
+    )
+    .map(JsValue::from)
+    .unwrap_or_else(JsValue::from)
+}
+
+fn stringify(value: JsValue) -> String {
+    JSON::stringify(&value).unwrap().into()
+}
+
+fn request_to_js(request: &HttpRequest) -> JsRequest {
+    let js_request = Object::new();
+    set(&js_request, "method", &JsValue::from(&request.method));
+    set(&js_request, "url", &JsValue::from(&request.url));
+    set(&js_request, "headers", &headers_to_js(&request.headers));
+    set(
+        &js_request,
+        "body",
+        &Uint8Array::from(request.body.as_slice()),
+    );
+
+    js_request.unchecked_into()
+}
+
+fn response_to_js(response: &HttpResponse) -> JsResponse {
+    let js_response = Object::new();
+    set(
+        &js_response,
+        "statusCode",
+        &JsValue::from(response.status_code),
+    );
+    set(&js_response, "headers", &headers_to_js(&response.headers));
+    set(
+        &js_response,
+        "body",
+        &Uint8Array::from(response.body.as_slice()),
+    );
+
+    js_response.unchecked_into()
+}
+
+fn headers_to_js(headers: &[HeaderField]) -> JsValue {
+    headers
+        .iter()
+        .map(|(name, value)| Array::of2(&JsValue::from(name), &JsValue::from(value)))
+        .collect::<Array>()
+        .into()
+}
+
+fn set(target: &Object, key: &str, value: &JsValue) {
+    Reflect::set(target, &JsValue::from(key), value).unwrap();
+}
@@ -0,0 +1,148 @@
+use crate::bytes::{bytes_from_js, JsBytes};
+use crate::request::request_from_js;
+use crate::response::response_from_js;
+use crate::JsRequest;
+use ic_response_verification::{
+    types::VerifiedResponse,
+    verify_request_response_headers as verify_request_response_headers_impl,
+    IncrementalVerification as IncrementalVerificationImpl, ResponseVerificationJsError,
+};
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Omit<Response, 'body'>")]
+    pub type JsResponseHead;
+
+    #[wasm_bindgen(typescript_type = "VerifiedResponse | undefined")]
+    pub type JsOptionalVerifiedResponse;
+}
+
+/// A response whose certificate, CEL expression and certified headers have been verified, but
+/// whose body has not been verified yet.
+///
+/// The body should be passed to `update` as it is received, and `finish` must be called once the
+/// full body has been received. The certified status code and headers should not be considered
+/// certified until `finish` has returned without throwing.
+#[wasm_bindgen]
+pub struct IncrementalVerification {
+    inner: IncrementalVerificationImpl,
+}
+
+#[wasm_bindgen]
+impl IncrementalVerification {
+    /// The status code and headers that were included in the certification, or `undefined` if
+    /// certification was skipped by the canister. The body of this object is always empty.
+    #[wasm_bindgen(getter)]
+    pub fn response(&self) -> JsOptionalVerifiedResponse {
+        let response = self
+            .inner
+            .response
+            .as_ref()
+            .map(|response| VerifiedResponse {
+                status_code: response.status_code,
+                headers: response.headers.clone(),
+                body: Vec::new(),
+            });
+
+        JsValue::from(response).unchecked_into::<JsOptionalVerifiedResponse>()
+    }
+
+    /// The version of verification that was used to verify the response.
+    #[wasm_bindgen(getter, js_name = verificationVersion)]
+    pub fn verification_version(&self) -> u16 {
+        self.inner.verification_version
+    }
+
+    /// Provides the next chunk of the response body.
+    pub fn update(&mut self, chunk: JsBytes) {
+        self.inner.update(&bytes_from_js(JsValue::from(chunk)));
+    }
+
+    /// Completes verification once the full response body has been provided with `update`.
+    /// Throws a `ResponseVerificationError` if the body, status code or headers are not valid.
+    pub fn finish(self) -> Result<(), ResponseVerificationJsError> {
+        self.inner
+            .finish()
+            .map_err(ResponseVerificationJsError::from)
+    }
+}
+
+/// Verifies the certificate, CEL expression and certified headers of a response with respect to
+/// the request, before the response body has been received. The body of the response is verified
+/// incrementally with the returned `IncrementalVerification`.
+#[wasm_bindgen(js_name = verifyRequestResponseHeaders)]
+pub fn verify_request_response_headers(
+    request: JsRequest,
+    response: JsResponseHead,
+    canister_id: JsBytes,
+    current_time_ns: u64,
+    max_cert_time_offset_ns: u64,
+    ic_public_key: JsBytes,
+    min_requested_verification_version: u8,
+) -> Result<IncrementalVerification, ResponseVerificationJsError> {
+    let request = request_from_js(JsValue::from(request));
+    let response = response_from_js(JsValue::from(response));
+    let canister_id = bytes_from_js(JsValue::from(canister_id));
+    let ic_public_key = bytes_from_js(JsValue::from(ic_public_key));
+
+    verify_request_response_headers_impl(
+        request,
+        response.status_code,
+        &response.headers,
+        &canister_id,
+        current_time_ns as u128,
+        max_cert_time_offset_ns as u128,
+        &ic_public_key,
+        min_requested_verification_version,
+    )
+    .map(|inner| IncrementalVerification { inner })
+    .map_err(ResponseVerificationJsError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_response_verification::ResponseVerificationJsErrorCode;
+    use js_sys::{Uint8Array, JSON};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    #[wasm_bindgen_test]
+    fn verify_request_response_headers_without_certificate() {
+        let request = JSON::parse(
+            r#"{
+                "method": "GET",
+                "url": "/",
+                "headers": [],
+                "body": []
+            }"#,
+        )
+        .unwrap();
+        let response = JSON::parse(
+            r#"{
+                "statusCode": 200,
+                "headers": [["Cache-Control", "max-age=604800"]]
+            }"#,
+        )
+        .unwrap();
+        let canister_id = Uint8Array::from(&[0, 0, 0, 0, 0, 0, 0, 1][..]);
+        let root_key = Uint8Array::from(&[0, 1, 2, 3][..]);
+
+        let error = verify_request_response_headers(
+            request.unchecked_into(),
+            response.unchecked_into(),
+            JsValue::from(canister_id).unchecked_into(),
+            0,
+            0,
+            JsValue::from(root_key).unchecked_into(),
+            2,
+        )
+        .err()
+        .unwrap();
+
+        assert_eq!(
+            error.code,
+            ResponseVerificationJsErrorCode::MissingCertification
+        );
+    }
+}
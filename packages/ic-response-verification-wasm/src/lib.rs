@@ -1,15 +1,31 @@
 #![deny(clippy::all)]
 
+use crate::bytes::{bytes_from_js, JsBytes};
 use crate::request::request_from_js;
 use crate::response::response_from_js;
+use crate::verification_options::{verification_options_from_js, JsVerificationOptions};
 use ic_response_verification::{
     types::VerificationInfo, verify_request_response_pair as verify_request_response_pair_impl,
     ResponseVerificationJsError, MAX_VERIFICATION_VERSION, MIN_VERIFICATION_VERSION,
 };
 use wasm_bindgen::{prelude::*, JsCast};
 
+mod batch_verification;
+mod bytes;
+mod incremental_verification;
 mod request;
 mod response;
+mod verification_options;
+mod verify_fetch;
+
+#[cfg(test)]
+mod test_utils;
+
+#[cfg(test)]
+mod differential_tests;
+
+#[cfg(feature = "wasm-threads")]
+pub use wasm_bindgen_rayon::init_thread_pool;
 
 #[wasm_bindgen]
 extern "C" {
@@ -46,22 +62,24 @@ pub fn main_js() {
 pub fn verify_request_response_pair(
     request: JsRequest,
     response: JsResponse,
-    canister_id: &[u8],
+    canister_id: JsBytes,
     current_time_ns: u64,
     max_cert_time_offset_ns: u64,
-    ic_public_key: &[u8],
+    ic_public_key: JsBytes,
     min_requested_verification_version: u8,
 ) -> Result<JsVerificationInfo, ResponseVerificationJsError> {
     let request = request_from_js(JsValue::from(request));
     let response = response_from_js(JsValue::from(response));
+    let canister_id = bytes_from_js(JsValue::from(canister_id));
+    let ic_public_key = bytes_from_js(JsValue::from(ic_public_key));
 
     verify_request_response_pair_impl(
         request.into(),
         response.into(),
-        canister_id,
+        &canister_id,
         current_time_ns as u128,
         max_cert_time_offset_ns as u128,
-        ic_public_key,
+        &ic_public_key,
         min_requested_verification_version,
     )
     .map(|verification_result| {
@@ -70,3 +88,33 @@ pub fn verify_request_response_pair(
     })
     .map_err(|e| ResponseVerificationJsError::from(e))
 }
+
+/// Verifies a request and response pair in the same way as `verifyRequestResponsePair`, but with
+/// the canister ID, root key and other parameters provided as `VerificationOptions`.
+///
+/// The mainnet root key and the current time are used unless they are provided in the options.
+#[wasm_bindgen(js_name = verifyRequestResponsePairWithOptions)]
+pub fn verify_request_response_pair_with_options(
+    request: JsRequest,
+    response: JsResponse,
+    options: JsVerificationOptions,
+) -> Result<JsVerificationInfo, JsValue> {
+    let request = request_from_js(JsValue::from(request));
+    let response = response_from_js(JsValue::from(response));
+    let options = verification_options_from_js(&options)?;
+
+    verify_request_response_pair_impl(
+        request,
+        response,
+        &options.canister_id,
+        options.current_time_ns,
+        options.max_cert_time_offset_ns,
+        &options.root_key,
+        options.min_verification_version,
+    )
+    .map(|verification_result| {
+        JsValue::from(VerificationInfo::from(verification_result))
+            .unchecked_into::<JsVerificationInfo>()
+    })
+    .map_err(|e| JsValue::from(ResponseVerificationJsError::from(e)))
+}
@@ -1,18 +1,19 @@
+use crate::bytes::bytes_from_js;
 use ic_http_certification::HttpRequest;
 use wasm_bindgen::{prelude::*, JsCast};
 
 #[wasm_bindgen(typescript_custom_section)]
 const REQUEST: &'static str = r#"
-interface Request {
-    method: String;
-    url: String;
-    headers: [string, string][];
-    body: Uint8Array;
-}
+type Request = {
+    method: string,
+    url: string,
+    headers: Array<[string, string]>,
+    body: Uint8Array | ArrayBuffer,
+};
 "#;
 
 pub fn request_from_js(req: JsValue) -> HttpRequest {
-    use js_sys::{Array, JsString, Object, Uint8Array};
+    use js_sys::{Array, JsString, Object};
 
     let method_str = JsString::from("method");
     let url_str = JsString::from("url");
@@ -54,7 +55,7 @@ pub fn request_from_js(req: JsValue) -> HttpRequest {
         }
 
         if k == body_str {
-            body = Uint8Array::unchecked_from_js(entry.get(1)).to_vec();
+            body = bytes_from_js(entry.get(1));
         }
     }
 
@@ -69,6 +70,7 @@ pub fn request_from_js(req: JsValue) -> HttpRequest {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::assert_ts_decl_in_source;
     use js_sys::JSON;
     use wasm_bindgen_test::wasm_bindgen_test;
 
@@ -101,4 +103,28 @@ mod tests {
             }
         );
     }
+
+    #[wasm_bindgen_test]
+    fn request_from_array_buffer_body() {
+        use js_sys::{Reflect, Uint8Array};
+
+        let v = JSON::parse(
+            r#"{
+                "method": "GET",
+                "url": "http://url.com",
+                "headers": []
+            }"#,
+        )
+        .expect("failed to parse JSON");
+        let body = Uint8Array::from(&[0, 1, 2, 3, 4, 5, 6][..]).buffer();
+        Reflect::set(&v, &"body".into(), &body).expect("failed to set body");
+        let r = request_from_js(v);
+
+        assert_eq!(r.body, vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[wasm_bindgen_test]
+    fn request_type() {
+        assert_ts_decl_in_source::<HttpRequest>(include_str!("request.rs"));
+    }
 }
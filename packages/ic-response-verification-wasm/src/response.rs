@@ -1,17 +1,18 @@
+use crate::bytes::bytes_from_js;
 use ic_http_certification::HttpResponse;
 use wasm_bindgen::{prelude::*, JsCast};
 
 #[wasm_bindgen(typescript_custom_section)]
 const RESPONSE: &'static str = r#"
-interface Response {
-    statusCode: number;
-    headers: [string, string][];
-    body: Uint8Array;
-}
+type Response = {
+    statusCode: number,
+    headers: Array<[string, string]>,
+    body: Uint8Array | ArrayBuffer,
+};
 "#;
 
 pub fn response_from_js(resp: JsValue) -> HttpResponse {
-    use js_sys::{Array, JsString, Number, Object, Uint8Array};
+    use js_sys::{Array, JsString, Number, Object};
 
     let status_code_str = JsString::from("statusCode");
     let headers_str = JsString::from("headers");
@@ -43,7 +44,7 @@ pub fn response_from_js(resp: JsValue) -> HttpResponse {
         }
 
         if k == body_str {
-            body = Uint8Array::unchecked_from_js(entry.get(1)).to_vec();
+            body = bytes_from_js(entry.get(1));
         }
     }
 
@@ -52,12 +53,14 @@ pub fn response_from_js(resp: JsValue) -> HttpResponse {
         headers,
         body,
         upgrade: None,
+        trailers: None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::assert_ts_decl_in_source;
     use js_sys::JSON;
     use wasm_bindgen_test::wasm_bindgen_test;
 
@@ -86,7 +89,13 @@ mod tests {
                     ("header2".into(), "header2val".into()),
                 ],
                 upgrade: None,
+                trailers: None,
             }
         );
     }
+
+    #[wasm_bindgen_test]
+    fn response_type() {
+        assert_ts_decl_in_source::<HttpResponse>(include_str!("response.rs"));
+    }
 }
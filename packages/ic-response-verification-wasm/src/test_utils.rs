@@ -0,0 +1,27 @@
+use ts_rs::TS;
+
+/// Asserts that a Rust source file contains a TypeScript declaration matching the declaration generated
+/// from the corresponding Rust type, ignoring comments and whitespace.
+///
+/// `typescript_custom_section` constants are consumed by `wasm_bindgen`, so the source file is checked
+/// instead of the constant itself.
+pub fn assert_ts_decl_in_source<T: TS>(source: &str) {
+    let decl = normalize_ts(&T::decl());
+
+    assert!(
+        normalize_ts(source).contains(&decl),
+        "expected TypeScript declaration `{decl}` was not found"
+    );
+}
+
+fn normalize_ts(ts: &str) -> String {
+    let mut ts = ts.to_string();
+    while let Some(start) = ts.find("/**") {
+        let end = ts[start..]
+            .find("*/")
+            .map_or(ts.len(), |end| start + end + 2);
+        ts.replace_range(start..end, "");
+    }
+
+    ts.split_whitespace().collect()
+}
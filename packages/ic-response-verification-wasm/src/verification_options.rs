@@ -0,0 +1,171 @@
+use crate::bytes::bytes_from_js;
+use ic_response_verification::MAX_VERIFICATION_VERSION;
+use js_sys::{Date, JsString, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+
+/// The DER encoded public key of the Internet Computer mainnet.
+const IC_ROOT_KEY: &[u8; 133] = b"\x30\x81\x82\x30\x1d\x06\x0d\x2b\x06\x01\x04\x01\x82\xdc\x7c\x05\x03\x01\x02\x01\x06\x0c\x2b\x06\x01\x04\x01\x82\xdc\x7c\x05\x03\x02\x01\x03\x61\x00\x81\x4c\x0e\x6e\xc7\x1f\xab\x58\x3b\x08\xbd\x81\x37\x3c\x25\x5c\x3c\x37\x1b\x2e\x84\x86\x3c\x98\xa4\xf1\xe0\x8b\x74\x23\x5d\x14\xfb\x5d\x9c\x0c\xd5\x46\xd9\x68\x5f\x91\x3a\x0c\x0b\x2c\xc5\x34\x15\x83\xbf\x4b\x43\x92\xe4\x67\xdb\x96\xd6\x5b\x9b\xb4\xcb\x71\x71\x12\xf8\x47\x2e\x0d\x5a\x4d\x14\x50\x5f\xfd\x74\x84\xb0\x12\x91\x09\x1c\x5f\x87\xb9\x88\x83\x46\x3f\x98\x09\x1a\x0b\xaa\xae";
+
+const DEFAULT_MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+
+#[wasm_bindgen(typescript_custom_section)]
+const VERIFICATION_OPTIONS: &'static str = r#"
+interface VerificationOptions {
+    canisterId: Bytes;
+    rootKey?: Bytes;
+    pinMainnetRootKey?: boolean;
+    currentTimeNs?: bigint;
+    maxCertTimeOffsetNs?: bigint;
+    minVerificationVersion?: number;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "VerificationOptions")]
+    pub type JsVerificationOptions;
+}
+
+pub(crate) struct VerificationOptions {
+    pub(crate) canister_id: Vec<u8>,
+    pub(crate) root_key: Vec<u8>,
+    pub(crate) current_time_ns: u128,
+    pub(crate) max_cert_time_offset_ns: u128,
+    pub(crate) min_verification_version: u8,
+}
+
+/// Returns the DER encoded public key of the Internet Computer mainnet. This is the root key that
+/// is used for verification when no `rootKey` option is provided.
+#[wasm_bindgen(js_name = getIcRootKey)]
+pub fn get_ic_root_key() -> Uint8Array {
+    Uint8Array::from(&IC_ROOT_KEY[..])
+}
+
+/// Reads [VerificationOptions] from a JS `VerificationOptions` object.
+///
+/// The mainnet root key is used when no `rootKey` is provided. Since a custom root key makes it
+/// possible to verify responses that were not certified by the mainnet, it is rejected unless
+/// `pinMainnetRootKey` is explicitly set to `false`, such as when verifying responses from a
+/// local replica.
+pub(crate) fn verification_options_from_js(
+    options: &JsValue,
+) -> Result<VerificationOptions, JsValue> {
+    let canister_id = get(options, "canisterId");
+    if canister_id.is_undefined() {
+        return Err(JsError::new("The `canisterId` option is required").into());
+    }
+
+    let pin_mainnet_root_key = get(options, "pinMainnetRootKey").as_bool().unwrap_or(true);
+    let root_key = get(options, "rootKey");
+    let root_key = match root_key.is_undefined() {
+        true => IC_ROOT_KEY.to_vec(),
+        false => bytes_from_js(root_key),
+    };
+    if pin_mainnet_root_key && root_key != IC_ROOT_KEY {
+        return Err(JsError::new(
+            "The `rootKey` option does not match the mainnet root key, set `pinMainnetRootKey` to `false` to use a custom root key",
+        )
+        .into());
+    }
+
+    let current_time_ns = get(options, "currentTimeNs");
+    let current_time_ns = match current_time_ns.is_undefined() {
+        true => (Date::now() as u128) * 1_000_000,
+        false => u64::try_from(current_time_ns)? as u128,
+    };
+
+    let max_cert_time_offset_ns = get(options, "maxCertTimeOffsetNs");
+    let max_cert_time_offset_ns = match max_cert_time_offset_ns.is_undefined() {
+        true => DEFAULT_MAX_CERT_TIME_OFFSET_NS,
+        false => u64::try_from(max_cert_time_offset_ns)? as u128,
+    };
+
+    let min_verification_version = get(options, "minVerificationVersion")
+        .as_f64()
+        .map(|version| version as u8)
+        .unwrap_or(MAX_VERIFICATION_VERSION);
+
+    Ok(VerificationOptions {
+        canister_id: bytes_from_js(canister_id),
+        root_key,
+        current_time_ns,
+        max_cert_time_offset_ns,
+        min_verification_version,
+    })
+}
+
+pub(crate) fn get(target: &JsValue, key: &str) -> JsValue {
+    if !target.is_object() {
+        return JsValue::UNDEFINED;
+    }
+
+    Reflect::get(target, &JsString::from(key)).unwrap_or(JsValue::UNDEFINED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use js_sys::{BigInt, JSON};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn options(json: &str) -> JsValue {
+        let options = JSON::parse(json).unwrap();
+        Reflect::set(
+            &options,
+            &"canisterId".into(),
+            &Uint8Array::from(&[0, 0, 0, 0, 0, 0, 0, 1][..]),
+        )
+        .unwrap();
+
+        options
+    }
+
+    #[wasm_bindgen_test]
+    fn options_with_defaults() {
+        let result = verification_options_from_js(&options("{}")).unwrap();
+
+        assert_eq!(result.canister_id, vec![0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(result.root_key, IC_ROOT_KEY.to_vec());
+        assert_eq!(
+            result.max_cert_time_offset_ns,
+            DEFAULT_MAX_CERT_TIME_OFFSET_NS
+        );
+        assert_eq!(result.min_verification_version, MAX_VERIFICATION_VERSION);
+    }
+
+    #[wasm_bindgen_test]
+    fn options_with_custom_root_key() {
+        let options = options(r#"{ "pinMainnetRootKey": false, "minVerificationVersion": 1 }"#);
+        Reflect::set(
+            &options,
+            &"rootKey".into(),
+            &Uint8Array::from(&[0, 1, 2, 3][..]),
+        )
+        .unwrap();
+        Reflect::set(&options, &"currentTimeNs".into(), &BigInt::from(42u64)).unwrap();
+
+        let result = verification_options_from_js(&options).unwrap();
+
+        assert_eq!(result.root_key, vec![0, 1, 2, 3]);
+        assert_eq!(result.current_time_ns, 42);
+        assert_eq!(result.min_verification_version, 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn options_with_custom_root_key_and_pinning() {
+        let options = options("{}");
+        Reflect::set(
+            &options,
+            &"rootKey".into(),
+            &Uint8Array::from(&[0, 1, 2, 3][..]),
+        )
+        .unwrap();
+
+        assert!(verification_options_from_js(&options).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn options_without_canister_id() {
+        assert!(verification_options_from_js(&JSON::parse("{}").unwrap()).is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use crate::bytes::bytes_from_js;
+use crate::verification_options::{get, verification_options_from_js};
+use ic_http_certification::{HttpRequest, HttpResponse};
+use ic_response_verification::{
+    verify_request_response_pair, ResponseVerificationJsError, ResponseVerificationJsErrorCode,
+};
+use js_sys::{Array, Function, JsString, Promise, Reflect, Uint8Array};
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen(typescript_custom_section)]
+const VERIFY_FETCH_OPTIONS: &'static str = r#"
+interface VerifyFetchOptions extends VerificationOptions {
+    init?: RequestInit;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "VerifyFetchOptions")]
+    pub type JsVerifyFetchOptions;
+
+    #[wasm_bindgen(typescript_type = "VerifiedResponse")]
+    pub type JsVerifiedResponse;
+}
+
+/// Performs a `fetch` request and verifies the response with respect to the request.
+///
+/// Resolves with only the certified parts of the response. Rejects with a `ResponseVerificationError`
+/// if verification fails, or if certification was skipped by the canister, and with the original
+/// error if the `fetch` request itself fails.
+#[wasm_bindgen(js_name = verifyFetch)]
+pub async fn verify_fetch(
+    url: String,
+    options: JsVerifyFetchOptions,
+) -> Result<JsVerifiedResponse, JsValue> {
+    let init = get(&options, "init");
+    let options = verification_options_from_js(&options)?;
+    let request = HttpRequest {
+        method: request_method_from_init(&init),
+        url: url.clone(),
+        headers: headers_from_js(&get(&init, "headers"))?,
+        body: request_body_from_init(&init),
+    };
+
+    let fetch = Function::unchecked_from_js(Reflect::get(&js_sys::global(), &"fetch".into())?);
+    let response = fetch.call2(&JsValue::UNDEFINED, &url.into(), &init)?;
+    let response = JsFuture::from(Promise::unchecked_from_js(response)).await?;
+    let response = response_from_fetch_response(response).await?;
+
+    let verification_info = verify_request_response_pair(
+        request,
+        response,
+        &options.canister_id,
+        options.current_time_ns,
+        options.max_cert_time_offset_ns,
+        &options.root_key,
+        options.min_verification_version,
+    )
+    .map_err(|e| JsValue::from(ResponseVerificationJsError::from(e)))?;
+
+    match verification_info.response {
+        Some(verified_response) => {
+            Ok(JsValue::from(verified_response).unchecked_into::<JsVerifiedResponse>())
+        }
+        None => Err(JsValue::from(ResponseVerificationJsError {
+            code: ResponseVerificationJsErrorCode::UncertifiedResponse,
+            message: "Response certification was skipped by the canister".to_string(),
+            details: None,
+        })),
+    }
+}
+
+fn request_method_from_init(init: &JsValue) -> String {
+    get(init, "method")
+        .as_string()
+        .map(|method| method.to_uppercase())
+        .unwrap_or_else(|| "GET".to_string())
+}
+
+fn request_body_from_init(init: &JsValue) -> Vec<u8> {
+    let body = get(init, "body");
+
+    if let Some(body) = body.as_string() {
+        return body.into_bytes();
+    }
+
+    if body.is_instance_of::<Uint8Array>() || body.is_instance_of::<js_sys::ArrayBuffer>() {
+        return bytes_from_js(body);
+    }
+
+    Vec::new()
+}
+
+async fn response_from_fetch_response(response: JsValue) -> Result<HttpResponse, JsValue> {
+    let status_code = get(&response, "status").as_f64().unwrap_or_default() as u16;
+    let headers = headers_from_js(&get(&response, "headers"))?;
+
+    let array_buffer = Function::unchecked_from_js(get(&response, "arrayBuffer"));
+    let body = array_buffer.call0(&response)?;
+    let body = JsFuture::from(Promise::unchecked_from_js(body)).await?;
+
+    Ok(HttpResponse {
+        status_code,
+        headers,
+        body: bytes_from_js(body),
+        upgrade: None,
+        trailers: None,
+    })
+}
+
+/// Reads headers from anything that can be passed to the `Headers` constructor, such as a `Headers`
+/// object, an array of `[name, value]` tuples or a record of header names to values.
+fn headers_from_js(headers: &JsValue) -> Result<Vec<(String, String)>, JsValue> {
+    if headers.is_undefined() || headers.is_null() {
+        return Ok(Vec::new());
+    }
+
+    let headers_constructor =
+        Function::unchecked_from_js(Reflect::get(&js_sys::global(), &JsString::from("Headers"))?);
+    let headers = Reflect::construct(&headers_constructor, &Array::of1(headers))?;
+    let entries = Function::unchecked_from_js(get(&headers, "entries")).call0(&headers)?;
+
+    let mut result = Vec::new();
+    for entry in js_sys::try_iter(&entries)?.into_iter().flatten() {
+        let entry = Array::unchecked_from_js(entry?);
+        let name = entry.get(0).as_string().unwrap_or_default();
+        let value = entry.get(1).as_string().unwrap_or_default();
+
+        result.push((name, value));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use js_sys::JSON;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn mock_fetch(body: &str) {
+        let fetch = Function::new_with_args("url, init", body);
+        Reflect::set(&js_sys::global(), &"fetch".into(), &fetch).unwrap();
+    }
+
+    fn options() -> JsVerifyFetchOptions {
+        let options =
+            JSON::parse(r#"{ "init": { "headers": [["Accept", "text/html"]] } }"#).unwrap();
+        Reflect::set(
+            &options,
+            &"canisterId".into(),
+            &Uint8Array::from(&[0, 0, 0, 0, 0, 0, 0, 1][..]),
+        )
+        .unwrap();
+
+        options.unchecked_into()
+    }
+
+    #[wasm_bindgen_test]
+    fn headers_from_array() {
+        let headers =
+            JSON::parse(r#"[["Accept", "text/html"], ["Accept-Encoding", "gzip"]]"#).unwrap();
+
+        let result = headers_from_js(&headers).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                ("accept".to_string(), "text/html".to_string()),
+                ("accept-encoding".to_string(), "gzip".to_string()),
+            ]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn headers_from_record() {
+        let headers = JSON::parse(r#"{ "Accept": "text/html" }"#).unwrap();
+
+        let result = headers_from_js(&headers).unwrap();
+
+        assert_eq!(
+            result,
+            vec![("accept".to_string(), "text/html".to_string())]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn request_details_from_init() {
+        let init = JSON::parse(r#"{ "method": "post", "body": "Hello World!" }"#).unwrap();
+
+        assert_eq!(request_method_from_init(&init), "POST");
+        assert_eq!(request_body_from_init(&init), b"Hello World!".to_vec());
+        assert_eq!(request_method_from_init(&JsValue::UNDEFINED), "GET");
+        assert_eq!(
+            request_body_from_init(&JsValue::UNDEFINED),
+            Vec::<u8>::new()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn verify_fetch_rejects_uncertified_response() {
+        mock_fetch(
+            r#"return Promise.resolve({
+                status: 200,
+                headers: new Headers([["Content-Type", "text/html"]]),
+                arrayBuffer: () => Promise.resolve(new ArrayBuffer(0)),
+            });"#,
+        );
+
+        let error = verify_fetch("https://example.com/".to_string(), options())
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            get(&error, "code").as_f64(),
+            Some(ResponseVerificationJsErrorCode::MissingCertification as u32 as f64)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn verify_fetch_rejects_with_fetch_error() {
+        mock_fetch(r#"return Promise.reject(new TypeError("Failed to fetch"));"#);
+
+        let error = verify_fetch("https://example.com/".to_string(), options())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(error.is_instance_of::<js_sys::TypeError>());
+    }
+}
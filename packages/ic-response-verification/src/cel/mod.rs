@@ -1,13 +1,5 @@
 //! Utilities for parsing CEL expressions into Rust consumable types.
+//!
+//! The parser itself lives in the [ic_http_certification::cel] module and is re-exported here.
 
-mod error;
-pub use error::*;
-
-mod ast_mapping;
-mod parser;
-
-pub(crate) use ast_mapping::map_cel_ast;
-pub(crate) use parser::parse_cel_expression;
-
-#[cfg(test)]
-mod tests;
+pub use ic_http_certification::cel::{parse_cel_expression, CelParserError, CelParserResult};
@@ -96,6 +96,37 @@ pub enum ResponseVerificationError {
     /// HTTP Certification error
     #[error(r#"HTTP Certification error: "{0}""#)]
     HttpCertificationError(#[from] ic_http_certification::HttpCertificationError),
+
+    /// The response body is larger than the maximum allowed size
+    #[error(r#"The response body exceeds the maximum allowed size of {max_body_size:?} bytes"#)]
+    ResponseBodyTooLarge {
+        /// The maximum allowed size of the response body, in bytes
+        max_body_size: usize,
+    },
+
+    /// The response body is not correctly encoded with the chunked transfer coding
+    #[error(r#"Malformed chunked body: "{0}""#)]
+    MalformedChunkedBody(String),
+
+    /// A certification header or certificate header field was found more than once, which is
+    /// rejected by the [Strict](crate::VerificationProfile::Strict) verification profile
+    #[error(r#"Duplicate certification header or header field: "{0}""#)]
+    DuplicateCertificationHeader(String),
+
+    /// The certificate was rejected by the freshness check provided by the caller
+    #[error(r#"Freshness check failed: "{0}""#)]
+    FreshnessCheckFailed(String),
+
+    /// The tree does not prove that no certification exists for the requested path
+    #[error("Certification absence could not be proven")]
+    CertificationNotAbsent,
+
+    /// The encoded response body decodes to more than the maximum allowed size
+    #[error(r#"The decoded response body exceeds the maximum allowed size of {max_decoded_body_size:?} bytes"#)]
+    DecodedBodyTooLarge {
+        /// The maximum allowed size of the decoded response body, in bytes
+        max_decoded_body_size: usize,
+    },
 }
 
 /// JS Representation of the ResponseVerificationError code
@@ -139,20 +170,98 @@ pub enum ResponseVerificationJsErrorCode {
     CertificateVerificationFailed,
     /// HTTP Certification error
     HttpCertificationError,
+    /// The response passed verification, but certification was skipped by the canister so no part
+    /// of the response is certified. Only used by `verifyFetch`.
+    UncertifiedResponse,
+    /// The response body is larger than the maximum allowed size
+    ResponseBodyTooLarge,
+    /// The response body is not correctly encoded with the chunked transfer coding
+    MalformedChunkedBody,
+    /// A certification header or certificate header field was found more than once
+    DuplicateCertificationHeader,
+    /// The certificate was rejected by the freshness check provided by the caller
+    FreshnessCheckFailed,
+    /// The tree does not prove that no certification exists for the requested path
+    CertificationNotAbsent,
+    /// The encoded response body decodes to more than the maximum allowed size
+    DecodedBodyTooLarge,
 }
 
+#[cfg(all(target_arch = "wasm32", feature = "js"))]
+#[wasm_bindgen(typescript_custom_section)]
+const RESPONSE_VERIFICATION_ERROR: &'static str = r#"
+interface ResponseVerificationError {
+    name: "ResponseVerificationError";
+    code: ResponseVerificationErrorCode;
+    message: string;
+    details?: string;
+}
+
+export function isResponseVerificationError(error: unknown): error is ResponseVerificationError;
+"#;
+
+#[cfg(all(target_arch = "wasm32", feature = "js"))]
+const RESPONSE_VERIFICATION_ERROR_NAME: &str = "ResponseVerificationError";
+
 /// JS Representation of the ResponseVerificationError
+///
+/// This is converted to a plain JS object, rather than an instance of a class, so that it can be
+/// passed between a service worker and a page with `postMessage` without losing any information.
 #[cfg(all(target_arch = "wasm32", feature = "js"))]
-#[wasm_bindgen(inspectable, js_name = ResponseVerificationError)]
 #[derive(Debug, Eq, PartialEq)]
 pub struct ResponseVerificationJsError {
     /// Error code as an enum
-    #[wasm_bindgen(readonly)]
     pub code: ResponseVerificationJsErrorCode,
 
     /// Stringified error message
-    #[wasm_bindgen(getter_with_clone, readonly)]
     pub message: String,
+
+    /// Stringified error message of the underlying error, if there is one
+    pub details: Option<String>,
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "js"))]
+impl From<ResponseVerificationJsError> for JsValue {
+    fn from(error: ResponseVerificationJsError) -> Self {
+        use js_sys::{Array, Object};
+
+        let mut entries = vec![
+            Array::of2(
+                &JsValue::from("name"),
+                &JsValue::from(RESPONSE_VERIFICATION_ERROR_NAME),
+            ),
+            Array::of2(&JsValue::from("code"), &JsValue::from(error.code)),
+            Array::of2(&JsValue::from("message"), &JsValue::from(error.message)),
+        ];
+        if let Some(details) = error.details {
+            entries.push(Array::of2(
+                &JsValue::from("details"),
+                &JsValue::from(details),
+            ));
+        }
+
+        let entries = entries.into_iter().collect::<Array>();
+        JsValue::from(Object::from_entries(&entries).unwrap())
+    }
+}
+
+/// Checks if a value thrown by this package is a `ResponseVerificationError`.
+///
+/// This also works for errors that have been passed through `postMessage`, where an `instanceof`
+/// check would not.
+#[cfg(all(target_arch = "wasm32", feature = "js"))]
+#[wasm_bindgen(js_name = isResponseVerificationError, skip_typescript)]
+pub fn is_response_verification_error(error: &JsValue) -> bool {
+    use js_sys::{JsString, Reflect};
+
+    if !error.is_object() {
+        return false;
+    }
+
+    let name = Reflect::get(error, &JsString::from("name")).unwrap_or(JsValue::UNDEFINED);
+    let code = Reflect::get(error, &JsString::from("code")).unwrap_or(JsValue::UNDEFINED);
+
+    name.as_string().as_deref() == Some(RESPONSE_VERIFICATION_ERROR_NAME) && code.as_f64().is_some()
 }
 
 #[cfg(all(target_arch = "wasm32", feature = "js"))]
@@ -205,12 +314,32 @@ impl From<ResponseVerificationError> for ResponseVerificationJsError {
             ResponseVerificationError::HttpCertificationError(_) => {
                 ResponseVerificationJsErrorCode::HttpCertificationError
             }
+            ResponseVerificationError::ResponseBodyTooLarge { .. } => {
+                ResponseVerificationJsErrorCode::ResponseBodyTooLarge
+            }
+            ResponseVerificationError::MalformedChunkedBody(_) => {
+                ResponseVerificationJsErrorCode::MalformedChunkedBody
+            }
+            ResponseVerificationError::DuplicateCertificationHeader(_) => {
+                ResponseVerificationJsErrorCode::DuplicateCertificationHeader
+            }
+            ResponseVerificationError::FreshnessCheckFailed(_) => {
+                ResponseVerificationJsErrorCode::FreshnessCheckFailed
+            }
+            ResponseVerificationError::CertificationNotAbsent => {
+                ResponseVerificationJsErrorCode::CertificationNotAbsent
+            }
+            ResponseVerificationError::DecodedBodyTooLarge { .. } => {
+                ResponseVerificationJsErrorCode::DecodedBodyTooLarge
+            }
         };
         let message = error.to_string();
+        let details = std::error::Error::source(&error).map(|source| source.to_string());
 
         ResponseVerificationJsError {
             code: code.into(),
             message,
+            details,
         }
     }
 }
@@ -222,8 +351,50 @@ mod tests {
     use base64::{engine::general_purpose, Engine as _};
     use ic_http_certification::HttpCertificationError;
     use ic_response_verification_test_utils::hex_decode;
+    use js_sys::JSON;
     use wasm_bindgen_test::wasm_bindgen_test;
 
+    #[wasm_bindgen_test]
+    fn error_into_plain_object() {
+        let error = JsValue::from(ResponseVerificationJsError {
+            code: ResponseVerificationJsErrorCode::CborDecodingFailed,
+            message: "CBOR decoding failed".into(),
+            details: Some("Invalid cbor: HashTree CBOR is malformed".into()),
+        });
+
+        assert_eq!(
+            JSON::stringify(&error).unwrap(),
+            r#"{"name":"ResponseVerificationError","code":15,"message":"CBOR decoding failed","details":"Invalid cbor: HashTree CBOR is malformed"}"#
+        );
+        assert!(is_response_verification_error(&error));
+    }
+
+    #[wasm_bindgen_test]
+    fn error_into_plain_object_without_details() {
+        let error = JsValue::from(ResponseVerificationJsError {
+            code: ResponseVerificationJsErrorCode::InvalidTree,
+            message: "Invalid tree root hash".into(),
+            details: None,
+        });
+
+        assert_eq!(
+            JSON::stringify(&error).unwrap(),
+            r#"{"name":"ResponseVerificationError","code":6,"message":"Invalid tree root hash"}"#
+        );
+        assert!(is_response_verification_error(&error));
+    }
+
+    #[wasm_bindgen_test]
+    fn is_response_verification_error_with_other_values() {
+        assert!(!is_response_verification_error(&JsValue::UNDEFINED));
+        assert!(!is_response_verification_error(&JsValue::from(
+            "ResponseVerificationError"
+        )));
+        assert!(!is_response_verification_error(
+            &JSON::parse(r#"{"name":"TypeError","message":"Failed to fetch"}"#).unwrap()
+        ));
+    }
+
     #[wasm_bindgen_test]
     fn error_into_http_certification_error() {
         let error = ResponseVerificationError::HttpCertificationError(
@@ -236,6 +407,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::HttpCertificationError,
                 message: r#"HTTP Certification error: "Failed to parse url: "https://internetcomputer.org"""#.into(),
+                details: Some(r#"Failed to parse url: "https://internetcomputer.org""#.into()),
             }
         )
     }
@@ -253,7 +425,8 @@ mod tests {
             result,
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::IoError,
-                message: format!(r#"IO error: "{}""#, error_msg),
+                message: format!(r#"IO error: "{}""#, error_msg.clone()),
+                details: Some(error_msg),
             }
         )
     }
@@ -277,6 +450,10 @@ mod tests {
                     r#"HTTP Certification error: "Error converting UTF8 string bytes: "{0}"""#,
                     inner_error.to_string()
                 ),
+                details: Some(format!(
+                    r#"Error converting UTF8 string bytes: "{0}""#,
+                    inner_error.to_string()
+                )),
             }
         )
     }
@@ -296,6 +473,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::UnsupportedVerificationVersion,
                 message: r#"The requested verification version 42 is not supported, the current supported range is 1-2"#.into(),
+                details: None,
             }
         )
     }
@@ -314,6 +492,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::RequestedVerificationVersionMismatch,
                 message: r#"The requested verification version 1 is lower than the minimum requested version 2"#.into(),
+                details: None,
             }
         )
     }
@@ -332,6 +511,9 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::CelError,
                 message: r#"Cel parser error"#.into(),
+                details: Some(
+                    "Cel Syntax Expception: Garbage is not allowed in the CEL expression!".into()
+                ),
             }
         )
     }
@@ -342,6 +524,7 @@ mod tests {
         let inner_error = general_purpose::STANDARD
             .decode(invalid_base64)
             .expect_err("Expected error");
+        let error_msg = inner_error.to_string();
 
         let error = ResponseVerificationError::Base64DecodingError(inner_error);
 
@@ -352,6 +535,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::Base64DecodingError,
                 message: format!(r#"Base64 decoding error"#),
+                details: Some(error_msg),
             }
         )
     }
@@ -360,6 +544,7 @@ mod tests {
     fn error_into_parse_int_error() {
         let invalid_int = "fortytwo";
         let inner_error = invalid_int.parse::<u8>().expect_err("Expected error");
+        let error_msg = inner_error.to_string();
 
         let error = ResponseVerificationError::ParseIntError(inner_error);
 
@@ -370,6 +555,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::ParseIntError,
                 message: format!(r#"Error parsing int"#),
+                details: Some(error_msg),
             }
         )
     }
@@ -384,6 +570,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::InvalidTree,
                 message: format!(r#"Invalid tree root hash"#),
+                details: None,
             }
         )
     }
@@ -398,6 +585,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::InvalidExpressionPath,
                 message: format!(r#"Invalid expression path"#),
+                details: None,
             }
         )
     }
@@ -412,6 +600,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::InvalidResponseBody,
                 message: format!(r#"Invalid response body"#),
+                details: None,
             }
         )
     }
@@ -426,6 +615,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::InvalidResponseHashes,
                 message: format!(r#"Invalid response hashes"#),
+                details: None,
             }
         )
     }
@@ -440,6 +630,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::MissingCertificate,
                 message: format!(r#"Certificate not found"#),
+                details: None,
             }
         )
     }
@@ -454,6 +645,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::MissingTree,
                 message: format!(r#"Tree not found"#),
+                details: None,
             }
         )
     }
@@ -468,6 +660,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::MissingCertificateExpressionPath,
                 message: format!(r#"Certificate expression path not found"#),
+                details: None,
             }
         )
     }
@@ -482,6 +675,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::MissingCertificateExpression,
                 message: format!(r#"Certificate expression not found"#),
+                details: None,
             }
         )
     }
@@ -496,6 +690,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::MissingCertification,
                 message: format!(r#"Certification values not found"#),
+                details: None,
             }
         )
     }
@@ -512,6 +707,7 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::CborDecodingFailed,
                 message: format!(r#"CBOR decoding failed"#),
+                details: Some("Invalid cbor: HashTree CBOR is malformed".into()),
             }
         )
     }
@@ -530,6 +726,111 @@ mod tests {
             ResponseVerificationJsError {
                 code: ResponseVerificationJsErrorCode::CertificateVerificationFailed,
                 message: format!(r#"Certificate verification failed"#),
+                details: Some(
+                    "Time not found in certificate at path: [[116, 105, 109, 101]]".into()
+                ),
+            }
+        )
+    }
+
+    #[wasm_bindgen_test]
+    fn error_into_response_body_too_large_error() {
+        let error = ResponseVerificationError::ResponseBodyTooLarge {
+            max_body_size: 1024,
+        };
+        let result = ResponseVerificationJsError::from(error);
+
+        assert_eq!(
+            result,
+            ResponseVerificationJsError {
+                code: ResponseVerificationJsErrorCode::ResponseBodyTooLarge,
+                message: format!(
+                    r#"The response body exceeds the maximum allowed size of 1024 bytes"#
+                ),
+                details: None,
+            }
+        )
+    }
+
+    #[wasm_bindgen_test]
+    fn error_into_malformed_chunked_body_error() {
+        let error = ResponseVerificationError::MalformedChunkedBody("Invalid chunk size".into());
+        let result = ResponseVerificationJsError::from(error);
+
+        assert_eq!(
+            result,
+            ResponseVerificationJsError {
+                code: ResponseVerificationJsErrorCode::MalformedChunkedBody,
+                message: format!(r#"Malformed chunked body: "Invalid chunk size""#),
+                details: None,
+            }
+        )
+    }
+
+    #[wasm_bindgen_test]
+    fn error_into_duplicate_certification_header_error() {
+        let error =
+            ResponseVerificationError::DuplicateCertificationHeader("ic-certificate".into());
+        let result = ResponseVerificationJsError::from(error);
+
+        assert_eq!(
+            result,
+            ResponseVerificationJsError {
+                code: ResponseVerificationJsErrorCode::DuplicateCertificationHeader,
+                message: format!(
+                    r#"Duplicate certification header or header field: "ic-certificate""#
+                ),
+                details: None,
+            }
+        )
+    }
+
+    #[wasm_bindgen_test]
+    fn error_into_freshness_check_failed_error() {
+        let error =
+            ResponseVerificationError::FreshnessCheckFailed("Certificate is outdated".into());
+        let result = ResponseVerificationJsError::from(error);
+
+        assert_eq!(
+            result,
+            ResponseVerificationJsError {
+                code: ResponseVerificationJsErrorCode::FreshnessCheckFailed,
+                message: format!(r#"Freshness check failed: "Certificate is outdated""#),
+                details: None,
+            }
+        )
+    }
+
+    #[wasm_bindgen_test]
+    fn error_into_certification_not_absent_error() {
+        let error = ResponseVerificationError::CertificationNotAbsent;
+        let result = ResponseVerificationJsError::from(error);
+
+        assert_eq!(
+            result,
+            ResponseVerificationJsError {
+                code: ResponseVerificationJsErrorCode::CertificationNotAbsent,
+                message: format!(r#"Certification absence could not be proven"#),
+                details: None,
+            }
+        )
+    }
+
+    #[wasm_bindgen_test]
+    fn error_into_decoded_body_too_large_error() {
+        let error = ResponseVerificationError::DecodedBodyTooLarge {
+            max_decoded_body_size: 1_024,
+        };
+        let result = ResponseVerificationJsError::from(error);
+
+        assert_eq!(
+            result,
+            ResponseVerificationJsError {
+                code: ResponseVerificationJsErrorCode::DecodedBodyTooLarge,
+                message: format!(
+                    r#"The decoded response body exceeds the maximum allowed size of 1024 bytes"#
+                ),
+                details: None,
             }
         )
     }
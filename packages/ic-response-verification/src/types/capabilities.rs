@@ -0,0 +1,62 @@
+use crate::VerificationProfile;
+use ic_http_certification::cel::CelVersion;
+
+/// A mode of certification that a canister can use for a response, as declared by its CEL
+/// expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CertificationMode {
+    /// Certification is skipped entirely.
+    Skip,
+    /// Only the response is certified.
+    ResponseOnly,
+    /// Both the request and the response are certified.
+    Full,
+}
+
+/// A feature of the CEL expressions used for v2 verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CelFeature {
+    /// The `default_certification` CEL function.
+    DefaultCertification,
+    /// Certification of a subset of the request headers.
+    CertifiedRequestHeaders,
+    /// Certification of a subset of the request query parameters.
+    CertifiedQueryParameters,
+    /// Certification of a subset of the response headers.
+    CertifiedResponseHeaders,
+    /// Certification of all response headers, except for a subset of excluded headers.
+    ResponseHeaderExclusions,
+    /// Certification of a subset of the response headers, except for a subset of excluded headers.
+    CertifiedResponseHeadersWithExclusions,
+    /// Certification of the decoded response body, rather than the body as it was served.
+    DecodedBodyCertification,
+    /// Certification of the class of the response status code, rather than the exact status code.
+    StatusCodeClassCertification,
+    /// Certification of the set of request methods that a response may be served for.
+    AllowedRequestMethods,
+}
+
+/// The spec versions, CEL features and certification modes supported by this build of the
+/// package, as returned by [capabilities](crate::capabilities).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The minimum supported verification version
+    pub min_verification_version: u8,
+    /// The maximum supported verification version
+    pub max_verification_version: u8,
+    /// The supported certification modes
+    pub certification_modes: Vec<CertificationMode>,
+    /// The supported versions of the CEL syntax, in ascending order. Use
+    /// [CelVersion::negotiate] to select the highest version that is also supported by a canister.
+    pub cel_versions: Vec<CelVersion>,
+    /// The supported CEL features
+    pub cel_features: Vec<CelFeature>,
+    /// The supported verification profiles
+    pub verification_profiles: Vec<VerificationProfile>,
+    /// The supported values of the `Content-Encoding` header when decoding response bodies
+    pub content_encodings: Vec<&'static str>,
+    /// Whether the JS bindings are enabled, with the `js` cargo feature
+    pub js: bool,
+    /// Whether trace logging is enabled, with the `debug` cargo feature
+    pub debug: bool,
+}
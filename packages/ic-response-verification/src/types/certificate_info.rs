@@ -0,0 +1,13 @@
+use ic_certification::hash_tree::Hash;
+
+/// Information about the certificate of a response that has passed certificate verification,
+/// provided to the freshness check of
+/// [verify_request_response_pair_with_freshness_check](crate::verify_request_response_pair_with_freshness_check).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateInfo {
+    /// The time of the certificate, in nanoseconds since the UNIX epoch
+    pub certificate_time_ns: u128,
+    /// The root hash of the tree provided by the canister, which matches the canister's certified
+    /// data at the time of the certificate
+    pub tree_root_hash: Hash,
+}
@@ -0,0 +1,16 @@
+use crate::types::VerifiedResponse;
+
+/// A verified response that has been normalized to be forwarded to a client, returned by
+/// [verify_request_response_pair_for_forwarding](crate::verify_request_response_pair_for_forwarding).
+#[derive(Debug, PartialEq, Eq)]
+pub struct ForwardedResponse {
+    /// The status code, certified headers and body of the response. This will be [None] if the
+    /// canister skipped certification of the response.
+    pub response: Option<VerifiedResponse>,
+    /// The headers of the response that are not included in the certification, in the order that
+    /// they appear in the response. The `IC-Certificate` and `IC-CertificateExpression` headers
+    /// are only used for verification and are not included.
+    pub uncertified_headers: Vec<(String, String)>,
+    /// The version of verification that was used to verify the response
+    pub verification_version: u16,
+}
@@ -7,3 +7,23 @@ pub use verification_result::*;
 /// Types to represent a certified response that clients can use to determine which parts of a response are safe to use.
 mod verified_response;
 pub use verified_response::*;
+
+/// Types to represent the certificate of a response that is provided to freshness checks.
+mod certificate_info;
+pub use certificate_info::*;
+
+/// Types to represent a report of a verification that can be signed and archived.
+mod verification_report;
+pub use verification_report::*;
+
+/// Types to represent non-fatal findings of a verification.
+mod verification_warning;
+pub use verification_warning::*;
+
+/// Types to represent a verified response that can be forwarded to a client.
+mod forwarded_response;
+pub use forwarded_response::*;
+
+/// Types to represent the capabilities of this build of the package.
+mod capabilities;
+pub use capabilities::*;
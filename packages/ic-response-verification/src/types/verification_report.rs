@@ -0,0 +1,150 @@
+use candid::{CandidType, Deserialize};
+use ic_certification::hash_tree::Hash;
+use ic_representation_independent_hash::{representation_independent_hash, Value};
+
+/// A compact, self-contained report of a verification, produced by
+/// [verify_request_response_pair_with_report](crate::verify_request_response_pair_with_report).
+///
+/// The report contains hashes of the inputs rather than the inputs themselves, so it can be
+/// archived cheaply. It can be encoded with Candid for archiving, and [hash](VerificationReport::hash)
+/// provides a digest of the report that is suitable for signing.
+#[derive(Debug, Clone, PartialEq, Eq, CandidType, Deserialize)]
+pub struct VerificationReport {
+    /// The hash of the request, see [hash_request](crate::hash_request).
+    pub request_hash: Hash,
+    /// The hash of the response, see [hash_response](crate::hash_response).
+    pub response_hash: Hash,
+    /// The SHA-256 hash of the CBOR encoded certificate from the `IC-Certificate` header, if the
+    /// header contained a certificate.
+    pub certificate_hash: Option<Hash>,
+    /// The ID of the canister that the response was verified for.
+    pub canister_id: Vec<u8>,
+    /// The SHA-256 hash of the root public key that the certificate was verified against.
+    pub root_key_hash: Hash,
+    /// The current time, in nanoseconds since the UNIX epoch, that was used for verification.
+    pub current_time_ns: u128,
+    /// The maximum allowed offset between the certificate time and the current time.
+    pub max_cert_time_offset_ns: u128,
+    /// The minimum verification version that was accepted.
+    pub min_requested_verification_version: u8,
+    /// The outcome of the verification.
+    pub outcome: VerificationOutcome,
+}
+
+/// The outcome of a verification, as recorded in a [VerificationReport].
+#[derive(Debug, Clone, PartialEq, Eq, CandidType, Deserialize)]
+pub enum VerificationOutcome {
+    /// The response passed verification.
+    Verified {
+        /// The version of verification that was used to verify the response.
+        verification_version: u16,
+    },
+    /// The response failed verification.
+    Failed {
+        /// The error that verification failed with.
+        error: String,
+    },
+}
+
+impl VerificationReport {
+    /// Calculates the [representation independent hash](https://internetcomputer.org/docs/current/references/ic-interface-spec/#hash-of-map)
+    /// of the report. Any change to the report results in a different hash, so the hash can be
+    /// signed in place of the full report.
+    ///
+    /// 128-bit times are hashed as their big-endian bytes.
+    pub fn hash(&self) -> Hash {
+        let mut map = vec![
+            (
+                "request_hash".into(),
+                Value::Bytes(self.request_hash.to_vec()),
+            ),
+            (
+                "response_hash".into(),
+                Value::Bytes(self.response_hash.to_vec()),
+            ),
+            ("canister_id".into(), Value::Bytes(self.canister_id.clone())),
+            (
+                "root_key_hash".into(),
+                Value::Bytes(self.root_key_hash.to_vec()),
+            ),
+            (
+                "current_time_ns".into(),
+                Value::Bytes(self.current_time_ns.to_be_bytes().to_vec()),
+            ),
+            (
+                "max_cert_time_offset_ns".into(),
+                Value::Bytes(self.max_cert_time_offset_ns.to_be_bytes().to_vec()),
+            ),
+            (
+                "min_requested_verification_version".into(),
+                Value::Number(self.min_requested_verification_version.into()),
+            ),
+        ];
+
+        if let Some(certificate_hash) = self.certificate_hash {
+            map.push((
+                "certificate_hash".into(),
+                Value::Bytes(certificate_hash.to_vec()),
+            ));
+        }
+
+        match &self.outcome {
+            VerificationOutcome::Verified {
+                verification_version,
+            } => {
+                map.push(("outcome".into(), Value::String("verified".into())));
+                map.push((
+                    "verification_version".into(),
+                    Value::Number((*verification_version).into()),
+                ));
+            }
+            VerificationOutcome::Failed { error } => {
+                map.push(("outcome".into(), Value::String("failed".into())));
+                map.push(("error".into(), Value::String(error.clone())));
+            }
+        }
+
+        representation_independent_hash(&map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn report() -> VerificationReport {
+        VerificationReport {
+            request_hash: [1; 32],
+            response_hash: [2; 32],
+            certificate_hash: Some([3; 32]),
+            canister_id: vec![0, 0, 0, 0, 0, 0, 0, 1],
+            root_key_hash: [4; 32],
+            current_time_ns: 1_700_000_000_000_000_000,
+            max_cert_time_offset_ns: 300_000_000_000,
+            min_requested_verification_version: 2,
+            outcome: VerificationOutcome::Verified {
+                verification_version: 2,
+            },
+        }
+    }
+
+    #[rstest]
+    fn hash_is_deterministic(report: VerificationReport) {
+        assert_eq!(report.hash(), report.clone().hash());
+    }
+
+    #[rstest]
+    #[case::request_hash(VerificationReport { request_hash: [0; 32], ..report() })]
+    #[case::certificate_hash(VerificationReport { certificate_hash: None, ..report() })]
+    #[case::current_time(VerificationReport { current_time_ns: 0, ..report() })]
+    #[case::min_version(VerificationReport { min_requested_verification_version: 1, ..report() })]
+    #[case::outcome(VerificationReport {
+        outcome: VerificationOutcome::Failed { error: "Invalid tree root hash".into() },
+        ..report()
+    })]
+    fn hash_changes_with_report(report: VerificationReport, #[case] changed: VerificationReport) {
+        assert_ne!(report.hash(), changed.hash());
+    }
+}
@@ -0,0 +1,31 @@
+/// A non-fatal finding of a verification, returned by
+/// [verify_request_response_pair_with_warnings](crate::verify_request_response_pair_with_warnings).
+///
+/// Warnings do not affect the outcome of verification, but they can be logged or monitored to
+/// tighten verification policies gradually, for example before switching to the
+/// [Strict](crate::VerificationProfile::Strict) verification profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationWarning {
+    /// The certificate is close to the maximum allowed age, so a slightly slower client or a
+    /// slightly more skewed clock would have rejected it.
+    CertificateNearingStaleness {
+        /// The time of the certificate, in nanoseconds since the UNIX epoch
+        certificate_time_ns: u128,
+        /// The earliest certificate time that would have been accepted
+        min_certificate_time_ns: u128,
+    },
+
+    /// The response contains headers that are not included in the certification, and so cannot
+    /// be trusted.
+    UncertifiedHeaders {
+        /// The names of the uncertified headers, in the order that they appear in the response
+        header_names: Vec<String>,
+    },
+
+    /// The response was verified with the legacy v1 verification, which only certifies the body.
+    LegacyVerificationVersion,
+
+    /// The canister skipped certification of the response, so no part of the response is
+    /// certified.
+    CertificationSkipped,
+}
@@ -15,13 +15,29 @@ pub fn validate_tree(canister_id: &[u8], certificate: &Certificate, tree: &HashT
     };
 
     let digest = tree.digest();
-    if witness != digest {
+    if !constant_time_eq(witness, &digest) {
         return false;
     }
 
     true
 }
 
+/// Compares two hashes in constant time with respect to their contents, so that the time taken
+/// does not reveal how many leading bytes of the hashes are equal. Hashes of different lengths are
+/// never equal.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let difference = a
+        .iter()
+        .zip(b.iter())
+        .fold(0u8, |difference, (a, b)| difference | (a ^ b));
+
+    std::hint::black_box(difference) == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -32,10 +48,25 @@ mod tests {
     use ic_response_verification_test_utils::{
         create_canister_id, create_certified_data, AssetTree,
     };
+    use rstest::*;
 
     static CANISTER_ID: &str = "r7inp-6aaaa-aaaaa-aaabq-cai";
     static OTHER_CANISTER_ID: &str = "rdmx6-jaaaa-aaaaa-aaadq-cai";
 
+    #[rstest]
+    #[case::empty(&[], &[], true)]
+    #[case::equal(&[1, 2, 3], &[1, 2, 3], true)]
+    #[case::different_first_byte(&[0, 2, 3], &[1, 2, 3], false)]
+    #[case::different_last_byte(&[1, 2, 3], &[1, 2, 4], false)]
+    #[case::different_lengths(&[1, 2, 3], &[1, 2], false)]
+    fn constant_time_eq_compares_hashes(
+        #[case] a: &[u8],
+        #[case] b: &[u8],
+        #[case] expected: bool,
+    ) {
+        assert_eq!(constant_time_eq(a, b), expected);
+    }
+
     #[test]
     fn validate_tree_with_matching_digest() {
         let canister_id = create_canister_id(CANISTER_ID);
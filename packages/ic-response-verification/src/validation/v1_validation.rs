@@ -1,3 +1,4 @@
+use super::constant_time_eq;
 use ic_certification::{hash_tree::Hash, HashTree, LookupResult};
 
 pub fn validate_body(tree: &HashTree, request_path: &str, body_sha: &Hash) -> bool {
@@ -19,7 +20,7 @@ pub fn validate_body(tree: &HashTree, request_path: &str, body_sha: &Hash) -> bo
         },
     };
 
-    body_sha == tree_sha
+    constant_time_eq(body_sha, tree_sha)
 }
 
 #[cfg(test)]
@@ -166,11 +166,15 @@ mod tests {
     use crate::test_utils::{create_pruned, remove_whitespace, sha256_from_hex};
     use ic_certification::hash_tree::{fork, label, leaf};
     use ic_http_certification::{
-        cel::{DefaultFullCelExpression, DefaultRequestCertification},
+        cel::{
+            DefaultBodyCertification, DefaultFullCelExpression, DefaultRequestCertification,
+            DefaultStatusCodeCertification,
+        },
         DefaultResponseCertification,
     };
     use ic_representation_independent_hash::hash;
     use ic_response_verification_test_utils::hex_decode;
+    use std::borrow::Cow;
 
     const REQUEST_HASH: &str = "5fac69685533f0650991441a2b818e8ad5ab2fec51de8cfdbea1276135494815";
     const RESPONSE_HASH: &str = "07b7c729f4083db0e266fef3f8f5acf1315135605bf38884c07ebb59fbf91ce8";
@@ -1053,6 +1057,9 @@ mod tests {
                 "Accept-Encoding",
                 "Cache-Control",
             ]),
+            body: DefaultBodyCertification::AsServed,
+            status: DefaultStatusCodeCertification::Exact,
+            allowed_methods: Cow::Borrowed(&[]),
         }))
     }
 }
@@ -1,18 +1,43 @@
-use crate::error::ResponseVerificationResult;
+use crate::error::{ResponseVerificationError, ResponseVerificationResult};
+use brotli_decompressor::Decompressor;
 use flate2::read::{DeflateDecoder, GzDecoder};
 use std::io::Read;
 
 const MAX_CHUNK_SIZE_TO_DECOMPRESS: usize = 1_024;
 
+/// The maximum size, in bytes, that an encoded response body is decoded to before it is hashed.
+///
+/// A small encoded body can decode to a much larger one, so decoding stops with
+/// [DecodedBodyTooLarge](ResponseVerificationError::DecodedBodyTooLarge) once this size is
+/// exceeded, instead of exhausting the verifier's memory.
+pub const MAX_DECODED_BODY_SIZE: usize = 64 * 1_024 * 1_024;
+
 pub fn decode_body(body: &Vec<u8>, encoding: Option<&str>) -> ResponseVerificationResult<Vec<u8>> {
-    return match encoding {
-        Some("gzip") => body_from_decoder(GzDecoder::new(body.as_slice())),
-        Some("deflate") => body_from_decoder(DeflateDecoder::new(body.as_slice())),
+    decode_body_with_max_size(body, encoding, MAX_DECODED_BODY_SIZE)
+}
+
+fn decode_body_with_max_size(
+    body: &Vec<u8>,
+    encoding: Option<&str>,
+    max_decoded_body_size: usize,
+) -> ResponseVerificationResult<Vec<u8>> {
+    match encoding {
+        Some("gzip") => body_from_decoder(GzDecoder::new(body.as_slice()), max_decoded_body_size),
+        Some("deflate") => {
+            body_from_decoder(DeflateDecoder::new(body.as_slice()), max_decoded_body_size)
+        }
+        Some("br") => body_from_decoder(
+            Decompressor::new(body.as_slice(), MAX_CHUNK_SIZE_TO_DECOMPRESS),
+            max_decoded_body_size,
+        ),
         _ => Ok(body.to_owned()),
-    };
+    }
 }
 
-fn body_from_decoder<D: Read>(mut decoder: D) -> ResponseVerificationResult<Vec<u8>> {
+fn body_from_decoder<D: Read>(
+    mut decoder: D,
+    max_decoded_body_size: usize,
+) -> ResponseVerificationResult<Vec<u8>> {
     let mut decoded = Vec::new();
     let mut buffer = [0u8; MAX_CHUNK_SIZE_TO_DECOMPRESS];
 
@@ -23,6 +48,12 @@ fn body_from_decoder<D: Read>(mut decoder: D) -> ResponseVerificationResult<Vec<
             return Ok(decoded);
         }
 
+        if bytes > max_decoded_body_size - decoded.len() {
+            return Err(ResponseVerificationError::DecodedBodyTooLarge {
+                max_decoded_body_size,
+            });
+        }
+
         decoded.extend_from_slice(&buffer[..bytes]);
     }
 }
@@ -32,6 +63,7 @@ mod tests {
     use super::*;
     use flate2::write::{DeflateEncoder, GzEncoder};
     use flate2::Compression;
+    use rstest::*;
     use std::io::Write;
 
     const BODY: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
@@ -64,4 +96,88 @@ mod tests {
 
         assert_eq!(result.as_slice(), BODY);
     }
+
+    #[rstest]
+    #[case("gzip")]
+    #[case("deflate")]
+    fn decode_body_within_max_size(#[case] encoding: &str) {
+        let body = vec![0; 1_024];
+        let encoded_body = encode_body(&body, encoding);
+
+        let result = decode_body_with_max_size(&encoded_body, Some(encoding), 1_024).unwrap();
+
+        assert_eq!(result, body);
+    }
+
+    #[rstest]
+    #[case("gzip")]
+    #[case("deflate")]
+    fn decode_body_larger_than_max_size(#[case] encoding: &str) {
+        let encoded_body = encode_body(&[0; 1_025], encoding);
+
+        let result = decode_body_with_max_size(&encoded_body, Some(encoding), 1_024);
+
+        assert!(matches!(
+            result,
+            Err(ResponseVerificationError::DecodedBodyTooLarge {
+                max_decoded_body_size: 1_024
+            })
+        ));
+    }
+
+    #[rstest]
+    #[case("gzip")]
+    #[case("deflate")]
+    fn decode_highly_compressible_body(#[case] encoding: &str) {
+        let encoded_body = encode_body(&vec![0; MAX_DECODED_BODY_SIZE + 1], encoding);
+        assert!(encoded_body.len() < MAX_DECODED_BODY_SIZE / 512);
+
+        let result = decode_body(&encoded_body, Some(encoding));
+
+        assert!(matches!(
+            result,
+            Err(ResponseVerificationError::DecodedBodyTooLarge {
+                max_decoded_body_size: MAX_DECODED_BODY_SIZE
+            })
+        ));
+    }
+
+    #[test]
+    fn decode_brotli_body_larger_than_max_size() {
+        // the brotli encoding of BODY
+        let encoded_body = vec![139, 3, 128, 1, 2, 3, 4, 5, 6, 7, 8, 3];
+
+        let result = decode_body_with_max_size(&encoded_body, Some("br"), BODY.len() - 1);
+
+        assert!(matches!(
+            result,
+            Err(ResponseVerificationError::DecodedBodyTooLarge { .. })
+        ));
+    }
+
+    fn encode_body(body: &[u8], encoding: &str) -> Vec<u8> {
+        match encoding {
+            "gzip" => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+                encoder.write_all(body).unwrap();
+                encoder.finish().unwrap()
+            }
+            "deflate" => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+                encoder.write_all(body).unwrap();
+                encoder.finish().unwrap()
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn decode_brotli_body() {
+        // the brotli encoding of BODY
+        let encoded_body = vec![139, 3, 128, 1, 2, 3, 4, 5, 6, 7, 8, 3];
+
+        let result = decode_body(&encoded_body, Some("br")).unwrap();
+
+        assert_eq!(result.as_slice(), BODY);
+    }
 }
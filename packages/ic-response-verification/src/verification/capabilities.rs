@@ -0,0 +1,103 @@
+use super::{MAX_VERIFICATION_VERSION, MIN_VERIFICATION_VERSION};
+use crate::{
+    types::{Capabilities, CelFeature, CertificationMode},
+    VerificationProfile,
+};
+use ic_http_certification::cel::CelVersion;
+
+/// Reports the spec versions, CEL features and certification modes that are supported by this
+/// build of the package, taking enabled cargo features into account.
+///
+/// Gateways can log this at startup, or use it to negotiate behavior with canisters and clients.
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        min_verification_version: MIN_VERIFICATION_VERSION,
+        max_verification_version: MAX_VERIFICATION_VERSION,
+        certification_modes: vec![
+            CertificationMode::Skip,
+            CertificationMode::ResponseOnly,
+            CertificationMode::Full,
+        ],
+        cel_versions: CelVersion::SUPPORTED.to_vec(),
+        cel_features: vec![
+            CelFeature::DefaultCertification,
+            CelFeature::CertifiedRequestHeaders,
+            CelFeature::CertifiedQueryParameters,
+            CelFeature::CertifiedResponseHeaders,
+            CelFeature::ResponseHeaderExclusions,
+            CelFeature::CertifiedResponseHeadersWithExclusions,
+            CelFeature::DecodedBodyCertification,
+            CelFeature::StatusCodeClassCertification,
+            CelFeature::AllowedRequestMethods,
+        ],
+        verification_profiles: vec![VerificationProfile::Strict, VerificationProfile::Lenient],
+        content_encodings: vec!["identity", "gzip", "deflate", "br"],
+        js: cfg!(feature = "js"),
+        debug: cfg!(feature = "debug"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verification::body::decode_body;
+    use flate2::write::{DeflateEncoder, GzEncoder};
+    use flate2::Compression;
+    use rstest::*;
+    use std::io::Write;
+
+    #[rstest]
+    fn capabilities_match_supported_verification_versions() {
+        let capabilities = capabilities();
+
+        assert_eq!(capabilities.min_verification_version, 1);
+        assert_eq!(capabilities.max_verification_version, 2);
+    }
+
+    #[rstest]
+    fn capabilities_match_supported_cel_versions() {
+        let capabilities = capabilities();
+
+        assert_eq!(capabilities.cel_versions, vec![CelVersion::V2]);
+        assert_eq!(
+            CelVersion::negotiate(&capabilities.cel_versions),
+            Some(CelVersion::LATEST)
+        );
+    }
+
+    #[rstest]
+    fn capabilities_match_cargo_features() {
+        let capabilities = capabilities();
+
+        assert_eq!(capabilities.js, cfg!(feature = "js"));
+        assert_eq!(capabilities.debug, cfg!(feature = "debug"));
+    }
+
+    #[rstest]
+    fn capabilities_content_encodings_can_be_decoded() {
+        const BODY: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+
+        for encoding in capabilities().content_encodings {
+            let encoded_body = match encoding {
+                "identity" => BODY.to_vec(),
+                "gzip" => {
+                    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                    encoder.write_all(BODY).unwrap();
+                    encoder.finish().unwrap()
+                }
+                "deflate" => {
+                    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                    encoder.write_all(BODY).unwrap();
+                    encoder.finish().unwrap()
+                }
+                // the brotli encoding of BODY
+                "br" => vec![139, 3, 128, 1, 2, 3, 4, 5, 6, 7, 8, 3],
+                encoding => panic!("No test body for content encoding {encoding}"),
+            };
+
+            let result = decode_body(&encoded_body, Some(encoding)).unwrap();
+
+            assert_eq!(result.as_slice(), BODY);
+        }
+    }
+}
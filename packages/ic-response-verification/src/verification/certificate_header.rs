@@ -1,9 +1,9 @@
-use super::certificate_header_field::CertificateHeaderField;
+use super::{certificate_header_field::CertificateHeaderField, VerificationProfile};
 use crate::{
     base64::BASE64,
     error::{ResponseVerificationError, ResponseVerificationResult},
 };
-use base64::Engine as _;
+use base64::{engine::general_purpose, Engine as _};
 use log::warn;
 
 /// Parsed `Ic-Certificate` header, containing a certificate and tree.
@@ -16,8 +16,12 @@ pub struct CertificateHeader {
 }
 
 impl CertificateHeader {
-    /// Parses the given header and returns a new CertificateHeader.
-    pub fn from(header_value: &str) -> ResponseVerificationResult<CertificateHeader> {
+    /// Parses the given header and returns a new CertificateHeader, applying the base64 and
+    /// duplicate field tolerances of the given profile.
+    pub fn from(
+        header_value: &str,
+        profile: &VerificationProfile,
+    ) -> ResponseVerificationResult<CertificateHeader> {
         let mut certificate_header = CertificateHeader {
             certificate: None,
             tree: None,
@@ -30,8 +34,10 @@ impl CertificateHeader {
                 match name {
                     "certificate" => {
                         certificate_header.certificate = match certificate_header.certificate {
-                            None => Some(decode_base64_header(value)?),
+                            None => Some(decode_base64_header(value, profile)?),
                             Some(existing_certificate) => {
+                                check_duplicate_field("certificate", profile)?;
+
                                 warn!("Found duplicate certificate field in certificate header, ignoring...");
 
                                 Some(existing_certificate)
@@ -40,8 +46,10 @@ impl CertificateHeader {
                     }
                     "tree" => {
                         certificate_header.tree = match certificate_header.tree {
-                            None => Some(decode_base64_header(value)?),
+                            None => Some(decode_base64_header(value, profile)?),
                             Some(existing_tree) => {
+                                check_duplicate_field("tree", profile)?;
+
                                 warn!(
                                     "Found duplicate tree field in certificate header, ignoring..."
                                 );
@@ -54,6 +62,8 @@ impl CertificateHeader {
                         certificate_header.version = match certificate_header.version {
                             None => Some(parse_int_header(value)?),
                             Some(existing_version) => {
+                                check_duplicate_field("version", profile)?;
+
                                 warn!(
                                     "Found duplicate version field in certificate header, ignoring..."
                                 );
@@ -64,8 +74,10 @@ impl CertificateHeader {
                     }
                     "expr_path" => {
                         certificate_header.expr_path = match certificate_header.expr_path {
-                            None => Some(decode_base64_header(value)?),
+                            None => Some(decode_base64_header(value, profile)?),
                             Some(existing_expr_path) => {
+                                check_duplicate_field("expr_path", profile)?;
+
                                 warn!(
                                     "Found duplicate expr_path field in certificate header, ignoring..."
                                 );
@@ -83,10 +95,25 @@ impl CertificateHeader {
     }
 }
 
-fn decode_base64_header(value: &str) -> ResponseVerificationResult<Vec<u8>> {
-    BASE64
-        .decode(value)
-        .map_err(ResponseVerificationError::Base64DecodingError)
+fn decode_base64_header(
+    value: &str,
+    profile: &VerificationProfile,
+) -> ResponseVerificationResult<Vec<u8>> {
+    let result = match profile.allows_lenient_base64() {
+        true => BASE64.decode(value),
+        false => general_purpose::STANDARD.decode(value),
+    };
+
+    result.map_err(ResponseVerificationError::Base64DecodingError)
+}
+
+fn check_duplicate_field(name: &str, profile: &VerificationProfile) -> ResponseVerificationResult {
+    match profile.allows_duplicate_certification_headers() {
+        true => Ok(()),
+        false => Err(ResponseVerificationError::DuplicateCertificationHeader(
+            format!("ic-certificate: {name}"),
+        )),
+    }
 }
 
 fn parse_int_header(value: &str) -> ResponseVerificationResult<u8> {
@@ -102,7 +129,6 @@ mod tests {
     use ic_response_verification_test_utils::{cbor_encode, create_certificate};
 
     fn base64_encode_no_padding(data: &[u8]) -> String {
-        use base64::engine::general_purpose;
         general_purpose::STANDARD_NO_PAD.encode(data)
     }
 
@@ -120,7 +146,8 @@ mod tests {
         ]
         .join(",");
 
-        let certificate_header = CertificateHeader::from(header.as_str()).unwrap();
+        let certificate_header =
+            CertificateHeader::from(header.as_str(), &VerificationProfile::Lenient).unwrap();
 
         assert_eq!(certificate_header.certificate.unwrap(), certificate);
         assert_eq!(certificate_header.tree.unwrap(), tree);
@@ -142,7 +169,8 @@ mod tests {
         ]
         .join(",");
 
-        let certificate_header = CertificateHeader::from(header.as_str()).unwrap();
+        let certificate_header =
+            CertificateHeader::from(header.as_str(), &VerificationProfile::Lenient).unwrap();
 
         assert_eq!(certificate_header.certificate.unwrap(), certificate);
         assert_eq!(certificate_header.tree.unwrap(), tree);
@@ -160,7 +188,8 @@ mod tests {
         ]
         .join(",");
 
-        let result = CertificateHeader::from(header.as_str()).unwrap();
+        let result =
+            CertificateHeader::from(header.as_str(), &VerificationProfile::Lenient).unwrap();
 
         assert_eq!(
             result,
@@ -188,7 +217,8 @@ mod tests {
         ]
         .join(",");
 
-        let certificate_header = CertificateHeader::from(header.as_str()).unwrap();
+        let certificate_header =
+            CertificateHeader::from(header.as_str(), &VerificationProfile::Lenient).unwrap();
 
         assert_eq!(certificate_header.certificate.unwrap(), certificate);
         assert_eq!(certificate_header.tree.unwrap(), tree);
@@ -208,7 +238,8 @@ mod tests {
         ]
         .join(",");
 
-        let certificate_header = CertificateHeader::from(header.as_str()).unwrap();
+        let certificate_header =
+            CertificateHeader::from(header.as_str(), &VerificationProfile::Lenient).unwrap();
 
         assert_eq!(certificate_header.certificate.unwrap(), certificate);
         assert!(certificate_header.tree.is_none());
@@ -228,7 +259,8 @@ mod tests {
         ]
         .join(",");
 
-        let certificate_header = CertificateHeader::from(header.as_str()).unwrap();
+        let certificate_header =
+            CertificateHeader::from(header.as_str(), &VerificationProfile::Lenient).unwrap();
 
         assert!(certificate_header.certificate.is_none());
         assert_eq!(certificate_header.tree.unwrap(), tree);
@@ -246,7 +278,8 @@ mod tests {
         ]
         .join(",");
 
-        let certificate_header = CertificateHeader::from(header.as_str()).unwrap();
+        let certificate_header =
+            CertificateHeader::from(header.as_str(), &VerificationProfile::Lenient).unwrap();
 
         assert_eq!(certificate_header.certificate.unwrap(), certificate);
         assert_eq!(certificate_header.tree.unwrap(), tree);
@@ -266,7 +299,8 @@ mod tests {
         ]
         .join(",");
 
-        let certificate_header = CertificateHeader::from(header.as_str()).unwrap();
+        let certificate_header =
+            CertificateHeader::from(header.as_str(), &VerificationProfile::Lenient).unwrap();
 
         assert_eq!(certificate_header.certificate.unwrap(), certificate);
         assert_eq!(certificate_header.tree.unwrap(), tree);
@@ -298,11 +332,64 @@ mod tests {
         ]
         .join(",");
 
-        let certificate_header = CertificateHeader::from(header.as_str()).unwrap();
+        let certificate_header =
+            CertificateHeader::from(header.as_str(), &VerificationProfile::Lenient).unwrap();
 
         assert_eq!(certificate_header.certificate.unwrap(), certificate);
         assert_eq!(certificate_header.tree.unwrap(), tree.as_slice());
         assert_eq!(certificate_header.version.unwrap(), version);
         assert_eq!(certificate_header.expr_path.unwrap(), expr_path);
     }
+
+    #[test]
+    fn certificate_header_strict_profile_parses_valid_header() {
+        let certificate = cbor_encode(&create_certificate(None));
+        let tree = cbor_encode(&create_tree(None));
+        let version = 2u8;
+        let expr_path = cbor_encode(&vec!["/", "assets", "img.jpg"]);
+        let header = [
+            create_encoded_header_field("certificate", &certificate),
+            create_encoded_header_field("tree", &tree),
+            create_header_field("version", &version.to_string()),
+            create_encoded_header_field("expr_path", &expr_path),
+        ]
+        .join(",");
+
+        let certificate_header =
+            CertificateHeader::from(header.as_str(), &VerificationProfile::Strict).unwrap();
+
+        assert_eq!(certificate_header.certificate.unwrap(), certificate);
+        assert_eq!(certificate_header.tree.unwrap(), tree);
+        assert_eq!(certificate_header.version.unwrap(), version);
+        assert_eq!(certificate_header.expr_path.unwrap(), expr_path);
+    }
+
+    #[test]
+    fn certificate_header_strict_profile_rejects_unpadded_base64() {
+        let header = create_header_field("certificate", &base64_encode_no_padding(b"ab"));
+
+        let result = CertificateHeader::from(header.as_str(), &VerificationProfile::Strict);
+
+        assert!(matches!(
+            result,
+            Err(ResponseVerificationError::Base64DecodingError(_))
+        ));
+    }
+
+    #[test]
+    fn certificate_header_strict_profile_rejects_duplicate_fields() {
+        let tree = cbor_encode(&create_tree(None));
+        let header = [
+            create_encoded_header_field("tree", &tree),
+            create_encoded_header_field("tree", "Goodbye tree!"),
+        ]
+        .join(",");
+
+        let result = CertificateHeader::from(header.as_str(), &VerificationProfile::Strict);
+
+        assert!(matches!(
+            result,
+            Err(ResponseVerificationError::DuplicateCertificationHeader(name)) if name == "ic-certificate: tree"
+        ));
+    }
 }
@@ -0,0 +1,222 @@
+use crate::error::{ResponseVerificationError, ResponseVerificationResult};
+use futures_core::Stream;
+use std::{future::poll_fn, pin::Pin};
+
+const CRLF: &[u8] = b"\r\n";
+
+/// Collects a streamed response body into a single body that can be passed to
+/// [verify_request_response_pair](crate::verify_request_response_pair).
+///
+/// The body is rejected with [ResponseBodyTooLarge](ResponseVerificationError::ResponseBodyTooLarge)
+/// as soon as it grows beyond `max_body_size` bytes, so a malicious replica or boundary node cannot
+/// make the client buffer an unbounded amount of data. Errors from the stream are converted into a
+/// [ResponseVerificationError], so streams of [std::io::Error] can be used directly.
+///
+/// If the stream carries the raw bytes of a body with the chunked transfer coding, rather than the
+/// decoded chunks, the collected body should also be passed to [decode_chunked_body].
+pub async fn collect_body_stream<S, B, E>(
+    mut stream: S,
+    max_body_size: usize,
+) -> ResponseVerificationResult<Vec<u8>>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+    E: Into<ResponseVerificationError>,
+{
+    let mut body = Vec::new();
+
+    while let Some(chunk) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+        let chunk = chunk.map_err(Into::into)?;
+        let chunk = chunk.as_ref();
+
+        if body.len() + chunk.len() > max_body_size {
+            return Err(ResponseVerificationError::ResponseBodyTooLarge { max_body_size });
+        }
+
+        body.extend_from_slice(chunk);
+    }
+
+    Ok(body)
+}
+
+/// Decodes a body with the
+/// [chunked transfer coding](https://www.rfc-editor.org/rfc/rfc9112#section-7.1) into the body
+/// that was certified by the canister.
+///
+/// Chunk extensions and trailer fields are ignored, since neither is included in the certification
+/// of a response. The body is rejected with
+/// [ResponseBodyTooLarge](ResponseVerificationError::ResponseBodyTooLarge) if the decoded body is
+/// larger than `max_body_size` bytes, and with
+/// [MalformedChunkedBody](ResponseVerificationError::MalformedChunkedBody) if it is not correctly
+/// encoded.
+pub fn decode_chunked_body(
+    body: &[u8],
+    max_body_size: usize,
+) -> ResponseVerificationResult<Vec<u8>> {
+    let mut decoded_body = Vec::new();
+    let mut remaining = body;
+
+    loop {
+        let (chunk_size_line, rest) = split_line(remaining)?;
+        let chunk_size = parse_chunk_size(chunk_size_line)?;
+        remaining = rest;
+
+        if chunk_size == 0 {
+            break;
+        }
+
+        if chunk_size > max_body_size.saturating_sub(decoded_body.len()) {
+            return Err(ResponseVerificationError::ResponseBodyTooLarge { max_body_size });
+        }
+
+        if remaining.len() < chunk_size + CRLF.len() {
+            return Err(malformed_chunked_body(
+                "Chunk is shorter than its chunk size",
+            ));
+        }
+
+        let (chunk, rest) = remaining.split_at(chunk_size);
+        let Some(rest) = rest.strip_prefix(CRLF) else {
+            return Err(malformed_chunked_body(
+                "Chunk does not match its chunk size",
+            ));
+        };
+
+        decoded_body.extend_from_slice(chunk);
+        remaining = rest;
+    }
+
+    // the trailer section ends with an empty line
+    loop {
+        let (trailer_line, rest) = split_line(remaining)?;
+        remaining = rest;
+
+        if trailer_line.is_empty() {
+            break;
+        }
+    }
+
+    if !remaining.is_empty() {
+        return Err(malformed_chunked_body(
+            "Unexpected data after the last chunk",
+        ));
+    }
+
+    Ok(decoded_body)
+}
+
+fn split_line(bytes: &[u8]) -> ResponseVerificationResult<(&[u8], &[u8])> {
+    let Some(line_end) = bytes.windows(CRLF.len()).position(|window| window == CRLF) else {
+        return Err(malformed_chunked_body("Missing CRLF"));
+    };
+
+    Ok((&bytes[..line_end], &bytes[line_end + CRLF.len()..]))
+}
+
+fn parse_chunk_size(line: &[u8]) -> ResponseVerificationResult<usize> {
+    let chunk_size = match line.iter().position(|byte| *byte == b';') {
+        Some(extensions_start) => &line[..extensions_start],
+        None => line,
+    };
+    let chunk_size = std::str::from_utf8(chunk_size)
+        .map_err(|_| malformed_chunked_body("Invalid chunk size"))?
+        .trim_matches(|c| c == ' ' || c == '\t');
+
+    if chunk_size.is_empty() || !chunk_size.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(malformed_chunked_body("Invalid chunk size"));
+    }
+
+    usize::from_str_radix(chunk_size, 16).map_err(|_| malformed_chunked_body("Invalid chunk size"))
+}
+
+fn malformed_chunked_body(message: &str) -> ResponseVerificationError {
+    ResponseVerificationError::MalformedChunkedBody(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{executor::block_on, stream};
+    use rstest::*;
+
+    const MAX_BODY_SIZE: usize = 1_024;
+
+    #[rstest]
+    #[case::single_chunk(b"c\r\nHello World!\r\n0\r\n\r\n", b"Hello World!")]
+    #[case::multiple_chunks(b"6\r\nHello \r\n6\r\nWorld!\r\n0\r\n\r\n", b"Hello World!")]
+    #[case::uppercase_hex(b"C\r\nHello World!\r\n0\r\n\r\n", b"Hello World!")]
+    #[case::chunk_extensions(
+        b"6;name=value\r\nHello \r\n6 ; a\r\nWorld!\r\n0\r\n\r\n",
+        b"Hello World!"
+    )]
+    #[case::trailers(b"c\r\nHello World!\r\n0\r\nExpires: never\r\n\r\n", b"Hello World!")]
+    #[case::crlf_in_chunk(b"4\r\n\r\n\r\n\r\n0\r\n\r\n", b"\r\n\r\n")]
+    #[case::empty_body(b"0\r\n\r\n", b"")]
+    fn decode_chunked_body_with_valid_body(#[case] body: &[u8], #[case] expected_body: &[u8]) {
+        let result = decode_chunked_body(body, MAX_BODY_SIZE).unwrap();
+
+        assert_eq!(result, expected_body);
+    }
+
+    #[rstest]
+    #[case::missing_last_chunk(b"c\r\nHello World!\r\n")]
+    #[case::missing_trailer_end(b"c\r\nHello World!\r\n0\r\n")]
+    #[case::short_chunk(b"d\r\nHello World!\r\n0\r\n\r\n")]
+    #[case::long_chunk(b"b\r\nHello World!\r\n0\r\n\r\n")]
+    #[case::invalid_chunk_size(b"z\r\nHello World!\r\n0\r\n\r\n")]
+    #[case::signed_chunk_size(b"+c\r\nHello World!\r\n0\r\n\r\n")]
+    #[case::empty_chunk_size(b"\r\nHello World!\r\n0\r\n\r\n")]
+    #[case::overflowing_chunk_size(b"fffffffffffffffffffff\r\nHello World!\r\n0\r\n\r\n")]
+    #[case::trailing_data(b"c\r\nHello World!\r\n0\r\n\r\nHello")]
+    fn decode_chunked_body_with_malformed_body(#[case] body: &[u8]) {
+        let result = decode_chunked_body(body, MAX_BODY_SIZE);
+
+        assert!(matches!(
+            result,
+            Err(ResponseVerificationError::MalformedChunkedBody(_))
+        ));
+    }
+
+    #[rstest]
+    fn decode_chunked_body_larger_than_max_body_size() {
+        let result = decode_chunked_body(b"6\r\nHello \r\n6\r\nWorld!\r\n0\r\n\r\n", 8);
+
+        assert!(matches!(
+            result,
+            Err(ResponseVerificationError::ResponseBodyTooLarge { max_body_size: 8 })
+        ));
+    }
+
+    #[rstest]
+    fn collect_body_stream_with_chunks() {
+        let chunks: Vec<Result<&[u8], std::io::Error>> = vec![Ok(b"Hello "), Ok(b"World!")];
+
+        let result = block_on(collect_body_stream(stream::iter(chunks), MAX_BODY_SIZE)).unwrap();
+
+        assert_eq!(result, b"Hello World!");
+    }
+
+    #[rstest]
+    fn collect_body_stream_with_error() {
+        let chunks: Vec<Result<&[u8], std::io::Error>> = vec![
+            Ok(b"Hello "),
+            Err(std::io::ErrorKind::ConnectionReset.into()),
+        ];
+
+        let result = block_on(collect_body_stream(stream::iter(chunks), MAX_BODY_SIZE));
+
+        assert!(matches!(result, Err(ResponseVerificationError::IoError(_))));
+    }
+
+    #[rstest]
+    fn collect_body_stream_larger_than_max_body_size() {
+        let chunks: Vec<Result<&[u8], std::io::Error>> = vec![Ok(b"Hello "), Ok(b"World!")];
+
+        let result = block_on(collect_body_stream(stream::iter(chunks), 8));
+
+        assert!(matches!(
+            result,
+            Err(ResponseVerificationError::ResponseBodyTooLarge { max_body_size: 8 })
+        ));
+    }
+}
@@ -0,0 +1,107 @@
+use super::verify_request_response_pair;
+use crate::{
+    error::ResponseVerificationResult,
+    types::{ForwardedResponse, VerifiedResponse},
+};
+use ic_http_certification::{
+    HttpRequest, HttpResponse, CERTIFICATE_EXPRESSION_HEADER_NAME, CERTIFICATE_HEADER_NAME,
+};
+
+/// Verifies a request and response pair with [verify_request_response_pair], and normalizes the
+/// verified response so that it can be forwarded to a client.
+///
+/// The returned [ForwardedResponse] separates the headers that are included in the certification
+/// from those that are not, so gateways do not need to filter the headers of the response
+/// themselves. Certified header names are lowercased.
+pub fn verify_request_response_pair_for_forwarding(
+    request: HttpRequest,
+    response: HttpResponse,
+    canister_id: &[u8],
+    current_time_ns: u128,
+    max_cert_time_offset_ns: u128,
+    ic_public_key: &[u8],
+    min_requested_verification_version: u8,
+) -> ResponseVerificationResult<ForwardedResponse> {
+    let response_headers = response.headers.clone();
+
+    let verification_info = verify_request_response_pair(
+        request,
+        response,
+        canister_id,
+        current_time_ns,
+        max_cert_time_offset_ns,
+        ic_public_key,
+        min_requested_verification_version,
+    )?;
+
+    let uncertified_headers = response_headers
+        .into_iter()
+        .filter(|(name, _)| {
+            !is_certification_header(name)
+                && !is_certified_header(name, verification_info.response.as_ref())
+        })
+        .collect();
+
+    Ok(ForwardedResponse {
+        response: verification_info.response,
+        uncertified_headers,
+        verification_version: verification_info.verification_version,
+    })
+}
+
+pub(crate) fn is_certification_header(name: &str) -> bool {
+    name.eq_ignore_ascii_case(CERTIFICATE_HEADER_NAME)
+        || name.eq_ignore_ascii_case(CERTIFICATE_EXPRESSION_HEADER_NAME)
+}
+
+pub(crate) fn is_certified_header(
+    name: &str,
+    verified_response: Option<&VerifiedResponse>,
+) -> bool {
+    verified_response.is_some_and(|verified_response| {
+        verified_response
+            .headers
+            .iter()
+            .any(|(certified_name, _)| certified_name.eq_ignore_ascii_case(name))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case::certificate("IC-Certificate", true)]
+    #[case::certificate_lowercase("ic-certificate", true)]
+    #[case::certificate_expression("IC-CertificateExpression", true)]
+    #[case::other("Cache-Control", false)]
+    fn is_certification_header_matches_certification_headers(
+        #[case] name: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(is_certification_header(name), expected);
+    }
+
+    #[rstest]
+    #[case::certified("Cache-Control", true)]
+    #[case::certified_lowercase("cache-control", true)]
+    #[case::uncertified("Set-Cookie", false)]
+    fn is_certified_header_matches_verified_headers(#[case] name: &str, #[case] expected: bool) {
+        let verified_response = VerifiedResponse {
+            status_code: Some(200),
+            headers: vec![("cache-control".into(), "max-age=604800".into())],
+            body: vec![],
+        };
+
+        assert_eq!(
+            is_certified_header(name, Some(&verified_response)),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn is_certified_header_without_verified_response() {
+        assert!(!is_certified_header("Cache-Control", None));
+    }
+}
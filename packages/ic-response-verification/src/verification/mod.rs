@@ -1,8 +1,36 @@
 //! The primary entry point for the repsonse verification API.
 
 mod body;
+pub use body::MAX_DECODED_BODY_SIZE;
 mod certificate_header;
 mod certificate_header_field;
 
+mod verification_profile;
+pub use verification_profile::*;
+
 mod verify_request_response_pair;
 pub use verify_request_response_pair::*;
+
+mod verify_request_response_headers;
+pub use verify_request_response_headers::*;
+
+mod verify_certification_absence;
+pub use verify_certification_absence::*;
+
+mod chunked_body;
+pub use chunked_body::*;
+
+mod verification_report;
+pub use verification_report::*;
+
+mod verification_cache;
+pub use verification_cache::*;
+
+mod verification_warnings;
+pub use verification_warnings::*;
+
+mod forwarded_response;
+pub use forwarded_response::*;
+
+mod capabilities;
+pub use capabilities::*;
@@ -0,0 +1,260 @@
+use super::{hash_request, hash_response, verify_request_response_pair_with_freshness_check};
+use crate::{
+    error::ResponseVerificationResult,
+    types::{VerificationInfo, VerifiedResponse},
+};
+use cached::{Cached, SizedCache};
+use ic_certification::hash_tree::Hash;
+use ic_http_certification::{HttpRequest, HttpResponse};
+use sha2::{Digest, Sha256};
+
+/// An optional cache layer that memoizes successful verifications for a bounded time.
+///
+/// Verifications are keyed by the `IC-Certificate` header (which contains the certificate, tree
+/// and expression path), the full request and response, and the remaining verification
+/// parameters, so a cached verification is only reused for an identical request and response.
+/// Identical requests that are repeatedly served through a gateway can then skip decoding,
+/// tree walks and certification hashing.
+///
+/// Failed verifications are never cached. A cached verification expires after the configured
+/// time to live, or once the certificate would no longer pass the certificate time check,
+/// whichever is earlier. Time is measured with the `current_time_ns` that is passed to
+/// [verify_request_response_pair](VerificationCache::verify_request_response_pair), so the cache
+/// can also be used in environments without a system clock.
+#[derive(Debug)]
+pub struct VerificationCache {
+    cache: parking_lot::Mutex<SizedCache<Hash, CachedVerification>>,
+    ttl_ns: u128,
+}
+
+#[derive(Debug, Clone)]
+struct CachedVerification {
+    response: Option<CachedResponse>,
+    verification_version: u16,
+    cached_at_ns: u128,
+    expires_at_ns: u128,
+}
+
+#[derive(Debug, Clone)]
+struct CachedResponse {
+    status_code: Option<u16>,
+    headers: Vec<(String, String)>,
+}
+
+impl VerificationCache {
+    /// Creates a new cache that holds at most `max_size` verifications, for at most `ttl_ns`
+    /// nanoseconds each.
+    pub fn new(max_size: usize, ttl_ns: u128) -> Self {
+        Self {
+            cache: parking_lot::Mutex::new(SizedCache::with_size(max_size)),
+            ttl_ns,
+        }
+    }
+
+    /// Verifies a request and response pair in the same way as
+    /// [verify_request_response_pair](crate::verify_request_response_pair), reusing a previous
+    /// successful verification of the same request and response if one is cached.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_request_response_pair(
+        &self,
+        request: HttpRequest,
+        response: HttpResponse,
+        canister_id: &[u8],
+        current_time_ns: u128,
+        max_cert_time_offset_ns: u128,
+        ic_public_key: &[u8],
+        min_requested_verification_version: u8,
+    ) -> ResponseVerificationResult<VerificationInfo> {
+        let key = cache_key(
+            &request,
+            &response,
+            canister_id,
+            max_cert_time_offset_ns,
+            ic_public_key,
+            min_requested_verification_version,
+        );
+
+        if let Some(cached_verification) = self.get(&key, current_time_ns) {
+            return Ok(VerificationInfo {
+                response: cached_verification
+                    .response
+                    .map(|cached_response| VerifiedResponse {
+                        status_code: cached_response.status_code,
+                        headers: cached_response.headers,
+                        body: response.body,
+                    }),
+                verification_version: cached_verification.verification_version,
+            });
+        }
+
+        let mut certificate_time_ns = None;
+        let verification_info = verify_request_response_pair_with_freshness_check(
+            request,
+            response,
+            canister_id,
+            current_time_ns,
+            max_cert_time_offset_ns,
+            ic_public_key,
+            min_requested_verification_version,
+            |certificate_info| {
+                certificate_time_ns = Some(certificate_info.certificate_time_ns);
+                Ok(())
+            },
+        )?;
+
+        if let Some(certificate_time_ns) = certificate_time_ns {
+            self.insert(
+                key,
+                CachedVerification {
+                    response: verification_info
+                        .response
+                        .as_ref()
+                        .map(|response| CachedResponse {
+                            status_code: response.status_code,
+                            headers: response.headers.clone(),
+                        }),
+                    verification_version: verification_info.verification_version,
+                    cached_at_ns: current_time_ns,
+                    expires_at_ns: (current_time_ns + self.ttl_ns)
+                        .min(certificate_time_ns + max_cert_time_offset_ns),
+                },
+            );
+        }
+
+        Ok(verification_info)
+    }
+
+    /// Removes all cached verifications.
+    pub fn clear(&self) {
+        self.cache.lock().cache_clear();
+    }
+
+    /// Returns the number of cached verifications, including expired verifications that have not
+    /// been evicted yet.
+    pub fn len(&self) -> usize {
+        self.cache.lock().cache_size()
+    }
+
+    /// Returns true if there are no cached verifications.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, key: &Hash, current_time_ns: u128) -> Option<CachedVerification> {
+        let mut cache = self.cache.lock();
+        let cached_verification = cache.cache_get(key)?.clone();
+
+        if current_time_ns < cached_verification.cached_at_ns
+            || current_time_ns >= cached_verification.expires_at_ns
+        {
+            cache.cache_remove(key);
+            return None;
+        }
+
+        Some(cached_verification)
+    }
+
+    fn insert(&self, key: Hash, cached_verification: CachedVerification) {
+        self.cache.lock().cache_set(key, cached_verification);
+    }
+}
+
+fn cache_key(
+    request: &HttpRequest,
+    response: &HttpResponse,
+    canister_id: &[u8],
+    max_cert_time_offset_ns: u128,
+    ic_public_key: &[u8],
+    min_requested_verification_version: u8,
+) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update(hash_request(request));
+    hasher.update(hash_response(response));
+    hasher.update(Sha256::digest(canister_id));
+    hasher.update(Sha256::digest(ic_public_key));
+    hasher.update(max_cert_time_offset_ns.to_be_bytes());
+    hasher.update([min_requested_verification_version]);
+
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    const TTL_NS: u128 = 60_000_000_000;
+
+    fn cached_verification(cached_at_ns: u128, expires_at_ns: u128) -> CachedVerification {
+        CachedVerification {
+            response: Some(CachedResponse {
+                status_code: Some(200),
+                headers: vec![],
+            }),
+            verification_version: 2,
+            cached_at_ns,
+            expires_at_ns,
+        }
+    }
+
+    #[rstest]
+    #[case::at_cache_time(1_000, true)]
+    #[case::before_expiry(1_999, true)]
+    #[case::at_expiry(2_000, false)]
+    #[case::before_cache_time(999, false)]
+    fn get_respects_cache_lifetime(#[case] current_time_ns: u128, #[case] expected_hit: bool) {
+        let cache = VerificationCache::new(10, TTL_NS);
+        cache.insert([1; 32], cached_verification(1_000, 2_000));
+
+        let result = cache.get(&[1; 32], current_time_ns);
+
+        assert_eq!(result.is_some(), expected_hit);
+        assert_eq!(cache.len(), usize::from(expected_hit));
+    }
+
+    #[rstest]
+    fn insert_evicts_least_recently_used_verification() {
+        let cache = VerificationCache::new(1, TTL_NS);
+        cache.insert([1; 32], cached_verification(1_000, 2_000));
+        cache.insert([2; 32], cached_verification(1_000, 2_000));
+
+        assert!(cache.get(&[1; 32], 1_000).is_none());
+        assert!(cache.get(&[2; 32], 1_000).is_some());
+    }
+
+    #[rstest]
+    fn clear_removes_all_verifications() {
+        let cache = VerificationCache::new(10, TTL_NS);
+        cache.insert([1; 32], cached_verification(1_000, 2_000));
+
+        cache.clear();
+
+        assert!(cache.is_empty());
+    }
+
+    #[rstest]
+    fn cache_key_changes_with_response() {
+        let request = HttpRequest {
+            method: "GET".into(),
+            url: "/".into(),
+            headers: vec![],
+            body: vec![],
+        };
+        let response = HttpResponse {
+            status_code: 200,
+            headers: vec![],
+            body: b"Hello World!".to_vec(),
+            upgrade: None,
+            trailers: None,
+        };
+        let modified_response = HttpResponse {
+            body: b"Hello IC!".to_vec(),
+            ..response.clone()
+        };
+
+        assert_ne!(
+            cache_key(&request, &response, &[1], 0, &[2], 2),
+            cache_key(&request, &modified_response, &[1], 0, &[2], 2)
+        );
+    }
+}
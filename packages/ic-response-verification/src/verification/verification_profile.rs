@@ -0,0 +1,86 @@
+use ic_certificate_verification::DEFAULT_MAX_DELEGATION_CHAIN_LENGTH;
+
+/// Presets for the tolerances that are applied during response verification.
+///
+/// Rather than tuning each tolerance individually, integrators can pick the posture that fits
+/// their deployment and pass it to
+/// [verify_request_response_pair_with_profile](super::verify_request_response_pair_with_profile)
+/// or [verify_request_response_headers_with_profile](super::verify_request_response_headers_with_profile).
+///
+/// | Tolerance                               | [Strict](VerificationProfile::Strict) | [Lenient](VerificationProfile::Lenient) |
+/// | --------------------------------------- | ------------------------------------- | --------------------------------------- |
+/// | Maximum certificate time offset         | 1 minute                              | 5 minutes                               |
+/// | Minimum verification version            | 2                                     | 1                                       |
+/// | Unpadded or non-canonical base64        | Rejected                              | Accepted                                |
+/// | Duplicate certification headers/fields  | Rejected                              | First field, last header is used        |
+/// | Body with a 1xx, 204, 205 or 304 status | Rejected                              | Accepted                                |
+/// | Maximum certificate delegation chain    | 1 delegation                          | 1 delegation                            |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationProfile {
+    /// Only accepts responses that are certified with the current verification version and
+    /// strictly follow the HTTP Gateway Protocol. Recommended for new deployments, where both the
+    /// canister and the client are under the integrator's control.
+    Strict,
+
+    /// Accepts responses from older canisters and boundary nodes that rely on legacy v1
+    /// verification, or that encode the certificate header loosely. This matches the behavior of
+    /// [verify_request_response_pair](super::verify_request_response_pair) with the recommended
+    /// maximum certificate time offset.
+    Lenient,
+}
+
+impl VerificationProfile {
+    /// The maximum offset, in nanoseconds, between the certificate time and the current time.
+    pub const fn max_cert_time_offset_ns(&self) -> u128 {
+        match self {
+            VerificationProfile::Strict => 60_000_000_000,
+            VerificationProfile::Lenient => 300_000_000_000,
+        }
+    }
+
+    /// The minimum verification version that will be accepted. Responses with a lower version,
+    /// such as legacy v1 responses, are rejected.
+    pub const fn min_requested_verification_version(&self) -> u8 {
+        match self {
+            VerificationProfile::Strict => 2,
+            VerificationProfile::Lenient => 1,
+        }
+    }
+
+    /// Whether base64 encoded certificate header fields may omit padding or contain trailing bits.
+    pub const fn allows_lenient_base64(&self) -> bool {
+        match self {
+            VerificationProfile::Strict => false,
+            VerificationProfile::Lenient => true,
+        }
+    }
+
+    /// Whether the `IC-Certificate` and `IC-CertificateExpression` headers, or the fields of the
+    /// `IC-Certificate` header, may be present more than once.
+    pub const fn allows_duplicate_certification_headers(&self) -> bool {
+        match self {
+            VerificationProfile::Strict => false,
+            VerificationProfile::Lenient => true,
+        }
+    }
+
+    /// Whether a response with a `1xx`, `204 No Content`, `205 Reset Content` or
+    /// `304 Not Modified` status code may have a body. HTTP does not permit a body for these status
+    /// codes, so canisters cannot certify one, see
+    /// [status_code_permits_body](ic_http_certification::status_code_permits_body).
+    pub const fn allows_body_for_no_body_status_codes(&self) -> bool {
+        match self {
+            VerificationProfile::Strict => false,
+            VerificationProfile::Lenient => true,
+        }
+    }
+
+    /// The maximum number of delegations that the certificate may have. Longer delegation chains
+    /// are rejected before any of their signatures are verified.
+    pub const fn max_delegation_chain_length(&self) -> usize {
+        match self {
+            VerificationProfile::Strict => DEFAULT_MAX_DELEGATION_CHAIN_LENGTH,
+            VerificationProfile::Lenient => DEFAULT_MAX_DELEGATION_CHAIN_LENGTH,
+        }
+    }
+}
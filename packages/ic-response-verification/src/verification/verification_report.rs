@@ -0,0 +1,217 @@
+use super::{
+    certificate_header::CertificateHeader, verify_request_response_pair, VerificationProfile,
+};
+use crate::{
+    error::ResponseVerificationResult,
+    types::{VerificationInfo, VerificationOutcome, VerificationReport},
+};
+use ic_certification::hash_tree::Hash;
+use ic_http_certification::{HttpRequest, HttpResponse};
+use ic_representation_independent_hash::{hash, representation_independent_hash, Value};
+
+/// Verifies a request and response pair with [verify_request_response_pair], and produces a
+/// [VerificationReport] of the verification.
+///
+/// The report is produced for failed verifications as well, so that gateways can archive the
+/// outcome of every verification.
+pub fn verify_request_response_pair_with_report(
+    request: HttpRequest,
+    response: HttpResponse,
+    canister_id: &[u8],
+    current_time_ns: u128,
+    max_cert_time_offset_ns: u128,
+    ic_public_key: &[u8],
+    min_requested_verification_version: u8,
+) -> (
+    ResponseVerificationResult<VerificationInfo>,
+    VerificationReport,
+) {
+    let request_hash = hash_request(&request);
+    let response_hash = hash_response(&response);
+    let certificate_hash = hash_certificate(&response);
+
+    let result = verify_request_response_pair(
+        request,
+        response,
+        canister_id,
+        current_time_ns,
+        max_cert_time_offset_ns,
+        ic_public_key,
+        min_requested_verification_version,
+    );
+
+    let outcome = match &result {
+        Ok(verification_info) => VerificationOutcome::Verified {
+            verification_version: verification_info.verification_version,
+        },
+        Err(error) => VerificationOutcome::Failed {
+            error: error.to_string(),
+        },
+    };
+
+    let report = VerificationReport {
+        request_hash,
+        response_hash,
+        certificate_hash,
+        canister_id: canister_id.to_vec(),
+        root_key_hash: hash(ic_public_key),
+        current_time_ns,
+        max_cert_time_offset_ns,
+        min_requested_verification_version,
+        outcome,
+    };
+
+    (result, report)
+}
+
+/// Calculates the hash of a request, as recorded in a [VerificationReport].
+///
+/// Unlike [request_hash](ic_http_certification::request_hash), this hash covers the full request,
+/// including the URL and all headers, in their original order.
+pub fn hash_request(request: &HttpRequest) -> Hash {
+    representation_independent_hash(&[
+        ("method".into(), Value::String(request.method.clone())),
+        ("url".into(), Value::String(request.url.clone())),
+        (
+            "headers".into(),
+            Value::Bytes(hash_headers(&request.headers).to_vec()),
+        ),
+        ("body".into(), Value::Bytes(request.body.clone())),
+    ])
+}
+
+/// Calculates the hash of a response, as recorded in a [VerificationReport].
+///
+/// Unlike [response_hash](ic_http_certification::response_hash), this hash covers the full
+/// response, including all headers, in their original order.
+pub fn hash_response(response: &HttpResponse) -> Hash {
+    representation_independent_hash(&[
+        (
+            "status_code".into(),
+            Value::Number(response.status_code.into()),
+        ),
+        (
+            "headers".into(),
+            Value::Bytes(hash_headers(&response.headers).to_vec()),
+        ),
+        ("body".into(), Value::Bytes(response.body.clone())),
+    ])
+}
+
+fn hash_headers(headers: &[(String, String)]) -> Hash {
+    let header_hashes = headers
+        .iter()
+        .flat_map(|(name, value)| {
+            representation_independent_hash(&[
+                ("name".into(), Value::String(name.clone())),
+                ("value".into(), Value::String(value.clone())),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    hash(&header_hashes)
+}
+
+fn hash_certificate(response: &HttpResponse) -> Option<Hash> {
+    let certificate_header = response
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("ic-certificate"))?;
+
+    CertificateHeader::from(&certificate_header.1, &VerificationProfile::Lenient)
+        .ok()?
+        .certificate
+        .map(|certificate| hash(&certificate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[fixture]
+    fn request() -> HttpRequest {
+        HttpRequest {
+            method: "GET".into(),
+            url: "/".into(),
+            headers: vec![("Accept".into(), "text/html".into())],
+            body: vec![],
+        }
+    }
+
+    #[fixture]
+    fn response() -> HttpResponse {
+        HttpResponse {
+            status_code: 200,
+            headers: vec![("Cache-Control".into(), "max-age=604800".into())],
+            body: b"Hello World!".to_vec(),
+            upgrade: None,
+            trailers: None,
+        }
+    }
+
+    #[rstest]
+    fn verify_request_response_pair_with_report_without_certificate(
+        request: HttpRequest,
+        response: HttpResponse,
+    ) {
+        let canister_id = [0, 0, 0, 0, 0, 0, 0, 1];
+        let root_key = [1, 2, 3];
+        let request_hash = hash_request(&request);
+        let response_hash = hash_response(&response);
+
+        let (result, report) = verify_request_response_pair_with_report(
+            request,
+            response,
+            &canister_id,
+            1_700_000_000_000_000_000,
+            300_000_000_000,
+            &root_key,
+            2,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            report,
+            VerificationReport {
+                request_hash,
+                response_hash,
+                certificate_hash: None,
+                canister_id: canister_id.to_vec(),
+                root_key_hash: hash(&root_key),
+                current_time_ns: 1_700_000_000_000_000_000,
+                max_cert_time_offset_ns: 300_000_000_000,
+                min_requested_verification_version: 2,
+                outcome: VerificationOutcome::Failed {
+                    error: "Certification values not found".into(),
+                },
+            }
+        );
+    }
+
+    #[rstest]
+    fn hash_request_changes_with_header_order(request: HttpRequest) {
+        let mut reordered_request = request.clone();
+        reordered_request
+            .headers
+            .insert(0, ("Accept-Encoding".into(), "gzip".into()));
+        let mut modified_request = request.clone();
+        modified_request
+            .headers
+            .push(("Accept-Encoding".into(), "gzip".into()));
+
+        assert_ne!(
+            hash_request(&reordered_request),
+            hash_request(&modified_request)
+        );
+        assert_ne!(hash_request(&request), hash_request(&modified_request));
+    }
+
+    #[rstest]
+    fn hash_response_changes_with_body(response: HttpResponse) {
+        let mut modified_response = response.clone();
+        modified_response.body = b"Hello IC!".to_vec();
+
+        assert_ne!(hash_response(&response), hash_response(&modified_response));
+    }
+}
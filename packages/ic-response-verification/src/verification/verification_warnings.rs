@@ -0,0 +1,234 @@
+use super::{
+    is_certification_header, is_certified_header, verify_request_response_pair_with_freshness_check,
+};
+use crate::{
+    error::ResponseVerificationResult,
+    types::{VerificationInfo, VerificationWarning},
+};
+use ic_http_certification::{HttpRequest, HttpResponse};
+
+/// Warnings about the certificate age are raised once less than this fraction of the maximum
+/// certificate time offset remains.
+const STALENESS_WARNING_FRACTION: u128 = 5;
+
+/// Verifies a request and response pair with the same checks as
+/// [verify_request_response_pair](super::verify_request_response_pair), and also returns a list of
+/// [VerificationWarning]s for findings that do not fail verification.
+///
+/// A certificate is considered to be nearing staleness once less than a fifth of the maximum
+/// certificate time offset remains before it would be rejected. Warnings are only returned for
+/// responses that pass verification.
+pub fn verify_request_response_pair_with_warnings(
+    request: HttpRequest,
+    response: HttpResponse,
+    canister_id: &[u8],
+    current_time_ns: u128,
+    max_cert_time_offset_ns: u128,
+    ic_public_key: &[u8],
+    min_requested_verification_version: u8,
+) -> (
+    ResponseVerificationResult<VerificationInfo>,
+    Vec<VerificationWarning>,
+) {
+    let response_header_names = response
+        .headers
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+
+    let mut certificate_time_ns = None;
+    let result = verify_request_response_pair_with_freshness_check(
+        request,
+        response,
+        canister_id,
+        current_time_ns,
+        max_cert_time_offset_ns,
+        ic_public_key,
+        min_requested_verification_version,
+        |certificate_info| {
+            certificate_time_ns = Some(certificate_info.certificate_time_ns);
+            Ok(())
+        },
+    );
+
+    let warnings = match (&result, certificate_time_ns) {
+        (Ok(verification_info), Some(certificate_time_ns)) => collect_warnings(
+            verification_info,
+            &response_header_names,
+            certificate_time_ns,
+            current_time_ns,
+            max_cert_time_offset_ns,
+        ),
+        _ => Vec::new(),
+    };
+
+    (result, warnings)
+}
+
+fn collect_warnings(
+    verification_info: &VerificationInfo,
+    response_header_names: &[String],
+    certificate_time_ns: u128,
+    current_time_ns: u128,
+    max_cert_time_offset_ns: u128,
+) -> Vec<VerificationWarning> {
+    let mut warnings = Vec::new();
+
+    let min_certificate_time_ns = current_time_ns.saturating_sub(max_cert_time_offset_ns);
+    let staleness_warning_time_ns =
+        min_certificate_time_ns + max_cert_time_offset_ns / STALENESS_WARNING_FRACTION;
+    if certificate_time_ns < staleness_warning_time_ns {
+        warnings.push(VerificationWarning::CertificateNearingStaleness {
+            certificate_time_ns,
+            min_certificate_time_ns,
+        });
+    }
+
+    if verification_info.verification_version < 2 {
+        warnings.push(VerificationWarning::LegacyVerificationVersion);
+    }
+
+    let Some(verified_response) = &verification_info.response else {
+        warnings.push(VerificationWarning::CertificationSkipped);
+        return warnings;
+    };
+
+    let uncertified_header_names = response_header_names
+        .iter()
+        .filter(|name| {
+            !is_certification_header(name) && !is_certified_header(name, Some(verified_response))
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    if !uncertified_header_names.is_empty() {
+        warnings.push(VerificationWarning::UncertifiedHeaders {
+            header_names: uncertified_header_names,
+        });
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::VerifiedResponse;
+    use rstest::*;
+
+    const CURRENT_TIME_NS: u128 = 1_700_000_000_000_000_000;
+    const MAX_CERT_TIME_OFFSET_NS: u128 = 300_000_000_000;
+
+    fn verification_info(
+        verification_version: u16,
+        certified_header_names: &[&str],
+    ) -> VerificationInfo {
+        VerificationInfo {
+            response: Some(VerifiedResponse {
+                status_code: Some(200),
+                headers: certified_header_names
+                    .iter()
+                    .map(|name| (name.to_string(), String::new()))
+                    .collect(),
+                body: vec![],
+            }),
+            verification_version,
+        }
+    }
+
+    fn header_names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[rstest]
+    fn collect_warnings_without_warnings() {
+        let warnings = collect_warnings(
+            &verification_info(2, &["cache-control"]),
+            &header_names(&[
+                "IC-Certificate",
+                "IC-CertificateExpression",
+                "Cache-Control",
+            ]),
+            CURRENT_TIME_NS,
+            CURRENT_TIME_NS,
+            MAX_CERT_TIME_OFFSET_NS,
+        );
+
+        assert_eq!(warnings, vec![]);
+    }
+
+    #[rstest]
+    fn collect_warnings_with_stale_certificate() {
+        let certificate_time_ns = CURRENT_TIME_NS - MAX_CERT_TIME_OFFSET_NS + 1;
+
+        let warnings = collect_warnings(
+            &verification_info(2, &[]),
+            &header_names(&[]),
+            certificate_time_ns,
+            CURRENT_TIME_NS,
+            MAX_CERT_TIME_OFFSET_NS,
+        );
+
+        assert_eq!(
+            warnings,
+            vec![VerificationWarning::CertificateNearingStaleness {
+                certificate_time_ns,
+                min_certificate_time_ns: CURRENT_TIME_NS - MAX_CERT_TIME_OFFSET_NS,
+            }]
+        );
+    }
+
+    #[rstest]
+    fn collect_warnings_with_uncertified_headers() {
+        let warnings = collect_warnings(
+            &verification_info(2, &["cache-control"]),
+            &header_names(&["IC-Certificate", "Cache-Control", "Set-Cookie", "X-Custom"]),
+            CURRENT_TIME_NS,
+            CURRENT_TIME_NS,
+            MAX_CERT_TIME_OFFSET_NS,
+        );
+
+        assert_eq!(
+            warnings,
+            vec![VerificationWarning::UncertifiedHeaders {
+                header_names: header_names(&["Set-Cookie", "X-Custom"]),
+            }]
+        );
+    }
+
+    #[rstest]
+    fn collect_warnings_with_v1_verification() {
+        let warnings = collect_warnings(
+            &verification_info(1, &[]),
+            &header_names(&["IC-Certificate", "Content-Type"]),
+            CURRENT_TIME_NS,
+            CURRENT_TIME_NS,
+            MAX_CERT_TIME_OFFSET_NS,
+        );
+
+        assert_eq!(
+            warnings,
+            vec![
+                VerificationWarning::LegacyVerificationVersion,
+                VerificationWarning::UncertifiedHeaders {
+                    header_names: header_names(&["Content-Type"]),
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    fn collect_warnings_with_skipped_certification() {
+        let warnings = collect_warnings(
+            &VerificationInfo {
+                response: None,
+                verification_version: 2,
+            },
+            &header_names(&["IC-Certificate", "Content-Type"]),
+            CURRENT_TIME_NS,
+            CURRENT_TIME_NS,
+            MAX_CERT_TIME_OFFSET_NS,
+        );
+
+        assert_eq!(warnings, vec![VerificationWarning::CertificationSkipped]);
+    }
+}
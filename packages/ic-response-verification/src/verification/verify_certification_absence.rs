@@ -0,0 +1,68 @@
+use super::{certificate_header::CertificateHeader, VerificationProfile};
+use crate::{
+    error::{ResponseVerificationError, ResponseVerificationResult},
+    validation::validate_tree,
+};
+use ic_cbor::{CertificateToCbor, HashTreeToCbor};
+use ic_certificate_verification::{validate_certificate_time, VerifyCertificate};
+use ic_certification::{Certificate, HashTree};
+use ic_http_certification::{is_certification_absent, HttpRequest, HttpResponse};
+
+/// Verifies that a response, such as a "not found" response, proves that the canister has no
+/// certification for the request, neither for its exact path nor for any wildcard path matching
+/// it.
+///
+/// Such responses are created with
+/// [HttpCertificationTree::certify_absence](ic_http_certification::HttpCertificationTree::certify_absence).
+/// The certificate in the `IC-Certificate` header is verified in the same way as for
+/// [verify_request_response_pair](super::verify_request_response_pair), but the response itself
+/// is not certified, so only the fact that the requested URL is not certified should be relied on.
+pub fn verify_certification_absence(
+    request: &HttpRequest,
+    response: &HttpResponse,
+    canister_id: &[u8],
+    current_time_ns: u128,
+    max_cert_time_offset_ns: u128,
+    ic_public_key: &[u8],
+) -> ResponseVerificationResult {
+    let Some((_, certificate_header)) = response
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("ic-certificate"))
+    else {
+        return Err(ResponseVerificationError::MissingCertification);
+    };
+
+    let certificate_header =
+        CertificateHeader::from(certificate_header, &VerificationProfile::Lenient)?;
+
+    let Some(tree) = certificate_header
+        .tree
+        .map(|tree| HashTree::from_cbor(&tree))
+        .transpose()?
+    else {
+        return Err(ResponseVerificationError::MissingTree);
+    };
+
+    let Some(certificate) = certificate_header
+        .certificate
+        .map(|certificate| Certificate::from_cbor(&certificate))
+        .transpose()?
+    else {
+        return Err(ResponseVerificationError::MissingCertificate);
+    };
+
+    validate_certificate_time(&certificate, &current_time_ns, &max_cert_time_offset_ns)?;
+    certificate.verify(canister_id, ic_public_key)?;
+
+    if !validate_tree(canister_id, &certificate, &tree) {
+        return Err(ResponseVerificationError::InvalidTree);
+    }
+
+    let request_path = request.canonical_path()?;
+    if !is_certification_absent(&tree, &request_path) {
+        return Err(ResponseVerificationError::CertificationNotAbsent);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,756 @@
+use super::{
+    body::decode_body, certificate_header::CertificateHeader, VerificationProfile,
+    MAX_VERIFICATION_VERSION, MIN_VERIFICATION_VERSION,
+};
+use crate::{
+    cel::parse_cel_expression,
+    error::{ResponseVerificationError, ResponseVerificationResult},
+    types::{CertificateInfo, VerifiedResponse},
+    validation::{
+        validate_body, validate_expr_hash, validate_expr_path, validate_hashes, validate_tree,
+    },
+};
+use ic_cbor::{parse_cbor_string_array, CertificateToCbor, HashTreeToCbor};
+use ic_certificate_verification::{
+    get_certificate_time, validate_certificate_time, VerifyCertificate,
+    DEFAULT_MAX_DELEGATION_CHAIN_LENGTH,
+};
+use ic_certification::{hash_tree::Hash, Certificate, HashTree};
+use ic_http_certification::{
+    cel::{
+        CelExpression, DefaultBodyCertification, DefaultCelExpression, DefaultFullCelExpression,
+        DefaultResponseOnlyCelExpression,
+    },
+    filter_response_headers, fragmented_body_hash, request_hash,
+    response_headers_hash_with_status_certification, response_trailers_hash, split_fragments,
+    status_code_permits_body, DefaultResponseCertification, HttpCertificationError, HttpRequest,
+    HttpResponse, CERTIFICATE_FRAGMENTS_HEADER_NAME,
+};
+use ic_representation_independent_hash::{hash, Sha256Algorithm};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+type FreshnessCheck<'a> = &'a mut dyn FnMut(&CertificateInfo) -> Result<(), String>;
+
+/// Verifies the certificate, CEL expression and certified headers of a response with respect to
+/// the request, before the response body has been received.
+///
+/// The returned [IncrementalVerification] must be provided with the response body, using
+/// [update](IncrementalVerification::update), and then [finished](IncrementalVerification::finish)
+/// to complete verification. The certified status code and headers are hashed together with the
+/// body, so they should not be considered certified until [finish](IncrementalVerification::finish)
+/// has succeeded.
+///
+/// This allows clients such as service workers to start processing a response while it is still
+/// streaming, but consumers must be prepared to discard the response if the body turns out to be
+/// invalid. The parameters are the same as [verify_request_response_pair](super::verify_request_response_pair),
+/// except for the response body.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_request_response_headers(
+    request: HttpRequest,
+    status_code: u16,
+    response_headers: &[(String, String)],
+    canister_id: &[u8],
+    current_time_ns: u128,
+    max_cert_time_offset_ns: u128,
+    ic_public_key: &[u8],
+    min_requested_verification_version: u8,
+) -> ResponseVerificationResult<IncrementalVerification> {
+    verify_headers(
+        request,
+        status_code,
+        response_headers,
+        canister_id,
+        current_time_ns,
+        max_cert_time_offset_ns,
+        ic_public_key,
+        min_requested_verification_version,
+        DEFAULT_MAX_DELEGATION_CHAIN_LENGTH,
+        &VerificationProfile::Lenient,
+        None,
+    )
+}
+
+/// Verifies the certificate, CEL expression and certified headers of a response with respect to
+/// the request, before the response body has been received, and checks the freshness of the
+/// certificate with the provided callback.
+///
+/// This is the same as [verify_request_response_headers], except that `freshness_check` is called
+/// once the certificate has been verified. See
+/// [verify_request_response_pair_with_freshness_check](super::verify_request_response_pair_with_freshness_check)
+/// for more details.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_request_response_headers_with_freshness_check(
+    request: HttpRequest,
+    status_code: u16,
+    response_headers: &[(String, String)],
+    canister_id: &[u8],
+    current_time_ns: u128,
+    max_cert_time_offset_ns: u128,
+    ic_public_key: &[u8],
+    min_requested_verification_version: u8,
+    mut freshness_check: impl FnMut(&CertificateInfo) -> Result<(), String>,
+) -> ResponseVerificationResult<IncrementalVerification> {
+    verify_headers(
+        request,
+        status_code,
+        response_headers,
+        canister_id,
+        current_time_ns,
+        max_cert_time_offset_ns,
+        ic_public_key,
+        min_requested_verification_version,
+        DEFAULT_MAX_DELEGATION_CHAIN_LENGTH,
+        &VerificationProfile::Lenient,
+        Some(&mut freshness_check),
+    )
+}
+
+/// Verifies the certificate, CEL expression and certified headers of a response with respect to
+/// the request, before the response body has been received, using the tolerances of the given
+/// [VerificationProfile].
+///
+/// This is the same as [verify_request_response_headers], except that the maximum certificate time
+/// offset, the minimum verification version and the maximum delegation chain length are taken from
+/// the profile.
+pub fn verify_request_response_headers_with_profile(
+    request: HttpRequest,
+    status_code: u16,
+    response_headers: &[(String, String)],
+    canister_id: &[u8],
+    current_time_ns: u128,
+    ic_public_key: &[u8],
+    profile: VerificationProfile,
+) -> ResponseVerificationResult<IncrementalVerification> {
+    verify_headers(
+        request,
+        status_code,
+        response_headers,
+        canister_id,
+        current_time_ns,
+        profile.max_cert_time_offset_ns(),
+        ic_public_key,
+        profile.min_requested_verification_version(),
+        profile.max_delegation_chain_length(),
+        &profile,
+        None,
+    )
+}
+
+/// Verifies the certificate, CEL expression and certified headers of a response with respect to
+/// the request, before the response body has been received, allowing the certificate to have at
+/// most `max_delegation_chain_length` delegations.
+///
+/// This is the same as [verify_request_response_headers], which allows at most
+/// [DEFAULT_MAX_DELEGATION_CHAIN_LENGTH] delegations. See
+/// [verify_request_response_pair_with_max_delegation_chain_length](super::verify_request_response_pair_with_max_delegation_chain_length)
+/// for more details.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_request_response_headers_with_max_delegation_chain_length(
+    request: HttpRequest,
+    status_code: u16,
+    response_headers: &[(String, String)],
+    canister_id: &[u8],
+    current_time_ns: u128,
+    max_cert_time_offset_ns: u128,
+    ic_public_key: &[u8],
+    min_requested_verification_version: u8,
+    max_delegation_chain_length: usize,
+) -> ResponseVerificationResult<IncrementalVerification> {
+    verify_headers(
+        request,
+        status_code,
+        response_headers,
+        canister_id,
+        current_time_ns,
+        max_cert_time_offset_ns,
+        ic_public_key,
+        min_requested_verification_version,
+        max_delegation_chain_length,
+        &VerificationProfile::Lenient,
+        None,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn verify_headers(
+    request: HttpRequest,
+    status_code: u16,
+    response_headers: &[(String, String)],
+    canister_id: &[u8],
+    current_time_ns: u128,
+    max_cert_time_offset_ns: u128,
+    ic_public_key: &[u8],
+    min_requested_verification_version: u8,
+    max_delegation_chain_length: usize,
+    profile: &VerificationProfile,
+    freshness_check: Option<FreshnessCheck<'_>>,
+) -> ResponseVerificationResult<IncrementalVerification> {
+    if !profile.allows_duplicate_certification_headers() {
+        for header_name in ["ic-certificate", "ic-certificateexpression"] {
+            let header_count = response_headers
+                .iter()
+                .filter(|(name, _)| name.eq_ignore_ascii_case(header_name))
+                .count();
+
+            if header_count > 1 {
+                return Err(ResponseVerificationError::DuplicateCertificationHeader(
+                    header_name.to_string(),
+                ));
+            }
+        }
+    }
+
+    let headers: HashMap<_, _> = response_headers
+        .iter()
+        .map(|(k, v)| (k.to_lowercase(), v.clone()))
+        .collect();
+
+    let encoding = headers
+        .get("content-encoding")
+        .map(|encoding| encoding.as_str());
+
+    let Some(certificate_header) = headers.get("ic-certificate") else {
+        return Err(ResponseVerificationError::MissingCertification);
+    };
+
+    let certificate_header = CertificateHeader::from(certificate_header, profile)?;
+
+    let Some(tree) = certificate_header
+        .tree
+        .map(|tree| HashTree::from_cbor(&tree))
+        .transpose()?
+    else {
+        return Err(ResponseVerificationError::MissingTree);
+    };
+
+    let Some(certificate) = certificate_header
+        .certificate
+        .map(|certificate| Certificate::from_cbor(&certificate))
+        .transpose()?
+    else {
+        return Err(ResponseVerificationError::MissingCertificate);
+    };
+
+    let version = certificate_header
+        .version
+        .unwrap_or(MIN_VERIFICATION_VERSION);
+
+    match version {
+        version if version < min_requested_verification_version => Err(
+            ResponseVerificationError::RequestedVerificationVersionMismatch {
+                requested_version: version,
+                min_requested_verification_version,
+            },
+        ),
+        1 => v1_verification(
+            V1VerificationOpts {
+                request,
+                canister_id,
+                current_time_ns,
+                max_cert_time_offset_ns,
+                max_delegation_chain_length,
+                tree,
+                certificate,
+                encoding,
+                ic_public_key,
+            },
+            freshness_check,
+        ),
+        2 => match headers.get("ic-certificateexpression") {
+            Some(certificate_expression_header) => {
+                let Some(expr_path) = certificate_header
+                    .expr_path
+                    .map(|expr_path| parse_cbor_string_array(&expr_path))
+                    .transpose()?
+                else {
+                    return Err(ResponseVerificationError::MissingCertificateExpressionPath);
+                };
+
+                let certification = parse_cel_expression(certificate_expression_header)?;
+                let expr_hash = hash(certificate_expression_header.as_bytes());
+
+                v2_verification(
+                    V2VerificationOpts {
+                        request,
+                        status_code,
+                        response_headers,
+                        canister_id,
+                        current_time_ns,
+                        max_cert_time_offset_ns,
+                        max_delegation_chain_length,
+                        tree,
+                        certificate,
+                        expr_path,
+                        expr_hash,
+                        certificate_expression: certificate_expression_header,
+                        certification,
+                        encoding,
+                        ic_public_key,
+                        allows_body_for_no_body_status_codes: profile
+                            .allows_body_for_no_body_status_codes(),
+                    },
+                    freshness_check,
+                )
+            }
+            None => Err(ResponseVerificationError::MissingCertification),
+        },
+        _ => Err(ResponseVerificationError::UnsupportedVerificationVersion {
+            min_supported_version: MIN_VERIFICATION_VERSION,
+            max_supported_version: MAX_VERIFICATION_VERSION,
+            requested_version: version,
+        }),
+    }
+}
+
+/// A response whose certificate, CEL expression and certified headers have been verified, but
+/// whose body has not been verified yet.
+///
+/// Created by [verify_request_response_headers].
+#[derive(Debug)]
+pub struct IncrementalVerification {
+    /// Response object including the status code and headers that were included in the
+    /// certification. The body of this object is always empty. If the response was not certified
+    /// then this object will be empty.
+    ///
+    /// The status code and headers are only certified once [finish](IncrementalVerification::finish)
+    /// has succeeded.
+    pub response: Option<VerifiedResponse>,
+    /// The version of verification that was used to verify the response
+    pub verification_version: u16,
+    body_verification: BodyVerification,
+}
+
+#[derive(Debug)]
+enum BodyVerification {
+    Skip,
+    V1 {
+        tree: HashTree,
+        request_path: String,
+        encoding: Option<String>,
+        body_hasher: Sha256,
+        encoded_body: Vec<u8>,
+    },
+    V2 {
+        tree: HashTree,
+        expr_path: Vec<String>,
+        expr_hash: Hash,
+        certificate_expression: String,
+        request_hash: Option<Hash>,
+        response_headers_hash: Hash,
+        status_code: u16,
+        has_body: bool,
+        allows_body_for_no_body_status_codes: bool,
+        encoding: Option<String>,
+        fragments: Option<String>,
+        body_hasher: Sha256,
+        encoded_body: Vec<u8>,
+    },
+}
+
+impl IncrementalVerification {
+    /// Provides the next chunk of the response body.
+    pub fn update(&mut self, chunk: &[u8]) {
+        match &mut self.body_verification {
+            BodyVerification::Skip => {}
+            BodyVerification::V1 {
+                encoding,
+                body_hasher,
+                encoded_body,
+                ..
+            } => {
+                body_hasher.update(chunk);
+
+                // encoded bodies need to be decoded in full before they can be hashed
+                if encoding.is_some() {
+                    encoded_body.extend_from_slice(chunk);
+                }
+            }
+            BodyVerification::V2 {
+                has_body,
+                encoding,
+                fragments,
+                body_hasher,
+                encoded_body,
+                ..
+            } => {
+                *has_body |= !chunk.is_empty();
+
+                match (encoding, fragments) {
+                    // encoded and fragmented bodies need to be received in full before they can be
+                    // hashed
+                    (None, None) => body_hasher.update(chunk),
+                    _ => encoded_body.extend_from_slice(chunk),
+                }
+            }
+        }
+    }
+
+    /// Completes verification once the full response body has been provided with
+    /// [update](IncrementalVerification::update).
+    ///
+    /// Use [finish_with_trailers](IncrementalVerification::finish_with_trailers) if the response
+    /// has trailers, since certified trailers are included in the response hash.
+    pub fn finish(self) -> ResponseVerificationResult<()> {
+        self.finish_with_trailers(&[])
+    }
+
+    /// Completes verification once the full response body has been provided with
+    /// [update](IncrementalVerification::update) and the response trailers have been received.
+    ///
+    /// Trailers are only verified for version 2 certification, where they are certified with the
+    /// same rules as headers, see [response_hash](ic_http_certification::response_hash).
+    pub fn finish_with_trailers(
+        self,
+        trailers: &[(String, String)],
+    ) -> ResponseVerificationResult<()> {
+        match self.body_verification {
+            BodyVerification::Skip => Ok(()),
+            BodyVerification::V1 {
+                tree,
+                request_path,
+                encoding,
+                body_hasher,
+                encoded_body,
+            } => {
+                let body_sha: Hash = body_hasher.finalize().into();
+
+                let mut valid_body = match encoding.as_deref() {
+                    Some(encoding) => {
+                        let decoded_body = decode_body(&encoded_body, Some(encoding))?;
+                        validate_body(&tree, &request_path, &hash(decoded_body.as_slice()))
+                    }
+                    None => false,
+                };
+                if !valid_body {
+                    valid_body = validate_body(&tree, &request_path, &body_sha);
+                }
+
+                match valid_body {
+                    true => Ok(()),
+                    false => Err(ResponseVerificationError::InvalidResponseBody),
+                }
+            }
+            BodyVerification::V2 {
+                tree,
+                expr_path,
+                expr_hash,
+                certificate_expression,
+                request_hash,
+                response_headers_hash,
+                status_code,
+                has_body,
+                allows_body_for_no_body_status_codes,
+                encoding,
+                fragments,
+                body_hasher,
+                encoded_body,
+            } => {
+                // mirrors the check applied when the response is certified, see
+                // [status_code_permits_body](ic_http_certification::status_code_permits_body)
+                if has_body
+                    && !allows_body_for_no_body_status_codes
+                    && !status_code_permits_body(status_code)
+                {
+                    return Err(
+                        HttpCertificationError::ResponseBodyNotPermitted { status_code }.into(),
+                    );
+                }
+
+                let certification = parse_cel_expression(&certificate_expression)?;
+                let body_hash: Hash = match (encoding.as_deref(), fragments.as_deref()) {
+                    (_, Some(fragments)) => {
+                        let fragment_hashes: Vec<Hash> = split_fragments(&encoded_body, fragments)?
+                            .into_iter()
+                            .map(hash)
+                            .collect();
+
+                        fragmented_body_hash(&fragment_hashes)
+                    }
+                    (Some(encoding), None) => {
+                        hash(decode_body(&encoded_body, Some(encoding))?.as_slice())
+                    }
+                    (None, None) => body_hasher.finalize().into(),
+                };
+                let mut concatenated_hashes = [response_headers_hash, body_hash].concat();
+                if let Some(trailers_hash) = response_certification(&certification)
+                    .and_then(|certification| response_trailers_hash(trailers, certification))
+                {
+                    concatenated_hashes.extend_from_slice(&trailers_hash);
+                }
+                let response_hash = hash(concatenated_hashes.as_slice());
+
+                let are_hashes_valid = validate_hashes(
+                    &expr_hash,
+                    &request_hash,
+                    &response_hash,
+                    &expr_path,
+                    &tree,
+                    &certification,
+                );
+
+                match are_hashes_valid {
+                    true => Ok(()),
+                    false => Err(ResponseVerificationError::InvalidResponseHashes),
+                }
+            }
+        }
+    }
+}
+
+fn response_certification<'a>(
+    certification: &'a CelExpression<'a>,
+) -> Option<&'a DefaultResponseCertification<'a>> {
+    match certification {
+        CelExpression::Default(DefaultCelExpression::Skip) => None,
+        CelExpression::Default(DefaultCelExpression::ResponseOnly(
+            DefaultResponseOnlyCelExpression { response, .. },
+        ))
+        | CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
+            response,
+            ..
+        })) => Some(response),
+    }
+}
+
+struct V1VerificationOpts<'a> {
+    request: HttpRequest,
+    canister_id: &'a [u8],
+    current_time_ns: u128,
+    max_cert_time_offset_ns: u128,
+    max_delegation_chain_length: usize,
+    tree: HashTree,
+    certificate: Certificate,
+    encoding: Option<&'a str>,
+    ic_public_key: &'a [u8],
+}
+
+fn v1_verification(
+    V1VerificationOpts {
+        request,
+        canister_id,
+        current_time_ns,
+        max_cert_time_offset_ns,
+        max_delegation_chain_length,
+        tree,
+        certificate,
+        encoding,
+        ic_public_key,
+    }: V1VerificationOpts<'_>,
+    freshness_check: Option<FreshnessCheck<'_>>,
+) -> ResponseVerificationResult<IncrementalVerification> {
+    validate_certificate_time(&certificate, &current_time_ns, &max_cert_time_offset_ns)?;
+    certificate.verify_with_max_delegation_chain_length(
+        canister_id,
+        ic_public_key,
+        max_delegation_chain_length,
+    )?;
+
+    let request_path = request.get_path()?;
+
+    if !validate_tree(canister_id, &certificate, &tree) {
+        return Err(ResponseVerificationError::InvalidTree);
+    }
+
+    check_freshness(&certificate, &tree, freshness_check)?;
+
+    Ok(IncrementalVerification {
+        response: Some(VerifiedResponse {
+            status_code: None,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }),
+        verification_version: 1,
+        body_verification: BodyVerification::V1 {
+            tree,
+            request_path,
+            encoding: encoding.map(String::from),
+            body_hasher: Sha256::new(),
+            encoded_body: Vec::new(),
+        },
+    })
+}
+
+struct V2VerificationOpts<'a> {
+    request: HttpRequest,
+    status_code: u16,
+    response_headers: &'a [(String, String)],
+    canister_id: &'a [u8],
+    current_time_ns: u128,
+    max_cert_time_offset_ns: u128,
+    max_delegation_chain_length: usize,
+    tree: HashTree,
+    certificate: Certificate,
+    expr_path: Vec<String>,
+    expr_hash: Hash,
+    certificate_expression: &'a str,
+    certification: CelExpression<'a>,
+    encoding: Option<&'a str>,
+    ic_public_key: &'a [u8],
+    allows_body_for_no_body_status_codes: bool,
+}
+
+fn v2_verification(
+    V2VerificationOpts {
+        request,
+        status_code,
+        response_headers,
+        canister_id,
+        current_time_ns,
+        max_cert_time_offset_ns,
+        max_delegation_chain_length,
+        tree,
+        certificate,
+        expr_path,
+        expr_hash,
+        certificate_expression,
+        certification,
+        encoding,
+        ic_public_key,
+        allows_body_for_no_body_status_codes,
+    }: V2VerificationOpts<'_>,
+    freshness_check: Option<FreshnessCheck<'_>>,
+) -> ResponseVerificationResult<IncrementalVerification> {
+    // the canister derives the expr_path from the same normalized path
+    let request_path = request.canonical_path()?;
+
+    validate_certificate_time(&certificate, &current_time_ns, &max_cert_time_offset_ns)?;
+    certificate.verify_with_max_delegation_chain_length(
+        canister_id,
+        ic_public_key,
+        max_delegation_chain_length,
+    )?;
+
+    if !validate_tree(canister_id, &certificate, &tree) {
+        return Err(ResponseVerificationError::InvalidTree);
+    }
+
+    check_freshness(&certificate, &tree, freshness_check)?;
+
+    if !validate_expr_path(&expr_path, &request_path, &tree) {
+        return Err(ResponseVerificationError::InvalidExpressionPath);
+    }
+
+    let (
+        request_certification,
+        response_certification,
+        body_certification,
+        status_certification,
+        allowed_methods,
+    ) = match &certification {
+        CelExpression::Default(DefaultCelExpression::Skip) => {
+            return match validate_expr_hash(&expr_path, &expr_hash, &tree).is_some() {
+                true => Ok(IncrementalVerification {
+                    response: None,
+                    verification_version: 2,
+                    body_verification: BodyVerification::Skip,
+                }),
+                false => Err(ResponseVerificationError::InvalidExpressionPath),
+            };
+        }
+        CelExpression::Default(DefaultCelExpression::ResponseOnly(
+            DefaultResponseOnlyCelExpression {
+                response,
+                body,
+                status,
+                allowed_methods,
+            },
+        )) => (None, response, body, status, allowed_methods),
+        CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
+            request,
+            response,
+            body,
+            status,
+            allowed_methods,
+        })) => (Some(request), response, body, status, allowed_methods),
+    };
+
+    let request_hash = request_certification
+        .as_ref()
+        .map(|request_certification| request_hash(&request, request_certification))
+        .transpose()?;
+
+    if validate_expr_hash(&expr_path, &expr_hash, &tree).is_none() {
+        return Err(ResponseVerificationError::InvalidResponseHashes);
+    }
+
+    if !certification.allows_method(&request.method) {
+        return Err(HttpCertificationError::UnexpectedRequestMethod {
+            expected: allowed_methods.join(", "),
+            found: request.method.clone(),
+        }
+        .into());
+    }
+
+    let response = HttpResponse {
+        status_code,
+        headers: response_headers.to_vec(),
+        body: Vec::new(),
+        upgrade: None,
+        trailers: None,
+    };
+    let response_headers = filter_response_headers(&response, response_certification);
+    let response_headers_hash = response_headers_hash_with_status_certification::<Sha256Algorithm>(
+        &status_code.into(),
+        &response_headers,
+        status_certification,
+    );
+
+    let fragments = match body_certification {
+        DefaultBodyCertification::Fragmented => Some(
+            response
+                .headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(CERTIFICATE_FRAGMENTS_HEADER_NAME))
+                .map(|(_, value)| value.clone())
+                .ok_or_else(|| {
+                    HttpCertificationError::MalformedFragmentsHeader("the header is missing".into())
+                })?,
+        ),
+        DefaultBodyCertification::AsServed | DefaultBodyCertification::Decoded => None,
+    };
+
+    Ok(IncrementalVerification {
+        response: Some(VerifiedResponse {
+            status_code: Some(status_code),
+            headers: response_headers.headers,
+            body: Vec::new(),
+        }),
+        verification_version: 2,
+        body_verification: BodyVerification::V2 {
+            tree,
+            expr_path,
+            expr_hash,
+            certificate_expression: certificate_expression.to_string(),
+            request_hash,
+            response_headers_hash,
+            status_code,
+            has_body: false,
+            allows_body_for_no_body_status_codes,
+            encoding: match body_certification {
+                DefaultBodyCertification::Decoded => encoding.map(String::from),
+                DefaultBodyCertification::AsServed | DefaultBodyCertification::Fragmented => None,
+            },
+            fragments,
+            body_hasher: Sha256::new(),
+            encoded_body: Vec::new(),
+        },
+    })
+}
+
+fn check_freshness(
+    certificate: &Certificate,
+    tree: &HashTree,
+    freshness_check: Option<FreshnessCheck<'_>>,
+) -> ResponseVerificationResult {
+    let Some(freshness_check) = freshness_check else {
+        return Ok(());
+    };
+
+    let certificate_info = CertificateInfo {
+        certificate_time_ns: get_certificate_time(certificate)?,
+        tree_root_hash: tree.digest(),
+    };
+
+    freshness_check(&certificate_info).map_err(ResponseVerificationError::FreshnessCheckFailed)
+}
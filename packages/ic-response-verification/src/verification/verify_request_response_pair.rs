@@ -1,24 +1,13 @@
-use super::{body::decode_body, certificate_header::CertificateHeader};
-use crate::{
-    cel::{map_cel_ast, parse_cel_expression},
-    error::{ResponseVerificationError, ResponseVerificationResult},
-    types::{VerificationInfo, VerifiedResponse},
-    validation::{
-        validate_body, validate_expr_hash, validate_expr_path, validate_hashes, validate_tree,
-    },
+use super::{
+    verify_request_response_headers, verify_request_response_headers_with_freshness_check,
+    verify_request_response_headers_with_max_delegation_chain_length,
+    verify_request_response_headers_with_profile, IncrementalVerification, VerificationProfile,
 };
-use ic_cbor::{parse_cbor_string_array, CertificateToCbor, HashTreeToCbor};
-use ic_certificate_verification::{validate_certificate_time, VerifyCertificate};
-use ic_certification::{hash_tree::Hash, Certificate, HashTree};
-use ic_http_certification::{
-    cel::{
-        CelExpression, DefaultCelExpression, DefaultFullCelExpression,
-        DefaultResponseOnlyCelExpression,
-    },
-    filter_response_headers, request_hash, response_headers_hash, HttpRequest, HttpResponse,
+use crate::{
+    error::ResponseVerificationResult,
+    types::{CertificateInfo, VerificationInfo, VerifiedResponse},
 };
-use ic_representation_independent_hash::hash;
-use std::collections::HashMap;
+use ic_http_certification::{HeaderField, HttpRequest, HttpResponse};
 
 /// The minimum verification version supported by this package.
 pub const MIN_VERIFICATION_VERSION: u8 = 1;
@@ -27,6 +16,10 @@ pub const MAX_VERIFICATION_VERSION: u8 = 2;
 
 /// The primary entry point for verifying a request and response pair. This will verify the response
 /// with respect to the request, according the [Response Verification Spec]().
+///
+/// Computed hashes, such as the body hash and the root hash of the tree, are compared against
+/// their certified values in constant time. Looking up certified values in the hash tree is not
+/// constant time, since the labels of the tree are sent with the response and are public.
 pub fn verify_request_response_pair(
     request: HttpRequest,
     response: HttpResponse,
@@ -36,244 +29,133 @@ pub fn verify_request_response_pair(
     ic_public_key: &[u8],
     min_requested_verification_version: u8,
 ) -> ResponseVerificationResult<VerificationInfo> {
-    let headers: HashMap<_, _> = response
-        .headers
-        .iter()
-        .map(|(k, v)| (k.to_lowercase(), v.clone()))
-        .collect();
-
-    let encoding = headers
-        .get("content-encoding")
-        .map(|encoding| encoding.as_str());
-
-    let Some(certificate_header) = headers.get("ic-certificate") else {
-        return Err(ResponseVerificationError::MissingCertification);
-    };
-
-    let certificate_header = CertificateHeader::from(certificate_header)?;
-
-    let Some(tree) = certificate_header
-        .tree
-        .map(|tree| HashTree::from_cbor(&tree))
-        .transpose()?
-    else {
-        return Err(ResponseVerificationError::MissingTree);
-    };
-
-    let Some(certificate) = certificate_header
-        .certificate
-        .map(|certificate| Certificate::from_cbor(&certificate))
-        .transpose()?
-    else {
-        return Err(ResponseVerificationError::MissingCertificate);
-    };
-
-    let version = certificate_header
-        .version
-        .unwrap_or(MIN_VERIFICATION_VERSION);
+    let verification = verify_request_response_headers(
+        request,
+        response.status_code,
+        &response.headers,
+        canister_id,
+        current_time_ns,
+        max_cert_time_offset_ns,
+        ic_public_key,
+        min_requested_verification_version,
+    )?;
 
-    match version {
-        version if version < min_requested_verification_version => Err(
-            ResponseVerificationError::RequestedVerificationVersionMismatch {
-                requested_version: version,
-                min_requested_verification_version,
-            },
-        ),
-        1 => v1_verification(V1VerificationOpts {
-            request,
-            response,
-            canister_id,
-            current_time_ns,
-            max_cert_time_offset_ns,
-            tree,
-            certificate,
-            encoding,
-            ic_public_key,
-        }),
-        2 => match headers.get("ic-certificateexpression") {
-            Some(certificate_expression_header) => {
-                let Some(expr_path) = certificate_header
-                    .expr_path
-                    .map(|expr_path| parse_cbor_string_array(&expr_path))
-                    .transpose()?
-                else {
-                    return Err(ResponseVerificationError::MissingCertificateExpressionPath);
-                };
+    verify_body(verification, response.body, response.trailers)
+}
 
-                let cel_ast = parse_cel_expression(certificate_expression_header)?;
-                let certification = map_cel_ast(&cel_ast)?;
-                let expr_hash = hash(certificate_expression_header.as_bytes());
+/// Verifies a request and response pair using the tolerances of the given [VerificationProfile].
+///
+/// This is the same as [verify_request_response_pair], except that the maximum certificate time
+/// offset, the minimum verification version and the maximum delegation chain length are taken from
+/// the profile.
+pub fn verify_request_response_pair_with_profile(
+    request: HttpRequest,
+    response: HttpResponse,
+    canister_id: &[u8],
+    current_time_ns: u128,
+    ic_public_key: &[u8],
+    profile: VerificationProfile,
+) -> ResponseVerificationResult<VerificationInfo> {
+    let verification = verify_request_response_headers_with_profile(
+        request,
+        response.status_code,
+        &response.headers,
+        canister_id,
+        current_time_ns,
+        ic_public_key,
+        profile,
+    )?;
 
-                v2_verification(V2VerificationOpts {
-                    request,
-                    response,
-                    canister_id,
-                    current_time_ns,
-                    max_cert_time_offset_ns,
-                    tree,
-                    certificate,
-                    expr_path,
-                    expr_hash,
-                    certification,
-                    ic_public_key,
-                })
-            }
-            None => Err(ResponseVerificationError::MissingCertification),
-        },
-        _ => Err(ResponseVerificationError::UnsupportedVerificationVersion {
-            min_supported_version: MIN_VERIFICATION_VERSION,
-            max_supported_version: MAX_VERIFICATION_VERSION,
-            requested_version: version,
-        }),
-    }
+    verify_body(verification, response.body, response.trailers)
 }
 
-struct V1VerificationOpts<'a> {
+/// Verifies a request and response pair, and checks the freshness of the response's certificate
+/// with the provided callback.
+///
+/// This is the same as [verify_request_response_pair], except that `freshness_check` is called
+/// with the [CertificateInfo] of the response once its certificate has been verified. Returning an
+/// error from the callback fails verification with
+/// [FreshnessCheckFailed](crate::ResponseVerificationError::FreshnessCheckFailed).
+///
+/// This allows integrators to implement replay detection or monotonicity checks, such as
+/// rejecting a certificate that is older than the last certificate that was seen for the same
+/// canister.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_request_response_pair_with_freshness_check(
     request: HttpRequest,
     response: HttpResponse,
-    canister_id: &'a [u8],
+    canister_id: &[u8],
     current_time_ns: u128,
     max_cert_time_offset_ns: u128,
-    tree: HashTree,
-    certificate: Certificate,
-    encoding: Option<&'a str>,
-    ic_public_key: &'a [u8],
-}
-
-fn v1_verification(
-    V1VerificationOpts {
+    ic_public_key: &[u8],
+    min_requested_verification_version: u8,
+    freshness_check: impl FnMut(&CertificateInfo) -> Result<(), String>,
+) -> ResponseVerificationResult<VerificationInfo> {
+    let verification = verify_request_response_headers_with_freshness_check(
         request,
-        response,
+        response.status_code,
+        &response.headers,
         canister_id,
         current_time_ns,
         max_cert_time_offset_ns,
-        tree,
-        certificate,
-        encoding,
         ic_public_key,
-    }: V1VerificationOpts<'_>,
-) -> ResponseVerificationResult<VerificationInfo> {
-    validate_certificate_time(&certificate, &current_time_ns, &max_cert_time_offset_ns)?;
-    certificate.verify(canister_id, ic_public_key)?;
-
-    let request_path = request.get_path()?;
-    let decoded_body = decode_body(&response.body, encoding)?;
-    let decoded_body_sha = hash(decoded_body.as_slice());
-
-    if !validate_tree(canister_id, &certificate, &tree) {
-        return Err(ResponseVerificationError::InvalidTree);
-    }
+        min_requested_verification_version,
+        freshness_check,
+    )?;
 
-    let mut valid_body = validate_body(&tree, &request_path, &decoded_body_sha);
-    if encoding.is_some() && !valid_body {
-        let body_sha = hash(response.body.as_slice());
-        valid_body = validate_body(&tree, &request_path, &body_sha);
-    }
-
-    if !valid_body {
-        return Err(ResponseVerificationError::InvalidResponseBody);
-    }
-
-    Ok(VerificationInfo {
-        response: Some(VerifiedResponse {
-            status_code: None,
-            headers: Vec::new(),
-            body: response.body,
-        }),
-        verification_version: 1,
-    })
+    verify_body(verification, response.body, response.trailers)
 }
 
-struct V2VerificationOpts<'a> {
+/// Verifies a request and response pair, allowing the response's certificate to have at most
+/// `max_delegation_chain_length` delegations.
+///
+/// This is the same as [verify_request_response_pair], which allows at most
+/// [DEFAULT_MAX_DELEGATION_CHAIN_LENGTH](ic_certificate_verification::DEFAULT_MAX_DELEGATION_CHAIN_LENGTH)
+/// delegations. Certificates with a longer delegation chain fail verification with
+/// [CertificateVerificationFailed](crate::ResponseVerificationError::CertificateVerificationFailed)
+/// before any of their signatures are verified.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_request_response_pair_with_max_delegation_chain_length(
     request: HttpRequest,
     response: HttpResponse,
-    canister_id: &'a [u8],
+    canister_id: &[u8],
     current_time_ns: u128,
     max_cert_time_offset_ns: u128,
-    tree: HashTree,
-    certificate: Certificate,
-    expr_path: Vec<String>,
-    expr_hash: Hash,
-    certification: CelExpression<'a>,
-    ic_public_key: &'a [u8],
-}
-
-fn v2_verification(
-    V2VerificationOpts {
+    ic_public_key: &[u8],
+    min_requested_verification_version: u8,
+    max_delegation_chain_length: usize,
+) -> ResponseVerificationResult<VerificationInfo> {
+    let verification = verify_request_response_headers_with_max_delegation_chain_length(
         request,
-        response,
+        response.status_code,
+        &response.headers,
         canister_id,
         current_time_ns,
         max_cert_time_offset_ns,
-        tree,
-        certificate,
-        expr_path,
-        expr_hash,
-        certification,
         ic_public_key,
-    }: V2VerificationOpts<'_>,
-) -> ResponseVerificationResult<VerificationInfo> {
-    let request_path = request.get_path()?;
-
-    validate_certificate_time(&certificate, &current_time_ns, &max_cert_time_offset_ns)?;
-    certificate.verify(canister_id, ic_public_key)?;
-
-    if !validate_tree(canister_id, &certificate, &tree) {
-        return Err(ResponseVerificationError::InvalidTree);
-    }
+        min_requested_verification_version,
+        max_delegation_chain_length,
+    )?;
 
-    if !validate_expr_path(&expr_path, &request_path, &tree) {
-        return Err(ResponseVerificationError::InvalidExpressionPath);
-    }
-
-    let (request_certification, response_certification) = match &certification {
-        CelExpression::Default(DefaultCelExpression::Skip) => {
-            return match validate_expr_hash(&expr_path, &expr_hash, &tree).is_some() {
-                true => Ok(VerificationInfo {
-                    response: None,
-                    verification_version: 2,
-                }),
-                false => Err(ResponseVerificationError::InvalidExpressionPath),
-            };
-        }
-        CelExpression::Default(DefaultCelExpression::ResponseOnly(
-            DefaultResponseOnlyCelExpression { response },
-        )) => (None, response),
-        CelExpression::Default(DefaultCelExpression::Full(DefaultFullCelExpression {
-            request,
-            response,
-        })) => (Some(request), response),
-    };
-
-    let request_hash = request_certification
-        .as_ref()
-        .map(|request_certification| request_hash(&request, request_certification))
-        .transpose()?;
+    verify_body(verification, response.body, response.trailers)
+}
 
-    let body_hash = hash(&response.body);
-    let response_headers = filter_response_headers(&response, response_certification);
-    let response_headers_hash =
-        response_headers_hash(&response.status_code.into(), &response_headers);
-    let response_hash = hash([response_headers_hash, body_hash].concat().as_slice());
+fn verify_body(
+    mut verification: IncrementalVerification,
+    body: Vec<u8>,
+    trailers: Option<Vec<HeaderField>>,
+) -> ResponseVerificationResult<VerificationInfo> {
+    verification.update(&body);
 
-    let are_hashes_valid = validate_hashes(
-        &expr_hash,
-        &request_hash,
-        &response_hash,
-        &expr_path,
-        &tree,
-        &certification,
-    );
+    let verification_version = verification.verification_version;
+    let verified_response = verification.response.take();
+    verification.finish_with_trailers(trailers.as_deref().unwrap_or_default())?;
 
-    match are_hashes_valid {
-        true => Ok(VerificationInfo {
-            response: Some(VerifiedResponse {
-                status_code: Some(response.status_code),
-                headers: response_headers.headers,
-                body: response.body,
-            }),
-            verification_version: 2,
+    Ok(VerificationInfo {
+        response: verified_response.map(|verified_response| VerifiedResponse {
+            body,
+            ..verified_response
         }),
-        false => Err(ResponseVerificationError::InvalidResponseHashes),
-    }
+        verification_version,
+    })
 }
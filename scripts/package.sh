@@ -24,6 +24,13 @@ delete_generated_files() {
   find $OUT_DIR -name "LICENSE" -type f -delete || early_exit
 }
 
+# the web packages are ES modules, but the root package.json must not set `"type": "module"`
+# because the nodejs packages are CommonJS modules
+add_esm_package_files() {
+  echo '{ "type": "module" }' > $OUT_DIR/web/package.json || early_exit
+  echo '{ "type": "module" }' > $OUT_DIR/debug/dist/web/package.json || early_exit
+}
+
 add_debug_files() {
   cp $PKG_ROOT/package.json $OUT_DIR/debug/ || early_exit
   cp $PKG_ROOT/LICENSE $OUT_DIR/debug/ || early_exit
@@ -32,4 +39,5 @@ add_debug_files() {
 build_release_packages
 build_debug_packages
 delete_generated_files
+add_esm_package_files
 add_debug_files